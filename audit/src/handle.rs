@@ -12,15 +12,10 @@ use netlink_proto::{sys::SocketAddr, ConnectionHandle};
 use crate::packet::{
     rules::RuleMessage,
     AuditMessage,
+    NetlinkFlags,
     NetlinkMessage,
     NetlinkPayload,
     StatusMessage,
-    NLM_F_ACK,
-    NLM_F_CREATE,
-    NLM_F_DUMP,
-    NLM_F_EXCL,
-    NLM_F_NONREC,
-    NLM_F_REQUEST,
 };
 
 // ==========================================
@@ -89,21 +84,21 @@ impl Handle {
     /// Add the given rule
     pub async fn add_rule(&mut self, rule: RuleMessage) -> Result<(), Error> {
         let mut req = NetlinkMessage::from(AuditMessage::AddRule(rule));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_EXCL | NLM_F_CREATE;
+        req.header.flags = NetlinkFlags::new_create();
         self.acked_request(req).await
     }
 
     /// Deletes a given rule
     pub async fn del_rule(&mut self, rule: RuleMessage) -> Result<(), Error> {
         let mut req = NetlinkMessage::from(AuditMessage::DelRule(rule));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_NONREC;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK | NetlinkFlags::NONREC;
         self.acked_request(req).await
     }
 
     /// List the current rules
     pub fn list_rules(&mut self) -> impl TryStream<Ok = RuleMessage, Error = Error> {
         let mut req = NetlinkMessage::from(AuditMessage::ListRules(None));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         match self.request(req) {
             Ok(response) => Either::Left(response.map(move |msg| {
@@ -129,14 +124,14 @@ impl Handle {
         status.pid = process::id();
         status.mask = AUDIT_STATUS_ENABLED | AUDIT_STATUS_PID;
         let mut req = NetlinkMessage::from(AuditMessage::SetStatus(status));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK;
         self.acked_request(req).await
     }
 
     /// Get current audit status
     pub async fn get_status(&mut self) -> Result<StatusMessage, Error> {
         let mut req = NetlinkMessage::from(AuditMessage::GetStatus(None));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
         let mut request = self.request(req)?;
 
         let response = request.next().await.ok_or(Error::RequestFailed)?;
@@ -2,7 +2,7 @@
 
 use futures::{future::Either, FutureExt, Stream, StreamExt, TryStream};
 use genetlink::GenetlinkHandle;
-use netlink_packet_core::{NetlinkMessage, NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST};
+use netlink_packet_core::{NetlinkFlags, NetlinkMessage};
 use netlink_packet_generic::GenlMessage;
 use netlink_packet_utils::DecodeError;
 
@@ -73,9 +73,9 @@ pub(crate) async fn ethtool_execute(
         // multiple netlink message in single socket reply.
         // Using NLM_F_ACK will force rust-netlink to parse all till
         // acked at the end.
-        NLM_F_DUMP | NLM_F_REQUEST | NLM_F_ACK
+        NetlinkFlags::new_dump() | NetlinkFlags::ACK
     } else {
-        NLM_F_REQUEST
+        NetlinkFlags::new_get()
     };
 
     let mut nl_msg = NetlinkMessage::from(GenlMessage::from_payload(ethtool_msg));
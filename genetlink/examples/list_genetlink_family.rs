@@ -8,13 +8,7 @@
 use anyhow::{bail, Error};
 use futures::StreamExt;
 use genetlink::new_connection;
-use netlink_packet_core::{
-    NetlinkHeader,
-    NetlinkMessage,
-    NetlinkPayload,
-    NLM_F_DUMP,
-    NLM_F_REQUEST,
-};
+use netlink_packet_core::{NetlinkFlags, NetlinkHeader, NetlinkMessage, NetlinkPayload};
 use netlink_packet_generic::{
     ctrl::{nlas::GenlCtrlAttrs, GenlCtrl, GenlCtrlCmd},
     GenlMessage,
@@ -24,7 +18,7 @@ use netlink_packet_generic::{
 async fn main() -> Result<(), Error> {
     let nlmsg = NetlinkMessage {
         header: NetlinkHeader {
-            flags: NLM_F_REQUEST | NLM_F_DUMP,
+            flags: NetlinkFlags::new_dump(),
             ..Default::default()
         },
         payload: GenlMessage::from_payload(GenlCtrl {
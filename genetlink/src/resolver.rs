@@ -2,7 +2,7 @@
 
 use crate::{error::GenetlinkError, GenetlinkHandle};
 use futures::{future::Either, StreamExt};
-use netlink_packet_core::{NetlinkMessage, NetlinkPayload, NLM_F_REQUEST};
+use netlink_packet_core::{NetlinkFlags, NetlinkMessage, NetlinkPayload};
 use netlink_packet_generic::{
     ctrl::{nlas::GenlCtrlAttrs, GenlCtrl, GenlCtrlCmd},
     GenlMessage,
@@ -42,7 +42,7 @@ impl Resolver {
                 genlmsg.finalize();
                 // We don't have to set family id here, since nlctrl has static family id (0x10)
                 let mut nlmsg = NetlinkMessage::from(genlmsg);
-                nlmsg.header.flags = NLM_F_REQUEST;
+                nlmsg.header.flags = NetlinkFlags::new_get();
                 nlmsg.finalize();
 
                 let mut res = handle.send_request(nlmsg)?;
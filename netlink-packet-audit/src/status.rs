@@ -68,11 +68,11 @@ impl<T: AsRef<[u8]>> StatusMessageBuffer<T> {
     fn check_buffer_length(&self) -> Result<(), DecodeError> {
         let len = self.buffer.as_ref().len();
         if len < STATUS_MESSAGE_LEN {
-            return Err(format!(
-                "invalid StatusMessageBuffer buffer: length is {} instead of {}",
-                len, STATUS_MESSAGE_LEN
-            )
-            .into());
+            return Err(DecodeError::BufferTooShort {
+                context: "StatusMessageBuffer",
+                expected: STATUS_MESSAGE_LEN,
+                actual: len,
+            });
         }
         Ok(())
     }
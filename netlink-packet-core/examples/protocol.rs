@@ -120,6 +120,6 @@ fn main() {
     assert_eq!(deserialized_packet, packet);
 
     // This should print:
-    // NetlinkMessage { header: NetlinkHeader { length: 20, message_type: 18, flags: 0, sequence_number: 0, port_number: 0 }, payload: InnerMessage(Ping([0, 1, 2, 3])) }
+    // NetlinkMessage { header: NetlinkHeader { length: 20, message_type: 18, flags: (empty), sequence_number: 0, port_number: 0 }, payload: InnerMessage(Ping([0, 1, 2, 3])) }
     println!("{:?}", packet);
 }
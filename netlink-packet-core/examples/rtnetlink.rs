@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: MIT
 
-use netlink_packet_core::{NetlinkHeader, NetlinkMessage, NLM_F_DUMP, NLM_F_REQUEST};
+use netlink_packet_core::{NetlinkFlags, NetlinkHeader, NetlinkMessage};
 use netlink_packet_route::rtnl::{LinkMessage, RtnlMessage};
 
 fn main() {
@@ -9,14 +9,14 @@ fn main() {
     let mut packet = NetlinkMessage {
         header: NetlinkHeader {
             sequence_number: 1,
-            flags: NLM_F_DUMP | NLM_F_REQUEST,
+            flags: NetlinkFlags::new_dump(),
             ..Default::default()
         },
         payload: RtnlMessage::GetLink(LinkMessage::default()).into(),
     };
 
     // Set a few fields in the packet's header
-    packet.header.flags = NLM_F_DUMP | NLM_F_REQUEST;
+    packet.header.flags = NetlinkFlags::new_dump();
     packet.header.sequence_number = 1;
 
     // Before serializing the packet, it is very important to call
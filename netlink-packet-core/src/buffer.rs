@@ -163,24 +163,22 @@ impl<T: AsRef<[u8]>> NetlinkBuffer<T> {
     fn check_buffer_length(&self) -> Result<(), DecodeError> {
         let len = self.buffer.as_ref().len();
         if len < PORT_NUMBER.end {
-            Err(format!(
-                "invalid netlink buffer: length is {} but netlink packets are at least {} bytes",
-                len, PORT_NUMBER.end
-            )
-            .into())
+            Err(DecodeError::BufferTooShort {
+                context: "netlink header",
+                expected: PORT_NUMBER.end,
+                actual: len,
+            })
         } else if len < self.length() as usize {
-            Err(format!(
-                "invalid netlink buffer: length field says {} the buffer is {} bytes long",
-                self.length(),
-                len
-            )
-            .into())
+            Err(DecodeError::BufferTooShort {
+                context: "netlink message",
+                expected: self.length() as usize,
+                actual: len,
+            })
         } else if (self.length() as usize) < PORT_NUMBER.end {
-            Err(format!(
-                "invalid netlink buffer: length field says {} but netlink packets are at least {} bytes",
-                self.length(),
-                len
-            ).into())
+            Err(DecodeError::InvalidLength {
+                context: "netlink message",
+                length: self.length() as usize,
+            })
         } else {
             Ok(())
         }
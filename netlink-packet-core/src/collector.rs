@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use crate::{ErrorMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload};
+
+/// Outcome of feeding a message to a [`MultipartCollector`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CollectResult<T> {
+    /// More messages are expected for this request
+    Pending,
+    /// The response is complete: these are all the messages of the dump, in
+    /// the order they were received
+    Complete(Vec<T>),
+    /// The kernel answered the request with an error. The messages received
+    /// so far for this request are dropped.
+    Error(ErrorMessage),
+}
+
+/// Reassemble multi-part responses (typically the answer to a `NLM_F_DUMP`
+/// request) from the individual messages they are made of.
+///
+/// Responses are grouped by sequence number, so the messages of several dumps
+/// running on the same socket can be interleaved. Messages without the
+/// `NLM_F_MULTI` flag are complete responses on their own.
+#[derive(Debug, Clone)]
+pub struct MultipartCollector<T> {
+    pending: HashMap<u32, Vec<T>>,
+}
+
+impl<T> Default for MultipartCollector<T> {
+    fn default() -> Self {
+        MultipartCollector {
+            pending: HashMap::new(),
+        }
+    }
+}
+
+impl<T> MultipartCollector<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a message received from the socket to the collector.
+    ///
+    /// `NLMSG_NOOP`, `NLMSG_OVERRUN` and acknowledgements are ignored and
+    /// return [`CollectResult::Pending`].
+    pub fn push(&mut self, msg: NetlinkMessage<T>) -> CollectResult<T> {
+        let (header, payload) = msg.into_parts();
+        let sequence_number = header.sequence_number;
+        match payload {
+            NetlinkPayload::InnerMessage(inner) => {
+                if header.flags.contains(NetlinkFlags::MULTI) {
+                    self.pending.entry(sequence_number).or_default().push(inner);
+                    CollectResult::Pending
+                } else {
+                    CollectResult::Complete(vec![inner])
+                }
+            }
+            NetlinkPayload::Done => {
+                CollectResult::Complete(self.pending.remove(&sequence_number).unwrap_or_default())
+            }
+            NetlinkPayload::Error(e) => {
+                self.pending.remove(&sequence_number);
+                CollectResult::Error(e)
+            }
+            NetlinkPayload::Ack(_) | NetlinkPayload::Noop | NetlinkPayload::Overrun(_) => {
+                CollectResult::Pending
+            }
+        }
+    }
+
+    /// Return `true` if no response is being assembled
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Drop the messages received so far for the given sequence number
+    pub fn discard(&mut self, sequence_number: u32) -> Option<Vec<T>> {
+        self.pending.remove(&sequence_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NetlinkBuffer, NetlinkDeserializable, NetlinkHeader, Parseable};
+
+    /// Minimal protocol for the tests: the payload is a single `u32`.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    struct Dummy(u32);
+
+    impl NetlinkDeserializable for Dummy {
+        type Error = crate::DecodeError;
+
+        fn deserialize(_header: &NetlinkHeader, payload: &[u8]) -> Result<Self, Self::Error> {
+            if payload.len() < 4 {
+                return Err("payload too short".into());
+            }
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(&payload[..4]);
+            Ok(Dummy(u32::from_ne_bytes(bytes)))
+        }
+    }
+
+    // two interleaved dumps (sequence numbers 1 and 2); the first one ends
+    // with NLMSG_DONE and the second one with an error
+    #[rustfmt::skip]
+    static INTERLEAVED_DUMPS: [u8; 116] = [
+        // seq 1, first message
+        0x14, 0x00, 0x00, 0x00, // length = 20
+        0x10, 0x00, // message type = 16
+        0x02, 0x00, // flags = NLM_F_MULTI
+        0x01, 0x00, 0x00, 0x00, // sequence number = 1
+        0x00, 0x00, 0x00, 0x00, // port id = 0
+        0x0a, 0x00, 0x00, 0x00, // payload = 10
+
+        // seq 2, first message
+        0x14, 0x00, 0x00, 0x00, // length = 20
+        0x10, 0x00, // message type = 16
+        0x02, 0x00, // flags = NLM_F_MULTI
+        0x02, 0x00, 0x00, 0x00, // sequence number = 2
+        0x00, 0x00, 0x00, 0x00, // port id = 0
+        0x14, 0x00, 0x00, 0x00, // payload = 20
+
+        // seq 1, second message
+        0x14, 0x00, 0x00, 0x00, // length = 20
+        0x10, 0x00, // message type = 16
+        0x02, 0x00, // flags = NLM_F_MULTI
+        0x01, 0x00, 0x00, 0x00, // sequence number = 1
+        0x00, 0x00, 0x00, 0x00, // port id = 0
+        0x0b, 0x00, 0x00, 0x00, // payload = 11
+
+        // seq 1, end of dump
+        0x14, 0x00, 0x00, 0x00, // length = 20
+        0x03, 0x00, // message type = 3 (NLMSG_DONE)
+        0x02, 0x00, // flags = NLM_F_MULTI
+        0x01, 0x00, 0x00, 0x00, // sequence number = 1
+        0x00, 0x00, 0x00, 0x00, // port id = 0
+        0x00, 0x00, 0x00, 0x00, // number of errors = 0
+
+        // seq 2, error
+        0x24, 0x00, 0x00, 0x00, // length = 36
+        0x02, 0x00, // message type = 2 (NLMSG_ERROR)
+        0x00, 0x01, // flags = NLM_F_CAPPED
+        0x02, 0x00, 0x00, 0x00, // sequence number = 2
+        0x00, 0x00, 0x00, 0x00, // port id = 0
+        0xf0, 0xff, 0xff, 0xff, // error code = -16 (EBUSY)
+        // header of the request that caused the error
+        0x10, 0x00, 0x00, 0x00, 0x12, 0x00, 0x01, 0x03,
+        0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    fn parse_all(mut bytes: &[u8]) -> Vec<NetlinkMessage<Dummy>> {
+        let mut messages = vec![];
+        while !bytes.is_empty() {
+            let buffer = NetlinkBuffer::new_checked(&bytes).unwrap();
+            let length = buffer.length() as usize;
+            messages.push(NetlinkMessage::parse(&buffer).unwrap());
+            bytes = &bytes[length..];
+        }
+        messages
+    }
+
+    #[test]
+    fn collect_interleaved_dumps() {
+        let mut collector = MultipartCollector::new();
+        let mut results = parse_all(&INTERLEAVED_DUMPS[..])
+            .into_iter()
+            .map(|msg| collector.push(msg));
+
+        assert_eq!(results.next(), Some(CollectResult::Pending));
+        assert_eq!(results.next(), Some(CollectResult::Pending));
+        assert_eq!(results.next(), Some(CollectResult::Pending));
+        assert_eq!(
+            results.next(),
+            Some(CollectResult::Complete(vec![Dummy(10), Dummy(11)]))
+        );
+        match results.next() {
+            Some(CollectResult::Error(e)) => {
+                assert_eq!(e.code, -16);
+                assert_eq!(e.header, &INTERLEAVED_DUMPS[100..]);
+            }
+            other => panic!("expected an error, got {:?}", other),
+        }
+        assert_eq!(results.next(), None);
+        drop(results);
+        assert!(collector.is_empty());
+    }
+
+    #[test]
+    fn collect_single_message() {
+        let mut collector = MultipartCollector::new();
+        // same as the first message of INTERLEAVED_DUMPS, without NLM_F_MULTI
+        let mut bytes = [0; 20];
+        bytes.copy_from_slice(&INTERLEAVED_DUMPS[..20]);
+        bytes[6] = 0;
+        let msg = parse_all(&bytes[..]).pop().unwrap();
+        assert_eq!(
+            collector.push(msg),
+            CollectResult::Complete(vec![Dummy(10)])
+        );
+        assert!(collector.is_empty());
+    }
+
+    #[test]
+    fn collect_empty_dump() {
+        let mut collector = MultipartCollector::<Dummy>::new();
+        let done = parse_all(&INTERLEAVED_DUMPS[60..80]).pop().unwrap();
+        assert_eq!(collector.push(done), CollectResult::Complete(vec![]));
+    }
+}
@@ -37,3 +37,17 @@ pub const NLM_F_NONREC: u16 = 256;
 pub const NLM_F_CAPPED: u16 = 256;
 /// extended ACK TVLs were included
 pub const NLM_F_ACK_TLVS: u16 = 512;
+
+/// Human readable description of the error, a null-terminated string
+pub const NLMSGERR_ATTR_MSG: u16 = 1;
+/// Offset of the invalid attribute in the original message, counting from the beginning of the
+/// header
+pub const NLMSGERR_ATTR_OFFS: u16 = 2;
+/// Arbitrary subsystem specific cookie
+pub const NLMSGERR_ATTR_COOKIE: u16 = 3;
+/// Policy of the rejected attribute
+pub const NLMSGERR_ATTR_POLICY: u16 = 4;
+/// Type of a missing required attribute
+pub const NLMSGERR_ATTR_MISS_TYPE: u16 = 5;
+/// Offset of the nest where the required attribute is missing
+pub const NLMSGERR_ATTR_MISS_NEST: u16 = 6;
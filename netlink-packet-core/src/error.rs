@@ -2,9 +2,22 @@
 
 use std::{fmt, io, mem::size_of};
 
+use anyhow::Context;
 use byteorder::{ByteOrder, NativeEndian};
 
-use crate::{DecodeError, Emitable, Field, Parseable, Rest};
+use crate::{
+    constants::*,
+    utils::{
+        nla::{DefaultNla, Nla, NlasIterator},
+        parsers::{parse_string, parse_u32},
+    },
+    DecodeError,
+    Emitable,
+    Field,
+    Parseable,
+    ParseableParametrized,
+    Rest,
+};
 
 const CODE: Field = 0..4;
 const PAYLOAD: Rest = 4..;
@@ -34,11 +47,11 @@ impl<T: AsRef<[u8]>> ErrorBuffer<T> {
     fn check_buffer_length(&self) -> Result<(), DecodeError> {
         let len = self.buffer.as_ref().len();
         if len < ERROR_HEADER_LEN {
-            Err(format!(
-                "invalid ErrorBuffer: length is {} but ErrorBuffer are at least {} bytes",
-                len, ERROR_HEADER_LEN
-            )
-            .into())
+            Err(DecodeError::BufferTooShort {
+                context: "ErrorBuffer",
+                expected: ERROR_HEADER_LEN,
+                actual: len,
+            })
         } else {
             Ok(())
         }
@@ -78,19 +91,35 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> ErrorBuffer<T> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ErrorMessage {
     pub code: i32,
+    /// The message that caused the error (or just its header when the message has the
+    /// `NLM_F_CAPPED` flag)
     pub header: Vec<u8>,
+    /// Extended acknowledgement attributes, sent by the kernel when the `NETLINK_EXT_ACK` socket
+    /// option is enabled. The message must have the `NLM_F_ACK_TLVS` flag when this is set. It is
+    /// boxed to keep error messages, and the netlink messages that contain them, small.
+    pub ext_ack: Option<Box<ExtendedAck>>,
 }
 
 pub type AckMessage = ErrorMessage;
 
 impl Emitable for ErrorMessage {
     fn buffer_len(&self) -> usize {
-        size_of::<i32>() + self.header.len()
+        size_of::<i32>()
+            + self.header.len()
+            + self
+                .ext_ack
+                .as_ref()
+                .map(|ext_ack| ext_ack.buffer_len())
+                .unwrap_or(0)
     }
     fn emit(&self, buffer: &mut [u8]) {
         let mut buffer = ErrorBuffer::new(buffer);
         buffer.set_code(self.code);
-        buffer.payload_mut().copy_from_slice(&self.header)
+        let payload = buffer.payload_mut();
+        payload[..self.header.len()].copy_from_slice(&self.header);
+        if let Some(ref ext_ack) = self.ext_ack {
+            ext_ack.emit(&mut payload[self.header.len()..]);
+        }
     }
 }
 
@@ -107,10 +136,161 @@ impl<'buffer, T: AsRef<[u8]> + 'buffer> Parseable<ErrorBuffer<&'buffer T>> for E
         Ok(ErrorMessage {
             code: buf.code(),
             header: buf.payload().to_vec(),
+            ext_ack: None,
         })
     }
 }
 
+/// Parse an error message, given the flags of its netlink header, which tell whether the message
+/// that caused the error is truncated and whether it is followed by extended acknowledgement
+/// attributes.
+impl<'buffer, T: AsRef<[u8]> + 'buffer> ParseableParametrized<ErrorBuffer<&'buffer T>, u16>
+    for ErrorMessage
+{
+    fn parse_with_param(
+        buf: &ErrorBuffer<&'buffer T>,
+        flags: u16,
+    ) -> Result<ErrorMessage, DecodeError> {
+        let payload = buf.payload();
+        if flags & NLM_F_ACK_TLVS == 0 {
+            return ErrorMessage::parse(buf);
+        }
+        // The original message starts with its header, which holds its length
+        let header_len = if flags & NLM_F_CAPPED != 0 || payload.len() < 4 {
+            ORIGINAL_HEADER_LEN
+        } else {
+            (NativeEndian::read_u32(&payload[..4]) as usize + 3) & !3
+        };
+        let header_len = header_len.min(payload.len());
+        Ok(ErrorMessage {
+            code: buf.code(),
+            header: payload[..header_len].to_vec(),
+            // The attributes only give details about the error: if they are malformed, the error
+            // code is still worth returning.
+            ext_ack: ExtendedAck::parse(&payload[header_len..])
+                .ok()
+                .map(Box::new),
+        })
+    }
+}
+
+const ORIGINAL_HEADER_LEN: usize = 16;
+
+/// Extended acknowledgement attributes, which give details about an error (or a warning for
+/// acknowledgements)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ExtendedAck {
+    /// Human readable description of the error (`NLMSGERR_ATTR_MSG`)
+    pub message: Option<String>,
+    /// Offset of the invalid attribute in the original message (`NLMSGERR_ATTR_OFFS`)
+    pub offset: Option<u32>,
+    /// Type of the missing required attribute (`NLMSGERR_ATTR_MISS_TYPE`)
+    pub missing_type: Option<u32>,
+    /// Subsystem specific cookie (`NLMSGERR_ATTR_COOKIE`)
+    pub cookie: Option<Vec<u8>>,
+    /// Attributes that are not handled above
+    pub other: Vec<DefaultNla>,
+}
+
+impl ExtendedAck {
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let mut ext_ack = ExtendedAck::default();
+        for nla in NlasIterator::new(payload) {
+            let nla = nla.context("invalid extended ack attribute")?;
+            let value = nla.value();
+            match nla.kind() {
+                NLMSGERR_ATTR_MSG => {
+                    ext_ack.message =
+                        Some(parse_string(value).context("invalid NLMSGERR_ATTR_MSG value")?)
+                }
+                NLMSGERR_ATTR_OFFS => {
+                    ext_ack.offset =
+                        Some(parse_u32(value).context("invalid NLMSGERR_ATTR_OFFS value")?)
+                }
+                NLMSGERR_ATTR_MISS_TYPE => {
+                    ext_ack.missing_type =
+                        Some(parse_u32(value).context("invalid NLMSGERR_ATTR_MISS_TYPE value")?)
+                }
+                NLMSGERR_ATTR_COOKIE => ext_ack.cookie = Some(value.to_vec()),
+                _ => ext_ack
+                    .other
+                    .push(DefaultNla::parse(&nla).context("invalid extended ack attribute")?),
+            }
+        }
+        Ok(ext_ack)
+    }
+
+    fn nlas(&self) -> Vec<ExtendedAckNla<'_>> {
+        let mut nlas = vec![];
+        if let Some(ref message) = self.message {
+            nlas.push(ExtendedAckNla::Message(message));
+        }
+        if let Some(offset) = self.offset {
+            nlas.push(ExtendedAckNla::U32(NLMSGERR_ATTR_OFFS, offset));
+        }
+        if let Some(missing_type) = self.missing_type {
+            nlas.push(ExtendedAckNla::U32(NLMSGERR_ATTR_MISS_TYPE, missing_type));
+        }
+        if let Some(ref cookie) = self.cookie {
+            nlas.push(ExtendedAckNla::Cookie(cookie));
+        }
+        nlas.extend(self.other.iter().map(ExtendedAckNla::Other));
+        nlas
+    }
+}
+
+impl Emitable for ExtendedAck {
+    fn buffer_len(&self) -> usize {
+        self.nlas().as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas().as_slice().emit(buffer)
+    }
+}
+
+enum ExtendedAckNla<'a> {
+    Message(&'a str),
+    U32(u16, u32),
+    Cookie(&'a [u8]),
+    Other(&'a DefaultNla),
+}
+
+impl<'a> Nla for ExtendedAckNla<'a> {
+    fn value_len(&self) -> usize {
+        use self::ExtendedAckNla::*;
+        match self {
+            Message(message) => message.len() + 1,
+            U32(_, _) => 4,
+            Cookie(cookie) => cookie.len(),
+            Other(nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::ExtendedAckNla::*;
+        match self {
+            Message(message) => {
+                buffer[..message.len()].copy_from_slice(message.as_bytes());
+                buffer[message.len()] = 0;
+            }
+            U32(_, value) => NativeEndian::write_u32(buffer, *value),
+            Cookie(cookie) => buffer.copy_from_slice(cookie),
+            Other(nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::ExtendedAckNla::*;
+        match self {
+            Message(_) => NLMSGERR_ATTR_MSG,
+            U32(kind, _) => *kind,
+            Cookie(_) => NLMSGERR_ATTR_COOKIE,
+            Other(nla) => nla.kind(),
+        }
+    }
+}
+
 impl ErrorMessage {
     /// According to [`netlink(7)`](https://linux.die.net/man/7/netlink)
     /// the `NLMSG_ERROR` return Negative errno or 0 for acknowledgements.
@@ -124,7 +304,14 @@ impl ErrorMessage {
 
 impl fmt::Display for ErrorMessage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(&self.to_io(), f)
+        fmt::Display::fmt(&self.to_io(), f)?;
+        match self.ext_ack.as_deref() {
+            Some(ExtendedAck {
+                message: Some(message),
+                ..
+            }) => write!(f, ": {}", message),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -144,6 +331,7 @@ mod tests {
         let err_msg = ErrorMessage {
             code: -95,
             header: vec![],
+            ext_ack: None,
         };
 
         let to_io: io::Error = err_msg.to_io();
@@ -151,4 +339,63 @@ mod tests {
         assert_eq!(err_msg.to_string(), io_err.to_string());
         assert_eq!(to_io.raw_os_error(), io_err.raw_os_error());
     }
+
+    #[rustfmt::skip]
+    static EXT_ACK_ERROR: [u8; 44] = [
+        0xea, 0xff, 0xff, 0xff, // code = -22 (EINVAL)
+        // header of the original message
+        0x28, 0x00, 0x00, 0x00, // length = 40
+        0x10, 0x00, // type = 16 = RTM_NEWLINK
+        0x05, 0x06, // flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_EXCL | NLM_F_CREATE
+        0x01, 0x00, 0x00, 0x00, // sequence number = 1
+        0x00, 0x00, 0x00, 0x00, // port number = 0
+        0x0f, 0x00, // length = 15
+        0x01, 0x00, // type = NLMSGERR_ATTR_MSG
+        0x62, 0x61, 0x64, 0x20, 0x69, 0x66, 0x6e, 0x61, 0x6d, 0x65, 0x00, // "bad ifname\0"
+        0x00, // padding
+        0x08, 0x00, // length = 8
+        0x02, 0x00, // type = NLMSGERR_ATTR_OFFS
+        0x24, 0x00, 0x00, 0x00, // offset = 36
+    ];
+
+    #[test]
+    fn parse_ext_ack() {
+        let buf = ErrorBuffer::new_checked(&EXT_ACK_ERROR).unwrap();
+        let msg = ErrorMessage::parse_with_param(&buf, NLM_F_CAPPED | NLM_F_ACK_TLVS).unwrap();
+        assert_eq!(msg.code, -22);
+        assert_eq!(msg.header, &EXT_ACK_ERROR[4..20]);
+        assert_eq!(
+            msg.ext_ack,
+            Some(Box::new(ExtendedAck {
+                message: Some("bad ifname".into()),
+                offset: Some(36),
+                ..Default::default()
+            }))
+        );
+        assert_eq!(
+            msg.to_string(),
+            format!("{}: bad ifname", io::Error::from_raw_os_error(22))
+        );
+
+        let mut buffer = vec![0xff; 44];
+        assert_eq!(msg.buffer_len(), 44);
+        msg.emit(&mut buffer);
+        assert_eq!(&buffer[..], &EXT_ACK_ERROR[..]);
+
+        // Without NLM_F_ACK_TLVS the attributes are just part of the payload
+        let msg = ErrorMessage::parse_with_param(&buf, 0).unwrap();
+        assert_eq!(msg.header, &EXT_ACK_ERROR[4..]);
+        assert_eq!(msg.ext_ack, None);
+    }
+
+    #[test]
+    fn parse_truncated_ext_ack() {
+        // the NLMSGERR_ATTR_OFFS attribute of EXT_ACK_ERROR is cut in the middle of its value
+        let truncated = &EXT_ACK_ERROR[..42];
+        let buf = ErrorBuffer::new_checked(&truncated).unwrap();
+        let msg = ErrorMessage::parse_with_param(&buf, NLM_F_CAPPED | NLM_F_ACK_TLVS).unwrap();
+        assert_eq!(msg.code, -22);
+        assert_eq!(msg.header, &EXT_ACK_ERROR[4..20]);
+        assert_eq!(msg.ext_ack, None);
+    }
 }
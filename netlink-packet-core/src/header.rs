@@ -1,6 +1,63 @@
 // SPDX-License-Identifier: MIT
 
-use crate::{buffer::NETLINK_HEADER_LEN, DecodeError, Emitable, NetlinkBuffer, Parseable};
+use crate::{
+    buffer::NETLINK_HEADER_LEN,
+    constants::*,
+    DecodeError,
+    Emitable,
+    NetlinkBuffer,
+    Parseable,
+};
+
+bitflags! {
+    /// Flags of a netlink header. Some values are shared by several flags,
+    /// whose meaning depends on the kind of request: `ROOT`, `MATCH` and
+    /// `ATOMIC` apply to `GET` requests, while `REPLACE`, `EXCL`, `CREATE`
+    /// and `APPEND` apply to `NEW` requests.
+    pub struct NetlinkFlags: u16 {
+        const REQUEST = NLM_F_REQUEST;
+        const MULTI = NLM_F_MULTIPART;
+        const ACK = NLM_F_ACK;
+        const ECHO = NLM_F_ECHO;
+        const DUMP_INTR = NLM_F_DUMP_INTR;
+        const DUMP_FILTERED = NLM_F_DUMP_FILTERED;
+        const ROOT = NLM_F_ROOT;
+        const MATCH = NLM_F_MATCH;
+        const ATOMIC = NLM_F_ATOMIC;
+        const DUMP = NLM_F_DUMP;
+        const REPLACE = NLM_F_REPLACE;
+        const EXCL = NLM_F_EXCL;
+        const CREATE = NLM_F_CREATE;
+        const APPEND = NLM_F_APPEND;
+        const NONREC = NLM_F_NONREC;
+        const CAPPED = NLM_F_CAPPED;
+        const ACK_TLVS = NLM_F_ACK_TLVS;
+    }
+}
+
+impl Default for NetlinkFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl NetlinkFlags {
+    /// Flags of a request for a single object: `REQUEST`
+    pub const fn new_get() -> Self {
+        Self::REQUEST
+    }
+
+    /// Flags of a request for a whole table: `REQUEST | DUMP`
+    pub const fn new_dump() -> Self {
+        Self::from_bits_truncate(NLM_F_REQUEST | NLM_F_DUMP)
+    }
+
+    /// Flags of a request creating an object that must not already exist:
+    /// `REQUEST | ACK | EXCL | CREATE`
+    pub const fn new_create() -> Self {
+        Self::from_bits_truncate(NLM_F_REQUEST | NLM_F_ACK | NLM_F_EXCL | NLM_F_CREATE)
+    }
+}
 
 /// A Netlink header representation. A netlink header has the following structure:
 ///
@@ -24,8 +81,8 @@ pub struct NetlinkHeader {
     /// NetlinkMessage type. The meaning of this field depends on the netlink protocol family in use.
     pub message_type: u16,
 
-    /// Flags. It should be set to one of the `NLM_F_*` constants.
-    pub flags: u16,
+    /// Flags of the message
+    pub flags: NetlinkFlags,
 
     /// Sequence number of the packet
     pub sequence_number: u32,
@@ -43,7 +100,7 @@ impl Emitable for NetlinkHeader {
         let mut buffer = NetlinkBuffer::new(buffer);
         buffer.set_message_type(self.message_type);
         buffer.set_length(self.length);
-        buffer.set_flags(self.flags);
+        buffer.set_flags(self.flags.bits());
         buffer.set_sequence_number(self.sequence_number);
         buffer.set_port_number(self.port_number);
     }
@@ -54,7 +111,9 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NetlinkBuffer<&'a T>> for NetlinkHea
         Ok(NetlinkHeader {
             length: buf.length(),
             message_type: buf.message_type(),
-            flags: buf.flags(),
+            // Keep the bits we do not know about, so that emitting the header
+            // gives back the flags that were parsed
+            flags: unsafe { NetlinkFlags::from_bits_unchecked(buf.flags()) },
             sequence_number: buf.sequence_number(),
             port_number: buf.port_number(),
         })
@@ -64,7 +123,6 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NetlinkBuffer<&'a T>> for NetlinkHea
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::*;
 
     // a packet captured with tcpdump that was sent when running `ip link show`
     #[rustfmt::skip]
@@ -90,7 +148,10 @@ mod tests {
         assert_eq!(repr.message_type, RTM_GETLINK);
         assert_eq!(repr.sequence_number, 1_526_271_540);
         assert_eq!(repr.port_number, 0);
-        assert_eq!(repr.flags, NLM_F_ROOT | NLM_F_REQUEST | NLM_F_MATCH);
+        assert_eq!(
+            repr.flags,
+            NetlinkFlags::ROOT | NetlinkFlags::REQUEST | NetlinkFlags::MATCH
+        );
     }
 
     #[test]
@@ -99,7 +160,7 @@ mod tests {
             length: 40,
             message_type: RTM_GETLINK,
             sequence_number: 1_526_271_540,
-            flags: NLM_F_ROOT | NLM_F_REQUEST | NLM_F_MATCH,
+            flags: NetlinkFlags::ROOT | NetlinkFlags::REQUEST | NetlinkFlags::MATCH,
             port_number: 0,
         };
         assert_eq!(repr.buffer_len(), 16);
@@ -107,4 +168,27 @@ mod tests {
         repr.emit(&mut buf[..]);
         assert_eq!(&buf[..], &IP_LINK_SHOW_PKT[..16]);
     }
+
+    #[test]
+    fn undefined_flags_round_trip() {
+        let mut pkt = IP_LINK_SHOW_PKT;
+        // set the undefined 0x80 flag
+        pkt[6] = 0x81;
+        let repr = NetlinkHeader::parse(&NetlinkBuffer::new_checked(&pkt[..]).unwrap()).unwrap();
+        assert_eq!(repr.flags.bits(), 0x381);
+        let mut buf = vec![0; 16];
+        repr.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &pkt[..16]);
+    }
+
+    #[test]
+    fn flags_constructors() {
+        assert_eq!(NetlinkFlags::new_get().bits(), NLM_F_REQUEST);
+        assert_eq!(NetlinkFlags::new_dump().bits(), 0x301);
+        assert_eq!(
+            NetlinkFlags::new_create(),
+            NetlinkFlags::REQUEST | NetlinkFlags::ACK | NetlinkFlags::EXCL | NetlinkFlags::CREATE
+        );
+        assert!(NetlinkFlags::new_dump().contains(NetlinkFlags::ROOT | NetlinkFlags::MATCH));
+    }
 }
@@ -29,7 +29,7 @@
 //! `netlink-packet-route`.
 //!
 //! ```rust
-//! use netlink_packet_core::{NetlinkHeader, NetlinkMessage, NLM_F_DUMP, NLM_F_REQUEST};
+//! use netlink_packet_core::{NetlinkFlags, NetlinkHeader, NetlinkMessage};
 //! use netlink_packet_route::{LinkMessage, RtnlMessage};
 //!
 //! // Create the netlink message, that contains the rtnetlink
@@ -37,7 +37,7 @@
 //! let mut packet = NetlinkMessage {
 //!     header: NetlinkHeader {
 //!         sequence_number: 1,
-//!         flags: NLM_F_DUMP | NLM_F_REQUEST,
+//!         flags: NetlinkFlags::new_dump(),
 //!         ..Default::default()
 //!     },
 //!     payload: RtnlMessage::GetLink(LinkMessage::default()).into(),
@@ -232,11 +232,14 @@
 //!     assert_eq!(deserialized_packet, packet);
 //!
 //!     // This should print:
-//!     // NetlinkMessage { header: NetlinkHeader { length: 20, message_type: 18, flags: 0, sequence_number: 0, port_number: 0 }, payload: InnerMessage(Ping([0, 1, 2, 3])) }
+//!     // NetlinkMessage { header: NetlinkHeader { length: 20, message_type: 18, flags: (empty), sequence_number: 0, port_number: 0 }, payload: InnerMessage(Ping([0, 1, 2, 3])) }
 //!     println!("{:?}", packet);
 //! }
 //! ```
 
+#[macro_use]
+extern crate bitflags;
+
 use core::ops::{Range, RangeFrom};
 /// Represent a multi-bytes field with a fixed size in a packet
 pub(crate) type Field = Range<usize>;
@@ -261,6 +264,12 @@ pub use self::payload::*;
 mod message;
 pub use self::message::*;
 
+mod collector;
+pub use self::collector::*;
+
+mod nla_writer;
+pub use self::nla_writer::*;
+
 pub mod constants;
 pub use self::constants::*;
 
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MIT
+
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::utils::nla::NlaBuffer;
+
+const NLA_HEADER_LEN: usize = 4;
+const NLA_ALIGNTO: usize = 4;
+
+const fn nla_align(len: usize) -> usize {
+    (len + NLA_ALIGNTO - 1) & !(NLA_ALIGNTO - 1)
+}
+
+/// Incremental builder for a buffer of netlink attributes. Each attribute is written with its
+/// header and padded to a 4 bytes boundary, and the length of nested attributes is filled in
+/// once their content has been written.
+///
+/// ```rust
+/// use netlink_packet_core::NlaWriter;
+///
+/// let mut writer = NlaWriter::new();
+/// writer.push_str(3, "lo");
+/// writer.push_nested(18, |info| {
+///     info.push_str(1, "vlan");
+/// });
+/// let nlas = writer.finish();
+/// assert_eq!(nlas.len(), 8 + 16);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NlaWriter {
+    buffer: Vec<u8>,
+}
+
+impl NlaWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_u8(&mut self, kind: u16, val: u8) {
+        self.push_bytes(kind, &[val]);
+    }
+
+    pub fn push_u16(&mut self, kind: u16, val: u16) {
+        let mut bytes = [0; 2];
+        NativeEndian::write_u16(&mut bytes, val);
+        self.push_bytes(kind, &bytes);
+    }
+
+    pub fn push_u32(&mut self, kind: u16, val: u32) {
+        let mut bytes = [0; 4];
+        NativeEndian::write_u32(&mut bytes, val);
+        self.push_bytes(kind, &bytes);
+    }
+
+    pub fn push_u64(&mut self, kind: u16, val: u64) {
+        let mut bytes = [0; 8];
+        NativeEndian::write_u64(&mut bytes, val);
+        self.push_bytes(kind, &bytes);
+    }
+
+    pub fn push_bytes(&mut self, kind: u16, val: &[u8]) {
+        let start = self.start_nla(kind);
+        self.buffer.extend_from_slice(val);
+        self.end_nla(start);
+    }
+
+    /// Push a null-terminated string
+    pub fn push_str(&mut self, kind: u16, val: &str) {
+        let start = self.start_nla(kind);
+        self.buffer.extend_from_slice(val.as_bytes());
+        self.buffer.push(0);
+        self.end_nla(start);
+    }
+
+    /// Push an attribute whose value is the attributes written by `f`. Like the kernel's
+    /// `nla_nest_start()`, the attribute has the `NLA_F_NESTED` flag set.
+    pub fn push_nested<F>(&mut self, kind: u16, f: F)
+    where
+        F: FnOnce(&mut NlaWriter),
+    {
+        let start = self.start_nla(kind);
+        NlaBuffer::new(&mut self.buffer[start..]).set_nested_flag();
+        let mut nested = NlaWriter {
+            buffer: std::mem::take(&mut self.buffer),
+        };
+        f(&mut nested);
+        self.buffer = nested.buffer;
+        self.end_nla(start);
+    }
+
+    /// Return the attributes written so far
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Write the header of a new attribute, and return its offset
+    fn start_nla(&mut self, kind: u16) -> usize {
+        let start = self.buffer.len();
+        self.buffer.resize(start + NLA_HEADER_LEN, 0);
+        NlaBuffer::new(&mut self.buffer[start..]).set_kind(kind);
+        start
+    }
+
+    /// Fill in the length of the attribute starting at `start`, and pad it
+    fn end_nla(&mut self, start: usize) {
+        let len = self.buffer.len() - start;
+        NlaBuffer::new(&mut self.buffer[start..]).set_length(len as u16);
+        self.buffer.resize(start + nla_align(len), 0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[rustfmt::skip]
+    static INTEGERS: [u8; 40] = [
+        0x05, 0x00, 0x01, 0x00, // length = 5, type = 1
+        0x2a, 0x00, 0x00, 0x00, // 42 + 3 bytes of padding
+        0x06, 0x00, 0x02, 0x00, // length = 6, type = 2
+        0x34, 0x12, 0x00, 0x00, // 0x1234 + 2 bytes of padding
+        0x08, 0x00, 0x03, 0x00, // length = 8, type = 3
+        0x78, 0x56, 0x34, 0x12, // 0x12345678
+        0x0c, 0x00, 0x04, 0x00, // length = 12, type = 4
+        0x08, 0x07, 0x06, 0x05, // 0x0102030405060708
+        0x04, 0x03, 0x02, 0x01,
+        0x04, 0x00, 0x05, 0x00, // length = 4, type = 5, empty value
+    ];
+
+    #[test]
+    fn integers() {
+        let mut writer = NlaWriter::new();
+        writer.push_u8(1, 42);
+        writer.push_u16(2, 0x1234);
+        writer.push_u32(3, 0x1234_5678);
+        writer.push_u64(4, 0x0102_0304_0506_0708);
+        writer.push_bytes(5, &[]);
+        assert_eq!(writer.finish(), &INTEGERS[..]);
+    }
+
+    #[rustfmt::skip]
+    static NESTED: [u8; 32] = [
+        0x07, 0x00, 0x03, 0x00, // length = 7, type = 3
+        0x6c, 0x6f, 0x00, 0x00, // "lo\0" + 1 byte of padding
+        0x18, 0x00, 0x12, 0x80, // length = 24, type = 18 | NLA_F_NESTED
+            0x09, 0x00, 0x01, 0x00, // length = 9, type = 1
+            0x76, 0x6c, 0x61, 0x6e, // "vlan\0" + 3 bytes of padding
+            0x00, 0x00, 0x00, 0x00,
+            0x06, 0x00, 0x02, 0x00, // length = 6, type = 2
+            0x64, 0x00, 0x00, 0x00, // 100 + 2 bytes of padding
+    ];
+
+    #[test]
+    fn nested() {
+        let mut writer = NlaWriter::new();
+        writer.push_str(3, "lo");
+        writer.push_nested(18, |info| {
+            info.push_str(1, "vlan");
+            info.push_u16(2, 100);
+        });
+        assert_eq!(writer.finish(), &NESTED[..]);
+    }
+}
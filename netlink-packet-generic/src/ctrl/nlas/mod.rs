@@ -44,8 +44,8 @@ impl Nla for GenlCtrlAttrs {
             Version(v) => size_of_val(v),
             HdrSize(v) => size_of_val(v),
             MaxAttr(v) => size_of_val(v),
-            Ops(nlas) => nlas.iter().map(|op| op.as_slice().buffer_len()).sum(),
-            McastGroups(nlas) => nlas.iter().map(|op| op.as_slice().buffer_len()).sum(),
+            Ops(nlas) => indexed(nlas).as_slice().buffer_len(),
+            McastGroups(nlas) => indexed(nlas).as_slice().buffer_len(),
             Policy(nla) => nla.buffer_len(),
             OpPolicy(nla) => nla.buffer_len(),
             Op(v) => size_of_val(v),
@@ -79,20 +79,8 @@ impl Nla for GenlCtrlAttrs {
             Version(v) => NativeEndian::write_u32(buffer, *v),
             HdrSize(v) => NativeEndian::write_u32(buffer, *v),
             MaxAttr(v) => NativeEndian::write_u32(buffer, *v),
-            Ops(nlas) => {
-                let mut len = 0;
-                for op in nlas {
-                    op.as_slice().emit(&mut buffer[len..]);
-                    len += op.as_slice().buffer_len();
-                }
-            }
-            McastGroups(nlas) => {
-                let mut len = 0;
-                for op in nlas {
-                    op.as_slice().emit(&mut buffer[len..]);
-                    len += op.as_slice().buffer_len();
-                }
-            }
+            Ops(nlas) => indexed(nlas).as_slice().emit(buffer),
+            McastGroups(nlas) => indexed(nlas).as_slice().emit(buffer),
             Policy(nla) => nla.emit_value(buffer),
             OpPolicy(nla) => nla.emit_value(buffer),
             Op(v) => NativeEndian::write_u32(buffer, *v),
@@ -100,6 +88,32 @@ impl Nla for GenlCtrlAttrs {
     }
 }
 
+// The entries of CTRL_ATTR_OPS and CTRL_ATTR_MCAST_GROUPS are nested
+// attributes whose type is the index of the entry, starting at 1
+struct IndexedNlas<'a, T>(u16, &'a [T]);
+
+impl<'a, T: Nla> Nla for IndexedNlas<'a, T> {
+    fn value_len(&self) -> usize {
+        self.1.buffer_len()
+    }
+
+    fn kind(&self) -> u16 {
+        self.0
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.1.emit(buffer)
+    }
+}
+
+fn indexed<T>(entries: &[Vec<T>]) -> Vec<IndexedNlas<'_, T>> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, nlas)| IndexedNlas(i as u16 + 1, nlas.as_slice()))
+        .collect()
+}
+
 impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for GenlCtrlAttrs {
     fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
         let payload = buf.value();
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{NetlinkFlags, NetlinkMessage, NetlinkPayload};
+use netlink_packet_generic::{
+    ctrl::{
+        nlas::{GenlCtrlAttrs, McastGrpAttrs, OpAttrs},
+        GenlCtrl,
+        GenlCtrlCmd,
+    },
+    GenlMessage,
+};
+
+// CTRL_CMD_GETFAMILY request for the "nlctrl" family, as sent by
+// `genl ctrl get name nlctrl`
+#[rustfmt::skip]
+static GET_FAMILY: [u8; 32] = [
+    0x20, 0x00, 0x00, 0x00, // length = 32
+    0x10, 0x00, // message type = 16 (GENL_ID_CTRL)
+    0x01, 0x00, // flags = NLM_F_REQUEST
+    0x01, 0x00, 0x00, 0x00, // sequence number = 1
+    0x00, 0x00, 0x00, 0x00, // port id = 0
+    0x03, // cmd = 3 (CTRL_CMD_GETFAMILY)
+    0x02, // version = 2
+    0x00, 0x00, // reserved
+    0x0b, 0x00, 0x02, 0x00, // length = 11, type = 2 (CTRL_ATTR_FAMILY_NAME)
+    0x6e, 0x6c, 0x63, 0x74, 0x72, 0x6c, 0x00, 0x00, // "nlctrl\0" + padding
+];
+
+// CTRL_CMD_NEWFAMILY answer to GET_FAMILY, trimmed down to a single
+// operation and a single multicast group
+#[rustfmt::skip]
+static NEW_FAMILY: [u8; 116] = [
+    0x74, 0x00, 0x00, 0x00, // length = 116
+    0x10, 0x00, // message type = 16 (GENL_ID_CTRL)
+    0x00, 0x00, // flags
+    0x01, 0x00, 0x00, 0x00, // sequence number = 1
+    0x39, 0x30, 0x00, 0x00, // port id = 12345
+    0x01, // cmd = 1 (CTRL_CMD_NEWFAMILY)
+    0x02, // version = 2
+    0x00, 0x00, // reserved
+
+    0x0b, 0x00, 0x02, 0x00, // length = 11, type = 2 (CTRL_ATTR_FAMILY_NAME)
+    0x6e, 0x6c, 0x63, 0x74, 0x72, 0x6c, 0x00, 0x00, // "nlctrl\0" + padding
+
+    0x06, 0x00, 0x01, 0x00, // length = 6, type = 1 (CTRL_ATTR_FAMILY_ID)
+    0x10, 0x00, 0x00, 0x00, // 16 + padding
+
+    0x08, 0x00, 0x03, 0x00, // length = 8, type = 3 (CTRL_ATTR_VERSION)
+    0x02, 0x00, 0x00, 0x00, // 2
+
+    0x08, 0x00, 0x04, 0x00, // length = 8, type = 4 (CTRL_ATTR_HDRSIZE)
+    0x00, 0x00, 0x00, 0x00, // 0
+
+    0x08, 0x00, 0x05, 0x00, // length = 8, type = 5 (CTRL_ATTR_MAXATTR)
+    0x0a, 0x00, 0x00, 0x00, // 10
+
+    0x18, 0x00, 0x06, 0x00, // length = 24, type = 6 (CTRL_ATTR_OPS)
+        0x14, 0x00, 0x01, 0x00, // length = 20, type = 1 (first operation)
+            0x08, 0x00, 0x01, 0x00, // length = 8, type = 1 (CTRL_ATTR_OP_ID)
+            0x03, 0x00, 0x00, 0x00, // 3 (CTRL_CMD_GETFAMILY)
+            0x08, 0x00, 0x02, 0x00, // length = 8, type = 2 (CTRL_ATTR_OP_FLAGS)
+            0x0e, 0x00, 0x00, 0x00, // GENL_CMD_CAP_DO | GENL_CMD_CAP_DUMP | GENL_CMD_CAP_HASPOL
+
+    0x1c, 0x00, 0x07, 0x00, // length = 28, type = 7 (CTRL_ATTR_MCAST_GROUPS)
+        0x18, 0x00, 0x01, 0x00, // length = 24, type = 1 (first group)
+            0x0b, 0x00, 0x01, 0x00, // length = 11, type = 1 (CTRL_ATTR_MCAST_GRP_NAME)
+            0x6e, 0x6f, 0x74, 0x69, 0x66, 0x79, 0x00, 0x00, // "notify\0" + padding
+            0x08, 0x00, 0x02, 0x00, // length = 8, type = 2 (CTRL_ATTR_MCAST_GRP_ID)
+            0x10, 0x00, 0x00, 0x00, // 16
+];
+
+#[test]
+fn emit_get_family() {
+    let mut message = NetlinkMessage::from(GenlMessage::from_payload(GenlCtrl {
+        cmd: GenlCtrlCmd::GetFamily,
+        nlas: vec![GenlCtrlAttrs::FamilyName("nlctrl".to_owned())],
+    }));
+    message.header.flags = NetlinkFlags::new_get();
+    message.header.sequence_number = 1;
+    message.finalize();
+
+    let mut buf = [0; 32];
+    assert_eq!(message.buffer_len(), buf.len());
+    message.serialize(&mut buf);
+    assert_eq!(buf, GET_FAMILY);
+}
+
+#[test]
+fn parse_new_family() {
+    let message = NetlinkMessage::<GenlMessage<GenlCtrl>>::deserialize(&NEW_FAMILY).unwrap();
+    assert_eq!(message.header.port_number, 12345);
+    let genlmsg = match message.payload {
+        NetlinkPayload::InnerMessage(genlmsg) => genlmsg,
+        payload => panic!("unexpected payload {:?}", payload),
+    };
+    assert_eq!(genlmsg.header.version, 2);
+    assert_eq!(
+        genlmsg.payload,
+        GenlCtrl {
+            cmd: GenlCtrlCmd::NewFamily,
+            nlas: vec![
+                GenlCtrlAttrs::FamilyName("nlctrl".to_owned()),
+                GenlCtrlAttrs::FamilyId(16),
+                GenlCtrlAttrs::Version(2),
+                GenlCtrlAttrs::HdrSize(0),
+                GenlCtrlAttrs::MaxAttr(10),
+                GenlCtrlAttrs::Ops(vec![vec![OpAttrs::Id(3), OpAttrs::Flags(0x0e)]]),
+                GenlCtrlAttrs::McastGroups(vec![vec![
+                    McastGrpAttrs::Name("notify".to_owned()),
+                    McastGrpAttrs::Id(16),
+                ]]),
+            ],
+        }
+    );
+}
+
+#[test]
+fn emit_new_family() {
+    let message = NetlinkMessage::<GenlMessage<GenlCtrl>>::deserialize(&NEW_FAMILY).unwrap();
+    let mut buf = [0; 116];
+    assert_eq!(message.buffer_len(), buf.len());
+    message.serialize(&mut buf);
+    assert_eq!(buf, NEW_FAMILY);
+}
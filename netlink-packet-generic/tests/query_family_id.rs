@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: MIT
 
-use netlink_packet_core::{NetlinkMessage, NetlinkPayload, NLM_F_REQUEST};
+use netlink_packet_core::{NetlinkFlags, NetlinkMessage, NetlinkPayload};
 use netlink_packet_generic::{
     ctrl::{nlas::GenlCtrlAttrs, GenlCtrl, GenlCtrlCmd},
     GenlMessage,
@@ -19,7 +19,7 @@ fn query_family_id() {
     });
     genlmsg.finalize();
     let mut nlmsg = NetlinkMessage::from(genlmsg);
-    nlmsg.header.flags = NLM_F_REQUEST;
+    nlmsg.header.flags = NetlinkFlags::new_get();
     nlmsg.finalize();
 
     println!("Buffer length: {}", nlmsg.buffer_len());
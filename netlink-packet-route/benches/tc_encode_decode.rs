@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: MIT
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use netlink_packet_core::NlaWriter;
+use netlink_packet_route::{
+    nlas::{
+        tc::{
+            qdisc::{
+                htb::{HtbQdiscNla, TcaHtbGlob},
+                HtbQdisc,
+            },
+            Nla,
+            Qdisc,
+            Stats2,
+        },
+        NlaBuffer,
+        NlasIterator,
+    },
+    traits::{Emitable, Parseable, ParseableParametrized},
+    TcHandle,
+    TcHeader,
+    TcMessage,
+    TcMessageBuffer,
+    TCA_STATS2,
+    TCA_STATS_APP,
+    TCA_STATS_BASIC,
+    TCA_STATS_QUEUE,
+    TCA_STATS_RATE_EST,
+    TCA_STATS_RATE_EST64,
+};
+
+// `tc qdisc add dev lo ingress`, as dumped by the kernel
+#[rustfmt::skip]
+static QDISC_INGRESS_PACKET: [u8; 92] = [
+    0x5c, 0x00, 0x00, 0x00, // length = 92
+    0x24, 0x00, // message type = 36 = RTM_NEWQDISC
+    0x02, 0x00, // flags = NLM_F_MULTI
+    0x01, 0x00, 0x00, 0x00, // seq number
+    0x00, 0x00, 0x00, 0x00, // pid
+
+    // tcmsg
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x01, 0x00, 0x00, 0x00, // interface index = 1
+    0x00, 0x00, 0xff, 0xff, // handle = ffff:0
+    0xf1, 0xff, 0xff, 0xff, // parent = TC_H_INGRESS
+    0x01, 0x00, 0x00, 0x00, // info (refcnt) = 1
+
+    // TCA_KIND
+    0x0c, 0x00, // length = 12
+    0x01, 0x00, // type = 1
+    0x69, 0x6e, 0x67, 0x72, 0x65, 0x73, 0x73, 0x00, // "ingress\0"
+
+    // TCA_HW_OFFLOAD
+    0x05, 0x00, // length = 5
+    0x0c, 0x00, // type = 12
+    0x00, // value = 0
+    0x00, 0x00, 0x00, // padding
+
+    // TCA_OPTIONS
+    0x04, 0x00, // length = 4
+    0x02, 0x00, // type = 2
+
+    // TCA_STATS2
+    0x20, 0x00, // length = 32
+    0x07, 0x00, // type = 7
+        // TCA_STATS_BASIC
+        0x10, 0x00, // length = 16
+        0x01, 0x00, // type = 1
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // bytes
+        0x00, 0x00, 0x00, 0x00, // packets
+        // TCA_STATS_QUEUE
+        0x0c, 0x00, // length = 12
+        0x03, 0x00, // type = 3
+        0x00, 0x00, 0x00, 0x00, // truncated queue stats
+        0x00, 0x00, 0x00, 0x00,
+];
+
+/// Statistics attributes of an htb class
+fn stats2_nla() -> Vec<u8> {
+    let mut writer = NlaWriter::new();
+    writer.push_nested(TCA_STATS2, |stats| {
+        stats.push_bytes(TCA_STATS_BASIC, &[0x11; 12]);
+        stats.push_bytes(TCA_STATS_RATE_EST, &[0x22; 8]);
+        stats.push_bytes(TCA_STATS_QUEUE, &[0x33; 20]);
+        stats.push_bytes(TCA_STATS_APP, &[0x44; 20]);
+        stats.push_bytes(TCA_STATS_RATE_EST64, &[0x55; 16]);
+    });
+    writer.finish()
+}
+
+/// `tc qdisc add dev eth0 root handle 1: htb default 10`, with statistics
+fn qdisc_htb_packet() -> Vec<u8> {
+    let msg: TcMessage<Qdisc> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 2,
+        },
+        nlas: vec![
+            Nla::Kind("htb".to_string()),
+            Nla::Options(Qdisc::Htb(HtbQdisc {
+                nlas: vec![
+                    HtbQdiscNla::Init(TcaHtbGlob {
+                        version: 3,
+                        rate2quantum: 10,
+                        defcls: 0x10,
+                        debug: 0,
+                        direct_pkts: 0,
+                    }),
+                    HtbQdiscNla::DirectQlen(1000),
+                ],
+            })),
+            Nla::HwOffload(0),
+            Nla::Stats2(vec![
+                Stats2::StatsBasic(vec![0; 12]),
+                Stats2::StatsQueue(vec![0; 20]),
+            ]),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    buf
+}
+
+/// 4096 bytes of attributes: 512 u32 values
+fn nlas_packet() -> Vec<u8> {
+    let mut writer = NlaWriter::new();
+    for i in 0..512 {
+        writer.push_u32(i as u16 + 1, i);
+    }
+    writer.finish()
+}
+
+fn tc(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tc");
+    group.throughput(Throughput::Elements(1));
+
+    let ingress = &QDISC_INGRESS_PACKET[16..];
+    group.bench_function("bench_tc_qdisc_ingress_parse", |b| {
+        b.iter(|| {
+            TcMessage::<Qdisc>::parse(&TcMessageBuffer::new(&ingress)).unwrap();
+        })
+    });
+
+    let htb = qdisc_htb_packet();
+    group.bench_function("bench_tc_qdisc_htb_parse", |b| {
+        b.iter(|| {
+            TcMessage::<Qdisc>::parse(&TcMessageBuffer::new(&&htb[..])).unwrap();
+        })
+    });
+
+    let msg = TcMessage::<Qdisc>::parse(&TcMessageBuffer::new(&ingress)).unwrap();
+    let mut buf = vec![0; msg.buffer_len()];
+    group.bench_function("bench_tc_qdisc_ingress_emit", |b| {
+        b.iter(|| msg.emit(&mut buf))
+    });
+
+    let nlas = nlas_packet();
+    assert_eq!(nlas.len(), 4096);
+    group.bench_function("bench_nlas_iterator", |b| {
+        b.iter(|| {
+            for nla in NlasIterator::new(&nlas[..]) {
+                nla.unwrap();
+            }
+        })
+    });
+
+    let stats2 = stats2_nla();
+    group.bench_function("bench_stats2_parse", |b| {
+        b.iter(|| {
+            Nla::<Qdisc>::parse_with_param(&NlaBuffer::new(&stats2[..]), "htb").unwrap();
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, tc);
+criterion_main!(benches);
@@ -6,6 +6,7 @@ use netlink_packet_route::{
     constants::*,
     nlas::neighbour::Nla,
     NeighbourMessage,
+    NetlinkFlags,
     NetlinkHeader,
     NetlinkMessage,
     NetlinkPayload,
@@ -20,7 +21,7 @@ fn main() {
 
     let mut req = NetlinkMessage {
         header: NetlinkHeader {
-            flags: NLM_F_DUMP | NLM_F_REQUEST,
+            flags: NetlinkFlags::new_dump(),
             ..Default::default()
         },
         payload: NetlinkPayload::from(RtnlMessage::GetNeighbour(NeighbourMessage::default())),
@@ -2,12 +2,11 @@
 
 use netlink_packet_route::{
     LinkMessage,
+    NetlinkFlags,
     NetlinkHeader,
     NetlinkMessage,
     NetlinkPayload,
     RtnlMessage,
-    NLM_F_DUMP,
-    NLM_F_REQUEST,
 };
 use netlink_sys::{protocols::NETLINK_ROUTE, Socket, SocketAddr};
 
@@ -20,7 +19,7 @@ fn main() {
         header: NetlinkHeader::default(),
         payload: NetlinkPayload::from(RtnlMessage::GetLink(LinkMessage::default())),
     };
-    packet.header.flags = NLM_F_DUMP | NLM_F_REQUEST;
+    packet.header.flags = NetlinkFlags::new_dump();
     packet.header.sequence_number = 1;
     packet.finalize();
 
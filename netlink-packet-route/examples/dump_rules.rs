@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: MIT
 
 use netlink_packet_route::{
-    constants::*,
+    NetlinkFlags,
     NetlinkHeader,
     NetlinkMessage,
     NetlinkPayload,
@@ -17,7 +17,7 @@ fn main() {
 
     let mut packet = NetlinkMessage {
         header: NetlinkHeader {
-            flags: NLM_F_REQUEST | NLM_F_DUMP,
+            flags: NetlinkFlags::new_dump(),
             ..Default::default()
         },
         payload: NetlinkPayload::from(RtnlMessage::GetRule(RuleMessage::default())),
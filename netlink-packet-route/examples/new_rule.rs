@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: MIT
 
-use netlink_packet_core::{NetlinkHeader, NetlinkMessage, NetlinkPayload};
-use netlink_packet_route::{constants::*, rule, RtnlMessage, RuleHeader, RuleMessage};
+use netlink_packet_core::{NetlinkFlags, NetlinkHeader, NetlinkMessage, NetlinkPayload};
+use netlink_packet_route::{constants::*, rule, RtnlMessage, RuleAction, RuleHeader, RuleMessage};
 use netlink_sys::{protocols::NETLINK_ROUTE, Socket, SocketAddr};
 
 fn main() {
@@ -11,14 +11,14 @@ fn main() {
 
     let mut msg = NetlinkMessage {
         header: NetlinkHeader {
-            flags: NLM_F_REQUEST | NLM_F_CREATE | NLM_F_EXCL | NLM_F_ACK,
+            flags: NetlinkFlags::new_create(),
             ..Default::default()
         },
         payload: NetlinkPayload::from(RtnlMessage::NewRule(RuleMessage {
             header: RuleHeader {
                 family: AF_INET as u8,
                 table: RT_TABLE_DEFAULT,
-                action: FR_ACT_TO_TBL,
+                action: RuleAction::ToTable,
                 ..Default::default()
             },
             nlas: vec![
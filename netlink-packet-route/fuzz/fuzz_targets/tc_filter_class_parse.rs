@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use netlink_packet_route::{
+    tc::{Class, Filter},
+    traits::Parseable,
+    TcMessage,
+    TcMessageBuffer,
+};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(buf) = TcMessageBuffer::new_checked(&data) {
+        let _ = TcMessage::<Filter>::parse(&buf);
+        let _ = TcMessage::<Class>::parse(&buf);
+    }
+});
@@ -5,11 +5,12 @@ extern crate bitflags;
 #[macro_use]
 pub(crate) extern crate netlink_packet_utils as utils;
 pub(crate) use self::utils::parsers;
-pub use self::utils::{traits, DecodeError};
+pub use self::utils::{traits, DecodeError, EncodeError};
 
 pub use netlink_packet_core::{
     ErrorMessage,
     NetlinkBuffer,
+    NetlinkFlags,
     NetlinkHeader,
     NetlinkMessage,
     NetlinkPayload,
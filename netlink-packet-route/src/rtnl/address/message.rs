@@ -3,6 +3,7 @@
 use anyhow::Context;
 
 use crate::{
+    constants::*,
     nlas::address::Nla,
     traits::{Emitable, Parseable},
     AddressMessageBuffer,
@@ -16,11 +17,51 @@ pub struct AddressMessage {
     pub nlas: Vec<Nla>,
 }
 
+bitflags! {
+    /// Flags of an address. Only the lowest 8 bits fit in the header, the full set is carried by
+    /// the `IFA_FLAGS` attribute ([`Nla::Flags`]).
+    pub struct AddressFlags: u32 {
+        const SECONDARY = IFA_F_SECONDARY;
+        /// Temporary address generated by the IPv6 privacy extensions (same bit as `SECONDARY`)
+        const TEMPORARY = IFA_F_TEMPORARY;
+        /// Do not perform duplicate address detection
+        const NODAD = IFA_F_NODAD;
+        /// Optimistic duplicate address detection (RFC 4429)
+        const OPTIMISTIC = IFA_F_OPTIMISTIC;
+        /// Duplicate address detection failed
+        const DADFAILED = IFA_F_DADFAILED;
+        /// Home address of a mobile IPv6 node
+        const HOMEADDRESS = IFA_F_HOMEADDRESS;
+        /// The preferred lifetime of the address expired
+        const DEPRECATED = IFA_F_DEPRECATED;
+        /// Duplicate address detection is still in progress
+        const TENTATIVE = IFA_F_TENTATIVE;
+        /// The address doesn't expire
+        const PERMANENT = IFA_F_PERMANENT;
+        /// Let the kernel manage the temporary addresses created from this one
+        const MANAGETEMPADDR = IFA_F_MANAGETEMPADDR;
+        /// Do not create a prefix route for this address
+        const NOPREFIXROUTE = IFA_F_NOPREFIXROUTE;
+        /// Join the multicast group of the address automatically
+        const MCAUTOJOIN = IFA_F_MCAUTOJOIN;
+        /// Interface identifier generated as per RFC 7217
+        const STABLE_PRIVACY = IFA_F_STABLE_PRIVACY;
+    }
+}
+
+impl Default for AddressFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct AddressHeader {
     pub family: u8,
     pub prefix_len: u8,
-    pub flags: u8,
+    /// Flags of the address. Only the lowest 8 bits are emitted, use [`Nla::Flags`] for the
+    /// others.
+    pub flags: AddressFlags,
     pub scope: u8,
     pub index: u32,
 }
@@ -34,7 +75,7 @@ impl Emitable for AddressHeader {
         let mut packet = AddressMessageBuffer::new(buffer);
         packet.set_family(self.family);
         packet.set_prefix_len(self.prefix_len);
-        packet.set_flags(self.flags);
+        packet.set_flags(self.flags.bits() as u8);
         packet.set_scope(self.scope);
         packet.set_index(self.index);
     }
@@ -58,7 +99,7 @@ impl<T: AsRef<[u8]>> Parseable<AddressMessageBuffer<T>> for AddressHeader {
         Ok(Self {
             family: buf.family(),
             prefix_len: buf.prefix_len(),
-            flags: buf.flags(),
+            flags: AddressFlags::from_bits_truncate(buf.flags().into()),
             scope: buf.scope(),
             index: buf.index(),
         })
@@ -83,3 +124,131 @@ impl<'a, T: AsRef<[u8]> + 'a> Parseable<AddressMessageBuffer<&'a T>> for Vec<Nla
         Ok(nlas)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        constants::*,
+        nlas::address::{CacheInfo, Nla},
+        traits::{Emitable, Parseable},
+        AddressFlags,
+        AddressHeader,
+        AddressMessage,
+        AddressMessageBuffer,
+    };
+
+    // IPv6 address configured by SLAAC from a router advertisement
+    #[rustfmt::skip]
+    static MESSAGE: [u8; 56] = [
+        0x0a, // address family (inet6)
+        0x40, // prefix length = 64
+        0x00, // flags
+        0x00, // scope RT_SCOPE_UNIVERSE
+        0x02, 0x00, 0x00, 0x00, // interface index = 2
+        0x14, 0x00, // length = 20
+        0x01, 0x00, // type = IFA_ADDRESS
+        0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x11, 0x22, 0xff, 0xfe, 0x33, 0x44, 0x55, // 2001:db8::211:22ff:fe33:4455
+        0x14, 0x00, // length = 20
+        0x06, 0x00, // type = IFA_CACHEINFO
+        0x10, 0x0e, 0x00, 0x00, // preferred = 3600
+        0x20, 0x1c, 0x00, 0x00, // valid = 7200
+        0xe8, 0x03, 0x00, 0x00, // cstamp = 1000
+        0xd0, 0x07, 0x00, 0x00, // tstamp = 2000
+        0x08, 0x00, // length = 8
+        0x08, 0x00, // type = IFA_FLAGS
+        0x00, 0x01, 0x00, 0x00, // IFA_F_MANAGETEMPADDR
+    ];
+
+    #[test]
+    fn parse_and_emit_cache_info() {
+        let expected = AddressMessage {
+            header: AddressHeader {
+                family: AF_INET6 as u8,
+                prefix_len: 64,
+                flags: AddressFlags::empty(),
+                scope: RT_SCOPE_UNIVERSE,
+                index: 2,
+            },
+            nlas: vec![
+                Nla::Address(vec![
+                    0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x02, 0x11, 0x22, 0xff, 0xfe,
+                    0x33, 0x44, 0x55,
+                ]),
+                Nla::CacheInfo(CacheInfo {
+                    ifa_preferred: 3600,
+                    ifa_valid: 7200,
+                    cstamp: 1000,
+                    tstamp: 2000,
+                }),
+                Nla::Flags(AddressFlags::MANAGETEMPADDR),
+            ],
+        };
+        let parsed = AddressMessage::parse(&AddressMessageBuffer::new(&&MESSAGE[..])).unwrap();
+        assert_eq!(parsed, expected);
+
+        let mut buf = [0; 56];
+        assert_eq!(expected.buffer_len(), 56);
+        expected.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &MESSAGE[..]);
+    }
+
+    // ip -6 address add 2001:db8::1/64 dev eth0 nodad noprefixroute
+    #[rustfmt::skip]
+    static FLAGS_MESSAGE: [u8; 36] = [
+        0x0a, // address family (inet6)
+        0x40, // prefix length = 64
+        0x82, // flags IFA_F_NODAD | IFA_F_PERMANENT
+        0x00, // scope RT_SCOPE_UNIVERSE
+        0x02, 0x00, 0x00, 0x00, // interface index = 2
+        0x14, 0x00, // length = 20
+        0x01, 0x00, // type = IFA_ADDRESS
+        0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, // 2001:db8::1
+        0x08, 0x00, // length = 8
+        0x08, 0x00, // type = IFA_FLAGS
+        0x82, 0x02, 0x00, 0x00, // IFA_F_NODAD | IFA_F_PERMANENT | IFA_F_NOPREFIXROUTE
+    ];
+
+    #[test]
+    fn parse_and_emit_flags() {
+        let expected = AddressMessage {
+            header: AddressHeader {
+                family: AF_INET6 as u8,
+                prefix_len: 64,
+                flags: AddressFlags::NODAD | AddressFlags::PERMANENT,
+                scope: RT_SCOPE_UNIVERSE,
+                index: 2,
+            },
+            nlas: vec![
+                Nla::Address(vec![
+                    0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x01,
+                ]),
+                Nla::Flags(
+                    AddressFlags::NODAD | AddressFlags::PERMANENT | AddressFlags::NOPREFIXROUTE,
+                ),
+            ],
+        };
+        let parsed =
+            AddressMessage::parse(&AddressMessageBuffer::new(&&FLAGS_MESSAGE[..])).unwrap();
+        assert_eq!(parsed, expected);
+
+        let mut buf = [0; 36];
+        assert_eq!(expected.buffer_len(), 36);
+        expected.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &FLAGS_MESSAGE[..]);
+    }
+
+    #[test]
+    fn emit_header_flags_subset() {
+        // the flags that don't fit in the header are only carried by IFA_FLAGS
+        let header = AddressHeader {
+            flags: AddressFlags::PERMANENT | AddressFlags::STABLE_PRIVACY,
+            ..Default::default()
+        };
+        let mut buf = [0; 8];
+        header.emit(&mut buf[..]);
+        assert_eq!(buf[2], 0x80);
+    }
+}
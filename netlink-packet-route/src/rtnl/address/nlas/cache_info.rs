@@ -5,20 +5,29 @@ use crate::{
     DecodeError,
 };
 
+/// Lifetimes of an address (`struct ifa_cacheinfo`)
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
 pub struct CacheInfo {
-    pub ifa_preferred: i32,
-    pub ifa_valid: i32,
-    pub cstamp: i32,
-    pub tstamp: i32,
+    /// Remaining preferred lifetime in seconds, [`INFINITY_LIFE_TIME`] if the address never
+    /// becomes deprecated
+    pub ifa_preferred: u32,
+    /// Remaining valid lifetime in seconds, [`INFINITY_LIFE_TIME`] if the address never expires
+    pub ifa_valid: u32,
+    /// Creation time of the address, in hundredths of seconds since boot
+    pub cstamp: u32,
+    /// Last update time of the address, in hundredths of seconds since boot
+    pub tstamp: u32,
 }
 
+/// Lifetime of the addresses that don't expire
+pub const INFINITY_LIFE_TIME: u32 = 0xffff_ffff;
+
 pub const ADDRESSS_CACHE_INFO_LEN: usize = 16;
 buffer!(CacheInfoBuffer(ADDRESSS_CACHE_INFO_LEN) {
-    ifa_preferred: (i32, 0..4),
-    ifa_valid: (i32, 4..8),
-    cstamp: (i32, 8..12),
-    tstamp: (i32, 12..16),
+    ifa_preferred: (u32, 0..4),
+    ifa_valid: (u32, 4..8),
+    cstamp: (u32, 8..12),
+    tstamp: (u32, 12..16),
 });
 
 impl<T: AsRef<[u8]>> Parseable<CacheInfoBuffer<T>> for CacheInfo {
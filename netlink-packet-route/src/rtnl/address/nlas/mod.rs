@@ -12,7 +12,8 @@ use crate::{
     constants::*,
     nlas::{self, DefaultNla, NlaBuffer},
     parsers::{parse_string, parse_u32},
-    traits::Parseable,
+    traits::{Emitable, Parseable},
+    AddressFlags,
     DecodeError,
 };
 
@@ -24,9 +25,9 @@ pub enum Nla {
     Label(String),
     Broadcast(Vec<u8>),
     Anycast(Vec<u8>),
-    CacheInfo(Vec<u8>),
+    CacheInfo(CacheInfo),
     Multicast(Vec<u8>),
-    Flags(u32),
+    Flags(AddressFlags),
     Other(DefaultNla),
 }
 
@@ -49,8 +50,7 @@ impl nlas::Nla for Nla {
             // u32
             Flags(_) => size_of::<u32>(),
 
-            // Native
-            CacheInfo(ref buffer) => buffer.len(),
+            CacheInfo(ref cache_info) => cache_info.buffer_len(),
 
             // Defaults
             Other(ref attr)  => attr.value_len(),
@@ -67,9 +67,10 @@ impl nlas::Nla for Nla {
                 | Local(ref bytes)
                 | Broadcast(ref bytes)
                 | Anycast(ref bytes)
-                | CacheInfo(ref bytes)
                 | Multicast(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
 
+            CacheInfo(ref cache_info) => cache_info.emit(buffer),
+
             // String
             Label(ref string) => {
                 buffer[..string.len()].copy_from_slice(string.as_bytes());
@@ -77,7 +78,7 @@ impl nlas::Nla for Nla {
             }
 
             // u32
-            Flags(ref value) => NativeEndian::write_u32(buffer, *value),
+            Flags(ref value) => NativeEndian::write_u32(buffer, value.bits()),
 
 
             // Default
@@ -113,9 +114,14 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
             IFA_LABEL => Label(parse_string(payload).context("invalid IFA_LABEL value")?),
             IFA_BROADCAST => Broadcast(payload.to_vec()),
             IFA_ANYCAST => Anycast(payload.to_vec()),
-            IFA_CACHEINFO => CacheInfo(payload.to_vec()),
+            IFA_CACHEINFO => CacheInfo(
+                cache_info::CacheInfo::parse(&CacheInfoBuffer::new_checked(payload)?)
+                    .context("invalid IFA_CACHEINFO value")?,
+            ),
             IFA_MULTICAST => Multicast(payload.to_vec()),
-            IFA_FLAGS => Flags(parse_u32(payload).context("invalid IFA_FLAGS value")?),
+            IFA_FLAGS => Flags(AddressFlags::from_bits_truncate(
+                parse_u32(payload).context("invalid IFA_FLAGS value")?,
+            )),
             kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
         })
     }
@@ -13,6 +13,8 @@ use crate::{
     NeighbourMessageBuffer,
     NeighbourTableMessage,
     NeighbourTableMessageBuffer,
+    NexthopMessage,
+    NexthopMessageBuffer,
     NsidMessage,
     NsidMessageBuffer,
     RouteHeader,
@@ -154,19 +156,31 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<RtnlMessageBuffer<&'a T>
                 }
             }
             // TC Messages
-            RTM_NEWQDISC | RTM_DELQDISC | RTM_GETQDISC |
-            RTM_NEWTCLASS | RTM_DELTCLASS | RTM_GETTCLASS |
-            RTM_NEWTFILTER | RTM_DELTFILTER | RTM_GETTFILTER |
-            RTM_NEWCHAIN | RTM_DELCHAIN | RTM_GETCHAIN => {
+            RTM_NEWQDISC | RTM_DELQDISC | RTM_GETQDISC => {
                 let err = "invalid tc message";
                 let msg = TcMessage::parse(&TcMessageBuffer::new_checked(&buf.inner()).context(err)?).context(err)?;
                 match message_type {
                     RTM_NEWQDISC => NewQueueDiscipline(msg),
                     RTM_DELQDISC => DelQueueDiscipline(msg),
                     RTM_GETQDISC => GetQueueDiscipline(msg),
+                    _ => unreachable!(),
+                }
+            }
+            RTM_NEWTCLASS | RTM_DELTCLASS | RTM_GETTCLASS => {
+                let err = "invalid tc message";
+                let msg = TcMessage::parse(&TcMessageBuffer::new_checked(&buf.inner()).context(err)?).context(err)?;
+                match message_type {
                     RTM_NEWTCLASS => NewTrafficClass(msg),
                     RTM_DELTCLASS => DelTrafficClass(msg),
                     RTM_GETTCLASS => GetTrafficClass(msg),
+                    _ => unreachable!(),
+                }
+            }
+            RTM_NEWTFILTER | RTM_DELTFILTER | RTM_GETTFILTER |
+            RTM_NEWCHAIN | RTM_DELCHAIN | RTM_GETCHAIN => {
+                let err = "invalid tc message";
+                let msg = TcMessage::parse(&TcMessageBuffer::new_checked(&buf.inner()).context(err)?).context(err)?;
+                match message_type {
                     RTM_NEWTFILTER => NewTrafficFilter(msg),
                     RTM_DELTFILTER => DelTrafficFilter(msg),
                     RTM_GETTFILTER => GetTrafficFilter(msg),
@@ -177,6 +191,18 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<RtnlMessageBuffer<&'a T>
                 }
             }
 
+            // Nexthop object messages
+            RTM_NEWNEXTHOP | RTM_DELNEXTHOP | RTM_GETNEXTHOP => {
+                let err = "invalid nexthop message";
+                let msg = NexthopMessage::parse(&NexthopMessageBuffer::new_checked(&buf.inner()).context(err)?).context(err)?;
+                match message_type {
+                    RTM_NEWNEXTHOP => NewNexthop(msg),
+                    RTM_DELNEXTHOP => DelNexthop(msg),
+                    RTM_GETNEXTHOP => GetNexthop(msg),
+                    _ => unreachable!(),
+                }
+            }
+
             // ND ID Messages
             RTM_NEWNSID | RTM_GETNSID | RTM_DELNSID => {
                 let err = "invalid nsid message";
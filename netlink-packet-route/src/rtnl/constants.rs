@@ -58,6 +58,9 @@ pub const RTM_NEWCACHEREPORT: u16 = 96;
 pub const RTM_NEWCHAIN: u16 = 100;
 pub const RTM_DELCHAIN: u16 = 101;
 pub const RTM_GETCHAIN: u16 = 102;
+pub const RTM_NEWNEXTHOP: u16 = 104;
+pub const RTM_DELNEXTHOP: u16 = 105;
+pub const RTM_GETNEXTHOP: u16 = 106;
 pub const RTM_NEWLINKPROP: u16 = 108;
 pub const RTM_DELLINKPROP: u16 = 109;
 
@@ -106,7 +109,14 @@ pub const RTPROT_XORP: u8 = 14;
 pub const RTPROT_NTK: u8 = 15;
 pub const RTPROT_DHCP: u8 = 16;
 pub const RTPROT_MROUTED: u8 = 17;
+pub const RTPROT_KEEPALIVED: u8 = 18;
 pub const RTPROT_BABEL: u8 = 42;
+pub const RTPROT_OPENR: u8 = 99;
+pub const RTPROT_BGP: u8 = 186;
+pub const RTPROT_ISIS: u8 = 187;
+pub const RTPROT_OSPF: u8 = 188;
+pub const RTPROT_RIP: u8 = 189;
+pub const RTPROT_EIGRP: u8 = 192;
 
 /// The destination is globally valid.
 pub const RT_SCOPE_UNIVERSE: u8 = 0;
@@ -276,6 +286,25 @@ pub const NETNSA_PID: u16 = 2;
 pub const NETNSA_FD: u16 = 3;
 pub const NETNSA_NSID_NOT_ASSIGNED: i32 = -1;
 
+/// see `https://github.com/torvalds/linux/blob/master/include/uapi/linux/nexthop.h`
+pub const NHA_UNSPEC: u16 = 0;
+pub const NHA_ID: u16 = 1;
+pub const NHA_GROUP: u16 = 2;
+pub const NHA_GROUP_TYPE: u16 = 3;
+pub const NHA_BLACKHOLE: u16 = 4;
+pub const NHA_OIF: u16 = 5;
+pub const NHA_GATEWAY: u16 = 6;
+pub const NHA_ENCAP_TYPE: u16 = 7;
+pub const NHA_ENCAP: u16 = 8;
+pub const NHA_GROUPS: u16 = 9;
+pub const NHA_MASTER: u16 = 10;
+pub const NHA_FDB: u16 = 11;
+
+/// Hash-threshold multipath group
+pub const NEXTHOP_GRP_TYPE_MPATH: u16 = 0;
+/// Resilient multipath group
+pub const NEXTHOP_GRP_TYPE_RES: u16 = 1;
+
 /// Neighbour cache entry state: the neighbour has not (yet) been resolved
 pub const NUD_INCOMPLETE: u16 = 1;
 /// Neighbour cache entry state: the neighbour entry is valid until its lifetime expires
@@ -330,6 +359,677 @@ pub const TCA_STATS_RATE_EST64: u16 = 5;
 pub const TCA_STATS_PAD: u16 = 6;
 pub const TCA_STATS_BASIC_HW: u16 = 7;
 
+pub const TCA_HTB_UNSPEC: u16 = 0;
+pub const TCA_HTB_PARMS: u16 = 1;
+pub const TCA_HTB_INIT: u16 = 2;
+pub const TCA_HTB_CTAB: u16 = 3;
+pub const TCA_HTB_RTAB: u16 = 4;
+pub const TCA_HTB_DIRECT_QLEN: u16 = 5;
+pub const TCA_HTB_RATE64: u16 = 6;
+pub const TCA_HTB_CEIL64: u16 = 7;
+pub const TCA_HTB_PAD: u16 = 8;
+pub const TCA_HTB_OFFLOAD: u16 = 9;
+
+pub const TC_LINKLAYER_UNAWARE: u8 = 0;
+pub const TC_LINKLAYER_ETHERNET: u8 = 1;
+pub const TC_LINKLAYER_ATM: u8 = 2;
+
+pub const TCA_TBF_UNSPEC: u16 = 0;
+pub const TCA_TBF_PARMS: u16 = 1;
+pub const TCA_TBF_RTAB: u16 = 2;
+pub const TCA_TBF_PTAB: u16 = 3;
+pub const TCA_TBF_RATE64: u16 = 4;
+pub const TCA_TBF_PRATE64: u16 = 5;
+pub const TCA_TBF_BURST: u16 = 6;
+pub const TCA_TBF_PBURST: u16 = 7;
+pub const TCA_TBF_PAD: u16 = 8;
+
+pub const TCA_FQ_CODEL_UNSPEC: u16 = 0;
+pub const TCA_FQ_CODEL_TARGET: u16 = 1;
+pub const TCA_FQ_CODEL_LIMIT: u16 = 2;
+pub const TCA_FQ_CODEL_INTERVAL: u16 = 3;
+pub const TCA_FQ_CODEL_ECN: u16 = 4;
+pub const TCA_FQ_CODEL_FLOWS: u16 = 5;
+pub const TCA_FQ_CODEL_QUANTUM: u16 = 6;
+pub const TCA_FQ_CODEL_CE_THRESHOLD: u16 = 7;
+pub const TCA_FQ_CODEL_DROP_BATCH_SIZE: u16 = 8;
+pub const TCA_FQ_CODEL_MEMORY_LIMIT: u16 = 9;
+pub const TCA_FQ_CODEL_CE_THRESHOLD_SELECTOR: u16 = 10;
+pub const TCA_FQ_CODEL_CE_THRESHOLD_MASK: u16 = 11;
+
+pub const TCA_FQ_CODEL_XSTATS_QDISC: u32 = 0;
+pub const TCA_FQ_CODEL_XSTATS_CLASS: u32 = 1;
+
+pub const TCA_NETEM_UNSPEC: u16 = 0;
+pub const TCA_NETEM_CORR: u16 = 1;
+pub const TCA_NETEM_DELAY_DIST: u16 = 2;
+pub const TCA_NETEM_REORDER: u16 = 3;
+pub const TCA_NETEM_CORRUPT: u16 = 4;
+pub const TCA_NETEM_LOSS: u16 = 5;
+pub const TCA_NETEM_RATE: u16 = 6;
+pub const TCA_NETEM_ECN: u16 = 7;
+pub const TCA_NETEM_RATE64: u16 = 8;
+pub const TCA_NETEM_PAD: u16 = 9;
+pub const TCA_NETEM_LATENCY64: u16 = 10;
+pub const TCA_NETEM_JITTER64: u16 = 11;
+pub const TCA_NETEM_SLOT: u16 = 12;
+pub const TCA_NETEM_SLOT_DIST: u16 = 13;
+pub const TCA_NETEM_PRNG_SEED: u16 = 14;
+
+pub const NETEM_LOSS_UNSPEC: u16 = 0;
+pub const NETEM_LOSS_GI: u16 = 1;
+pub const NETEM_LOSS_GE: u16 = 2;
+
+pub const TC_PRIO_BESTEFFORT: u8 = 0;
+pub const TC_PRIO_FILLER: u8 = 1;
+pub const TC_PRIO_BULK: u8 = 2;
+pub const TC_PRIO_INTERACTIVE_BULK: u8 = 4;
+pub const TC_PRIO_INTERACTIVE: u8 = 6;
+pub const TC_PRIO_CONTROL: u8 = 7;
+pub const TC_PRIO_MAX: u8 = 15;
+
+pub const TCA_RED_UNSPEC: u16 = 0;
+pub const TCA_RED_PARMS: u16 = 1;
+pub const TCA_RED_STAB: u16 = 2;
+pub const TCA_RED_MAX_P: u16 = 3;
+pub const TCA_RED_FLAGS: u16 = 4;
+pub const TCA_RED_EARLY_DROP_BLOCK: u16 = 5;
+pub const TCA_RED_MARK_BLOCK: u16 = 6;
+
+pub const TC_RED_ECN: u8 = 1;
+pub const TC_RED_HARDDROP: u8 = 2;
+pub const TC_RED_ADAPTATIVE: u8 = 4;
+pub const TC_RED_NODROP: u8 = 8;
+
+pub const TCA_CAKE_UNSPEC: u16 = 0;
+pub const TCA_CAKE_PAD: u16 = 1;
+pub const TCA_CAKE_BASE_RATE64: u16 = 2;
+pub const TCA_CAKE_DIFFSERV_MODE: u16 = 3;
+pub const TCA_CAKE_ATM: u16 = 4;
+pub const TCA_CAKE_FLOW_MODE: u16 = 5;
+pub const TCA_CAKE_OVERHEAD: u16 = 6;
+pub const TCA_CAKE_RTT: u16 = 7;
+pub const TCA_CAKE_TARGET: u16 = 8;
+pub const TCA_CAKE_AUTORATE: u16 = 9;
+pub const TCA_CAKE_MEMORY: u16 = 10;
+pub const TCA_CAKE_NAT: u16 = 11;
+pub const TCA_CAKE_RAW: u16 = 12;
+pub const TCA_CAKE_WASH: u16 = 13;
+pub const TCA_CAKE_MPU: u16 = 14;
+pub const TCA_CAKE_INGRESS: u16 = 15;
+pub const TCA_CAKE_ACK_FILTER: u16 = 16;
+pub const TCA_CAKE_SPLIT_GSO: u16 = 17;
+pub const TCA_CAKE_FWMARK: u16 = 18;
+
+pub const TCA_CAKE_STATS_INVALID: u16 = 0;
+pub const TCA_CAKE_STATS_PAD: u16 = 1;
+pub const TCA_CAKE_STATS_CAPACITY_ESTIMATE64: u16 = 2;
+pub const TCA_CAKE_STATS_MEMORY_LIMIT: u16 = 3;
+pub const TCA_CAKE_STATS_MEMORY_USED: u16 = 4;
+pub const TCA_CAKE_STATS_AVG_NETOFF: u16 = 5;
+pub const TCA_CAKE_STATS_MIN_NETLEN: u16 = 6;
+pub const TCA_CAKE_STATS_MAX_NETLEN: u16 = 7;
+pub const TCA_CAKE_STATS_MIN_ADJLEN: u16 = 8;
+pub const TCA_CAKE_STATS_MAX_ADJLEN: u16 = 9;
+pub const TCA_CAKE_STATS_TIN_STATS: u16 = 10;
+pub const TCA_CAKE_STATS_DEFICIT: u16 = 11;
+pub const TCA_CAKE_STATS_COBALT_COUNT: u16 = 12;
+pub const TCA_CAKE_STATS_DROPPING: u16 = 13;
+pub const TCA_CAKE_STATS_DROP_NEXT_US: u16 = 14;
+pub const TCA_CAKE_STATS_P_DROP: u16 = 15;
+pub const TCA_CAKE_STATS_BLUE_TIMER_US: u16 = 16;
+
+pub const TCA_CAKE_TIN_STATS_UNSPEC: u16 = 0;
+pub const TCA_CAKE_TIN_STATS_PAD: u16 = 1;
+pub const TCA_CAKE_TIN_STATS_SENT_PACKETS: u16 = 2;
+pub const TCA_CAKE_TIN_STATS_SENT_BYTES64: u16 = 3;
+pub const TCA_CAKE_TIN_STATS_DROPPED_PACKETS: u16 = 4;
+pub const TCA_CAKE_TIN_STATS_DROPPED_BYTES64: u16 = 5;
+pub const TCA_CAKE_TIN_STATS_ACKS_DROPPED_PACKETS: u16 = 6;
+pub const TCA_CAKE_TIN_STATS_ACKS_DROPPED_BYTES64: u16 = 7;
+pub const TCA_CAKE_TIN_STATS_ECN_MARKED_PACKETS: u16 = 8;
+pub const TCA_CAKE_TIN_STATS_ECN_MARKED_BYTES64: u16 = 9;
+pub const TCA_CAKE_TIN_STATS_BACKLOG_PACKETS: u16 = 10;
+pub const TCA_CAKE_TIN_STATS_BACKLOG_BYTES: u16 = 11;
+pub const TCA_CAKE_TIN_STATS_THRESHOLD_RATE64: u16 = 12;
+pub const TCA_CAKE_TIN_STATS_TARGET_US: u16 = 13;
+pub const TCA_CAKE_TIN_STATS_INTERVAL_US: u16 = 14;
+pub const TCA_CAKE_TIN_STATS_WAY_INDIRECT_HITS: u16 = 15;
+pub const TCA_CAKE_TIN_STATS_WAY_MISSES: u16 = 16;
+pub const TCA_CAKE_TIN_STATS_WAY_COLLISIONS: u16 = 17;
+pub const TCA_CAKE_TIN_STATS_PEAK_DELAY_US: u16 = 18;
+pub const TCA_CAKE_TIN_STATS_AVG_DELAY_US: u16 = 19;
+pub const TCA_CAKE_TIN_STATS_BASE_DELAY_US: u16 = 20;
+pub const TCA_CAKE_TIN_STATS_SPARSE_FLOWS: u16 = 21;
+pub const TCA_CAKE_TIN_STATS_BULK_FLOWS: u16 = 22;
+pub const TCA_CAKE_TIN_STATS_UNRESPONSIVE_FLOWS: u16 = 23;
+pub const TCA_CAKE_TIN_STATS_MAX_SKBLEN: u16 = 24;
+pub const TCA_CAKE_TIN_STATS_FLOW_QUANTUM: u16 = 25;
+
+pub const CAKE_FLOW_NONE: u32 = 0;
+pub const CAKE_FLOW_SRC_IP: u32 = 1;
+pub const CAKE_FLOW_DST_IP: u32 = 2;
+pub const CAKE_FLOW_HOSTS: u32 = 3;
+pub const CAKE_FLOW_FLOWS: u32 = 4;
+pub const CAKE_FLOW_DUAL_SRC: u32 = 5;
+pub const CAKE_FLOW_DUAL_DST: u32 = 6;
+pub const CAKE_FLOW_TRIPLE: u32 = 7;
+
+pub const CAKE_DIFFSERV_DIFFSERV3: u32 = 0;
+pub const CAKE_DIFFSERV_DIFFSERV4: u32 = 1;
+pub const CAKE_DIFFSERV_DIFFSERV8: u32 = 2;
+pub const CAKE_DIFFSERV_BESTEFFORT: u32 = 3;
+pub const CAKE_DIFFSERV_PRECEDENCE: u32 = 4;
+
+pub const CAKE_ACK_NONE: u32 = 0;
+pub const CAKE_ACK_FILTER: u32 = 1;
+pub const CAKE_ACK_AGGRESSIVE: u32 = 2;
+
+pub const CAKE_ATM_NONE: u32 = 0;
+pub const CAKE_ATM_ATM: u32 = 1;
+pub const CAKE_ATM_PTM: u32 = 2;
+
+pub const TCA_CODEL_UNSPEC: u16 = 0;
+pub const TCA_CODEL_TARGET: u16 = 1;
+pub const TCA_CODEL_LIMIT: u16 = 2;
+pub const TCA_CODEL_INTERVAL: u16 = 3;
+pub const TCA_CODEL_ECN: u16 = 4;
+pub const TCA_CODEL_CE_THRESHOLD: u16 = 5;
+
+pub const TCA_HFSC_UNSPEC: u16 = 0;
+pub const TCA_HFSC_RSC: u16 = 1;
+pub const TCA_HFSC_FSC: u16 = 2;
+pub const TCA_HFSC_USC: u16 = 3;
+
+pub const TCA_DRR_UNSPEC: u16 = 0;
+pub const TCA_DRR_QUANTUM: u16 = 1;
+
+pub const TCQ_ETS_MAX_BANDS: u8 = 16;
+pub const TCA_ETS_UNSPEC: u16 = 0;
+pub const TCA_ETS_NBANDS: u16 = 1;
+pub const TCA_ETS_NSTRICT: u16 = 2;
+pub const TCA_ETS_QUANTA: u16 = 3;
+pub const TCA_ETS_QUANTA_BAND: u16 = 4;
+pub const TCA_ETS_PRIOMAP: u16 = 5;
+pub const TCA_ETS_PRIOMAP_BAND: u16 = 6;
+
+pub const TC_QOPT_BITMASK: u8 = 15;
+pub const TC_QOPT_MAX_QUEUE: usize = 16;
+pub const TC_TAPRIO_CMD_SET_GATES: u8 = 0;
+pub const TC_TAPRIO_CMD_SET_AND_HOLD: u8 = 1;
+pub const TC_TAPRIO_CMD_SET_AND_RELEASE: u8 = 2;
+pub const TCA_TAPRIO_SCHED_ENTRY_UNSPEC: u16 = 0;
+pub const TCA_TAPRIO_SCHED_ENTRY_INDEX: u16 = 1;
+pub const TCA_TAPRIO_SCHED_ENTRY_CMD: u16 = 2;
+pub const TCA_TAPRIO_SCHED_ENTRY_GATE_MASK: u16 = 3;
+pub const TCA_TAPRIO_SCHED_ENTRY_INTERVAL: u16 = 4;
+pub const TCA_TAPRIO_SCHED_UNSPEC: u16 = 0;
+pub const TCA_TAPRIO_SCHED_ENTRY: u16 = 1;
+pub const TCA_TAPRIO_ATTR_FLAG_TXTIME_ASSIST: u32 = 1;
+pub const TCA_TAPRIO_ATTR_FLAG_FULL_OFFLOAD: u32 = 2;
+pub const TCA_TAPRIO_ATTR_UNSPEC: u16 = 0;
+pub const TCA_TAPRIO_ATTR_PRIOMAP: u16 = 1;
+pub const TCA_TAPRIO_ATTR_SCHED_ENTRY_LIST: u16 = 2;
+pub const TCA_TAPRIO_ATTR_SCHED_BASE_TIME: u16 = 3;
+pub const TCA_TAPRIO_ATTR_SCHED_SINGLE_ENTRY: u16 = 4;
+pub const TCA_TAPRIO_ATTR_SCHED_CLOCKID: u16 = 5;
+pub const TCA_TAPRIO_PAD: u16 = 6;
+pub const TCA_TAPRIO_ATTR_ADMIN_SCHED: u16 = 7;
+pub const TCA_TAPRIO_ATTR_SCHED_CYCLE_TIME: u16 = 8;
+pub const TCA_TAPRIO_ATTR_SCHED_CYCLE_TIME_EXTENSION: u16 = 9;
+pub const TCA_TAPRIO_ATTR_FLAGS: u16 = 10;
+pub const TCA_TAPRIO_ATTR_TXTIME_DELAY: u16 = 11;
+
+pub const TCA_PIE_UNSPEC: u16 = 0;
+pub const TCA_PIE_TARGET: u16 = 1;
+pub const TCA_PIE_LIMIT: u16 = 2;
+pub const TCA_PIE_TUPDATE: u16 = 3;
+pub const TCA_PIE_ALPHA: u16 = 4;
+pub const TCA_PIE_BETA: u16 = 5;
+pub const TCA_PIE_ECN: u16 = 6;
+pub const TCA_PIE_BYTEMODE: u16 = 7;
+pub const TCA_PIE_DQ_RATE_ESTIMATOR: u16 = 8;
+
+pub const MAX_DPS: u32 = 16;
+pub const TCA_GRED_UNSPEC: u16 = 0;
+pub const TCA_GRED_PARMS: u16 = 1;
+pub const TCA_GRED_STAB: u16 = 2;
+pub const TCA_GRED_DPS: u16 = 3;
+pub const TCA_GRED_MAX_P: u16 = 4;
+pub const TCA_GRED_LIMIT: u16 = 5;
+pub const TCA_GRED_VQ_LIST: u16 = 6;
+pub const TCA_GRED_VQ_ENTRY_UNSPEC: u16 = 0;
+pub const TCA_GRED_VQ_ENTRY: u16 = 1;
+pub const TCA_GRED_VQ_UNSPEC: u16 = 0;
+pub const TCA_GRED_VQ_PAD: u16 = 1;
+pub const TCA_GRED_VQ_DP: u16 = 2;
+pub const TCA_GRED_VQ_STAT_BYTES: u16 = 3;
+pub const TCA_GRED_VQ_STAT_PACKETS: u16 = 4;
+pub const TCA_GRED_VQ_STAT_BACKLOG: u16 = 5;
+pub const TCA_GRED_VQ_STAT_PROB_DROP: u16 = 6;
+pub const TCA_GRED_VQ_STAT_PROB_MARK: u16 = 7;
+pub const TCA_GRED_VQ_STAT_FORCED_DROP: u16 = 8;
+pub const TCA_GRED_VQ_STAT_FORCED_MARK: u16 = 9;
+pub const TCA_GRED_VQ_STAT_PDROP: u16 = 10;
+pub const TCA_GRED_VQ_STAT_OTHER: u16 = 11;
+pub const TCA_GRED_VQ_FLAGS: u16 = 12;
+
+pub const TCA_QFQ_UNSPEC: u16 = 0;
+pub const TCA_QFQ_WEIGHT: u16 = 1;
+pub const TCA_QFQ_LMAX: u16 = 2;
+
+pub const TC_H_MAJ_MASK: u32 = 0xffff_0000;
+pub const TC_H_MIN_MASK: u32 = 0x0000_ffff;
+pub const TC_H_UNSPEC: u32 = 0;
+pub const TC_H_ROOT: u32 = 0xffff_ffff;
+pub const TC_H_INGRESS: u32 = 0xffff_fff1;
+pub const TC_H_CLSACT: u32 = TC_H_INGRESS;
+pub const TC_H_MIN_PRIORITY: u32 = 0xffe0;
+pub const TC_H_MIN_INGRESS: u32 = 0xfff2;
+pub const TC_H_MIN_EGRESS: u32 = 0xfff3;
+
+pub const TCF_CBQ_LSS_BOUNDED: u8 = 1;
+pub const TCF_CBQ_LSS_ISOLATED: u8 = 2;
+pub const TCF_CBQ_LSS_FLAGS: u8 = 1;
+pub const TCF_CBQ_LSS_EWMA: u8 = 2;
+pub const TCF_CBQ_LSS_MAXIDLE: u8 = 4;
+pub const TCF_CBQ_LSS_MINIDLE: u8 = 8;
+pub const TCF_CBQ_LSS_OFFTIME: u8 = 0x10;
+pub const TCF_CBQ_LSS_AVPKT: u8 = 0x20;
+pub const TC_CBQ_MAXPRIO: u8 = 8;
+pub const TC_CBQ_MAXLEVEL: u8 = 8;
+pub const TC_CBQ_DEF_EWMA: u8 = 5;
+pub const TC_CBQ_OVL_CLASSIC: u8 = 0;
+pub const TC_CBQ_OVL_DELAY: u8 = 1;
+pub const TC_CBQ_OVL_LOWPRIO: u8 = 2;
+pub const TC_CBQ_OVL_DROP: u8 = 3;
+pub const TC_CBQ_OVL_RCLASSIC: u8 = 4;
+pub const TCA_CBQ_UNSPEC: u16 = 0;
+pub const TCA_CBQ_LSSOPT: u16 = 1;
+pub const TCA_CBQ_WRROPT: u16 = 2;
+pub const TCA_CBQ_FOPT: u16 = 3;
+pub const TCA_CBQ_OVL_STRATEGY: u16 = 4;
+pub const TCA_CBQ_RATE: u16 = 5;
+pub const TCA_CBQ_RTAB: u16 = 6;
+pub const TCA_CBQ_POLICE: u16 = 7;
+
+pub const TCA_CLS_FLAGS_SKIP_HW: u32 = 1;
+pub const TCA_CLS_FLAGS_SKIP_SW: u32 = 2;
+pub const TCA_CLS_FLAGS_IN_HW: u32 = 4;
+pub const TCA_CLS_FLAGS_NOT_IN_HW: u32 = 8;
+pub const TCA_CLS_FLAGS_VERBOSE: u32 = 16;
+pub const TC_U32_TERMINAL: u8 = 1;
+pub const TC_U32_OFFSET: u8 = 2;
+pub const TC_U32_VAROFFSET: u8 = 4;
+pub const TC_U32_EAT: u8 = 8;
+pub const TC_U32_MAXDEPTH: u8 = 8;
+pub const TCA_U32_UNSPEC: u16 = 0;
+pub const TCA_U32_CLASSID: u16 = 1;
+pub const TCA_U32_HASH: u16 = 2;
+pub const TCA_U32_LINK: u16 = 3;
+pub const TCA_U32_DIVISOR: u16 = 4;
+pub const TCA_U32_SEL: u16 = 5;
+pub const TCA_U32_POLICE: u16 = 6;
+pub const TCA_U32_ACT: u16 = 7;
+pub const TCA_U32_INDEV: u16 = 8;
+pub const TCA_U32_PCNT: u16 = 9;
+pub const TCA_U32_MARK: u16 = 10;
+pub const TCA_U32_FLAGS: u16 = 11;
+pub const TCA_U32_PAD: u16 = 12;
+
+pub const TCA_BPF_FLAG_ACT_DIRECT: u32 = 1;
+pub const TCA_BPF_UNSPEC: u16 = 0;
+pub const TCA_BPF_ACT: u16 = 1;
+pub const TCA_BPF_POLICE: u16 = 2;
+pub const TCA_BPF_CLASSID: u16 = 3;
+pub const TCA_BPF_OPS_LEN: u16 = 4;
+pub const TCA_BPF_OPS: u16 = 5;
+pub const TCA_BPF_FD: u16 = 6;
+pub const TCA_BPF_NAME: u16 = 7;
+pub const TCA_BPF_FLAGS: u16 = 8;
+pub const TCA_BPF_FLAGS_GEN: u16 = 9;
+pub const TCA_BPF_TAG: u16 = 10;
+pub const TCA_BPF_ID: u16 = 11;
+
+pub const TCA_BASIC_UNSPEC: u16 = 0;
+pub const TCA_BASIC_CLASSID: u16 = 1;
+pub const TCA_BASIC_EMATCHES: u16 = 2;
+pub const TCA_BASIC_ACT: u16 = 3;
+pub const TCA_BASIC_POLICE: u16 = 4;
+pub const TCA_BASIC_PCNT: u16 = 5;
+pub const TCA_BASIC_PAD: u16 = 6;
+
+pub const TCA_FW_UNSPEC: u16 = 0;
+pub const TCA_FW_CLASSID: u16 = 1;
+pub const TCA_FW_POLICE: u16 = 2;
+pub const TCA_FW_INDEV: u16 = 3;
+pub const TCA_FW_ACT: u16 = 4;
+pub const TCA_FW_MASK: u16 = 5;
+
+pub const FLOW_KEY_SRC: u32 = 0;
+pub const FLOW_KEY_DST: u32 = 1;
+pub const FLOW_KEY_PROTO: u32 = 2;
+pub const FLOW_KEY_PROTO_SRC: u32 = 3;
+pub const FLOW_KEY_PROTO_DST: u32 = 4;
+pub const FLOW_KEY_IIF: u32 = 5;
+pub const FLOW_KEY_PRIORITY: u32 = 6;
+pub const FLOW_KEY_MARK: u32 = 7;
+pub const FLOW_KEY_NFCT: u32 = 8;
+pub const FLOW_KEY_NFCT_SRC: u32 = 9;
+pub const FLOW_KEY_NFCT_DST: u32 = 10;
+pub const FLOW_KEY_NFCT_PROTO_SRC: u32 = 11;
+pub const FLOW_KEY_NFCT_PROTO_DST: u32 = 12;
+pub const FLOW_KEY_RTCLASSID: u32 = 13;
+pub const FLOW_KEY_SKUID: u32 = 14;
+pub const FLOW_KEY_SKGID: u32 = 15;
+pub const FLOW_KEY_VLAN_TAG: u32 = 16;
+pub const FLOW_KEY_RXHASH: u32 = 17;
+pub const FLOW_MODE_MAP: u32 = 0;
+pub const FLOW_MODE_HASH: u32 = 1;
+pub const TCA_FLOW_UNSPEC: u16 = 0;
+pub const TCA_FLOW_KEYS: u16 = 1;
+pub const TCA_FLOW_MODE: u16 = 2;
+pub const TCA_FLOW_BASECLASS: u16 = 3;
+pub const TCA_FLOW_RSHIFT: u16 = 4;
+pub const TCA_FLOW_ADDEND: u16 = 5;
+pub const TCA_FLOW_MASK: u16 = 6;
+pub const TCA_FLOW_XOR: u16 = 7;
+pub const TCA_FLOW_DIVISOR: u16 = 8;
+pub const TCA_FLOW_ACT: u16 = 9;
+pub const TCA_FLOW_POLICE: u16 = 10;
+pub const TCA_FLOW_EMATCHES: u16 = 11;
+pub const TCA_FLOW_PERTURB: u16 = 12;
+
+pub const TCA_FLOWER_UNSPEC: u16 = 0;
+pub const TCA_FLOWER_CLASSID: u16 = 1;
+pub const TCA_FLOWER_INDEV: u16 = 2;
+pub const TCA_FLOWER_ACT: u16 = 3;
+pub const TCA_FLOWER_KEY_ETH_DST: u16 = 4;
+pub const TCA_FLOWER_KEY_ETH_DST_MASK: u16 = 5;
+pub const TCA_FLOWER_KEY_ETH_SRC: u16 = 6;
+pub const TCA_FLOWER_KEY_ETH_SRC_MASK: u16 = 7;
+pub const TCA_FLOWER_KEY_ETH_TYPE: u16 = 8;
+pub const TCA_FLOWER_KEY_IP_PROTO: u16 = 9;
+pub const TCA_FLOWER_KEY_IPV4_SRC: u16 = 10;
+pub const TCA_FLOWER_KEY_IPV4_SRC_MASK: u16 = 11;
+pub const TCA_FLOWER_KEY_IPV4_DST: u16 = 12;
+pub const TCA_FLOWER_KEY_IPV4_DST_MASK: u16 = 13;
+pub const TCA_FLOWER_KEY_IPV6_SRC: u16 = 14;
+pub const TCA_FLOWER_KEY_IPV6_SRC_MASK: u16 = 15;
+pub const TCA_FLOWER_KEY_IPV6_DST: u16 = 16;
+pub const TCA_FLOWER_KEY_IPV6_DST_MASK: u16 = 17;
+pub const TCA_FLOWER_KEY_TCP_SRC: u16 = 18;
+pub const TCA_FLOWER_KEY_TCP_DST: u16 = 19;
+pub const TCA_FLOWER_KEY_UDP_SRC: u16 = 20;
+pub const TCA_FLOWER_KEY_UDP_DST: u16 = 21;
+pub const TCA_FLOWER_FLAGS: u16 = 22;
+pub const TCA_FLOWER_KEY_VLAN_ID: u16 = 23;
+pub const TCA_FLOWER_KEY_VLAN_PRIO: u16 = 24;
+pub const TCA_FLOWER_KEY_VLAN_ETH_TYPE: u16 = 25;
+pub const TCA_FLOWER_KEY_ENC_KEY_ID: u16 = 26;
+pub const TCA_FLOWER_KEY_ENC_IPV4_SRC: u16 = 27;
+pub const TCA_FLOWER_KEY_ENC_IPV4_SRC_MASK: u16 = 28;
+pub const TCA_FLOWER_KEY_ENC_IPV4_DST: u16 = 29;
+pub const TCA_FLOWER_KEY_ENC_IPV4_DST_MASK: u16 = 30;
+pub const TCA_FLOWER_KEY_ENC_IPV6_SRC: u16 = 31;
+pub const TCA_FLOWER_KEY_ENC_IPV6_SRC_MASK: u16 = 32;
+pub const TCA_FLOWER_KEY_ENC_IPV6_DST: u16 = 33;
+pub const TCA_FLOWER_KEY_ENC_IPV6_DST_MASK: u16 = 34;
+pub const TCA_FLOWER_KEY_TCP_SRC_MASK: u16 = 35;
+pub const TCA_FLOWER_KEY_TCP_DST_MASK: u16 = 36;
+pub const TCA_FLOWER_KEY_UDP_SRC_MASK: u16 = 37;
+pub const TCA_FLOWER_KEY_UDP_DST_MASK: u16 = 38;
+pub const TCA_FLOWER_KEY_ENC_UDP_SRC_PORT: u16 = 43;
+pub const TCA_FLOWER_KEY_ENC_UDP_SRC_PORT_MASK: u16 = 44;
+pub const TCA_FLOWER_KEY_ENC_UDP_DST_PORT: u16 = 45;
+pub const TCA_FLOWER_KEY_ENC_UDP_DST_PORT_MASK: u16 = 46;
+pub const TCA_FLOWER_KEY_FLAGS: u16 = 47;
+pub const TCA_FLOWER_KEY_FLAGS_MASK: u16 = 48;
+pub const TCA_FLOWER_KEY_ICMPV6_CODE: u16 = 53;
+pub const TCA_FLOWER_KEY_ICMPV6_CODE_MASK: u16 = 54;
+pub const TCA_FLOWER_KEY_ICMPV6_TYPE: u16 = 55;
+pub const TCA_FLOWER_KEY_ICMPV6_TYPE_MASK: u16 = 56;
+pub const TCA_FLOWER_KEY_MPLS_TTL: u16 = 67;
+pub const TCA_FLOWER_KEY_MPLS_BOS: u16 = 68;
+pub const TCA_FLOWER_KEY_MPLS_TC: u16 = 69;
+pub const TCA_FLOWER_KEY_MPLS_LABEL: u16 = 70;
+pub const TCA_FLOWER_KEY_IP_TOS: u16 = 73;
+pub const TCA_FLOWER_KEY_IP_TOS_MASK: u16 = 74;
+pub const TCA_FLOWER_KEY_IP_TTL: u16 = 75;
+pub const TCA_FLOWER_KEY_IP_TTL_MASK: u16 = 76;
+pub const TCA_FLOWER_KEY_ENC_IP_TOS: u16 = 80;
+pub const TCA_FLOWER_KEY_ENC_IP_TOS_MASK: u16 = 81;
+pub const TCA_FLOWER_KEY_ENC_IP_TTL: u16 = 82;
+pub const TCA_FLOWER_KEY_ENC_IP_TTL_MASK: u16 = 83;
+pub const TCA_FLOWER_IN_HW_COUNT: u16 = 86;
+pub const TCA_FLOWER_KEY_CT_STATE: u16 = 91;
+pub const TCA_FLOWER_KEY_CT_STATE_MASK: u16 = 92;
+pub const TCA_FLOWER_KEY_CT_ZONE: u16 = 93;
+pub const TCA_FLOWER_KEY_CT_ZONE_MASK: u16 = 94;
+pub const TCA_FLOWER_KEY_CT_MARK: u16 = 95;
+pub const TCA_FLOWER_KEY_CT_MARK_MASK: u16 = 96;
+pub const TCA_FLOWER_KEY_CT_LABELS: u16 = 97;
+pub const TCA_FLOWER_KEY_CT_LABELS_MASK: u16 = 98;
+pub const TCA_FLOWER_KEY_MPLS_OPTS: u16 = 99;
+
+pub const TCA_FLOWER_KEY_MPLS_OPTS_UNSPEC: u16 = 0;
+pub const TCA_FLOWER_KEY_MPLS_OPTS_LSE: u16 = 1;
+
+pub const TCA_FLOWER_KEY_MPLS_OPT_LSE_UNSPEC: u16 = 0;
+pub const TCA_FLOWER_KEY_MPLS_OPT_LSE_DEPTH: u16 = 1;
+pub const TCA_FLOWER_KEY_MPLS_OPT_LSE_TTL: u16 = 2;
+pub const TCA_FLOWER_KEY_MPLS_OPT_LSE_BOS: u16 = 3;
+pub const TCA_FLOWER_KEY_MPLS_OPT_LSE_TC: u16 = 4;
+pub const TCA_FLOWER_KEY_MPLS_OPT_LSE_LABEL: u16 = 5;
+
+pub const TCA_FLOWER_KEY_FLAGS_IS_FRAGMENT: u32 = 1;
+pub const TCA_FLOWER_KEY_FLAGS_FRAG_IS_FIRST: u32 = 2;
+
+pub const TCA_FLOWER_KEY_CT_FLAGS_NEW: u16 = 1;
+pub const TCA_FLOWER_KEY_CT_FLAGS_ESTABLISHED: u16 = 2;
+pub const TCA_FLOWER_KEY_CT_FLAGS_RELATED: u16 = 4;
+pub const TCA_FLOWER_KEY_CT_FLAGS_TRACKED: u16 = 8;
+pub const TCA_FLOWER_KEY_CT_FLAGS_INVALID: u16 = 16;
+pub const TCA_FLOWER_KEY_CT_FLAGS_REPLY: u16 = 32;
+
+pub const TCA_MATCHALL_UNSPEC: u16 = 0;
+pub const TCA_MATCHALL_CLASSID: u16 = 1;
+pub const TCA_MATCHALL_ACT: u16 = 2;
+pub const TCA_MATCHALL_FLAGS: u16 = 3;
+pub const TCA_MATCHALL_PCNT: u16 = 4;
+pub const TCA_MATCHALL_PAD: u16 = 5;
+
+pub const TC_ACT_UNSPEC: i32 = -1;
+pub const TC_ACT_OK: i32 = 0;
+pub const TC_ACT_RECLASSIFY: i32 = 1;
+pub const TC_ACT_SHOT: i32 = 2;
+pub const TC_ACT_PIPE: i32 = 3;
+pub const TC_ACT_STOLEN: i32 = 4;
+pub const TC_ACT_QUEUED: i32 = 5;
+pub const TC_ACT_REPEAT: i32 = 6;
+pub const TC_ACT_REDIRECT: i32 = 7;
+pub const TC_ACT_TRAP: i32 = 8;
+pub const TC_ACT_EXT_SHIFT: i32 = 28;
+pub const TC_ACT_JUMP: i32 = 1 << TC_ACT_EXT_SHIFT;
+pub const TC_ACT_GOTO_CHAIN: i32 = 2 << TC_ACT_EXT_SHIFT;
+pub const TCA_ACT_UNSPEC: u16 = 0;
+pub const TCA_ACT_KIND: u16 = 1;
+pub const TCA_ACT_OPTIONS: u16 = 2;
+pub const TCA_ACT_INDEX: u16 = 3;
+pub const TCA_ACT_STATS: u16 = 4;
+pub const TCA_ACT_PAD: u16 = 5;
+pub const TCA_ACT_COOKIE: u16 = 6;
+pub const TCA_ACT_FLAGS: u16 = 7;
+pub const TCA_ACT_HW_STATS: u16 = 8;
+pub const TCA_ACT_USED_HW_STATS: u16 = 9;
+pub const TCA_ACT_IN_HW_COUNT: u16 = 10;
+pub const TCA_EGRESS_REDIR: i32 = 1;
+pub const TCA_EGRESS_MIRROR: i32 = 2;
+pub const TCA_INGRESS_REDIR: i32 = 3;
+pub const TCA_INGRESS_MIRROR: i32 = 4;
+pub const TCA_MIRRED_UNSPEC: u16 = 0;
+pub const TCA_MIRRED_TM: u16 = 1;
+pub const TCA_MIRRED_PARMS: u16 = 2;
+pub const TCA_MIRRED_PAD: u16 = 3;
+
+pub const PGACT_NONE: u16 = 0;
+pub const PGACT_NETRAND: u16 = 1;
+pub const PGACT_DETERM: u16 = 2;
+pub const TCA_GACT_UNSPEC: u16 = 0;
+pub const TCA_GACT_TM: u16 = 1;
+pub const TCA_GACT_PARMS: u16 = 2;
+pub const TCA_GACT_PROB: u16 = 3;
+pub const TCA_GACT_PAD: u16 = 4;
+
+pub const TCA_VLAN_ACT_POP: i32 = 1;
+pub const TCA_VLAN_ACT_PUSH: i32 = 2;
+pub const TCA_VLAN_ACT_MODIFY: i32 = 3;
+pub const TCA_VLAN_ACT_POP_ETH: i32 = 4;
+pub const TCA_VLAN_ACT_PUSH_ETH: i32 = 5;
+pub const TCA_VLAN_UNSPEC: u16 = 0;
+pub const TCA_VLAN_TM: u16 = 1;
+pub const TCA_VLAN_PARMS: u16 = 2;
+pub const TCA_VLAN_PUSH_VLAN_ID: u16 = 3;
+pub const TCA_VLAN_PUSH_VLAN_PROTOCOL: u16 = 4;
+pub const TCA_VLAN_PAD: u16 = 5;
+pub const TCA_VLAN_PUSH_VLAN_PRIORITY: u16 = 6;
+pub const TCA_VLAN_PUSH_ETH_DST: u16 = 7;
+pub const TCA_VLAN_PUSH_ETH_SRC: u16 = 8;
+
+pub const TC_POLICE_UNSPEC: i32 = TC_ACT_UNSPEC;
+pub const TC_POLICE_OK: i32 = TC_ACT_OK;
+pub const TC_POLICE_RECLASSIFY: i32 = TC_ACT_RECLASSIFY;
+pub const TC_POLICE_SHOT: i32 = TC_ACT_SHOT;
+pub const TC_POLICE_PIPE: i32 = TC_ACT_PIPE;
+pub const TCA_POLICE_UNSPEC: u16 = 0;
+pub const TCA_POLICE_TBF: u16 = 1;
+pub const TCA_POLICE_RATE: u16 = 2;
+pub const TCA_POLICE_PEAKRATE: u16 = 3;
+pub const TCA_POLICE_AVRATE: u16 = 4;
+pub const TCA_POLICE_RESULT: u16 = 5;
+pub const TCA_POLICE_TM: u16 = 6;
+pub const TCA_POLICE_PAD: u16 = 7;
+pub const TCA_POLICE_RATE64: u16 = 8;
+pub const TCA_POLICE_PEAKRATE64: u16 = 9;
+pub const TCA_POLICE_PKTRATE64: u16 = 10;
+pub const TCA_POLICE_PKTBURST64: u16 = 11;
+
+pub const SKBEDIT_F_PRIORITY: u64 = 0x1;
+pub const SKBEDIT_F_QUEUE_MAPPING: u64 = 0x2;
+pub const SKBEDIT_F_MARK: u64 = 0x4;
+pub const SKBEDIT_F_PTYPE: u64 = 0x8;
+pub const SKBEDIT_F_MASK: u64 = 0x10;
+pub const SKBEDIT_F_INHERITDSFIELD: u64 = 0x20;
+pub const SKBEDIT_F_TXQ_SKBHASH: u64 = 0x40;
+pub const TCA_SKBEDIT_UNSPEC: u16 = 0;
+pub const TCA_SKBEDIT_TM: u16 = 1;
+pub const TCA_SKBEDIT_PARMS: u16 = 2;
+pub const TCA_SKBEDIT_PRIORITY: u16 = 3;
+pub const TCA_SKBEDIT_QUEUE_MAPPING: u16 = 4;
+pub const TCA_SKBEDIT_MARK: u16 = 5;
+pub const TCA_SKBEDIT_PAD: u16 = 6;
+pub const TCA_SKBEDIT_PTYPE: u16 = 7;
+pub const TCA_SKBEDIT_MASK: u16 = 8;
+pub const TCA_SKBEDIT_FLAGS: u16 = 9;
+pub const TCA_SKBEDIT_QUEUE_MAPPING_MAX: u16 = 10;
+
+pub const TCA_CSUM_UNSPEC: u16 = 0;
+pub const TCA_CSUM_PARMS: u16 = 1;
+pub const TCA_CSUM_TM: u16 = 2;
+pub const TCA_CSUM_PAD: u16 = 3;
+pub const TCA_CSUM_UPDATE_FLAG_IPV4HDR: u32 = 1;
+pub const TCA_CSUM_UPDATE_FLAG_ICMP: u32 = 2;
+pub const TCA_CSUM_UPDATE_FLAG_IGMP: u32 = 4;
+pub const TCA_CSUM_UPDATE_FLAG_TCP: u32 = 8;
+pub const TCA_CSUM_UPDATE_FLAG_UDP: u32 = 16;
+pub const TCA_CSUM_UPDATE_FLAG_UDPLITE: u32 = 32;
+pub const TCA_CSUM_UPDATE_FLAG_SCTP: u32 = 64;
+
+pub const TCA_TUNNEL_KEY_ACT_SET: i32 = 1;
+pub const TCA_TUNNEL_KEY_ACT_RELEASE: i32 = 2;
+pub const TCA_TUNNEL_KEY_UNSPEC: u16 = 0;
+pub const TCA_TUNNEL_KEY_TM: u16 = 1;
+pub const TCA_TUNNEL_KEY_PARMS: u16 = 2;
+pub const TCA_TUNNEL_KEY_ENC_IPV4_SRC: u16 = 3;
+pub const TCA_TUNNEL_KEY_ENC_IPV4_DST: u16 = 4;
+pub const TCA_TUNNEL_KEY_ENC_IPV6_SRC: u16 = 5;
+pub const TCA_TUNNEL_KEY_ENC_IPV6_DST: u16 = 6;
+pub const TCA_TUNNEL_KEY_ENC_KEY_ID: u16 = 7;
+pub const TCA_TUNNEL_KEY_PAD: u16 = 8;
+pub const TCA_TUNNEL_KEY_ENC_DST_PORT: u16 = 9;
+pub const TCA_TUNNEL_KEY_NO_CSUM: u16 = 10;
+pub const TCA_TUNNEL_KEY_ENC_OPTS: u16 = 11;
+pub const TCA_TUNNEL_KEY_ENC_TOS: u16 = 12;
+pub const TCA_TUNNEL_KEY_ENC_TTL: u16 = 13;
+pub const TCA_TUNNEL_KEY_NO_FRAG: u16 = 14;
+
+pub const TCA_SAMPLE_UNSPEC: u16 = 0;
+pub const TCA_SAMPLE_TM: u16 = 1;
+pub const TCA_SAMPLE_PARMS: u16 = 2;
+pub const TCA_SAMPLE_RATE: u16 = 3;
+pub const TCA_SAMPLE_TRUNC_SIZE: u16 = 4;
+pub const TCA_SAMPLE_PSAMPLE_GROUP: u16 = 5;
+pub const TCA_SAMPLE_PAD: u16 = 6;
+
+pub const TCA_CT_ACT_COMMIT: u16 = 1 << 0;
+pub const TCA_CT_ACT_FORCE: u16 = 1 << 1;
+pub const TCA_CT_ACT_CLEAR: u16 = 1 << 2;
+pub const TCA_CT_ACT_NAT: u16 = 1 << 3;
+pub const TCA_CT_ACT_NAT_SRC: u16 = 1 << 4;
+pub const TCA_CT_ACT_NAT_DST: u16 = 1 << 5;
+pub const TCA_CT_UNSPEC: u16 = 0;
+pub const TCA_CT_PARMS: u16 = 1;
+pub const TCA_CT_TM: u16 = 2;
+pub const TCA_CT_ACTION: u16 = 3;
+pub const TCA_CT_ZONE: u16 = 4;
+pub const TCA_CT_MARK: u16 = 5;
+pub const TCA_CT_MARK_MASK: u16 = 6;
+pub const TCA_CT_LABELS: u16 = 7;
+pub const TCA_CT_LABELS_MASK: u16 = 8;
+pub const TCA_CT_NAT_IPV4_MIN: u16 = 9;
+pub const TCA_CT_NAT_IPV4_MAX: u16 = 10;
+pub const TCA_CT_NAT_IPV6_MIN: u16 = 11;
+pub const TCA_CT_NAT_IPV6_MAX: u16 = 12;
+pub const TCA_CT_NAT_PORT_MIN: u16 = 13;
+pub const TCA_CT_NAT_PORT_MAX: u16 = 14;
+pub const TCA_CT_PAD: u16 = 15;
+pub const TCA_CT_HELPER_NAME: u16 = 16;
+pub const TCA_CT_HELPER_FAMILY: u16 = 17;
+pub const TCA_CT_HELPER_PROTO: u16 = 18;
+
+pub const TCA_MPLS_ACT_POP: i32 = 1;
+pub const TCA_MPLS_ACT_PUSH: i32 = 2;
+pub const TCA_MPLS_ACT_MODIFY: i32 = 3;
+pub const TCA_MPLS_ACT_DEC_TTL: i32 = 4;
+pub const TCA_MPLS_ACT_MAC_PUSH: i32 = 5;
+pub const TCA_MPLS_UNSPEC: u16 = 0;
+pub const TCA_MPLS_TM: u16 = 1;
+pub const TCA_MPLS_PARMS: u16 = 2;
+pub const TCA_MPLS_PAD: u16 = 3;
+pub const TCA_MPLS_PROTO: u16 = 4;
+pub const TCA_MPLS_LABEL: u16 = 5;
+pub const TCA_MPLS_TC: u16 = 6;
+pub const TCA_MPLS_TTL: u16 = 7;
+pub const TCA_MPLS_BOS: u16 = 8;
+
+pub const TCA_PEDIT_UNSPEC: u16 = 0;
+pub const TCA_PEDIT_TM: u16 = 1;
+pub const TCA_PEDIT_PARMS: u16 = 2;
+pub const TCA_PEDIT_PAD: u16 = 3;
+pub const TCA_PEDIT_PARMS_EX: u16 = 4;
+pub const TCA_PEDIT_KEYS_EX: u16 = 5;
+pub const TCA_PEDIT_KEY_EX: u16 = 6;
+pub const TCA_PEDIT_KEY_EX_HTYPE: u16 = 1;
+pub const TCA_PEDIT_KEY_EX_CMD: u16 = 2;
+pub const TCA_PEDIT_KEY_EX_HDR_TYPE_NETWORK: u16 = 0;
+pub const TCA_PEDIT_KEY_EX_HDR_TYPE_ETH: u16 = 1;
+pub const TCA_PEDIT_KEY_EX_HDR_TYPE_IP4: u16 = 2;
+pub const TCA_PEDIT_KEY_EX_HDR_TYPE_IP6: u16 = 3;
+pub const TCA_PEDIT_KEY_EX_HDR_TYPE_TCP: u16 = 4;
+pub const TCA_PEDIT_KEY_EX_HDR_TYPE_UDP: u16 = 5;
+pub const TCA_PEDIT_KEY_EX_CMD_SET: u16 = 0;
+pub const TCA_PEDIT_KEY_EX_CMD_ADD: u16 = 1;
+
 pub const NDTA_UNSPEC: u16 = 0;
 pub const NDTA_NAME: u16 = 1;
 pub const NDTA_THRESH1: u16 = 2;
@@ -368,6 +1068,82 @@ pub const RTA_EXPIRES: u16 = 23;
 pub const RTA_PAD: u16 = 24;
 pub const RTA_UID: u16 = 25;
 pub const RTA_TTL_PROPAGATE: u16 = 26;
+pub const RTA_IP_PROTO: u16 = 27;
+pub const RTA_SPORT: u16 = 28;
+pub const RTA_DPORT: u16 = 29;
+pub const RTA_NH_ID: u16 = 30;
+
+/// see `https://github.com/torvalds/linux/blob/master/include/uapi/linux/lwtunnel.h`
+pub const LWTUNNEL_ENCAP_NONE: u16 = 0;
+pub const LWTUNNEL_ENCAP_MPLS: u16 = 1;
+pub const LWTUNNEL_ENCAP_IP: u16 = 2;
+pub const LWTUNNEL_ENCAP_ILA: u16 = 3;
+pub const LWTUNNEL_ENCAP_IP6: u16 = 4;
+pub const LWTUNNEL_ENCAP_SEG6: u16 = 5;
+pub const LWTUNNEL_ENCAP_BPF: u16 = 6;
+pub const LWTUNNEL_ENCAP_SEG6_LOCAL: u16 = 7;
+pub const LWTUNNEL_ENCAP_RPL: u16 = 8;
+
+/// see `https://github.com/torvalds/linux/blob/master/include/uapi/linux/mpls_iptunnel.h`
+pub const MPLS_IPTUNNEL_UNSPEC: u16 = 0;
+pub const MPLS_IPTUNNEL_DST: u16 = 1;
+pub const MPLS_IPTUNNEL_TTL: u16 = 2;
+
+/// see `https://github.com/torvalds/linux/blob/master/include/uapi/linux/seg6_iptunnel.h`
+pub const SEG6_IPTUNNEL_UNSPEC: u16 = 0;
+pub const SEG6_IPTUNNEL_SRH: u16 = 1;
+
+pub const SEG6_IPTUN_MODE_INLINE: i32 = 0;
+pub const SEG6_IPTUN_MODE_ENCAP: i32 = 1;
+pub const SEG6_IPTUN_MODE_L2ENCAP: i32 = 2;
+pub const SEG6_IPTUN_MODE_ENCAP_RED: i32 = 3;
+pub const SEG6_IPTUN_MODE_L2ENCAP_RED: i32 = 4;
+
+/// Segment routing header type
+pub const IPV6_SRCRT_TYPE_4: u8 = 4;
+
+/// see `https://github.com/torvalds/linux/blob/master/include/uapi/linux/seg6_local.h`
+pub const SEG6_LOCAL_UNSPEC: u16 = 0;
+pub const SEG6_LOCAL_ACTION: u16 = 1;
+pub const SEG6_LOCAL_SRH: u16 = 2;
+pub const SEG6_LOCAL_TABLE: u16 = 3;
+pub const SEG6_LOCAL_NH4: u16 = 4;
+pub const SEG6_LOCAL_NH6: u16 = 5;
+pub const SEG6_LOCAL_IIF: u16 = 6;
+pub const SEG6_LOCAL_OIF: u16 = 7;
+pub const SEG6_LOCAL_BPF: u16 = 8;
+pub const SEG6_LOCAL_VRFTABLE: u16 = 9;
+pub const SEG6_LOCAL_COUNTERS: u16 = 10;
+
+pub const SEG6_LOCAL_ACTION_UNSPEC: u32 = 0;
+/// Node segment
+pub const SEG6_LOCAL_ACTION_END: u32 = 1;
+/// Adjacency segment (IPv6 cross-connect)
+pub const SEG6_LOCAL_ACTION_END_X: u32 = 2;
+/// Lookup of the next segment in a table
+pub const SEG6_LOCAL_ACTION_END_T: u32 = 3;
+/// Decapsulation and L2 cross-connect
+pub const SEG6_LOCAL_ACTION_END_DX2: u32 = 4;
+/// Decapsulation and IPv6 cross-connect
+pub const SEG6_LOCAL_ACTION_END_DX6: u32 = 5;
+/// Decapsulation and IPv4 cross-connect
+pub const SEG6_LOCAL_ACTION_END_DX4: u32 = 6;
+/// Decapsulation and IPv6 table lookup
+pub const SEG6_LOCAL_ACTION_END_DT6: u32 = 7;
+/// Decapsulation and IPv4 table lookup
+pub const SEG6_LOCAL_ACTION_END_DT4: u32 = 8;
+/// Binding segment with insertion of a SRH
+pub const SEG6_LOCAL_ACTION_END_B6: u32 = 9;
+/// Binding segment with encapsulation in an outer IPv6 header
+pub const SEG6_LOCAL_ACTION_END_B6_ENCAP: u32 = 10;
+/// Binding segment to a MPLS label stack
+pub const SEG6_LOCAL_ACTION_END_BM: u32 = 11;
+pub const SEG6_LOCAL_ACTION_END_S: u32 = 12;
+pub const SEG6_LOCAL_ACTION_END_AS: u32 = 13;
+pub const SEG6_LOCAL_ACTION_END_AM: u32 = 14;
+pub const SEG6_LOCAL_ACTION_END_BPF: u32 = 15;
+/// Decapsulation and IPv4 or IPv6 table lookup
+pub const SEG6_LOCAL_ACTION_END_DT46: u32 = 16;
 
 pub const RTAX_UNSPEC: u16 = 0;
 pub const RTAX_LOCK: u16 = 1;
@@ -466,6 +1242,13 @@ pub const IFLA_VLAN_FLAGS: u16 = 2;
 pub const IFLA_VLAN_EGRESS_QOS: u16 = 3;
 pub const IFLA_VLAN_INGRESS_QOS: u16 = 4;
 pub const IFLA_VLAN_PROTOCOL: u16 = 5;
+pub const IFLA_VLAN_QOS_UNSPEC: u16 = 0;
+pub const IFLA_VLAN_QOS_MAPPING: u16 = 1;
+pub const VLAN_FLAG_REORDER_HDR: u32 = 0x1;
+pub const VLAN_FLAG_GVRP: u32 = 0x2;
+pub const VLAN_FLAG_LOOSE_BINDING: u32 = 0x4;
+pub const VLAN_FLAG_MVRP: u32 = 0x8;
+pub const VLAN_FLAG_BRIDGE_BINDING: u32 = 0x10;
 pub const IFLA_VRF_UNSPEC: u16 = 0;
 pub const IFLA_VRF_TABLE: u16 = 1;
 pub const IFLA_IPVLAN_UNSPEC: u16 = 0;
@@ -612,6 +1395,11 @@ pub const IFLA_PROP_LIST: u16 = 52;
 pub const IFLA_ALT_IFNAME: u16 = 53;
 pub const IFLA_PERM_ADDRESS: u16 = 54;
 pub const IFLA_PROTO_DOWN_REASON: u16 = 55;
+pub const IFLA_PARENT_DEV_NAME: u16 = 56;
+pub const IFLA_PARENT_DEV_BUS_NAME: u16 = 57;
+pub const IFLA_GRO_MAX_SIZE: u16 = 58;
+pub const IFLA_TSO_MAX_SIZE: u16 = 59;
+pub const IFLA_TSO_MAX_SEGS: u16 = 60;
 pub const IFLA_INET_UNSPEC: u16 = 0;
 pub const IFLA_INET_CONF: u16 = 1;
 pub const IFLA_INET6_UNSPEC: u16 = 0;
@@ -769,12 +1557,13 @@ pub const FIB_RULE_FIND_SADDR: u32 = 10000;
 // pub const PORT_PROFILE_MAX: int = 40;
 // pub const PORT_UUID_MAX: int = 16;
 // pub const PORT_SELF_VF: int = -1;
-// pub const XDP_FLAGS_UPDATE_IF_NOEXIST: int = 1;
-// pub const XDP_FLAGS_SKB_MODE: int = 2;
-// pub const XDP_FLAGS_DRV_MODE: int = 4;
-// pub const XDP_FLAGS_HW_MODE: int = 8;
-// pub const XDP_FLAGS_MODES: int = 14;
-// pub const XDP_FLAGS_MASK: int = 15;
+pub const XDP_FLAGS_UPDATE_IF_NOEXIST: u32 = 1;
+pub const XDP_FLAGS_SKB_MODE: u32 = 2;
+pub const XDP_FLAGS_DRV_MODE: u32 = 4;
+pub const XDP_FLAGS_HW_MODE: u32 = 8;
+pub const XDP_FLAGS_REPLACE: u32 = 16;
+pub const XDP_FLAGS_MODES: u32 = XDP_FLAGS_SKB_MODE | XDP_FLAGS_DRV_MODE | XDP_FLAGS_HW_MODE;
+pub const XDP_FLAGS_MASK: u32 = XDP_FLAGS_UPDATE_IF_NOEXIST | XDP_FLAGS_MODES | XDP_FLAGS_REPLACE;
 
 pub const IFA_F_SECONDARY: u32 = 1;
 pub const IFA_F_TEMPORARY: u32 = 1;
@@ -804,11 +1593,11 @@ pub const RTNH_F_UNRESOLVED: u8 = 32;
 // pub const RTNH_COMPARE_MASK: int = 25;
 // pub const RTNH_ALIGNTO: int = 4;
 // pub const RTNETLINK_HAVE_PEERINFO: int = 1;
-// pub const RTAX_FEATURE_ECN: int = 1;
-// pub const RTAX_FEATURE_SACK: int = 2;
-// pub const RTAX_FEATURE_TIMESTAMP: int = 4;
-// pub const RTAX_FEATURE_ALLFRAG: int = 8;
-// pub const RTAX_FEATURE_MASK: int = 15;
+pub const RTAX_FEATURE_ECN: u32 = 1;
+pub const RTAX_FEATURE_SACK: u32 = 2;
+pub const RTAX_FEATURE_TIMESTAMP: u32 = 4;
+pub const RTAX_FEATURE_ALLFRAG: u32 = 8;
+pub const RTAX_FEATURE_MASK: u32 = 15;
 // #[allow(overflowing_literals)]
 // pub const TCM_IFINDEX_MAGIC_BLOCK: int = 0xffff_ffff;
 // pub const TCA_FLAG_LARGE_DUMP_ON: int = 1;
@@ -829,42 +1618,46 @@ pub const RTEXT_FILTER_SKIP_STATS: u32 = 8;
 // pub const BRIDGE_MODE_UNSPEC: int = 0;
 // pub const BRIDGE_MODE_HAIRPIN: int = 1;
 //
-// pub const IFLA_BRPORT_UNSPEC: int = 0;
-// pub const IFLA_BRPORT_STATE: int = 1;
-// pub const IFLA_BRPORT_PRIORITY: int = 2;
-// pub const IFLA_BRPORT_COST: int = 3;
-// pub const IFLA_BRPORT_MODE: int = 4;
-// pub const IFLA_BRPORT_GUARD: int = 5;
-// pub const IFLA_BRPORT_PROTECT: int = 6;
-// pub const IFLA_BRPORT_FAST_LEAVE: int = 7;
-// pub const IFLA_BRPORT_LEARNING: int = 8;
-// pub const IFLA_BRPORT_UNICAST_FLOOD: int = 9;
-// pub const IFLA_BRPORT_PROXYARP: int = 10;
-// pub const IFLA_BRPORT_LEARNING_SYNC: int = 11;
-// pub const IFLA_BRPORT_PROXYARP_WIFI: int = 12;
-// pub const IFLA_BRPORT_ROOT_ID: int = 13;
-// pub const IFLA_BRPORT_BRIDGE_ID: int = 14;
-// pub const IFLA_BRPORT_DESIGNATED_PORT: int = 15;
-// pub const IFLA_BRPORT_DESIGNATED_COST: int = 16;
-// pub const IFLA_BRPORT_ID: int = 17;
-// pub const IFLA_BRPORT_NO: int = 18;
-// pub const IFLA_BRPORT_TOPOLOGY_CHANGE_ACK: int = 19;
-// pub const IFLA_BRPORT_CONFIG_PENDING: int = 20;
-// pub const IFLA_BRPORT_MESSAGE_AGE_TIMER: int = 21;
-// pub const IFLA_BRPORT_FORWARD_DELAY_TIMER: int = 22;
-// pub const IFLA_BRPORT_HOLD_TIMER: int = 23;
-// pub const IFLA_BRPORT_FLUSH: int = 24;
-// pub const IFLA_BRPORT_MULTICAST_ROUTER: int = 25;
-// pub const IFLA_BRPORT_PAD: int = 26;
-// pub const IFLA_BRPORT_MCAST_FLOOD: int = 27;
-// pub const IFLA_BRPORT_MCAST_TO_UCAST: int = 28;
-// pub const IFLA_BRPORT_VLAN_TUNNEL: int = 29;
-// pub const IFLA_BRPORT_BCAST_FLOOD: int = 30;
-// pub const IFLA_BRPORT_GROUP_FWD_MASK: int = 31;
-// pub const IFLA_BRPORT_NEIGH_SUPPRESS: int = 32;
-//
-// pub const IFLA_VLAN_QOS_UNSPEC: int = 0;
-// pub const IFLA_VLAN_QOS_MAPPING: int = 1;
+pub const IFLA_BRPORT_UNSPEC: u16 = 0;
+pub const IFLA_BRPORT_STATE: u16 = 1;
+pub const IFLA_BRPORT_PRIORITY: u16 = 2;
+pub const IFLA_BRPORT_COST: u16 = 3;
+pub const IFLA_BRPORT_MODE: u16 = 4;
+pub const IFLA_BRPORT_GUARD: u16 = 5;
+pub const IFLA_BRPORT_PROTECT: u16 = 6;
+pub const IFLA_BRPORT_FAST_LEAVE: u16 = 7;
+pub const IFLA_BRPORT_LEARNING: u16 = 8;
+pub const IFLA_BRPORT_UNICAST_FLOOD: u16 = 9;
+pub const IFLA_BRPORT_PROXYARP: u16 = 10;
+pub const IFLA_BRPORT_LEARNING_SYNC: u16 = 11;
+pub const IFLA_BRPORT_PROXYARP_WIFI: u16 = 12;
+pub const IFLA_BRPORT_ROOT_ID: u16 = 13;
+pub const IFLA_BRPORT_BRIDGE_ID: u16 = 14;
+pub const IFLA_BRPORT_DESIGNATED_PORT: u16 = 15;
+pub const IFLA_BRPORT_DESIGNATED_COST: u16 = 16;
+pub const IFLA_BRPORT_ID: u16 = 17;
+pub const IFLA_BRPORT_NO: u16 = 18;
+pub const IFLA_BRPORT_TOPOLOGY_CHANGE_ACK: u16 = 19;
+pub const IFLA_BRPORT_CONFIG_PENDING: u16 = 20;
+pub const IFLA_BRPORT_MESSAGE_AGE_TIMER: u16 = 21;
+pub const IFLA_BRPORT_FORWARD_DELAY_TIMER: u16 = 22;
+pub const IFLA_BRPORT_HOLD_TIMER: u16 = 23;
+pub const IFLA_BRPORT_FLUSH: u16 = 24;
+pub const IFLA_BRPORT_MULTICAST_ROUTER: u16 = 25;
+pub const IFLA_BRPORT_PAD: u16 = 26;
+pub const IFLA_BRPORT_MCAST_FLOOD: u16 = 27;
+pub const IFLA_BRPORT_MCAST_TO_UCAST: u16 = 28;
+pub const IFLA_BRPORT_VLAN_TUNNEL: u16 = 29;
+pub const IFLA_BRPORT_BCAST_FLOOD: u16 = 30;
+pub const IFLA_BRPORT_GROUP_FWD_MASK: u16 = 31;
+pub const IFLA_BRPORT_NEIGH_SUPPRESS: u16 = 32;
+pub const IFLA_BRPORT_ISOLATED: u16 = 33;
+
+pub const BR_STATE_DISABLED: u8 = 0;
+pub const BR_STATE_LISTENING: u8 = 1;
+pub const BR_STATE_LEARNING: u8 = 2;
+pub const BR_STATE_FORWARDING: u8 = 3;
+pub const BR_STATE_BLOCKING: u8 = 4;
 //
 // pub const IFLA_MACVLAN_UNSPEC: int = 0;
 // pub const IFLA_MACVLAN_MODE: int = 1;
@@ -954,6 +1747,31 @@ pub const IFLA_VXLAN_GPE: u16 = 27;
 pub const IFLA_VXLAN_TTL_INHERIT: u16 = 28;
 pub const IFLA_VXLAN_DF: u16 = 29;
 pub const __IFLA_VXLAN_MAX: u16 = 30;
+pub const IFLA_GRE_UNSPEC: u16 = 0;
+pub const IFLA_GRE_LINK: u16 = 1;
+pub const IFLA_GRE_IFLAGS: u16 = 2;
+pub const IFLA_GRE_OFLAGS: u16 = 3;
+pub const IFLA_GRE_IKEY: u16 = 4;
+pub const IFLA_GRE_OKEY: u16 = 5;
+pub const IFLA_GRE_LOCAL: u16 = 6;
+pub const IFLA_GRE_REMOTE: u16 = 7;
+pub const IFLA_GRE_TTL: u16 = 8;
+pub const IFLA_GRE_TOS: u16 = 9;
+pub const IFLA_GRE_PMTUDISC: u16 = 10;
+pub const IFLA_GRE_ENCAP_LIMIT: u16 = 11;
+pub const IFLA_GRE_FLOWINFO: u16 = 12;
+pub const IFLA_GRE_FLAGS: u16 = 13;
+pub const IFLA_GRE_ENCAP_TYPE: u16 = 14;
+pub const IFLA_GRE_ENCAP_FLAGS: u16 = 15;
+pub const IFLA_GRE_ENCAP_SPORT: u16 = 16;
+pub const IFLA_GRE_ENCAP_DPORT: u16 = 17;
+pub const IFLA_GRE_COLLECT_METADATA: u16 = 18;
+pub const IFLA_GRE_IGNORE_DF: u16 = 19;
+pub const IFLA_GRE_FWMARK: u16 = 20;
+pub const GRE_CSUM: u16 = 0x8000;
+pub const GRE_ROUTING: u16 = 0x4000;
+pub const GRE_KEY: u16 = 0x2000;
+pub const GRE_SEQ: u16 = 0x1000;
 //
 // pub const IFLA_GENEVE_UNSPEC: int = 0;
 // pub const IFLA_GENEVE_ID: int = 1;
@@ -980,34 +1798,44 @@ pub const __IFLA_VXLAN_MAX: u16 = 30;
 // pub const IFLA_GTP_PDP_HASHSIZE: int = 3;
 // pub const IFLA_GTP_ROLE: int = 4;
 //
-// pub const IFLA_BOND_UNSPEC: int = 0;
-// pub const IFLA_BOND_MODE: int = 1;
-// pub const IFLA_BOND_ACTIVE_SLAVE: int = 2;
-// pub const IFLA_BOND_MIIMON: int = 3;
-// pub const IFLA_BOND_UPDELAY: int = 4;
-// pub const IFLA_BOND_DOWNDELAY: int = 5;
-// pub const IFLA_BOND_USE_CARRIER: int = 6;
-// pub const IFLA_BOND_ARP_INTERVAL: int = 7;
-// pub const IFLA_BOND_ARP_IP_TARGET: int = 8;
-// pub const IFLA_BOND_ARP_VALIDATE: int = 9;
-// pub const IFLA_BOND_ARP_ALL_TARGETS: int = 10;
-// pub const IFLA_BOND_PRIMARY: int = 11;
-// pub const IFLA_BOND_PRIMARY_RESELECT: int = 12;
-// pub const IFLA_BOND_FAIL_OVER_MAC: int = 13;
-// pub const IFLA_BOND_XMIT_HASH_POLICY: int = 14;
-// pub const IFLA_BOND_RESEND_IGMP: int = 15;
-// pub const IFLA_BOND_NUM_PEER_NOTIF: int = 16;
-// pub const IFLA_BOND_ALL_SLAVES_ACTIVE: int = 17;
-// pub const IFLA_BOND_MIN_LINKS: int = 18;
-// pub const IFLA_BOND_LP_INTERVAL: int = 19;
-// pub const IFLA_BOND_PACKETS_PER_SLAVE: int = 20;
-// pub const IFLA_BOND_AD_LACP_RATE: int = 21;
-// pub const IFLA_BOND_AD_SELECT: int = 22;
-// pub const IFLA_BOND_AD_INFO: int = 23;
-// pub const IFLA_BOND_AD_ACTOR_SYS_PRIO: int = 24;
-// pub const IFLA_BOND_AD_USER_PORT_KEY: int = 25;
-// pub const IFLA_BOND_AD_ACTOR_SYSTEM: int = 26;
-// pub const IFLA_BOND_TLB_DYNAMIC_LB: int = 27;
+pub const IFLA_BOND_UNSPEC: u16 = 0;
+pub const IFLA_BOND_MODE: u16 = 1;
+pub const IFLA_BOND_ACTIVE_SLAVE: u16 = 2;
+pub const IFLA_BOND_MIIMON: u16 = 3;
+pub const IFLA_BOND_UPDELAY: u16 = 4;
+pub const IFLA_BOND_DOWNDELAY: u16 = 5;
+pub const IFLA_BOND_USE_CARRIER: u16 = 6;
+pub const IFLA_BOND_ARP_INTERVAL: u16 = 7;
+pub const IFLA_BOND_ARP_IP_TARGET: u16 = 8;
+pub const IFLA_BOND_ARP_VALIDATE: u16 = 9;
+pub const IFLA_BOND_ARP_ALL_TARGETS: u16 = 10;
+pub const IFLA_BOND_PRIMARY: u16 = 11;
+pub const IFLA_BOND_PRIMARY_RESELECT: u16 = 12;
+pub const IFLA_BOND_FAIL_OVER_MAC: u16 = 13;
+pub const IFLA_BOND_XMIT_HASH_POLICY: u16 = 14;
+pub const IFLA_BOND_RESEND_IGMP: u16 = 15;
+pub const IFLA_BOND_NUM_PEER_NOTIF: u16 = 16;
+pub const IFLA_BOND_ALL_SLAVES_ACTIVE: u16 = 17;
+pub const IFLA_BOND_MIN_LINKS: u16 = 18;
+pub const IFLA_BOND_LP_INTERVAL: u16 = 19;
+pub const IFLA_BOND_PACKETS_PER_SLAVE: u16 = 20;
+pub const IFLA_BOND_AD_LACP_RATE: u16 = 21;
+pub const IFLA_BOND_AD_SELECT: u16 = 22;
+pub const IFLA_BOND_AD_INFO: u16 = 23;
+pub const IFLA_BOND_AD_ACTOR_SYS_PRIO: u16 = 24;
+pub const IFLA_BOND_AD_USER_PORT_KEY: u16 = 25;
+pub const IFLA_BOND_AD_ACTOR_SYSTEM: u16 = 26;
+pub const IFLA_BOND_TLB_DYNAMIC_LB: u16 = 27;
+pub const IFLA_BOND_PEER_NOTIF_DELAY: u16 = 28;
+pub const IFLA_BOND_AD_LACP_ACTIVE: u16 = 29;
+pub const IFLA_BOND_MISSED_MAX: u16 = 30;
+pub const BOND_MODE_ROUNDROBIN: u8 = 0;
+pub const BOND_MODE_ACTIVEBACKUP: u8 = 1;
+pub const BOND_MODE_XOR: u8 = 2;
+pub const BOND_MODE_BROADCAST: u8 = 3;
+pub const BOND_MODE_8023AD: u8 = 4;
+pub const BOND_MODE_TLB: u8 = 5;
+pub const BOND_MODE_ALB: u8 = 6;
 //
 // pub const IFLA_BOND_AD_INFO_UNSPEC: int = 0;
 // pub const IFLA_BOND_AD_INFO_AGGREGATOR: int = 1;
@@ -1049,6 +1877,10 @@ pub const __IFLA_VXLAN_MAX: u16 = 30;
 // pub const NDUSEROPT_UNSPEC: int = 0;
 // pub const NDUSEROPT_SRCADDR: int = 1;
 //
+
+// rtnetlink multicast groups. These are group numbers, to be joined with
+// `Socket::add_membership`, while the legacy `RTMGRP_*` constants of rtnetlink are bit masks for
+// the groups of a `SocketAddr`.
 pub const RTNLGRP_NONE: u32 = 0;
 pub const RTNLGRP_LINK: u32 = 1;
 pub const RTNLGRP_NOTIFY: u32 = 2;
@@ -1081,6 +1913,7 @@ pub const RTNLGRP_NSID: u32 = 28;
 pub const RTNLGRP_MPLS_NETCONF: u32 = 29;
 pub const RTNLGRP_IPV4_MROUTE_R: u32 = 30;
 pub const RTNLGRP_IPV6_MROUTE_R: u32 = 31;
+pub const RTNLGRP_NEXTHOP: u32 = 32;
 //
 // pub const IFLA_VF_LINK_STATE_AUTO: int = 0;
 // pub const IFLA_VF_LINK_STATE_ENABLE: int = 1;
@@ -1155,17 +1988,22 @@ pub const RTNLGRP_IPV6_MROUTE_R: u32 = 31;
 //
 // pub const IFLA_OFFLOAD_XSTATS_UNSPEC: int = 0;
 // pub const IFLA_OFFLOAD_XSTATS_CPU_HIT: int = 1;
-//
-// pub const XDP_ATTACHED_NONE: int = 0;
-// pub const XDP_ATTACHED_DRV: int = 1;
-// pub const XDP_ATTACHED_SKB: int = 2;
-// pub const XDP_ATTACHED_HW: int = 3;
-
-pub const IFLA_XDP_UNSPEC: u32 = 0;
-pub const IFLA_XDP_FD: u32 = 1;
-pub const IFLA_XDP_ATTACHED: u32 = 2;
-pub const IFLA_XDP_FLAGS: u32 = 3;
-pub const IFLA_XDP_PROG_ID: u32 = 4;
+
+pub const XDP_ATTACHED_NONE: u8 = 0;
+pub const XDP_ATTACHED_DRV: u8 = 1;
+pub const XDP_ATTACHED_SKB: u8 = 2;
+pub const XDP_ATTACHED_HW: u8 = 3;
+pub const XDP_ATTACHED_MULTI: u8 = 4;
+
+pub const IFLA_XDP_UNSPEC: u16 = 0;
+pub const IFLA_XDP_FD: u16 = 1;
+pub const IFLA_XDP_ATTACHED: u16 = 2;
+pub const IFLA_XDP_FLAGS: u16 = 3;
+pub const IFLA_XDP_PROG_ID: u16 = 4;
+pub const IFLA_XDP_DRV_PROG_ID: u16 = 5;
+pub const IFLA_XDP_SKB_PROG_ID: u16 = 6;
+pub const IFLA_XDP_HW_PROG_ID: u16 = 7;
+pub const IFLA_XDP_EXPECTED_FD: u16 = 8;
 
 // pub const IFLA_EVENT_NONE: int = 0;
 // pub const IFLA_EVENT_REBOOT: int = 1;
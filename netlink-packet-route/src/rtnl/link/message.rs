@@ -59,8 +59,12 @@ impl<'a, T: AsRef<[u8]> + 'a> ParseableParametrized<LinkMessageBuffer<&'a T>, u8
 mod test {
     use crate::{
         constants::*,
-        nlas::link::{Nla, State},
-        traits::{Emitable, ParseableParametrized},
+        nlas::{
+            link::{Info, InfoKind, LinkXdp, Nla, State, Stats64, XdpAttached},
+            NlaBuffer,
+            NlasIterator,
+        },
+        traits::{Emitable, Parseable, ParseableParametrized},
         LinkHeader,
         LinkMessage,
         LinkMessageBuffer,
@@ -227,4 +231,284 @@ mod test {
         assert_eq!(packet.buffer_len(), 96);
         packet.emit(&mut buf[..]);
     }
+    #[rustfmt::skip]
+    static STATS64_MESSAGE: [u8; 232] = [
+        0x00, // interface family
+        0x00, // reserved
+        0x01, 0x00, // link layer type 1 = ether
+        0x02, 0x00, 0x00, 0x00, // interface index = 2
+        0x43, 0x10, 0x01, 0x00, // device flags: UP, BROADCAST, RUNNING, MULTICAST, LOWERUP
+        0x00, 0x00, 0x00, 0x00, // reserved 2 (aka device change flag)
+
+        // nlas
+        0x09, 0x00, 0x03, 0x00, 0x65, 0x74, 0x68, 0x30, 0x00, // device name L=9,T=3,V=eth0
+        0x00, 0x00, 0x00, // padding
+        0xcc, 0x00, 0x17, 0x00, // 64 bits statistics L=204,T=23
+        0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, // rx_packets = 8589934593
+        0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, // tx_packets = 4294967298
+        0xd0, 0x07, 0x00, 0x00, 0xb8, 0x0b, 0x00, 0x00, // rx_bytes = 12884901890000
+        0x00, 0x00, 0x00, 0x00, 0xdc, 0x05, 0x00, 0x00, // tx_bytes = 6442450944000
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_errors = 3
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_errors = 0
+        0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_dropped = 17
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_dropped = 0
+        0x2a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // multicast = 42
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // collisions = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_length_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_over_errors = 0
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_crc_errors = 3
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_frame_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_fifo_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_missed_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_aborted_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_carrier_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_fifo_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_heartbeat_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_window_errors = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_compressed = 0
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_compressed = 0
+        0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_nohandler = 5
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_otherhost_dropped = 0
+    ];
+
+    fn stats64_message() -> LinkMessage {
+        let mut header = LinkHeader::default();
+        header.link_layer_type = ARPHRD_ETHER;
+        header.index = 2;
+        header.flags = IFF_UP | IFF_BROADCAST | IFF_RUNNING | IFF_MULTICAST | IFF_LOWER_UP;
+        let nlas = vec![
+            Nla::IfName("eth0".into()),
+            Nla::Stats64(Stats64 {
+                rx_packets: 8_589_934_593,
+                tx_packets: 4_294_967_298,
+                rx_bytes: 12_884_901_890_000,
+                tx_bytes: 6_442_450_944_000,
+                rx_errors: 3,
+                rx_dropped: 17,
+                multicast: 42,
+                rx_crc_errors: 3,
+                rx_nohandler: 5,
+                ..Default::default()
+            }),
+        ];
+        LinkMessage { header, nlas }
+    }
+
+    #[test]
+    fn parse_stats64() {
+        let parsed = LinkMessage::parse(&LinkMessageBuffer::new(&STATS64_MESSAGE)).unwrap();
+        assert_eq!(parsed, stats64_message());
+    }
+
+    #[test]
+    fn emit_stats64() {
+        let packet = stats64_message();
+        let mut buf = [0; 232];
+        assert_eq!(packet.buffer_len(), 232);
+        packet.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &STATS64_MESSAGE[..]);
+    }
+
+    #[test]
+    fn parse_stats64_without_otherhost_dropped() {
+        // Linux < 5.19 reports 192 bytes
+        let mut bytes = STATS64_MESSAGE[28..224].to_vec();
+        bytes[0] = 0xc4;
+        let nla = NlaBuffer::new_checked(&bytes[..]).unwrap();
+        assert_eq!(
+            Nla::parse_with_param(&nla, AF_UNSPEC).unwrap(),
+            stats64_message().nlas[1]
+        );
+    }
+
+    #[test]
+    fn parse_stats64_without_nohandler() {
+        // Linux < 4.6 reports 184 bytes
+        let mut bytes = STATS64_MESSAGE[28..216].to_vec();
+        bytes[0] = 0xbc;
+        let nla = NlaBuffer::new_checked(&bytes[..]).unwrap();
+        let mut expected = stats64_message().nlas.remove(1);
+        if let Nla::Stats64(ref mut stats) = expected {
+            stats.rx_nohandler = 0;
+        }
+        assert_eq!(Nla::parse_with_param(&nla, AF_UNSPEC).unwrap(), expected);
+    }
+
+    // Response to RTM_GETLINK for a link with an XDP program attached in driver mode
+    #[rustfmt::skip]
+    static XDP_MESSAGE: [u8; 56] = [
+        0x00, // interface family
+        0x00, // reserved
+        0x01, 0x00, // link layer type 1 = ether
+        0x02, 0x00, 0x00, 0x00, // interface index = 2
+        0x43, 0x10, 0x01, 0x00, // device flags: UP, BROADCAST, RUNNING, MULTICAST, LOWERUP
+        0x00, 0x00, 0x00, 0x00, // reserved 2 (aka device change flag)
+
+        // nlas
+        0x09, 0x00, 0x03, 0x00, 0x65, 0x74, 0x68, 0x30, 0x00, // device name L=9,T=3,V=eth0
+        0x00, 0x00, 0x00, // padding
+        0x1c, 0x00, 0x2b, 0x00, // XDP L=28,T=43
+        0x05, 0x00, 0x02, 0x00, 0x01, // attached L=5,T=2,V=XDP_ATTACHED_DRV
+        0x00, 0x00, 0x00, // padding
+        0x08, 0x00, 0x05, 0x00, 0x2a, 0x00, 0x00, 0x00, // driver program id L=8,T=5,V=42
+        0x08, 0x00, 0x04, 0x00, 0x2a, 0x00, 0x00, 0x00, // program id L=8,T=4,V=42
+    ];
+
+    #[test]
+    fn parse_xdp() {
+        let parsed = LinkMessage::parse(&LinkMessageBuffer::new(&XDP_MESSAGE)).unwrap();
+        assert_eq!(
+            parsed.nlas[1],
+            Nla::Xdp(vec![
+                LinkXdp::Attached(XdpAttached::Driver),
+                LinkXdp::DrvProgId(42),
+                LinkXdp::ProgId(42),
+            ])
+        );
+    }
+
+    // Attach a program in generic mode, unless one is already attached
+    #[rustfmt::skip]
+    static XDP_ATTACH_NLA: [u8; 20] = [
+        0x14, 0x00, 0x2b, 0x80, // XDP L=20,T=43 (nested)
+        0x08, 0x00, 0x01, 0x00, 0x07, 0x00, 0x00, 0x00, // program fd L=8,T=1,V=7
+        0x08, 0x00, 0x03, 0x00, 0x03, 0x00, 0x00, 0x00, // flags L=8,T=3,V=UPDATE_IF_NOEXIST|SKB_MODE
+    ];
+
+    #[test]
+    fn emit_xdp() {
+        let nla = Nla::Xdp(vec![
+            LinkXdp::Fd(7),
+            LinkXdp::Flags(XDP_FLAGS_UPDATE_IF_NOEXIST | XDP_FLAGS_SKB_MODE),
+        ]);
+        let mut buf = [0; 20];
+        assert_eq!(nla.buffer_len(), 20);
+        nla.emit(&mut buf[..]);
+        assert_eq!(buf, XDP_ATTACH_NLA);
+
+        let parsed =
+            Nla::parse_with_param(&NlaBuffer::new_checked(&XDP_ATTACH_NLA).unwrap(), AF_UNSPEC)
+                .unwrap();
+        assert_eq!(parsed, nla);
+    }
+    // Bond slave whose address was changed by the bond, with an alias and protodown set
+    #[rustfmt::skip]
+    static OPERATIONAL_MESSAGE: [u8; 64] = [
+        0x00, // interface family
+        0x00, // reserved
+        0x01, 0x00, // link layer type 1 = ether
+        0x03, 0x00, 0x00, 0x00, // interface index = 3
+        0x02, 0x18, 0x00, 0x00, // device flags: BROADCAST, SLAVE, MULTICAST
+        0x00, 0x00, 0x00, 0x00, // reserved 2 (aka device change flag)
+
+        // nlas
+        0x08, 0x00, 0x0a, 0x00, 0x05, 0x00, 0x00, 0x00, // master L=8,T=10,V=5
+        0x08, 0x00, 0x1b, 0x00, 0x02, 0x00, 0x00, 0x00, // group L=8,T=27,V=2
+        0x05, 0x00, 0x27, 0x00, 0x01, // protodown L=5,T=39,V=1
+        0x00, 0x00, 0x00, // padding
+        0x0b, 0x00, 0x14, 0x00, 0x75, 0x70, 0x6c, 0x69, 0x6e, 0x6b, 0x00, // alias L=11,T=20,V=uplink
+        0x00, // padding
+        0x0a, 0x00, 0x36, 0x00, 0x52, 0x54, 0x00, 0x12, 0x34, 0x56, // perm address L=10,T=54
+        0x00, 0x00, // padding
+    ];
+
+    fn operational_message() -> LinkMessage {
+        let mut header = LinkHeader::default();
+        header.link_layer_type = ARPHRD_ETHER;
+        header.index = 3;
+        header.flags = IFF_BROADCAST | IFF_SLAVE | IFF_MULTICAST;
+        let nlas = vec![
+            Nla::Master(5),
+            Nla::Group(2),
+            Nla::ProtoDown(1),
+            Nla::IfAlias("uplink".into()),
+            Nla::PermAddress(vec![0x52, 0x54, 0x00, 0x12, 0x34, 0x56]),
+        ];
+        LinkMessage { header, nlas }
+    }
+
+    #[test]
+    fn parse_operational_nlas() {
+        let parsed = LinkMessage::parse(&LinkMessageBuffer::new(&OPERATIONAL_MESSAGE)).unwrap();
+        assert_eq!(parsed, operational_message());
+    }
+
+    #[test]
+    fn emit_operational_nlas() {
+        let packet = operational_message();
+        let mut buf = [0; 64];
+        assert_eq!(packet.buffer_len(), 64);
+        packet.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &OPERATIONAL_MESSAGE[..]);
+    }
+    // ip link add dummy1 numtxqueues 4 numrxqueues 2 type dummy
+    #[rustfmt::skip]
+    static QUEUES_MESSAGE: [u8; 60] = [
+        0x00, // interface family
+        0x00, // reserved
+        0x00, 0x00, // link layer type
+        0x00, 0x00, 0x00, 0x00, // interface index
+        0x00, 0x00, 0x00, 0x00, // device flags
+        0x00, 0x00, 0x00, 0x00, // reserved 2 (aka device change flag)
+
+        // nlas
+        0x0b, 0x00, 0x03, 0x00, 0x64, 0x75, 0x6d, 0x6d, 0x79, 0x31, 0x00, // name L=11,T=3,V=dummy1
+        0x00, // padding
+        0x08, 0x00, 0x1f, 0x00, 0x04, 0x00, 0x00, 0x00, // number of tx queues L=8,T=31,V=4
+        0x08, 0x00, 0x20, 0x00, 0x02, 0x00, 0x00, 0x00, // number of rx queues L=8,T=32,V=2
+        0x10, 0x00, 0x12, 0x00, // link info L=16,T=18
+        0x0a, 0x00, 0x01, 0x00, 0x64, 0x75, 0x6d, 0x6d, 0x79, 0x00, // kind L=10,T=1,V=dummy
+        0x00, 0x00, // padding
+    ];
+
+    #[test]
+    fn emit_queues() {
+        let packet = LinkMessage {
+            header: LinkHeader::default(),
+            nlas: vec![
+                Nla::IfName("dummy1".into()),
+                Nla::NumTxQueues(4),
+                Nla::NumRxQueues(2),
+                Nla::Info(vec![Info::Kind(InfoKind::Dummy)]),
+            ],
+        };
+        let mut buf = [0; 60];
+        assert_eq!(packet.buffer_len(), 60);
+        packet.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &QUEUES_MESSAGE[..]);
+
+        let parsed = LinkMessage::parse(&LinkMessageBuffer::new(&QUEUES_MESSAGE)).unwrap();
+        assert_eq!(parsed, packet);
+    }
+
+    #[rustfmt::skip]
+    static OFFLOAD_LIMITS_NLAS: [u8; 40] = [
+        0x08, 0x00, 0x28, 0x00, 0xff, 0xff, 0x00, 0x00, // GSO max segments L=8,T=40,V=65535
+        0x08, 0x00, 0x29, 0x00, 0x00, 0x00, 0x01, 0x00, // GSO max size L=8,T=41,V=65536
+        0x08, 0x00, 0x3a, 0x00, 0x00, 0x00, 0x01, 0x00, // GRO max size L=8,T=58,V=65536
+        0x08, 0x00, 0x3b, 0x00, 0x00, 0x00, 0x01, 0x00, // TSO max size L=8,T=59,V=65536
+        0x08, 0x00, 0x3c, 0x00, 0xff, 0xff, 0x00, 0x00, // TSO max segments L=8,T=60,V=65535
+    ];
+
+    #[test]
+    fn parse_offload_limits() {
+        let nlas: Vec<Nla> = NlasIterator::new(&OFFLOAD_LIMITS_NLAS[..])
+            .map(|nla| Nla::parse_with_param(&nla.unwrap(), AF_UNSPEC).unwrap())
+            .collect();
+        assert_eq!(
+            nlas,
+            vec![
+                Nla::GsoMaxSegs(65535),
+                Nla::GsoMaxSize(65536),
+                Nla::GroMaxSize(65536),
+                Nla::TsoMaxSize(65536),
+                Nla::TsoMaxSegs(65535),
+            ]
+        );
+
+        let mut buf = [0; 40];
+        assert_eq!(nlas.as_slice().buffer_len(), 40);
+        nlas.as_slice().emit(&mut buf[..]);
+        assert_eq!(buf, OFFLOAD_LIMITS_NLAS);
+    }
 }
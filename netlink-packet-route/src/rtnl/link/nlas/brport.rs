@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: MIT
+
+use std::mem::size_of;
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{DefaultNla, Nla, NlaBuffer},
+    parsers::{parse_u16, parse_u32, parse_u8},
+    traits::Parseable,
+    DecodeError,
+};
+
+/// Attributes of a bridge port, found in the `IFLA_PROTINFO` attribute of the `AF_BRIDGE` link
+/// messages sent by the bridge, and accepted in `RTM_SETLINK` messages to configure the port.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BrportNla {
+    /// STP state of the port, one of the `BR_STATE_*` constants
+    State(u8),
+    Priority(u16),
+    Cost(u32),
+    /// Hairpin mode: whether frames can be sent back on the port they were received on
+    Mode(bool),
+    /// Whether STP BPDUs received on the port are dropped
+    Guard(bool),
+    /// Whether the port is prevented from becoming a root port
+    ProtectRoot(bool),
+    /// Whether the port leaves multicast groups immediately when it receives an IGMP leave
+    FastLeave(bool),
+    /// Whether source addresses are learned on the port
+    Learning(bool),
+    /// Whether unknown unicast traffic is flooded to the port
+    UnicastFlood(bool),
+    ProxyArp(bool),
+    /// Whether the port can only talk to the ports that are not isolated
+    Isolated(bool),
+    Other(DefaultNla),
+}
+
+impl Nla for BrportNla {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::BrportNla::*;
+        match *self {
+            State(_)
+                | Mode(_)
+                | Guard(_)
+                | ProtectRoot(_)
+                | FastLeave(_)
+                | Learning(_)
+                | UnicastFlood(_)
+                | ProxyArp(_)
+                | Isolated(_) => size_of::<u8>(),
+            Priority(_) => size_of::<u16>(),
+            Cost(_) => size_of::<u32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::BrportNla::*;
+        match *self {
+            State(value) => buffer[0] = value,
+            Mode(value)
+                | Guard(value)
+                | ProtectRoot(value)
+                | FastLeave(value)
+                | Learning(value)
+                | UnicastFlood(value)
+                | ProxyArp(value)
+                | Isolated(value) => buffer[0] = value.into(),
+            Priority(value) => NativeEndian::write_u16(buffer, value),
+            Cost(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::BrportNla::*;
+        match *self {
+            State(_) => IFLA_BRPORT_STATE,
+            Priority(_) => IFLA_BRPORT_PRIORITY,
+            Cost(_) => IFLA_BRPORT_COST,
+            Mode(_) => IFLA_BRPORT_MODE,
+            Guard(_) => IFLA_BRPORT_GUARD,
+            ProtectRoot(_) => IFLA_BRPORT_PROTECT,
+            FastLeave(_) => IFLA_BRPORT_FAST_LEAVE,
+            Learning(_) => IFLA_BRPORT_LEARNING,
+            UnicastFlood(_) => IFLA_BRPORT_UNICAST_FLOOD,
+            ProxyArp(_) => IFLA_BRPORT_PROXYARP,
+            Isolated(_) => IFLA_BRPORT_ISOLATED,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for BrportNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::BrportNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            IFLA_BRPORT_STATE => {
+                State(parse_u8(payload).context("invalid IFLA_BRPORT_STATE value")?)
+            }
+            IFLA_BRPORT_PRIORITY => {
+                Priority(parse_u16(payload).context("invalid IFLA_BRPORT_PRIORITY value")?)
+            }
+            IFLA_BRPORT_COST => Cost(parse_u32(payload).context("invalid IFLA_BRPORT_COST value")?),
+            IFLA_BRPORT_MODE => {
+                Mode(parse_u8(payload).context("invalid IFLA_BRPORT_MODE value")? != 0)
+            }
+            IFLA_BRPORT_GUARD => {
+                Guard(parse_u8(payload).context("invalid IFLA_BRPORT_GUARD value")? != 0)
+            }
+            IFLA_BRPORT_PROTECT => {
+                ProtectRoot(parse_u8(payload).context("invalid IFLA_BRPORT_PROTECT value")? != 0)
+            }
+            IFLA_BRPORT_FAST_LEAVE => {
+                FastLeave(parse_u8(payload).context("invalid IFLA_BRPORT_FAST_LEAVE value")? != 0)
+            }
+            IFLA_BRPORT_LEARNING => {
+                Learning(parse_u8(payload).context("invalid IFLA_BRPORT_LEARNING value")? != 0)
+            }
+            IFLA_BRPORT_UNICAST_FLOOD => UnicastFlood(
+                parse_u8(payload).context("invalid IFLA_BRPORT_UNICAST_FLOOD value")? != 0,
+            ),
+            IFLA_BRPORT_PROXYARP => {
+                ProxyArp(parse_u8(payload).context("invalid IFLA_BRPORT_PROXYARP value")? != 0)
+            }
+            IFLA_BRPORT_ISOLATED => {
+                Isolated(parse_u8(payload).context("invalid IFLA_BRPORT_ISOLATED value")? != 0)
+            }
+            kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
+        })
+    }
+}
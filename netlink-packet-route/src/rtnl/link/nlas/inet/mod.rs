@@ -5,7 +5,7 @@ use anyhow::Context;
 use crate::{
     constants::{IFLA_INET_CONF, IFLA_INET_UNSPEC},
     nlas::{DefaultNla, Nla, NlaBuffer},
-    traits::Parseable,
+    traits::{Emitable, Parseable},
     DecodeError,
 };
 
@@ -14,7 +14,9 @@ pub use self::dev_conf::*;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum Inet {
-    DevConf(Vec<u8>),
+    /// Configuration of the interface. The kernel adds new settings over time: the ones that
+    /// this crate doesn't know are ignored, and those missing on older kernels are set to 0.
+    DevConf(InetDevConf),
     Unspec(Vec<u8>),
     Other(DefaultNla),
 }
@@ -33,7 +35,7 @@ impl Nla for Inet {
         use self::Inet::*;
         match *self {
             Unspec(ref bytes) => (&mut buffer[..bytes.len()]).copy_from_slice(bytes.as_slice()),
-            DevConf(ref dev_conf) => buffer[..dev_conf.len()].copy_from_slice(dev_conf.as_slice()),
+            DevConf(ref dev_conf) => dev_conf.emit(buffer),
             Other(ref nla) => nla.emit_value(buffer),
         }
     }
@@ -55,7 +57,15 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Inet {
         let payload = buf.value();
         Ok(match buf.kind() {
             IFLA_INET_UNSPEC => Unspec(payload.to_vec()),
-            IFLA_INET_CONF => DevConf(payload.to_vec()),
+            IFLA_INET_CONF => {
+                let mut dev_conf = [0; DEV_CONF_LEN];
+                let len = payload.len().min(DEV_CONF_LEN);
+                dev_conf[..len].copy_from_slice(&payload[..len]);
+                DevConf(
+                    InetDevConf::parse(&InetDevConfBuffer::new(&dev_conf[..]))
+                        .context("invalid IFLA_INET_CONF value")?,
+                )
+            }
             kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
         })
     }
@@ -7,7 +7,7 @@ use crate::{
     constants::*,
     nlas::{DefaultNla, Nla, NlaBuffer},
     parsers::{parse_ipv6, parse_u32, parse_u8},
-    traits::Parseable,
+    traits::{Emitable, Parseable},
     DecodeError,
 };
 
@@ -23,8 +23,10 @@ pub use self::stats::*;
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum Inet6 {
     Flags(u32),
-    CacheInfo(Vec<u8>),
-    DevConf(Vec<u8>),
+    CacheInfo(Inet6CacheInfo),
+    /// Configuration of the interface. The kernel adds new settings over time: the ones that
+    /// this crate doesn't know are ignored, and those missing on older kernels are set to 0.
+    DevConf(Inet6DevConf),
     Unspec(Vec<u8>),
     Stats(Vec<u8>),
     IcmpStats(Vec<u8>),
@@ -38,8 +40,8 @@ impl Nla for Inet6 {
         use self::Inet6::*;
         match *self {
             Unspec(ref bytes) => bytes.len(),
-            CacheInfo(ref cache_info) => cache_info.len(),
-            DevConf(ref dev_conf) => dev_conf.len(),
+            CacheInfo(ref cache_info) => cache_info.buffer_len(),
+            DevConf(ref dev_conf) => dev_conf.buffer_len(),
             Stats(ref stats) => stats.len(),
             IcmpStats(ref icmp_stats) => icmp_stats.len(),
             Flags(_) => 4,
@@ -54,8 +56,8 @@ impl Nla for Inet6 {
         match *self {
             Unspec(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
             Flags(ref value) => NativeEndian::write_u32(buffer, *value),
-            CacheInfo(ref cache_info) => buffer.copy_from_slice(cache_info.as_slice()),
-            DevConf(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            CacheInfo(ref cache_info) => cache_info.emit(buffer),
+            DevConf(ref dev_conf) => dev_conf.emit(buffer),
             Stats(ref inet6_stats) => buffer.copy_from_slice(inet6_stats.as_slice()),
             IcmpStats(ref icmp6_stats) => buffer.copy_from_slice(icmp6_stats.as_slice()),
             Token(ref ipv6) => buffer.copy_from_slice(&ipv6[..]),
@@ -89,8 +91,19 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Inet6 {
             IFLA_INET6_FLAGS => {
                 Flags(parse_u32(payload).context("invalid IFLA_INET6_FLAGS value")?)
             }
-            IFLA_INET6_CACHEINFO => CacheInfo(payload.to_vec()),
-            IFLA_INET6_CONF => DevConf(payload.to_vec()),
+            IFLA_INET6_CACHEINFO => CacheInfo(
+                Inet6CacheInfo::parse(&Inet6CacheInfoBuffer::new_checked(payload)?)
+                    .context("invalid IFLA_INET6_CACHEINFO value")?,
+            ),
+            IFLA_INET6_CONF => {
+                let mut dev_conf = [0; LINK_INET6_DEV_CONF_LEN];
+                let len = payload.len().min(LINK_INET6_DEV_CONF_LEN);
+                dev_conf[..len].copy_from_slice(&payload[..len]);
+                DevConf(
+                    Inet6DevConf::parse(&Inet6DevConfBuffer::new(&dev_conf[..]))
+                        .context("invalid IFLA_INET6_CONF value")?,
+                )
+            }
             IFLA_INET6_STATS => Stats(payload.to_vec()),
             IFLA_INET6_ICMP6STATS => IcmpStats(payload.to_vec()),
             IFLA_INET6_TOKEN => {
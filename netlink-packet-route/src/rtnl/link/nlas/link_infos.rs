@@ -2,8 +2,20 @@
 
 use crate::{
     constants::*,
+    emit_ip,
+    ip_len,
     nlas::{DefaultNla, Nla, NlaBuffer, NlasIterator},
-    parsers::{parse_mac, parse_string, parse_u16, parse_u16_be, parse_u32, parse_u64, parse_u8},
+    parsers::{
+        parse_ip,
+        parse_mac,
+        parse_string,
+        parse_u16,
+        parse_u16_be,
+        parse_u32,
+        parse_u32_be,
+        parse_u64,
+        parse_u8,
+    },
     traits::{Emitable, Parseable},
     DecodeError,
     LinkMessage,
@@ -11,6 +23,7 @@ use crate::{
 };
 use anyhow::Context;
 use byteorder::{BigEndian, ByteOrder, NativeEndian};
+use std::net::{IpAddr, Ipv4Addr};
 
 const DUMMY: &str = "dummy";
 const IFB: &str = "ifb";
@@ -165,7 +178,17 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VecInfo {
                                 }
                                 InfoData::Vxlan(v)
                             }
-                            InfoKind::Bond => InfoData::Bond(payload.to_vec()),
+                            InfoKind::Bond => {
+                                let mut v = Vec::new();
+                                let err =
+                                    "failed to parse IFLA_INFO_DATA (IFLA_INFO_KIND is 'bond')";
+                                for nla in NlasIterator::new(payload) {
+                                    let nla = &nla.context(err)?;
+                                    let parsed = InfoBond::parse(nla).context(err)?;
+                                    v.push(parsed);
+                                }
+                                InfoData::Bond(v)
+                            }
                             InfoKind::IpVlan => {
                                 let mut v = Vec::new();
                                 let err =
@@ -199,12 +222,52 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VecInfo {
                                 }
                                 InfoData::MacVtap(v)
                             }
-                            InfoKind::GreTap => InfoData::GreTap(payload.to_vec()),
-                            InfoKind::GreTap6 => InfoData::GreTap6(payload.to_vec()),
+                            InfoKind::GreTap => {
+                                let mut v = Vec::new();
+                                let err =
+                                    "failed to parse IFLA_INFO_DATA (IFLA_INFO_KIND is 'gretap')";
+                                for nla in NlasIterator::new(payload) {
+                                    let nla = &nla.context(err)?;
+                                    let parsed = InfoGre::parse(nla).context(err)?;
+                                    v.push(parsed);
+                                }
+                                InfoData::GreTap(v)
+                            }
+                            InfoKind::GreTap6 => {
+                                let mut v = Vec::new();
+                                let err =
+                                    "failed to parse IFLA_INFO_DATA (IFLA_INFO_KIND is 'ip6gretap')";
+                                for nla in NlasIterator::new(payload) {
+                                    let nla = &nla.context(err)?;
+                                    let parsed = InfoGre::parse(nla).context(err)?;
+                                    v.push(parsed);
+                                }
+                                InfoData::GreTap6(v)
+                            }
                             InfoKind::IpTun => InfoData::IpTun(payload.to_vec()),
                             InfoKind::SitTun => InfoData::SitTun(payload.to_vec()),
-                            InfoKind::GreTun => InfoData::GreTun(payload.to_vec()),
-                            InfoKind::GreTun6 => InfoData::GreTun6(payload.to_vec()),
+                            InfoKind::GreTun => {
+                                let mut v = Vec::new();
+                                let err =
+                                    "failed to parse IFLA_INFO_DATA (IFLA_INFO_KIND is 'gre')";
+                                for nla in NlasIterator::new(payload) {
+                                    let nla = &nla.context(err)?;
+                                    let parsed = InfoGre::parse(nla).context(err)?;
+                                    v.push(parsed);
+                                }
+                                InfoData::GreTun(v)
+                            }
+                            InfoKind::GreTun6 => {
+                                let mut v = Vec::new();
+                                let err =
+                                    "failed to parse IFLA_INFO_DATA (IFLA_INFO_KIND is 'ip6gre')";
+                                for nla in NlasIterator::new(payload) {
+                                    let nla = &nla.context(err)?;
+                                    let parsed = InfoGre::parse(nla).context(err)?;
+                                    v.push(parsed);
+                                }
+                                InfoData::GreTun6(v)
+                            }
                             InfoKind::Vti => InfoData::Vti(payload.to_vec()),
                             InfoKind::Vrf => {
                                 let mut v = Vec::new();
@@ -255,20 +318,23 @@ pub enum InfoData {
     Ifb(Vec<u8>),
     Veth(VethInfo),
     Vxlan(Vec<InfoVxlan>),
-    Bond(Vec<u8>),
+    Bond(Vec<InfoBond>),
     IpVlan(Vec<InfoIpVlan>),
     MacVlan(Vec<InfoMacVlan>),
     MacVtap(Vec<InfoMacVtap>),
-    GreTap(Vec<u8>),
-    GreTap6(Vec<u8>),
+    GreTap(Vec<InfoGre>),
+    GreTap6(Vec<InfoGre>),
     IpTun(Vec<u8>),
     SitTun(Vec<u8>),
-    GreTun(Vec<u8>),
-    GreTun6(Vec<u8>),
+    GreTun(Vec<InfoGre>),
+    GreTun6(Vec<InfoGre>),
     Vti(Vec<u8>),
     Vrf(Vec<InfoVrf>),
     Gtp(Vec<u8>),
     Ipoib(Vec<InfoIpoib>),
+    /// WireGuard devices have no link info data: they are configured with
+    /// the `wireguard` generic netlink family (see the
+    /// `netlink-packet-wireguard` crate).
     Wireguard(Vec<u8>),
     Other(Vec<u8>),
 }
@@ -287,17 +353,18 @@ impl Nla for InfoData {
             MacVtap(ref nlas) => nlas.as_slice().buffer_len(),
             Vrf(ref nlas) => nlas.as_slice().buffer_len(),
             Vxlan(ref nlas) => nlas.as_slice().buffer_len(),
+            Bond(ref nlas) => nlas.as_slice().buffer_len(),
+            GreTap(ref nlas)
+                | GreTap6(ref nlas)
+                | GreTun(ref nlas)
+                | GreTun6(ref nlas)
+                => nlas.as_slice().buffer_len(),
             Dummy(ref bytes)
                 | Tun(ref bytes)
                 | Nlmon(ref bytes)
                 | Ifb(ref bytes)
-                | Bond(ref bytes)
-                | GreTap(ref bytes)
-                | GreTap6(ref bytes)
                 | IpTun(ref bytes)
                 | SitTun(ref bytes)
-                | GreTun(ref bytes)
-                | GreTun6(ref bytes)
                 | Vti(ref bytes)
                 | Gtp(ref bytes)
                 | Wireguard(ref bytes)
@@ -319,17 +386,18 @@ impl Nla for InfoData {
             MacVtap(ref nlas) => nlas.as_slice().emit(buffer),
             Vrf(ref nlas) => nlas.as_slice().emit(buffer),
             Vxlan(ref nlas) => nlas.as_slice().emit(buffer),
+            Bond(ref nlas) => nlas.as_slice().emit(buffer),
+            GreTap(ref nlas)
+                | GreTap6(ref nlas)
+                | GreTun(ref nlas)
+                | GreTun6(ref nlas)
+                => nlas.as_slice().emit(buffer),
             Dummy(ref bytes)
                 | Tun(ref bytes)
                 | Nlmon(ref bytes)
                 | Ifb(ref bytes)
-                | Bond(ref bytes)
-                | GreTap(ref bytes)
-                | GreTap6(ref bytes)
                 | IpTun(ref bytes)
                 | SitTun(ref bytes)
-                | GreTun(ref bytes)
-                | GreTun6(ref bytes)
                 | Vti(ref bytes)
                 | Gtp(ref bytes)
                 | Wireguard(ref bytes)
@@ -506,9 +574,11 @@ pub enum InfoVxlan {
     UDPZeroCsumRX(u8),
     RemCsumTX(u8),
     RemCsumRX(u8),
-    Gbp(u8),
-    Gpe(u8),
-    RemCsumNoPartial(u8),
+    /// Group Based Policy extension, this is a flag attribute
+    Gbp,
+    /// Generic Protocol Extension, this is a flag attribute
+    Gpe,
+    RemCsumNoPartial,
     TtlInherit(u8),
     Df(u8),
 }
@@ -531,12 +601,10 @@ impl Nla for InfoVxlan {
                 | UDPZeroCsumRX(_)
                 | RemCsumTX(_)
                 | RemCsumRX(_)
-                | Gbp(_)
-                | Gpe(_)
-                | RemCsumNoPartial(_)
                 | TtlInherit(_)
                 | Df(_)
             => 1,
+            Gbp | Gpe | RemCsumNoPartial => 0,
             Port(_) => 2,
             Id(_)
                 | Label(_)
@@ -559,6 +627,7 @@ impl Nla for InfoVxlan {
         use self::InfoVxlan::*;
         match self {
             Unspec(ref bytes) => buffer.copy_from_slice(bytes),
+            Gbp | Gpe | RemCsumNoPartial => {}
             Id(ref value)
                 | Label(ref value)
                 | Link(ref value)
@@ -578,9 +647,6 @@ impl Nla for InfoVxlan {
                 | UDPZeroCsumRX(ref value)
                 | RemCsumTX(ref value)
                 | RemCsumRX(ref value)
-                | Gbp(ref value)
-                | Gpe(ref value)
-                | RemCsumNoPartial(ref value)
                 | TtlInherit(ref value)
                 | Df(ref value)
             =>  buffer[0] = *value,
@@ -589,10 +655,10 @@ impl Nla for InfoVxlan {
                 | Group6(ref value)
                 | Local6(ref value)
             => buffer.copy_from_slice(value.as_slice()),
-            Port(ref value) => NativeEndian::write_u16(buffer, *value),
+            Port(ref value) => BigEndian::write_u16(buffer, *value),
             PortRange(ref range) => {
-                NativeEndian::write_u16(buffer, range.0);
-                NativeEndian::write_u16(buffer, range.1)
+                BigEndian::write_u16(&mut buffer[..2], range.0);
+                BigEndian::write_u16(&mut buffer[2..4], range.1)
             }
         }
     }
@@ -606,7 +672,7 @@ impl Nla for InfoVxlan {
             Group6(_) => IFLA_VXLAN_GROUP6,
             Link(_) => IFLA_VXLAN_LINK,
             Local(_) => IFLA_VXLAN_LOCAL,
-            Local6(_) => IFLA_VXLAN_LOCAL,
+            Local6(_) => IFLA_VXLAN_LOCAL6,
             Tos(_) => IFLA_VXLAN_TOS,
             Ttl(_) => IFLA_VXLAN_TTL,
             Label(_) => IFLA_VXLAN_LABEL,
@@ -625,9 +691,9 @@ impl Nla for InfoVxlan {
             UDPZeroCsumRX(_) => IFLA_VXLAN_UDP_ZERO_CSUM6_RX,
             RemCsumTX(_) => IFLA_VXLAN_REMCSUM_TX,
             RemCsumRX(_) => IFLA_VXLAN_REMCSUM_RX,
-            Gbp(_) => IFLA_VXLAN_GBP,
-            Gpe(_) => IFLA_VXLAN_GPE,
-            RemCsumNoPartial(_) => IFLA_VXLAN_REMCSUM_NOPARTIAL,
+            Gbp => IFLA_VXLAN_GBP,
+            Gpe => IFLA_VXLAN_GPE,
+            RemCsumNoPartial => IFLA_VXLAN_REMCSUM_NOPARTIAL,
             TtlInherit(_) => IFLA_VXLAN_TTL_INHERIT,
             Df(_) => IFLA_VXLAN_DF,
             Unspec(_) => IFLA_VXLAN_UNSPEC,
@@ -640,7 +706,7 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoVxlan {
         use self::InfoVxlan::*;
         let payload = buf.value();
         Ok(match buf.kind() {
-            IFLA_VLAN_UNSPEC => Unspec(payload.to_vec()),
+            IFLA_VXLAN_UNSPEC => Unspec(payload.to_vec()),
             IFLA_VXLAN_ID => Id(parse_u32(payload).context("invalid IFLA_VXLAN_ID value")?),
             IFLA_VXLAN_GROUP => Group(payload.to_vec()),
             IFLA_VXLAN_GROUP6 => Group6(payload.to_vec()),
@@ -673,12 +739,12 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoVxlan {
                 parse_u8(payload).context("invalid IFLA_VXLAN_COLLECT_METADATA value")?,
             ),
             IFLA_VXLAN_PORT_RANGE => {
-                let err = "invalid IFLA_VXLAN_PORT value";
+                let err = "invalid IFLA_VXLAN_PORT_RANGE value";
                 if payload.len() != 4 {
                     return Err(err.into());
                 }
-                let low = parse_u16(&payload[0..2]).context(err)?;
-                let high = parse_u16(&payload[2..]).context(err)?;
+                let low = parse_u16_be(&payload[0..2]).context(err)?;
+                let high = parse_u16_be(&payload[2..]).context(err)?;
                 PortRange((low, high))
             }
             IFLA_VXLAN_PORT => {
@@ -700,11 +766,9 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoVxlan {
                 RemCsumRX(parse_u8(payload).context("invalid IFLA_VXLAN_REMCSUM_RX value")?)
             }
             IFLA_VXLAN_DF => Df(parse_u8(payload).context("invalid IFLA_VXLAN_DF value")?),
-            IFLA_VXLAN_GBP => Gbp(parse_u8(payload).context("invalid IFLA_VXLAN_GBP value")?),
-            IFLA_VXLAN_GPE => Gpe(parse_u8(payload).context("invalid IFLA_VXLAN_GPE value")?),
-            IFLA_VXLAN_REMCSUM_NOPARTIAL => RemCsumNoPartial(
-                parse_u8(payload).context("invalid IFLA_VXLAN_REMCSUM_NO_PARTIAL")?,
-            ),
+            IFLA_VXLAN_GBP => Gbp,
+            IFLA_VXLAN_GPE => Gpe,
+            IFLA_VXLAN_REMCSUM_NOPARTIAL => RemCsumNoPartial,
             IFLA_VXLAN_TTL_INHERIT => {
                 TtlInherit(parse_u8(payload).context("invalid IFLA_VXLAN_TTL_INHERIT value")?)
             }
@@ -719,9 +783,15 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoVxlan {
 pub enum InfoVlan {
     Unspec(Vec<u8>),
     Id(u16),
-    Flags((u32, u32)),
-    EgressQos(Vec<u8>),
-    IngressQos(Vec<u8>),
+    /// Flags and mask of the flags to change
+    Flags((VlanFlags, VlanFlags)),
+    /// Mapping of the skb priorities to the 802.1p priorities of the
+    /// outgoing frames
+    EgressQos(Vec<VlanQosMapping>),
+    /// Mapping of the 802.1p priorities of the incoming frames to skb
+    /// priorities
+    IngressQos(Vec<VlanQosMapping>),
+    /// Ethernet protocol of the tag (`ETH_P_8021Q` or `ETH_P_8021AD`)
     Protocol(u16),
 }
 
@@ -732,10 +802,10 @@ impl Nla for InfoVlan {
         match self {
             Id(_) | Protocol(_) => 2,
             Flags(_) => 8,
-            Unspec(bytes)
-                | EgressQos(bytes)
-                | IngressQos(bytes)
-                => bytes.len(),
+            Unspec(bytes) => bytes.len(),
+            EgressQos(ref mappings)
+                | IngressQos(ref mappings)
+                => mappings.as_slice().buffer_len(),
         }
     }
 
@@ -743,18 +813,15 @@ impl Nla for InfoVlan {
     fn emit_value(&self, buffer: &mut [u8]) {
         use self::InfoVlan::*;
         match self {
-            Unspec(ref bytes)
-                | EgressQos(ref bytes)
-                | IngressQos(ref bytes)
-                => buffer.copy_from_slice(bytes),
-
-            Id(ref value)
-                | Protocol(ref value)
-                => NativeEndian::write_u16(buffer, *value),
-
-            Flags(ref flags) => {
-                NativeEndian::write_u32(buffer, flags.0);
-                NativeEndian::write_u32(buffer, flags.1)
+            Unspec(ref bytes) => buffer.copy_from_slice(bytes),
+            EgressQos(ref mappings)
+                | IngressQos(ref mappings)
+                => mappings.as_slice().emit(buffer),
+            Id(ref value) => NativeEndian::write_u16(buffer, *value),
+            Protocol(ref value) => BigEndian::write_u16(buffer, *value),
+            Flags((ref flags, ref mask)) => {
+                NativeEndian::write_u32(&mut buffer[..4], flags.bits());
+                NativeEndian::write_u32(&mut buffer[4..8], mask.bits())
             }
         }
     }
@@ -786,10 +853,17 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoVlan {
                 }
                 let flags = parse_u32(&payload[0..4]).context(err)?;
                 let mask = parse_u32(&payload[4..]).context(err)?;
-                Flags((flags, mask))
+                Flags((
+                    VlanFlags::from_bits_truncate(flags),
+                    VlanFlags::from_bits_truncate(mask),
+                ))
             }
-            IFLA_VLAN_EGRESS_QOS => EgressQos(payload.to_vec()),
-            IFLA_VLAN_INGRESS_QOS => IngressQos(payload.to_vec()),
+            IFLA_VLAN_EGRESS_QOS => EgressQos(
+                parse_qos_mappings(payload).context("invalid IFLA_VLAN_EGRESS_QOS value")?,
+            ),
+            IFLA_VLAN_INGRESS_QOS => IngressQos(
+                parse_qos_mappings(payload).context("invalid IFLA_VLAN_INGRESS_QOS value")?,
+            ),
             IFLA_VLAN_PROTOCOL => {
                 Protocol(parse_u16_be(payload).context("invalid IFLA_VLAN_PROTOCOL value")?)
             }
@@ -798,6 +872,74 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoVlan {
     }
 }
 
+bitflags! {
+    pub struct VlanFlags: u32 {
+        /// Reorder the header so that the VLAN device looks like an
+        /// ethernet device
+        const REORDER_HDR = VLAN_FLAG_REORDER_HDR;
+        const GVRP = VLAN_FLAG_GVRP;
+        /// Do not follow the operational state of the lower device
+        const LOOSE_BINDING = VLAN_FLAG_LOOSE_BINDING;
+        const MVRP = VLAN_FLAG_MVRP;
+        /// Follow the state of the bridge VLAN rather than the one of the
+        /// lower device
+        const BRIDGE_BINDING = VLAN_FLAG_BRIDGE_BINDING;
+    }
+}
+
+impl Default for VlanFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Priority mapping of a VLAN device (`struct ifla_vlan_qos_mapping`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct VlanQosMapping {
+    pub from: u32,
+    pub to: u32,
+}
+
+impl Nla for VlanQosMapping {
+    fn value_len(&self) -> usize {
+        8
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        NativeEndian::write_u32(&mut buffer[..4], self.from);
+        NativeEndian::write_u32(&mut buffer[4..8], self.to);
+    }
+
+    fn kind(&self) -> u16 {
+        IFLA_VLAN_QOS_MAPPING
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VlanQosMapping {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        if buf.kind() != IFLA_VLAN_QOS_MAPPING {
+            return Err(format!("unknown NLA type {}", buf.kind()).into());
+        }
+        if payload.len() != 8 {
+            return Err("invalid IFLA_VLAN_QOS_MAPPING value".into());
+        }
+        Ok(Self {
+            from: parse_u32(&payload[..4]).context("invalid IFLA_VLAN_QOS_MAPPING value")?,
+            to: parse_u32(&payload[4..]).context("invalid IFLA_VLAN_QOS_MAPPING value")?,
+        })
+    }
+}
+
+fn parse_qos_mappings(payload: &[u8]) -> Result<Vec<VlanQosMapping>, DecodeError> {
+    let mut mappings = vec![];
+    for nla in NlasIterator::new(payload) {
+        let nla = nla.context("invalid IFLA_VLAN_QOS_MAPPING")?;
+        mappings.push(VlanQosMapping::parse(&nla)?);
+    }
+    Ok(mappings)
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum InfoBridge {
     Unspec(Vec<u8>),
@@ -830,7 +972,9 @@ pub enum InfoBridge {
     Priority(u16),
     VlanProtocol(u16),
     GroupFwdMask(u16),
+    /// Priority and address of the root bridge
     RootId((u16, [u8; 6])),
+    /// Priority and address of this bridge
     BridgeId((u16, [u8; 6])),
     RootPort(u16),
     VlanDefaultPvid(u16),
@@ -970,7 +1114,7 @@ impl Nla for InfoBridge {
             RootId((ref priority, ref address))
                 | BridgeId((ref priority, ref address))
                 => {
-                    NativeEndian::write_u16(buffer, *priority);
+                    BigEndian::write_u16(buffer, *priority);
                     buffer[2..].copy_from_slice(&address[..]);
                 }
 
@@ -1141,7 +1285,7 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoBridge {
                     return Err("invalid IFLA_BR_ROOT_ID or IFLA_BR_BRIDGE_ID value".into());
                 }
 
-                let priority = NativeEndian::read_u16(&payload[..2]);
+                let priority = BigEndian::read_u16(&payload[..2]);
                 let address = parse_mac(&payload[2..])
                     .context("invalid IFLA_BR_ROOT_ID or IFLA_BR_BRIDGE_ID value")?;
 
@@ -1326,6 +1470,507 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VethInfo {
     }
 }
 
+// https://elixir.bootlin.com/linux/latest/source/drivers/net/bonding/bond_netlink.c
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InfoBond {
+    Mode(BondMode),
+    /// Index of the active slave in active-backup mode
+    ActiveSlave(u32),
+    /// Interval of the MII link monitoring, in milliseconds
+    MiiMon(u32),
+    UpDelay(u32),
+    DownDelay(u32),
+    UseCarrier(u8),
+    /// Interval of the ARP link monitoring, in milliseconds
+    ArpInterval(u32),
+    ArpIpTarget(Vec<Ipv4Addr>),
+    ArpValidate(u32),
+    ArpAllTargets(u32),
+    /// Index of the primary slave
+    Primary(u32),
+    PrimaryReselect(u8),
+    FailOverMac(u8),
+    XmitHashPolicy(u8),
+    ResendIgmp(u32),
+    NumPeerNotif(u8),
+    AllSlavesActive(u8),
+    MinLinks(u32),
+    LpInterval(u32),
+    PacketsPerSlave(u32),
+    AdLacpRate(u8),
+    AdSelect(u8),
+    AdActorSysPrio(u16),
+    AdUserPortKey(u16),
+    AdActorSystem([u8; 6]),
+    TlbDynamicLb(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for InfoBond {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::InfoBond::*;
+        match self {
+            Mode(_)
+                | UseCarrier(_)
+                | PrimaryReselect(_)
+                | FailOverMac(_)
+                | XmitHashPolicy(_)
+                | NumPeerNotif(_)
+                | AllSlavesActive(_)
+                | AdLacpRate(_)
+                | AdSelect(_)
+                | TlbDynamicLb(_)
+                => 1,
+            AdActorSysPrio(_) | AdUserPortKey(_) => 2,
+            ActiveSlave(_)
+                | MiiMon(_)
+                | UpDelay(_)
+                | DownDelay(_)
+                | ArpInterval(_)
+                | ArpValidate(_)
+                | ArpAllTargets(_)
+                | Primary(_)
+                | ResendIgmp(_)
+                | MinLinks(_)
+                | LpInterval(_)
+                | PacketsPerSlave(_)
+                => 4,
+            AdActorSystem(_) => 6,
+            ArpIpTarget(ref addrs) => arp_ip_targets(addrs).as_slice().buffer_len(),
+            Other(nla) => nla.value_len(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::InfoBond::*;
+        match self {
+            Mode(value) => buffer[0] = (*value).into(),
+            UseCarrier(value)
+                | PrimaryReselect(value)
+                | FailOverMac(value)
+                | XmitHashPolicy(value)
+                | NumPeerNotif(value)
+                | AllSlavesActive(value)
+                | AdLacpRate(value)
+                | AdSelect(value)
+                | TlbDynamicLb(value)
+                => buffer[0] = *value,
+            AdActorSysPrio(value)
+                | AdUserPortKey(value)
+                => NativeEndian::write_u16(buffer, *value),
+            ActiveSlave(value)
+                | MiiMon(value)
+                | UpDelay(value)
+                | DownDelay(value)
+                | ArpInterval(value)
+                | ArpValidate(value)
+                | ArpAllTargets(value)
+                | Primary(value)
+                | ResendIgmp(value)
+                | MinLinks(value)
+                | LpInterval(value)
+                | PacketsPerSlave(value)
+                => NativeEndian::write_u32(buffer, *value),
+            AdActorSystem(ref value) => buffer.copy_from_slice(&value[..]),
+            ArpIpTarget(ref addrs) => arp_ip_targets(addrs).as_slice().emit(buffer),
+            Other(nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::InfoBond::*;
+        match self {
+            Mode(_) => IFLA_BOND_MODE,
+            ActiveSlave(_) => IFLA_BOND_ACTIVE_SLAVE,
+            MiiMon(_) => IFLA_BOND_MIIMON,
+            UpDelay(_) => IFLA_BOND_UPDELAY,
+            DownDelay(_) => IFLA_BOND_DOWNDELAY,
+            UseCarrier(_) => IFLA_BOND_USE_CARRIER,
+            ArpInterval(_) => IFLA_BOND_ARP_INTERVAL,
+            ArpIpTarget(_) => IFLA_BOND_ARP_IP_TARGET,
+            ArpValidate(_) => IFLA_BOND_ARP_VALIDATE,
+            ArpAllTargets(_) => IFLA_BOND_ARP_ALL_TARGETS,
+            Primary(_) => IFLA_BOND_PRIMARY,
+            PrimaryReselect(_) => IFLA_BOND_PRIMARY_RESELECT,
+            FailOverMac(_) => IFLA_BOND_FAIL_OVER_MAC,
+            XmitHashPolicy(_) => IFLA_BOND_XMIT_HASH_POLICY,
+            ResendIgmp(_) => IFLA_BOND_RESEND_IGMP,
+            NumPeerNotif(_) => IFLA_BOND_NUM_PEER_NOTIF,
+            AllSlavesActive(_) => IFLA_BOND_ALL_SLAVES_ACTIVE,
+            MinLinks(_) => IFLA_BOND_MIN_LINKS,
+            LpInterval(_) => IFLA_BOND_LP_INTERVAL,
+            PacketsPerSlave(_) => IFLA_BOND_PACKETS_PER_SLAVE,
+            AdLacpRate(_) => IFLA_BOND_AD_LACP_RATE,
+            AdSelect(_) => IFLA_BOND_AD_SELECT,
+            AdActorSysPrio(_) => IFLA_BOND_AD_ACTOR_SYS_PRIO,
+            AdUserPortKey(_) => IFLA_BOND_AD_USER_PORT_KEY,
+            AdActorSystem(_) => IFLA_BOND_AD_ACTOR_SYSTEM,
+            TlbDynamicLb(_) => IFLA_BOND_TLB_DYNAMIC_LB,
+            Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoBond {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::InfoBond::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            IFLA_BOND_MODE => Mode(
+                parse_u8(payload)
+                    .context("invalid IFLA_BOND_MODE value")?
+                    .into(),
+            ),
+            IFLA_BOND_ACTIVE_SLAVE => {
+                ActiveSlave(parse_u32(payload).context("invalid IFLA_BOND_ACTIVE_SLAVE value")?)
+            }
+            IFLA_BOND_MIIMON => {
+                MiiMon(parse_u32(payload).context("invalid IFLA_BOND_MIIMON value")?)
+            }
+            IFLA_BOND_UPDELAY => {
+                UpDelay(parse_u32(payload).context("invalid IFLA_BOND_UPDELAY value")?)
+            }
+            IFLA_BOND_DOWNDELAY => {
+                DownDelay(parse_u32(payload).context("invalid IFLA_BOND_DOWNDELAY value")?)
+            }
+            IFLA_BOND_USE_CARRIER => {
+                UseCarrier(parse_u8(payload).context("invalid IFLA_BOND_USE_CARRIER value")?)
+            }
+            IFLA_BOND_ARP_INTERVAL => {
+                ArpInterval(parse_u32(payload).context("invalid IFLA_BOND_ARP_INTERVAL value")?)
+            }
+            IFLA_BOND_ARP_IP_TARGET => {
+                let err = "invalid IFLA_BOND_ARP_IP_TARGET value";
+                let mut addrs = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context(err)?;
+                    let value = nla.value();
+                    if value.len() != 4 {
+                        return Err(err.into());
+                    }
+                    addrs.push(Ipv4Addr::new(value[0], value[1], value[2], value[3]));
+                }
+                ArpIpTarget(addrs)
+            }
+            IFLA_BOND_ARP_VALIDATE => {
+                ArpValidate(parse_u32(payload).context("invalid IFLA_BOND_ARP_VALIDATE value")?)
+            }
+            IFLA_BOND_ARP_ALL_TARGETS => ArpAllTargets(
+                parse_u32(payload).context("invalid IFLA_BOND_ARP_ALL_TARGETS value")?,
+            ),
+            IFLA_BOND_PRIMARY => {
+                Primary(parse_u32(payload).context("invalid IFLA_BOND_PRIMARY value")?)
+            }
+            IFLA_BOND_PRIMARY_RESELECT => PrimaryReselect(
+                parse_u8(payload).context("invalid IFLA_BOND_PRIMARY_RESELECT value")?,
+            ),
+            IFLA_BOND_FAIL_OVER_MAC => {
+                FailOverMac(parse_u8(payload).context("invalid IFLA_BOND_FAIL_OVER_MAC value")?)
+            }
+            IFLA_BOND_XMIT_HASH_POLICY => XmitHashPolicy(
+                parse_u8(payload).context("invalid IFLA_BOND_XMIT_HASH_POLICY value")?,
+            ),
+            IFLA_BOND_RESEND_IGMP => {
+                ResendIgmp(parse_u32(payload).context("invalid IFLA_BOND_RESEND_IGMP value")?)
+            }
+            IFLA_BOND_NUM_PEER_NOTIF => {
+                NumPeerNotif(parse_u8(payload).context("invalid IFLA_BOND_NUM_PEER_NOTIF value")?)
+            }
+            IFLA_BOND_ALL_SLAVES_ACTIVE => AllSlavesActive(
+                parse_u8(payload).context("invalid IFLA_BOND_ALL_SLAVES_ACTIVE value")?,
+            ),
+            IFLA_BOND_MIN_LINKS => {
+                MinLinks(parse_u32(payload).context("invalid IFLA_BOND_MIN_LINKS value")?)
+            }
+            IFLA_BOND_LP_INTERVAL => {
+                LpInterval(parse_u32(payload).context("invalid IFLA_BOND_LP_INTERVAL value")?)
+            }
+            IFLA_BOND_PACKETS_PER_SLAVE => PacketsPerSlave(
+                parse_u32(payload).context("invalid IFLA_BOND_PACKETS_PER_SLAVE value")?,
+            ),
+            IFLA_BOND_AD_LACP_RATE => {
+                AdLacpRate(parse_u8(payload).context("invalid IFLA_BOND_AD_LACP_RATE value")?)
+            }
+            IFLA_BOND_AD_SELECT => {
+                AdSelect(parse_u8(payload).context("invalid IFLA_BOND_AD_SELECT value")?)
+            }
+            IFLA_BOND_AD_ACTOR_SYS_PRIO => AdActorSysPrio(
+                parse_u16(payload).context("invalid IFLA_BOND_AD_ACTOR_SYS_PRIO value")?,
+            ),
+            IFLA_BOND_AD_USER_PORT_KEY => AdUserPortKey(
+                parse_u16(payload).context("invalid IFLA_BOND_AD_USER_PORT_KEY value")?,
+            ),
+            IFLA_BOND_AD_ACTOR_SYSTEM => AdActorSystem(
+                parse_mac(payload).context("invalid IFLA_BOND_AD_ACTOR_SYSTEM value")?,
+            ),
+            IFLA_BOND_TLB_DYNAMIC_LB => {
+                TlbDynamicLb(parse_u8(payload).context("invalid IFLA_BOND_TLB_DYNAMIC_LB value")?)
+            }
+            kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
+        })
+    }
+}
+
+// The ARP targets are nested attributes whose type is their index in the
+// list.
+struct BondArpIpTarget(u16, Ipv4Addr);
+
+impl Nla for BondArpIpTarget {
+    fn value_len(&self) -> usize {
+        4
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        buffer.copy_from_slice(&self.1.octets())
+    }
+
+    fn kind(&self) -> u16 {
+        self.0
+    }
+}
+
+fn arp_ip_targets(addrs: &[Ipv4Addr]) -> Vec<BondArpIpTarget> {
+    addrs
+        .iter()
+        .enumerate()
+        .map(|(index, addr)| BondArpIpTarget(index as u16, *addr))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BondMode {
+    /// Transmit packets on each slave in turn
+    RoundRobin,
+    /// Only one slave is active, another one takes over when it fails
+    ActiveBackup,
+    /// Select the slave from a hash of the packet
+    Xor,
+    /// Transmit every packet on every slave
+    Broadcast,
+    /// IEEE 802.3ad link aggregation
+    Ieee8023Ad,
+    /// Adaptive transmit load balancing
+    Tlb,
+    /// Adaptive load balancing, on both the transmit and the receive side
+    Alb,
+    Other(u8),
+}
+
+impl From<u8> for BondMode {
+    fn from(value: u8) -> Self {
+        use self::BondMode::*;
+        match value {
+            BOND_MODE_ROUNDROBIN => RoundRobin,
+            BOND_MODE_ACTIVEBACKUP => ActiveBackup,
+            BOND_MODE_XOR => Xor,
+            BOND_MODE_BROADCAST => Broadcast,
+            BOND_MODE_8023AD => Ieee8023Ad,
+            BOND_MODE_TLB => Tlb,
+            BOND_MODE_ALB => Alb,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<BondMode> for u8 {
+    fn from(value: BondMode) -> Self {
+        use self::BondMode::*;
+        match value {
+            RoundRobin => BOND_MODE_ROUNDROBIN,
+            ActiveBackup => BOND_MODE_ACTIVEBACKUP,
+            Xor => BOND_MODE_XOR,
+            Broadcast => BOND_MODE_BROADCAST,
+            Ieee8023Ad => BOND_MODE_8023AD,
+            Tlb => BOND_MODE_TLB,
+            Alb => BOND_MODE_ALB,
+            Other(other) => other,
+        }
+    }
+}
+
+// https://elixir.bootlin.com/linux/latest/source/net/ipv4/ip_gre.c
+//
+// These attributes are shared by the gre, gretap, ip6gre and ip6gretap link
+// kinds. The family of the local and remote addresses depends on the kind.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InfoGre {
+    /// Index of the underlying device
+    Link(u32),
+    /// `GRE_*` flags of the received packets (`GRE_KEY`, `GRE_SEQ`...)
+    Iflags(u16),
+    /// `GRE_*` flags of the transmitted packets
+    Oflags(u16),
+    /// Key of the received packets, used when `GRE_KEY` is set
+    Ikey(u32),
+    /// Key of the transmitted packets, used when `GRE_KEY` is set
+    Okey(u32),
+    Local(IpAddr),
+    Remote(IpAddr),
+    Ttl(u8),
+    Tos(u8),
+    /// Path MTU discovery, IPv4 only
+    Pmtudisc(u8),
+    /// Tunnel encapsulation limit, IPv6 only
+    EncapLimit(u8),
+    /// Flow label and traffic class, IPv6 only
+    FlowInfo(u32),
+    /// `IP6_TNL_F_*` flags, IPv6 only
+    Flags(u32),
+    /// `TUNNEL_ENCAP_*` type of the outer encapsulation (FOU, GUE...)
+    EncapType(u16),
+    EncapFlags(u16),
+    EncapSport(u16),
+    EncapDport(u16),
+    /// Metadata based (external) tunnel, this is a flag attribute
+    CollectMetadata,
+    IgnoreDf(u8),
+    FwMark(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for InfoGre {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::InfoGre::*;
+        match self {
+            CollectMetadata => 0,
+            Ttl(_)
+                | Tos(_)
+                | Pmtudisc(_)
+                | EncapLimit(_)
+                | IgnoreDf(_)
+                => 1,
+            Iflags(_)
+                | Oflags(_)
+                | EncapType(_)
+                | EncapFlags(_)
+                | EncapSport(_)
+                | EncapDport(_)
+                => 2,
+            Link(_)
+                | Ikey(_)
+                | Okey(_)
+                | FlowInfo(_)
+                | Flags(_)
+                | FwMark(_)
+                => 4,
+            Local(ref addr) | Remote(ref addr) => ip_len(addr),
+            Other(nla) => nla.value_len(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::InfoGre::*;
+        match self {
+            CollectMetadata => {}
+            Ttl(value)
+                | Tos(value)
+                | Pmtudisc(value)
+                | EncapLimit(value)
+                | IgnoreDf(value)
+                => buffer[0] = *value,
+            Iflags(value)
+                | Oflags(value)
+                | EncapSport(value)
+                | EncapDport(value)
+                => BigEndian::write_u16(buffer, *value),
+            EncapType(value)
+                | EncapFlags(value)
+                => NativeEndian::write_u16(buffer, *value),
+            Ikey(value)
+                | Okey(value)
+                | FlowInfo(value)
+                => BigEndian::write_u32(buffer, *value),
+            Link(value)
+                | Flags(value)
+                | FwMark(value)
+                => NativeEndian::write_u32(buffer, *value),
+            Local(ref addr) | Remote(ref addr) => emit_ip(buffer, addr),
+            Other(nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::InfoGre::*;
+        match self {
+            Link(_) => IFLA_GRE_LINK,
+            Iflags(_) => IFLA_GRE_IFLAGS,
+            Oflags(_) => IFLA_GRE_OFLAGS,
+            Ikey(_) => IFLA_GRE_IKEY,
+            Okey(_) => IFLA_GRE_OKEY,
+            Local(_) => IFLA_GRE_LOCAL,
+            Remote(_) => IFLA_GRE_REMOTE,
+            Ttl(_) => IFLA_GRE_TTL,
+            Tos(_) => IFLA_GRE_TOS,
+            Pmtudisc(_) => IFLA_GRE_PMTUDISC,
+            EncapLimit(_) => IFLA_GRE_ENCAP_LIMIT,
+            FlowInfo(_) => IFLA_GRE_FLOWINFO,
+            Flags(_) => IFLA_GRE_FLAGS,
+            EncapType(_) => IFLA_GRE_ENCAP_TYPE,
+            EncapFlags(_) => IFLA_GRE_ENCAP_FLAGS,
+            EncapSport(_) => IFLA_GRE_ENCAP_SPORT,
+            EncapDport(_) => IFLA_GRE_ENCAP_DPORT,
+            CollectMetadata => IFLA_GRE_COLLECT_METADATA,
+            IgnoreDf(_) => IFLA_GRE_IGNORE_DF,
+            FwMark(_) => IFLA_GRE_FWMARK,
+            Other(nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for InfoGre {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::InfoGre::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            IFLA_GRE_LINK => Link(parse_u32(payload).context("invalid IFLA_GRE_LINK value")?),
+            IFLA_GRE_IFLAGS => {
+                Iflags(parse_u16_be(payload).context("invalid IFLA_GRE_IFLAGS value")?)
+            }
+            IFLA_GRE_OFLAGS => {
+                Oflags(parse_u16_be(payload).context("invalid IFLA_GRE_OFLAGS value")?)
+            }
+            IFLA_GRE_IKEY => Ikey(parse_u32_be(payload).context("invalid IFLA_GRE_IKEY value")?),
+            IFLA_GRE_OKEY => Okey(parse_u32_be(payload).context("invalid IFLA_GRE_OKEY value")?),
+            IFLA_GRE_LOCAL => Local(parse_ip(payload).context("invalid IFLA_GRE_LOCAL value")?),
+            IFLA_GRE_REMOTE => Remote(parse_ip(payload).context("invalid IFLA_GRE_REMOTE value")?),
+            IFLA_GRE_TTL => Ttl(parse_u8(payload).context("invalid IFLA_GRE_TTL value")?),
+            IFLA_GRE_TOS => Tos(parse_u8(payload).context("invalid IFLA_GRE_TOS value")?),
+            IFLA_GRE_PMTUDISC => {
+                Pmtudisc(parse_u8(payload).context("invalid IFLA_GRE_PMTUDISC value")?)
+            }
+            IFLA_GRE_ENCAP_LIMIT => {
+                EncapLimit(parse_u8(payload).context("invalid IFLA_GRE_ENCAP_LIMIT value")?)
+            }
+            IFLA_GRE_FLOWINFO => {
+                FlowInfo(parse_u32_be(payload).context("invalid IFLA_GRE_FLOWINFO value")?)
+            }
+            IFLA_GRE_FLAGS => Flags(parse_u32(payload).context("invalid IFLA_GRE_FLAGS value")?),
+            IFLA_GRE_ENCAP_TYPE => {
+                EncapType(parse_u16(payload).context("invalid IFLA_GRE_ENCAP_TYPE value")?)
+            }
+            IFLA_GRE_ENCAP_FLAGS => {
+                EncapFlags(parse_u16(payload).context("invalid IFLA_GRE_ENCAP_FLAGS value")?)
+            }
+            IFLA_GRE_ENCAP_SPORT => {
+                EncapSport(parse_u16_be(payload).context("invalid IFLA_GRE_ENCAP_SPORT value")?)
+            }
+            IFLA_GRE_ENCAP_DPORT => {
+                EncapDport(parse_u16_be(payload).context("invalid IFLA_GRE_ENCAP_DPORT value")?)
+            }
+            IFLA_GRE_COLLECT_METADATA => CollectMetadata,
+            IFLA_GRE_IGNORE_DF => {
+                IgnoreDf(parse_u8(payload).context("invalid IFLA_GRE_IGNORE_DF value")?)
+            }
+            IFLA_GRE_FWMARK => FwMark(parse_u32(payload).context("invalid IFLA_GRE_FWMARK value")?),
+            kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum InfoIpVlan {
     Unspec(Vec<u8>),
@@ -1675,12 +2320,12 @@ mod tests {
 
             0x0c, 0x00, // L = 12
             0x0b, 0x00, // T = 11 (IFLA_BR_BRIDGE_ID)
-            0x80, 0x00, // V (priority) = 128 (0x80)
+            0x80, 0x00, // V (priority) = 32768 (0x8000, big endian)
             0x52, 0x54, 0x00, 0xd7, 0x19, 0x3e, // V (address) = 52:54:00:d7:19:3e
 
             0x0c, 0x00, // L = 12
             0x0a, 0x00, // T = 10 (IFLA_BR_ROOT_ID)
-            0x80, 0x00, // V (priority) = 128 (0x80)
+            0x80, 0x00, // V (priority) = 32768 (0x8000, big endian)
             0x52, 0x54, 0x00, 0xd7, 0x19, 0x3e, // V (address) = 52:54:00:d7:19:3e
 
             0x06, 0x00, // L = 6
@@ -1837,8 +2482,8 @@ mod tests {
             InfoBridge::Priority(0x8000),
             InfoBridge::VlanFiltering(0),
             InfoBridge::GroupFwdMask(0),
-            InfoBridge::BridgeId((128, [0x52, 0x54, 0x00, 0xd7, 0x19, 0x3e])),
-            InfoBridge::RootId((128, [0x52, 0x54, 0x00, 0xd7, 0x19, 0x3e])),
+            InfoBridge::BridgeId((0x8000, [0x52, 0x54, 0x00, 0xd7, 0x19, 0x3e])),
+            InfoBridge::RootId((0x8000, [0x52, 0x54, 0x00, 0xd7, 0x19, 0x3e])),
             InfoBridge::RootPort(0),
             InfoBridge::RootPathCost(0),
             InfoBridge::TopologyChange(0),
@@ -1872,6 +2517,52 @@ mod tests {
         ];
     }
 
+    #[rustfmt::skip]
+    static VRF: [u8; 20] = [
+        0x08, 0x00, // length = 8
+        0x01, 0x00, // type = 1 = IFLA_INFO_KIND
+        0x76, 0x72, 0x66, 0x00, // V = "vrf\0"
+        0x0c, 0x00, // length = 12
+        0x02, 0x00, // type = 2 = IFLA_INFO_DATA
+            0x08, 0x00, // length = 8
+            0x01, 0x00, // type = 1 = IFLA_VRF_TABLE
+            0x64, 0x00, 0x00, 0x00, // V = 100
+    ];
+
+    #[test]
+    fn parse_info_vrf() {
+        let nla = NlaBuffer::new_checked(&VRF[..]).unwrap();
+        let parsed = VecInfo::parse(&nla).unwrap().0;
+        let expected = vec![
+            Info::Kind(InfoKind::Vrf),
+            Info::Data(InfoData::Vrf(vec![InfoVrf::TableId(100)])),
+        ];
+        assert_eq!(expected, parsed);
+
+        let mut vec = vec![0xff; 20];
+        parsed.as_slice().emit(&mut vec);
+        assert_eq!(&vec[..], &VRF[..]);
+    }
+
+    #[rustfmt::skip]
+    static WIREGUARD: [u8; 16] = [
+        0x0e, 0x00, // length = 14
+        0x01, 0x00, // type = 1 = IFLA_INFO_KIND
+        0x77, 0x69, 0x72, 0x65, 0x67, 0x75, 0x61, 0x72, 0x64, 0x00, // V = "wireguard\0"
+        0x00, 0x00, // padding
+    ];
+
+    #[test]
+    fn parse_info_wireguard() {
+        let nla = NlaBuffer::new_checked(&WIREGUARD[..]).unwrap();
+        let parsed = VecInfo::parse(&nla).unwrap().0;
+        assert_eq!(parsed, vec![Info::Kind(InfoKind::Wireguard)]);
+
+        let mut vec = vec![0xff; 16];
+        parsed.as_slice().emit(&mut vec);
+        assert_eq!(&vec[..], &WIREGUARD[..]);
+    }
+
     #[test]
     fn parse_info_kind() {
         let info_kind_nla = NlaBuffer::new_checked(&BRIDGE[..12]).unwrap();
@@ -1989,6 +2680,309 @@ mod tests {
         assert_eq!(&vec[..], &IPVLAN[..]);
     }
 
+    #[rustfmt::skip]
+    static GRE: [u8; 60] = [
+        0x08, 0x00, // length = 8
+        0x01, 0x00, // type = 1 = IFLA_INFO_KIND
+        0x67, 0x72, 0x65, 0x00, // V = "gre\0"
+        0x34, 0x00, // length = 52
+        0x02, 0x00, // type = 2 = IFLA_INFO_DATA
+            0x08, 0x00, // length = 8
+            0x01, 0x00, // type = 1 = IFLA_GRE_LINK
+            0x02, 0x00, 0x00, 0x00, // V = 2
+            0x06, 0x00, // length = 6
+            0x02, 0x00, // type = 2 = IFLA_GRE_IFLAGS
+            0x20, 0x00, // V = GRE_KEY (big endian)
+            0x00, 0x00, // padding
+            0x08, 0x00, // length = 8
+            0x04, 0x00, // type = 4 = IFLA_GRE_IKEY
+            0x00, 0x00, 0x00, 0x2a, // V = 42 (big endian)
+            0x08, 0x00, // length = 8
+            0x06, 0x00, // type = 6 = IFLA_GRE_LOCAL
+            0x0a, 0x00, 0x00, 0x01, // V = 10.0.0.1
+            0x08, 0x00, // length = 8
+            0x07, 0x00, // type = 7 = IFLA_GRE_REMOTE
+            0x0a, 0x00, 0x00, 0x02, // V = 10.0.0.2
+            0x05, 0x00, // length = 5
+            0x08, 0x00, // type = 8 = IFLA_GRE_TTL
+            0x40, // V = 64
+            0x00, 0x00, 0x00, // padding
+    ];
+
+    lazy_static! {
+        static ref GRE_INFO: Vec<InfoGre> = vec![
+            InfoGre::Link(2),
+            InfoGre::Iflags(GRE_KEY),
+            InfoGre::Ikey(42),
+            InfoGre::Local("10.0.0.1".parse().unwrap()),
+            InfoGre::Remote("10.0.0.2".parse().unwrap()),
+            InfoGre::Ttl(64),
+        ];
+    }
+
+    #[test]
+    fn parse_info_gre() {
+        let nla = NlaBuffer::new_checked(&GRE[..]).unwrap();
+        let parsed = VecInfo::parse(&nla).unwrap().0;
+        let expected = vec![
+            Info::Kind(InfoKind::GreTun),
+            Info::Data(InfoData::GreTun(GRE_INFO.clone())),
+        ];
+        assert_eq!(expected, parsed);
+    }
+
+    #[test]
+    fn emit_info_gre() {
+        let nlas = vec![
+            Info::Kind(InfoKind::GreTun),
+            Info::Data(InfoData::GreTun(GRE_INFO.clone())),
+        ];
+
+        assert_eq!(nlas.as_slice().buffer_len(), 60);
+
+        let mut vec = vec![0xff; 60];
+        nlas.as_slice().emit(&mut vec);
+        assert_eq!(&vec[..], &GRE[..]);
+    }
+
+    #[rustfmt::skip]
+    static IP6GRE: [u8; 68] = [
+        0x0b, 0x00, // length = 11
+        0x01, 0x00, // type = 1 = IFLA_INFO_KIND
+        0x69, 0x70, 0x36, 0x67, 0x72, 0x65, 0x00, // V = "ip6gre\0"
+        0x00, // padding
+        0x38, 0x00, // length = 56
+        0x02, 0x00, // type = 2 = IFLA_INFO_DATA
+            0x14, 0x00, // length = 20
+            0x06, 0x00, // type = 6 = IFLA_GRE_LOCAL
+            0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, // V = 2001:db8::1
+            0x14, 0x00, // length = 20
+            0x07, 0x00, // type = 7 = IFLA_GRE_REMOTE
+            0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, // V = 2001:db8::2
+            0x05, 0x00, // length = 5
+            0x0b, 0x00, // type = 11 = IFLA_GRE_ENCAP_LIMIT
+            0x04, // V = 4
+            0x00, 0x00, 0x00, // padding
+            0x04, 0x00, // length = 4
+            0x12, 0x00, // type = 18 = IFLA_GRE_COLLECT_METADATA
+    ];
+
+    #[test]
+    fn parse_info_ip6gre() {
+        let nla = NlaBuffer::new_checked(&IP6GRE[..]).unwrap();
+        let parsed = VecInfo::parse(&nla).unwrap().0;
+        let expected = vec![
+            Info::Kind(InfoKind::GreTun6),
+            Info::Data(InfoData::GreTun6(vec![
+                InfoGre::Local("2001:db8::1".parse().unwrap()),
+                InfoGre::Remote("2001:db8::2".parse().unwrap()),
+                InfoGre::EncapLimit(4),
+                InfoGre::CollectMetadata,
+            ])),
+        ];
+        assert_eq!(expected, parsed);
+
+        let mut vec = vec![0xff; 68];
+        parsed.as_slice().emit(&mut vec);
+        assert_eq!(&vec[..], &IP6GRE[..]);
+    }
+
+    #[rustfmt::skip]
+    static BOND: [u8; 64] = [
+        0x09, 0x00, // length = 9
+        0x01, 0x00, // type = 1 = IFLA_INFO_KIND
+        0x62, 0x6f, 0x6e, 0x64, 0x00, // V = "bond\0"
+        0x00, 0x00, 0x00, // padding
+        0x34, 0x00, // length = 52
+        0x02, 0x00, // type = 2 = IFLA_INFO_DATA
+            0x05, 0x00, // length = 5
+            0x01, 0x00, // type = 1 = IFLA_BOND_MODE
+            0x01, // V = 1 = BOND_MODE_ACTIVEBACKUP
+            0x00, 0x00, 0x00, // padding
+            0x08, 0x00, // length = 8
+            0x03, 0x00, // type = 3 = IFLA_BOND_MIIMON
+            0x64, 0x00, 0x00, 0x00, // V = 100
+            0x14, 0x00, // length = 20
+            0x08, 0x00, // type = 8 = IFLA_BOND_ARP_IP_TARGET
+                0x08, 0x00, // length = 8
+                0x00, 0x00, // type = 0 (index)
+                0x0a, 0x00, 0x00, 0x01, // V = 10.0.0.1
+                0x08, 0x00, // length = 8
+                0x01, 0x00, // type = 1 (index)
+                0x0a, 0x00, 0x00, 0x02, // V = 10.0.0.2
+            0x0a, 0x00, // length = 10
+            0x1a, 0x00, // type = 26 = IFLA_BOND_AD_ACTOR_SYSTEM
+            0x52, 0x54, 0x00, 0xd7, 0x19, 0x3e, // V = 52:54:00:d7:19:3e
+            0x00, 0x00, // padding
+    ];
+
+    lazy_static! {
+        static ref BOND_INFO: Vec<InfoBond> = vec![
+            InfoBond::Mode(BondMode::ActiveBackup),
+            InfoBond::MiiMon(100),
+            InfoBond::ArpIpTarget(vec![Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2)]),
+            InfoBond::AdActorSystem([0x52, 0x54, 0x00, 0xd7, 0x19, 0x3e]),
+        ];
+    }
+
+    #[test]
+    fn parse_info_bond() {
+        let nla = NlaBuffer::new_checked(&BOND[..]).unwrap();
+        let parsed = VecInfo::parse(&nla).unwrap().0;
+        let expected = vec![
+            Info::Kind(InfoKind::Bond),
+            Info::Data(InfoData::Bond(BOND_INFO.clone())),
+        ];
+        assert_eq!(expected, parsed);
+    }
+
+    #[test]
+    fn emit_info_bond() {
+        let nlas = vec![
+            Info::Kind(InfoKind::Bond),
+            Info::Data(InfoData::Bond(BOND_INFO.clone())),
+        ];
+
+        assert_eq!(nlas.as_slice().buffer_len(), 64);
+
+        let mut vec = vec![0xff; 64];
+        nlas.as_slice().emit(&mut vec);
+        assert_eq!(&vec[..], &BOND[..]);
+    }
+
+    #[rustfmt::skip]
+    static VXLAN: [u8; 68] = [
+        0x0a, 0x00, // length = 10
+        0x01, 0x00, // type = 1 = IFLA_INFO_KIND
+        0x76, 0x78, 0x6c, 0x61, 0x6e, 0x00, // V = "vxlan\0"
+        0x00, 0x00, // padding
+        0x38, 0x00, // length = 56
+        0x02, 0x00, // type = 2 = IFLA_INFO_DATA
+            0x08, 0x00, // length = 8
+            0x01, 0x00, // type = 1 = IFLA_VXLAN_ID
+            0x2a, 0x00, 0x00, 0x00, // V = 42
+            0x08, 0x00, // length = 8
+            0x02, 0x00, // type = 2 = IFLA_VXLAN_GROUP
+            0x0a, 0x00, 0x00, 0x02, // V = 10.0.0.2
+            0x08, 0x00, // length = 8
+            0x04, 0x00, // type = 4 = IFLA_VXLAN_LOCAL
+            0x0a, 0x00, 0x00, 0x01, // V = 10.0.0.1
+            0x06, 0x00, // length = 6
+            0x0f, 0x00, // type = 15 = IFLA_VXLAN_PORT
+            0x12, 0xb5, // V = 4789 (big endian)
+            0x00, 0x00, // padding
+            0x08, 0x00, // length = 8
+            0x0a, 0x00, // type = 10 = IFLA_VXLAN_PORT_RANGE
+            0x80, 0x00, 0xff, 0xff, // V = 32768-65535 (big endian)
+            0x05, 0x00, // length = 5
+            0x07, 0x00, // type = 7 = IFLA_VXLAN_LEARNING
+            0x01, // V = 1
+            0x00, 0x00, 0x00, // padding
+            0x04, 0x00, // length = 4
+            0x17, 0x00, // type = 23 = IFLA_VXLAN_GBP
+    ];
+
+    lazy_static! {
+        static ref VXLAN_INFO: Vec<InfoVxlan> = vec![
+            InfoVxlan::Id(42),
+            InfoVxlan::Group(vec![10, 0, 0, 2]),
+            InfoVxlan::Local(vec![10, 0, 0, 1]),
+            InfoVxlan::Port(4789),
+            InfoVxlan::PortRange((32768, 65535)),
+            InfoVxlan::Learning(1),
+            InfoVxlan::Gbp,
+        ];
+    }
+
+    #[test]
+    fn parse_info_vxlan() {
+        let nla = NlaBuffer::new_checked(&VXLAN[..]).unwrap();
+        let parsed = VecInfo::parse(&nla).unwrap().0;
+        let expected = vec![
+            Info::Kind(InfoKind::Vxlan),
+            Info::Data(InfoData::Vxlan(VXLAN_INFO.clone())),
+        ];
+        assert_eq!(expected, parsed);
+    }
+
+    #[test]
+    fn emit_info_vxlan() {
+        let nlas = vec![
+            Info::Kind(InfoKind::Vxlan),
+            Info::Data(InfoData::Vxlan(VXLAN_INFO.clone())),
+        ];
+
+        assert_eq!(nlas.as_slice().buffer_len(), 68);
+
+        let mut vec = vec![0xff; 68];
+        nlas.as_slice().emit(&mut vec);
+        assert_eq!(&vec[..], &VXLAN[..]);
+    }
+
+    #[rustfmt::skip]
+    static VLAN: [u8; 60] = [
+        0x09, 0x00, // length = 9
+        0x01, 0x00, // type = 1 = IFLA_INFO_KIND
+        0x76, 0x6c, 0x61, 0x6e, 0x00, // V = "vlan\0"
+        0x00, 0x00, 0x00, // padding
+        0x30, 0x00, // length = 48
+        0x02, 0x00, // type = 2 = IFLA_INFO_DATA
+            0x06, 0x00, // length = 6
+            0x01, 0x00, // type = 1 = IFLA_VLAN_ID
+            0x64, 0x00, // V = 100
+            0x00, 0x00, // padding
+            0x06, 0x00, // length = 6
+            0x05, 0x00, // type = 5 = IFLA_VLAN_PROTOCOL
+            0x81, 0x00, // V = 0x8100 (ETH_P_8021Q, big endian)
+            0x00, 0x00, // padding
+            0x0c, 0x00, // length = 12
+            0x02, 0x00, // type = 2 = IFLA_VLAN_FLAGS
+            0x01, 0x00, 0x00, 0x00, // flags = VLAN_FLAG_REORDER_HDR
+            0x01, 0x00, 0x00, 0x00, // mask = VLAN_FLAG_REORDER_HDR
+            0x10, 0x00, // length = 16
+            0x03, 0x00, // type = 3 = IFLA_VLAN_EGRESS_QOS
+                0x0c, 0x00, // length = 12
+                0x01, 0x00, // type = 1 = IFLA_VLAN_QOS_MAPPING
+                0x02, 0x00, 0x00, 0x00, // from = 2
+                0x05, 0x00, 0x00, 0x00, // to = 5
+    ];
+
+    lazy_static! {
+        static ref VLAN_INFO: Vec<InfoVlan> = vec![
+            InfoVlan::Id(100),
+            InfoVlan::Protocol(0x8100),
+            InfoVlan::Flags((VlanFlags::REORDER_HDR, VlanFlags::REORDER_HDR)),
+            InfoVlan::EgressQos(vec![VlanQosMapping { from: 2, to: 5 }]),
+        ];
+    }
+
+    #[test]
+    fn parse_info_vlan() {
+        let nla = NlaBuffer::new_checked(&VLAN[..]).unwrap();
+        let parsed = VecInfo::parse(&nla).unwrap().0;
+        let expected = vec![
+            Info::Kind(InfoKind::Vlan),
+            Info::Data(InfoData::Vlan(VLAN_INFO.clone())),
+        ];
+        assert_eq!(expected, parsed);
+    }
+
+    #[test]
+    fn emit_info_vlan() {
+        let nlas = vec![
+            Info::Kind(InfoKind::Vlan),
+            Info::Data(InfoData::Vlan(VLAN_INFO.clone())),
+        ];
+
+        assert_eq!(nlas.as_slice().buffer_len(), 60);
+
+        let mut vec = vec![0xff; 60];
+        nlas.as_slice().emit(&mut vec);
+        assert_eq!(&vec[..], &VLAN[..]);
+    }
+
     #[rustfmt::skip]
     static MACVLAN: [u8; 24] = [
         0x0c, 0x00, // length = 12
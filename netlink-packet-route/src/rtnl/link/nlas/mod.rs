@@ -27,6 +27,12 @@ pub use self::stats64::*;
 mod link_state;
 pub use self::link_state::*;
 
+mod xdp;
+pub use self::xdp::*;
+
+mod brport;
+pub use self::brport::*;
+
 #[cfg(test)]
 mod tests;
 
@@ -56,7 +62,7 @@ pub enum Nla {
     PhysPortId(Vec<u8>),
     PhysSwitchId(Vec<u8>),
     Pad(Vec<u8>),
-    Xdp(Vec<u8>),
+    Xdp(Vec<LinkXdp>),
     Event(Vec<u8>),
     NewNetnsId(Vec<u8>),
     IfNetnsId(Vec<u8>),
@@ -66,6 +72,9 @@ pub enum Nla {
     Info(Vec<Info>),
     Wireless(Vec<u8>),
     ProtoInfo(Vec<u8>),
+    /// Bridge port attributes, reported in a nested `IFLA_PROTINFO` attribute for the ports of a
+    /// bridge
+    ProtoInfoBridge(Vec<BrportNla>),
     /// A list of properties for the device. For additional context see the related linux kernel
     /// threads<sup>[1][1],[2][2]</sup>. In particular see [this message][defining message] from
     /// the first thread describing the design.
@@ -123,6 +132,12 @@ pub enum Nla {
     CarrierChanges(u32),
     GsoMaxSegs(u32),
     GsoMaxSize(u32),
+    /// Maximum size of the packets aggregated by GRO
+    GroMaxSize(u32),
+    /// Maximum size of a TSO packet supported by the device
+    TsoMaxSize(u32),
+    /// Maximum number of segments of a TSO packet supported by the device
+    TsoMaxSegs(u32),
     /// The minimum MTU for the device.
     /// For additional context see the related [linux kernel message][1].
     ///
@@ -138,7 +153,7 @@ pub enum Nla {
     // custom
     OperState(State),
     Stats(Vec<u8>),
-    Stats64(Vec<u8>),
+    Stats64(Stats64),
     Map(Vec<u8>),
     // AF_SPEC (the type of af_spec depends on the interface family of the message)
     AfSpecInet(Vec<AfSpecInet>),
@@ -164,7 +179,6 @@ impl nlas::Nla for Nla {
                 | PhysPortId(ref bytes)
                 | PhysSwitchId(ref bytes)
                 | Pad(ref bytes)
-                | Xdp(ref bytes)
                 | Event(ref bytes)
                 | NewNetnsId(ref bytes)
                 | IfNetnsId(ref bytes)
@@ -212,6 +226,9 @@ impl nlas::Nla for Nla {
                 | CarrierChanges(_)
                 | GsoMaxSegs(_)
                 | GsoMaxSize(_)
+                | GroMaxSize(_)
+                | TsoMaxSize(_)
+                | TsoMaxSegs(_)
                 | NetnsId(_)
                 | MinMtu(_)
                 | MaxMtu(_) => 4,
@@ -222,6 +239,8 @@ impl nlas::Nla for Nla {
             Stats64(_) => LINK_STATS64_LEN,
             Info(ref nlas) => nlas.as_slice().buffer_len(),
             PropList(ref nlas) => nlas.as_slice().buffer_len(),
+            Xdp(ref nlas) => nlas.as_slice().buffer_len(),
+            ProtoInfoBridge(ref nlas) => nlas.as_slice().buffer_len(),
             AfSpecInet(ref nlas) => nlas.as_slice().buffer_len(),
             // AfSpecBridge(ref nlas) => nlas.as_slice().buffer_len(),
             Other(ref attr)  => attr.value_len(),
@@ -245,7 +264,6 @@ impl nlas::Nla for Nla {
                 | Wireless(ref bytes)
                 | ProtoInfo(ref bytes)
                 | Pad(ref bytes)
-                | Xdp(ref bytes)
                 | Event(ref bytes)
                 | NewNetnsId(ref bytes)
                 | IfNetnsId(ref bytes)
@@ -260,7 +278,6 @@ impl nlas::Nla for Nla {
                 | AfSpecUnknown(ref bytes)
                 | AfSpecBridge(ref bytes)
                 | Stats(ref bytes)
-                | Stats64(ref bytes)
                 | Map(ref bytes)
                 | ProtoDownReason(ref bytes)
                 => buffer.copy_from_slice(bytes.as_slice()),
@@ -297,6 +314,9 @@ impl nlas::Nla for Nla {
                 | CarrierChanges(ref value)
                 | GsoMaxSegs(ref value)
                 | GsoMaxSize(ref value)
+                | GroMaxSize(ref value)
+                | TsoMaxSize(ref value)
+                | TsoMaxSegs(ref value)
                 | MinMtu(ref value)
                 | MaxMtu(ref value)
                 => NativeEndian::write_u32(buffer, *value),
@@ -306,8 +326,11 @@ impl nlas::Nla for Nla {
                 => NativeEndian::write_i32(buffer, *value),
 
             OperState(state) => buffer[0] = state.into(),
+            Stats64(ref stats) => stats.emit(buffer),
             Info(ref nlas) => nlas.as_slice().emit(buffer),
             PropList(ref nlas) => nlas.as_slice().emit(buffer),
+            Xdp(ref nlas) => nlas.as_slice().emit(buffer),
+            ProtoInfoBridge(ref nlas) => nlas.as_slice().emit(buffer),
             AfSpecInet(ref nlas) => nlas.as_slice().emit(buffer),
             // AfSpecBridge(ref nlas) => nlas.as_slice().emit(buffer),
             // default nlas
@@ -331,8 +354,9 @@ impl nlas::Nla for Nla {
             Info(_) => IFLA_LINKINFO,
             Wireless(_) => IFLA_WIRELESS,
             ProtoInfo(_) => IFLA_PROTINFO,
+            ProtoInfoBridge(_) => IFLA_PROTINFO | NLA_F_NESTED,
             Pad(_) => IFLA_PAD,
-            Xdp(_) => IFLA_XDP,
+            Xdp(_) => IFLA_XDP | NLA_F_NESTED,
             Event(_) => IFLA_EVENT,
             NewNetnsId(_) => IFLA_NEW_NETNSID,
             IfNetnsId(_) => IFLA_IF_NETNSID,
@@ -371,6 +395,9 @@ impl nlas::Nla for Nla {
             CarrierChanges(_) => IFLA_CARRIER_CHANGES,
             GsoMaxSegs(_) => IFLA_GSO_MAX_SEGS,
             GsoMaxSize(_) => IFLA_GSO_MAX_SIZE,
+            GroMaxSize(_) => IFLA_GRO_MAX_SIZE,
+            TsoMaxSize(_) => IFLA_TSO_MAX_SIZE,
+            TsoMaxSegs(_) => IFLA_TSO_MAX_SEGS,
             MinMtu(_) => IFLA_MIN_MTU,
             MaxMtu(_) => IFLA_MAX_MTU,
             // i32
@@ -405,9 +432,28 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, u16> f
             IFLA_PHYS_PORT_ID => PhysPortId(payload.to_vec()),
             IFLA_PHYS_SWITCH_ID => PhysSwitchId(payload.to_vec()),
             IFLA_WIRELESS => Wireless(payload.to_vec()),
+            // Only the bridge sets NLA_F_NESTED on IFLA_PROTINFO: IPv6 uses the same attribute
+            // without the flag for its own information.
+            IFLA_PROTINFO if buf.nested_flag() => {
+                let error_msg = "invalid IFLA_PROTINFO value";
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = &nla.context(error_msg)?;
+                    nlas.push(BrportNla::parse(nla).context(error_msg)?);
+                }
+                ProtoInfoBridge(nlas)
+            }
             IFLA_PROTINFO => ProtoInfo(payload.to_vec()),
             IFLA_PAD => Pad(payload.to_vec()),
-            IFLA_XDP => Xdp(payload.to_vec()),
+            IFLA_XDP => {
+                let error_msg = "invalid IFLA_XDP value";
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = &nla.context(error_msg)?;
+                    nlas.push(LinkXdp::parse(nla).context(error_msg)?);
+                }
+                Xdp(nlas)
+            }
             IFLA_EVENT => Event(payload.to_vec()),
             IFLA_NEW_NETNSID => NewNetnsId(payload.to_vec()),
             IFLA_IF_NETNSID => IfNetnsId(payload.to_vec()),
@@ -477,6 +523,15 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, u16> f
             IFLA_GSO_MAX_SIZE => {
                 GsoMaxSize(parse_u32(payload).context("invalid IFLA_GSO_MAX_SIZE value")?)
             }
+            IFLA_GRO_MAX_SIZE => {
+                GroMaxSize(parse_u32(payload).context("invalid IFLA_GRO_MAX_SIZE value")?)
+            }
+            IFLA_TSO_MAX_SIZE => {
+                TsoMaxSize(parse_u32(payload).context("invalid IFLA_TSO_MAX_SIZE value")?)
+            }
+            IFLA_TSO_MAX_SEGS => {
+                TsoMaxSegs(parse_u32(payload).context("invalid IFLA_TSO_MAX_SEGS value")?)
+            }
             IFLA_MIN_MTU => MinMtu(parse_u32(payload).context("invalid IFLA_MIN_MTU value")?),
             IFLA_MAX_MTU => MaxMtu(parse_u32(payload).context("invalid IFLA_MAX_MTU value")?),
             IFLA_LINK_NETNSID => {
@@ -489,7 +544,15 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, u16> f
             ),
             IFLA_MAP => Map(payload.to_vec()),
             IFLA_STATS => Stats(payload.to_vec()),
-            IFLA_STATS64 => Stats64(payload.to_vec()),
+            IFLA_STATS64 => {
+                let err = "invalid IFLA_STATS64 value";
+                // Older kernels don't report the last counters: rx_otherhost_dropped was added in
+                // Linux 5.19 and rx_nohandler in Linux 4.6. They are left to 0.
+                let mut stats = [0; LINK_STATS64_LEN];
+                let len = payload.len().min(LINK_STATS64_LEN);
+                stats[..len].copy_from_slice(&payload[..len]);
+                Stats64(stats64::Stats64::parse(&Stats64Buffer::new(&stats[..])).context(err)?)
+            }
             IFLA_AF_SPEC => match interface_family as u16 {
                 AF_INET | AF_INET6 | AF_UNSPEC => {
                     let mut nlas = vec![];
@@ -5,7 +5,7 @@ use crate::{
     DecodeError,
 };
 
-pub const LINK_STATS64_LEN: usize = 192;
+pub const LINK_STATS64_LEN: usize = 200;
 buffer!(Stats64Buffer(LINK_STATS64_LEN) {
     rx_packets: (u64, 0..8),
     tx_packets: (u64, 8..16),
@@ -31,9 +31,13 @@ buffer!(Stats64Buffer(LINK_STATS64_LEN) {
     rx_compressed: (u64, 168..176),
     tx_compressed: (u64, 176..184),
     rx_nohandler: (u64, 184..192),
+    rx_otherhost_dropped: (u64, 192..200),
 });
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+/// 64 bits link statistics (`struct rtnl_link_stats64`). The kernel reports them with both
+/// `IFLA_STATS` and `IFLA_STATS64`, but the 32 bits counters of the former wrap quickly on fast
+/// links, so this one should be preferred when available.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
 pub struct Stats64 {
     /// total packets received
     pub rx_packets: u64,
@@ -81,6 +85,9 @@ pub struct Stats64 {
 
     /// dropped, no handler found
     pub rx_nohandler: u64,
+
+    /// dropped because of a mismatching destination MAC address (Linux 5.19 and later)
+    pub rx_otherhost_dropped: u64,
 }
 
 impl<T: AsRef<[u8]>> Parseable<Stats64Buffer<T>> for Stats64 {
@@ -110,6 +117,7 @@ impl<T: AsRef<[u8]>> Parseable<Stats64Buffer<T>> for Stats64 {
             rx_compressed: buf.rx_compressed(),
             tx_compressed: buf.tx_compressed(),
             rx_nohandler: buf.rx_nohandler(),
+            rx_otherhost_dropped: buf.rx_otherhost_dropped(),
         })
     }
 }
@@ -145,5 +153,6 @@ impl Emitable for Stats64 {
         buffer.set_rx_compressed(self.rx_compressed);
         buffer.set_tx_compressed(self.tx_compressed);
         buffer.set_rx_nohandler(self.rx_nohandler);
+        buffer.set_rx_otherhost_dropped(self.rx_otherhost_dropped);
     }
 }
@@ -185,13 +185,13 @@ fn get_byte_buffer(nla: &dyn Emitable) -> Vec<u8> {
 lazy_static! {
     static ref PARSED_AF_INET6: AfSpecInet = AfSpecInet::Inet6(vec![
         Inet6::Flags(2147483648),
-        Inet6::CacheInfo(get_byte_buffer(&Inet6CacheInfo {
+        Inet6::CacheInfo(Inet6CacheInfo {
             max_reasm_len: 65535,
             tstamp: 175,
             reachable_time: 25730,
             retrans_time: 1000,
-        })),
-        Inet6::DevConf(get_byte_buffer(&Inet6DevConf {
+        }),
+        Inet6::DevConf(Inet6DevConf {
             forwarding: 0,
             hoplimit: 64,
             mtu6: 65536,
@@ -243,7 +243,7 @@ lazy_static! {
             disable_policy: 0,
             accept_ra_rt_info_min_plen: 0,
             ndisc_tclass: 0,
-        })),
+        }),
         Inet6::Stats(get_byte_buffer(&Inet6Stats {
             num: 36,
             in_pkts: 6,
@@ -296,40 +296,39 @@ lazy_static! {
 }
 
 lazy_static! {
-    static ref PARSED_AF_INET: AfSpecInet =
-        AfSpecInet::Inet(vec![Inet::DevConf(get_byte_buffer(&InetDevConf {
-            forwarding: 1,
-            mc_forwarding: 0,
-            proxy_arp: 0,
-            accept_redirects: 1,
-            secure_redirects: 1,
-            send_redirects: 1,
-            shared_media: 1,
-            rp_filter: 0,
-            accept_source_route: 1,
-            bootp_relay: 0,
-            log_martians: 0,
-            tag: 0,
-            arpfilter: 0,
-            medium_id: 0,
-            noxfrm: 1,
-            nopolicy: 1,
-            force_igmp_version: 0,
-            arp_announce: 0,
-            arp_ignore: 0,
-            promote_secondaries: 0,
-            arp_accept: 0,
-            arp_notify: 0,
-            accept_local: 0,
-            src_vmark: 0,
-            proxy_arp_pvlan: 0,
-            route_localnet: 0,
-            igmpv2_unsolicited_report_interval: 10000,
-            igmpv3_unsolicited_report_interval: 1000,
-            ignore_routes_with_linkdown: 0,
-            drop_unicast_in_l2_multicast: 0,
-            drop_gratuitous_arp: 0,
-        }))]);
+    static ref PARSED_AF_INET: AfSpecInet = AfSpecInet::Inet(vec![Inet::DevConf(InetDevConf {
+        forwarding: 1,
+        mc_forwarding: 0,
+        proxy_arp: 0,
+        accept_redirects: 1,
+        secure_redirects: 1,
+        send_redirects: 1,
+        shared_media: 1,
+        rp_filter: 0,
+        accept_source_route: 1,
+        bootp_relay: 0,
+        log_martians: 0,
+        tag: 0,
+        arpfilter: 0,
+        medium_id: 0,
+        noxfrm: 1,
+        nopolicy: 1,
+        force_igmp_version: 0,
+        arp_announce: 0,
+        arp_ignore: 0,
+        promote_secondaries: 0,
+        arp_accept: 0,
+        arp_notify: 0,
+        accept_local: 0,
+        src_vmark: 0,
+        proxy_arp_pvlan: 0,
+        route_localnet: 0,
+        igmpv2_unsolicited_report_interval: 10000,
+        igmpv3_unsolicited_report_interval: 1000,
+        ignore_routes_with_linkdown: 0,
+        drop_unicast_in_l2_multicast: 0,
+        drop_gratuitous_arp: 0,
+    })]);
 }
 
 #[test]
@@ -411,3 +410,114 @@ fn parse_af_inet6() {
     // Normally this is the end of the nla iterator
     assert!(nlas.next().is_none());
 }
+
+#[test]
+fn parse_forwarding() {
+    let mut nlas = get_nlas();
+    let inet = AfSpecInet::parse(&nlas.next().unwrap().unwrap()).unwrap();
+    let inet6 = AfSpecInet::parse(&nlas.next().unwrap().unwrap()).unwrap();
+
+    match inet {
+        AfSpecInet::Inet(nlas) => match nlas[0] {
+            Inet::DevConf(ref dev_conf) => assert_eq!(dev_conf.forwarding, 1),
+            ref nla => panic!("unexpected nla {:?}", nla),
+        },
+        nla => panic!("unexpected nla {:?}", nla),
+    }
+    match inet6 {
+        AfSpecInet::Inet6(nlas) => match nlas[2] {
+            Inet6::DevConf(ref dev_conf) => assert_eq!(dev_conf.forwarding, 0),
+            ref nla => panic!("unexpected nla {:?}", nla),
+        },
+        nla => panic!("unexpected nla {:?}", nla),
+    }
+}
+
+#[test]
+fn parse_short_dev_conf() {
+    // older kernels report less settings
+    #[rustfmt::skip]
+    let bytes = [
+        0x0c, 0x00, // length = 12
+        0x01, 0x00, // type = IFLA_INET_CONF
+        0x01, 0x00, 0x00, 0x00, // forwarding = 1
+        0x01, 0x00, 0x00, 0x00, // mc_forwarding = 1
+    ];
+    let parsed = Inet::parse(&NlaBuffer::new_checked(&bytes[..]).unwrap()).unwrap();
+    match parsed {
+        Inet::DevConf(dev_conf) => {
+            assert_eq!(dev_conf.forwarding, 1);
+            assert_eq!(dev_conf.mc_forwarding, 1);
+            assert_eq!(dev_conf.proxy_arp, 0);
+        }
+        nla => panic!("unexpected nla {:?}", nla),
+    }
+}
+
+#[test]
+fn parse_nested_flag() {
+    // recent kernels set NLA_F_NESTED on nested attributes such as IFLA_LINKINFO
+    #[rustfmt::skip]
+    let bytes = [
+        0x10, 0x00, // length = 16
+        0x12, 0x80, // type = IFLA_LINKINFO | NLA_F_NESTED
+            0x09, 0x00, // length = 9
+            0x01, 0x00, // type = IFLA_INFO_KIND
+            0x76, 0x6c, 0x61, 0x6e, // "vlan\0"
+            0x00, 0x00, 0x00, 0x00, // padding
+    ];
+    let buf = NlaBuffer::new_checked(&bytes[..]).unwrap();
+    assert_eq!(buf.kind(), IFLA_LINKINFO);
+    assert!(buf.nested_flag());
+
+    let parsed = super::Nla::parse_with_param(&buf, AF_INET).unwrap();
+    assert_eq!(parsed, super::Nla::Info(vec![Info::Kind(InfoKind::Vlan)]));
+}
+
+#[rustfmt::skip]
+static BRPORT: [u8; 44] = [
+    0x2c, 0x00, // length = 44
+    0x0c, 0x80, // type = IFLA_PROTINFO | NLA_F_NESTED
+        0x05, 0x00, 0x01, 0x00, // length = 5, type = IFLA_BRPORT_STATE
+        0x03, 0x00, 0x00, 0x00, // BR_STATE_FORWARDING + 3 bytes of padding
+        0x06, 0x00, 0x02, 0x00, // length = 6, type = IFLA_BRPORT_PRIORITY
+        0x20, 0x00, 0x00, 0x00, // 32 + 2 bytes of padding
+        0x08, 0x00, 0x03, 0x00, // length = 8, type = IFLA_BRPORT_COST
+        0x64, 0x00, 0x00, 0x00, // 100
+        0x05, 0x00, 0x04, 0x00, // length = 5, type = IFLA_BRPORT_MODE
+        0x00, 0x00, 0x00, 0x00, // false + 3 bytes of padding
+        0x05, 0x00, 0x21, 0x00, // length = 5, type = IFLA_BRPORT_ISOLATED
+        0x01, 0x00, 0x00, 0x00, // true + 3 bytes of padding
+];
+
+lazy_static! {
+    static ref PARSED_BRPORT: super::Nla = super::Nla::ProtoInfoBridge(vec![
+        BrportNla::State(BR_STATE_FORWARDING),
+        BrportNla::Priority(32),
+        BrportNla::Cost(100),
+        BrportNla::Mode(false),
+        BrportNla::Isolated(true),
+    ]);
+}
+
+#[test]
+fn parse_brport() {
+    let buf = NlaBuffer::new_checked(&BRPORT[..]).unwrap();
+    let parsed = super::Nla::parse_with_param(&buf, AF_BRIDGE).unwrap();
+    assert_eq!(parsed, *PARSED_BRPORT);
+}
+
+#[test]
+fn emit_brport() {
+    assert_eq!(get_byte_buffer(&*PARSED_BRPORT), &BRPORT[..]);
+}
+
+#[test]
+fn parse_protinfo_without_nested_flag() {
+    // IPv6 reports its own attributes in IFLA_PROTINFO, without NLA_F_NESTED
+    let mut bytes = BRPORT;
+    bytes[3] = 0x00;
+    let buf = NlaBuffer::new_checked(&bytes[..]).unwrap();
+    let parsed = super::Nla::parse_with_param(&buf, AF_INET6).unwrap();
+    assert_eq!(parsed, super::Nla::ProtoInfo(BRPORT[4..].to_vec()));
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: MIT
+
+use std::{mem::size_of, os::unix::io::RawFd};
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{DefaultNla, Nla, NlaBuffer},
+    parsers::{parse_i32, parse_u32, parse_u8},
+    traits::Parseable,
+    DecodeError,
+};
+
+/// Attributes of `IFLA_XDP`. To attach a program, set [`LinkXdp::Fd`] to the file descriptor of
+/// the program (or -1 to detach it) in a `RTM_NEWLINK` or `RTM_SETLINK` message, possibly with
+/// some `XDP_FLAGS_*` [`LinkXdp::Flags`]. The kernel reports the current attachment with
+/// [`LinkXdp::Attached`] and [`LinkXdp::ProgId`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LinkXdp {
+    Fd(RawFd),
+    Attached(XdpAttached),
+    /// `XDP_FLAGS_*`
+    Flags(u32),
+    /// ID of the attached program
+    ProgId(u32),
+    /// ID of the program attached in driver mode, when several modes are used
+    DrvProgId(u32),
+    /// ID of the program attached in generic (skb) mode, when several modes are used
+    SkbProgId(u32),
+    /// ID of the program offloaded to the hardware, when several modes are used
+    HwProgId(u32),
+    /// With `XDP_FLAGS_REPLACE`, file descriptor of the program expected to be replaced
+    ExpectedFd(RawFd),
+    Other(DefaultNla),
+}
+
+impl Nla for LinkXdp {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::LinkXdp::*;
+        match *self {
+            Attached(_) => size_of::<u8>(),
+            Fd(_)
+                | Flags(_)
+                | ProgId(_)
+                | DrvProgId(_)
+                | SkbProgId(_)
+                | HwProgId(_)
+                | ExpectedFd(_) => size_of::<u32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::LinkXdp::*;
+        match *self {
+            Attached(value) => buffer[0] = value.into(),
+            Fd(value) | ExpectedFd(value) => NativeEndian::write_i32(buffer, value),
+            Flags(value)
+                | ProgId(value)
+                | DrvProgId(value)
+                | SkbProgId(value)
+                | HwProgId(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::LinkXdp::*;
+        match *self {
+            Fd(_) => IFLA_XDP_FD,
+            Attached(_) => IFLA_XDP_ATTACHED,
+            Flags(_) => IFLA_XDP_FLAGS,
+            ProgId(_) => IFLA_XDP_PROG_ID,
+            DrvProgId(_) => IFLA_XDP_DRV_PROG_ID,
+            SkbProgId(_) => IFLA_XDP_SKB_PROG_ID,
+            HwProgId(_) => IFLA_XDP_HW_PROG_ID,
+            ExpectedFd(_) => IFLA_XDP_EXPECTED_FD,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for LinkXdp {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::LinkXdp::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            IFLA_XDP_FD => Fd(parse_i32(payload).context("invalid IFLA_XDP_FD value")?),
+            IFLA_XDP_ATTACHED => Attached(
+                parse_u8(payload)
+                    .context("invalid IFLA_XDP_ATTACHED value")?
+                    .into(),
+            ),
+            IFLA_XDP_FLAGS => Flags(parse_u32(payload).context("invalid IFLA_XDP_FLAGS value")?),
+            IFLA_XDP_PROG_ID => {
+                ProgId(parse_u32(payload).context("invalid IFLA_XDP_PROG_ID value")?)
+            }
+            IFLA_XDP_DRV_PROG_ID => {
+                DrvProgId(parse_u32(payload).context("invalid IFLA_XDP_DRV_PROG_ID value")?)
+            }
+            IFLA_XDP_SKB_PROG_ID => {
+                SkbProgId(parse_u32(payload).context("invalid IFLA_XDP_SKB_PROG_ID value")?)
+            }
+            IFLA_XDP_HW_PROG_ID => {
+                HwProgId(parse_u32(payload).context("invalid IFLA_XDP_HW_PROG_ID value")?)
+            }
+            IFLA_XDP_EXPECTED_FD => {
+                ExpectedFd(parse_i32(payload).context("invalid IFLA_XDP_EXPECTED_FD value")?)
+            }
+            kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
+        })
+    }
+}
+
+/// How an XDP program is attached to a link
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum XdpAttached {
+    /// No program is attached
+    None,
+    /// The program is run by the driver
+    Driver,
+    /// The program is run by the generic XDP implementation of the kernel
+    Skb,
+    /// The program is offloaded to the hardware
+    Hardware,
+    /// Several programs are attached in different modes
+    Multiple,
+    Other(u8),
+}
+
+impl From<u8> for XdpAttached {
+    fn from(value: u8) -> Self {
+        use self::XdpAttached::*;
+        match value {
+            XDP_ATTACHED_NONE => None,
+            XDP_ATTACHED_DRV => Driver,
+            XDP_ATTACHED_SKB => Skb,
+            XDP_ATTACHED_HW => Hardware,
+            XDP_ATTACHED_MULTI => Multiple,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<XdpAttached> for u8 {
+    fn from(value: XdpAttached) -> Self {
+        use self::XdpAttached::*;
+        match value {
+            None => XDP_ATTACHED_NONE,
+            Driver => XDP_ATTACHED_DRV,
+            Skb => XDP_ATTACHED_SKB,
+            Hardware => XDP_ATTACHED_HW,
+            Multiple => XDP_ATTACHED_MULTI,
+            Other(other) => other,
+        }
+    }
+}
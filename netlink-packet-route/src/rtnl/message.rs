@@ -2,6 +2,7 @@
 
 use crate::{
     constants::*,
+    nlas::tc::{Class, Filter, Qdisc},
     traits::{Emitable, ParseableParametrized},
     AddressMessage,
     DecodeError,
@@ -12,6 +13,7 @@ use crate::{
     NetlinkHeader,
     NetlinkPayload,
     NetlinkSerializable,
+    NexthopMessage,
     NsidMessage,
     RouteMessage,
     RtnlMessageBuffer,
@@ -39,24 +41,27 @@ pub enum RtnlMessage {
     NewRoute(RouteMessage),
     DelRoute(RouteMessage),
     GetRoute(RouteMessage),
-    NewQueueDiscipline(TcMessage),
-    DelQueueDiscipline(TcMessage),
-    GetQueueDiscipline(TcMessage),
-    NewTrafficClass(TcMessage),
-    DelTrafficClass(TcMessage),
-    GetTrafficClass(TcMessage),
-    NewTrafficFilter(TcMessage),
-    DelTrafficFilter(TcMessage),
-    GetTrafficFilter(TcMessage),
-    NewTrafficChain(TcMessage),
-    DelTrafficChain(TcMessage),
-    GetTrafficChain(TcMessage),
+    NewQueueDiscipline(TcMessage<Qdisc>),
+    DelQueueDiscipline(TcMessage<Qdisc>),
+    GetQueueDiscipline(TcMessage<Qdisc>),
+    NewTrafficClass(TcMessage<Class>),
+    DelTrafficClass(TcMessage<Class>),
+    GetTrafficClass(TcMessage<Class>),
+    NewTrafficFilter(TcMessage<Filter>),
+    DelTrafficFilter(TcMessage<Filter>),
+    GetTrafficFilter(TcMessage<Filter>),
+    NewTrafficChain(TcMessage<Filter>),
+    DelTrafficChain(TcMessage<Filter>),
+    GetTrafficChain(TcMessage<Filter>),
     NewNsId(NsidMessage),
     DelNsId(NsidMessage),
     GetNsId(NsidMessage),
     NewRule(RuleMessage),
     DelRule(RuleMessage),
     GetRule(RuleMessage),
+    NewNexthop(NexthopMessage),
+    DelNexthop(NexthopMessage),
+    GetNexthop(NexthopMessage),
 }
 
 impl RtnlMessage {
@@ -196,6 +201,18 @@ impl RtnlMessage {
         matches!(self, RtnlMessage::DelRule(_))
     }
 
+    pub fn is_new_nexthop(&self) -> bool {
+        matches!(self, RtnlMessage::NewNexthop(_))
+    }
+
+    pub fn is_del_nexthop(&self) -> bool {
+        matches!(self, RtnlMessage::DelNexthop(_))
+    }
+
+    pub fn is_get_nexthop(&self) -> bool {
+        matches!(self, RtnlMessage::GetNexthop(_))
+    }
+
     pub fn message_type(&self) -> u16 {
         use self::RtnlMessage::*;
 
@@ -236,6 +253,9 @@ impl RtnlMessage {
             GetRule(_) => RTM_GETRULE,
             NewRule(_) => RTM_NEWRULE,
             DelRule(_) => RTM_DELRULE,
+            NewNexthop(_) => RTM_NEWNEXTHOP,
+            DelNexthop(_) => RTM_DELNEXTHOP,
+            GetNexthop(_) => RTM_GETNEXTHOP,
         }
     }
 }
@@ -276,9 +296,13 @@ impl Emitable for RtnlMessage {
             | NewQueueDiscipline(ref msg)
             | DelQueueDiscipline(ref msg)
             | GetQueueDiscipline(ref msg)
+            => msg.buffer_len(),
+
             | NewTrafficClass(ref msg)
             | DelTrafficClass(ref msg)
             | GetTrafficClass(ref msg)
+            => msg.buffer_len(),
+
             | NewTrafficFilter(ref msg)
             | DelTrafficFilter(ref msg)
             | GetTrafficFilter(ref msg)
@@ -295,6 +319,11 @@ impl Emitable for RtnlMessage {
             | NewRule(ref msg)
             | DelRule(ref msg)
             | GetRule(ref msg)
+            => msg.buffer_len(),
+
+            | NewNexthop(ref msg)
+            | DelNexthop(ref msg)
+            | GetNexthop(ref msg)
             => msg.buffer_len()
         }
     }
@@ -334,9 +363,13 @@ impl Emitable for RtnlMessage {
             | NewQueueDiscipline(ref msg)
             | DelQueueDiscipline(ref msg)
             | GetQueueDiscipline(ref msg)
+            => msg.emit(buffer),
+
             | NewTrafficClass(ref msg)
             | DelTrafficClass(ref msg)
             | GetTrafficClass(ref msg)
+            => msg.emit(buffer),
+
             | NewTrafficFilter(ref msg)
             | DelTrafficFilter(ref msg)
             | GetTrafficFilter(ref msg)
@@ -353,6 +386,11 @@ impl Emitable for RtnlMessage {
             | NewRule(ref msg)
             | DelRule(ref msg)
             | GetRule(ref msg)
+            => msg.emit(buffer),
+
+            | NewNexthop(ref msg)
+            | DelNexthop(ref msg)
+            | GetNexthop(ref msg)
             => msg.emit(buffer)
         }
     }
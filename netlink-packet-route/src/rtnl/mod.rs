@@ -1,7 +1,13 @@
 // SPDX-License-Identifier: MIT
 
 pub mod address;
-pub use address::{AddressHeader, AddressMessage, AddressMessageBuffer, ADDRESS_HEADER_LEN};
+pub use address::{
+    AddressFlags,
+    AddressHeader,
+    AddressMessage,
+    AddressMessageBuffer,
+    ADDRESS_HEADER_LEN,
+};
 
 pub mod link;
 pub use link::{LinkHeader, LinkMessage, LinkMessageBuffer, LINK_HEADER_LEN};
@@ -22,17 +28,29 @@ pub use neighbour_table::{
     NEIGHBOUR_TABLE_HEADER_LEN,
 };
 
+pub mod nexthop;
+pub use nexthop::{NexthopHeader, NexthopMessage, NexthopMessageBuffer, NEXTHOP_HEADER_LEN};
+
 pub mod nsid;
 pub use nsid::{NsidHeader, NsidMessage, NsidMessageBuffer, NSID_HEADER_LEN};
 
 pub mod route;
-pub use route::{RouteFlags, RouteHeader, RouteMessage, RouteMessageBuffer, ROUTE_HEADER_LEN};
+pub use route::{
+    RouteFlags,
+    RouteHeader,
+    RouteMessage,
+    RouteMessageBuffer,
+    RouteProtocol,
+    RouteScope,
+    RouteType,
+    ROUTE_HEADER_LEN,
+};
 
 pub mod rule;
-pub use rule::{RuleHeader, RuleMessage, RuleMessageBuffer, RULE_HEADER_LEN};
+pub use rule::{RuleAction, RuleHeader, RuleMessage, RuleMessageBuffer, RULE_HEADER_LEN};
 
 pub mod tc;
-pub use tc::{TcHeader, TcMessage, TcMessageBuffer, TC_HEADER_LEN};
+pub use tc::{TcHandle, TcHeader, TcMessage, TcMessageBuffer, TcMessageBuilder, TC_HEADER_LEN};
 
 pub mod constants;
 pub use self::constants::*;
@@ -49,6 +67,7 @@ pub mod nlas {
         link::nlas as link,
         neighbour::nlas as neighbour,
         neighbour_table::nlas as neighbour_table,
+        nexthop::nlas as nexthop,
         nsid::nlas as nsid,
         route::nlas as route,
         rule::nlas as rule,
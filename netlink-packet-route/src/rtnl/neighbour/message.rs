@@ -53,7 +53,8 @@ impl<'a, T: AsRef<[u8]> + 'a> Parseable<NeighbourMessageBuffer<&'a T>> for Vec<N
 mod test {
     use crate::{
         constants::*,
-        traits::Emitable,
+        nlas::neighbour::{CacheInfo, Nla},
+        traits::{Emitable, Parseable},
         NeighbourHeader,
         NeighbourMessage,
         NeighbourMessageBuffer,
@@ -119,4 +120,65 @@ mod test {
         assert_eq!(packet.buffer_len(), 12);
         packet.emit(&mut buf[..]);
     }
+
+    #[rustfmt::skip]
+    static MESSAGE: [u8; 72] = [
+        0x0a, // interface family (inet6)
+        0x00, 0x00, 0x00, // padding
+        0x02, 0x00, 0x00, 0x00, // interface index = 2
+        0x02, 0x00, // state NUD_REACHABLE
+        0x80, // flags NTF_ROUTER
+        0x01, // ntype
+        0x14, 0x00, // length = 20
+        0x01, 0x00, // type = NDA_DST
+        0x2a, 0x02, 0x80, 0x10, 0x66, 0xd5, 0x00, 0x00,
+        0xf6, 0x90, 0xea, 0xff, 0xfe, 0x00, 0x2d, 0x83, // 2a02:8010:66d5::f690:eaff:fe00:2d83
+        0x0a, 0x00, // length = 10
+        0x02, 0x00, // type = NDA_LLADDR
+        0xf4, 0x90, 0xea, 0x00, 0x2d, 0x83, // f4:90:ea:00:2d:83
+        0x00, 0x00, // padding
+        0x08, 0x00, // length = 8
+        0x04, 0x00, // type = NDA_PROBES
+        0x01, 0x00, 0x00, 0x00, // 1
+        0x14, 0x00, // length = 20
+        0x03, 0x00, // type = NDA_CACHEINFO
+        0x00, 0x00, 0x00, 0x00, // confirmed
+        0x00, 0x00, 0x00, 0x00, // used
+        0x00, 0x00, 0x00, 0x00, // updated
+        0x02, 0x00, 0x00, 0x00, // refcnt
+    ];
+
+    #[test]
+    fn parse_and_emit_nlas() {
+        let expected = NeighbourMessage {
+            header: NeighbourHeader {
+                family: AF_INET6 as u8,
+                ifindex: 2,
+                state: NUD_REACHABLE,
+                flags: NTF_ROUTER,
+                ntype: NDA_DST as u8,
+            },
+            nlas: vec![
+                Nla::Destination(vec![
+                    0x2a, 0x02, 0x80, 0x10, 0x66, 0xd5, 0x00, 0x00, 0xf6, 0x90, 0xea, 0xff, 0xfe,
+                    0x00, 0x2d, 0x83,
+                ]),
+                Nla::LinkLocalAddress(vec![0xf4, 0x90, 0xea, 0x00, 0x2d, 0x83]),
+                Nla::Probes(1),
+                Nla::CacheInfo(CacheInfo {
+                    confirmed: 0,
+                    used: 0,
+                    updated: 0,
+                    refcnt: 2,
+                }),
+            ],
+        };
+        let parsed = NeighbourMessage::parse(&NeighbourMessageBuffer::new(&&MESSAGE[..])).unwrap();
+        assert_eq!(parsed, expected);
+
+        let mut buf = [0; 72];
+        assert_eq!(expected.buffer_len(), 72);
+        expected.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &MESSAGE[..]);
+    }
 }
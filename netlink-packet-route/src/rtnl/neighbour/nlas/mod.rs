@@ -4,13 +4,13 @@ mod cache_info;
 pub use self::cache_info::*;
 
 use anyhow::Context;
-use byteorder::{ByteOrder, NativeEndian};
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
 
 use crate::{
     constants::*,
     nlas::{self, DefaultNla, NlaBuffer},
-    parsers::{parse_u16, parse_u32},
-    traits::Parseable,
+    parsers::{parse_i32, parse_u16, parse_u16_be, parse_u32},
+    traits::{Emitable, Parseable},
     DecodeError,
 };
 
@@ -19,14 +19,15 @@ pub enum Nla {
     Unspec(Vec<u8>),
     Destination(Vec<u8>),
     LinkLocalAddress(Vec<u8>),
-    CacheInfo(Vec<u8>),
-    Probes(Vec<u8>),
+    CacheInfo(CacheInfo),
+    Probes(u32),
     Vlan(u16),
-    Port(Vec<u8>),
+    /// UDP destination port of the VXLAN remote
+    Port(u16),
     Vni(u32),
     IfIndex(u32),
-    Master(Vec<u8>),
-    LinkNetNsId(Vec<u8>),
+    Master(u32),
+    LinkNetNsId(i32),
     SourceVni(u32),
     Other(DefaultNla),
 }
@@ -38,15 +39,15 @@ impl nlas::Nla for Nla {
         match *self {
             Unspec(ref bytes)
             | Destination(ref bytes)
-            | LinkLocalAddress(ref bytes)
-            | Probes(ref bytes)
-            | Port(ref bytes)
-            | Master(ref bytes)
-            | CacheInfo(ref bytes)
-            | LinkNetNsId(ref bytes) => bytes.len(),
-            Vlan(_) => 2,
-            Vni(_)
+            | LinkLocalAddress(ref bytes) => bytes.len(),
+            CacheInfo(ref cache_info) => cache_info.buffer_len(),
+            Vlan(_)
+            | Port(_) => 2,
+            Probes(_)
+            | Vni(_)
             | IfIndex(_)
+            | Master(_)
+            | LinkNetNsId(_)
             | SourceVni(_) => 4,
             Other(ref attr) => attr.value_len(),
         }
@@ -58,16 +59,16 @@ impl nlas::Nla for Nla {
         match *self {
             Unspec(ref bytes)
             | Destination(ref bytes)
-            | LinkLocalAddress(ref bytes)
-            | Probes(ref bytes)
-            | Port(ref bytes)
-            | Master(ref bytes)
-            | CacheInfo(ref bytes)
-            | LinkNetNsId(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            | LinkLocalAddress(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            CacheInfo(ref cache_info) => cache_info.emit(buffer),
             Vlan(ref value) => NativeEndian::write_u16(buffer, *value),
-            Vni(ref value)
+            Port(ref value) => BigEndian::write_u16(buffer, *value),
+            Probes(ref value)
+            | Vni(ref value)
             | IfIndex(ref value)
+            | Master(ref value)
             | SourceVni(ref value) => NativeEndian::write_u32(buffer, *value),
+            LinkNetNsId(ref value) => NativeEndian::write_i32(buffer, *value),
             Other(ref attr) => attr.emit_value(buffer),
         }
     }
@@ -101,14 +102,19 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
             NDA_UNSPEC => Unspec(payload.to_vec()),
             NDA_DST => Destination(payload.to_vec()),
             NDA_LLADDR => LinkLocalAddress(payload.to_vec()),
-            NDA_CACHEINFO => CacheInfo(payload.to_vec()),
-            NDA_PROBES => Probes(payload.to_vec()),
+            NDA_CACHEINFO => CacheInfo(
+                cache_info::CacheInfo::parse(&CacheInfoBuffer::new_checked(payload)?)
+                    .context("invalid NDA_CACHEINFO value")?,
+            ),
+            NDA_PROBES => Probes(parse_u32(payload).context("invalid NDA_PROBES value")?),
             NDA_VLAN => Vlan(parse_u16(payload)?),
-            NDA_PORT => Port(payload.to_vec()),
+            NDA_PORT => Port(parse_u16_be(payload).context("invalid NDA_PORT value")?),
             NDA_VNI => Vni(parse_u32(payload)?),
             NDA_IFINDEX => IfIndex(parse_u32(payload)?),
-            NDA_MASTER => Master(payload.to_vec()),
-            NDA_LINK_NETNSID => LinkNetNsId(payload.to_vec()),
+            NDA_MASTER => Master(parse_u32(payload).context("invalid NDA_MASTER value")?),
+            NDA_LINK_NETNSID => {
+                LinkNetNsId(parse_i32(payload).context("invalid NDA_LINK_NETNSID value")?)
+            }
             NDA_SRC_VNI => SourceVni(parse_u32(payload)?),
             _ => Other(DefaultNla::parse(buf).context("invalid link NLA value (unknown type)")?),
         })
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: MIT
+
+use super::{NexthopMessageBuffer, NEXTHOP_HEADER_LEN};
+use crate::{
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+/// Header of the nexthop object messages (`struct nhmsg`)
+///
+/// see https://github.com/torvalds/linux/blob/master/include/uapi/linux/nexthop.h
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct NexthopHeader {
+    /// Address family: one of the `AF_*` constants. It is `AF_UNSPEC` for
+    /// groups and blackhole nexthops.
+    pub family: u8,
+    /// One of the `RT_SCOPE_*` constants
+    pub scope: u8,
+    /// Protocol which installed the nexthop: one of the `RTPROT_*` constants
+    pub protocol: u8,
+    /// `RTNH_F_*` flags
+    pub flags: u32,
+}
+
+impl Emitable for NexthopHeader {
+    fn buffer_len(&self) -> usize {
+        NEXTHOP_HEADER_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut packet = NexthopMessageBuffer::new(buffer);
+        packet.set_family(self.family);
+        packet.set_scope(self.scope);
+        packet.set_protocol(self.protocol);
+        packet.set_reserved(0);
+        packet.set_flags(self.flags);
+    }
+}
+
+impl<T: AsRef<[u8]>> Parseable<NexthopMessageBuffer<T>> for NexthopHeader {
+    fn parse(buf: &NexthopMessageBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(NexthopHeader {
+            family: buf.family(),
+            scope: buf.scope(),
+            protocol: buf.protocol(),
+            flags: buf.flags(),
+        })
+    }
+}
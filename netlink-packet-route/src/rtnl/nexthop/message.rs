@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    nlas::nexthop::Nla,
+    traits::{Emitable, Parseable},
+    DecodeError,
+    NexthopHeader,
+    NexthopMessageBuffer,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct NexthopMessage {
+    pub header: NexthopHeader,
+    pub nlas: Vec<Nla>,
+}
+
+impl<'a, T: AsRef<[u8]> + 'a> Parseable<NexthopMessageBuffer<&'a T>> for NexthopMessage {
+    fn parse(buf: &NexthopMessageBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            header: NexthopHeader::parse(buf).context("failed to parse nexthop message header")?,
+            nlas: Vec::<Nla>::parse(buf).context("failed to parse nexthop message NLAs")?,
+        })
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + 'a> Parseable<NexthopMessageBuffer<&'a T>> for Vec<Nla> {
+    fn parse(buf: &NexthopMessageBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla_buf in buf.nlas() {
+            nlas.push(Nla::parse(&nla_buf?)?);
+        }
+        Ok(nlas)
+    }
+}
+
+impl Emitable for NexthopMessage {
+    fn buffer_len(&self) -> usize {
+        self.header.buffer_len() + self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.header.emit(buffer);
+        self.nlas
+            .as_slice()
+            .emit(&mut buffer[self.header.buffer_len()..]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv4Addr;
+
+    use crate::{
+        constants::*,
+        nlas::{
+            nexthop::{NexthopGroup, Nla},
+            route,
+        },
+        traits::{Emitable, ParseableParametrized},
+        NetlinkBuffer,
+        NexthopHeader,
+        NexthopMessage,
+        RouteMessage,
+        RouteProtocol,
+        RouteType,
+        RtnlMessage,
+        RtnlMessageBuffer,
+    };
+
+    fn parse_rtnl(data: &[u8]) -> RtnlMessage {
+        let nl_buffer = NetlinkBuffer::new(&data);
+        let rtnl_buffer = RtnlMessageBuffer::new(nl_buffer.payload());
+        RtnlMessage::parse_with_param(&rtnl_buffer, nl_buffer.message_type()).unwrap()
+    }
+
+    fn emit_rtnl(message: &RtnlMessage) -> Vec<u8> {
+        let mut buf = vec![0; message.buffer_len()];
+        message.emit(&mut buf);
+        buf
+    }
+
+    // ip nexthop add id 1 via 192.168.1.1 dev eth0
+    #[rustfmt::skip]
+    static NEW_NEXTHOP: [u8; 48] = [
+        0x30, 0x00, 0x00, 0x00, // length = 48
+        0x68, 0x00, // message type = 104 = RTM_NEWNEXTHOP
+        0x05, 0x06, // flags
+        0x00, 0x00, 0x00, 0x00, // seq number
+        0x00, 0x00, 0x00, 0x00, // pid
+
+        // nexthop message
+        0x02, // family AF_INET
+        0x00, // scope
+        0x03, // protocol RTPROT_BOOT
+        0x00, // reserved
+        0x00, 0x00, 0x00, 0x00, // flags
+
+        0x08, 0x00, 0x01, 0x00, // length = 8, type = NHA_ID
+        0x01, 0x00, 0x00, 0x00, // 1
+
+        0x08, 0x00, 0x06, 0x00, // length = 8, type = NHA_GATEWAY
+        0xc0, 0xa8, 0x01, 0x01, // 192.168.1.1
+
+        0x08, 0x00, 0x05, 0x00, // length = 8, type = NHA_OIF
+        0x02, 0x00, 0x00, 0x00, // 2
+    ];
+
+    #[test]
+    fn parse_and_emit_new_nexthop() {
+        let expected = RtnlMessage::NewNexthop(NexthopMessage {
+            header: NexthopHeader {
+                family: AF_INET as u8,
+                scope: 0,
+                protocol: RTPROT_BOOT,
+                flags: 0,
+            },
+            nlas: vec![
+                Nla::Id(1),
+                Nla::Gateway(Ipv4Addr::new(192, 168, 1, 1).into()),
+                Nla::Oif(2),
+            ],
+        });
+        assert_eq!(parse_rtnl(&NEW_NEXTHOP), expected);
+        assert_eq!(emit_rtnl(&expected), &NEW_NEXTHOP[16..]);
+    }
+
+    // ip nexthop add id 10 group 1/2,3
+    #[rustfmt::skip]
+    static NEW_NEXTHOP_GROUP: [u8; 52] = [
+        0x34, 0x00, 0x00, 0x00, // length = 52
+        0x68, 0x00, // message type = 104 = RTM_NEWNEXTHOP
+        0x05, 0x06, // flags
+        0x00, 0x00, 0x00, 0x00, // seq number
+        0x00, 0x00, 0x00, 0x00, // pid
+
+        // nexthop message
+        0x00, // family AF_UNSPEC
+        0x00, // scope
+        0x03, // protocol RTPROT_BOOT
+        0x00, // reserved
+        0x00, 0x00, 0x00, 0x00, // flags
+
+        0x08, 0x00, 0x01, 0x00, // length = 8, type = NHA_ID
+        0x0a, 0x00, 0x00, 0x00, // 10
+
+        0x14, 0x00, 0x02, 0x00, // length = 20, type = NHA_GROUP
+        0x01, 0x00, 0x00, 0x00, // id = 1
+        0x00, // weight = 1
+        0x00, 0x00, 0x00, // reserved
+        0x02, 0x00, 0x00, 0x00, // id = 2
+        0x02, // weight = 3
+        0x00, 0x00, 0x00, // reserved
+    ];
+
+    #[test]
+    fn parse_and_emit_new_nexthop_group() {
+        let expected = RtnlMessage::NewNexthop(NexthopMessage {
+            header: NexthopHeader {
+                family: AF_UNSPEC as u8,
+                scope: 0,
+                protocol: RTPROT_BOOT,
+                flags: 0,
+            },
+            nlas: vec![
+                Nla::Id(10),
+                Nla::Group(vec![
+                    NexthopGroup { id: 1, weight: 0 },
+                    NexthopGroup { id: 2, weight: 2 },
+                ]),
+            ],
+        });
+        assert_eq!(parse_rtnl(&NEW_NEXTHOP_GROUP), expected);
+        assert_eq!(emit_rtnl(&expected), &NEW_NEXTHOP_GROUP[16..]);
+    }
+
+    // ip route add 10.1.0.0/16 nhid 10
+    #[rustfmt::skip]
+    static NEW_ROUTE_WITH_NEXTHOP: [u8; 44] = [
+        0x2c, 0x00, 0x00, 0x00, // length = 44
+        0x18, 0x00, // message type = 24 = RTM_NEWROUTE
+        0x05, 0x06, // flags
+        0x00, 0x00, 0x00, 0x00, // seq number
+        0x00, 0x00, 0x00, 0x00, // pid
+
+        // route message
+        0x02, // address family
+        0x10, // length of destination
+        0x00, // length of source
+        0x00, // TOS
+        0xfe, // routing table id
+        0x03, // routing protocol (boot)
+        0x00, // route origin (global)
+        0x01, // gateway or direct route
+        0x00, 0x00, 0x00, 0x00, // flags
+
+        0x08, 0x00, 0x01, 0x00, // length = 8, type = RTA_DST
+        0x0a, 0x01, 0x00, 0x00, // 10.1.0.0
+
+        0x08, 0x00, 0x1e, 0x00, // length = 8, type = RTA_NH_ID
+        0x0a, 0x00, 0x00, 0x00, // 10
+    ];
+
+    #[test]
+    fn parse_and_emit_route_with_nexthop() {
+        let mut route = RouteMessage::default();
+        route.header.address_family = AF_INET as u8;
+        route.header.destination_prefix_length = 16;
+        route.header.table = RT_TABLE_MAIN;
+        route.header.protocol = RouteProtocol::Boot;
+        route.header.kind = RouteType::Unicast;
+        route.nlas = vec![
+            route::Nla::Destination(vec![10, 1, 0, 0]),
+            route::Nla::NhId(10),
+        ];
+        let expected = RtnlMessage::NewRoute(route);
+        assert_eq!(parse_rtnl(&NEW_ROUTE_WITH_NEXTHOP), expected);
+        assert_eq!(emit_rtnl(&expected), &NEW_ROUTE_WITH_NEXTHOP[16..]);
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+use std::net::IpAddr;
+
+use crate::{
+    constants::*,
+    emit_ip,
+    ip_len,
+    nlas::{self, DefaultNla, NlaBuffer},
+    parsers::{parse_ip, parse_u16, parse_u32},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+/// Netlink attributes for `RTM_NEWNEXTHOP`, `RTM_DELNEXTHOP` and
+/// `RTM_GETNEXTHOP` messages.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Nla {
+    Unspec(Vec<u8>),
+    /// Identifier of the nexthop, which routes use to refer to it with the
+    /// `RTA_NH_ID` attribute
+    Id(u32),
+    /// Nexthops of a group
+    Group(Vec<NexthopGroup>),
+    /// One of the `NEXTHOP_GRP_TYPE_*` constants
+    GroupType(u16),
+    /// The packets routed through this nexthop are dropped
+    Blackhole,
+    /// Index of the output interface
+    Oif(u32),
+    Gateway(IpAddr),
+    /// One of the `LWTUNNEL_ENCAP_*` constants
+    EncapType(u16),
+    /// Lightweight tunnel encapsulation, whose attributes depend on
+    /// the `EncapType`
+    Encap(Vec<u8>),
+    /// Only dump the groups (flag used in `RTM_GETNEXTHOP` requests)
+    Groups,
+    /// Only dump the nexthops using this master device
+    Master(u32),
+    /// The nexthop is used by the bridge FDB entries rather than by routes
+    Fdb,
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for Nla {
+    fn value_len(&self) -> usize {
+        use self::Nla::*;
+        match *self {
+            Unspec(ref bytes) | Encap(ref bytes) => bytes.len(),
+            Group(ref entries) => entries.len() * NEXTHOP_GROUP_LEN,
+            Gateway(ref addr) => ip_len(addr),
+            Blackhole | Groups | Fdb => 0,
+            GroupType(_) | EncapType(_) => 2,
+            Id(_) | Oif(_) | Master(_) => 4,
+            Other(ref attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::Nla::*;
+        match *self {
+            Unspec(ref bytes) | Encap(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Group(ref entries) => {
+                for (entry, chunk) in entries.iter().zip(buffer.chunks_mut(NEXTHOP_GROUP_LEN)) {
+                    entry.emit(chunk);
+                }
+            }
+            Gateway(ref addr) => emit_ip(buffer, addr),
+            Blackhole | Groups | Fdb => {}
+            GroupType(value) | EncapType(value) => NativeEndian::write_u16(buffer, value),
+            Id(value) | Oif(value) | Master(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::Nla::*;
+        match *self {
+            Unspec(_) => NHA_UNSPEC,
+            Id(_) => NHA_ID,
+            Group(_) => NHA_GROUP,
+            GroupType(_) => NHA_GROUP_TYPE,
+            Blackhole => NHA_BLACKHOLE,
+            Oif(_) => NHA_OIF,
+            Gateway(_) => NHA_GATEWAY,
+            EncapType(_) => NHA_ENCAP_TYPE,
+            Encap(_) => NHA_ENCAP,
+            Groups => NHA_GROUPS,
+            Master(_) => NHA_MASTER,
+            Fdb => NHA_FDB,
+            Other(ref attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::Nla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            NHA_UNSPEC => Unspec(payload.to_vec()),
+            NHA_ID => Id(parse_u32(payload).context("invalid NHA_ID value")?),
+            NHA_GROUP => {
+                let mut entries = vec![];
+                for chunk in payload.chunks(NEXTHOP_GROUP_LEN) {
+                    entries.push(NexthopGroup::parse(chunk).context("invalid NHA_GROUP value")?);
+                }
+                Group(entries)
+            }
+            NHA_GROUP_TYPE => {
+                GroupType(parse_u16(payload).context("invalid NHA_GROUP_TYPE value")?)
+            }
+            NHA_BLACKHOLE => Blackhole,
+            NHA_OIF => Oif(parse_u32(payload).context("invalid NHA_OIF value")?),
+            NHA_GATEWAY => Gateway(parse_ip(payload).context("invalid NHA_GATEWAY value")?),
+            NHA_ENCAP_TYPE => {
+                EncapType(parse_u16(payload).context("invalid NHA_ENCAP_TYPE value")?)
+            }
+            NHA_ENCAP => Encap(payload.to_vec()),
+            NHA_GROUPS => Groups,
+            NHA_MASTER => Master(parse_u32(payload).context("invalid NHA_MASTER value")?),
+            NHA_FDB => Fdb,
+            kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
+        })
+    }
+}
+
+pub const NEXTHOP_GROUP_LEN: usize = 8;
+
+/// Member of a nexthop group (`struct nexthop_grp`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct NexthopGroup {
+    /// Identifier of the member nexthop
+    pub id: u32,
+    /// Weight of the nexthop in the group, minus one: a weight of 0 is a
+    /// weight of 1.
+    pub weight: u8,
+}
+
+impl NexthopGroup {
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        if payload.len() != NEXTHOP_GROUP_LEN {
+            return Err(format!("invalid nexthop group entry length {}", payload.len()).into());
+        }
+        Ok(NexthopGroup {
+            id: NativeEndian::read_u32(&payload[..4]),
+            weight: payload[4],
+        })
+    }
+}
+
+impl Emitable for NexthopGroup {
+    fn buffer_len(&self) -> usize {
+        NEXTHOP_GROUP_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        NativeEndian::write_u32(&mut buffer[..4], self.id);
+        buffer[4] = self.weight;
+        // reserved bytes
+        buffer[5..NEXTHOP_GROUP_LEN].copy_from_slice(&[0; 3]);
+    }
+}
@@ -60,7 +60,14 @@ impl Default for RouteFlags {
 ///
 /// ```rust
 /// extern crate netlink_packet_route;
-/// use netlink_packet_route::{constants::*, RouteFlags, RouteHeader};
+/// use netlink_packet_route::{
+///     constants::*,
+///     RouteFlags,
+///     RouteHeader,
+///     RouteProtocol,
+///     RouteScope,
+///     RouteType,
+/// };
 ///
 /// fn main() {
 ///     let mut hdr = RouteHeader::default();
@@ -69,16 +76,16 @@ impl Default for RouteFlags {
 ///     assert_eq!(hdr.source_prefix_length, 0u8);
 ///     assert_eq!(hdr.tos, 0u8);
 ///     assert_eq!(hdr.table, RT_TABLE_UNSPEC);
-///     assert_eq!(hdr.protocol, RTPROT_UNSPEC);
-///     assert_eq!(hdr.scope, RT_SCOPE_UNIVERSE);
-///     assert_eq!(hdr.kind, RTN_UNSPEC);
+///     assert_eq!(hdr.protocol, RouteProtocol::Unspec);
+///     assert_eq!(hdr.scope, RouteScope::Universe);
+///     assert_eq!(hdr.kind, RouteType::Unspec);
 ///     assert_eq!(hdr.flags.bits(), 0u32);
 ///
 ///     // set some values
 ///     hdr.destination_prefix_length = 8;
 ///     hdr.table = RT_TABLE_MAIN;
-///     hdr.protocol = RTPROT_KERNEL;
-///     hdr.scope = RT_SCOPE_NOWHERE;
+///     hdr.protocol = RouteProtocol::Kernel;
+///     hdr.scope = RouteScope::Nowhere;
 ///
 ///     // ...
 /// }
@@ -119,14 +126,12 @@ pub struct RouteHeader {
     /// 255, in which case this attribute will be set to [`RT_TABLE_COMPAT`] and an [`Nla::Table`]
     /// netlink attribute will be present in the message.
     pub table: u8,
-    /// Protocol from which the route was learnt. It should be set to one of the `RTPROT_*`
-    /// constants.
-    pub protocol: u8,
-    /// The scope of the area where the addresses in the destination subnet are valid. Predefined
-    /// scope values are the `RT_SCOPE_*` constants.
-    pub scope: u8,
-    /// Route type. It should be set to one of the `RTN_*` constants.
-    pub kind: u8,
+    /// Protocol from which the route was learnt.
+    pub protocol: RouteProtocol,
+    /// The scope of the area where the addresses in the destination subnet are valid.
+    pub scope: RouteScope,
+    /// Route type.
+    pub kind: RouteType,
     /// Flags when querying the kernel with a `RTM_GETROUTE` message. See [`RouteFlags`].
     pub flags: RouteFlags,
 }
@@ -139,9 +144,9 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<RouteMessageBuffer<&'a T>> for Route
             source_prefix_length: buf.source_prefix_length(),
             tos: buf.tos(),
             table: buf.table(),
-            protocol: buf.protocol(),
-            scope: buf.scope(),
-            kind: buf.kind(),
+            protocol: buf.protocol().into(),
+            scope: buf.scope().into(),
+            kind: buf.kind().into(),
             flags: RouteFlags::from_bits_truncate(buf.flags()),
         })
     }
@@ -159,9 +164,226 @@ impl Emitable for RouteHeader {
         buffer.set_source_prefix_length(self.source_prefix_length);
         buffer.set_tos(self.tos);
         buffer.set_table(self.table);
-        buffer.set_protocol(self.protocol);
-        buffer.set_scope(self.scope);
-        buffer.set_kind(self.kind);
+        buffer.set_protocol(self.protocol.into());
+        buffer.set_scope(self.scope.into());
+        buffer.set_kind(self.kind.into());
         buffer.set_flags(self.flags.bits());
     }
 }
+
+/// Protocol from which a route was learnt, carried by the `protocol` field of the header
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum RouteProtocol {
+    #[default]
+    Unspec,
+    /// Route was learnt by an ICMP redirect
+    Redirect,
+    /// Route was learnt by the kernel
+    Kernel,
+    /// Route was learnt during boot
+    Boot,
+    /// Route was set statically by the administrator
+    Static,
+    Gated,
+    /// Route was set from an IPv6 router advertisement
+    Ra,
+    Mrt,
+    Zebra,
+    Bird,
+    DnRouted,
+    Xorp,
+    Ntk,
+    /// Route was set by a DHCP client
+    Dhcp,
+    Mrouted,
+    KeepAlived,
+    Babel,
+    OpenR,
+    Bgp,
+    Isis,
+    Ospf,
+    Rip,
+    Eigrp,
+    Other(u8),
+}
+
+impl From<u8> for RouteProtocol {
+    fn from(value: u8) -> Self {
+        use self::RouteProtocol::*;
+        match value {
+            RTPROT_UNSPEC => Unspec,
+            RTPROT_REDIRECT => Redirect,
+            RTPROT_KERNEL => Kernel,
+            RTPROT_BOOT => Boot,
+            RTPROT_STATIC => Static,
+            RTPROT_GATED => Gated,
+            RTPROT_RA => Ra,
+            RTPROT_MRT => Mrt,
+            RTPROT_ZEBRA => Zebra,
+            RTPROT_BIRD => Bird,
+            RTPROT_DNROUTED => DnRouted,
+            RTPROT_XORP => Xorp,
+            RTPROT_NTK => Ntk,
+            RTPROT_DHCP => Dhcp,
+            RTPROT_MROUTED => Mrouted,
+            RTPROT_KEEPALIVED => KeepAlived,
+            RTPROT_BABEL => Babel,
+            RTPROT_OPENR => OpenR,
+            RTPROT_BGP => Bgp,
+            RTPROT_ISIS => Isis,
+            RTPROT_OSPF => Ospf,
+            RTPROT_RIP => Rip,
+            RTPROT_EIGRP => Eigrp,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<RouteProtocol> for u8 {
+    fn from(value: RouteProtocol) -> Self {
+        use self::RouteProtocol::*;
+        match value {
+            Unspec => RTPROT_UNSPEC,
+            Redirect => RTPROT_REDIRECT,
+            Kernel => RTPROT_KERNEL,
+            Boot => RTPROT_BOOT,
+            Static => RTPROT_STATIC,
+            Gated => RTPROT_GATED,
+            Ra => RTPROT_RA,
+            Mrt => RTPROT_MRT,
+            Zebra => RTPROT_ZEBRA,
+            Bird => RTPROT_BIRD,
+            DnRouted => RTPROT_DNROUTED,
+            Xorp => RTPROT_XORP,
+            Ntk => RTPROT_NTK,
+            Dhcp => RTPROT_DHCP,
+            Mrouted => RTPROT_MROUTED,
+            KeepAlived => RTPROT_KEEPALIVED,
+            Babel => RTPROT_BABEL,
+            OpenR => RTPROT_OPENR,
+            Bgp => RTPROT_BGP,
+            Isis => RTPROT_ISIS,
+            Ospf => RTPROT_OSPF,
+            Rip => RTPROT_RIP,
+            Eigrp => RTPROT_EIGRP,
+            Other(other) => other,
+        }
+    }
+}
+
+/// Type of a route, carried by the `kind` field of the header
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum RouteType {
+    #[default]
+    Unspec,
+    /// A gateway or direct route
+    Unicast,
+    /// A local interface route
+    Local,
+    /// A local broadcast route (sent as a broadcast)
+    Broadcast,
+    /// A local broadcast route (sent as a unicast)
+    Anycast,
+    /// A multicast route
+    Multicast,
+    /// A packet dropping route
+    BlackHole,
+    /// An unreachable destination
+    Unreachable,
+    /// A packet rejection route
+    Prohibit,
+    /// Continue routing lookup in another table
+    Throw,
+    /// A network address translation rule
+    Nat,
+    /// Refer to an external resolver (not implemented)
+    ExternalResolve,
+    Other(u8),
+}
+
+impl From<u8> for RouteType {
+    fn from(value: u8) -> Self {
+        use self::RouteType::*;
+        match value {
+            RTN_UNSPEC => Unspec,
+            RTN_UNICAST => Unicast,
+            RTN_LOCAL => Local,
+            RTN_BROADCAST => Broadcast,
+            RTN_ANYCAST => Anycast,
+            RTN_MULTICAST => Multicast,
+            RTN_BLACKHOLE => BlackHole,
+            RTN_UNREACHABLE => Unreachable,
+            RTN_PROHIBIT => Prohibit,
+            RTN_THROW => Throw,
+            RTN_NAT => Nat,
+            RTN_XRESOLVE => ExternalResolve,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<RouteType> for u8 {
+    fn from(value: RouteType) -> Self {
+        use self::RouteType::*;
+        match value {
+            Unspec => RTN_UNSPEC,
+            Unicast => RTN_UNICAST,
+            Local => RTN_LOCAL,
+            Broadcast => RTN_BROADCAST,
+            Anycast => RTN_ANYCAST,
+            Multicast => RTN_MULTICAST,
+            BlackHole => RTN_BLACKHOLE,
+            Unreachable => RTN_UNREACHABLE,
+            Prohibit => RTN_PROHIBIT,
+            Throw => RTN_THROW,
+            Nat => RTN_NAT,
+            ExternalResolve => RTN_XRESOLVE,
+            Other(other) => other,
+        }
+    }
+}
+
+/// Scope of a route, carried by the `scope` field of the header
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum RouteScope {
+    /// The destination is globally valid
+    #[default]
+    Universe,
+    /// (IPv6 only) the destination is valid inside this site
+    Site,
+    /// The destination is link local
+    Link,
+    /// The destination is valid only on this host
+    Host,
+    /// The destination doesn't exist
+    Nowhere,
+    Other(u8),
+}
+
+impl From<u8> for RouteScope {
+    fn from(value: u8) -> Self {
+        use self::RouteScope::*;
+        match value {
+            RT_SCOPE_UNIVERSE => Universe,
+            RT_SCOPE_SITE => Site,
+            RT_SCOPE_LINK => Link,
+            RT_SCOPE_HOST => Host,
+            RT_SCOPE_NOWHERE => Nowhere,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<RouteScope> for u8 {
+    fn from(value: RouteScope) -> Self {
+        use self::RouteScope::*;
+        match value {
+            Universe => RT_SCOPE_UNIVERSE,
+            Site => RT_SCOPE_SITE,
+            Link => RT_SCOPE_LINK,
+            Host => RT_SCOPE_HOST,
+            Nowhere => RT_SCOPE_NOWHERE,
+            Other(other) => other,
+        }
+    }
+}
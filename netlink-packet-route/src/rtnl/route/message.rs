@@ -1,11 +1,17 @@
 // SPDX-License-Identifier: MIT
 
 use crate::{
-    nlas::route::Nla,
-    traits::{Emitable, Parseable},
+    constants::*,
+    nlas::route::{encap_type, Nla},
+    traits::{Emitable, Parseable, ParseableParametrized},
     DecodeError,
+    NetlinkFlags,
+    NetlinkHeader,
+    NetlinkMessage,
+    NetlinkPayload,
     RouteHeader,
     RouteMessageBuffer,
+    RtnlMessage,
 };
 use anyhow::Context;
 use std::net::IpAddr;
@@ -40,9 +46,10 @@ impl<'a, T: AsRef<[u8]> + 'a> Parseable<RouteMessageBuffer<&'a T>> for RouteMess
 
 impl<'a, T: AsRef<[u8]> + 'a> Parseable<RouteMessageBuffer<&'a T>> for Vec<Nla> {
     fn parse(buf: &RouteMessageBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let encap_type = encap_type(buf.nlas());
         let mut nlas = vec![];
         for nla_buf in buf.nlas() {
-            nlas.push(Nla::parse(&nla_buf?)?);
+            nlas.push(Nla::parse_with_param(&nla_buf?, encap_type)?);
         }
         Ok(nlas)
     }
@@ -63,6 +70,38 @@ fn octets_to_addr(octets: &[u8]) -> Result<IpAddr, DecodeError> {
 }
 
 impl RouteMessage {
+    /// Build a `RTM_GETROUTE` request that looks up the route the kernel would use to reach
+    /// `dst`, like `ip route get` does. Unlike a dump request, only `NLM_F_REQUEST` is set, so the
+    /// kernel responds with a single `RTM_NEWROUTE` message, or with a `NLMSG_ERROR` message if
+    /// there is no route to `dst`.
+    ///
+    /// When strict checking is enabled on the socket, the kernel only accepts a `prefix_len` of 0
+    /// or of the length of the address. The flags of the route header, such as
+    /// [`RouteFlags::RTM_F_FIB_MATCH`](crate::RouteFlags::RTM_F_FIB_MATCH), can be set before
+    /// sending the message.
+    pub fn lookup_request(dst: IpAddr, prefix_len: u8) -> NetlinkMessage<RtnlMessage> {
+        let (address_family, octets) = match dst {
+            IpAddr::V4(addr) => (AF_INET, addr.octets().to_vec()),
+            IpAddr::V6(addr) => (AF_INET6, addr.octets().to_vec()),
+        };
+        let message = RouteMessage {
+            header: RouteHeader {
+                address_family: address_family as u8,
+                destination_prefix_length: prefix_len,
+                ..Default::default()
+            },
+            nlas: vec![Nla::Destination(octets)],
+        };
+        let header = NetlinkHeader {
+            flags: NetlinkFlags::new_get(),
+            ..Default::default()
+        };
+        let mut packet =
+            NetlinkMessage::new(header, NetlinkPayload::from(RtnlMessage::GetRoute(message)));
+        packet.finalize();
+        packet
+    }
+
     /// Returns the input interface index, if present.
     pub fn input_interface(&self) -> Option<u32> {
         self.nlas.iter().find_map(|nla| {
@@ -4,8 +4,9 @@ mod buffer;
 mod header;
 mod message;
 pub mod nlas;
+mod validate;
 
-pub use self::{buffer::*, header::*, message::*, nlas::*};
+pub use self::{buffer::*, header::*, message::*, nlas::*, validate::*};
 
 #[cfg(test)]
 mod test;
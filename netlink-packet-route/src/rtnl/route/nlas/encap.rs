@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{
+    constants::*,
+    nlas::{DefaultNla, Nla, NlaBuffer, NlasIterator},
+    parsers::parse_u8,
+    traits::{Emitable, Parseable, ParseableParametrized},
+    DecodeError,
+};
+
+use super::{Seg6IpTunnel, Seg6Local};
+
+/// Lightweight tunnel encapsulation of a route (`RTA_ENCAP` attribute). Its
+/// content depends on the `RTA_ENCAP_TYPE` attribute of the route, which
+/// must be set to the matching `LWTUNNEL_ENCAP_*` type.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LwtEncap {
+    /// `LWTUNNEL_ENCAP_MPLS`: push a stack of MPLS labels on the packets
+    Mpls(Vec<MplsIpTunnel>),
+    /// `LWTUNNEL_ENCAP_SEG6`: steer the packets through a list of IPv6
+    /// segments
+    Seg6(Vec<Seg6IpTunnel>),
+    /// `LWTUNNEL_ENCAP_SEG6_LOCAL`: apply a segment routing endpoint behavior
+    /// to the packets
+    Seg6Local(Vec<Seg6Local>),
+    /// Encapsulation of another type, or of an unknown type
+    Other(Vec<u8>),
+}
+
+impl Emitable for LwtEncap {
+    fn buffer_len(&self) -> usize {
+        match self {
+            LwtEncap::Mpls(ref nlas) => nlas.as_slice().buffer_len(),
+            LwtEncap::Seg6(ref nlas) => nlas.as_slice().buffer_len(),
+            LwtEncap::Seg6Local(ref nlas) => nlas.as_slice().buffer_len(),
+            LwtEncap::Other(ref bytes) => bytes.len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            LwtEncap::Mpls(ref nlas) => nlas.as_slice().emit(buffer),
+            LwtEncap::Seg6(ref nlas) => nlas.as_slice().emit(buffer),
+            LwtEncap::Seg6Local(ref nlas) => nlas.as_slice().emit(buffer),
+            LwtEncap::Other(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, u16> for LwtEncap {
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, encap_type: u16) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match encap_type {
+            LWTUNNEL_ENCAP_MPLS => {
+                LwtEncap::Mpls(parse_nlas(payload).context("invalid MPLS encapsulation")?)
+            }
+            LWTUNNEL_ENCAP_SEG6 => {
+                LwtEncap::Seg6(parse_nlas(payload).context("invalid seg6 encapsulation")?)
+            }
+            LWTUNNEL_ENCAP_SEG6_LOCAL => {
+                LwtEncap::Seg6Local(parse_nlas(payload).context("invalid seg6local encapsulation")?)
+            }
+            _ => LwtEncap::Other(payload.to_vec()),
+        })
+    }
+}
+
+fn parse_nlas<N>(payload: &[u8]) -> Result<Vec<N>, DecodeError>
+where
+    N: for<'a> Parseable<NlaBuffer<&'a [u8]>>,
+{
+    let mut nlas = vec![];
+    for nla in NlasIterator::new(payload) {
+        nlas.push(N::parse(&nla?)?);
+    }
+    Ok(nlas)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MplsIpTunnel {
+    /// Labels to push, the first one being the outermost
+    Destination(Vec<MplsLabel>),
+    /// TTL of the pushed labels. When it is not set, the TTL of the IP
+    /// header is propagated, or the default TTL is used.
+    Ttl(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for MplsIpTunnel {
+    fn value_len(&self) -> usize {
+        use self::MplsIpTunnel::*;
+        match *self {
+            Destination(ref labels) => labels.len() * MPLS_LABEL_LEN,
+            Ttl(_) => 1,
+            Other(ref attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::MplsIpTunnel::*;
+        match *self {
+            Destination(ref labels) => {
+                for (label, chunk) in labels.iter().zip(buffer.chunks_mut(MPLS_LABEL_LEN)) {
+                    BigEndian::write_u32(chunk, (*label).into());
+                }
+            }
+            Ttl(value) => buffer[0] = value,
+            Other(ref attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::MplsIpTunnel::*;
+        match *self {
+            Destination(_) => MPLS_IPTUNNEL_DST,
+            Ttl(_) => MPLS_IPTUNNEL_TTL,
+            Other(ref attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for MplsIpTunnel {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::MplsIpTunnel::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            MPLS_IPTUNNEL_DST => {
+                let chunks = payload.chunks_exact(MPLS_LABEL_LEN);
+                if !chunks.remainder().is_empty() {
+                    return Err(format!(
+                        "invalid MPLS_IPTUNNEL_DST value: length {} is not a multiple of {}",
+                        payload.len(),
+                        MPLS_LABEL_LEN
+                    )
+                    .into());
+                }
+                Destination(
+                    chunks
+                        .map(|chunk| BigEndian::read_u32(chunk).into())
+                        .collect(),
+                )
+            }
+            MPLS_IPTUNNEL_TTL => Ttl(parse_u8(payload).context("invalid MPLS_IPTUNNEL_TTL value")?),
+            kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
+        })
+    }
+}
+
+pub const MPLS_LABEL_LEN: usize = 4;
+
+/// MPLS label stack entry. On the wire, it is a big endian 32 bits word
+/// made of the label (20 bits), the traffic class (3 bits), the bottom of
+/// stack flag and the TTL (8 bits).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct MplsLabel {
+    pub label: u32,
+    /// Traffic class
+    pub tc: u8,
+    /// Bottom of stack: set on the last label of the stack
+    pub bos: bool,
+    pub ttl: u8,
+}
+
+impl From<u32> for MplsLabel {
+    fn from(value: u32) -> Self {
+        MplsLabel {
+            label: value >> 12,
+            tc: ((value >> 9) & 0x7) as u8,
+            bos: (value >> 8) & 0x1 == 1,
+            ttl: (value & 0xff) as u8,
+        }
+    }
+}
+
+impl From<MplsLabel> for u32 {
+    fn from(value: MplsLabel) -> Self {
+        ((value.label & 0xf_ffff) << 12)
+            | ((u32::from(value.tc) & 0x7) << 9)
+            | (u32::from(value.bos) << 8)
+            | u32::from(value.ttl)
+    }
+}
@@ -7,7 +7,7 @@ use std::mem::size_of;
 use crate::{
     constants::*,
     nlas::{DefaultNla, Nla, NlaBuffer},
-    parsers::parse_u32,
+    parsers::{parse_string, parse_u32},
     traits::Parseable,
     DecodeError,
 };
@@ -15,10 +15,14 @@ use crate::{
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Metrics {
     Unspec(Vec<u8>),
+    /// Bitmask of the metrics that are locked, i.e. that the kernel must not
+    /// update (`1 << RTAX_*`)
     Lock(u32),
     Mtu(u32),
     Window(u32),
+    /// Round trip time, in milliseconds multiplied by 8
     Rtt(u32),
+    /// Round trip time variance, in milliseconds multiplied by 4
     RttVar(u32),
     SsThresh(u32),
     Cwnd(u32),
@@ -26,11 +30,14 @@ pub enum Metrics {
     Reordering(u32),
     Hoplimit(u32),
     InitCwnd(u32),
+    /// `RTAX_FEATURE_*` flags
     Features(u32),
+    /// Minimum retransmission timeout, in milliseconds
     RtoMin(u32),
     InitRwnd(u32),
     QuickAck(u32),
-    CcAlgo(u32),
+    /// Name of the TCP congestion control algorithm
+    CcAlgo(String),
     FastopenNoCookie(u32),
     Other(DefaultNla),
 }
@@ -41,6 +48,7 @@ impl Nla for Metrics {
         use self::Metrics::*;
         match *self {
             Unspec(ref bytes) => bytes.len(),
+            CcAlgo(ref name) => name.len() + 1,
             Lock(_)
                 | Mtu(_)
                 | Window(_)
@@ -56,7 +64,6 @@ impl Nla for Metrics {
                 | RtoMin(_)
                 | InitRwnd(_)
                 | QuickAck(_)
-                | CcAlgo(_)
                 | FastopenNoCookie(_)
                 => size_of::<u32>(),
             Other(ref attr) => attr.value_len(),
@@ -68,6 +75,10 @@ impl Nla for Metrics {
         use self::Metrics::*;
         match *self {
             Unspec(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            CcAlgo(ref name) => {
+                buffer[..name.len()].copy_from_slice(name.as_bytes());
+                buffer[name.len()] = 0;
+            }
 
             Lock(value)
                 | Mtu(value)
@@ -84,7 +95,6 @@ impl Nla for Metrics {
                 | RtoMin(value)
                 | InitRwnd(value)
                 | QuickAck(value)
-                | CcAlgo(value)
                 | FastopenNoCookie(value)
                 => NativeEndian::write_u32(buffer, value),
 
@@ -141,7 +151,7 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Metrics {
             RTAX_RTO_MIN => RtoMin(parse_u32(payload).context("invalid RTAX_RTO_MIN value")?),
             RTAX_INITRWND => InitRwnd(parse_u32(payload).context("invalid RTAX_INITRWND value")?),
             RTAX_QUICKACK => QuickAck(parse_u32(payload).context("invalid RTAX_QUICKACK value")?),
-            RTAX_CC_ALGO => CcAlgo(parse_u32(payload).context("invalid RTAX_CC_ALGO value")?),
+            RTAX_CC_ALGO => CcAlgo(parse_string(payload).context("invalid RTAX_CC_ALGO value")?),
             RTAX_FASTOPEN_NO_COOKIE => FastopenNoCookie(
                 parse_u32(payload).context("invalid RTAX_FASTOPEN_NO_COOKIE value")?,
             ),
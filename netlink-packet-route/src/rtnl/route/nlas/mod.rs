@@ -3,6 +3,9 @@
 mod cache_info;
 pub use self::cache_info::*;
 
+mod encap;
+pub use self::encap::*;
+
 mod metrics;
 pub use self::metrics::*;
 
@@ -12,6 +15,9 @@ pub use self::mfc_stats::*;
 mod next_hops;
 pub use self::next_hops::*;
 
+mod seg6;
+pub use self::seg6::*;
+
 use anyhow::Context;
 use byteorder::{ByteOrder, NativeEndian};
 
@@ -19,12 +25,12 @@ use crate::{
     constants::*,
     nlas::{self, DefaultNla, NlaBuffer},
     parsers::{parse_u16, parse_u32},
-    traits::Parseable,
+    traits::{Parseable, ParseableParametrized},
     DecodeError,
 };
 
 #[cfg(feature = "rich_nlas")]
-use crate::traits::Emitable;
+use crate::{nlas::NlasIterator, traits::Emitable};
 
 /// Netlink attributes for `RTM_NEWROUTE`, `RTM_DELROUTE`,
 /// `RTM_GETROUTE` messages.
@@ -33,7 +39,7 @@ pub enum Nla {
     #[cfg(not(feature = "rich_nlas"))]
     Metrics(Vec<u8>),
     #[cfg(feature = "rich_nlas")]
-    Metrics(Metrics),
+    Metrics(Vec<Metrics>),
     #[cfg(not(feature = "rich_nlas"))]
     MfcStats(Vec<u8>),
     #[cfg(feature = "rich_nlas")]
@@ -57,7 +63,10 @@ pub enum Nla {
     Via(Vec<u8>),
     NewDestination(Vec<u8>),
     Pref(Vec<u8>),
+    #[cfg(not(feature = "rich_nlas"))]
     Encap(Vec<u8>),
+    #[cfg(feature = "rich_nlas")]
+    Encap(LwtEncap),
     Expires(Vec<u8>),
     Pad(Vec<u8>),
     Uid(Vec<u8>),
@@ -68,6 +77,8 @@ pub enum Nla {
     Priority(u32),
     ProtocolInfo(u32),
     Flow(u32),
+    /// Identifier of the nexthop object used by the route
+    NhId(u32),
     Table(u32),
     Mark(u32),
     Other(DefaultNla),
@@ -88,7 +99,6 @@ impl nlas::Nla for Nla {
                 | Via(ref bytes)
                 | NewDestination(ref bytes)
                 | Pref(ref bytes)
-                | Encap(ref bytes)
                 | Expires(ref bytes)
                 | Pad(ref bytes)
                 | Uid(ref bytes)
@@ -100,6 +110,7 @@ impl nlas::Nla for Nla {
                 | MfcStats(ref bytes)
                 | Metrics(ref bytes)
                 | MultiPath(ref bytes)
+                | Encap(ref bytes)
                 => bytes.len(),
 
             #[cfg(feature = "rich_nlas")]
@@ -107,7 +118,9 @@ impl nlas::Nla for Nla {
             #[cfg(feature = "rich_nlas")]
             MfcStats(ref stats) => stats.buffer_len(),
             #[cfg(feature = "rich_nlas")]
-            Metrics(ref metrics) => metrics.buffer_len(),
+            Metrics(ref metrics) => metrics.as_slice().buffer_len(),
+            #[cfg(feature = "rich_nlas")]
+            Encap(ref encap) => encap.buffer_len(),
             #[cfg(feature = "rich_nlas")]
             MultiPath(ref next_hops) => next_hops.iter().map(|nh| nh.buffer_len()).sum(),
 
@@ -117,6 +130,7 @@ impl nlas::Nla for Nla {
                 | Priority(_)
                 | ProtocolInfo(_)
                 | Flow(_)
+                | NhId(_)
                 | Table(_)
                 | Mark(_)
                 => 4,
@@ -139,7 +153,6 @@ impl nlas::Nla for Nla {
                 | Via(ref bytes)
                 | NewDestination(ref bytes)
                 | Pref(ref bytes)
-                | Encap(ref bytes)
                 | Expires(ref bytes)
                 | Pad(ref bytes)
                 | Uid(ref bytes)
@@ -151,6 +164,7 @@ impl nlas::Nla for Nla {
                 | CacheInfo(ref bytes)
                 | MfcStats(ref bytes)
                 | Metrics(ref bytes)
+                | Encap(ref bytes)
                 => buffer.copy_from_slice(bytes.as_slice()),
 
             #[cfg(feature = "rich_nlas")]
@@ -158,7 +172,9 @@ impl nlas::Nla for Nla {
             #[cfg(feature = "rich_nlas")]
             MfcStats(ref stats) => stats.emit(buffer),
             #[cfg(feature = "rich_nlas")]
-            Metrics(ref metrics) => metrics.emit(buffer),
+            Metrics(ref metrics) => metrics.as_slice().emit(buffer),
+            #[cfg(feature = "rich_nlas")]
+            Encap(ref encap) => encap.emit(buffer),
             #[cfg(feature = "rich_nlas")]
             MultiPath(ref next_hops) => {
                 let mut offset = 0;
@@ -175,6 +191,7 @@ impl nlas::Nla for Nla {
                 | Priority(value)
                 | ProtocolInfo(value)
                 | Flow(value)
+                | NhId(value)
                 | Table(value)
                 | Mark(value)
                 => NativeEndian::write_u32(buffer, value),
@@ -212,6 +229,7 @@ impl nlas::Nla for Nla {
             Pad(_) => RTA_PAD,
             Uid(_) => RTA_UID,
             TtlPropagate(_) => RTA_TTL_PROPAGATE,
+            NhId(_) => RTA_NH_ID,
             Other(ref attr) => attr.kind(),
         }
     }
@@ -219,6 +237,16 @@ impl nlas::Nla for Nla {
 
 impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
     fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Self::parse_with_param(buf, LWTUNNEL_ENCAP_NONE)
+    }
+}
+
+/// The parameter is the `LWTUNNEL_ENCAP_*` type of the `RTA_ENCAP` attribute,
+/// given by the `RTA_ENCAP_TYPE` attribute of the same route or next-hop (see
+/// [`encap_type`]).
+impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, u16> for Nla {
+    #[cfg_attr(not(feature = "rich_nlas"), allow(unused_variables))]
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, encap_type: u16) -> Result<Self, DecodeError> {
         use self::Nla::*;
 
         let payload = buf.value();
@@ -233,7 +261,12 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
             RTA_VIA => Via(payload.to_vec()),
             RTA_NEWDST => NewDestination(payload.to_vec()),
             RTA_PREF => Pref(payload.to_vec()),
+            #[cfg(not(feature = "rich_nlas"))]
             RTA_ENCAP => Encap(payload.to_vec()),
+            #[cfg(feature = "rich_nlas")]
+            RTA_ENCAP => Encap(
+                LwtEncap::parse_with_param(buf, encap_type).context("invalid RTA_ENCAP value")?,
+            ),
             RTA_EXPIRES => Expires(payload.to_vec()),
             RTA_PAD => Pad(payload.to_vec()),
             RTA_UID => Uid(payload.to_vec()),
@@ -250,6 +283,7 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
             RTA_FLOW => Flow(parse_u32(payload).context("invalid RTA_FLOW value")?),
             RTA_TABLE => Table(parse_u32(payload).context("invalid RTA_TABLE value")?),
             RTA_MARK => Mark(parse_u32(payload).context("invalid RTA_MARK value")?),
+            RTA_NH_ID => NhId(parse_u32(payload).context("invalid RTA_NH_ID value")?),
 
             #[cfg(not(feature = "rich_nlas"))]
             RTA_CACHEINFO => CacheInfo(payload.to_vec()),
@@ -273,12 +307,15 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
             #[cfg(not(feature = "rich_nlas"))]
             RTA_METRICS => Metrics(payload.to_vec()),
             #[cfg(feature = "rich_nlas")]
-            RTA_METRICS => Metrics(
-                metrics::Metrics::parse(
-                    &NlaBuffer::new_checked(payload).context("invalid RTA_METRICS value")?,
-                )
-                .context("invalid RTA_METRICS value")?,
-            ),
+            RTA_METRICS => {
+                let mut metrics = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = &nla.context("invalid RTA_METRICS value")?;
+                    metrics
+                        .push(metrics::Metrics::parse(nla).context("invalid RTA_METRICS value")?);
+                }
+                Metrics(metrics)
+            }
             #[cfg(not(feature = "rich_nlas"))]
             RTA_MULTIPATH => MultiPath(payload.to_vec()),
             #[cfg(feature = "rich_nlas")]
@@ -302,3 +339,16 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
         })
     }
 }
+
+/// Find the `LWTUNNEL_ENCAP_*` type carried by the `RTA_ENCAP_TYPE` attribute,
+/// which is needed to parse the `RTA_ENCAP` attribute. The kernel puts it
+/// after `RTA_ENCAP`, so the attributes must be looked up beforehand.
+pub fn encap_type<'a, I>(nlas: I) -> u16
+where
+    I: Iterator<Item = Result<NlaBuffer<&'a [u8]>, DecodeError>>,
+{
+    nlas.filter_map(|nla| nla.ok())
+        .find(|nla| nla.kind() == RTA_ENCAP_TYPE)
+        .and_then(|nla| parse_u16(nla.value()).ok())
+        .unwrap_or(LWTUNNEL_ENCAP_NONE)
+}
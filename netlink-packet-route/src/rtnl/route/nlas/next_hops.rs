@@ -1,13 +1,14 @@
 use anyhow::Context;
 use std::net::IpAddr;
 
+use super::Nla;
 use crate::{
     constants::{self, RTA_GATEWAY},
     emit_ip,
     ip_len,
-    nlas::{Nla, NlaBuffer},
+    nlas::{self, NlaBuffer, NlasIterator},
     parsers::parse_ip,
-    traits::{Emitable, Parseable},
+    traits::{Emitable, Parseable, ParseableParametrized},
     DecodeError,
 };
 
@@ -28,7 +29,7 @@ buffer!(NextHopBuffer {
     flags: (u8, 2),
     hops: (u8, 3),
     interface_id: (u32, 4..8),
-    gateway_nla: (slice, GATEWAY_OFFSET..),
+    payload: (slice, PAYLOAD_OFFSET..),
 });
 
 impl<T: AsRef<[u8]>> NextHopBuffer<T> {
@@ -40,24 +41,42 @@ impl<T: AsRef<[u8]>> NextHopBuffer<T> {
 
     fn check_buffer_length(&self) -> Result<(), DecodeError> {
         let len = self.buffer.as_ref().len();
-        if len < 8 {
-            return Err(format!("invalid NextHopBuffer: length {} < {}", len, 8).into());
+        if len < PAYLOAD_OFFSET {
+            return Err(DecodeError::BufferTooShort {
+                context: "NextHopBuffer",
+                expected: PAYLOAD_OFFSET,
+                actual: len,
+            });
+        }
+        if (self.length() as usize) < PAYLOAD_OFFSET {
+            return Err(DecodeError::InvalidLength {
+                context: "NextHopBuffer",
+                length: self.length() as usize,
+            });
         }
         if len < self.length() as usize {
-            return Err(format!(
-                "invalid NextHopBuffer: length {} < {}",
-                len,
-                8 + self.length()
-            )
-            .into());
+            return Err(DecodeError::BufferTooShort {
+                context: "NextHopBuffer",
+                expected: self.length() as usize,
+                actual: len,
+            });
         }
         Ok(())
     }
 }
 
-const GATEWAY_OFFSET: usize = 8;
+impl<'a, T: AsRef<[u8]> + ?Sized> NextHopBuffer<&'a T> {
+    /// Attributes of the next-hop, which are only found in the first `length`
+    /// bytes of the buffer
+    pub fn nlas(&self) -> impl Iterator<Item = Result<NlaBuffer<&'a [u8]>, DecodeError>> {
+        let len = self.length() as usize - PAYLOAD_OFFSET;
+        NlasIterator::new(&self.payload()[..len])
+    }
+}
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+const PAYLOAD_OFFSET: usize = 8;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct NextHop {
     /// Next-hop flags (see [`NextHopFlags`])
     pub flags: NextHopFlags,
@@ -67,35 +86,42 @@ pub struct NextHop {
     pub interface_id: u32,
     /// Gateway address (it is actually encoded as an `RTA_GATEWAY` nla)
     pub gateway: Option<IpAddr>,
+    /// Other attributes of the next-hop, such as `RTA_FLOW`, `RTA_NEWDST`,
+    /// or `RTA_ENCAP_TYPE` and `RTA_ENCAP` for lightweight tunnels
+    pub nlas: Vec<Nla>,
 }
 
 impl<'a, T: AsRef<[u8]>> Parseable<NextHopBuffer<&'a T>> for NextHop {
     fn parse(buf: &NextHopBuffer<&T>) -> Result<NextHop, DecodeError> {
-        let gateway = if buf.length() as usize > GATEWAY_OFFSET {
-            let gateway_nla_buf = NlaBuffer::new_checked(buf.gateway_nla())
-                .context("cannot parse RTA_GATEWAY attribute in next-hop")?;
-            if gateway_nla_buf.kind() != RTA_GATEWAY {
-                return Err(format!("invalid RTA_GATEWAY attribute in next-hop: expected NLA type to be RTA_GATEWAY ({}), but got {} instead", RTA_GATEWAY, gateway_nla_buf.kind()).into());
+        let mut gateway = None;
+        let mut nlas = vec![];
+        let encap_type = super::encap_type(buf.nlas());
+        for nla_buf in buf.nlas() {
+            let nla_buf = nla_buf.context("cannot parse attribute in next-hop")?;
+            if nla_buf.kind() == RTA_GATEWAY {
+                gateway = Some(parse_ip(nla_buf.value()).context(
+                    "invalid RTA_GATEWAY attribute in next-hop: failed to parse NLA value as an IP address",
+                )?);
+            } else {
+                nlas.push(
+                    Nla::parse_with_param(&nla_buf, encap_type)
+                        .context("invalid attribute in next-hop")?,
+                );
             }
-            let gateway = parse_ip(gateway_nla_buf.value()).context(
-                "invalid RTA_GATEWAY attribute in next-hop: failed to parse NLA value as an IP address",
-            )?;
-            Some(gateway)
-        } else {
-            None
-        };
+        }
         Ok(NextHop {
             flags: NextHopFlags::from_bits_truncate(buf.flags()),
             hops: buf.hops(),
             interface_id: buf.interface_id(),
             gateway,
+            nlas,
         })
     }
 }
 
 struct GatewayNla<'a>(&'a IpAddr);
 
-impl<'a> Nla for GatewayNla<'a> {
+impl<'a> nlas::Nla for GatewayNla<'a> {
     fn value_len(&self) -> usize {
         ip_len(self.0)
     }
@@ -118,6 +144,7 @@ impl Emitable for NextHop {
                 4 + ip_len(ip)
             })
             .unwrap_or(0)
+            + self.nlas.as_slice().buffer_len()
     }
 
     fn emit(&self, buffer: &mut [u8]) {
@@ -126,9 +153,14 @@ impl Emitable for NextHop {
         nh_buffer.set_flags(self.flags.bits());
         nh_buffer.set_hops(self.hops);
         nh_buffer.set_interface_id(self.interface_id);
+        let mut offset = 0;
         if let Some(ref gateway) = self.gateway {
             let gateway_nla = GatewayNla(gateway);
-            gateway_nla.emit(nh_buffer.gateway_nla_mut());
+            gateway_nla.emit(nh_buffer.payload_mut());
+            offset = gateway_nla.buffer_len();
         }
+        self.nlas
+            .as_slice()
+            .emit(&mut nh_buffer.payload_mut()[offset..]);
     }
 }
@@ -0,0 +1,379 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::{
+    constants::*,
+    nlas::{DefaultNla, Nla, NlaBuffer},
+    parsers::{parse_i32, parse_ipv4, parse_ipv6, parse_u32},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+/// Attributes of the `LWTUNNEL_ENCAP_SEG6` encapsulation
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Seg6IpTunnel {
+    Srh(Seg6Encap),
+    Other(DefaultNla),
+}
+
+impl Nla for Seg6IpTunnel {
+    fn value_len(&self) -> usize {
+        match *self {
+            Seg6IpTunnel::Srh(ref encap) => encap.buffer_len(),
+            Seg6IpTunnel::Other(ref attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match *self {
+            Seg6IpTunnel::Srh(ref encap) => encap.emit(buffer),
+            Seg6IpTunnel::Other(ref attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match *self {
+            Seg6IpTunnel::Srh(_) => SEG6_IPTUNNEL_SRH,
+            Seg6IpTunnel::Other(ref attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Seg6IpTunnel {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            SEG6_IPTUNNEL_SRH => Seg6IpTunnel::Srh(
+                Seg6Encap::parse(payload).context("invalid SEG6_IPTUNNEL_SRH value")?,
+            ),
+            kind => Seg6IpTunnel::Other(
+                DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
+
+/// Segment routing encapsulation (`struct seg6_iptunnel_encap`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Seg6Encap {
+    pub mode: Seg6Mode,
+    pub srh: Ipv6SrHeader,
+}
+
+impl Seg6Encap {
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        if payload.len() < 4 {
+            return Err(format!("invalid seg6 encapsulation length {}", payload.len()).into());
+        }
+        Ok(Seg6Encap {
+            mode: parse_i32(&payload[..4])?.into(),
+            srh: Ipv6SrHeader::parse(&payload[4..])?,
+        })
+    }
+}
+
+impl Emitable for Seg6Encap {
+    fn buffer_len(&self) -> usize {
+        4 + self.srh.buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        NativeEndian::write_i32(&mut buffer[..4], self.mode.into());
+        self.srh.emit(&mut buffer[4..]);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Seg6Mode {
+    /// Insert the SRH in the IPv6 header of the packet. The last segment
+    /// is replaced by the original destination of the packet, so it should
+    /// be set to `::`.
+    Inline,
+    /// Encapsulate the packet in an outer IPv6 header carrying the SRH
+    Encap,
+    /// Encapsulate the L2 frame in an outer IPv6 header carrying the SRH
+    L2Encap,
+    /// Like `Encap`, without the first segment in the SRH
+    EncapRed,
+    /// Like `L2Encap`, without the first segment in the SRH
+    L2EncapRed,
+    Other(i32),
+}
+
+impl From<i32> for Seg6Mode {
+    fn from(value: i32) -> Self {
+        use self::Seg6Mode::*;
+        match value {
+            SEG6_IPTUN_MODE_INLINE => Inline,
+            SEG6_IPTUN_MODE_ENCAP => Encap,
+            SEG6_IPTUN_MODE_L2ENCAP => L2Encap,
+            SEG6_IPTUN_MODE_ENCAP_RED => EncapRed,
+            SEG6_IPTUN_MODE_L2ENCAP_RED => L2EncapRed,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<Seg6Mode> for i32 {
+    fn from(value: Seg6Mode) -> Self {
+        use self::Seg6Mode::*;
+        match value {
+            Inline => SEG6_IPTUN_MODE_INLINE,
+            Encap => SEG6_IPTUN_MODE_ENCAP,
+            L2Encap => SEG6_IPTUN_MODE_L2ENCAP,
+            EncapRed => SEG6_IPTUN_MODE_ENCAP_RED,
+            L2EncapRed => SEG6_IPTUN_MODE_L2ENCAP_RED,
+            Other(other) => other,
+        }
+    }
+}
+
+const IPV6_SR_HEADER_LEN: usize = 8;
+const SEGMENT_LEN: usize = 16;
+
+/// IPv6 segment routing header (`struct ipv6_sr_hdr`)
+///
+/// The segments are given in the order they are visited: the first one is
+/// the destination of the packet. On the wire, the SRH holds them in the
+/// reverse order.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Ipv6SrHeader {
+    pub flags: u8,
+    pub tag: u16,
+    pub segments: Vec<Ipv6Addr>,
+    /// Optional TLVs (HMAC, padding...) that follow the segments
+    pub tlvs: Vec<u8>,
+}
+
+impl Ipv6SrHeader {
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        if payload.len() < IPV6_SR_HEADER_LEN {
+            return Err(format!("invalid segment routing header length {}", payload.len()).into());
+        }
+        let len = IPV6_SR_HEADER_LEN + payload[1] as usize * 8;
+        let segments_len = (payload[4] as usize + 1) * SEGMENT_LEN;
+        if payload.len() < len || len < IPV6_SR_HEADER_LEN + segments_len {
+            return Err(format!(
+                "invalid segment routing header: length {}, header length {}, {} segments",
+                payload.len(),
+                len,
+                payload[4] as usize + 1
+            )
+            .into());
+        }
+        let mut segments = payload[IPV6_SR_HEADER_LEN..IPV6_SR_HEADER_LEN + segments_len]
+            .chunks(SEGMENT_LEN)
+            .map(|segment| parse_ipv6(segment).map(Ipv6Addr::from))
+            .collect::<Result<Vec<_>, _>>()
+            .context("invalid segment")?;
+        segments.reverse();
+        Ok(Ipv6SrHeader {
+            flags: payload[5],
+            tag: BigEndian::read_u16(&payload[6..8]),
+            segments,
+            tlvs: payload[IPV6_SR_HEADER_LEN + segments_len..len].to_vec(),
+        })
+    }
+}
+
+impl Emitable for Ipv6SrHeader {
+    fn buffer_len(&self) -> usize {
+        IPV6_SR_HEADER_LEN + self.segments.len() * SEGMENT_LEN + self.tlvs.len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let last_segment = self.segments.len().saturating_sub(1) as u8;
+        // next header, set by the kernel
+        buffer[0] = 0;
+        buffer[1] = ((self.buffer_len() - IPV6_SR_HEADER_LEN) / 8) as u8;
+        buffer[2] = IPV6_SRCRT_TYPE_4;
+        // segments left
+        buffer[3] = last_segment;
+        // first segment
+        buffer[4] = last_segment;
+        buffer[5] = self.flags;
+        BigEndian::write_u16(&mut buffer[6..8], self.tag);
+        let segments = &mut buffer[IPV6_SR_HEADER_LEN..];
+        for (segment, chunk) in self
+            .segments
+            .iter()
+            .rev()
+            .zip(segments.chunks_mut(SEGMENT_LEN))
+        {
+            chunk.copy_from_slice(&segment.octets());
+        }
+        buffer[IPV6_SR_HEADER_LEN + self.segments.len() * SEGMENT_LEN..self.buffer_len()]
+            .copy_from_slice(&self.tlvs);
+    }
+}
+
+/// Attributes of the `LWTUNNEL_ENCAP_SEG6_LOCAL` encapsulation, which tells
+/// what to do with the packets whose active segment is the destination of
+/// the route
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Seg6Local {
+    Action(Seg6LocalAction),
+    /// Segment routing header used by the binding actions
+    Srh(Ipv6SrHeader),
+    /// Table used by the lookup actions
+    Table(u32),
+    /// IPv4 nexthop of the `EndDX4` action
+    Nh4(Ipv4Addr),
+    /// IPv6 nexthop of the `EndX` and `EndDX6` actions
+    Nh6(Ipv6Addr),
+    Iif(u32),
+    /// Output interface of the `EndDX2` action
+    Oif(u32),
+    /// VRF table used by the `EndDT4`, `EndDT6` and `EndDT46` actions
+    VrfTable(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for Seg6Local {
+    fn value_len(&self) -> usize {
+        use self::Seg6Local::*;
+        match *self {
+            Srh(ref srh) => srh.buffer_len(),
+            Nh6(_) => 16,
+            Action(_) | Table(_) | Nh4(_) | Iif(_) | Oif(_) | VrfTable(_) => 4,
+            Other(ref attr) => attr.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::Seg6Local::*;
+        match *self {
+            Action(action) => NativeEndian::write_u32(buffer, action.into()),
+            Srh(ref srh) => srh.emit(buffer),
+            Nh4(ref addr) => buffer.copy_from_slice(&addr.octets()),
+            Nh6(ref addr) => buffer.copy_from_slice(&addr.octets()),
+            Table(value) | Iif(value) | Oif(value) | VrfTable(value) => {
+                NativeEndian::write_u32(buffer, value)
+            }
+            Other(ref attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::Seg6Local::*;
+        match *self {
+            Action(_) => SEG6_LOCAL_ACTION,
+            Srh(_) => SEG6_LOCAL_SRH,
+            Table(_) => SEG6_LOCAL_TABLE,
+            Nh4(_) => SEG6_LOCAL_NH4,
+            Nh6(_) => SEG6_LOCAL_NH6,
+            Iif(_) => SEG6_LOCAL_IIF,
+            Oif(_) => SEG6_LOCAL_OIF,
+            VrfTable(_) => SEG6_LOCAL_VRFTABLE,
+            Other(ref attr) => attr.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Seg6Local {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::Seg6Local::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            SEG6_LOCAL_ACTION => Action(
+                parse_u32(payload)
+                    .context("invalid SEG6_LOCAL_ACTION value")?
+                    .into(),
+            ),
+            SEG6_LOCAL_SRH => {
+                Srh(Ipv6SrHeader::parse(payload).context("invalid SEG6_LOCAL_SRH value")?)
+            }
+            SEG6_LOCAL_TABLE => {
+                Table(parse_u32(payload).context("invalid SEG6_LOCAL_TABLE value")?)
+            }
+            SEG6_LOCAL_NH4 => Nh4(parse_ipv4(payload).context("invalid SEG6_LOCAL_NH4 value")?),
+            SEG6_LOCAL_NH6 => Nh6(parse_ipv6(payload)
+                .context("invalid SEG6_LOCAL_NH6 value")?
+                .into()),
+            SEG6_LOCAL_IIF => Iif(parse_u32(payload).context("invalid SEG6_LOCAL_IIF value")?),
+            SEG6_LOCAL_OIF => Oif(parse_u32(payload).context("invalid SEG6_LOCAL_OIF value")?),
+            SEG6_LOCAL_VRFTABLE => {
+                VrfTable(parse_u32(payload).context("invalid SEG6_LOCAL_VRFTABLE value")?)
+            }
+            kind => Other(DefaultNla::parse(buf).context(format!("unknown NLA type {}", kind))?),
+        })
+    }
+}
+
+/// Endpoint behavior of a `Seg6Local` encapsulation
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Seg6LocalAction {
+    Unspec,
+    End,
+    EndX,
+    EndT,
+    EndDX2,
+    EndDX6,
+    EndDX4,
+    EndDT6,
+    EndDT4,
+    EndB6,
+    EndB6Encap,
+    EndBM,
+    EndS,
+    EndAS,
+    EndAM,
+    EndBPF,
+    EndDT46,
+    Other(u32),
+}
+
+impl From<u32> for Seg6LocalAction {
+    fn from(value: u32) -> Self {
+        use self::Seg6LocalAction::*;
+        match value {
+            SEG6_LOCAL_ACTION_UNSPEC => Unspec,
+            SEG6_LOCAL_ACTION_END => End,
+            SEG6_LOCAL_ACTION_END_X => EndX,
+            SEG6_LOCAL_ACTION_END_T => EndT,
+            SEG6_LOCAL_ACTION_END_DX2 => EndDX2,
+            SEG6_LOCAL_ACTION_END_DX6 => EndDX6,
+            SEG6_LOCAL_ACTION_END_DX4 => EndDX4,
+            SEG6_LOCAL_ACTION_END_DT6 => EndDT6,
+            SEG6_LOCAL_ACTION_END_DT4 => EndDT4,
+            SEG6_LOCAL_ACTION_END_B6 => EndB6,
+            SEG6_LOCAL_ACTION_END_B6_ENCAP => EndB6Encap,
+            SEG6_LOCAL_ACTION_END_BM => EndBM,
+            SEG6_LOCAL_ACTION_END_S => EndS,
+            SEG6_LOCAL_ACTION_END_AS => EndAS,
+            SEG6_LOCAL_ACTION_END_AM => EndAM,
+            SEG6_LOCAL_ACTION_END_BPF => EndBPF,
+            SEG6_LOCAL_ACTION_END_DT46 => EndDT46,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<Seg6LocalAction> for u32 {
+    fn from(value: Seg6LocalAction) -> Self {
+        use self::Seg6LocalAction::*;
+        match value {
+            Unspec => SEG6_LOCAL_ACTION_UNSPEC,
+            End => SEG6_LOCAL_ACTION_END,
+            EndX => SEG6_LOCAL_ACTION_END_X,
+            EndT => SEG6_LOCAL_ACTION_END_T,
+            EndDX2 => SEG6_LOCAL_ACTION_END_DX2,
+            EndDX6 => SEG6_LOCAL_ACTION_END_DX6,
+            EndDX4 => SEG6_LOCAL_ACTION_END_DX4,
+            EndDT6 => SEG6_LOCAL_ACTION_END_DT6,
+            EndDT4 => SEG6_LOCAL_ACTION_END_DT4,
+            EndB6 => SEG6_LOCAL_ACTION_END_B6,
+            EndB6Encap => SEG6_LOCAL_ACTION_END_B6_ENCAP,
+            EndBM => SEG6_LOCAL_ACTION_END_BM,
+            EndS => SEG6_LOCAL_ACTION_END_S,
+            EndAS => SEG6_LOCAL_ACTION_END_AS,
+            EndAM => SEG6_LOCAL_ACTION_END_AM,
+            EndBPF => SEG6_LOCAL_ACTION_END_BPF,
+            EndDT46 => SEG6_LOCAL_ACTION_END_DT46,
+            Other(other) => other,
+        }
+    }
+}
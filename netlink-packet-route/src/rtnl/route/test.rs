@@ -4,14 +4,34 @@
 mod test_rich_nlas {
     use crate::{
         rtnl::route::{
-            nlas::{NextHop, NextHopFlags, Nla},
+            nlas::{
+                Ipv6SrHeader,
+                LwtEncap,
+                Metrics,
+                MplsIpTunnel,
+                MplsLabel,
+                NextHop,
+                NextHopFlags,
+                Nla,
+                Seg6Encap,
+                Seg6IpTunnel,
+                Seg6Local,
+                Seg6LocalAction,
+                Seg6Mode,
+            },
             RouteFlags,
             RouteMessage,
             RouteMessageBuffer,
+            RouteProtocol,
+            RouteScope,
+            RouteType,
         },
         utils::{Emitable, Parseable},
+        LWTUNNEL_ENCAP_MPLS,
+        LWTUNNEL_ENCAP_SEG6,
+        LWTUNNEL_ENCAP_SEG6_LOCAL,
     };
-    use std::net::Ipv6Addr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
 
     #[rustfmt::skip]
     static ROUTE_MSG: [u8; 100] = [
@@ -71,9 +91,9 @@ mod test_rich_nlas {
         msg.header.source_prefix_length = 0;
         msg.header.tos = 0;
         msg.header.table = 0xfe;
-        msg.header.protocol = 0x03;
-        msg.header.scope = 0x00;
-        msg.header.kind = 0x01;
+        msg.header.protocol = RouteProtocol::Boot;
+        msg.header.scope = RouteScope::Universe;
+        msg.header.kind = RouteType::Unicast;
         msg.header.flags = RouteFlags::empty();
         msg.nlas = vec![
             Nla::Destination("1001::".parse::<Ipv6Addr>().unwrap().octets().to_vec()),
@@ -83,18 +103,21 @@ mod test_rich_nlas {
                     hops: 0,
                     interface_id: 0,
                     gateway: Some("fc00::1".parse().unwrap()),
+                    nlas: vec![],
                 },
                 NextHop {
                     flags: NextHopFlags::empty(),
                     hops: 0,
                     interface_id: 0,
                     gateway: Some("fc01::1".parse().unwrap()),
+                    nlas: vec![],
                 },
                 NextHop {
                     flags: NextHopFlags::empty(),
                     hops: 0,
                     interface_id: 2,
                     gateway: None,
+                    nlas: vec![],
                 },
             ]),
         ];
@@ -118,4 +141,703 @@ mod test_rich_nlas {
         msg.emit(&mut buf[..]);
         assert_eq!(buf, ROUTE_MSG);
     }
+
+    // ip route add 10.1.0.0/16 \
+    //     nexthop via 192.168.1.1 dev eth0 \
+    //     nexthop via 192.168.2.1 dev eth1 weight 2 realm 5 \
+    //     nexthop via 192.168.3.1 dev eth2
+    #[rustfmt::skip]
+    static ECMP_ROUTE_MSG: [u8; 80] = [
+        0x02, // address family
+        0x10, // length of destination
+        0x00, // length of source
+        0x00, // TOS
+        0xfe, // routing table id
+        0x03, // routing protocol (boot)
+        0x00, // route origin (global)
+        0x01, // gateway or direct route
+        0x00, 0x00, 0x00, 0x00,
+
+            // Route destination address NLA
+            0x08, 0x00, // Length (8)
+            0x01, 0x00, // Type
+            0x0a, 0x01, 0x00, 0x00, // Value
+
+            // RTA_MULTIPATH attribute
+            0x3c, 0x00, // Length (60)
+            0x09, 0x00, // Type
+
+                // next-hop 1
+                0x10, 0x00, // length (16)
+                0x00, // flags
+                0x00, // hops
+                0x02, 0x00, 0x00, 0x00, // interface ID
+                    // nested RTA_GATEWAY
+                    0x08, 0x00, // Length (8)
+                    0x05, 0x00, // Type
+                    0xc0, 0xa8, 0x01, 0x01, // Value
+
+                // next-hop 2
+                0x18, 0x00, // length (24)
+                0x00, // flags
+                0x01, // hops
+                0x03, 0x00, 0x00, 0x00, // interface ID
+                    // nested RTA_GATEWAY
+                    0x08, 0x00, // Length (8)
+                    0x05, 0x00, // Type
+                    0xc0, 0xa8, 0x02, 0x01, // Value
+                    // nested RTA_FLOW
+                    0x08, 0x00, // Length (8)
+                    0x0b, 0x00, // Type
+                    0x05, 0x00, 0x00, 0x00, // Value
+
+                // next-hop 3
+                0x10, 0x00, // length (16)
+                0x00, // flags
+                0x00, // hops
+                0x04, 0x00, 0x00, 0x00, // interface ID
+                    // nested RTA_GATEWAY
+                    0x08, 0x00, // Length (8)
+                    0x05, 0x00, // Type
+                    0xc0, 0xa8, 0x03, 0x01, // Value
+    ];
+
+    fn ecmp_route_message() -> RouteMessage {
+        let mut msg = RouteMessage::default();
+        msg.header.address_family = 0x02;
+        msg.header.destination_prefix_length = 0x10;
+        msg.header.table = 0xfe;
+        msg.header.protocol = RouteProtocol::Boot;
+        msg.header.kind = RouteType::Unicast;
+        msg.nlas = vec![
+            Nla::Destination(vec![10, 1, 0, 0]),
+            Nla::MultiPath(vec![
+                NextHop {
+                    flags: NextHopFlags::empty(),
+                    hops: 0,
+                    interface_id: 2,
+                    gateway: Some(Ipv4Addr::new(192, 168, 1, 1).into()),
+                    nlas: vec![],
+                },
+                NextHop {
+                    flags: NextHopFlags::empty(),
+                    hops: 1,
+                    interface_id: 3,
+                    gateway: Some(Ipv4Addr::new(192, 168, 2, 1).into()),
+                    nlas: vec![Nla::Flow(5)],
+                },
+                NextHop {
+                    flags: NextHopFlags::empty(),
+                    hops: 0,
+                    interface_id: 4,
+                    gateway: Some(Ipv4Addr::new(192, 168, 3, 1).into()),
+                    nlas: vec![],
+                },
+            ]),
+        ];
+        msg
+    }
+
+    #[test]
+    fn parse_message_with_ecmp_multipath_nla() {
+        let expected = ecmp_route_message();
+        let actual =
+            RouteMessage::parse(&RouteMessageBuffer::new_checked(&&ECMP_ROUTE_MSG[..]).unwrap())
+                .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn emit_message_with_ecmp_multipath_nla() {
+        let msg = ecmp_route_message();
+        let mut buf = [0; 80];
+        assert_eq!(msg.buffer_len(), 80);
+        msg.emit(&mut buf[..]);
+        assert_eq!(buf, ECMP_ROUTE_MSG);
+    }
+
+    // ip route add 10.2.0.0/16 via 192.168.1.1 mtu 1400 rtt 10ms
+    #[rustfmt::skip]
+    static METRICS_ROUTE_MSG: [u8; 48] = [
+        0x02, // address family
+        0x10, // length of destination
+        0x00, // length of source
+        0x00, // TOS
+        0xfe, // routing table id
+        0x03, // routing protocol (boot)
+        0x00, // route origin (global)
+        0x01, // gateway or direct route
+        0x00, 0x00, 0x00, 0x00,
+
+            // Route destination address NLA
+            0x08, 0x00, // Length (8)
+            0x01, 0x00, // Type
+            0x0a, 0x02, 0x00, 0x00, // Value
+
+            // Route gateway NLA
+            0x08, 0x00, // Length (8)
+            0x05, 0x00, // Type
+            0xc0, 0xa8, 0x01, 0x01, // Value
+
+            // RTA_METRICS attribute
+            0x14, 0x00, // Length (20)
+            0x08, 0x00, // Type
+
+                // nested RTAX_MTU
+                0x08, 0x00, // Length (8)
+                0x02, 0x00, // Type
+                0x78, 0x05, 0x00, 0x00, // Value (1400)
+
+                // nested RTAX_RTT
+                0x08, 0x00, // Length (8)
+                0x04, 0x00, // Type
+                0x50, 0x00, 0x00, 0x00, // Value (10ms << 3)
+    ];
+
+    fn metrics_route_message() -> RouteMessage {
+        let mut msg = RouteMessage::default();
+        msg.header.address_family = 0x02;
+        msg.header.destination_prefix_length = 0x10;
+        msg.header.table = 0xfe;
+        msg.header.protocol = RouteProtocol::Boot;
+        msg.header.kind = RouteType::Unicast;
+        msg.nlas = vec![
+            Nla::Destination(vec![10, 2, 0, 0]),
+            Nla::Gateway(vec![192, 168, 1, 1]),
+            Nla::Metrics(vec![Metrics::Mtu(1400), Metrics::Rtt(10 << 3)]),
+        ];
+        msg
+    }
+
+    #[test]
+    fn parse_message_with_metrics_nla() {
+        let expected = metrics_route_message();
+        let actual =
+            RouteMessage::parse(&RouteMessageBuffer::new_checked(&&METRICS_ROUTE_MSG[..]).unwrap())
+                .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn emit_message_with_metrics_nla() {
+        let msg = metrics_route_message();
+        let mut buf = [0; 48];
+        assert_eq!(msg.buffer_len(), 48);
+        msg.emit(&mut buf[..]);
+        assert_eq!(buf, METRICS_ROUTE_MSG);
+    }
+
+    // ip route add 10.3.0.0/16 encap mpls 100 via 192.168.1.1
+    #[rustfmt::skip]
+    static MPLS_ROUTE_MSG: [u8; 48] = [
+        0x02, // address family
+        0x10, // length of destination
+        0x00, // length of source
+        0x00, // TOS
+        0xfe, // routing table id
+        0x03, // routing protocol (boot)
+        0x00, // route origin (global)
+        0x01, // gateway or direct route
+        0x00, 0x00, 0x00, 0x00,
+
+            // Route destination address NLA
+            0x08, 0x00, // Length (8)
+            0x01, 0x00, // Type
+            0x0a, 0x03, 0x00, 0x00, // Value
+
+            // RTA_ENCAP attribute
+            0x0c, 0x00, // Length (12)
+            0x16, 0x00, // Type
+
+                // nested MPLS_IPTUNNEL_DST
+                0x08, 0x00, // Length (8)
+                0x01, 0x00, // Type
+                0x00, 0x06, 0x41, 0x00, // label 100, bottom of stack
+
+            // RTA_ENCAP_TYPE attribute
+            0x06, 0x00, // Length (6)
+            0x15, 0x00, // Type
+            0x01, 0x00, // Value (LWTUNNEL_ENCAP_MPLS)
+            0x00, 0x00, // Padding
+
+            // Route gateway NLA
+            0x08, 0x00, // Length (8)
+            0x05, 0x00, // Type
+            0xc0, 0xa8, 0x01, 0x01, // Value
+    ];
+
+    fn mpls_route_message(encap: Vec<MplsIpTunnel>) -> RouteMessage {
+        let mut msg = RouteMessage::default();
+        msg.header.address_family = 0x02;
+        msg.header.destination_prefix_length = 0x10;
+        msg.header.table = 0xfe;
+        msg.header.protocol = RouteProtocol::Boot;
+        msg.header.kind = RouteType::Unicast;
+        msg.nlas = vec![
+            Nla::Destination(vec![10, 3, 0, 0]),
+            Nla::Encap(LwtEncap::Mpls(encap)),
+            Nla::EncapType(LWTUNNEL_ENCAP_MPLS),
+            Nla::Gateway(vec![192, 168, 1, 1]),
+        ];
+        msg
+    }
+
+    fn single_label_route_message() -> RouteMessage {
+        mpls_route_message(vec![MplsIpTunnel::Destination(vec![MplsLabel {
+            label: 100,
+            tc: 0,
+            bos: true,
+            ttl: 0,
+        }])])
+    }
+
+    #[test]
+    fn parse_message_with_mpls_encap() {
+        let expected = single_label_route_message();
+        let actual =
+            RouteMessage::parse(&RouteMessageBuffer::new_checked(&&MPLS_ROUTE_MSG[..]).unwrap())
+                .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn emit_message_with_mpls_encap() {
+        let msg = single_label_route_message();
+        let mut buf = [0; 48];
+        assert_eq!(msg.buffer_len(), 48);
+        msg.emit(&mut buf[..]);
+        assert_eq!(buf, MPLS_ROUTE_MSG);
+    }
+
+    // ip route add 10.3.0.0/16 encap mpls 100/200 ttl 64 via 192.168.1.1
+    #[rustfmt::skip]
+    static MPLS_STACK_ROUTE_MSG: [u8; 60] = [
+        0x02, // address family
+        0x10, // length of destination
+        0x00, // length of source
+        0x00, // TOS
+        0xfe, // routing table id
+        0x03, // routing protocol (boot)
+        0x00, // route origin (global)
+        0x01, // gateway or direct route
+        0x00, 0x00, 0x00, 0x00,
+
+            // Route destination address NLA
+            0x08, 0x00, // Length (8)
+            0x01, 0x00, // Type
+            0x0a, 0x03, 0x00, 0x00, // Value
+
+            // RTA_ENCAP attribute
+            0x18, 0x00, // Length (24)
+            0x16, 0x00, // Type
+
+                // nested MPLS_IPTUNNEL_DST
+                0x0c, 0x00, // Length (12)
+                0x01, 0x00, // Type
+                0x00, 0x06, 0x40, 0x00, // label 100
+                0x00, 0x0c, 0x81, 0x00, // label 200, bottom of stack
+
+                // nested MPLS_IPTUNNEL_TTL
+                0x05, 0x00, // Length (5)
+                0x02, 0x00, // Type
+                0x40, // Value (64)
+                0x00, 0x00, 0x00, // Padding
+
+            // RTA_ENCAP_TYPE attribute
+            0x06, 0x00, // Length (6)
+            0x15, 0x00, // Type
+            0x01, 0x00, // Value (LWTUNNEL_ENCAP_MPLS)
+            0x00, 0x00, // Padding
+
+            // Route gateway NLA
+            0x08, 0x00, // Length (8)
+            0x05, 0x00, // Type
+            0xc0, 0xa8, 0x01, 0x01, // Value
+    ];
+
+    fn label_stack_route_message() -> RouteMessage {
+        mpls_route_message(vec![
+            MplsIpTunnel::Destination(vec![
+                MplsLabel {
+                    label: 100,
+                    tc: 0,
+                    bos: false,
+                    ttl: 0,
+                },
+                MplsLabel {
+                    label: 200,
+                    tc: 0,
+                    bos: true,
+                    ttl: 0,
+                },
+            ]),
+            MplsIpTunnel::Ttl(64),
+        ])
+    }
+
+    #[test]
+    fn parse_message_with_mpls_label_stack() {
+        let expected = label_stack_route_message();
+        let actual = RouteMessage::parse(
+            &RouteMessageBuffer::new_checked(&&MPLS_STACK_ROUTE_MSG[..]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn emit_message_with_mpls_label_stack() {
+        let msg = label_stack_route_message();
+        let mut buf = [0; 60];
+        assert_eq!(msg.buffer_len(), 60);
+        msg.emit(&mut buf[..]);
+        assert_eq!(buf, MPLS_STACK_ROUTE_MSG);
+    }
+
+    #[test]
+    fn mpls_label_encoding() {
+        let label = MplsLabel {
+            label: 0xabcde,
+            tc: 5,
+            bos: true,
+            ttl: 0x40,
+        };
+        assert_eq!(u32::from(label), 0xabcd_eb40);
+        assert_eq!(MplsLabel::from(0xabcd_eb40), label);
+    }
+
+    // ip -6 route add fc00::/64 encap seg6 mode encap segs fc00::a,fc00::b dev eth0
+    #[rustfmt::skip]
+    static SEG6_ROUTE_MSG: [u8; 100] = [
+        0x0a, // address family
+        0x40, // length of destination
+        0x00, // length of source
+        0x00, // TOS
+        0xfe, // routing table id
+        0x03, // routing protocol (boot)
+        0x00, // route origin (global)
+        0x01, // gateway or direct route
+        0x00, 0x00, 0x00, 0x00,
+
+            // Route destination address NLA
+            0x14, 0x00, // Length (20)
+            0x01, 0x00, // Type
+            0xfc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+
+            // RTA_ENCAP attribute
+            0x34, 0x00, // Length (52)
+            0x16, 0x00, // Type
+
+                // nested SEG6_IPTUNNEL_SRH
+                0x30, 0x00, // Length (48)
+                0x01, 0x00, // Type
+                0x01, 0x00, 0x00, 0x00, // mode (encap)
+                0x00, // next header
+                0x04, // header length
+                0x04, // routing type (SRH)
+                0x01, // segments left
+                0x01, // first segment
+                0x00, // flags
+                0x00, 0x00, // tag
+                // last segment
+                0xfc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0b,
+                // first segment
+                0xfc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a,
+
+            // RTA_ENCAP_TYPE attribute
+            0x06, 0x00, // Length (6)
+            0x15, 0x00, // Type
+            0x05, 0x00, // Value (LWTUNNEL_ENCAP_SEG6)
+            0x00, 0x00, // Padding
+
+            // RTA_OIF attribute
+            0x08, 0x00, // Length (8)
+            0x04, 0x00, // Type
+            0x02, 0x00, 0x00, 0x00, // Value
+    ];
+
+    fn seg6_route_message() -> RouteMessage {
+        let mut msg = RouteMessage::default();
+        msg.header.address_family = 0x0a;
+        msg.header.destination_prefix_length = 0x40;
+        msg.header.table = 0xfe;
+        msg.header.protocol = RouteProtocol::Boot;
+        msg.header.kind = RouteType::Unicast;
+        msg.nlas = vec![
+            Nla::Destination("fc00::".parse::<Ipv6Addr>().unwrap().octets().to_vec()),
+            Nla::Encap(LwtEncap::Seg6(vec![Seg6IpTunnel::Srh(Seg6Encap {
+                mode: Seg6Mode::Encap,
+                srh: Ipv6SrHeader {
+                    flags: 0,
+                    tag: 0,
+                    segments: vec!["fc00::a".parse().unwrap(), "fc00::b".parse().unwrap()],
+                    tlvs: vec![],
+                },
+            })])),
+            Nla::EncapType(LWTUNNEL_ENCAP_SEG6),
+            Nla::Oif(2),
+        ];
+        msg
+    }
+
+    #[test]
+    fn parse_message_with_seg6_encap() {
+        let expected = seg6_route_message();
+        let actual =
+            RouteMessage::parse(&RouteMessageBuffer::new_checked(&&SEG6_ROUTE_MSG[..]).unwrap())
+                .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn emit_message_with_seg6_encap() {
+        let msg = seg6_route_message();
+        let mut buf = [0; 100];
+        assert_eq!(msg.buffer_len(), 100);
+        msg.emit(&mut buf[..]);
+        assert_eq!(buf, SEG6_ROUTE_MSG);
+    }
+
+    // ip -6 route add fc00::100/128 encap seg6local action End.DX4 nh4 192.168.1.1 dev eth0
+    #[rustfmt::skip]
+    static SEG6_LOCAL_ROUTE_MSG: [u8; 68] = [
+        0x0a, // address family
+        0x80, // length of destination
+        0x00, // length of source
+        0x00, // TOS
+        0xfe, // routing table id
+        0x03, // routing protocol (boot)
+        0x00, // route origin (global)
+        0x01, // gateway or direct route
+        0x00, 0x00, 0x00, 0x00,
+
+            // Route destination address NLA
+            0x14, 0x00, // Length (20)
+            0x01, 0x00, // Type
+            0xfc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+
+            // RTA_ENCAP attribute
+            0x14, 0x00, // Length (20)
+            0x16, 0x00, // Type
+
+                // nested SEG6_LOCAL_ACTION
+                0x08, 0x00, // Length (8)
+                0x01, 0x00, // Type
+                0x06, 0x00, 0x00, 0x00, // Value (End.DX4)
+
+                // nested SEG6_LOCAL_NH4
+                0x08, 0x00, // Length (8)
+                0x04, 0x00, // Type
+                0xc0, 0xa8, 0x01, 0x01, // Value
+
+            // RTA_ENCAP_TYPE attribute
+            0x06, 0x00, // Length (6)
+            0x15, 0x00, // Type
+            0x07, 0x00, // Value (LWTUNNEL_ENCAP_SEG6_LOCAL)
+            0x00, 0x00, // Padding
+
+            // RTA_OIF attribute
+            0x08, 0x00, // Length (8)
+            0x04, 0x00, // Type
+            0x02, 0x00, 0x00, 0x00, // Value
+    ];
+
+    fn seg6_local_route_message() -> RouteMessage {
+        let mut msg = RouteMessage::default();
+        msg.header.address_family = 0x0a;
+        msg.header.destination_prefix_length = 0x80;
+        msg.header.table = 0xfe;
+        msg.header.protocol = RouteProtocol::Boot;
+        msg.header.kind = RouteType::Unicast;
+        msg.nlas = vec![
+            Nla::Destination("fc00::100".parse::<Ipv6Addr>().unwrap().octets().to_vec()),
+            Nla::Encap(LwtEncap::Seg6Local(vec![
+                Seg6Local::Action(Seg6LocalAction::EndDX4),
+                Seg6Local::Nh4(Ipv4Addr::new(192, 168, 1, 1)),
+            ])),
+            Nla::EncapType(LWTUNNEL_ENCAP_SEG6_LOCAL),
+            Nla::Oif(2),
+        ];
+        msg
+    }
+
+    #[test]
+    fn parse_message_with_seg6_local_encap() {
+        let expected = seg6_local_route_message();
+        let actual = RouteMessage::parse(
+            &RouteMessageBuffer::new_checked(&&SEG6_LOCAL_ROUTE_MSG[..]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn emit_message_with_seg6_local_encap() {
+        let msg = seg6_local_route_message();
+        let mut buf = [0; 68];
+        assert_eq!(msg.buffer_len(), 68);
+        msg.emit(&mut buf[..]);
+        assert_eq!(buf, SEG6_LOCAL_ROUTE_MSG);
+    }
+}
+
+mod test_validate {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use crate::{
+        constants::*,
+        rtnl::route::{
+            nlas::Nla,
+            RouteMessage,
+            RouteProtocol,
+            RouteScope,
+            RouteType,
+            RouteValidationError,
+        },
+    };
+
+    // ip route add 10.0.0.0/24 via 192.168.1.1
+    fn ipv4_route() -> RouteMessage {
+        let mut msg = RouteMessage::default();
+        msg.header.address_family = AF_INET as u8;
+        msg.header.destination_prefix_length = 24;
+        msg.header.table = RT_TABLE_MAIN;
+        msg.header.protocol = RouteProtocol::Boot;
+        msg.header.scope = RouteScope::Universe;
+        msg.header.kind = RouteType::Unicast;
+        msg.nlas = vec![
+            Nla::Destination(vec![10, 0, 0, 0]),
+            Nla::Gateway(vec![192, 168, 1, 1]),
+        ];
+        msg
+    }
+
+    // ip -6 route add 2001:db8::/32 via fe80::1 dev eth0 table 1000
+    fn ipv6_route() -> RouteMessage {
+        let mut msg = RouteMessage::default();
+        msg.header.address_family = AF_INET6 as u8;
+        msg.header.destination_prefix_length = 32;
+        msg.header.table = RT_TABLE_COMPAT;
+        msg.header.protocol = RouteProtocol::Boot;
+        msg.header.kind = RouteType::Unicast;
+        msg.nlas = vec![
+            Nla::Destination("2001:db8::".parse::<Ipv6Addr>().unwrap().octets().to_vec()),
+            Nla::Gateway("fe80::1".parse::<Ipv6Addr>().unwrap().octets().to_vec()),
+            Nla::Oif(2),
+            Nla::Table(1000),
+        ];
+        msg
+    }
+
+    #[test]
+    fn valid_routes() {
+        assert_eq!(ipv4_route().validate(), Ok(()));
+        assert_eq!(ipv6_route().validate(), Ok(()));
+
+        // routes of the local table, as dumped by the kernel
+        let mut local = ipv4_route();
+        local.header.destination_prefix_length = 32;
+        local.header.table = RT_TABLE_LOCAL;
+        local.header.scope = RouteScope::Host;
+        local.header.kind = RouteType::Local;
+        local.nlas = vec![Nla::Destination(Ipv4Addr::LOCALHOST.octets().to_vec())];
+        assert_eq!(local.validate(), Ok(()));
+
+        let mut local = ipv6_route();
+        local.header.destination_prefix_length = 128;
+        local.header.kind = RouteType::Local;
+        local.nlas = vec![Nla::Destination(Ipv6Addr::LOCALHOST.octets().to_vec())];
+        assert_eq!(local.validate(), Ok(()));
+
+        // requests don't need a table
+        let mut get = ipv4_route();
+        get.header.table = RT_TABLE_UNSPEC;
+        get.header.kind = RouteType::Unspec;
+        assert_eq!(get.validate(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_prefix_length() {
+        let mut msg = ipv4_route();
+        msg.header.destination_prefix_length = 33;
+        assert_eq!(
+            msg.validate(),
+            Err(RouteValidationError::InvalidPrefixLength {
+                prefix_len: 33,
+                max: 32
+            })
+        );
+
+        let mut msg = ipv6_route();
+        msg.header.destination_prefix_length = 129;
+        assert_eq!(
+            msg.validate(),
+            Err(RouteValidationError::InvalidPrefixLength {
+                prefix_len: 129,
+                max: 128
+            })
+        );
+    }
+
+    #[test]
+    fn gateway_family_mismatch() {
+        let mut msg = ipv4_route();
+        msg.nlas[1] = Nla::Gateway(Ipv6Addr::LOCALHOST.octets().to_vec());
+        let err = msg.validate().unwrap_err();
+        assert_eq!(
+            err,
+            RouteValidationError::GatewayFamilyMismatch {
+                address_family: AF_INET as u8,
+                gateway_len: 16
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "16 bytes gateway address does not match address family 2"
+        );
+    }
+
+    #[test]
+    fn empty_multipath() {
+        let mut msg = ipv4_route();
+        msg.nlas[1] = Nla::MultiPath(vec![]);
+        assert_eq!(msg.validate(), Err(RouteValidationError::EmptyMultipath));
+    }
+
+    #[test]
+    fn missing_table() {
+        let mut msg = ipv4_route();
+        msg.header.table = RT_TABLE_UNSPEC;
+        assert_eq!(msg.validate(), Err(RouteValidationError::MissingTable));
+
+        // the table attribute overrides the header
+        let mut msg = ipv6_route();
+        msg.nlas[3] = Nla::Table(0);
+        assert_eq!(msg.validate(), Err(RouteValidationError::MissingTable));
+    }
+
+    #[test]
+    fn invalid_scope() {
+        let mut msg = ipv4_route();
+        msg.header.kind = RouteType::Local;
+        assert_eq!(
+            msg.validate(),
+            Err(RouteValidationError::InvalidScope {
+                kind: RouteType::Local,
+                scope: RouteScope::Universe
+            })
+        );
+
+        msg.header.kind = RouteType::Broadcast;
+        msg.header.scope = RouteScope::Site;
+        assert_eq!(
+            msg.validate(),
+            Err(RouteValidationError::InvalidScope {
+                kind: RouteType::Broadcast,
+                scope: RouteScope::Site
+            })
+        );
+        msg.header.scope = RouteScope::Link;
+        assert_eq!(msg.validate(), Ok(()));
+    }
 }
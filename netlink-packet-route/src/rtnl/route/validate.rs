@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MIT
+
+use thiserror::Error;
+
+use crate::{constants::*, nlas::route::Nla, RouteMessage, RouteScope, RouteType};
+
+/// Inconsistency found by [`RouteMessage::validate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum RouteValidationError {
+    #[error("destination prefix length {prefix_len} is longer than the {max} bits of the address")]
+    InvalidPrefixLength { prefix_len: u8, max: u8 },
+
+    #[error("{gateway_len} bytes gateway address does not match address family {address_family}")]
+    GatewayFamilyMismatch {
+        address_family: u8,
+        gateway_len: usize,
+    },
+
+    #[error("multipath attribute without any next hop")]
+    EmptyMultipath,
+
+    #[error("routing table is not set")]
+    MissingTable,
+
+    #[error("scope {scope:?} is not valid for {kind:?} routes")]
+    InvalidScope { kind: RouteType, scope: RouteScope },
+}
+
+impl RouteMessage {
+    /// Check that the message is consistent before sending it to the kernel, which reports most
+    /// of these errors with a bare `EINVAL`. Only `AF_INET` and `AF_INET6` routes are checked
+    /// against the length of their addresses, and only `AF_INET` routes against their scope,
+    /// since the kernel ignores the scope of IPv6 routes. Note that the kernel puts routes without
+    /// a table in the main table, but this is usually a mistake, so it is reported as
+    /// [`RouteValidationError::MissingTable`].
+    pub fn validate(&self) -> Result<(), RouteValidationError> {
+        let header = &self.header;
+        let address_family = u16::from(header.address_family);
+        let address_len = match address_family {
+            AF_INET => Some(4),
+            AF_INET6 => Some(16),
+            _ => None,
+        };
+        if let Some(address_len) = address_len {
+            let max = address_len * 8;
+            if header.destination_prefix_length > max {
+                return Err(RouteValidationError::InvalidPrefixLength {
+                    prefix_len: header.destination_prefix_length,
+                    max,
+                });
+            }
+        }
+
+        let check_gateway = |gateway_len: usize| match address_len {
+            Some(len) if usize::from(len) != gateway_len => {
+                Err(RouteValidationError::GatewayFamilyMismatch {
+                    address_family: header.address_family,
+                    gateway_len,
+                })
+            }
+            _ => Ok(()),
+        };
+        let mut table = u32::from(header.table);
+        for nla in &self.nlas {
+            match nla {
+                Nla::Gateway(gateway) => check_gateway(gateway.len())?,
+                Nla::MultiPath(next_hops) if next_hops.is_empty() => {
+                    return Err(RouteValidationError::EmptyMultipath)
+                }
+                #[cfg(feature = "rich_nlas")]
+                Nla::MultiPath(next_hops) => {
+                    for gateway in next_hops.iter().filter_map(|next_hop| next_hop.gateway) {
+                        check_gateway(crate::ip_len(&gateway))?;
+                    }
+                }
+                Nla::Table(id) => table = *id,
+                _ => {}
+            }
+        }
+        if table == u32::from(RT_TABLE_UNSPEC) && header.kind != RouteType::Unspec {
+            return Err(RouteValidationError::MissingTable);
+        }
+
+        if address_family == AF_INET && u8::from(header.scope) < min_scope(header.kind) {
+            return Err(RouteValidationError::InvalidScope {
+                kind: header.kind,
+                scope: header.scope,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Widest scope the kernel accepts for IPv4 routes of the given type (`fib_props` in
+/// `net/ipv4/fib_semantics.c`). Scopes are numbered from the widest to the narrowest one.
+fn min_scope(kind: RouteType) -> u8 {
+    match kind {
+        RouteType::Local => RT_SCOPE_HOST,
+        RouteType::Broadcast | RouteType::Anycast => RT_SCOPE_LINK,
+        RouteType::ExternalResolve => RT_SCOPE_NOWHERE,
+        _ => RT_SCOPE_UNIVERSE,
+    }
+}
@@ -36,8 +36,7 @@ pub struct RuleHeader {
     pub tos: u8,
     /// RT_TABLE_*
     pub table: u8,
-    /// FR_ACT_*
-    pub action: u8,
+    pub action: RuleAction,
     /// fib rule flags
     pub flags: u32,
 }
@@ -55,7 +54,7 @@ impl Emitable for RuleHeader {
         packet.set_flags(self.flags);
         packet.set_table(self.table);
         packet.set_tos(self.tos);
-        packet.set_action(self.action);
+        packet.set_action(self.action.into());
     }
 }
 
@@ -67,8 +66,60 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<RuleMessageBuffer<&'a T>> for RuleHe
             src_len: buf.src_len(),
             tos: buf.tos(),
             table: buf.table(),
-            action: buf.action(),
+            action: buf.action().into(),
             flags: buf.flags(),
         })
     }
 }
+
+/// Action of a rule, carried by the `action` field of the header
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum RuleAction {
+    #[default]
+    Unspec,
+    /// Look the route up in the table of the rule
+    ToTable,
+    /// Jump to the rule given by the `FRA_GOTO` attribute
+    Goto,
+    /// Do nothing and go on with the next rule
+    Nop,
+    /// Drop the packet silently
+    Blackhole,
+    /// Drop the packet with `ENETUNREACH`
+    Unreachable,
+    /// Drop the packet with `EACCES`
+    Prohibit,
+    Other(u8),
+}
+
+impl From<u8> for RuleAction {
+    fn from(value: u8) -> Self {
+        use self::RuleAction::*;
+        match value {
+            FR_ACT_UNSPEC => Unspec,
+            FR_ACT_TO_TBL => ToTable,
+            FR_ACT_GOTO => Goto,
+            FR_ACT_NOP => Nop,
+            FR_ACT_BLACKHOLE => Blackhole,
+            FR_ACT_UNREACHABLE => Unreachable,
+            FR_ACT_PROHIBIT => Prohibit,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<RuleAction> for u8 {
+    fn from(value: RuleAction) -> Self {
+        use self::RuleAction::*;
+        match value {
+            Unspec => FR_ACT_UNSPEC,
+            ToTable => FR_ACT_TO_TBL,
+            Goto => FR_ACT_GOTO,
+            Nop => FR_ACT_NOP,
+            Blackhole => FR_ACT_BLACKHOLE,
+            Unreachable => FR_ACT_UNREACHABLE,
+            Prohibit => FR_ACT_PROHIBIT,
+            Other(other) => other,
+        }
+    }
+}
@@ -43,3 +43,90 @@ impl<'a, T: AsRef<[u8]> + 'a> Parseable<RuleMessageBuffer<&'a T>> for Vec<Nla> {
         Ok(nlas)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        constants::*,
+        nlas::rule::{Nla, RulePortRange, RuleUidRange},
+        traits::{Emitable, Parseable},
+        RuleAction,
+        RuleHeader,
+        RuleMessage,
+        RuleMessageBuffer,
+    };
+
+    // ip rule add from 10.0.0.0/8 uidrange 1000-2000 sport 1024-2048 table 100 pref 100
+    #[rustfmt::skip]
+    static RULE_MSG: [u8; 56] = [
+        0x02, // family AF_INET
+        0x00, // dst_len
+        0x08, // src_len
+        0x00, // tos
+        0x64, // table = 100
+        0x00, 0x00, // reserved
+        0x01, // action FR_ACT_TO_TBL
+        0x00, 0x00, 0x00, 0x00, // flags
+
+        // nlas
+        0x08, 0x00, 0x0f, 0x00, // length = 8, type = FRA_TABLE
+        0x64, 0x00, 0x00, 0x00, // 100
+
+        0x08, 0x00, 0x06, 0x00, // length = 8, type = FRA_PRIORITY
+        0x64, 0x00, 0x00, 0x00, // 100
+
+        0x08, 0x00, 0x02, 0x00, // length = 8, type = FRA_SRC
+        0x0a, 0x00, 0x00, 0x00, // 10.0.0.0
+
+        0x0c, 0x00, 0x14, 0x00, // length = 12, type = FRA_UID_RANGE
+        0xe8, 0x03, 0x00, 0x00, // start = 1000
+        0xd0, 0x07, 0x00, 0x00, // end = 2000
+
+        0x08, 0x00, 0x17, 0x00, // length = 8, type = FRA_SPORT_RANGE
+        0x00, 0x04, // start = 1024
+        0x00, 0x08, // end = 2048
+    ];
+
+    fn rule_message() -> RuleMessage {
+        RuleMessage {
+            header: RuleHeader {
+                family: AF_INET as u8,
+                dst_len: 0,
+                src_len: 8,
+                tos: 0,
+                table: 100,
+                action: RuleAction::ToTable,
+                flags: 0,
+            },
+            nlas: vec![
+                Nla::Table(100),
+                Nla::Priority(100),
+                Nla::Source(vec![10, 0, 0, 0]),
+                Nla::UidRange(RuleUidRange {
+                    start: 1000,
+                    end: 2000,
+                }),
+                Nla::SourcePortRange(RulePortRange {
+                    start: 1024,
+                    end: 2048,
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_rule_message() {
+        let parsed =
+            RuleMessage::parse(&RuleMessageBuffer::new_checked(&&RULE_MSG[..]).unwrap()).unwrap();
+        assert_eq!(parsed, rule_message());
+    }
+
+    #[test]
+    fn emit_rule_message() {
+        let message = rule_message();
+        assert_eq!(message.buffer_len(), RULE_MSG.len());
+        let mut buf = [0; 56];
+        message.emit(&mut buf);
+        assert_eq!(buf, RULE_MSG);
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
         byteorder::{ByteOrder, NativeEndian},
         nla::NlaBuffer,
         parsers::{parse_string, parse_u32, parse_u8},
+        Emitable,
         Parseable,
     },
     DecodeError,
@@ -59,13 +60,14 @@ pub enum Nla {
     Pad(Vec<u8>),
     /// iif or oif is l3mdev goto its table
     L3MDev(u8),
-    UidRange(Vec<u8>),
+    /// range of socket owner uids
+    UidRange(RuleUidRange),
     /// RTPROT_*
     Protocol(u8),
-    /// AF_*
+    /// IPPROTO_*
     IpProto(u8),
-    SourcePortRange(Vec<u8>),
-    DestinationPortRange(Vec<u8>),
+    SourcePortRange(RulePortRange),
+    DestinationPortRange(RulePortRange),
     Other(DefaultNla),
 }
 
@@ -73,13 +75,11 @@ impl nlas::Nla for Nla {
     fn value_len(&self) -> usize {
         use self::Nla::*;
         match self {
-            Unspec(ref bytes)
-            | Destination(ref bytes)
-            | Source(ref bytes)
-            | Pad(ref bytes)
-            | UidRange(ref bytes)
-            | SourcePortRange(ref bytes)
-            | DestinationPortRange(ref bytes) => bytes.len(),
+            Unspec(ref bytes) | Destination(ref bytes) | Source(ref bytes) | Pad(ref bytes) => {
+                bytes.len()
+            }
+            UidRange(_) => RULE_UID_RANGE_LEN,
+            SourcePortRange(_) | DestinationPortRange(_) => RULE_PORT_RANGE_LEN,
             Iifname(ref s) | OifName(ref s) => s.as_bytes().len() + 1,
             Priority(_) | FwMark(_) | FwMask(_) | Flow(_) | TunId(_) | Goto(_)
             | SuppressIfGroup(_) | SuppressPrefixLen(_) | Table(_) => 4,
@@ -119,13 +119,11 @@ impl nlas::Nla for Nla {
     fn emit_value(&self, buffer: &mut [u8]) {
         use self::Nla::*;
         match self {
-            Unspec(ref bytes)
-            | Destination(ref bytes)
-            | Source(ref bytes)
-            | Pad(ref bytes)
-            | UidRange(ref bytes)
-            | SourcePortRange(ref bytes)
-            | DestinationPortRange(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Unspec(ref bytes) | Destination(ref bytes) | Source(ref bytes) | Pad(ref bytes) => {
+                buffer.copy_from_slice(bytes.as_slice())
+            }
+            UidRange(ref range) => range.emit(buffer),
+            SourcePortRange(ref range) | DestinationPortRange(ref range) => range.emit(buffer),
             Iifname(ref s) | OifName(ref s) => buffer.copy_from_slice(s.as_bytes()),
 
             Priority(value)
@@ -170,12 +168,82 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
             FRA_OIFNAME => OifName(parse_string(payload).context("invalid FRA_OIFNAME value")?),
             FRA_PAD => Pad(payload.to_vec()),
             FRA_L3MDEV => L3MDev(parse_u8(payload).context("invalid FRA_L3MDEV value")?),
-            FRA_UID_RANGE => UidRange(payload.to_vec()),
+            FRA_UID_RANGE => {
+                UidRange(RuleUidRange::parse(payload).context("invalid FRA_UID_RANGE value")?)
+            }
             FRA_PROTOCOL => Protocol(parse_u8(payload).context("invalid FRA_PROTOCOL value")?),
             FRA_IP_PROTO => IpProto(parse_u8(payload).context("invalid FRA_IP_PROTO value")?),
-            FRA_SPORT_RANGE => SourcePortRange(payload.to_vec()),
-            FRA_DPORT_RANGE => DestinationPortRange(payload.to_vec()),
+            FRA_SPORT_RANGE => SourcePortRange(
+                RulePortRange::parse(payload).context("invalid FRA_SPORT_RANGE value")?,
+            ),
+            FRA_DPORT_RANGE => DestinationPortRange(
+                RulePortRange::parse(payload).context("invalid FRA_DPORT_RANGE value")?,
+            ),
             _ => Other(DefaultNla::parse(buf).context("invalid NLA (unknown kind)")?),
         })
     }
 }
+
+pub const RULE_UID_RANGE_LEN: usize = 8;
+
+/// Range of uids matched by a rule (`struct fib_rule_uid_range`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct RuleUidRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl RuleUidRange {
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        if payload.len() != RULE_UID_RANGE_LEN {
+            return Err(format!("invalid uid range length {}", payload.len()).into());
+        }
+        Ok(RuleUidRange {
+            start: NativeEndian::read_u32(&payload[..4]),
+            end: NativeEndian::read_u32(&payload[4..]),
+        })
+    }
+}
+
+impl Emitable for RuleUidRange {
+    fn buffer_len(&self) -> usize {
+        RULE_UID_RANGE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        NativeEndian::write_u32(&mut buffer[..4], self.start);
+        NativeEndian::write_u32(&mut buffer[4..RULE_UID_RANGE_LEN], self.end);
+    }
+}
+
+pub const RULE_PORT_RANGE_LEN: usize = 4;
+
+/// Range of ports matched by a rule (`struct fib_rule_port_range`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct RulePortRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl RulePortRange {
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        if payload.len() != RULE_PORT_RANGE_LEN {
+            return Err(format!("invalid port range length {}", payload.len()).into());
+        }
+        Ok(RulePortRange {
+            start: NativeEndian::read_u16(&payload[..2]),
+            end: NativeEndian::read_u16(&payload[2..]),
+        })
+    }
+}
+
+impl Emitable for RulePortRange {
+    fn buffer_len(&self) -> usize {
+        RULE_PORT_RANGE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        NativeEndian::write_u16(&mut buffer[..2], self.start);
+        NativeEndian::write_u16(&mut buffer[2..RULE_PORT_RANGE_LEN], self.end);
+    }
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: MIT
+
+use std::{fmt, str::FromStr};
+
+use crate::{constants::*, DecodeError};
+
+/// Handle of a qdisc, class or filter. It is made of a 16 bits major number,
+/// which identifies a qdisc, and a 16 bits minor number, which identifies a
+/// class of that qdisc, and it is written `major:minor` in hexadecimal by
+/// iproute2.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash, PartialOrd, Ord)]
+pub struct TcHandle(pub u32);
+
+impl TcHandle {
+    pub const fn major(self) -> u16 {
+        ((self.0 & TC_H_MAJ_MASK) >> 16) as u16
+    }
+
+    pub const fn minor(self) -> u16 {
+        (self.0 & TC_H_MIN_MASK) as u16
+    }
+
+    pub const fn from_parts(major: u16, minor: u16) -> Self {
+        TcHandle(((major as u32) << 16) | minor as u32)
+    }
+
+    /// Parent of the qdiscs attached to the root of a device
+    pub const fn root() -> Self {
+        TcHandle(TC_H_ROOT)
+    }
+
+    /// Parent of the ingress and clsact qdiscs
+    pub const fn ingress() -> Self {
+        TcHandle(TC_H_INGRESS)
+    }
+
+    pub const fn is_root(self) -> bool {
+        self.0 == TC_H_ROOT
+    }
+
+    pub const fn is_ingress(self) -> bool {
+        self.0 == TC_H_INGRESS
+    }
+}
+
+impl From<u32> for TcHandle {
+    fn from(handle: u32) -> Self {
+        TcHandle(handle)
+    }
+}
+
+impl From<TcHandle> for u32 {
+    fn from(handle: TcHandle) -> Self {
+        handle.0
+    }
+}
+
+impl fmt::Display for TcHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            TC_H_ROOT => write!(f, "root"),
+            TC_H_UNSPEC => write!(f, "none"),
+            _ if self.major() == 0 => write!(f, ":{:x}", self.minor()),
+            _ if self.minor() == 0 => write!(f, "{:x}:", self.major()),
+            _ => write!(f, "{:x}:{:x}", self.major(), self.minor()),
+        }
+    }
+}
+
+impl FromStr for TcHandle {
+    type Err = DecodeError;
+
+    /// Parse a handle the way iproute2 does: `root`, `ingress`, `none`,
+    /// `major:minor` in hexadecimal, where either number can be omitted and
+    /// then defaults to 0 (`1:`, `:10`), or a bare hexadecimal number which
+    /// is the whole 32 bits handle (`10` is `0:10`, `10000` is `1:0`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "root" => return Ok(Self::root()),
+            "ingress" => return Ok(Self::ingress()),
+            "none" => return Ok(TcHandle(TC_H_UNSPEC)),
+            _ => {}
+        }
+        let error = |e| DecodeError::from(format!("invalid tc handle {:?}: {}", s, e));
+        let (major, minor) = match s.split_once(':') {
+            Some(parts) => parts,
+            None => return u32::from_str_radix(s, 16).map(TcHandle).map_err(error),
+        };
+        let parse = |number: &str| {
+            if number.is_empty() {
+                Ok(0)
+            } else {
+                u16::from_str_radix(number, 16).map_err(error)
+            }
+        };
+        Ok(Self::from_parts(parse(major)?, parse(minor)?))
+    }
+}
+
+/// Handles are serialized with their textual representation (`1:10`,
+/// `root`...) rather than as a number.
+#[cfg(feature = "serde")]
+impl serde::Serialize for TcHandle {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TcHandle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
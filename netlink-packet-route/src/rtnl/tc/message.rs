@@ -1,43 +1,189 @@
 // SPDX-License-Identifier: MIT
 
+use std::fmt;
+
 use anyhow::Context;
 
 use crate::{
-    nlas::tc::Nla,
-    traits::{Emitable, Parseable},
+    constants::TCA_PAD,
+    nlas::{tc::Nla, NlaBuffer},
+    traits::{Emitable, Parseable, ParseableParametrized},
     DecodeError,
+    EncodeError,
+    TcHandle,
     TcMessageBuffer,
     TC_HEADER_LEN,
 };
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
-pub struct TcMessage {
+/// A traffic control message. The type parameter is the type of the
+/// `TCA_OPTIONS` attribute, which depends on the message type: qdisc
+/// messages carry [`Qdisc`](crate::nlas::tc::Qdisc) options, class messages
+/// [`Class`](crate::nlas::tc::Class) options and filter and chain messages
+/// [`Filter`](crate::nlas::tc::Filter) options.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcMessage<A> {
     pub header: TcHeader,
-    pub nlas: Vec<Nla>,
+    pub nlas: Vec<Nla<A>>,
+}
+
+impl<A> Default for TcMessage<A> {
+    fn default() -> Self {
+        TcMessage {
+            header: TcHeader::default(),
+            nlas: Vec::new(),
+        }
+    }
 }
 
-impl TcMessage {
-    pub fn into_parts(self) -> (TcHeader, Vec<Nla>) {
+impl<A> TcMessage<A> {
+    pub fn into_parts(self) -> (TcHeader, Vec<Nla<A>>) {
         (self.header, self.nlas)
     }
 
-    pub fn from_parts(header: TcHeader, nlas: Vec<Nla>) -> Self {
+    pub fn from_parts(header: TcHeader, nlas: Vec<Nla<A>>) -> Self {
         TcMessage { header, nlas }
     }
 }
 
+/// Builder of [`TcMessage`]s, which makes sure that `TCA_KIND` and
+/// `TCA_OPTIONS` are given together, and that the kind always comes first:
+/// the options are parsed according to the kind, by the kernel as well as by
+/// this crate.
+///
+/// ```rust
+/// use netlink_packet_route::{
+///     tc::{
+///         qdisc::{fq_codel::FqCodelQdiscNla, FqCodelQdisc},
+///         Qdisc,
+///     },
+///     TcHandle,
+///     TcMessageBuilder,
+/// };
+///
+/// // tc qdisc add dev eth0 root handle 1: fq_codel limit 1000
+/// let message = TcMessageBuilder::new()
+///     .index(2)
+///     .handle(TcHandle::from_parts(1, 0))
+///     .parent(TcHandle::root())
+///     .kind("fq_codel")
+///     .options(Qdisc::FqCodel(FqCodelQdisc {
+///         nlas: vec![FqCodelQdiscNla::Limit(1000)],
+///     }))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TcMessageBuilder<A> {
+    header: TcHeader,
+    kind: Option<String>,
+    options: Option<A>,
+    hw_offload: Option<u8>,
+}
+
+impl<A> Default for TcMessageBuilder<A> {
+    fn default() -> Self {
+        TcMessageBuilder {
+            header: TcHeader::default(),
+            kind: None,
+            options: None,
+            hw_offload: None,
+        }
+    }
+}
+
+impl<A> TcMessageBuilder<A> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the interface
+    pub fn index(mut self, index: i32) -> Self {
+        self.header.index = index;
+        self
+    }
+
+    pub fn handle(mut self, handle: TcHandle) -> Self {
+        self.header.handle = handle;
+        self
+    }
+
+    pub fn parent(mut self, parent: TcHandle) -> Self {
+        self.header.parent = parent;
+        self
+    }
+
+    /// For filters, the priority in the upper 16 bits and the protocol, in
+    /// network byte order, in the lower 16 bits
+    pub fn info(mut self, info: u32) -> Self {
+        self.header.info = info;
+        self
+    }
+
+    pub fn kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    pub fn options(mut self, options: A) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    pub fn hw_offload(mut self, hw_offload: u8) -> Self {
+        self.hw_offload = Some(hw_offload);
+        self
+    }
+
+    /// Build the message, failing if only one of the kind and the options
+    /// was given.
+    pub fn build(self) -> Result<TcMessage<A>, EncodeError> {
+        let mut nlas = vec![];
+        match (self.kind, self.options) {
+            (Some(kind), Some(options)) => {
+                nlas.push(Nla::Kind(kind));
+                nlas.push(Nla::Options(options));
+            }
+            (None, None) => {}
+            (Some(_), None) => return Err("TCA_KIND was given without TCA_OPTIONS".into()),
+            (None, Some(_)) => return Err("TCA_OPTIONS was given without TCA_KIND".into()),
+        }
+        if let Some(hw_offload) = self.hw_offload {
+            nlas.push(Nla::HwOffload(hw_offload));
+        }
+        Ok(TcMessage {
+            header: self.header,
+            nlas,
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TcHeader {
     pub family: u8,
     // Interface index
     pub index: i32,
     // Qdisc handle
-    pub handle: u32,
+    pub handle: TcHandle,
     // Parent Qdisc
-    pub parent: u32,
+    pub parent: TcHandle,
     pub info: u32,
 }
 
+/// Like the output of `tc`, without the kind of the qdisc, class or filter, and with the index
+/// of the interface instead of its name: `1:10 dev 2 parent 1:`
+impl fmt::Display for TcHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} dev {}", self.handle, self.index)?;
+        if self.parent.is_root() {
+            write!(f, " root")
+        } else {
+            write!(f, " parent {}", self.parent)
+        }
+    }
+}
+
 impl Emitable for TcHeader {
     fn buffer_len(&self) -> usize {
         TC_HEADER_LEN
@@ -47,20 +193,22 @@ impl Emitable for TcHeader {
         let mut packet = TcMessageBuffer::new(buffer);
         packet.set_family(self.family);
         packet.set_index(self.index);
-        packet.set_handle(self.handle);
-        packet.set_parent(self.parent);
+        packet.set_handle(self.handle.into());
+        packet.set_parent(self.parent.into());
         packet.set_info(self.info);
     }
 }
 
-impl Emitable for TcMessage {
+impl<A: Emitable> Emitable for TcMessage<A> {
     fn buffer_len(&self) -> usize {
         self.header.buffer_len() + self.nlas.as_slice().buffer_len()
     }
 
     fn emit(&self, buffer: &mut [u8]) {
         self.header.emit(buffer);
-        self.nlas.as_slice().emit(buffer);
+        self.nlas
+            .as_slice()
+            .emit(&mut buffer[self.header.buffer_len()..]);
     }
 }
 
@@ -69,27 +217,47 @@ impl<T: AsRef<[u8]>> Parseable<TcMessageBuffer<T>> for TcHeader {
         Ok(Self {
             family: buf.family(),
             index: buf.index(),
-            handle: buf.handle(),
-            parent: buf.parent(),
+            handle: buf.handle().into(),
+            parent: buf.parent().into(),
             info: buf.info(),
         })
     }
 }
 
-impl<'a, T: AsRef<[u8]> + 'a> Parseable<TcMessageBuffer<&'a T>> for TcMessage {
+impl<'a, T, A> Parseable<TcMessageBuffer<&'a T>> for TcMessage<A>
+where
+    T: AsRef<[u8]> + 'a,
+    A: for<'b> ParseableParametrized<NlaBuffer<&'a [u8]>, &'b str>,
+{
     fn parse(buf: &TcMessageBuffer<&'a T>) -> Result<Self, DecodeError> {
         Ok(Self {
             header: TcHeader::parse(buf).context("failed to parse tc message header")?,
-            nlas: Vec::<Nla>::parse(buf).context("failed to parse tc message NLAs")?,
+            nlas: Vec::<Nla<A>>::parse(buf).context("failed to parse tc message NLAs")?,
         })
     }
 }
 
-impl<'a, T: AsRef<[u8]> + 'a> Parseable<TcMessageBuffer<&'a T>> for Vec<Nla> {
+impl<'a, T, A> Parseable<TcMessageBuffer<&'a T>> for Vec<Nla<A>>
+where
+    T: AsRef<[u8]> + 'a,
+    A: for<'b> ParseableParametrized<NlaBuffer<&'a [u8]>, &'b str>,
+{
     fn parse(buf: &TcMessageBuffer<&'a T>) -> Result<Self, DecodeError> {
         let mut nlas = vec![];
+        // TCA_OPTIONS can only be parsed once we know the kind of qdisc,
+        // class or filter it belongs to.
+        let mut kind = String::new();
         for nla_buf in buf.nlas() {
-            nlas.push(Nla::parse(&nla_buf?)?);
+            let nla_buf = nla_buf?;
+            // TCA_PAD only aligns the 64 bits attributes that follow it
+            if nla_buf.kind() == TCA_PAD {
+                continue;
+            }
+            let nla = Nla::parse_with_param(&nla_buf, kind.as_str())?;
+            if let Nla::Kind(ref k) = nla {
+                kind = k.clone();
+            }
+            nlas.push(nla);
         }
         Ok(nlas)
     }
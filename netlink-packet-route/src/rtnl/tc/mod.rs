@@ -1,7 +1,16 @@
 // SPDX-License-Identifier: MIT
 
 mod buffer;
+mod handle;
 mod message;
 pub mod nlas;
+mod rate;
+mod stats;
 
-pub use self::{buffer::*, message::*, nlas::*};
+pub use self::{buffer::*, handle::*, message::*, nlas::*, rate::*, stats::*};
+
+#[cfg(test)]
+mod test;
+
+#[cfg(test)]
+mod proptests;
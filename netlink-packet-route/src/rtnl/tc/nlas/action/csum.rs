@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "csum";
+
+/// Options of the csum action, which recomputes the checksums of a packet
+/// modified by another action, such as pedit
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CsumAction {
+    pub nlas: Vec<CsumActionNla>,
+}
+
+impl Emitable for CsumAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CsumAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid csum action nla")?;
+            nlas.push(CsumActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+impl CsumAction {
+    /// Check that the action updates at least one checksum: without any
+    /// `TCA_CSUM_UPDATE_FLAG_*` flag, the kernel accepts the action but it
+    /// does nothing.
+    pub fn validate(&self) -> Result<(), DecodeError> {
+        for nla in &self.nlas {
+            if let CsumActionNla::Parms(ref parms) = nla {
+                if parms.update_flags.is_empty() {
+                    return Err("csum action does not update any checksum".into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CsumActionNla {
+    Parms(TcCsum),
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for CsumActionNla {
+    fn value_len(&self) -> usize {
+        use self::CsumActionNla::*;
+        match self {
+            Parms(_) => TC_CSUM_LEN,
+            Tm(_) => TCF_LEN,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::CsumActionNla::*;
+        match self {
+            Parms(ref parms) => parms.emit(buffer),
+            Tm(ref tm) => tm.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::CsumActionNla::*;
+        match self {
+            Parms(_) => TCA_CSUM_PARMS,
+            Tm(_) => TCA_CSUM_TM,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CsumActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::CsumActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CSUM_PARMS => Parms(
+                TcCsum::parse(&TcCsumBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CSUM_PARMS value")?,
+            ),
+            TCA_CSUM_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_CSUM_TM value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid csum action nla")?),
+        })
+    }
+}
+
+/// Csum parameters (`struct tc_csum`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcCsum {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the checksums are updated
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+    /// Checksums to update
+    pub update_flags: CsumUpdateFlags,
+}
+
+pub const TC_CSUM_LEN: usize = 24;
+
+buffer!(TcCsumBuffer(TC_CSUM_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+    update_flags: (u32, 20..24),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcCsumBuffer<T>> for TcCsum {
+    fn parse(buf: &TcCsumBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+            update_flags: CsumUpdateFlags::from_bits_truncate(buf.update_flags()),
+        })
+    }
+}
+
+impl Emitable for TcCsum {
+    fn buffer_len(&self) -> usize {
+        TC_CSUM_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcCsumBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+        buffer.set_update_flags(self.update_flags.bits());
+    }
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct CsumUpdateFlags: u32 {
+        const IPV4HDR = TCA_CSUM_UPDATE_FLAG_IPV4HDR;
+        const ICMP = TCA_CSUM_UPDATE_FLAG_ICMP;
+        const IGMP = TCA_CSUM_UPDATE_FLAG_IGMP;
+        const TCP = TCA_CSUM_UPDATE_FLAG_TCP;
+        const UDP = TCA_CSUM_UPDATE_FLAG_UDP;
+        const UDPLITE = TCA_CSUM_UPDATE_FLAG_UDPLITE;
+        const SCTP = TCA_CSUM_UPDATE_FLAG_SCTP;
+    }
+}
+
+impl Default for CsumUpdateFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
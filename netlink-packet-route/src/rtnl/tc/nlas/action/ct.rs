@@ -0,0 +1,261 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::Ipv4Addr;
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_ipv4, parse_ipv6, parse_u16, parse_u16_be, parse_u32},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "ct";
+
+/// Options of the ct action, which sends the packets through the connection
+/// tracking, and optionally commits the connection or applies NAT
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CtAction {
+    pub nlas: Vec<CtActionNla>,
+}
+
+impl Emitable for CtAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CtAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid ct action nla")?;
+            nlas.push(CtActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CtActionNla {
+    Parms(TcCt),
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    /// What the action does with the connection
+    Action(CtActionFlags),
+    /// Conntrack zone of the connection
+    Zone(u16),
+    /// Mark set on the committed connection
+    Mark(u32),
+    MarkMask(u32),
+    /// Labels set on the committed connection
+    Labels([u8; 16]),
+    LabelsMask([u8; 16]),
+    /// First address of the NAT range
+    NatIpv4Min(Ipv4Addr),
+    /// Last address of the NAT range
+    NatIpv4Max(Ipv4Addr),
+    NatIpv6Min([u8; 16]),
+    NatIpv6Max([u8; 16]),
+    /// First port of the NAT range. It is in network byte order on the wire.
+    NatPortMin(u16),
+    /// Last port of the NAT range. It is in network byte order on the wire.
+    NatPortMax(u16),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for CtActionNla {
+    fn value_len(&self) -> usize {
+        use self::CtActionNla::*;
+        match self {
+            Parms(_) => TC_CT_LEN,
+            Tm(_) => TCF_LEN,
+            Action(_) | Zone(_) | NatPortMin(_) | NatPortMax(_) => 2,
+            Mark(_) | MarkMask(_) | NatIpv4Min(_) | NatIpv4Max(_) => 4,
+            Labels(_) | LabelsMask(_) | NatIpv6Min(_) | NatIpv6Max(_) => 16,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::CtActionNla::*;
+        match self {
+            Parms(ref parms) => parms.emit(buffer),
+            Tm(ref tm) => tm.emit(buffer),
+            Action(flags) => NativeEndian::write_u16(buffer, flags.bits()),
+            Zone(value) => NativeEndian::write_u16(buffer, *value),
+            Mark(value) | MarkMask(value) => NativeEndian::write_u32(buffer, *value),
+            Labels(ref bytes)
+            | LabelsMask(ref bytes)
+            | NatIpv6Min(ref bytes)
+            | NatIpv6Max(ref bytes) => buffer.copy_from_slice(&bytes[..]),
+            NatIpv4Min(ref address) | NatIpv4Max(ref address) => {
+                buffer.copy_from_slice(&address.octets())
+            }
+            NatPortMin(value) | NatPortMax(value) => BigEndian::write_u16(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::CtActionNla::*;
+        match self {
+            Parms(_) => TCA_CT_PARMS,
+            Tm(_) => TCA_CT_TM,
+            Action(_) => TCA_CT_ACTION,
+            Zone(_) => TCA_CT_ZONE,
+            Mark(_) => TCA_CT_MARK,
+            MarkMask(_) => TCA_CT_MARK_MASK,
+            Labels(_) => TCA_CT_LABELS,
+            LabelsMask(_) => TCA_CT_LABELS_MASK,
+            NatIpv4Min(_) => TCA_CT_NAT_IPV4_MIN,
+            NatIpv4Max(_) => TCA_CT_NAT_IPV4_MAX,
+            NatIpv6Min(_) => TCA_CT_NAT_IPV6_MIN,
+            NatIpv6Max(_) => TCA_CT_NAT_IPV6_MAX,
+            NatPortMin(_) => TCA_CT_NAT_PORT_MIN,
+            NatPortMax(_) => TCA_CT_NAT_PORT_MAX,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+pub(crate) fn parse_labels(payload: &[u8]) -> Result<[u8; 16], DecodeError> {
+    if payload.len() != 16 {
+        return Err(format!("invalid conntrack labels: {:?}", payload).into());
+    }
+    let mut labels = [0; 16];
+    labels.copy_from_slice(payload);
+    Ok(labels)
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CtActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::CtActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CT_PARMS => Parms(
+                TcCt::parse(&TcCtBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CT_PARMS value")?,
+            ),
+            TCA_CT_TM => {
+                Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CT_TM value")?)
+            }
+            TCA_CT_ACTION => Action(CtActionFlags::from_bits_truncate(
+                parse_u16(payload).context("invalid TCA_CT_ACTION value")?,
+            )),
+            TCA_CT_ZONE => Zone(parse_u16(payload).context("invalid TCA_CT_ZONE value")?),
+            TCA_CT_MARK => Mark(parse_u32(payload).context("invalid TCA_CT_MARK value")?),
+            TCA_CT_MARK_MASK => {
+                MarkMask(parse_u32(payload).context("invalid TCA_CT_MARK_MASK value")?)
+            }
+            TCA_CT_LABELS => Labels(parse_labels(payload).context("invalid TCA_CT_LABELS value")?),
+            TCA_CT_LABELS_MASK => {
+                LabelsMask(parse_labels(payload).context("invalid TCA_CT_LABELS_MASK value")?)
+            }
+            TCA_CT_NAT_IPV4_MIN => {
+                NatIpv4Min(parse_ipv4(payload).context("invalid TCA_CT_NAT_IPV4_MIN value")?)
+            }
+            TCA_CT_NAT_IPV4_MAX => {
+                NatIpv4Max(parse_ipv4(payload).context("invalid TCA_CT_NAT_IPV4_MAX value")?)
+            }
+            TCA_CT_NAT_IPV6_MIN => {
+                NatIpv6Min(parse_ipv6(payload).context("invalid TCA_CT_NAT_IPV6_MIN value")?)
+            }
+            TCA_CT_NAT_IPV6_MAX => {
+                NatIpv6Max(parse_ipv6(payload).context("invalid TCA_CT_NAT_IPV6_MAX value")?)
+            }
+            TCA_CT_NAT_PORT_MIN => {
+                NatPortMin(parse_u16_be(payload).context("invalid TCA_CT_NAT_PORT_MIN value")?)
+            }
+            TCA_CT_NAT_PORT_MAX => {
+                NatPortMax(parse_u16_be(payload).context("invalid TCA_CT_NAT_PORT_MAX value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid ct action nla")?),
+        })
+    }
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct CtActionFlags: u16 {
+        /// Commit the connection to the conntrack table
+        const COMMIT = TCA_CT_ACT_COMMIT;
+        /// Commit the connection again if it was tracked in the other
+        /// direction
+        const FORCE = TCA_CT_ACT_FORCE;
+        /// Remove the conntrack state of the packet
+        const CLEAR = TCA_CT_ACT_CLEAR;
+        const NAT = TCA_CT_ACT_NAT;
+        const NAT_SRC = TCA_CT_ACT_NAT_SRC;
+        const NAT_DST = TCA_CT_ACT_NAT_DST;
+    }
+}
+
+impl Default for CtActionFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Ct parameters (`struct tc_ct`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcCt {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the packet is ctd
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+}
+
+pub const TC_CT_LEN: usize = 20;
+
+buffer!(TcCtBuffer(TC_CT_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcCtBuffer<T>> for TcCt {
+    fn parse(buf: &TcCtBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+        })
+    }
+}
+
+impl Emitable for TcCt {
+    fn buffer_len(&self) -> usize {
+        TC_CT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcCtBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+    }
+}
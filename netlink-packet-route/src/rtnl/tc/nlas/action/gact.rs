@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "gact";
+
+/// Options of the gact (generic action) action, which returns a fixed
+/// verdict such as `TC_ACT_OK` or `TC_ACT_SHOT`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GactAction {
+    pub nlas: Vec<GactActionNla>,
+}
+
+impl Emitable for GactAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for GactAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid gact action nla")?;
+            nlas.push(GactActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GactActionNla {
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Parms(TcGact),
+    /// Alternative verdict, returned with some probability
+    Prob(TcGactP),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for GactActionNla {
+    fn value_len(&self) -> usize {
+        use self::GactActionNla::*;
+        match self {
+            Tm(_) => TCF_LEN,
+            Parms(_) => TC_GACT_LEN,
+            Prob(_) => TC_GACT_P_LEN,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::GactActionNla::*;
+        match self {
+            Tm(ref tm) => tm.emit(buffer),
+            Parms(ref parms) => parms.emit(buffer),
+            Prob(ref prob) => prob.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::GactActionNla::*;
+        match self {
+            Tm(_) => TCA_GACT_TM,
+            Parms(_) => TCA_GACT_PARMS,
+            Prob(_) => TCA_GACT_PROB,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for GactActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::GactActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_GACT_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_GACT_TM value")?),
+            TCA_GACT_PARMS => Parms(
+                TcGact::parse(&TcGactBuffer::new_checked(payload)?)
+                    .context("invalid TCA_GACT_PARMS value")?,
+            ),
+            TCA_GACT_PROB => Prob(
+                TcGactP::parse(&TcGactPBuffer::new_checked(payload)?)
+                    .context("invalid TCA_GACT_PROB value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid gact action nla")?),
+        })
+    }
+}
+
+/// Gact parameters (`struct tc_gact`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcGact {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+}
+
+pub const TC_GACT_LEN: usize = 20;
+
+buffer!(TcGactBuffer(TC_GACT_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcGactBuffer<T>> for TcGact {
+    fn parse(buf: &TcGactBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+        })
+    }
+}
+
+impl Emitable for TcGact {
+    fn buffer_len(&self) -> usize {
+        TC_GACT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcGactBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+    }
+}
+
+/// Probabilistic gact parameters (`struct tc_gact_p`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcGactP {
+    /// How `paction` is chosen: `PGACT_NONE`, `PGACT_NETRAND` (randomly, one
+    /// packet out of `pval` on average) or `PGACT_DETERM` (every `pval`
+    /// packets)
+    pub ptype: u16,
+    pub pval: u16,
+    /// `TC_ACT_*` verdict returned instead of the one of the parameters
+    pub paction: i32,
+}
+
+pub const TC_GACT_P_LEN: usize = 8;
+
+buffer!(TcGactPBuffer(TC_GACT_P_LEN) {
+    ptype: (u16, 0..2),
+    pval: (u16, 2..4),
+    paction: (i32, 4..8),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcGactPBuffer<T>> for TcGactP {
+    fn parse(buf: &TcGactPBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            ptype: buf.ptype(),
+            pval: buf.pval(),
+            paction: buf.paction(),
+        })
+    }
+}
+
+impl Emitable for TcGactP {
+    fn buffer_len(&self) -> usize {
+        TC_GACT_P_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcGactPBuffer::new(buffer);
+        buffer.set_ptype(self.ptype);
+        buffer.set_pval(self.pval);
+        buffer.set_paction(self.paction);
+    }
+}
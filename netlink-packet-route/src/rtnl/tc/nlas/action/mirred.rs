@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "mirred";
+
+/// Options of the mirred action, which mirrors or redirects packets to
+/// another interface
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MirredAction {
+    pub nlas: Vec<MirredActionNla>,
+}
+
+impl Emitable for MirredAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for MirredAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid mirred action nla")?;
+            nlas.push(MirredActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MirredActionNla {
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Parms(TcMirred),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for MirredActionNla {
+    fn value_len(&self) -> usize {
+        use self::MirredActionNla::*;
+        match self {
+            Tm(_) => TCF_LEN,
+            Parms(_) => TC_MIRRED_LEN,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::MirredActionNla::*;
+        match self {
+            Tm(ref tm) => tm.emit(buffer),
+            Parms(ref parms) => parms.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::MirredActionNla::*;
+        match self {
+            Tm(_) => TCA_MIRRED_TM,
+            Parms(_) => TCA_MIRRED_PARMS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for MirredActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::MirredActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_MIRRED_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_MIRRED_TM value")?),
+            TCA_MIRRED_PARMS => Parms(
+                TcMirred::parse(&TcMirredBuffer::new_checked(payload)?)
+                    .context("invalid TCA_MIRRED_PARMS value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid mirred action nla")?),
+        })
+    }
+}
+
+/// Mirred parameters (`struct tc_mirred`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcMirred {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the packet is mirrored or redirected
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+    /// One of `TCA_EGRESS_REDIR`, `TCA_EGRESS_MIRROR`, `TCA_INGRESS_REDIR`
+    /// or `TCA_INGRESS_MIRROR`
+    pub eaction: i32,
+    /// Index of the interface the packets are sent to
+    pub ifindex: u32,
+}
+
+pub const TC_MIRRED_LEN: usize = 28;
+
+buffer!(TcMirredBuffer(TC_MIRRED_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+    eaction: (i32, 20..24),
+    ifindex: (u32, 24..28),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcMirredBuffer<T>> for TcMirred {
+    fn parse(buf: &TcMirredBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+            eaction: buf.eaction(),
+            ifindex: buf.ifindex(),
+        })
+    }
+}
+
+impl Emitable for TcMirred {
+    fn buffer_len(&self) -> usize {
+        TC_MIRRED_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcMirredBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+        buffer.set_eaction(self.eaction);
+        buffer.set_ifindex(self.ifindex);
+    }
+}
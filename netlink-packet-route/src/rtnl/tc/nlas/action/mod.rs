@@ -0,0 +1,346 @@
+// SPDX-License-Identifier: MIT
+
+pub mod mirred;
+pub use self::mirred::MirredAction;
+
+pub mod gact;
+pub use self::gact::GactAction;
+
+pub mod vlan;
+pub use self::vlan::VlanAction;
+
+pub mod police;
+pub use self::police::PoliceAction;
+
+pub mod skbedit;
+pub use self::skbedit::SkbeditAction;
+
+pub mod csum;
+pub use self::csum::CsumAction;
+
+pub mod tunnel_key;
+pub use self::tunnel_key::TunnelKeyAction;
+
+pub mod sample;
+pub use self::sample::SampleAction;
+
+pub mod ct;
+pub use self::ct::CtAction;
+
+pub mod mpls;
+pub use self::mpls::MplsAction;
+
+pub mod pedit;
+pub use self::pedit::PeditAction;
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_string, parse_u32},
+    traits::{Emitable, Parseable, ParseableParametrized},
+    DecodeError,
+};
+
+use super::Stats2;
+
+/// An action of a filter action list. Each action of the list is an
+/// attribute whose type is the position of the action in the list, starting
+/// at 1.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcAction {
+    /// Position of the action in the list
+    pub tab: u16,
+    pub nlas: Vec<TcActionNla>,
+}
+
+impl nlas::Nla for TcAction {
+    fn value_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+
+    fn kind(&self) -> u16 {
+        self.tab
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        // TCA_ACT_OPTIONS can only be parsed once we know the kind of the
+        // action.
+        let mut kind = String::new();
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid tc action nla")?;
+            let nla = TcActionNla::parse_with_param(&nla, kind.as_str())?;
+            if let TcActionNla::Kind(ref k) = nla {
+                kind = k.clone();
+            }
+            nlas.push(nla);
+        }
+        Ok(Self {
+            tab: buf.kind(),
+            nlas,
+        })
+    }
+}
+
+/// Parse an action list, as carried by the `*_ACT` attribute of filters
+pub(crate) fn parse_actions(payload: &[u8]) -> Result<Vec<TcAction>, DecodeError> {
+    let mut actions = vec![];
+    for nla in NlasIterator::new(payload) {
+        let nla = nla.context("invalid tc action list")?;
+        actions.push(TcAction::parse(&nla)?);
+    }
+    Ok(actions)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TcActionNla {
+    /// Kind of the action, for instance "mirred"
+    Kind(String),
+    /// Action specific options
+    Options(TcActionOptions),
+    /// Index of the action, shared by all the filters that use it
+    Index(u32),
+    Stats(Vec<Stats2>),
+    /// Opaque data attached to the action by userspace
+    Cookie(Vec<u8>),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for TcActionNla {
+    fn value_len(&self) -> usize {
+        use self::TcActionNla::*;
+        match self {
+            Kind(ref string) => string.len() + 1,
+            Options(ref opts) => opts.buffer_len(),
+            Index(_) => 4,
+            Stats(ref stats) => stats.as_slice().buffer_len(),
+            Cookie(ref bytes) => bytes.len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::TcActionNla::*;
+        match self {
+            Kind(ref string) => {
+                buffer[..string.len()].copy_from_slice(string.as_bytes());
+                buffer[string.len()] = 0;
+            }
+            Options(ref opts) => opts.emit(buffer),
+            Index(value) => NativeEndian::write_u32(buffer, *value),
+            Stats(ref stats) => stats.as_slice().emit(buffer),
+            Cookie(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::TcActionNla::*;
+        match self {
+            Kind(_) => TCA_ACT_KIND,
+            Options(_) => TCA_ACT_OPTIONS,
+            Index(_) => TCA_ACT_INDEX,
+            Stats(_) => TCA_ACT_STATS,
+            Cookie(_) => TCA_ACT_COOKIE,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, 'b, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, &'b str>
+    for TcActionNla
+{
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, kind: &'b str) -> Result<Self, DecodeError> {
+        use self::TcActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_ACT_KIND => Kind(parse_string(payload).context("invalid TCA_ACT_KIND value")?),
+            TCA_ACT_OPTIONS => Options(TcActionOptions::parse_with_param(buf, kind)?),
+            TCA_ACT_INDEX => Index(parse_u32(payload).context("invalid TCA_ACT_INDEX value")?),
+            TCA_ACT_STATS => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_ACT_STATS value")?;
+                    nlas.push(Stats2::parse_with_param(&nla, kind)?);
+                }
+                Stats(nlas)
+            }
+            TCA_ACT_COOKIE => Cookie(payload.to_vec()),
+            _ => Other(DefaultNla::parse(buf).context("invalid tc action nla")?),
+        })
+    }
+}
+
+/// Action specific options, carried by the `TCA_ACT_OPTIONS` attribute
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TcActionOptions {
+    Mirred(MirredAction),
+    Gact(GactAction),
+    Vlan(VlanAction),
+    Police(PoliceAction),
+    Skbedit(SkbeditAction),
+    Csum(CsumAction),
+    TunnelKey(TunnelKeyAction),
+    Sample(SampleAction),
+    Ct(CtAction),
+    Mpls(MplsAction),
+    Pedit(PeditAction),
+    Other(#[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_base64"))] Vec<u8>),
+}
+
+impl TcActionOptions {
+    /// Create empty options for the given action kind.
+    /// Unknown kinds give empty `Other` options rather than an error.
+    pub fn new(kind: &str) -> Self {
+        match kind {
+            mirred::KIND => TcActionOptions::Mirred(MirredAction::default()),
+            gact::KIND => TcActionOptions::Gact(GactAction::default()),
+            vlan::KIND => TcActionOptions::Vlan(VlanAction::default()),
+            police::KIND => TcActionOptions::Police(PoliceAction::default()),
+            skbedit::KIND => TcActionOptions::Skbedit(SkbeditAction::default()),
+            csum::KIND => TcActionOptions::Csum(CsumAction::default()),
+            tunnel_key::KIND => TcActionOptions::TunnelKey(TunnelKeyAction::default()),
+            sample::KIND => TcActionOptions::Sample(SampleAction::default()),
+            ct::KIND => TcActionOptions::Ct(CtAction::default()),
+            mpls::KIND => TcActionOptions::Mpls(MplsAction::default()),
+            pedit::KIND => TcActionOptions::Pedit(PeditAction::default()),
+            _ => TcActionOptions::Other(Vec::new()),
+        }
+    }
+}
+
+impl Emitable for TcActionOptions {
+    fn buffer_len(&self) -> usize {
+        match self {
+            TcActionOptions::Mirred(ref mirred) => mirred.buffer_len(),
+            TcActionOptions::Gact(ref gact) => gact.buffer_len(),
+            TcActionOptions::Vlan(ref vlan) => vlan.buffer_len(),
+            TcActionOptions::Police(ref police) => police.buffer_len(),
+            TcActionOptions::Skbedit(ref skbedit) => skbedit.buffer_len(),
+            TcActionOptions::Csum(ref csum) => csum.buffer_len(),
+            TcActionOptions::TunnelKey(ref tunnel_key) => tunnel_key.buffer_len(),
+            TcActionOptions::Sample(ref sample) => sample.buffer_len(),
+            TcActionOptions::Ct(ref ct) => ct.buffer_len(),
+            TcActionOptions::Mpls(ref mpls) => mpls.buffer_len(),
+            TcActionOptions::Pedit(ref pedit) => pedit.buffer_len(),
+            TcActionOptions::Other(ref bytes) => bytes.len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            TcActionOptions::Mirred(ref mirred) => mirred.emit(buffer),
+            TcActionOptions::Gact(ref gact) => gact.emit(buffer),
+            TcActionOptions::Vlan(ref vlan) => vlan.emit(buffer),
+            TcActionOptions::Police(ref police) => police.emit(buffer),
+            TcActionOptions::Skbedit(ref skbedit) => skbedit.emit(buffer),
+            TcActionOptions::Csum(ref csum) => csum.emit(buffer),
+            TcActionOptions::TunnelKey(ref tunnel_key) => tunnel_key.emit(buffer),
+            TcActionOptions::Sample(ref sample) => sample.emit(buffer),
+            TcActionOptions::Ct(ref ct) => ct.emit(buffer),
+            TcActionOptions::Mpls(ref mpls) => mpls.emit(buffer),
+            TcActionOptions::Pedit(ref pedit) => pedit.emit(buffer),
+            TcActionOptions::Other(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, &str>
+    for TcActionOptions
+{
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, kind: &str) -> Result<Self, DecodeError> {
+        Ok(match kind {
+            mirred::KIND => TcActionOptions::Mirred(
+                MirredAction::parse(buf).context("failed to parse mirred action options")?,
+            ),
+            gact::KIND => TcActionOptions::Gact(
+                GactAction::parse(buf).context("failed to parse gact action options")?,
+            ),
+            vlan::KIND => TcActionOptions::Vlan(
+                VlanAction::parse(buf).context("failed to parse vlan action options")?,
+            ),
+            police::KIND => TcActionOptions::Police(
+                PoliceAction::parse(buf).context("failed to parse police action options")?,
+            ),
+            skbedit::KIND => TcActionOptions::Skbedit(
+                SkbeditAction::parse(buf).context("failed to parse skbedit action options")?,
+            ),
+            csum::KIND => TcActionOptions::Csum(
+                CsumAction::parse(buf).context("failed to parse csum action options")?,
+            ),
+            tunnel_key::KIND => TcActionOptions::TunnelKey(
+                TunnelKeyAction::parse(buf).context("failed to parse tunnel_key action options")?,
+            ),
+            sample::KIND => TcActionOptions::Sample(
+                SampleAction::parse(buf).context("failed to parse sample action options")?,
+            ),
+            ct::KIND => TcActionOptions::Ct(
+                CtAction::parse(buf).context("failed to parse ct action options")?,
+            ),
+            mpls::KIND => TcActionOptions::Mpls(
+                MplsAction::parse(buf).context("failed to parse mpls action options")?,
+            ),
+            pedit::KIND => TcActionOptions::Pedit(
+                PeditAction::parse(buf).context("failed to parse pedit action options")?,
+            ),
+            _ => TcActionOptions::Other(buf.value().to_vec()),
+        })
+    }
+}
+
+/// Install, last use, expiration and first use times of an action, in
+/// jiffies (`struct tcf_t`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tcf {
+    pub install: u64,
+    pub lastuse: u64,
+    pub expires: u64,
+    pub firstuse: u64,
+}
+
+pub const TCF_LEN: usize = 32;
+
+buffer!(TcfBuffer(TCF_LEN) {
+    install: (u64, 0..8),
+    lastuse: (u64, 8..16),
+    expires: (u64, 16..24),
+    firstuse: (u64, 24..32),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcfBuffer<T>> for Tcf {
+    fn parse(buf: &TcfBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            install: buf.install(),
+            lastuse: buf.lastuse(),
+            expires: buf.expires(),
+            firstuse: buf.firstuse(),
+        })
+    }
+}
+
+impl Emitable for Tcf {
+    fn buffer_len(&self) -> usize {
+        TCF_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcfBuffer::new(buffer);
+        buffer.set_install(self.install);
+        buffer.set_lastuse(self.lastuse);
+        buffer.set_expires(self.expires);
+        buffer.set_firstuse(self.firstuse);
+    }
+}
@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_u16_be, parse_u32, parse_u8},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "mpls";
+
+/// Options of the mpls action, which pushes, pops or modifies MPLS label
+/// stack entries
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MplsAction {
+    pub nlas: Vec<MplsActionNla>,
+}
+
+impl Emitable for MplsAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for MplsAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid mpls action nla")?;
+            nlas.push(MplsActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+/// Labels are 20 bits long
+pub(crate) const MPLS_LABEL_MASK: u32 = 0xf_ffff;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MplsActionNla {
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Parms(TcMpls),
+    /// Protocol of the packet once the label is pushed or popped, such as
+    /// `ETH_P_MPLS_UC` when pushing, or `ETH_P_IP` when popping the last
+    /// label. It is in network byte order on the wire.
+    Proto(u16),
+    /// Label of the entry. Only the lower 20 bits are emitted.
+    Label(u32),
+    /// Traffic class of the entry
+    Tc(u8),
+    Ttl(u8),
+    /// Bottom of stack bit of the entry
+    Bos(u8),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for MplsActionNla {
+    fn value_len(&self) -> usize {
+        use self::MplsActionNla::*;
+        match self {
+            Tm(_) => TCF_LEN,
+            Parms(_) => TC_MPLS_LEN,
+            Proto(_) => 2,
+            Label(_) => 4,
+            Tc(_) | Ttl(_) | Bos(_) => 1,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::MplsActionNla::*;
+        match self {
+            Tm(ref tm) => tm.emit(buffer),
+            Parms(ref parms) => parms.emit(buffer),
+            Proto(value) => BigEndian::write_u16(buffer, *value),
+            Label(value) => NativeEndian::write_u32(buffer, *value & MPLS_LABEL_MASK),
+            Tc(value) | Ttl(value) | Bos(value) => buffer[0] = *value,
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::MplsActionNla::*;
+        match self {
+            Tm(_) => TCA_MPLS_TM,
+            Parms(_) => TCA_MPLS_PARMS,
+            Proto(_) => TCA_MPLS_PROTO,
+            Label(_) => TCA_MPLS_LABEL,
+            Tc(_) => TCA_MPLS_TC,
+            Ttl(_) => TCA_MPLS_TTL,
+            Bos(_) => TCA_MPLS_BOS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for MplsActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::MplsActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_MPLS_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_MPLS_TM value")?),
+            TCA_MPLS_PARMS => Parms(
+                TcMpls::parse(&TcMplsBuffer::new_checked(payload)?)
+                    .context("invalid TCA_MPLS_PARMS value")?,
+            ),
+            TCA_MPLS_PROTO => Proto(parse_u16_be(payload).context("invalid TCA_MPLS_PROTO value")?),
+            TCA_MPLS_LABEL => Label(parse_u32(payload).context("invalid TCA_MPLS_LABEL value")?),
+            TCA_MPLS_TC => Tc(parse_u8(payload).context("invalid TCA_MPLS_TC value")?),
+            TCA_MPLS_TTL => Ttl(parse_u8(payload).context("invalid TCA_MPLS_TTL value")?),
+            TCA_MPLS_BOS => Bos(parse_u8(payload).context("invalid TCA_MPLS_BOS value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid mpls action nla")?),
+        })
+    }
+}
+
+/// Mpls parameters (`struct tc_mpls`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcMpls {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the label stack is handled
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+    /// One of the `TCA_MPLS_ACT_*` operations
+    pub m_action: i32,
+}
+
+pub const TC_MPLS_LEN: usize = 24;
+
+buffer!(TcMplsBuffer(TC_MPLS_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+    m_action: (i32, 20..24),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcMplsBuffer<T>> for TcMpls {
+    fn parse(buf: &TcMplsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+            m_action: buf.m_action(),
+        })
+    }
+}
+
+impl Emitable for TcMpls {
+    fn buffer_len(&self) -> usize {
+        TC_MPLS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcMplsBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+        buffer.set_m_action(self.m_action);
+    }
+}
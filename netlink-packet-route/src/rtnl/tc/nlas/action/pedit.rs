@@ -0,0 +1,348 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u16,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "pedit";
+
+/// Options of the pedit action, which edits arbitrary parts of the packet
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeditAction {
+    pub nlas: Vec<PeditActionNla>,
+}
+
+impl Emitable for PeditAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for PeditAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid pedit action nla")?;
+            nlas.push(PeditActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+impl PeditAction {
+    /// Check that there are as many extended keys as keys: the `n`th extended
+    /// key gives the header type and command of the `n`th key.
+    pub fn validate(&self) -> Result<(), DecodeError> {
+        let mut keys = None;
+        let mut keys_ex = None;
+        for nla in &self.nlas {
+            match nla {
+                PeditActionNla::Parms(ref parms) | PeditActionNla::ParmsEx(ref parms) => {
+                    keys = Some(parms.keys.len())
+                }
+                PeditActionNla::KeysEx(ref ex) => keys_ex = Some(ex.len()),
+                _ => {}
+            }
+        }
+        match (keys, keys_ex) {
+            (Some(keys), Some(keys_ex)) if keys != keys_ex => {
+                Err(format!("{} extended keys were given for {} keys", keys_ex, keys).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PeditActionNla {
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Parms(TcPedit),
+    /// Same as `Parms`, used when `KeysEx` is given
+    ParmsEx(TcPedit),
+    /// Header type and command of each key
+    KeysEx(Vec<TcPeditKeyEx>),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for PeditActionNla {
+    fn value_len(&self) -> usize {
+        use self::PeditActionNla::*;
+        match self {
+            Tm(_) => TCF_LEN,
+            Parms(ref parms) | ParmsEx(ref parms) => parms.buffer_len(),
+            KeysEx(ref keys) => keys.as_slice().buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::PeditActionNla::*;
+        match self {
+            Tm(ref tm) => tm.emit(buffer),
+            Parms(ref parms) | ParmsEx(ref parms) => parms.emit(buffer),
+            KeysEx(ref keys) => keys.as_slice().emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::PeditActionNla::*;
+        match self {
+            Tm(_) => TCA_PEDIT_TM,
+            Parms(_) => TCA_PEDIT_PARMS,
+            ParmsEx(_) => TCA_PEDIT_PARMS_EX,
+            KeysEx(_) => TCA_PEDIT_KEYS_EX,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for PeditActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::PeditActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_PEDIT_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_PEDIT_TM value")?),
+            TCA_PEDIT_PARMS => Parms(
+                TcPedit::parse(&TcPeditBuffer::new_checked(payload)?)
+                    .context("invalid TCA_PEDIT_PARMS value")?,
+            ),
+            TCA_PEDIT_PARMS_EX => ParmsEx(
+                TcPedit::parse(&TcPeditBuffer::new_checked(payload)?)
+                    .context("invalid TCA_PEDIT_PARMS_EX value")?,
+            ),
+            TCA_PEDIT_KEYS_EX => {
+                let mut keys = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_PEDIT_KEYS_EX value")?;
+                    keys.push(
+                        TcPeditKeyEx::parse(&nla).context("invalid TCA_PEDIT_KEYS_EX value")?,
+                    );
+                }
+                KeysEx(keys)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid pedit action nla")?),
+        })
+    }
+}
+
+/// Pedit parameters (`struct tc_pedit_sel`)
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcPedit {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the packet is edited
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+    pub flags: u8,
+    /// Edits applied to the packet. Their number is the `nkeys` field of the
+    /// parameters.
+    pub keys: Vec<TcPeditKey>,
+}
+
+pub const TC_PEDIT_LEN: usize = 24;
+
+buffer!(TcPeditBuffer(TC_PEDIT_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+    nkeys: (u8, 20),
+    flags: (u8, 21),
+    pad: (u16, 22..24),
+    keys: (slice, TC_PEDIT_LEN..),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcPeditBuffer<&'a T>> for TcPedit {
+    fn parse(buf: &TcPeditBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let nkeys = buf.nkeys() as usize;
+        let payload = buf.keys();
+        if payload.len() < nkeys * TC_PEDIT_KEY_LEN {
+            return Err(format!(
+                "expected {} keys but only got {} bytes",
+                nkeys,
+                payload.len()
+            )
+            .into());
+        }
+        let mut keys = Vec::with_capacity(nkeys);
+        for chunk in payload.chunks_exact(TC_PEDIT_KEY_LEN).take(nkeys) {
+            keys.push(TcPeditKey::parse(&TcPeditKeyBuffer::new(chunk))?);
+        }
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+            flags: buf.flags(),
+            keys,
+        })
+    }
+}
+
+impl Emitable for TcPedit {
+    fn buffer_len(&self) -> usize {
+        TC_PEDIT_LEN + self.keys.len() * TC_PEDIT_KEY_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcPeditBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+        buffer.set_nkeys(self.keys.len() as u8);
+        buffer.set_flags(self.flags);
+        buffer.set_pad(0);
+        for (key, chunk) in self
+            .keys
+            .iter()
+            .zip(buffer.keys_mut().chunks_exact_mut(TC_PEDIT_KEY_LEN))
+        {
+            key.emit(chunk);
+        }
+    }
+}
+
+/// Edit of the packet (`struct tc_pedit_key`): the 32 bits word at `off` is
+/// ANDed with `mask` then XORed with `val`. As for the u32 filter, the mask
+/// and value are in host byte order, so setting the IPv4 TTL (the first byte
+/// of the word at offset 8) to 63 is `val: 0x3f00_0000` and
+/// `mask: 0x00ff_ffff`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcPeditKey {
+    pub mask: u32,
+    pub val: u32,
+    /// Offset of the word, relative to the header selected by the extended
+    /// key
+    pub off: i32,
+    /// Position of the byte used to compute a variable offset
+    pub at: u32,
+    /// Mask applied to the byte read at `at`
+    pub offmask: u32,
+    /// Shift applied to the byte read at `at`, once masked
+    pub shift: u32,
+}
+
+pub const TC_PEDIT_KEY_LEN: usize = 24;
+
+buffer!(TcPeditKeyBuffer(TC_PEDIT_KEY_LEN) {
+    mask: (slice, 0..4),
+    val: (slice, 4..8),
+    off: (i32, 8..12),
+    at: (u32, 12..16),
+    offmask: (u32, 16..20),
+    shift: (u32, 20..24),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcPeditKeyBuffer<&'a T>> for TcPeditKey {
+    fn parse(buf: &TcPeditKeyBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            mask: BigEndian::read_u32(buf.mask()),
+            val: BigEndian::read_u32(buf.val()),
+            off: buf.off(),
+            at: buf.at(),
+            offmask: buf.offmask(),
+            shift: buf.shift(),
+        })
+    }
+}
+
+impl Emitable for TcPeditKey {
+    fn buffer_len(&self) -> usize {
+        TC_PEDIT_KEY_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcPeditKeyBuffer::new(buffer);
+        BigEndian::write_u32(buffer.mask_mut(), self.mask);
+        BigEndian::write_u32(buffer.val_mut(), self.val);
+        buffer.set_off(self.off);
+        buffer.set_at(self.at);
+        buffer.set_offmask(self.offmask);
+        buffer.set_shift(self.shift);
+    }
+}
+
+/// Extended key, carried by a `TCA_PEDIT_KEY_EX` attribute
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcPeditKeyEx {
+    /// One of the `TCA_PEDIT_KEY_EX_HDR_TYPE_*` header types the offset of
+    /// the key is relative to
+    pub htype: u16,
+    /// `TCA_PEDIT_KEY_EX_CMD_SET` or `TCA_PEDIT_KEY_EX_CMD_ADD`
+    pub cmd: u16,
+}
+
+impl nlas::Nla for TcPeditKeyEx {
+    fn value_len(&self) -> usize {
+        // TCA_PEDIT_KEY_EX_HTYPE and TCA_PEDIT_KEY_EX_CMD, 8 bytes each
+        16
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        let attributes = [
+            (TCA_PEDIT_KEY_EX_HTYPE, self.htype),
+            (TCA_PEDIT_KEY_EX_CMD, self.cmd),
+        ];
+        for (chunk, (kind, value)) in buffer.chunks_exact_mut(8).zip(attributes.iter()) {
+            // padding
+            chunk[6..].copy_from_slice(&[0, 0]);
+            let mut nla = NlaBuffer::new(chunk);
+            nla.set_length(6);
+            nla.set_kind(*kind);
+            NativeEndian::write_u16(nla.value_mut(), *value);
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        TCA_PEDIT_KEY_EX
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcPeditKeyEx {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut key = Self::default();
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid TCA_PEDIT_KEY_EX value")?;
+            let payload = nla.value();
+            match nla.kind() {
+                TCA_PEDIT_KEY_EX_HTYPE => {
+                    key.htype =
+                        parse_u16(payload).context("invalid TCA_PEDIT_KEY_EX_HTYPE value")?
+                }
+                TCA_PEDIT_KEY_EX_CMD => {
+                    key.cmd = parse_u16(payload).context("invalid TCA_PEDIT_KEY_EX_CMD value")?
+                }
+                _ => {}
+            }
+        }
+        Ok(key)
+    }
+}
@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_i32, parse_u32, parse_u64},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{
+    super::{emit_u32_list, parse_u32_list, TcRateSpec, TcRateSpecBuffer},
+    Tcf,
+    TcfBuffer,
+    TCF_LEN,
+};
+
+pub const KIND: &str = "police";
+
+/// Options of the police action, which rate limits packets. They are also
+/// used by the legacy `*_POLICE` attribute of filters.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoliceAction {
+    pub nlas: Vec<PoliceActionNla>,
+}
+
+impl Emitable for PoliceAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for PoliceAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid police action nla")?;
+            nlas.push(PoliceActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PoliceActionNla {
+    Tbf(TcPolice),
+    /// Rate table of the rate of the parameters, see
+    /// [`TcRateSpec::rate_table`]
+    Rate(Vec<u32>),
+    /// Rate table of the peak rate of the parameters
+    PeakRate(Vec<u32>),
+    /// Average rate above which packets are over the limit, in bytes per
+    /// second
+    AvRate(u32),
+    /// `TC_ACT_*` verdict for the packets that are not over the limit
+    Result(i32),
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    /// Rate, in bytes per second, when it does not fit in 32 bits
+    Rate64(u64),
+    /// Peak rate, in bytes per second, when it does not fit in 32 bits
+    PeakRate64(u64),
+    /// Rate, in packets per second
+    PktRate64(u64),
+    /// Burst, in packets
+    PktBurst64(u64),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for PoliceActionNla {
+    fn value_len(&self) -> usize {
+        use self::PoliceActionNla::*;
+        match self {
+            Tbf(_) => TC_POLICE_LEN,
+            Rate(ref table) | PeakRate(ref table) => table.len() * 4,
+            AvRate(_) | Result(_) => 4,
+            Tm(_) => TCF_LEN,
+            Rate64(_) | PeakRate64(_) | PktRate64(_) | PktBurst64(_) => 8,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::PoliceActionNla::*;
+        match self {
+            Tbf(ref tbf) => tbf.emit(buffer),
+            Rate(ref table) | PeakRate(ref table) => emit_u32_list(buffer, table),
+            AvRate(value) => NativeEndian::write_u32(buffer, *value),
+            Result(value) => NativeEndian::write_i32(buffer, *value),
+            Tm(ref tm) => tm.emit(buffer),
+            Rate64(value) | PeakRate64(value) | PktRate64(value) | PktBurst64(value) => {
+                NativeEndian::write_u64(buffer, *value)
+            }
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::PoliceActionNla::*;
+        match self {
+            Tbf(_) => TCA_POLICE_TBF,
+            Rate(_) => TCA_POLICE_RATE,
+            PeakRate(_) => TCA_POLICE_PEAKRATE,
+            AvRate(_) => TCA_POLICE_AVRATE,
+            Result(_) => TCA_POLICE_RESULT,
+            Tm(_) => TCA_POLICE_TM,
+            Rate64(_) => TCA_POLICE_RATE64,
+            PeakRate64(_) => TCA_POLICE_PEAKRATE64,
+            PktRate64(_) => TCA_POLICE_PKTRATE64,
+            PktBurst64(_) => TCA_POLICE_PKTBURST64,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for PoliceActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::PoliceActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_POLICE_TBF => Tbf(TcPolice::parse(&TcPoliceBuffer::new_checked(payload)?)
+                .context("invalid TCA_POLICE_TBF value")?),
+            TCA_POLICE_RATE => {
+                Rate(parse_u32_list(payload).context("invalid TCA_POLICE_RATE value")?)
+            }
+            TCA_POLICE_PEAKRATE => {
+                PeakRate(parse_u32_list(payload).context("invalid TCA_POLICE_PEAKRATE value")?)
+            }
+            TCA_POLICE_AVRATE => {
+                AvRate(parse_u32(payload).context("invalid TCA_POLICE_AVRATE value")?)
+            }
+            TCA_POLICE_RESULT => {
+                Result(parse_i32(payload).context("invalid TCA_POLICE_RESULT value")?)
+            }
+            TCA_POLICE_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_POLICE_TM value")?),
+            TCA_POLICE_RATE64 => {
+                Rate64(parse_u64(payload).context("invalid TCA_POLICE_RATE64 value")?)
+            }
+            TCA_POLICE_PEAKRATE64 => {
+                PeakRate64(parse_u64(payload).context("invalid TCA_POLICE_PEAKRATE64 value")?)
+            }
+            TCA_POLICE_PKTRATE64 => {
+                PktRate64(parse_u64(payload).context("invalid TCA_POLICE_PKTRATE64 value")?)
+            }
+            TCA_POLICE_PKTBURST64 => {
+                PktBurst64(parse_u64(payload).context("invalid TCA_POLICE_PKTBURST64 value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid police action nla")?),
+        })
+    }
+}
+
+/// Police parameters (`struct tc_police`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcPolice {
+    pub index: u32,
+    /// `TC_ACT_*` verdict for the packets over the limit
+    pub action: i32,
+    /// Size of the queue, in bytes. Unused by the kernel.
+    pub limit: u32,
+    /// Time it takes to send the burst size at `rate`, in scheduler ticks
+    pub burst: u32,
+    /// Packets above this size are over the limit, in bytes
+    pub mtu: u32,
+    /// Rate, including the per-packet `overhead` such as the Ethernet header
+    pub rate: TcRateSpec,
+    pub peakrate: TcRateSpec,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+    pub capab: u32,
+}
+
+pub const TC_POLICE_LEN: usize = 56;
+
+buffer!(TcPoliceBuffer(TC_POLICE_LEN) {
+    index: (u32, 0..4),
+    action: (i32, 4..8),
+    limit: (u32, 8..12),
+    burst: (u32, 12..16),
+    mtu: (u32, 16..20),
+    rate: (slice, 20..32),
+    peakrate: (slice, 32..44),
+    refcnt: (i32, 44..48),
+    bindcnt: (i32, 48..52),
+    capab: (u32, 52..56),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcPoliceBuffer<&'a T>> for TcPolice {
+    fn parse(buf: &TcPoliceBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            action: buf.action(),
+            limit: buf.limit(),
+            burst: buf.burst(),
+            mtu: buf.mtu(),
+            rate: TcRateSpec::parse(&TcRateSpecBuffer::new(buf.rate()))?,
+            peakrate: TcRateSpec::parse(&TcRateSpecBuffer::new(buf.peakrate()))?,
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+            capab: buf.capab(),
+        })
+    }
+}
+
+impl Emitable for TcPolice {
+    fn buffer_len(&self) -> usize {
+        TC_POLICE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcPoliceBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_action(self.action);
+        buffer.set_limit(self.limit);
+        buffer.set_burst(self.burst);
+        buffer.set_mtu(self.mtu);
+        self.rate.emit(buffer.rate_mut());
+        self.peakrate.emit(buffer.peakrate_mut());
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+        buffer.set_capab(self.capab);
+    }
+}
@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "sample";
+
+/// Options of the sample action, which sends a copy of one packet out of
+/// `Rate` to the psample generic netlink family
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SampleAction {
+    pub nlas: Vec<SampleActionNla>,
+}
+
+impl Emitable for SampleAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for SampleAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid sample action nla")?;
+            nlas.push(SampleActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SampleActionNla {
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Parms(TcSample),
+    /// One packet out of `Rate` is sampled, on average
+    Rate(u32),
+    /// Number of bytes of the packet to copy, 0 for the whole packet
+    TruncSize(u32),
+    /// Group of the psample generic netlink family the packets are sent to
+    PsampleGroup(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for SampleActionNla {
+    fn value_len(&self) -> usize {
+        use self::SampleActionNla::*;
+        match self {
+            Tm(_) => TCF_LEN,
+            Parms(_) => TC_SAMPLE_LEN,
+            Rate(_) | TruncSize(_) | PsampleGroup(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::SampleActionNla::*;
+        match self {
+            Tm(ref tm) => tm.emit(buffer),
+            Parms(ref parms) => parms.emit(buffer),
+            Rate(value) | TruncSize(value) | PsampleGroup(value) => {
+                NativeEndian::write_u32(buffer, *value)
+            }
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::SampleActionNla::*;
+        match self {
+            Tm(_) => TCA_SAMPLE_TM,
+            Parms(_) => TCA_SAMPLE_PARMS,
+            Rate(_) => TCA_SAMPLE_RATE,
+            TruncSize(_) => TCA_SAMPLE_TRUNC_SIZE,
+            PsampleGroup(_) => TCA_SAMPLE_PSAMPLE_GROUP,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for SampleActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::SampleActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_SAMPLE_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_SAMPLE_TM value")?),
+            TCA_SAMPLE_PARMS => Parms(
+                TcSample::parse(&TcSampleBuffer::new_checked(payload)?)
+                    .context("invalid TCA_SAMPLE_PARMS value")?,
+            ),
+            TCA_SAMPLE_RATE => Rate(parse_u32(payload).context("invalid TCA_SAMPLE_RATE value")?),
+            TCA_SAMPLE_TRUNC_SIZE => {
+                TruncSize(parse_u32(payload).context("invalid TCA_SAMPLE_TRUNC_SIZE value")?)
+            }
+            TCA_SAMPLE_PSAMPLE_GROUP => {
+                PsampleGroup(parse_u32(payload).context("invalid TCA_SAMPLE_PSAMPLE_GROUP value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid sample action nla")?),
+        })
+    }
+}
+
+/// Sample parameters (`struct tc_sample`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcSample {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the packet is sampled
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+}
+
+pub const TC_SAMPLE_LEN: usize = 20;
+
+buffer!(TcSampleBuffer(TC_SAMPLE_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcSampleBuffer<T>> for TcSample {
+    fn parse(buf: &TcSampleBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+        })
+    }
+}
+
+impl Emitable for TcSample {
+    fn buffer_len(&self) -> usize {
+        TC_SAMPLE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcSampleBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+    }
+}
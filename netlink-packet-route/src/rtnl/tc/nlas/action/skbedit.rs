@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_u16, parse_u32, parse_u64},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "skbedit";
+
+/// Options of the skbedit action, which modifies the metadata of the packet
+/// (priority, queue mapping, firewall mark, packet type).
+///
+/// Only the fields present in `nlas` are modified, so attributes for the
+/// fields that must be left untouched should not be added.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SkbeditAction {
+    pub nlas: Vec<SkbeditActionNla>,
+}
+
+impl Emitable for SkbeditAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for SkbeditAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid skbedit action nla")?;
+            nlas.push(SkbeditActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SkbeditActionNla {
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Parms(TcSkbedit),
+    Priority(u32),
+    /// Transmit queue of the packet
+    QueueMapping(u16),
+    Mark(u32),
+    /// Mask applied to the firewall mark, only the masked bits are modified
+    Mask(u32),
+    /// One of the `PACKET_*` packet types
+    Ptype(u16),
+    /// `SKBEDIT_F_INHERITDSFIELD` and `SKBEDIT_F_TXQ_SKBHASH` flags
+    Flags(u64),
+    /// Last queue of the range used with `SKBEDIT_F_TXQ_SKBHASH`
+    QueueMappingMax(u16),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for SkbeditActionNla {
+    fn value_len(&self) -> usize {
+        use self::SkbeditActionNla::*;
+        match self {
+            Tm(_) => TCF_LEN,
+            Parms(_) => TC_SKBEDIT_LEN,
+            QueueMapping(_) | Ptype(_) | QueueMappingMax(_) => 2,
+            Priority(_) | Mark(_) | Mask(_) => 4,
+            Flags(_) => 8,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::SkbeditActionNla::*;
+        match self {
+            Tm(ref tm) => tm.emit(buffer),
+            Parms(ref parms) => parms.emit(buffer),
+            QueueMapping(value) | Ptype(value) | QueueMappingMax(value) => {
+                NativeEndian::write_u16(buffer, *value)
+            }
+            Priority(value) | Mark(value) | Mask(value) => NativeEndian::write_u32(buffer, *value),
+            Flags(value) => NativeEndian::write_u64(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::SkbeditActionNla::*;
+        match self {
+            Tm(_) => TCA_SKBEDIT_TM,
+            Parms(_) => TCA_SKBEDIT_PARMS,
+            Priority(_) => TCA_SKBEDIT_PRIORITY,
+            QueueMapping(_) => TCA_SKBEDIT_QUEUE_MAPPING,
+            Mark(_) => TCA_SKBEDIT_MARK,
+            Mask(_) => TCA_SKBEDIT_MASK,
+            Ptype(_) => TCA_SKBEDIT_PTYPE,
+            Flags(_) => TCA_SKBEDIT_FLAGS,
+            QueueMappingMax(_) => TCA_SKBEDIT_QUEUE_MAPPING_MAX,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for SkbeditActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::SkbeditActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_SKBEDIT_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_SKBEDIT_TM value")?),
+            TCA_SKBEDIT_PARMS => Parms(
+                TcSkbedit::parse(&TcSkbeditBuffer::new_checked(payload)?)
+                    .context("invalid TCA_SKBEDIT_PARMS value")?,
+            ),
+            TCA_SKBEDIT_PRIORITY => {
+                Priority(parse_u32(payload).context("invalid TCA_SKBEDIT_PRIORITY value")?)
+            }
+            TCA_SKBEDIT_QUEUE_MAPPING => {
+                QueueMapping(parse_u16(payload).context("invalid TCA_SKBEDIT_QUEUE_MAPPING value")?)
+            }
+            TCA_SKBEDIT_MARK => Mark(parse_u32(payload).context("invalid TCA_SKBEDIT_MARK value")?),
+            TCA_SKBEDIT_MASK => Mask(parse_u32(payload).context("invalid TCA_SKBEDIT_MASK value")?),
+            TCA_SKBEDIT_PTYPE => {
+                Ptype(parse_u16(payload).context("invalid TCA_SKBEDIT_PTYPE value")?)
+            }
+            TCA_SKBEDIT_FLAGS => {
+                Flags(parse_u64(payload).context("invalid TCA_SKBEDIT_FLAGS value")?)
+            }
+            TCA_SKBEDIT_QUEUE_MAPPING_MAX => QueueMappingMax(
+                parse_u16(payload).context("invalid TCA_SKBEDIT_QUEUE_MAPPING_MAX value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid skbedit action nla")?),
+        })
+    }
+}
+
+/// Skbedit parameters (`struct tc_skbedit`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcSkbedit {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the packet is modified
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+}
+
+pub const TC_SKBEDIT_LEN: usize = 20;
+
+buffer!(TcSkbeditBuffer(TC_SKBEDIT_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcSkbeditBuffer<T>> for TcSkbedit {
+    fn parse(buf: &TcSkbeditBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+        })
+    }
+}
+
+impl Emitable for TcSkbedit {
+    fn buffer_len(&self) -> usize {
+        TC_SKBEDIT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcSkbeditBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+    }
+}
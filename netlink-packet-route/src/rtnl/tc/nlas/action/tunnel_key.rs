@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::Ipv4Addr;
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_ipv4, parse_ipv6, parse_u16_be, parse_u32_be, parse_u8},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "tunnel_key";
+
+/// Options of the tunnel_key action, which sets or releases the metadata
+/// used by the encapsulating devices (vxlan, geneve...) in collect metadata
+/// mode
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TunnelKeyAction {
+    pub nlas: Vec<TunnelKeyActionNla>,
+}
+
+impl Emitable for TunnelKeyAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TunnelKeyAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid tunnel_key action nla")?;
+            nlas.push(TunnelKeyActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TunnelKeyActionNla {
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Parms(TcTunnelKey),
+    EncIpv4Src(Ipv4Addr),
+    EncIpv4Dst(Ipv4Addr),
+    EncIpv6Src([u8; 16]),
+    EncIpv6Dst([u8; 16]),
+    /// Tunnel id, such as the VXLAN network identifier
+    EncKeyId(u32),
+    /// Destination UDP port of the tunnel. It is in network byte order on the
+    /// wire.
+    EncDstPort(u16),
+    /// Do not compute the UDP checksum of the outer header
+    NoCsum(bool),
+    EncTos(u8),
+    EncTtl(u8),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for TunnelKeyActionNla {
+    fn value_len(&self) -> usize {
+        use self::TunnelKeyActionNla::*;
+        match self {
+            Tm(_) => TCF_LEN,
+            Parms(_) => TC_TUNNEL_KEY_LEN,
+            EncIpv4Src(_) | EncIpv4Dst(_) | EncKeyId(_) => 4,
+            EncIpv6Src(_) | EncIpv6Dst(_) => 16,
+            EncDstPort(_) => 2,
+            NoCsum(_) | EncTos(_) | EncTtl(_) => 1,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::TunnelKeyActionNla::*;
+        match self {
+            Tm(ref tm) => tm.emit(buffer),
+            Parms(ref parms) => parms.emit(buffer),
+            EncIpv4Src(ref address) | EncIpv4Dst(ref address) => {
+                buffer.copy_from_slice(&address.octets())
+            }
+            EncIpv6Src(ref address) | EncIpv6Dst(ref address) => {
+                buffer.copy_from_slice(&address[..])
+            }
+            EncKeyId(value) => BigEndian::write_u32(buffer, *value),
+            EncDstPort(value) => BigEndian::write_u16(buffer, *value),
+            NoCsum(value) => buffer[0] = *value as u8,
+            EncTos(value) | EncTtl(value) => buffer[0] = *value,
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::TunnelKeyActionNla::*;
+        match self {
+            Tm(_) => TCA_TUNNEL_KEY_TM,
+            Parms(_) => TCA_TUNNEL_KEY_PARMS,
+            EncIpv4Src(_) => TCA_TUNNEL_KEY_ENC_IPV4_SRC,
+            EncIpv4Dst(_) => TCA_TUNNEL_KEY_ENC_IPV4_DST,
+            EncIpv6Src(_) => TCA_TUNNEL_KEY_ENC_IPV6_SRC,
+            EncIpv6Dst(_) => TCA_TUNNEL_KEY_ENC_IPV6_DST,
+            EncKeyId(_) => TCA_TUNNEL_KEY_ENC_KEY_ID,
+            EncDstPort(_) => TCA_TUNNEL_KEY_ENC_DST_PORT,
+            NoCsum(_) => TCA_TUNNEL_KEY_NO_CSUM,
+            EncTos(_) => TCA_TUNNEL_KEY_ENC_TOS,
+            EncTtl(_) => TCA_TUNNEL_KEY_ENC_TTL,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TunnelKeyActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::TunnelKeyActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_TUNNEL_KEY_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_TUNNEL_KEY_TM value")?),
+            TCA_TUNNEL_KEY_PARMS => Parms(
+                TcTunnelKey::parse(&TcTunnelKeyBuffer::new_checked(payload)?)
+                    .context("invalid TCA_TUNNEL_KEY_PARMS value")?,
+            ),
+            TCA_TUNNEL_KEY_ENC_IPV4_SRC => EncIpv4Src(
+                parse_ipv4(payload).context("invalid TCA_TUNNEL_KEY_ENC_IPV4_SRC value")?,
+            ),
+            TCA_TUNNEL_KEY_ENC_IPV4_DST => EncIpv4Dst(
+                parse_ipv4(payload).context("invalid TCA_TUNNEL_KEY_ENC_IPV4_DST value")?,
+            ),
+            TCA_TUNNEL_KEY_ENC_IPV6_SRC => EncIpv6Src(
+                parse_ipv6(payload).context("invalid TCA_TUNNEL_KEY_ENC_IPV6_SRC value")?,
+            ),
+            TCA_TUNNEL_KEY_ENC_IPV6_DST => EncIpv6Dst(
+                parse_ipv6(payload).context("invalid TCA_TUNNEL_KEY_ENC_IPV6_DST value")?,
+            ),
+            TCA_TUNNEL_KEY_ENC_KEY_ID => {
+                EncKeyId(parse_u32_be(payload).context("invalid TCA_TUNNEL_KEY_ENC_KEY_ID value")?)
+            }
+            TCA_TUNNEL_KEY_ENC_DST_PORT => EncDstPort(
+                parse_u16_be(payload).context("invalid TCA_TUNNEL_KEY_ENC_DST_PORT value")?,
+            ),
+            TCA_TUNNEL_KEY_NO_CSUM => {
+                NoCsum(parse_u8(payload).context("invalid TCA_TUNNEL_KEY_NO_CSUM value")? != 0)
+            }
+            TCA_TUNNEL_KEY_ENC_TOS => {
+                EncTos(parse_u8(payload).context("invalid TCA_TUNNEL_KEY_ENC_TOS value")?)
+            }
+            TCA_TUNNEL_KEY_ENC_TTL => {
+                EncTtl(parse_u8(payload).context("invalid TCA_TUNNEL_KEY_ENC_TTL value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid tunnel_key action nla")?),
+        })
+    }
+}
+
+/// Tunnel key parameters (`struct tc_tunnel_key`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcTunnelKey {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the metadata is handled
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+    /// `TCA_TUNNEL_KEY_ACT_SET` or `TCA_TUNNEL_KEY_ACT_RELEASE`
+    pub t_action: i32,
+}
+
+pub const TC_TUNNEL_KEY_LEN: usize = 24;
+
+buffer!(TcTunnelKeyBuffer(TC_TUNNEL_KEY_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+    t_action: (i32, 20..24),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcTunnelKeyBuffer<T>> for TcTunnelKey {
+    fn parse(buf: &TcTunnelKeyBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+            t_action: buf.t_action(),
+        })
+    }
+}
+
+impl Emitable for TcTunnelKey {
+    fn buffer_len(&self) -> usize {
+        TC_TUNNEL_KEY_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcTunnelKeyBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+        buffer.set_t_action(self.t_action);
+    }
+}
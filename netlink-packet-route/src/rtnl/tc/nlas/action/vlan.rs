@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_mac, parse_u16, parse_u16_be, parse_u8},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{Tcf, TcfBuffer, TCF_LEN};
+
+pub const KIND: &str = "vlan";
+
+/// Options of the vlan action, which pops, pushes or modifies a VLAN tag
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VlanAction {
+    pub nlas: Vec<VlanActionNla>,
+}
+
+impl Emitable for VlanAction {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VlanAction {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid vlan action nla")?;
+            nlas.push(VlanActionNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VlanActionNla {
+    /// Times of the action, set by the kernel
+    Tm(Tcf),
+    Parms(TcVlan),
+    /// VLAN id of the pushed or modified tag
+    PushVlanId(u16),
+    /// Protocol of the pushed or modified tag, `ETH_P_8021Q` (0x8100) or
+    /// `ETH_P_8021AD` (0x88a8). It is in network byte order on the wire.
+    PushVlanProtocol(u16),
+    /// Priority of the pushed or modified tag
+    PushVlanPriority(u8),
+    /// Destination address of the pushed Ethernet header
+    PushEthDst([u8; 6]),
+    /// Source address of the pushed Ethernet header
+    PushEthSrc([u8; 6]),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for VlanActionNla {
+    fn value_len(&self) -> usize {
+        use self::VlanActionNla::*;
+        match self {
+            Tm(_) => TCF_LEN,
+            Parms(_) => TC_VLAN_LEN,
+            PushVlanId(_) | PushVlanProtocol(_) => 2,
+            PushVlanPriority(_) => 1,
+            PushEthDst(_) | PushEthSrc(_) => 6,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::VlanActionNla::*;
+        match self {
+            Tm(ref tm) => tm.emit(buffer),
+            Parms(ref parms) => parms.emit(buffer),
+            PushVlanId(value) => NativeEndian::write_u16(buffer, *value),
+            PushVlanProtocol(value) => BigEndian::write_u16(buffer, *value),
+            PushVlanPriority(value) => buffer[0] = *value,
+            PushEthDst(ref address) | PushEthSrc(ref address) => {
+                buffer.copy_from_slice(&address[..])
+            }
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::VlanActionNla::*;
+        match self {
+            Tm(_) => TCA_VLAN_TM,
+            Parms(_) => TCA_VLAN_PARMS,
+            PushVlanId(_) => TCA_VLAN_PUSH_VLAN_ID,
+            PushVlanProtocol(_) => TCA_VLAN_PUSH_VLAN_PROTOCOL,
+            PushVlanPriority(_) => TCA_VLAN_PUSH_VLAN_PRIORITY,
+            PushEthDst(_) => TCA_VLAN_PUSH_ETH_DST,
+            PushEthSrc(_) => TCA_VLAN_PUSH_ETH_SRC,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VlanActionNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::VlanActionNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_VLAN_TM => Tm(Tcf::parse(&TcfBuffer::new_checked(payload)?)
+                .context("invalid TCA_VLAN_TM value")?),
+            TCA_VLAN_PARMS => Parms(
+                TcVlan::parse(&TcVlanBuffer::new_checked(payload)?)
+                    .context("invalid TCA_VLAN_PARMS value")?,
+            ),
+            TCA_VLAN_PUSH_VLAN_ID => {
+                PushVlanId(parse_u16(payload).context("invalid TCA_VLAN_PUSH_VLAN_ID value")?)
+            }
+            TCA_VLAN_PUSH_VLAN_PROTOCOL => PushVlanProtocol(
+                parse_u16_be(payload).context("invalid TCA_VLAN_PUSH_VLAN_PROTOCOL value")?,
+            ),
+            TCA_VLAN_PUSH_VLAN_PRIORITY => PushVlanPriority(
+                parse_u8(payload).context("invalid TCA_VLAN_PUSH_VLAN_PRIORITY value")?,
+            ),
+            TCA_VLAN_PUSH_ETH_DST => {
+                PushEthDst(parse_mac(payload).context("invalid TCA_VLAN_PUSH_ETH_DST value")?)
+            }
+            TCA_VLAN_PUSH_ETH_SRC => {
+                PushEthSrc(parse_mac(payload).context("invalid TCA_VLAN_PUSH_ETH_SRC value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid vlan action nla")?),
+        })
+    }
+}
+
+/// Vlan parameters (`struct tc_vlan`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcVlan {
+    pub index: u32,
+    pub capab: u32,
+    /// `TC_ACT_*` verdict returned once the tag is handled
+    pub action: i32,
+    /// Reference count, set by the kernel
+    pub refcnt: i32,
+    /// Number of filters bound to the action, set by the kernel
+    pub bindcnt: i32,
+    /// One of the `TCA_VLAN_ACT_*` operations
+    pub v_action: i32,
+}
+
+pub const TC_VLAN_LEN: usize = 24;
+
+buffer!(TcVlanBuffer(TC_VLAN_LEN) {
+    index: (u32, 0..4),
+    capab: (u32, 4..8),
+    action: (i32, 8..12),
+    refcnt: (i32, 12..16),
+    bindcnt: (i32, 16..20),
+    v_action: (i32, 20..24),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcVlanBuffer<T>> for TcVlan {
+    fn parse(buf: &TcVlanBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            index: buf.index(),
+            capab: buf.capab(),
+            action: buf.action(),
+            refcnt: buf.refcnt(),
+            bindcnt: buf.bindcnt(),
+            v_action: buf.v_action(),
+        })
+    }
+}
+
+impl Emitable for TcVlan {
+    fn buffer_len(&self) -> usize {
+        TC_VLAN_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcVlanBuffer::new(buffer);
+        buffer.set_index(self.index);
+        buffer.set_capab(self.capab);
+        buffer.set_action(self.action);
+        buffer.set_refcnt(self.refcnt);
+        buffer.set_bindcnt(self.bindcnt);
+        buffer.set_v_action(self.v_action);
+    }
+}
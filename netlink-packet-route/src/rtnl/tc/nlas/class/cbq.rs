@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    nlas::{tc::qdisc::cbq::CbqNla, NlaBuffer, NlasIterator},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "cbq";
+
+/// Options of a CBQ (Class Based Queueing) class
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CbqClass {
+    pub nlas: Vec<CbqNla>,
+}
+
+impl Emitable for CbqClass {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CbqClass {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid cbq class nla")?;
+            nlas.push(CbqNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
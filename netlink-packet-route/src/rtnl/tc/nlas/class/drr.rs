@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "drr";
+
+/// Options of a DRR (Deficit Round Robin) class
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrrClass {
+    pub nlas: Vec<DrrClassNla>,
+}
+
+impl Emitable for DrrClass {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for DrrClass {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid drr class nla")?;
+            nlas.push(DrrClassNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrrClassNla {
+    /// Number of bytes the class can dequeue per round
+    Quantum(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for DrrClassNla {
+    fn value_len(&self) -> usize {
+        use self::DrrClassNla::*;
+        match self {
+            Quantum(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::DrrClassNla::*;
+        match self {
+            Quantum(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::DrrClassNla::*;
+        match self {
+            Quantum(_) => TCA_DRR_QUANTUM,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for DrrClassNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::DrrClassNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_DRR_QUANTUM => {
+                Quantum(parse_u32(payload).context("invalid TCA_DRR_QUANTUM value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid drr class nla")?),
+        })
+    }
+}
+
+/// DRR class statistics (`struct tc_drr_stats`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrrXStats {
+    /// Number of bytes the class can still dequeue in the current round
+    pub deficit: u32,
+}
+
+pub const DRR_XSTATS_LEN: usize = 4;
+
+buffer!(DrrXStatsBuffer(DRR_XSTATS_LEN) {
+    deficit: (u32, 0..4),
+});
+
+impl<T: AsRef<[u8]>> Parseable<DrrXStatsBuffer<T>> for DrrXStats {
+    fn parse(buf: &DrrXStatsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            deficit: buf.deficit(),
+        })
+    }
+}
+
+impl Emitable for DrrXStats {
+    fn buffer_len(&self) -> usize {
+        DRR_XSTATS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = DrrXStatsBuffer::new(buffer);
+        buffer.set_deficit(self.deficit);
+    }
+}
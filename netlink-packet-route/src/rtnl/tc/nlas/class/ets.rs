@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "ets";
+
+/// Options of an ETS (Enhanced Transmission Selection) class, i.e. of one
+/// of the bands of an ETS qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EtsClass {
+    pub nlas: Vec<EtsClassNla>,
+}
+
+impl Emitable for EtsClass {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for EtsClass {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid ets class nla")?;
+            nlas.push(EtsClassNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EtsClassNla {
+    /// Quantum of the band, in bytes. Strict priority bands do not have one.
+    QuantaBand(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for EtsClassNla {
+    fn value_len(&self) -> usize {
+        use self::EtsClassNla::*;
+        match self {
+            QuantaBand(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EtsClassNla::*;
+        match self {
+            QuantaBand(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EtsClassNla::*;
+        match self {
+            QuantaBand(_) => TCA_ETS_QUANTA_BAND,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for EtsClassNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::EtsClassNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_ETS_QUANTA_BAND => {
+                QuantaBand(parse_u32(payload).context("invalid TCA_ETS_QUANTA_BAND value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid ets class nla")?),
+        })
+    }
+}
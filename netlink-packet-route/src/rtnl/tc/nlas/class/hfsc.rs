@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "hfsc";
+
+/// Options of an HFSC (Hierarchical Fair Service Curve) class. A class can
+/// have any combination of service curves, a class without an upper-limit
+/// curve is not capped.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HfscClass {
+    pub nlas: Vec<HfscClassNla>,
+}
+
+impl Emitable for HfscClass {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for HfscClass {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid hfsc class nla")?;
+            nlas.push(HfscClassNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HfscClassNla {
+    /// Real-time service curve
+    Rsc(ServiceCurve),
+    /// Link-sharing service curve
+    Fsc(ServiceCurve),
+    /// Upper-limit service curve
+    Usc(ServiceCurve),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for HfscClassNla {
+    fn value_len(&self) -> usize {
+        use self::HfscClassNla::*;
+        match self {
+            Rsc(_) | Fsc(_) | Usc(_) => SERVICE_CURVE_LEN,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::HfscClassNla::*;
+        match self {
+            Rsc(ref curve) | Fsc(ref curve) | Usc(ref curve) => curve.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::HfscClassNla::*;
+        match self {
+            Rsc(_) => TCA_HFSC_RSC,
+            Fsc(_) => TCA_HFSC_FSC,
+            Usc(_) => TCA_HFSC_USC,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for HfscClassNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::HfscClassNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_HFSC_RSC => Rsc(
+                ServiceCurve::parse(&ServiceCurveBuffer::new_checked(payload)?)
+                    .context("invalid TCA_HFSC_RSC value")?,
+            ),
+            TCA_HFSC_FSC => Fsc(
+                ServiceCurve::parse(&ServiceCurveBuffer::new_checked(payload)?)
+                    .context("invalid TCA_HFSC_FSC value")?,
+            ),
+            TCA_HFSC_USC => Usc(
+                ServiceCurve::parse(&ServiceCurveBuffer::new_checked(payload)?)
+                    .context("invalid TCA_HFSC_USC value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid hfsc class nla")?),
+        })
+    }
+}
+
+/// Two-piece linear service curve (`struct tc_service_curve`): a slope of
+/// `m1` for the first `d` microseconds, then a slope of `m2`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ServiceCurve {
+    /// Slope of the first segment, in bytes per second
+    pub m1: u32,
+    /// Length of the first segment, in microseconds
+    pub d: u32,
+    /// Slope of the second segment, in bytes per second
+    pub m2: u32,
+}
+
+pub const SERVICE_CURVE_LEN: usize = 12;
+
+buffer!(ServiceCurveBuffer(SERVICE_CURVE_LEN) {
+    m1: (u32, 0..4),
+    d: (u32, 4..8),
+    m2: (u32, 8..12),
+});
+
+impl<T: AsRef<[u8]>> Parseable<ServiceCurveBuffer<T>> for ServiceCurve {
+    fn parse(buf: &ServiceCurveBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            m1: buf.m1(),
+            d: buf.d(),
+            m2: buf.m2(),
+        })
+    }
+}
+
+impl Emitable for ServiceCurve {
+    fn buffer_len(&self) -> usize {
+        SERVICE_CURVE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = ServiceCurveBuffer::new(buffer);
+        buffer.set_m1(self.m1);
+        buffer.set_d(self.d);
+        buffer.set_m2(self.m2);
+    }
+}
@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u64,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::super::{emit_u32_list, parse_u32_list, TcRateSpec, TcRateSpecBuffer};
+
+pub const KIND: &str = "htb";
+
+/// Options of an HTB (Hierarchical Token Bucket) class
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HtbClass {
+    pub nlas: Vec<HtbClassNla>,
+}
+
+impl Emitable for HtbClass {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for HtbClass {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid htb class nla")?;
+            nlas.push(HtbClassNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HtbClassNla {
+    /// Parameters of the class
+    Parms(TcaHtbOpt),
+    /// Rate table of `ceil`, see [`TcRateSpec::rate_table`]
+    Ctab(Vec<u32>),
+    /// Rate table of `rate`, see [`TcRateSpec::rate_table`]
+    Rtab(Vec<u32>),
+    /// Rate in bytes per second, for rates that do not fit in 32 bits
+    Rate64(u64),
+    /// Ceil in bytes per second, for rates that do not fit in 32 bits
+    Ceil64(u64),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for HtbClassNla {
+    fn value_len(&self) -> usize {
+        use self::HtbClassNla::*;
+        match self {
+            Parms(_) => TCA_HTB_OPT_LEN,
+            Ctab(ref table) | Rtab(ref table) => table.len() * 4,
+            Rate64(_) | Ceil64(_) => 8,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::HtbClassNla::*;
+        match self {
+            Parms(ref opt) => opt.emit(buffer),
+            Ctab(ref table) | Rtab(ref table) => emit_u32_list(buffer, table),
+            Rate64(ref value) | Ceil64(ref value) => NativeEndian::write_u64(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::HtbClassNla::*;
+        match self {
+            Parms(_) => TCA_HTB_PARMS,
+            Ctab(_) => TCA_HTB_CTAB,
+            Rtab(_) => TCA_HTB_RTAB,
+            Rate64(_) => TCA_HTB_RATE64,
+            Ceil64(_) => TCA_HTB_CEIL64,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for HtbClassNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::HtbClassNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_HTB_PARMS => Parms(
+                TcaHtbOpt::parse(&TcaHtbOptBuffer::new_checked(payload)?)
+                    .context("invalid TCA_HTB_PARMS value")?,
+            ),
+            TCA_HTB_CTAB => Ctab(parse_u32_list(payload).context("invalid TCA_HTB_CTAB value")?),
+            TCA_HTB_RTAB => Rtab(parse_u32_list(payload).context("invalid TCA_HTB_RTAB value")?),
+            TCA_HTB_RATE64 => Rate64(parse_u64(payload).context("invalid TCA_HTB_RATE64 value")?),
+            TCA_HTB_CEIL64 => Ceil64(parse_u64(payload).context("invalid TCA_HTB_CEIL64 value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid htb class nla")?),
+        })
+    }
+}
+
+/// HTB class parameters (`struct tc_htb_opt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcaHtbOpt {
+    /// Guaranteed rate of the class
+    pub rate: TcRateSpec,
+    /// Maximum rate of the class, when borrowing from its parent
+    pub ceil: TcRateSpec,
+    /// Size of the `rate` bucket, in scheduler ticks
+    pub buffer: u32,
+    /// Size of the `ceil` bucket, in scheduler ticks
+    pub cbuffer: u32,
+    /// Number of bytes the class can dequeue per round, when borrowing
+    pub quantum: u32,
+    /// Level of the class in the hierarchy, set by the kernel
+    pub level: u32,
+    /// Priority of the class, lower values are served first
+    pub prio: u32,
+}
+
+pub const TCA_HTB_OPT_LEN: usize = 44;
+
+buffer!(TcaHtbOptBuffer(TCA_HTB_OPT_LEN) {
+    rate: (slice, 0..12),
+    ceil: (slice, 12..24),
+    buffer: (u32, 24..28),
+    cbuffer: (u32, 28..32),
+    quantum: (u32, 32..36),
+    level: (u32, 36..40),
+    prio: (u32, 40..44),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcaHtbOptBuffer<&'a T>> for TcaHtbOpt {
+    fn parse(buf: &TcaHtbOptBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            rate: TcRateSpec::parse(&TcRateSpecBuffer::new(buf.rate()))?,
+            ceil: TcRateSpec::parse(&TcRateSpecBuffer::new(buf.ceil()))?,
+            buffer: buf.buffer(),
+            cbuffer: buf.cbuffer(),
+            quantum: buf.quantum(),
+            level: buf.level(),
+            prio: buf.prio(),
+        })
+    }
+}
+
+impl Emitable for TcaHtbOpt {
+    fn buffer_len(&self) -> usize {
+        TCA_HTB_OPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcaHtbOptBuffer::new(buffer);
+        self.rate.emit(buffer.rate_mut());
+        self.ceil.emit(buffer.ceil_mut());
+        buffer.set_buffer(self.buffer);
+        buffer.set_cbuffer(self.cbuffer);
+        buffer.set_quantum(self.quantum);
+        buffer.set_level(self.level);
+        buffer.set_prio(self.prio);
+    }
+}
+
+/// HTB class statistics (`struct tc_htb_xstats`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HtbXStats {
+    /// Number of packets the class sent with tokens borrowed from its parent
+    pub lends: u32,
+    /// Number of times the class borrowed tokens from its ancestors
+    pub borrows: u32,
+    /// Number of packets larger than the MTU of the class
+    pub giants: u32,
+    /// Remaining tokens for the rate, in ticks
+    pub tokens: i32,
+    /// Remaining tokens for the ceil rate, in ticks
+    pub ctokens: i32,
+}
+
+pub const HTB_XSTATS_LEN: usize = 20;
+
+buffer!(HtbXStatsBuffer(HTB_XSTATS_LEN) {
+    lends: (u32, 0..4),
+    borrows: (u32, 4..8),
+    giants: (u32, 8..12),
+    tokens: (i32, 12..16),
+    ctokens: (i32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<HtbXStatsBuffer<T>> for HtbXStats {
+    fn parse(buf: &HtbXStatsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            lends: buf.lends(),
+            borrows: buf.borrows(),
+            giants: buf.giants(),
+            tokens: buf.tokens(),
+            ctokens: buf.ctokens(),
+        })
+    }
+}
+
+impl Emitable for HtbXStats {
+    fn buffer_len(&self) -> usize {
+        HTB_XSTATS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = HtbXStatsBuffer::new(buffer);
+        buffer.set_lends(self.lends);
+        buffer.set_borrows(self.borrows);
+        buffer.set_giants(self.giants);
+        buffer.set_tokens(self.tokens);
+        buffer.set_ctokens(self.ctokens);
+    }
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MIT
+
+pub mod drr;
+pub use self::drr::DrrClass;
+
+pub mod ets;
+pub use self::ets::EtsClass;
+
+pub mod qfq;
+pub use self::qfq::QfqClass;
+
+pub mod htb;
+pub use self::htb::HtbClass;
+
+pub mod hfsc;
+pub use self::hfsc::HfscClass;
+
+pub mod cbq;
+pub use self::cbq::CbqClass;
+
+use anyhow::Context;
+
+use crate::{
+    nlas::NlaBuffer,
+    traits::{Emitable, Parseable, ParseableParametrized},
+    DecodeError,
+};
+
+/// Class specific options, carried by the `TCA_OPTIONS` attribute of
+/// `RTM_*TCLASS` messages.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Class {
+    Drr(DrrClass),
+    Ets(EtsClass),
+    Qfq(QfqClass),
+    Htb(HtbClass),
+    Hfsc(HfscClass),
+    Cbq(CbqClass),
+    Other(#[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_base64"))] Vec<u8>),
+}
+
+impl Class {
+    /// Create empty options for the given class kind.
+    /// Unknown kinds give empty `Other` options rather than an error.
+    pub fn new(kind: &str) -> Self {
+        match kind {
+            drr::KIND => Class::Drr(DrrClass::default()),
+            ets::KIND => Class::Ets(EtsClass::default()),
+            qfq::KIND => Class::Qfq(QfqClass::default()),
+            htb::KIND => Class::Htb(HtbClass::default()),
+            hfsc::KIND => Class::Hfsc(HfscClass::default()),
+            cbq::KIND => Class::Cbq(CbqClass::default()),
+            _ => Class::Other(Vec::new()),
+        }
+    }
+}
+
+impl Emitable for Class {
+    fn buffer_len(&self) -> usize {
+        match self {
+            Class::Drr(ref drr) => drr.buffer_len(),
+            Class::Ets(ref ets) => ets.buffer_len(),
+            Class::Qfq(ref qfq) => qfq.buffer_len(),
+            Class::Htb(ref htb) => htb.buffer_len(),
+            Class::Hfsc(ref hfsc) => hfsc.buffer_len(),
+            Class::Cbq(ref cbq) => cbq.buffer_len(),
+            Class::Other(ref bytes) => bytes.len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            Class::Drr(ref drr) => drr.emit(buffer),
+            Class::Ets(ref ets) => ets.emit(buffer),
+            Class::Qfq(ref qfq) => qfq.emit(buffer),
+            Class::Htb(ref htb) => htb.emit(buffer),
+            Class::Hfsc(ref hfsc) => hfsc.emit(buffer),
+            Class::Cbq(ref cbq) => cbq.emit(buffer),
+            Class::Other(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, &str> for Class {
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, kind: &str) -> Result<Self, DecodeError> {
+        Ok(match kind {
+            drr::KIND => {
+                Class::Drr(DrrClass::parse(buf).context("failed to parse drr class options")?)
+            }
+            ets::KIND => {
+                Class::Ets(EtsClass::parse(buf).context("failed to parse ets class options")?)
+            }
+            qfq::KIND => {
+                Class::Qfq(QfqClass::parse(buf).context("failed to parse qfq class options")?)
+            }
+            htb::KIND => {
+                Class::Htb(HtbClass::parse(buf).context("failed to parse htb class options")?)
+            }
+            hfsc::KIND => {
+                Class::Hfsc(HfscClass::parse(buf).context("failed to parse hfsc class options")?)
+            }
+            cbq::KIND => {
+                Class::Cbq(CbqClass::parse(buf).context("failed to parse cbq class options")?)
+            }
+            _ => Class::Other(buf.value().to_vec()),
+        })
+    }
+}
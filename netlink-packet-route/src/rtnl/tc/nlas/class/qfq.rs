@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "qfq";
+
+/// Options of a QFQ (Quick Fair Queueing) class
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QfqClass {
+    pub nlas: Vec<QfqClassNla>,
+}
+
+impl Emitable for QfqClass {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for QfqClass {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid qfq class nla")?;
+            nlas.push(QfqClassNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QfqClassNla {
+    /// Weight of the class, the sum of the weights of all the classes is
+    /// limited by the kernel
+    Weight(u32),
+    /// Size of the largest packet of the class, in bytes
+    Lmax(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for QfqClassNla {
+    fn value_len(&self) -> usize {
+        use self::QfqClassNla::*;
+        match self {
+            Weight(_) | Lmax(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::QfqClassNla::*;
+        match self {
+            Weight(ref value) | Lmax(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::QfqClassNla::*;
+        match self {
+            Weight(_) => TCA_QFQ_WEIGHT,
+            Lmax(_) => TCA_QFQ_LMAX,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for QfqClassNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::QfqClassNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_QFQ_WEIGHT => Weight(parse_u32(payload).context("invalid TCA_QFQ_WEIGHT value")?),
+            TCA_QFQ_LMAX => Lmax(parse_u32(payload).context("invalid TCA_QFQ_LMAX value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid qfq class nla")?),
+        })
+    }
+}
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{
+        self,
+        tc::action::{parse_actions, PoliceAction, TcAction},
+        DefaultNla,
+        NlaBuffer,
+        NlasIterator,
+    },
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "basic";
+
+/// Options of the basic filter
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BasicFilter {
+    pub nlas: Vec<BasicFilterNla>,
+}
+
+impl Emitable for BasicFilter {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for BasicFilter {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid basic filter nla")?;
+            nlas.push(BasicFilterNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BasicFilterNla {
+    /// Class the matching packets are sent to
+    ClassId(u32),
+    // FIXME: parse the ematch tree (TCA_EMATCH_TREE_HDR and
+    // TCA_EMATCH_TREE_LIST) instead of keeping the raw attributes
+    /// Extended match expression
+    Ematches(Vec<u8>),
+    /// Actions run on the matching packets
+    Act(Vec<TcAction>),
+    /// Legacy policer
+    Police(PoliceAction),
+    Pcnt(TcBasicPcnt),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for BasicFilterNla {
+    fn value_len(&self) -> usize {
+        use self::BasicFilterNla::*;
+        match self {
+            ClassId(_) => 4,
+            Ematches(ref bytes) => bytes.len(),
+            Pcnt(ref pcnt) => pcnt.buffer_len(),
+            Act(ref actions) => actions.as_slice().buffer_len(),
+            Police(ref police) => police.buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::BasicFilterNla::*;
+        match self {
+            ClassId(value) => NativeEndian::write_u32(buffer, *value),
+            Ematches(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Pcnt(ref pcnt) => pcnt.emit(buffer),
+            Act(ref actions) => actions.as_slice().emit(buffer),
+            Police(ref police) => police.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::BasicFilterNla::*;
+        match self {
+            ClassId(_) => TCA_BASIC_CLASSID,
+            Ematches(_) => TCA_BASIC_EMATCHES,
+            Act(_) => TCA_BASIC_ACT,
+            Police(_) => TCA_BASIC_POLICE,
+            Pcnt(_) => TCA_BASIC_PCNT,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for BasicFilterNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::BasicFilterNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_BASIC_CLASSID => {
+                ClassId(parse_u32(payload).context("invalid TCA_BASIC_CLASSID value")?)
+            }
+            TCA_BASIC_EMATCHES => Ematches(payload.to_vec()),
+            TCA_BASIC_ACT => Act(parse_actions(payload).context("invalid TCA_BASIC_ACT value")?),
+            TCA_BASIC_POLICE => {
+                Police(PoliceAction::parse(buf).context("invalid TCA_BASIC_POLICE value")?)
+            }
+            TCA_BASIC_PCNT => Pcnt(
+                TcBasicPcnt::parse(&TcBasicPcntBuffer::new_checked(payload)?)
+                    .context("invalid TCA_BASIC_PCNT value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid basic filter nla")?),
+        })
+    }
+}
+
+/// Performance counters of a basic filter (`struct tc_basic_pcnt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcBasicPcnt {
+    /// Number of packets the filter was run on
+    pub rcnt: u64,
+    /// Number of packets that matched
+    pub rhit: u64,
+}
+
+pub const TC_BASIC_PCNT_LEN: usize = 16;
+
+buffer!(TcBasicPcntBuffer(TC_BASIC_PCNT_LEN) {
+    rcnt: (u64, 0..8),
+    rhit: (u64, 8..16),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcBasicPcntBuffer<T>> for TcBasicPcnt {
+    fn parse(buf: &TcBasicPcntBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            rcnt: buf.rcnt(),
+            rhit: buf.rhit(),
+        })
+    }
+}
+
+impl Emitable for TcBasicPcnt {
+    fn buffer_len(&self) -> usize {
+        TC_BASIC_PCNT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcBasicPcntBuffer::new(buffer);
+        buffer.set_rcnt(self.rcnt);
+        buffer.set_rhit(self.rhit);
+    }
+}
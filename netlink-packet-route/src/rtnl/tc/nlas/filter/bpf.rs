@@ -0,0 +1,265 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{
+        self,
+        tc::action::{parse_actions, PoliceAction, TcAction},
+        DefaultNla,
+        NlaBuffer,
+        NlasIterator,
+    },
+    parsers::{parse_i32, parse_string, parse_u16, parse_u32},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "bpf";
+
+/// Options of the BPF filter. The program is either given as classic BPF
+/// bytecode with [`BpfFilterNla::OpsLen`] and [`BpfFilterNla::Ops`], or as
+/// the file descriptor of a loaded eBPF program with [`BpfFilterNla::Fd`],
+/// but not both.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BpfFilter {
+    pub nlas: Vec<BpfFilterNla>,
+}
+
+impl Emitable for BpfFilter {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for BpfFilter {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid bpf filter nla")?;
+            nlas.push(BpfFilterNla::parse(&nla)?);
+        }
+        let filter = Self { nlas };
+        filter.validate()?;
+        Ok(filter)
+    }
+}
+
+impl BpfFilter {
+    /// Check that the filter does not carry both a file descriptor and
+    /// bytecode, and that the bytecode length matches `TCA_BPF_OPS_LEN`.
+    pub fn validate(&self) -> Result<(), DecodeError> {
+        let mut fd = false;
+        let mut ops_len = None;
+        let mut ops = None;
+        for nla in &self.nlas {
+            match nla {
+                BpfFilterNla::Fd(_) => fd = true,
+                BpfFilterNla::OpsLen(len) => ops_len = Some(*len as usize),
+                BpfFilterNla::Ops(ref insns) => ops = Some(insns.len()),
+                _ => {}
+            }
+        }
+        if fd && (ops.is_some() || ops_len.is_some()) {
+            return Err("TCA_BPF_FD and TCA_BPF_OPS are mutually exclusive".into());
+        }
+        if ops_len != ops {
+            return Err(format!(
+                "TCA_BPF_OPS_LEN ({:?}) does not match the number of instructions ({:?})",
+                ops_len, ops
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BpfFilterNla {
+    /// Actions run on the matching packets
+    Act(Vec<TcAction>),
+    /// Legacy policer
+    Police(PoliceAction),
+    /// Class the matching packets are sent to
+    ClassId(u32),
+    /// Number of instructions of the classic BPF program
+    OpsLen(u16),
+    /// Classic BPF program
+    Ops(Vec<BpfInsn>),
+    /// File descriptor of the eBPF program
+    Fd(i32),
+    /// Name of the eBPF program
+    Name(String),
+    Flags(BpfFilterFlags),
+    /// Generic classifier flags, `TCA_CLS_FLAGS_*`
+    FlagsGen(u32),
+    /// Tag of the eBPF program, set by the kernel
+    Tag(Vec<u8>),
+    /// Id of the eBPF program, set by the kernel
+    Id(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for BpfFilterNla {
+    fn value_len(&self) -> usize {
+        use self::BpfFilterNla::*;
+        match self {
+            Tag(ref bytes) => bytes.len(),
+            ClassId(_) | Fd(_) | Flags(_) | FlagsGen(_) | Id(_) => 4,
+            OpsLen(_) => 2,
+            Ops(ref insns) => insns.len() * BPF_INSN_LEN,
+            Name(ref string) => string.len() + 1,
+            Act(ref actions) => actions.as_slice().buffer_len(),
+            Police(ref police) => police.buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::BpfFilterNla::*;
+        match self {
+            Tag(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            ClassId(value) | FlagsGen(value) | Id(value) => NativeEndian::write_u32(buffer, *value),
+            OpsLen(value) => NativeEndian::write_u16(buffer, *value),
+            Ops(ref insns) => {
+                for (insn, chunk) in insns.iter().zip(buffer.chunks_exact_mut(BPF_INSN_LEN)) {
+                    insn.emit(chunk);
+                }
+            }
+            Fd(value) => NativeEndian::write_i32(buffer, *value),
+            Name(ref string) => {
+                buffer[..string.len()].copy_from_slice(string.as_bytes());
+                buffer[string.len()] = 0;
+            }
+            Flags(flags) => NativeEndian::write_u32(buffer, flags.bits()),
+            Act(ref actions) => actions.as_slice().emit(buffer),
+            Police(ref police) => police.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::BpfFilterNla::*;
+        match self {
+            Act(_) => TCA_BPF_ACT,
+            Police(_) => TCA_BPF_POLICE,
+            ClassId(_) => TCA_BPF_CLASSID,
+            OpsLen(_) => TCA_BPF_OPS_LEN,
+            Ops(_) => TCA_BPF_OPS,
+            Fd(_) => TCA_BPF_FD,
+            Name(_) => TCA_BPF_NAME,
+            Flags(_) => TCA_BPF_FLAGS,
+            FlagsGen(_) => TCA_BPF_FLAGS_GEN,
+            Tag(_) => TCA_BPF_TAG,
+            Id(_) => TCA_BPF_ID,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for BpfFilterNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::BpfFilterNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_BPF_ACT => Act(parse_actions(payload).context("invalid TCA_BPF_ACT value")?),
+            TCA_BPF_POLICE => {
+                Police(PoliceAction::parse(buf).context("invalid TCA_BPF_POLICE value")?)
+            }
+            TCA_BPF_CLASSID => {
+                ClassId(parse_u32(payload).context("invalid TCA_BPF_CLASSID value")?)
+            }
+            TCA_BPF_OPS_LEN => OpsLen(parse_u16(payload).context("invalid TCA_BPF_OPS_LEN value")?),
+            TCA_BPF_OPS => Ops(parse_ops(payload).context("invalid TCA_BPF_OPS value")?),
+            TCA_BPF_FD => Fd(parse_i32(payload).context("invalid TCA_BPF_FD value")?),
+            TCA_BPF_NAME => Name(parse_string(payload).context("invalid TCA_BPF_NAME value")?),
+            TCA_BPF_FLAGS => Flags(BpfFilterFlags::from_bits_truncate(
+                parse_u32(payload).context("invalid TCA_BPF_FLAGS value")?,
+            )),
+            TCA_BPF_FLAGS_GEN => {
+                FlagsGen(parse_u32(payload).context("invalid TCA_BPF_FLAGS_GEN value")?)
+            }
+            TCA_BPF_TAG => Tag(payload.to_vec()),
+            TCA_BPF_ID => Id(parse_u32(payload).context("invalid TCA_BPF_ID value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid bpf filter nla")?),
+        })
+    }
+}
+
+fn parse_ops(payload: &[u8]) -> Result<Vec<BpfInsn>, DecodeError> {
+    let chunks = payload.chunks_exact(BPF_INSN_LEN);
+    if !chunks.remainder().is_empty() {
+        return Err(format!("invalid classic BPF program: {:?}", payload).into());
+    }
+    chunks
+        .map(|chunk| BpfInsn::parse(&BpfInsnBuffer::new(chunk)))
+        .collect()
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct BpfFilterFlags: u32 {
+        /// The program returns a TC action code instead of a class id
+        const ACT_DIRECT = TCA_BPF_FLAG_ACT_DIRECT;
+    }
+}
+
+impl Default for BpfFilterFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Classic BPF instruction (`struct sock_filter`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BpfInsn {
+    pub code: u16,
+    /// Offset of the next instruction if the condition is true
+    pub jt: u8,
+    /// Offset of the next instruction if the condition is false
+    pub jf: u8,
+    pub k: u32,
+}
+
+pub const BPF_INSN_LEN: usize = 8;
+
+buffer!(BpfInsnBuffer(BPF_INSN_LEN) {
+    code: (u16, 0..2),
+    jt: (u8, 2),
+    jf: (u8, 3),
+    k: (u32, 4..8),
+});
+
+impl<T: AsRef<[u8]>> Parseable<BpfInsnBuffer<T>> for BpfInsn {
+    fn parse(buf: &BpfInsnBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            code: buf.code(),
+            jt: buf.jt(),
+            jf: buf.jf(),
+            k: buf.k(),
+        })
+    }
+}
+
+impl Emitable for BpfInsn {
+    fn buffer_len(&self) -> usize {
+        BPF_INSN_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = BpfInsnBuffer::new(buffer);
+        buffer.set_code(self.code);
+        buffer.set_jt(self.jt);
+        buffer.set_jf(self.jf);
+        buffer.set_k(self.k);
+    }
+}
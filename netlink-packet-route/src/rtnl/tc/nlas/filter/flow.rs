@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{
+        self,
+        tc::action::{parse_actions, PoliceAction, TcAction},
+        DefaultNla,
+        NlaBuffer,
+        NlasIterator,
+    },
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "flow";
+
+/// Options of the flow filter, which maps packets to classes based on their
+/// header fields
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowFilter {
+    pub nlas: Vec<FlowFilterNla>,
+}
+
+impl Emitable for FlowFilter {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FlowFilter {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid flow filter nla")?;
+            nlas.push(FlowFilterNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlowFilterNla {
+    /// Fields the class is computed from
+    Keys(FlowKey),
+    Mode(FlowMode),
+    /// Class the computed value is added to
+    BaseClass(u32),
+    /// Right shift applied to the value, in map mode
+    Rshift(u32),
+    /// Value added to the value, in map mode
+    Addend(u32),
+    /// Mask ANDed with the value, in map mode
+    Mask(u32),
+    /// Value XORed with the value, in map mode
+    Xor(u32),
+    /// Number of classes the value is spread over
+    Divisor(u32),
+    /// Actions run on the matching packets
+    Act(Vec<TcAction>),
+    /// Legacy policer
+    Police(PoliceAction),
+    /// Extended match expression, kept as raw bytes
+    Ematches(Vec<u8>),
+    /// Period after which the hash is perturbed, in seconds
+    Perturb(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for FlowFilterNla {
+    fn value_len(&self) -> usize {
+        use self::FlowFilterNla::*;
+        match self {
+            Keys(_) | Mode(_) | BaseClass(_) | Rshift(_) | Addend(_) | Mask(_) | Xor(_)
+            | Divisor(_) | Perturb(_) => 4,
+            Ematches(ref bytes) => bytes.len(),
+            Act(ref actions) => actions.as_slice().buffer_len(),
+            Police(ref police) => police.buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::FlowFilterNla::*;
+        match self {
+            Keys(keys) => NativeEndian::write_u32(buffer, keys.bits()),
+            Mode(mode) => NativeEndian::write_u32(buffer, (*mode).into()),
+            BaseClass(value) | Rshift(value) | Addend(value) | Mask(value) | Xor(value)
+            | Divisor(value) | Perturb(value) => NativeEndian::write_u32(buffer, *value),
+            Ematches(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Act(ref actions) => actions.as_slice().emit(buffer),
+            Police(ref police) => police.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::FlowFilterNla::*;
+        match self {
+            Keys(_) => TCA_FLOW_KEYS,
+            Mode(_) => TCA_FLOW_MODE,
+            BaseClass(_) => TCA_FLOW_BASECLASS,
+            Rshift(_) => TCA_FLOW_RSHIFT,
+            Addend(_) => TCA_FLOW_ADDEND,
+            Mask(_) => TCA_FLOW_MASK,
+            Xor(_) => TCA_FLOW_XOR,
+            Divisor(_) => TCA_FLOW_DIVISOR,
+            Act(_) => TCA_FLOW_ACT,
+            Police(_) => TCA_FLOW_POLICE,
+            Ematches(_) => TCA_FLOW_EMATCHES,
+            Perturb(_) => TCA_FLOW_PERTURB,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FlowFilterNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::FlowFilterNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOW_KEYS => Keys(FlowKey::from_bits_truncate(
+                parse_u32(payload).context("invalid TCA_FLOW_KEYS value")?,
+            )),
+            TCA_FLOW_MODE => Mode(
+                parse_u32(payload)
+                    .context("invalid TCA_FLOW_MODE value")?
+                    .into(),
+            ),
+            TCA_FLOW_BASECLASS => {
+                BaseClass(parse_u32(payload).context("invalid TCA_FLOW_BASECLASS value")?)
+            }
+            TCA_FLOW_RSHIFT => Rshift(parse_u32(payload).context("invalid TCA_FLOW_RSHIFT value")?),
+            TCA_FLOW_ADDEND => Addend(parse_u32(payload).context("invalid TCA_FLOW_ADDEND value")?),
+            TCA_FLOW_MASK => Mask(parse_u32(payload).context("invalid TCA_FLOW_MASK value")?),
+            TCA_FLOW_XOR => Xor(parse_u32(payload).context("invalid TCA_FLOW_XOR value")?),
+            TCA_FLOW_DIVISOR => {
+                Divisor(parse_u32(payload).context("invalid TCA_FLOW_DIVISOR value")?)
+            }
+            TCA_FLOW_ACT => Act(parse_actions(payload).context("invalid TCA_FLOW_ACT value")?),
+            TCA_FLOW_POLICE => {
+                Police(PoliceAction::parse(buf).context("invalid TCA_FLOW_POLICE value")?)
+            }
+            TCA_FLOW_EMATCHES => Ematches(payload.to_vec()),
+            TCA_FLOW_PERTURB => {
+                Perturb(parse_u32(payload).context("invalid TCA_FLOW_PERTURB value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid flow filter nla")?),
+        })
+    }
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct FlowKey: u32 {
+        const SRC = 1 << FLOW_KEY_SRC;
+        const DST = 1 << FLOW_KEY_DST;
+        const PROTO = 1 << FLOW_KEY_PROTO;
+        const PROTO_SRC = 1 << FLOW_KEY_PROTO_SRC;
+        const PROTO_DST = 1 << FLOW_KEY_PROTO_DST;
+        const IIF = 1 << FLOW_KEY_IIF;
+        const PRIORITY = 1 << FLOW_KEY_PRIORITY;
+        const MARK = 1 << FLOW_KEY_MARK;
+        const NFCT = 1 << FLOW_KEY_NFCT;
+        const NFCT_SRC = 1 << FLOW_KEY_NFCT_SRC;
+        const NFCT_DST = 1 << FLOW_KEY_NFCT_DST;
+        const NFCT_PROTO_SRC = 1 << FLOW_KEY_NFCT_PROTO_SRC;
+        const NFCT_PROTO_DST = 1 << FLOW_KEY_NFCT_PROTO_DST;
+        const RTCLASSID = 1 << FLOW_KEY_RTCLASSID;
+        const SKUID = 1 << FLOW_KEY_SKUID;
+        const SKGID = 1 << FLOW_KEY_SKGID;
+        const VLAN_TAG = 1 << FLOW_KEY_VLAN_TAG;
+        const RXHASH = 1 << FLOW_KEY_RXHASH;
+    }
+}
+
+impl Default for FlowKey {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlowMode {
+    /// The class is computed from the value of a single key
+    Map,
+    /// The class is computed from a hash of the keys
+    Hash,
+    Other(u32),
+}
+
+impl From<u32> for FlowMode {
+    fn from(value: u32) -> Self {
+        use self::FlowMode::*;
+        match value {
+            FLOW_MODE_MAP => Map,
+            FLOW_MODE_HASH => Hash,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<FlowMode> for u32 {
+    fn from(value: FlowMode) -> Self {
+        use self::FlowMode::*;
+        match value {
+            Map => FLOW_MODE_MAP,
+            Hash => FLOW_MODE_HASH,
+            Other(other) => other,
+        }
+    }
+}
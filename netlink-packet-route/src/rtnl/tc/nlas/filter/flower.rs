@@ -0,0 +1,716 @@
+// SPDX-License-Identifier: MIT
+
+use std::net::Ipv4Addr;
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{
+        self,
+        tc::action::{ct::parse_labels, mpls::MPLS_LABEL_MASK, parse_actions, TcAction},
+        DefaultNla,
+        NlaBuffer,
+        NlasIterator,
+    },
+    parsers::{
+        parse_ipv4,
+        parse_ipv6,
+        parse_mac,
+        parse_string,
+        parse_u16,
+        parse_u16_be,
+        parse_u32,
+        parse_u32_be,
+        parse_u8,
+    },
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "flower";
+
+/// Options of the flower filter, which matches packets on the fields of
+/// their headers. Each key can be combined with a mask, a key without mask
+/// matches the field exactly.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowerFilter {
+    pub nlas: Vec<FlowerFilterNla>,
+}
+
+impl Emitable for FlowerFilter {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FlowerFilter {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid flower filter nla")?;
+            nlas.push(FlowerFilterNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+/// Flower filter attributes. Protocol numbers and ports are in host byte
+/// order, they are converted to network byte order on the wire.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlowerFilterNla {
+    /// Class the matching packets are sent to
+    ClassId(u32),
+    /// Only match packets received on this interface
+    Indev(String),
+    /// Actions run on the matching packets
+    Act(Vec<TcAction>),
+    /// `TCA_CLS_FLAGS_*` flags
+    Flags(u32),
+    /// Number of hardware devices the filter is offloaded to, set by the
+    /// kernel
+    InHwCount(u32),
+    KeyEthDst([u8; 6]),
+    KeyEthDstMask([u8; 6]),
+    KeyEthSrc([u8; 6]),
+    KeyEthSrcMask([u8; 6]),
+    /// `ETH_P_*` protocol, after the VLAN tags if [`KeyVlanEthType`] is
+    /// not used
+    ///
+    /// [`KeyVlanEthType`]: FlowerFilterNla::KeyVlanEthType
+    KeyEthType(u16),
+    /// `IPPROTO_*` protocol
+    KeyIpProto(u8),
+    KeyIpv4Src(Ipv4Addr),
+    KeyIpv4SrcMask(Ipv4Addr),
+    KeyIpv4Dst(Ipv4Addr),
+    KeyIpv4DstMask(Ipv4Addr),
+    KeyIpv6Src([u8; 16]),
+    KeyIpv6SrcMask([u8; 16]),
+    KeyIpv6Dst([u8; 16]),
+    KeyIpv6DstMask([u8; 16]),
+    KeyIcmpv6Type(u8),
+    KeyIcmpv6TypeMask(u8),
+    KeyIcmpv6Code(u8),
+    KeyIcmpv6CodeMask(u8),
+    /// Fragmentation flags of the IP packet
+    KeyFlags(FlowerKeyFlags),
+    KeyFlagsMask(FlowerKeyFlags),
+    KeyTcpSrc(u16),
+    KeyTcpSrcMask(u16),
+    KeyTcpDst(u16),
+    KeyTcpDstMask(u16),
+    KeyUdpSrc(u16),
+    KeyUdpSrcMask(u16),
+    KeyUdpDst(u16),
+    KeyUdpDstMask(u16),
+    KeyVlanId(u16),
+    KeyVlanPrio(u8),
+    /// `ETH_P_*` protocol carried by the VLAN tag
+    KeyVlanEthType(u16),
+    KeyIpTos(u8),
+    KeyIpTosMask(u8),
+    KeyIpTtl(u8),
+    KeyIpTtlMask(u8),
+    /// Conntrack state of the packet, set by the ct action
+    KeyCtState(FlowerCtState),
+    KeyCtStateMask(FlowerCtState),
+    KeyCtZone(u16),
+    KeyCtZoneMask(u16),
+    KeyCtMark(u32),
+    KeyCtMarkMask(u32),
+    KeyCtLabels([u8; 16]),
+    KeyCtLabelsMask([u8; 16]),
+    /// TTL of the outermost MPLS label stack entry
+    KeyMplsTtl(u8),
+    /// Bottom of stack bit of the outermost MPLS label stack entry
+    KeyMplsBos(u8),
+    /// Traffic class of the outermost MPLS label stack entry
+    KeyMplsTc(u8),
+    /// Label of the outermost MPLS label stack entry, only the 20 lower bits
+    /// are used
+    KeyMplsLabel(u32),
+    /// Label stack entries to match at any depth, this cannot be combined
+    /// with the other `KeyMpls*` keys
+    KeyMplsOpts(Vec<FlowerMplsLse>),
+    /// Tunnel id of the packet (VXLAN VNI, Geneve VNI, GRE key)
+    KeyEncKeyId(u32),
+    /// Outer source address of the tunnel
+    KeyEncIpv4Src(Ipv4Addr),
+    KeyEncIpv4SrcMask(Ipv4Addr),
+    /// Outer destination address of the tunnel
+    KeyEncIpv4Dst(Ipv4Addr),
+    KeyEncIpv4DstMask(Ipv4Addr),
+    KeyEncIpv6Src([u8; 16]),
+    KeyEncIpv6SrcMask([u8; 16]),
+    KeyEncIpv6Dst([u8; 16]),
+    KeyEncIpv6DstMask([u8; 16]),
+    KeyEncUdpSrcPort(u16),
+    KeyEncUdpSrcPortMask(u16),
+    KeyEncUdpDstPort(u16),
+    KeyEncUdpDstPortMask(u16),
+    KeyEncIpTos(u8),
+    KeyEncIpTosMask(u8),
+    KeyEncIpTtl(u8),
+    KeyEncIpTtlMask(u8),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for FlowerFilterNla {
+    fn value_len(&self) -> usize {
+        use self::FlowerFilterNla::*;
+        match self {
+            Indev(ref string) => string.len() + 1,
+            Act(ref actions) => actions.as_slice().buffer_len(),
+            KeyCtLabels(_) | KeyCtLabelsMask(_) => 16,
+            KeyIpv6Src(_) | KeyIpv6SrcMask(_) | KeyIpv6Dst(_) | KeyIpv6DstMask(_) => 16,
+            KeyIcmpv6Type(_) | KeyIcmpv6TypeMask(_) | KeyIcmpv6Code(_) | KeyIcmpv6CodeMask(_) => 1,
+            KeyFlags(_) | KeyFlagsMask(_) => 4,
+            KeyMplsTtl(_) | KeyMplsBos(_) | KeyMplsTc(_) => 1,
+            KeyMplsLabel(_) => 4,
+            KeyMplsOpts(ref entries) => entries.as_slice().buffer_len(),
+            KeyEncKeyId(_) | KeyEncIpv4Src(_) | KeyEncIpv4SrcMask(_) | KeyEncIpv4Dst(_)
+            | KeyEncIpv4DstMask(_) => 4,
+            KeyEncIpv6Src(_) | KeyEncIpv6SrcMask(_) | KeyEncIpv6Dst(_) | KeyEncIpv6DstMask(_) => 16,
+            KeyEncUdpSrcPort(_)
+            | KeyEncUdpSrcPortMask(_)
+            | KeyEncUdpDstPort(_)
+            | KeyEncUdpDstPortMask(_) => 2,
+            KeyEncIpTos(_) | KeyEncIpTosMask(_) | KeyEncIpTtl(_) | KeyEncIpTtlMask(_) => 1,
+            KeyEthDst(_) | KeyEthDstMask(_) | KeyEthSrc(_) | KeyEthSrcMask(_) => 6,
+            ClassId(_) | Flags(_) | InHwCount(_) | KeyIpv4Src(_) | KeyIpv4SrcMask(_)
+            | KeyIpv4Dst(_) | KeyIpv4DstMask(_) | KeyCtMark(_) | KeyCtMarkMask(_) => 4,
+            KeyEthType(_) | KeyTcpSrc(_) | KeyTcpSrcMask(_) | KeyTcpDst(_) | KeyTcpDstMask(_)
+            | KeyUdpSrc(_) | KeyUdpSrcMask(_) | KeyUdpDst(_) | KeyUdpDstMask(_) | KeyVlanId(_)
+            | KeyVlanEthType(_) | KeyCtState(_) | KeyCtStateMask(_) | KeyCtZone(_)
+            | KeyCtZoneMask(_) => 2,
+            KeyIpProto(_) | KeyVlanPrio(_) | KeyIpTos(_) | KeyIpTosMask(_) | KeyIpTtl(_)
+            | KeyIpTtlMask(_) => 1,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::FlowerFilterNla::*;
+        match self {
+            ClassId(value) | Flags(value) | InHwCount(value) | KeyCtMark(value)
+            | KeyCtMarkMask(value) => NativeEndian::write_u32(buffer, *value),
+            Indev(ref string) => {
+                buffer[..string.len()].copy_from_slice(string.as_bytes());
+                buffer[string.len()] = 0;
+            }
+            Act(ref actions) => actions.as_slice().emit(buffer),
+            KeyEthDst(ref mac)
+            | KeyEthDstMask(ref mac)
+            | KeyEthSrc(ref mac)
+            | KeyEthSrcMask(ref mac) => buffer.copy_from_slice(&mac[..]),
+            KeyIpv4Src(ref address)
+            | KeyIpv4SrcMask(ref address)
+            | KeyIpv4Dst(ref address)
+            | KeyIpv4DstMask(ref address) => buffer.copy_from_slice(&address.octets()),
+            KeyEthType(value)
+            | KeyTcpSrc(value)
+            | KeyTcpSrcMask(value)
+            | KeyTcpDst(value)
+            | KeyTcpDstMask(value)
+            | KeyUdpSrc(value)
+            | KeyUdpSrcMask(value)
+            | KeyUdpDst(value)
+            | KeyUdpDstMask(value)
+            | KeyVlanEthType(value) => BigEndian::write_u16(buffer, *value),
+            KeyVlanId(value) | KeyCtZone(value) | KeyCtZoneMask(value) => {
+                NativeEndian::write_u16(buffer, *value)
+            }
+            KeyCtState(state) | KeyCtStateMask(state) => {
+                NativeEndian::write_u16(buffer, state.bits())
+            }
+            KeyCtLabels(ref labels) | KeyCtLabelsMask(ref labels) => {
+                buffer.copy_from_slice(&labels[..])
+            }
+            KeyIpv6Src(ref address)
+            | KeyIpv6SrcMask(ref address)
+            | KeyIpv6Dst(ref address)
+            | KeyIpv6DstMask(ref address) => buffer.copy_from_slice(&address[..]),
+            KeyIcmpv6Type(value)
+            | KeyIcmpv6TypeMask(value)
+            | KeyIcmpv6Code(value)
+            | KeyIcmpv6CodeMask(value) => buffer[0] = *value,
+            KeyFlags(flags) | KeyFlagsMask(flags) => BigEndian::write_u32(buffer, flags.bits()),
+            KeyMplsTtl(value) | KeyMplsBos(value) | KeyMplsTc(value) => buffer[0] = *value,
+            KeyMplsLabel(value) => NativeEndian::write_u32(buffer, *value & MPLS_LABEL_MASK),
+            KeyMplsOpts(ref entries) => entries.as_slice().emit(buffer),
+            KeyEncKeyId(value) => BigEndian::write_u32(buffer, *value),
+            KeyEncIpv4Src(ref address)
+            | KeyEncIpv4SrcMask(ref address)
+            | KeyEncIpv4Dst(ref address)
+            | KeyEncIpv4DstMask(ref address) => buffer.copy_from_slice(&address.octets()),
+            KeyEncIpv6Src(ref address)
+            | KeyEncIpv6SrcMask(ref address)
+            | KeyEncIpv6Dst(ref address)
+            | KeyEncIpv6DstMask(ref address) => buffer.copy_from_slice(&address[..]),
+            KeyEncUdpSrcPort(value)
+            | KeyEncUdpSrcPortMask(value)
+            | KeyEncUdpDstPort(value)
+            | KeyEncUdpDstPortMask(value) => BigEndian::write_u16(buffer, *value),
+            KeyEncIpTos(value)
+            | KeyEncIpTosMask(value)
+            | KeyEncIpTtl(value)
+            | KeyEncIpTtlMask(value) => buffer[0] = *value,
+            KeyIpProto(value) | KeyVlanPrio(value) | KeyIpTos(value) | KeyIpTosMask(value)
+            | KeyIpTtl(value) | KeyIpTtlMask(value) => buffer[0] = *value,
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::FlowerFilterNla::*;
+        match self {
+            ClassId(_) => TCA_FLOWER_CLASSID,
+            Indev(_) => TCA_FLOWER_INDEV,
+            Act(_) => TCA_FLOWER_ACT,
+            Flags(_) => TCA_FLOWER_FLAGS,
+            InHwCount(_) => TCA_FLOWER_IN_HW_COUNT,
+            KeyEthDst(_) => TCA_FLOWER_KEY_ETH_DST,
+            KeyEthDstMask(_) => TCA_FLOWER_KEY_ETH_DST_MASK,
+            KeyEthSrc(_) => TCA_FLOWER_KEY_ETH_SRC,
+            KeyEthSrcMask(_) => TCA_FLOWER_KEY_ETH_SRC_MASK,
+            KeyEthType(_) => TCA_FLOWER_KEY_ETH_TYPE,
+            KeyIpProto(_) => TCA_FLOWER_KEY_IP_PROTO,
+            KeyIpv4Src(_) => TCA_FLOWER_KEY_IPV4_SRC,
+            KeyIpv4SrcMask(_) => TCA_FLOWER_KEY_IPV4_SRC_MASK,
+            KeyIpv4Dst(_) => TCA_FLOWER_KEY_IPV4_DST,
+            KeyIpv4DstMask(_) => TCA_FLOWER_KEY_IPV4_DST_MASK,
+            KeyTcpSrc(_) => TCA_FLOWER_KEY_TCP_SRC,
+            KeyTcpSrcMask(_) => TCA_FLOWER_KEY_TCP_SRC_MASK,
+            KeyTcpDst(_) => TCA_FLOWER_KEY_TCP_DST,
+            KeyTcpDstMask(_) => TCA_FLOWER_KEY_TCP_DST_MASK,
+            KeyUdpSrc(_) => TCA_FLOWER_KEY_UDP_SRC,
+            KeyUdpSrcMask(_) => TCA_FLOWER_KEY_UDP_SRC_MASK,
+            KeyUdpDst(_) => TCA_FLOWER_KEY_UDP_DST,
+            KeyUdpDstMask(_) => TCA_FLOWER_KEY_UDP_DST_MASK,
+            KeyVlanId(_) => TCA_FLOWER_KEY_VLAN_ID,
+            KeyVlanPrio(_) => TCA_FLOWER_KEY_VLAN_PRIO,
+            KeyVlanEthType(_) => TCA_FLOWER_KEY_VLAN_ETH_TYPE,
+            KeyIpTos(_) => TCA_FLOWER_KEY_IP_TOS,
+            KeyIpTosMask(_) => TCA_FLOWER_KEY_IP_TOS_MASK,
+            KeyIpTtl(_) => TCA_FLOWER_KEY_IP_TTL,
+            KeyIpTtlMask(_) => TCA_FLOWER_KEY_IP_TTL_MASK,
+            KeyIpv6Src(_) => TCA_FLOWER_KEY_IPV6_SRC,
+            KeyIpv6SrcMask(_) => TCA_FLOWER_KEY_IPV6_SRC_MASK,
+            KeyIpv6Dst(_) => TCA_FLOWER_KEY_IPV6_DST,
+            KeyIpv6DstMask(_) => TCA_FLOWER_KEY_IPV6_DST_MASK,
+            KeyIcmpv6Type(_) => TCA_FLOWER_KEY_ICMPV6_TYPE,
+            KeyIcmpv6TypeMask(_) => TCA_FLOWER_KEY_ICMPV6_TYPE_MASK,
+            KeyIcmpv6Code(_) => TCA_FLOWER_KEY_ICMPV6_CODE,
+            KeyIcmpv6CodeMask(_) => TCA_FLOWER_KEY_ICMPV6_CODE_MASK,
+            KeyFlags(_) => TCA_FLOWER_KEY_FLAGS,
+            KeyFlagsMask(_) => TCA_FLOWER_KEY_FLAGS_MASK,
+            KeyCtState(_) => TCA_FLOWER_KEY_CT_STATE,
+            KeyCtStateMask(_) => TCA_FLOWER_KEY_CT_STATE_MASK,
+            KeyCtZone(_) => TCA_FLOWER_KEY_CT_ZONE,
+            KeyCtZoneMask(_) => TCA_FLOWER_KEY_CT_ZONE_MASK,
+            KeyCtMark(_) => TCA_FLOWER_KEY_CT_MARK,
+            KeyCtMarkMask(_) => TCA_FLOWER_KEY_CT_MARK_MASK,
+            KeyCtLabels(_) => TCA_FLOWER_KEY_CT_LABELS,
+            KeyCtLabelsMask(_) => TCA_FLOWER_KEY_CT_LABELS_MASK,
+            KeyMplsTtl(_) => TCA_FLOWER_KEY_MPLS_TTL,
+            KeyMplsBos(_) => TCA_FLOWER_KEY_MPLS_BOS,
+            KeyMplsTc(_) => TCA_FLOWER_KEY_MPLS_TC,
+            KeyMplsLabel(_) => TCA_FLOWER_KEY_MPLS_LABEL,
+            KeyMplsOpts(_) => TCA_FLOWER_KEY_MPLS_OPTS,
+            KeyEncKeyId(_) => TCA_FLOWER_KEY_ENC_KEY_ID,
+            KeyEncIpv4Src(_) => TCA_FLOWER_KEY_ENC_IPV4_SRC,
+            KeyEncIpv4SrcMask(_) => TCA_FLOWER_KEY_ENC_IPV4_SRC_MASK,
+            KeyEncIpv4Dst(_) => TCA_FLOWER_KEY_ENC_IPV4_DST,
+            KeyEncIpv4DstMask(_) => TCA_FLOWER_KEY_ENC_IPV4_DST_MASK,
+            KeyEncIpv6Src(_) => TCA_FLOWER_KEY_ENC_IPV6_SRC,
+            KeyEncIpv6SrcMask(_) => TCA_FLOWER_KEY_ENC_IPV6_SRC_MASK,
+            KeyEncIpv6Dst(_) => TCA_FLOWER_KEY_ENC_IPV6_DST,
+            KeyEncIpv6DstMask(_) => TCA_FLOWER_KEY_ENC_IPV6_DST_MASK,
+            KeyEncUdpSrcPort(_) => TCA_FLOWER_KEY_ENC_UDP_SRC_PORT,
+            KeyEncUdpSrcPortMask(_) => TCA_FLOWER_KEY_ENC_UDP_SRC_PORT_MASK,
+            KeyEncUdpDstPort(_) => TCA_FLOWER_KEY_ENC_UDP_DST_PORT,
+            KeyEncUdpDstPortMask(_) => TCA_FLOWER_KEY_ENC_UDP_DST_PORT_MASK,
+            KeyEncIpTos(_) => TCA_FLOWER_KEY_ENC_IP_TOS,
+            KeyEncIpTosMask(_) => TCA_FLOWER_KEY_ENC_IP_TOS_MASK,
+            KeyEncIpTtl(_) => TCA_FLOWER_KEY_ENC_IP_TTL,
+            KeyEncIpTtlMask(_) => TCA_FLOWER_KEY_ENC_IP_TTL_MASK,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FlowerFilterNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::FlowerFilterNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_CLASSID => {
+                ClassId(parse_u32(payload).context("invalid TCA_FLOWER_CLASSID value")?)
+            }
+            TCA_FLOWER_INDEV => {
+                Indev(parse_string(payload).context("invalid TCA_FLOWER_INDEV value")?)
+            }
+            TCA_FLOWER_ACT => Act(parse_actions(payload).context("invalid TCA_FLOWER_ACT value")?),
+            TCA_FLOWER_FLAGS => {
+                Flags(parse_u32(payload).context("invalid TCA_FLOWER_FLAGS value")?)
+            }
+            TCA_FLOWER_IN_HW_COUNT => {
+                InHwCount(parse_u32(payload).context("invalid TCA_FLOWER_IN_HW_COUNT value")?)
+            }
+            TCA_FLOWER_KEY_ETH_DST => {
+                KeyEthDst(parse_mac(payload).context("invalid TCA_FLOWER_KEY_ETH_DST value")?)
+            }
+            TCA_FLOWER_KEY_ETH_DST_MASK => KeyEthDstMask(
+                parse_mac(payload).context("invalid TCA_FLOWER_KEY_ETH_DST_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ETH_SRC => {
+                KeyEthSrc(parse_mac(payload).context("invalid TCA_FLOWER_KEY_ETH_SRC value")?)
+            }
+            TCA_FLOWER_KEY_ETH_SRC_MASK => KeyEthSrcMask(
+                parse_mac(payload).context("invalid TCA_FLOWER_KEY_ETH_SRC_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ETH_TYPE => {
+                KeyEthType(parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_ETH_TYPE value")?)
+            }
+            TCA_FLOWER_KEY_IP_PROTO => {
+                KeyIpProto(parse_u8(payload).context("invalid TCA_FLOWER_KEY_IP_PROTO value")?)
+            }
+            TCA_FLOWER_KEY_IPV4_SRC => {
+                KeyIpv4Src(parse_ipv4(payload).context("invalid TCA_FLOWER_KEY_IPV4_SRC value")?)
+            }
+            TCA_FLOWER_KEY_IPV4_SRC_MASK => KeyIpv4SrcMask(
+                parse_ipv4(payload).context("invalid TCA_FLOWER_KEY_IPV4_SRC_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_IPV4_DST => {
+                KeyIpv4Dst(parse_ipv4(payload).context("invalid TCA_FLOWER_KEY_IPV4_DST value")?)
+            }
+            TCA_FLOWER_KEY_IPV4_DST_MASK => KeyIpv4DstMask(
+                parse_ipv4(payload).context("invalid TCA_FLOWER_KEY_IPV4_DST_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_TCP_SRC => {
+                KeyTcpSrc(parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_TCP_SRC value")?)
+            }
+            TCA_FLOWER_KEY_TCP_SRC_MASK => KeyTcpSrcMask(
+                parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_TCP_SRC_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_TCP_DST => {
+                KeyTcpDst(parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_TCP_DST value")?)
+            }
+            TCA_FLOWER_KEY_TCP_DST_MASK => KeyTcpDstMask(
+                parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_TCP_DST_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_UDP_SRC => {
+                KeyUdpSrc(parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_UDP_SRC value")?)
+            }
+            TCA_FLOWER_KEY_UDP_SRC_MASK => KeyUdpSrcMask(
+                parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_UDP_SRC_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_UDP_DST => {
+                KeyUdpDst(parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_UDP_DST value")?)
+            }
+            TCA_FLOWER_KEY_UDP_DST_MASK => KeyUdpDstMask(
+                parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_UDP_DST_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_VLAN_ID => {
+                KeyVlanId(parse_u16(payload).context("invalid TCA_FLOWER_KEY_VLAN_ID value")?)
+            }
+            TCA_FLOWER_KEY_VLAN_PRIO => {
+                KeyVlanPrio(parse_u8(payload).context("invalid TCA_FLOWER_KEY_VLAN_PRIO value")?)
+            }
+            TCA_FLOWER_KEY_VLAN_ETH_TYPE => KeyVlanEthType(
+                parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_VLAN_ETH_TYPE value")?,
+            ),
+            TCA_FLOWER_KEY_IP_TOS => {
+                KeyIpTos(parse_u8(payload).context("invalid TCA_FLOWER_KEY_IP_TOS value")?)
+            }
+            TCA_FLOWER_KEY_IP_TOS_MASK => {
+                KeyIpTosMask(parse_u8(payload).context("invalid TCA_FLOWER_KEY_IP_TOS_MASK value")?)
+            }
+            TCA_FLOWER_KEY_IP_TTL => {
+                KeyIpTtl(parse_u8(payload).context("invalid TCA_FLOWER_KEY_IP_TTL value")?)
+            }
+            TCA_FLOWER_KEY_IP_TTL_MASK => {
+                KeyIpTtlMask(parse_u8(payload).context("invalid TCA_FLOWER_KEY_IP_TTL_MASK value")?)
+            }
+            TCA_FLOWER_KEY_IPV6_SRC => {
+                KeyIpv6Src(parse_ipv6(payload).context("invalid TCA_FLOWER_KEY_IPV6_SRC value")?)
+            }
+            TCA_FLOWER_KEY_IPV6_SRC_MASK => KeyIpv6SrcMask(
+                parse_ipv6(payload).context("invalid TCA_FLOWER_KEY_IPV6_SRC_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_IPV6_DST => {
+                KeyIpv6Dst(parse_ipv6(payload).context("invalid TCA_FLOWER_KEY_IPV6_DST value")?)
+            }
+            TCA_FLOWER_KEY_IPV6_DST_MASK => KeyIpv6DstMask(
+                parse_ipv6(payload).context("invalid TCA_FLOWER_KEY_IPV6_DST_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ICMPV6_TYPE => KeyIcmpv6Type(
+                parse_u8(payload).context("invalid TCA_FLOWER_KEY_ICMPV6_TYPE value")?,
+            ),
+            TCA_FLOWER_KEY_ICMPV6_TYPE_MASK => KeyIcmpv6TypeMask(
+                parse_u8(payload).context("invalid TCA_FLOWER_KEY_ICMPV6_TYPE_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ICMPV6_CODE => KeyIcmpv6Code(
+                parse_u8(payload).context("invalid TCA_FLOWER_KEY_ICMPV6_CODE value")?,
+            ),
+            TCA_FLOWER_KEY_ICMPV6_CODE_MASK => KeyIcmpv6CodeMask(
+                parse_u8(payload).context("invalid TCA_FLOWER_KEY_ICMPV6_CODE_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_FLAGS => KeyFlags(FlowerKeyFlags::from_bits_truncate(
+                parse_u32_be(payload).context("invalid TCA_FLOWER_KEY_FLAGS value")?,
+            )),
+            TCA_FLOWER_KEY_FLAGS_MASK => KeyFlagsMask(FlowerKeyFlags::from_bits_truncate(
+                parse_u32_be(payload).context("invalid TCA_FLOWER_KEY_FLAGS_MASK value")?,
+            )),
+            TCA_FLOWER_KEY_CT_STATE => KeyCtState(FlowerCtState::from_bits_truncate(
+                parse_u16(payload).context("invalid TCA_FLOWER_KEY_CT_STATE value")?,
+            )),
+            TCA_FLOWER_KEY_CT_STATE_MASK => KeyCtStateMask(FlowerCtState::from_bits_truncate(
+                parse_u16(payload).context("invalid TCA_FLOWER_KEY_CT_STATE_MASK value")?,
+            )),
+            TCA_FLOWER_KEY_CT_ZONE => {
+                KeyCtZone(parse_u16(payload).context("invalid TCA_FLOWER_KEY_CT_ZONE value")?)
+            }
+            TCA_FLOWER_KEY_CT_ZONE_MASK => KeyCtZoneMask(
+                parse_u16(payload).context("invalid TCA_FLOWER_KEY_CT_ZONE_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_CT_MARK => {
+                KeyCtMark(parse_u32(payload).context("invalid TCA_FLOWER_KEY_CT_MARK value")?)
+            }
+            TCA_FLOWER_KEY_CT_MARK_MASK => KeyCtMarkMask(
+                parse_u32(payload).context("invalid TCA_FLOWER_KEY_CT_MARK_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_CT_LABELS => KeyCtLabels(
+                parse_labels(payload).context("invalid TCA_FLOWER_KEY_CT_LABELS value")?,
+            ),
+            TCA_FLOWER_KEY_CT_LABELS_MASK => KeyCtLabelsMask(
+                parse_labels(payload).context("invalid TCA_FLOWER_KEY_CT_LABELS_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_MPLS_TTL => {
+                KeyMplsTtl(parse_u8(payload).context("invalid TCA_FLOWER_KEY_MPLS_TTL value")?)
+            }
+            TCA_FLOWER_KEY_MPLS_BOS => {
+                KeyMplsBos(parse_u8(payload).context("invalid TCA_FLOWER_KEY_MPLS_BOS value")?)
+            }
+            TCA_FLOWER_KEY_MPLS_TC => {
+                KeyMplsTc(parse_u8(payload).context("invalid TCA_FLOWER_KEY_MPLS_TC value")?)
+            }
+            TCA_FLOWER_KEY_MPLS_LABEL => KeyMplsLabel(
+                parse_u32(payload).context("invalid TCA_FLOWER_KEY_MPLS_LABEL value")?
+                    & MPLS_LABEL_MASK,
+            ),
+            TCA_FLOWER_KEY_MPLS_OPTS => {
+                let mut entries = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_FLOWER_KEY_MPLS_OPTS value")?;
+                    entries.push(FlowerMplsLse::parse(&nla)?);
+                }
+                KeyMplsOpts(entries)
+            }
+            TCA_FLOWER_KEY_ENC_KEY_ID => KeyEncKeyId(
+                parse_u32_be(payload).context("invalid TCA_FLOWER_KEY_ENC_KEY_ID value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IPV4_SRC => KeyEncIpv4Src(
+                parse_ipv4(payload).context("invalid TCA_FLOWER_KEY_ENC_IPV4_SRC value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IPV4_SRC_MASK => KeyEncIpv4SrcMask(
+                parse_ipv4(payload).context("invalid TCA_FLOWER_KEY_ENC_IPV4_SRC_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IPV4_DST => KeyEncIpv4Dst(
+                parse_ipv4(payload).context("invalid TCA_FLOWER_KEY_ENC_IPV4_DST value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IPV4_DST_MASK => KeyEncIpv4DstMask(
+                parse_ipv4(payload).context("invalid TCA_FLOWER_KEY_ENC_IPV4_DST_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IPV6_SRC => KeyEncIpv6Src(
+                parse_ipv6(payload).context("invalid TCA_FLOWER_KEY_ENC_IPV6_SRC value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IPV6_SRC_MASK => KeyEncIpv6SrcMask(
+                parse_ipv6(payload).context("invalid TCA_FLOWER_KEY_ENC_IPV6_SRC_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IPV6_DST => KeyEncIpv6Dst(
+                parse_ipv6(payload).context("invalid TCA_FLOWER_KEY_ENC_IPV6_DST value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IPV6_DST_MASK => KeyEncIpv6DstMask(
+                parse_ipv6(payload).context("invalid TCA_FLOWER_KEY_ENC_IPV6_DST_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_UDP_SRC_PORT => KeyEncUdpSrcPort(
+                parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_ENC_UDP_SRC_PORT value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_UDP_SRC_PORT_MASK => KeyEncUdpSrcPortMask(
+                parse_u16_be(payload)
+                    .context("invalid TCA_FLOWER_KEY_ENC_UDP_SRC_PORT_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_UDP_DST_PORT => KeyEncUdpDstPort(
+                parse_u16_be(payload).context("invalid TCA_FLOWER_KEY_ENC_UDP_DST_PORT value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_UDP_DST_PORT_MASK => KeyEncUdpDstPortMask(
+                parse_u16_be(payload)
+                    .context("invalid TCA_FLOWER_KEY_ENC_UDP_DST_PORT_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IP_TOS => {
+                KeyEncIpTos(parse_u8(payload).context("invalid TCA_FLOWER_KEY_ENC_IP_TOS value")?)
+            }
+            TCA_FLOWER_KEY_ENC_IP_TOS_MASK => KeyEncIpTosMask(
+                parse_u8(payload).context("invalid TCA_FLOWER_KEY_ENC_IP_TOS_MASK value")?,
+            ),
+            TCA_FLOWER_KEY_ENC_IP_TTL => {
+                KeyEncIpTtl(parse_u8(payload).context("invalid TCA_FLOWER_KEY_ENC_IP_TTL value")?)
+            }
+            TCA_FLOWER_KEY_ENC_IP_TTL_MASK => KeyEncIpTtlMask(
+                parse_u8(payload).context("invalid TCA_FLOWER_KEY_ENC_IP_TTL_MASK value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid flower filter nla")?),
+        })
+    }
+}
+
+/// A label stack entry of `TCA_FLOWER_KEY_MPLS_OPTS`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowerMplsLse {
+    pub nlas: Vec<FlowerMplsLseNla>,
+}
+
+impl nlas::Nla for FlowerMplsLse {
+    fn value_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+
+    fn kind(&self) -> u16 {
+        TCA_FLOWER_KEY_MPLS_OPTS_LSE
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FlowerMplsLse {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid flower mpls label stack entry nla")?;
+            nlas.push(FlowerMplsLseNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlowerMplsLseNla {
+    /// Depth of the entry in the label stack, starting at 1 for the
+    /// outermost entry
+    Depth(u8),
+    Ttl(u8),
+    Bos(u8),
+    Tc(u8),
+    /// Label, only the 20 lower bits are used
+    Label(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for FlowerMplsLseNla {
+    fn value_len(&self) -> usize {
+        use self::FlowerMplsLseNla::*;
+        match self {
+            Depth(_) | Ttl(_) | Bos(_) | Tc(_) => 1,
+            Label(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::FlowerMplsLseNla::*;
+        match self {
+            Depth(value) | Ttl(value) | Bos(value) | Tc(value) => buffer[0] = *value,
+            Label(value) => NativeEndian::write_u32(buffer, *value & MPLS_LABEL_MASK),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::FlowerMplsLseNla::*;
+        match self {
+            Depth(_) => TCA_FLOWER_KEY_MPLS_OPT_LSE_DEPTH,
+            Ttl(_) => TCA_FLOWER_KEY_MPLS_OPT_LSE_TTL,
+            Bos(_) => TCA_FLOWER_KEY_MPLS_OPT_LSE_BOS,
+            Tc(_) => TCA_FLOWER_KEY_MPLS_OPT_LSE_TC,
+            Label(_) => TCA_FLOWER_KEY_MPLS_OPT_LSE_LABEL,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FlowerMplsLseNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::FlowerMplsLseNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FLOWER_KEY_MPLS_OPT_LSE_DEPTH => {
+                Depth(parse_u8(payload).context("invalid TCA_FLOWER_KEY_MPLS_OPT_LSE_DEPTH value")?)
+            }
+            TCA_FLOWER_KEY_MPLS_OPT_LSE_TTL => {
+                Ttl(parse_u8(payload).context("invalid TCA_FLOWER_KEY_MPLS_OPT_LSE_TTL value")?)
+            }
+            TCA_FLOWER_KEY_MPLS_OPT_LSE_BOS => {
+                Bos(parse_u8(payload).context("invalid TCA_FLOWER_KEY_MPLS_OPT_LSE_BOS value")?)
+            }
+            TCA_FLOWER_KEY_MPLS_OPT_LSE_TC => {
+                Tc(parse_u8(payload).context("invalid TCA_FLOWER_KEY_MPLS_OPT_LSE_TC value")?)
+            }
+            TCA_FLOWER_KEY_MPLS_OPT_LSE_LABEL => Label(
+                parse_u32(payload).context("invalid TCA_FLOWER_KEY_MPLS_OPT_LSE_LABEL value")?
+                    & MPLS_LABEL_MASK,
+            ),
+            _ => {
+                Other(DefaultNla::parse(buf).context("invalid flower mpls label stack entry nla")?)
+            }
+        })
+    }
+}
+
+bitflags! {
+    /// Fragmentation flags, `TCA_FLOWER_KEY_FLAGS_*`
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct FlowerKeyFlags: u32 {
+        const IS_FRAGMENT = TCA_FLOWER_KEY_FLAGS_IS_FRAGMENT;
+        const FRAG_IS_FIRST = TCA_FLOWER_KEY_FLAGS_FRAG_IS_FIRST;
+    }
+}
+
+impl Default for FlowerKeyFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+bitflags! {
+    /// Conntrack state bits, `TCA_FLOWER_KEY_CT_FLAGS_*`
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct FlowerCtState: u16 {
+        const NEW = TCA_FLOWER_KEY_CT_FLAGS_NEW;
+        const ESTABLISHED = TCA_FLOWER_KEY_CT_FLAGS_ESTABLISHED;
+        const RELATED = TCA_FLOWER_KEY_CT_FLAGS_RELATED;
+        /// The packet went through the ct action
+        const TRACKED = TCA_FLOWER_KEY_CT_FLAGS_TRACKED;
+        const INVALID = TCA_FLOWER_KEY_CT_FLAGS_INVALID;
+        /// The packet belongs to the reply direction of the connection
+        const REPLY = TCA_FLOWER_KEY_CT_FLAGS_REPLY;
+    }
+}
+
+impl Default for FlowerCtState {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{
+        self,
+        tc::action::{parse_actions, PoliceAction, TcAction},
+        DefaultNla,
+        NlaBuffer,
+        NlasIterator,
+    },
+    parsers::{parse_string, parse_u32},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "fw";
+
+/// Options of the fw filter, which classifies packets by firewall mark. The
+/// handle of the filter is the mark to match.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FwFilter {
+    pub nlas: Vec<FwFilterNla>,
+}
+
+impl Emitable for FwFilter {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FwFilter {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid fw filter nla")?;
+            nlas.push(FwFilterNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FwFilterNla {
+    /// Class the matching packets are sent to
+    ClassId(u32),
+    /// Legacy policer
+    Police(PoliceAction),
+    /// Only match packets received on this interface
+    Indev(String),
+    /// Actions run on the matching packets
+    Act(Vec<TcAction>),
+    /// Mask ANDed with the firewall mark before it is compared to the handle
+    Mask(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for FwFilterNla {
+    fn value_len(&self) -> usize {
+        use self::FwFilterNla::*;
+        match self {
+            ClassId(_) | Mask(_) => 4,
+            Indev(ref string) => string.len() + 1,
+            Act(ref actions) => actions.as_slice().buffer_len(),
+            Police(ref police) => police.buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::FwFilterNla::*;
+        match self {
+            ClassId(value) | Mask(value) => NativeEndian::write_u32(buffer, *value),
+            Indev(ref string) => {
+                buffer[..string.len()].copy_from_slice(string.as_bytes());
+                buffer[string.len()] = 0;
+            }
+            Act(ref actions) => actions.as_slice().emit(buffer),
+            Police(ref police) => police.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::FwFilterNla::*;
+        match self {
+            ClassId(_) => TCA_FW_CLASSID,
+            Police(_) => TCA_FW_POLICE,
+            Indev(_) => TCA_FW_INDEV,
+            Act(_) => TCA_FW_ACT,
+            Mask(_) => TCA_FW_MASK,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FwFilterNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::FwFilterNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FW_CLASSID => ClassId(parse_u32(payload).context("invalid TCA_FW_CLASSID value")?),
+            TCA_FW_POLICE => {
+                Police(PoliceAction::parse(buf).context("invalid TCA_FW_POLICE value")?)
+            }
+            TCA_FW_INDEV => Indev(parse_string(payload).context("invalid TCA_FW_INDEV value")?),
+            TCA_FW_ACT => Act(parse_actions(payload).context("invalid TCA_FW_ACT value")?),
+            TCA_FW_MASK => Mask(parse_u32(payload).context("invalid TCA_FW_MASK value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid fw filter nla")?),
+        })
+    }
+}
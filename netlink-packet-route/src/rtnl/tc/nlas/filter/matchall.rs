@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{
+        self,
+        tc::action::{parse_actions, TcAction},
+        DefaultNla,
+        NlaBuffer,
+        NlasIterator,
+    },
+    parsers::{parse_u32, parse_u64},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "matchall";
+
+/// Options of the matchall filter, which matches every packet. It is mostly
+/// used to run actions on all the traffic of a device.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchallFilter {
+    pub nlas: Vec<MatchallFilterNla>,
+}
+
+impl Emitable for MatchallFilter {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for MatchallFilter {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid matchall filter nla")?;
+            nlas.push(MatchallFilterNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MatchallFilterNla {
+    /// Class the packets are sent to
+    ClassId(u32),
+    /// Actions run on the packets
+    Act(Vec<TcAction>),
+    /// `TCA_CLS_FLAGS_*` flags
+    Flags(u32),
+    /// Number of packets that hit the filter, set by the kernel
+    Pcnt(u64),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for MatchallFilterNla {
+    fn value_len(&self) -> usize {
+        use self::MatchallFilterNla::*;
+        match self {
+            ClassId(_) | Flags(_) => 4,
+            Act(ref actions) => actions.as_slice().buffer_len(),
+            Pcnt(_) => 8,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::MatchallFilterNla::*;
+        match self {
+            ClassId(value) | Flags(value) => NativeEndian::write_u32(buffer, *value),
+            Act(ref actions) => actions.as_slice().emit(buffer),
+            Pcnt(value) => NativeEndian::write_u64(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::MatchallFilterNla::*;
+        match self {
+            ClassId(_) => TCA_MATCHALL_CLASSID,
+            Act(_) => TCA_MATCHALL_ACT,
+            Flags(_) => TCA_MATCHALL_FLAGS,
+            Pcnt(_) => TCA_MATCHALL_PCNT,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for MatchallFilterNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::MatchallFilterNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_MATCHALL_CLASSID => {
+                ClassId(parse_u32(payload).context("invalid TCA_MATCHALL_CLASSID value")?)
+            }
+            TCA_MATCHALL_ACT => {
+                Act(parse_actions(payload).context("invalid TCA_MATCHALL_ACT value")?)
+            }
+            TCA_MATCHALL_FLAGS => {
+                Flags(parse_u32(payload).context("invalid TCA_MATCHALL_FLAGS value")?)
+            }
+            TCA_MATCHALL_PCNT => {
+                Pcnt(parse_u64(payload).context("invalid TCA_MATCHALL_PCNT value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid matchall filter nla")?),
+        })
+    }
+}
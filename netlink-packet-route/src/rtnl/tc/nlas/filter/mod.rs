@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: MIT
+
+pub mod u32;
+pub use self::u32::U32Filter;
+
+pub mod bpf;
+pub use self::bpf::BpfFilter;
+
+pub mod basic;
+pub use self::basic::BasicFilter;
+
+pub mod fw;
+pub use self::fw::FwFilter;
+
+pub mod flow;
+pub use self::flow::FlowFilter;
+
+pub mod flower;
+pub use self::flower::FlowerFilter;
+
+pub mod matchall;
+pub use self::matchall::MatchallFilter;
+
+use anyhow::Context;
+
+use crate::{
+    nlas::NlaBuffer,
+    traits::{Emitable, Parseable, ParseableParametrized},
+    DecodeError,
+};
+
+/// Filter specific options, carried by the `TCA_OPTIONS` attribute of
+/// `RTM_*TFILTER` and `RTM_*CHAIN` messages.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Filter {
+    U32(U32Filter),
+    Bpf(BpfFilter),
+    Basic(BasicFilter),
+    Fw(FwFilter),
+    Flow(FlowFilter),
+    Flower(FlowerFilter),
+    Matchall(MatchallFilter),
+    Other(#[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_base64"))] Vec<u8>),
+}
+
+impl Filter {
+    /// Create empty options for the given filter kind.
+    /// Unknown kinds give empty `Other` options rather than an error.
+    pub fn new(kind: &str) -> Self {
+        match kind {
+            u32::KIND => Filter::U32(U32Filter::default()),
+            bpf::KIND => Filter::Bpf(BpfFilter::default()),
+            basic::KIND => Filter::Basic(BasicFilter::default()),
+            fw::KIND => Filter::Fw(FwFilter::default()),
+            flow::KIND => Filter::Flow(FlowFilter::default()),
+            flower::KIND => Filter::Flower(FlowerFilter::default()),
+            matchall::KIND => Filter::Matchall(MatchallFilter::default()),
+            _ => Filter::Other(Vec::new()),
+        }
+    }
+}
+
+impl Emitable for Filter {
+    fn buffer_len(&self) -> usize {
+        match self {
+            Filter::U32(ref u32) => u32.buffer_len(),
+            Filter::Bpf(ref bpf) => bpf.buffer_len(),
+            Filter::Basic(ref basic) => basic.buffer_len(),
+            Filter::Fw(ref fw) => fw.buffer_len(),
+            Filter::Flow(ref flow) => flow.buffer_len(),
+            Filter::Flower(ref flower) => flower.buffer_len(),
+            Filter::Matchall(ref matchall) => matchall.buffer_len(),
+            Filter::Other(ref bytes) => bytes.len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            Filter::U32(ref u32) => u32.emit(buffer),
+            Filter::Bpf(ref bpf) => bpf.emit(buffer),
+            Filter::Basic(ref basic) => basic.emit(buffer),
+            Filter::Fw(ref fw) => fw.emit(buffer),
+            Filter::Flow(ref flow) => flow.emit(buffer),
+            Filter::Flower(ref flower) => flower.emit(buffer),
+            Filter::Matchall(ref matchall) => matchall.emit(buffer),
+            Filter::Other(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, &str> for Filter {
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, kind: &str) -> Result<Self, DecodeError> {
+        Ok(match kind {
+            u32::KIND => {
+                Filter::U32(U32Filter::parse(buf).context("failed to parse u32 filter options")?)
+            }
+            bpf::KIND => {
+                Filter::Bpf(BpfFilter::parse(buf).context("failed to parse bpf filter options")?)
+            }
+            basic::KIND => Filter::Basic(
+                BasicFilter::parse(buf).context("failed to parse basic filter options")?,
+            ),
+            fw::KIND => {
+                Filter::Fw(FwFilter::parse(buf).context("failed to parse fw filter options")?)
+            }
+            flow::KIND => {
+                Filter::Flow(FlowFilter::parse(buf).context("failed to parse flow filter options")?)
+            }
+            flower::KIND => Filter::Flower(
+                FlowerFilter::parse(buf).context("failed to parse flower filter options")?,
+            ),
+            matchall::KIND => Filter::Matchall(
+                MatchallFilter::parse(buf).context("failed to parse matchall filter options")?,
+            ),
+            _ => Filter::Other(buf.value().to_vec()),
+        })
+    }
+}
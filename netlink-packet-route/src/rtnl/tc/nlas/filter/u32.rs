@@ -0,0 +1,392 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{
+        self,
+        tc::action::{parse_actions, PoliceAction, TcAction},
+        DefaultNla,
+        NlaBuffer,
+        NlasIterator,
+    },
+    parsers::{parse_string, parse_u32},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "u32";
+
+/// Options of the u32 filter
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct U32Filter {
+    pub nlas: Vec<U32FilterNla>,
+}
+
+impl Emitable for U32Filter {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for U32Filter {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid u32 filter nla")?;
+            nlas.push(U32FilterNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum U32FilterNla {
+    /// Class the matching packets are sent to
+    ClassId(u32),
+    /// Handle of the hash table the filter is inserted into
+    Hash(u32),
+    /// Handle of the hash table the matching packets are looked up in next
+    Link(u32),
+    /// Number of buckets of a hash table
+    Divisor(u32),
+    Sel(TcU32Sel),
+    /// Legacy policer
+    Police(PoliceAction),
+    /// Actions run on the matching packets
+    Act(Vec<TcAction>),
+    /// Only match packets received on this interface
+    Indev(String),
+    Pcnt(TcU32Pcnt),
+    Mark(TcU32Mark),
+    /// `TCA_CLS_FLAGS_*` flags
+    Flags(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for U32FilterNla {
+    fn value_len(&self) -> usize {
+        use self::U32FilterNla::*;
+        match self {
+            ClassId(_) | Hash(_) | Link(_) | Divisor(_) | Flags(_) => 4,
+            Sel(ref sel) => sel.buffer_len(),
+            Indev(ref string) => string.len() + 1,
+            Pcnt(ref pcnt) => pcnt.buffer_len(),
+            Mark(ref mark) => mark.buffer_len(),
+            Act(ref actions) => actions.as_slice().buffer_len(),
+            Police(ref police) => police.buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::U32FilterNla::*;
+        match self {
+            ClassId(value) | Hash(value) | Link(value) | Divisor(value) | Flags(value) => {
+                NativeEndian::write_u32(buffer, *value)
+            }
+            Sel(ref sel) => sel.emit(buffer),
+            Indev(ref string) => {
+                buffer[..string.len()].copy_from_slice(string.as_bytes());
+                buffer[string.len()] = 0;
+            }
+            Pcnt(ref pcnt) => pcnt.emit(buffer),
+            Mark(ref mark) => mark.emit(buffer),
+            Act(ref actions) => actions.as_slice().emit(buffer),
+            Police(ref police) => police.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::U32FilterNla::*;
+        match self {
+            ClassId(_) => TCA_U32_CLASSID,
+            Hash(_) => TCA_U32_HASH,
+            Link(_) => TCA_U32_LINK,
+            Divisor(_) => TCA_U32_DIVISOR,
+            Sel(_) => TCA_U32_SEL,
+            Police(_) => TCA_U32_POLICE,
+            Act(_) => TCA_U32_ACT,
+            Indev(_) => TCA_U32_INDEV,
+            Pcnt(_) => TCA_U32_PCNT,
+            Mark(_) => TCA_U32_MARK,
+            Flags(_) => TCA_U32_FLAGS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for U32FilterNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::U32FilterNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_U32_CLASSID => {
+                ClassId(parse_u32(payload).context("invalid TCA_U32_CLASSID value")?)
+            }
+            TCA_U32_HASH => Hash(parse_u32(payload).context("invalid TCA_U32_HASH value")?),
+            TCA_U32_LINK => Link(parse_u32(payload).context("invalid TCA_U32_LINK value")?),
+            TCA_U32_DIVISOR => {
+                Divisor(parse_u32(payload).context("invalid TCA_U32_DIVISOR value")?)
+            }
+            TCA_U32_SEL => Sel(TcU32Sel::parse(&TcU32SelBuffer::new_checked(payload)?)
+                .context("invalid TCA_U32_SEL value")?),
+            TCA_U32_POLICE => {
+                Police(PoliceAction::parse(buf).context("invalid TCA_U32_POLICE value")?)
+            }
+            TCA_U32_ACT => Act(parse_actions(payload).context("invalid TCA_U32_ACT value")?),
+            TCA_U32_INDEV => Indev(parse_string(payload).context("invalid TCA_U32_INDEV value")?),
+            TCA_U32_PCNT => Pcnt(
+                TcU32Pcnt::parse(&TcU32PcntBuffer::new_checked(payload)?)
+                    .context("invalid TCA_U32_PCNT value")?,
+            ),
+            TCA_U32_MARK => Mark(
+                TcU32Mark::parse(&TcU32MarkBuffer::new_checked(payload)?)
+                    .context("invalid TCA_U32_MARK value")?,
+            ),
+            TCA_U32_FLAGS => Flags(parse_u32(payload).context("invalid TCA_U32_FLAGS value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid u32 filter nla")?),
+        })
+    }
+}
+
+/// Selector of the u32 filter (`struct tc_u32_sel`). The fields that the
+/// kernel stores in network byte order are exposed in host byte order.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcU32Sel {
+    /// `TC_U32_*` flags
+    pub flags: u8,
+    /// Shift applied to the offset read at `offoff`
+    pub offshift: u8,
+    /// Mask applied to the offset read at `offoff`
+    pub offmask: u16,
+    /// Offset added to the start of the keys
+    pub off: u16,
+    /// Position of the variable offset, relative to the current header
+    pub offoff: i16,
+    /// Position of the 32 bits word used to compute the hash, relative to the
+    /// current header
+    pub hoff: i16,
+    /// Mask applied to the hashed word
+    pub hmask: u32,
+    /// Keys that all have to match. Their number is the `nkeys` field of the
+    /// selector.
+    pub keys: Vec<TcU32Key>,
+}
+
+pub const TC_U32_SEL_LEN: usize = 16;
+
+buffer!(TcU32SelBuffer(TC_U32_SEL_LEN) {
+    flags: (u8, 0),
+    offshift: (u8, 1),
+    nkeys: (u8, 2),
+    pad: (u8, 3),
+    offmask: (slice, 4..6),
+    off: (u16, 6..8),
+    offoff: (i16, 8..10),
+    hoff: (i16, 10..12),
+    hmask: (slice, 12..16),
+    keys: (slice, TC_U32_SEL_LEN..),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcU32SelBuffer<&'a T>> for TcU32Sel {
+    fn parse(buf: &TcU32SelBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let nkeys = buf.nkeys() as usize;
+        let payload = buf.keys();
+        if payload.len() < nkeys * TC_U32_KEY_LEN {
+            return Err(format!(
+                "expected {} keys but only got {} bytes",
+                nkeys,
+                payload.len()
+            )
+            .into());
+        }
+        let mut keys = Vec::with_capacity(nkeys);
+        for chunk in payload.chunks_exact(TC_U32_KEY_LEN).take(nkeys) {
+            keys.push(TcU32Key::parse(&TcU32KeyBuffer::new(chunk))?);
+        }
+        Ok(Self {
+            flags: buf.flags(),
+            offshift: buf.offshift(),
+            offmask: BigEndian::read_u16(buf.offmask()),
+            off: buf.off(),
+            offoff: buf.offoff(),
+            hoff: buf.hoff(),
+            hmask: BigEndian::read_u32(buf.hmask()),
+            keys,
+        })
+    }
+}
+
+impl Emitable for TcU32Sel {
+    fn buffer_len(&self) -> usize {
+        TC_U32_SEL_LEN + self.keys.len() * TC_U32_KEY_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcU32SelBuffer::new(buffer);
+        buffer.set_flags(self.flags);
+        buffer.set_offshift(self.offshift);
+        buffer.set_nkeys(self.keys.len() as u8);
+        buffer.set_pad(0);
+        BigEndian::write_u16(buffer.offmask_mut(), self.offmask);
+        buffer.set_off(self.off);
+        buffer.set_offoff(self.offoff);
+        buffer.set_hoff(self.hoff);
+        BigEndian::write_u32(buffer.hmask_mut(), self.hmask);
+        for (key, chunk) in self
+            .keys
+            .iter()
+            .zip(buffer.keys_mut().chunks_exact_mut(TC_U32_KEY_LEN))
+        {
+            key.emit(chunk);
+        }
+    }
+}
+
+/// Key of a u32 selector (`struct tc_u32_key`): the packet matches if the 32
+/// bits word at `off` ANDed with `mask` is equal to `val`. The mask and value
+/// are in host byte order, so `10.0.0.0/8` is `val: 0x0a00_0000` and
+/// `mask: 0xff00_0000`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcU32Key {
+    pub mask: u32,
+    pub val: u32,
+    /// Offset of the word, relative to the current header
+    pub off: i32,
+    pub offmask: i32,
+}
+
+pub const TC_U32_KEY_LEN: usize = 16;
+
+buffer!(TcU32KeyBuffer(TC_U32_KEY_LEN) {
+    mask: (slice, 0..4),
+    val: (slice, 4..8),
+    off: (i32, 8..12),
+    offmask: (i32, 12..16),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcU32KeyBuffer<&'a T>> for TcU32Key {
+    fn parse(buf: &TcU32KeyBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            mask: BigEndian::read_u32(buf.mask()),
+            val: BigEndian::read_u32(buf.val()),
+            off: buf.off(),
+            offmask: buf.offmask(),
+        })
+    }
+}
+
+impl Emitable for TcU32Key {
+    fn buffer_len(&self) -> usize {
+        TC_U32_KEY_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcU32KeyBuffer::new(buffer);
+        BigEndian::write_u32(buffer.mask_mut(), self.mask);
+        BigEndian::write_u32(buffer.val_mut(), self.val);
+        buffer.set_off(self.off);
+        buffer.set_offmask(self.offmask);
+    }
+}
+
+/// Firewall mark match (`struct tc_u32_mark`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcU32Mark {
+    pub val: u32,
+    pub mask: u32,
+    /// Number of packets that matched, set by the kernel
+    pub success: u32,
+}
+
+pub const TC_U32_MARK_LEN: usize = 12;
+
+buffer!(TcU32MarkBuffer(TC_U32_MARK_LEN) {
+    val: (u32, 0..4),
+    mask: (u32, 4..8),
+    success: (u32, 8..12),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcU32MarkBuffer<T>> for TcU32Mark {
+    fn parse(buf: &TcU32MarkBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            val: buf.val(),
+            mask: buf.mask(),
+            success: buf.success(),
+        })
+    }
+}
+
+impl Emitable for TcU32Mark {
+    fn buffer_len(&self) -> usize {
+        TC_U32_MARK_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcU32MarkBuffer::new(buffer);
+        buffer.set_val(self.val);
+        buffer.set_mask(self.mask);
+        buffer.set_success(self.success);
+    }
+}
+
+/// Performance counters of a u32 filter (`struct tc_u32_pcnt`), only dumped
+/// by kernels built with `CONFIG_CLS_U32_PERF`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcU32Pcnt {
+    /// Number of packets the filter was run on
+    pub rcnt: u64,
+    /// Number of packets that matched
+    pub rhit: u64,
+    /// Number of packets that matched each key
+    pub kcnts: Vec<u64>,
+}
+
+pub const TC_U32_PCNT_LEN: usize = 16;
+
+buffer!(TcU32PcntBuffer(TC_U32_PCNT_LEN) {
+    rcnt: (u64, 0..8),
+    rhit: (u64, 8..16),
+    kcnts: (slice, TC_U32_PCNT_LEN..),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcU32PcntBuffer<&'a T>> for TcU32Pcnt {
+    fn parse(buf: &TcU32PcntBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let kcnts = buf.kcnts();
+        if !kcnts.chunks_exact(8).remainder().is_empty() {
+            return Err(format!("invalid key counters length {}", kcnts.len()).into());
+        }
+        Ok(Self {
+            rcnt: buf.rcnt(),
+            rhit: buf.rhit(),
+            kcnts: kcnts.chunks_exact(8).map(NativeEndian::read_u64).collect(),
+        })
+    }
+}
+
+impl Emitable for TcU32Pcnt {
+    fn buffer_len(&self) -> usize {
+        TC_U32_PCNT_LEN + self.kcnts.len() * 8
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcU32PcntBuffer::new(buffer);
+        buffer.set_rcnt(self.rcnt);
+        buffer.set_rhit(self.rhit);
+        NativeEndian::write_u64_into(&self.kcnts, buffer.kcnts_mut());
+    }
+}
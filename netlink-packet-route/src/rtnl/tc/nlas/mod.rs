@@ -9,50 +9,78 @@ pub use self::stats_queue::*;
 mod stats_basic;
 pub use self::stats_basic::*;
 
+mod stats_rate_est;
+pub use self::stats_rate_est::*;
+
+mod rate_spec;
+pub use self::rate_spec::*;
+
+mod xstats;
+pub use self::xstats::*;
+
+pub mod qdisc;
+pub use self::qdisc::Qdisc;
+
+pub mod class;
+pub use self::class::Class;
+
+pub mod filter;
+pub use self::filter::Filter;
+
+pub mod action;
+pub use self::action::TcAction;
+
+use byteorder::{ByteOrder, NativeEndian};
+
 use crate::{
     constants::*,
     nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
-    parsers::{parse_string, parse_u8},
-    traits::{Emitable, Parseable},
+    parsers::{parse_string, parse_u32, parse_u8},
+    traits::{Emitable, Parseable, ParseableParametrized},
     DecodeError,
 };
 
+/// A TC attribute. `A` is the type of the `TCA_OPTIONS` payload, which
+/// depends on the kind of object described by the message: [`Qdisc`],
+/// [`Class`] or [`Filter`].
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub enum Nla {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Nla<A> {
     /// Unspecified
     Unspec(Vec<u8>),
     /// Name of queueing discipline
     Kind(String),
     /// Qdisc-specific options follow
-    Options(Vec<u8>),
+    Options(A),
     /// Qdisc statistics
     Stats(Stats),
     /// Module-specific statistics
-    XStats(Vec<u8>),
+    XStats(XStats),
     /// Rate limit
     Rate(Vec<u8>),
     Fcnt(Vec<u8>),
     Stats2(Vec<Stats2>),
     Stab(Vec<u8>),
-    Chain(Vec<u8>),
+    /// Index of the filter chain
+    Chain(u32),
     HwOffload(u8),
     Other(DefaultNla),
 }
 
-impl nlas::Nla for Nla {
+impl<A: Emitable> nlas::Nla for Nla<A> {
     #[rustfmt::skip]
     fn value_len(&self) -> usize {
         use self::Nla::*;
         match *self {
             // Vec<u8>
             Unspec(ref bytes)
-                | Options(ref bytes)
-                | XStats(ref bytes)
                 | Rate(ref bytes)
                 | Fcnt(ref bytes)
-                | Stab(ref bytes)
-                | Chain(ref bytes) => bytes.len(),
+                | Stab(ref bytes) => bytes.len(),
+            XStats(ref stats) => stats.buffer_len(),
+            Chain(_) => 4,
             HwOffload(_) => 1,
+            Options(ref opts) => opts.buffer_len(),
             Stats2(ref thing) => thing.as_slice().buffer_len(),
             Stats(_) => STATS_LEN,
             Kind(ref string) => string.as_bytes().len() + 1,
@@ -68,19 +96,19 @@ impl nlas::Nla for Nla {
         match *self {
             // Vec<u8>
             Unspec(ref bytes)
-                | Options(ref bytes)
-                | XStats(ref bytes)
                 | Rate(ref bytes)
                 | Fcnt(ref bytes)
-                | Stab(ref bytes)
-                | Chain(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+                | Stab(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            XStats(ref stats) => stats.emit(buffer),
 
+            Chain(ref val) => NativeEndian::write_u32(buffer, *val),
             HwOffload(ref val) => buffer[0] = *val,
+            Options(ref opts) => opts.emit(buffer),
             Stats2(ref stats) => stats.as_slice().emit(buffer),
             Stats(ref stats) => stats.emit(buffer),
 
             Kind(ref string) => {
-                buffer.copy_from_slice(string.as_bytes());
+                buffer[..string.as_bytes().len()].copy_from_slice(string.as_bytes());
                 buffer[string.as_bytes().len()] = 0;
             }
 
@@ -108,26 +136,32 @@ impl nlas::Nla for Nla {
     }
 }
 
-impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
-    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+// The `TCA_OPTIONS` payload can only be interpreted once the `TCA_KIND`
+// attribute that precedes it is known, so the kind is passed as parameter.
+impl<'a, 'b, T, A> ParseableParametrized<NlaBuffer<&'a T>, &'b str> for Nla<A>
+where
+    T: AsRef<[u8]> + ?Sized,
+    A: ParseableParametrized<NlaBuffer<&'a T>, &'b str>,
+{
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, kind: &'b str) -> Result<Self, DecodeError> {
         let payload = buf.value();
         Ok(match buf.kind() {
             TCA_UNSPEC => Self::Unspec(payload.to_vec()),
             TCA_KIND => Self::Kind(parse_string(payload)?),
-            TCA_OPTIONS => Self::Options(payload.to_vec()),
+            TCA_OPTIONS => Self::Options(A::parse_with_param(buf, kind)?),
             TCA_STATS => Self::Stats(Stats::parse(&StatsBuffer::new_checked(payload)?)?),
-            TCA_XSTATS => Self::XStats(payload.to_vec()),
+            TCA_XSTATS => Self::XStats(XStats::parse_with_kind(payload, kind)?),
             TCA_RATE => Self::Rate(payload.to_vec()),
             TCA_FCNT => Self::Fcnt(payload.to_vec()),
             TCA_STATS2 => {
                 let mut nlas = vec![];
                 for nla in NlasIterator::new(payload) {
-                    nlas.push(Stats2::parse(&(nla?))?);
+                    nlas.push(Stats2::parse_with_param(&(nla?), kind)?);
                 }
                 Self::Stats2(nlas)
             }
             TCA_STAB => Self::Stab(payload.to_vec()),
-            TCA_CHAIN => Self::Chain(payload.to_vec()),
+            TCA_CHAIN => Self::Chain(parse_u32(payload)?),
             TCA_HW_OFFLOAD => Self::HwOffload(parse_u8(payload)?),
             _ => Self::Other(DefaultNla::parse(buf)?),
         })
@@ -135,10 +169,13 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Nla {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Stats2 {
-    StatsApp(Vec<u8>),
+    StatsApp(XStats),
     StatsBasic(Vec<u8>),
     StatsQueue(Vec<u8>),
+    RateEst(TcRateEst),
+    RateEst64(TcRateEst64),
     Other(DefaultNla),
 }
 
@@ -146,7 +183,10 @@ impl nlas::Nla for Stats2 {
     fn value_len(&self) -> usize {
         use self::Stats2::*;
         match *self {
-            StatsBasic(ref bytes) | StatsQueue(ref bytes) | StatsApp(ref bytes) => bytes.len(),
+            StatsBasic(ref bytes) | StatsQueue(ref bytes) => bytes.len(),
+            StatsApp(ref stats) => stats.buffer_len(),
+            RateEst(ref est) => est.buffer_len(),
+            RateEst64(ref est) => est.buffer_len(),
             Other(ref nla) => nla.value_len(),
         }
     }
@@ -154,9 +194,12 @@ impl nlas::Nla for Stats2 {
     fn emit_value(&self, buffer: &mut [u8]) {
         use self::Stats2::*;
         match *self {
-            StatsBasic(ref bytes) | StatsQueue(ref bytes) | StatsApp(ref bytes) => {
+            StatsBasic(ref bytes) | StatsQueue(ref bytes) => {
                 buffer.copy_from_slice(bytes.as_slice())
             }
+            StatsApp(ref stats) => stats.emit(buffer),
+            RateEst(ref est) => est.emit(buffer),
+            RateEst64(ref est) => est.emit(buffer),
             Other(ref nla) => nla.emit_value(buffer),
         }
     }
@@ -167,19 +210,42 @@ impl nlas::Nla for Stats2 {
             StatsApp(_) => TCA_STATS_APP,
             StatsBasic(_) => TCA_STATS_BASIC,
             StatsQueue(_) => TCA_STATS_QUEUE,
+            RateEst(_) => TCA_STATS_RATE_EST,
+            RateEst64(_) => TCA_STATS_RATE_EST64,
             Other(ref nla) => nla.kind(),
         }
     }
 }
 
-impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for Stats2 {
-    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+impl<'a, 'b, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, &'b str> for Stats2 {
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, kind: &'b str) -> Result<Self, DecodeError> {
         let payload = buf.value();
         Ok(match buf.kind() {
-            TCA_STATS_APP => Self::StatsApp(payload.to_vec()),
+            TCA_STATS_APP => Self::StatsApp(XStats::parse_with_kind(payload, kind)?),
             TCA_STATS_BASIC => Self::StatsBasic(payload.to_vec()),
             TCA_STATS_QUEUE => Self::StatsQueue(payload.to_vec()),
+            TCA_STATS_RATE_EST => {
+                Self::RateEst(TcRateEst::parse(&TcRateEstBuffer::new_checked(payload)?)?)
+            }
+            TCA_STATS_RATE_EST64 => Self::RateEst64(TcRateEst64::parse(
+                &TcRateEst64Buffer::new_checked(payload)?,
+            )?),
             _ => Self::Other(DefaultNla::parse(buf)?),
         })
     }
 }
+
+// Rate tables and other arrays of 32 bits words used by qdisc options
+pub(crate) fn parse_u32_list(payload: &[u8]) -> Result<Vec<u32>, DecodeError> {
+    let chunks = payload.chunks_exact(4);
+    if !chunks.remainder().is_empty() {
+        return Err(format!("invalid u32 array: {:?}", payload).into());
+    }
+    chunks.map(parse_u32).collect()
+}
+
+pub(crate) fn emit_u32_list(buffer: &mut [u8], values: &[u32]) {
+    for (i, value) in values.iter().enumerate() {
+        NativeEndian::write_u32(&mut buffer[i * 4..(i + 1) * 4], *value);
+    }
+}
@@ -0,0 +1,645 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_i32, parse_u32, parse_u64},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "cake";
+
+/// Options of the CAKE (Common Applications Kept Enhanced) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CakeQdisc {
+    pub nlas: Vec<CakeQdiscNla>,
+}
+
+impl Emitable for CakeQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CakeQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid cake qdisc nla")?;
+            nlas.push(CakeQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CakeDiffservMode {
+    /// Three tins: bulk, best effort and voice
+    Diffserv3,
+    /// Four tins: bulk, best effort, video and voice
+    Diffserv4,
+    /// Eight tins, one for each precedence
+    Diffserv8,
+    /// A single tin, the DSCP is ignored
+    Besteffort,
+    /// Eight tins, based on the legacy IP precedence field
+    Precedence,
+    Other(u32),
+}
+
+impl From<u32> for CakeDiffservMode {
+    fn from(value: u32) -> Self {
+        use self::CakeDiffservMode::*;
+        match value {
+            CAKE_DIFFSERV_DIFFSERV3 => Diffserv3,
+            CAKE_DIFFSERV_DIFFSERV4 => Diffserv4,
+            CAKE_DIFFSERV_DIFFSERV8 => Diffserv8,
+            CAKE_DIFFSERV_BESTEFFORT => Besteffort,
+            CAKE_DIFFSERV_PRECEDENCE => Precedence,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<CakeDiffservMode> for u32 {
+    fn from(value: CakeDiffservMode) -> Self {
+        use self::CakeDiffservMode::*;
+        match value {
+            Diffserv3 => CAKE_DIFFSERV_DIFFSERV3,
+            Diffserv4 => CAKE_DIFFSERV_DIFFSERV4,
+            Diffserv8 => CAKE_DIFFSERV_DIFFSERV8,
+            Besteffort => CAKE_DIFFSERV_BESTEFFORT,
+            Precedence => CAKE_DIFFSERV_PRECEDENCE,
+            Other(other) => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CakeFlowMode {
+    /// All the traffic goes to a single queue
+    None,
+    /// Flows are told apart by source address
+    SrcIp,
+    /// Flows are told apart by destination address
+    DstIp,
+    /// Flows are told apart by source and destination addresses
+    Hosts,
+    /// Flows are told apart by their 5-tuple
+    Flows,
+    /// Fairness between sources first, then between flows
+    DualSrcHost,
+    /// Fairness between destinations first, then between flows
+    DualDstHost,
+    /// Fairness between sources and destinations first, then between flows
+    TripleIsolate,
+    Other(u32),
+}
+
+impl From<u32> for CakeFlowMode {
+    fn from(value: u32) -> Self {
+        use self::CakeFlowMode::*;
+        match value {
+            CAKE_FLOW_NONE => None,
+            CAKE_FLOW_SRC_IP => SrcIp,
+            CAKE_FLOW_DST_IP => DstIp,
+            CAKE_FLOW_HOSTS => Hosts,
+            CAKE_FLOW_FLOWS => Flows,
+            CAKE_FLOW_DUAL_SRC => DualSrcHost,
+            CAKE_FLOW_DUAL_DST => DualDstHost,
+            CAKE_FLOW_TRIPLE => TripleIsolate,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<CakeFlowMode> for u32 {
+    fn from(value: CakeFlowMode) -> Self {
+        use self::CakeFlowMode::*;
+        match value {
+            None => CAKE_FLOW_NONE,
+            SrcIp => CAKE_FLOW_SRC_IP,
+            DstIp => CAKE_FLOW_DST_IP,
+            Hosts => CAKE_FLOW_HOSTS,
+            Flows => CAKE_FLOW_FLOWS,
+            DualSrcHost => CAKE_FLOW_DUAL_SRC,
+            DualDstHost => CAKE_FLOW_DUAL_DST,
+            TripleIsolate => CAKE_FLOW_TRIPLE,
+            Other(other) => other,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CakeQdiscNla {
+    /// Shaper rate, in bytes per second. 0 means unlimited.
+    BaseRate64(u64),
+    DiffservMode(CakeDiffservMode),
+    /// One of the `CAKE_ATM_*` constants
+    Atm(u32),
+    FlowMode(CakeFlowMode),
+    /// Per-packet overhead, in bytes
+    Overhead(i32),
+    /// Expected round trip time, in microseconds
+    Rtt(u32),
+    /// Target queue delay, in microseconds
+    Target(u32),
+    /// Whether the shaper rate is estimated from the ingress traffic
+    AutorateIngress(bool),
+    /// Memory limit, in bytes
+    Memory(u32),
+    /// Whether NAT lookups are performed to find out the real hosts
+    Nat(bool),
+    /// Present when overhead compensation is disabled
+    Raw,
+    /// Whether the DSCP is cleared on ingress
+    Wash(bool),
+    /// Minimum packet size, in bytes
+    Mpu(u32),
+    /// Whether the qdisc is used on ingress traffic
+    Ingress(bool),
+    /// One of the `CAKE_ACK_*` constants
+    AckFilter(u32),
+    /// Whether GSO super-packets are split
+    SplitGso(bool),
+    /// Mask applied to the firewall mark to select the tin
+    Fwmark(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for CakeQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::CakeQdiscNla::*;
+        match self {
+            BaseRate64(_) => 8,
+            DiffservMode(_) | Atm(_) | FlowMode(_) | Overhead(_) | Rtt(_) | Target(_)
+            | AutorateIngress(_) | Memory(_) | Nat(_) | Raw | Wash(_) | Mpu(_) | Ingress(_)
+            | AckFilter(_) | SplitGso(_) | Fwmark(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::CakeQdiscNla::*;
+        match self {
+            BaseRate64(ref value) => NativeEndian::write_u64(buffer, *value),
+            DiffservMode(ref mode) => NativeEndian::write_u32(buffer, (*mode).into()),
+            FlowMode(ref mode) => NativeEndian::write_u32(buffer, (*mode).into()),
+            Overhead(ref value) => NativeEndian::write_i32(buffer, *value),
+            Atm(ref value) | Rtt(ref value) | Target(ref value) | Memory(ref value)
+            | Mpu(ref value) | AckFilter(ref value) | Fwmark(ref value) => {
+                NativeEndian::write_u32(buffer, *value)
+            }
+            AutorateIngress(ref value)
+            | Nat(ref value)
+            | Wash(ref value)
+            | Ingress(ref value)
+            | SplitGso(ref value) => NativeEndian::write_u32(buffer, *value as u32),
+            Raw => NativeEndian::write_u32(buffer, 0),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::CakeQdiscNla::*;
+        match self {
+            BaseRate64(_) => TCA_CAKE_BASE_RATE64,
+            DiffservMode(_) => TCA_CAKE_DIFFSERV_MODE,
+            Atm(_) => TCA_CAKE_ATM,
+            FlowMode(_) => TCA_CAKE_FLOW_MODE,
+            Overhead(_) => TCA_CAKE_OVERHEAD,
+            Rtt(_) => TCA_CAKE_RTT,
+            Target(_) => TCA_CAKE_TARGET,
+            AutorateIngress(_) => TCA_CAKE_AUTORATE,
+            Memory(_) => TCA_CAKE_MEMORY,
+            Nat(_) => TCA_CAKE_NAT,
+            Raw => TCA_CAKE_RAW,
+            Wash(_) => TCA_CAKE_WASH,
+            Mpu(_) => TCA_CAKE_MPU,
+            Ingress(_) => TCA_CAKE_INGRESS,
+            AckFilter(_) => TCA_CAKE_ACK_FILTER,
+            SplitGso(_) => TCA_CAKE_SPLIT_GSO,
+            Fwmark(_) => TCA_CAKE_FWMARK,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CakeQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::CakeQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CAKE_BASE_RATE64 => {
+                BaseRate64(parse_u64(payload).context("invalid TCA_CAKE_BASE_RATE64 value")?)
+            }
+            TCA_CAKE_DIFFSERV_MODE => DiffservMode(
+                parse_u32(payload)
+                    .context("invalid TCA_CAKE_DIFFSERV_MODE value")?
+                    .into(),
+            ),
+            TCA_CAKE_ATM => Atm(parse_u32(payload).context("invalid TCA_CAKE_ATM value")?),
+            TCA_CAKE_FLOW_MODE => FlowMode(
+                parse_u32(payload)
+                    .context("invalid TCA_CAKE_FLOW_MODE value")?
+                    .into(),
+            ),
+            TCA_CAKE_OVERHEAD => {
+                Overhead(parse_i32(payload).context("invalid TCA_CAKE_OVERHEAD value")?)
+            }
+            TCA_CAKE_RTT => Rtt(parse_u32(payload).context("invalid TCA_CAKE_RTT value")?),
+            TCA_CAKE_TARGET => Target(parse_u32(payload).context("invalid TCA_CAKE_TARGET value")?),
+            TCA_CAKE_AUTORATE => {
+                AutorateIngress(parse_u32(payload).context("invalid TCA_CAKE_AUTORATE value")? != 0)
+            }
+            TCA_CAKE_MEMORY => Memory(parse_u32(payload).context("invalid TCA_CAKE_MEMORY value")?),
+            TCA_CAKE_NAT => Nat(parse_u32(payload).context("invalid TCA_CAKE_NAT value")? != 0),
+            TCA_CAKE_RAW => Raw,
+            TCA_CAKE_WASH => Wash(parse_u32(payload).context("invalid TCA_CAKE_WASH value")? != 0),
+            TCA_CAKE_MPU => Mpu(parse_u32(payload).context("invalid TCA_CAKE_MPU value")?),
+            TCA_CAKE_INGRESS => {
+                Ingress(parse_u32(payload).context("invalid TCA_CAKE_INGRESS value")? != 0)
+            }
+            TCA_CAKE_ACK_FILTER => {
+                AckFilter(parse_u32(payload).context("invalid TCA_CAKE_ACK_FILTER value")?)
+            }
+            TCA_CAKE_SPLIT_GSO => {
+                SplitGso(parse_u32(payload).context("invalid TCA_CAKE_SPLIT_GSO value")? != 0)
+            }
+            TCA_CAKE_FWMARK => Fwmark(parse_u32(payload).context("invalid TCA_CAKE_FWMARK value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid cake qdisc nla")?),
+        })
+    }
+}
+
+/// CAKE statistics. Unlike most qdiscs, CAKE reports its statistics as
+/// attributes nested in `TCA_STATS_APP`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CakeXStats {
+    pub nlas: Vec<CakeStatsNla>,
+}
+
+impl Emitable for CakeXStats {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl CakeXStats {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(payload) {
+            let nla = nla.context("invalid cake stats nla")?;
+            nlas.push(CakeStatsNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CakeStatsNla {
+    /// Estimated capacity of the link, in bytes per second
+    CapacityEstimate64(u64),
+    MemoryLimit(u32),
+    MemoryUsed(u32),
+    AvgNetoff(u32),
+    MinNetlen(u32),
+    MaxNetlen(u32),
+    MinAdjlen(u32),
+    MaxAdjlen(u32),
+    /// Statistics of each tin
+    TinStats(Vec<CakeTinStats>),
+    Deficit(i32),
+    CobaltCount(u32),
+    Dropping(u32),
+    DropNextUs(i32),
+    PDrop(u32),
+    BlueTimerUs(i32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for CakeStatsNla {
+    fn value_len(&self) -> usize {
+        use self::CakeStatsNla::*;
+        match self {
+            CapacityEstimate64(_) => 8,
+            TinStats(ref tins) => tins.as_slice().buffer_len(),
+            Other(ref nla) => nla.value_len(),
+            _ => 4,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::CakeStatsNla::*;
+        match self {
+            CapacityEstimate64(ref value) => NativeEndian::write_u64(buffer, *value),
+            MemoryLimit(ref value)
+            | MemoryUsed(ref value)
+            | AvgNetoff(ref value)
+            | MinNetlen(ref value)
+            | MaxNetlen(ref value)
+            | MinAdjlen(ref value)
+            | MaxAdjlen(ref value)
+            | CobaltCount(ref value)
+            | Dropping(ref value)
+            | PDrop(ref value) => NativeEndian::write_u32(buffer, *value),
+            Deficit(ref value) | DropNextUs(ref value) | BlueTimerUs(ref value) => {
+                NativeEndian::write_i32(buffer, *value)
+            }
+            TinStats(ref tins) => tins.as_slice().emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::CakeStatsNla::*;
+        match self {
+            CapacityEstimate64(_) => TCA_CAKE_STATS_CAPACITY_ESTIMATE64,
+            MemoryLimit(_) => TCA_CAKE_STATS_MEMORY_LIMIT,
+            MemoryUsed(_) => TCA_CAKE_STATS_MEMORY_USED,
+            AvgNetoff(_) => TCA_CAKE_STATS_AVG_NETOFF,
+            MinNetlen(_) => TCA_CAKE_STATS_MIN_NETLEN,
+            MaxNetlen(_) => TCA_CAKE_STATS_MAX_NETLEN,
+            MinAdjlen(_) => TCA_CAKE_STATS_MIN_ADJLEN,
+            MaxAdjlen(_) => TCA_CAKE_STATS_MAX_ADJLEN,
+            TinStats(_) => TCA_CAKE_STATS_TIN_STATS,
+            Deficit(_) => TCA_CAKE_STATS_DEFICIT,
+            CobaltCount(_) => TCA_CAKE_STATS_COBALT_COUNT,
+            Dropping(_) => TCA_CAKE_STATS_DROPPING,
+            DropNextUs(_) => TCA_CAKE_STATS_DROP_NEXT_US,
+            PDrop(_) => TCA_CAKE_STATS_P_DROP,
+            BlueTimerUs(_) => TCA_CAKE_STATS_BLUE_TIMER_US,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CakeStatsNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::CakeStatsNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CAKE_STATS_CAPACITY_ESTIMATE64 => CapacityEstimate64(
+                parse_u64(payload).context("invalid TCA_CAKE_STATS_CAPACITY_ESTIMATE64 value")?,
+            ),
+            TCA_CAKE_STATS_MEMORY_LIMIT => MemoryLimit(
+                parse_u32(payload).context("invalid TCA_CAKE_STATS_MEMORY_LIMIT value")?,
+            ),
+            TCA_CAKE_STATS_MEMORY_USED => {
+                MemoryUsed(parse_u32(payload).context("invalid TCA_CAKE_STATS_MEMORY_USED value")?)
+            }
+            TCA_CAKE_STATS_AVG_NETOFF => {
+                AvgNetoff(parse_u32(payload).context("invalid TCA_CAKE_STATS_AVG_NETOFF value")?)
+            }
+            TCA_CAKE_STATS_MIN_NETLEN => {
+                MinNetlen(parse_u32(payload).context("invalid TCA_CAKE_STATS_MIN_NETLEN value")?)
+            }
+            TCA_CAKE_STATS_MAX_NETLEN => {
+                MaxNetlen(parse_u32(payload).context("invalid TCA_CAKE_STATS_MAX_NETLEN value")?)
+            }
+            TCA_CAKE_STATS_MIN_ADJLEN => {
+                MinAdjlen(parse_u32(payload).context("invalid TCA_CAKE_STATS_MIN_ADJLEN value")?)
+            }
+            TCA_CAKE_STATS_MAX_ADJLEN => {
+                MaxAdjlen(parse_u32(payload).context("invalid TCA_CAKE_STATS_MAX_ADJLEN value")?)
+            }
+            TCA_CAKE_STATS_TIN_STATS => {
+                let mut tins = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_CAKE_STATS_TIN_STATS value")?;
+                    tins.push(CakeTinStats::parse(&nla)?);
+                }
+                TinStats(tins)
+            }
+            TCA_CAKE_STATS_DEFICIT => {
+                Deficit(parse_i32(payload).context("invalid TCA_CAKE_STATS_DEFICIT value")?)
+            }
+            TCA_CAKE_STATS_COBALT_COUNT => CobaltCount(
+                parse_u32(payload).context("invalid TCA_CAKE_STATS_COBALT_COUNT value")?,
+            ),
+            TCA_CAKE_STATS_DROPPING => {
+                Dropping(parse_u32(payload).context("invalid TCA_CAKE_STATS_DROPPING value")?)
+            }
+            TCA_CAKE_STATS_DROP_NEXT_US => {
+                DropNextUs(parse_i32(payload).context("invalid TCA_CAKE_STATS_DROP_NEXT_US value")?)
+            }
+            TCA_CAKE_STATS_P_DROP => {
+                PDrop(parse_u32(payload).context("invalid TCA_CAKE_STATS_P_DROP value")?)
+            }
+            TCA_CAKE_STATS_BLUE_TIMER_US => BlueTimerUs(
+                parse_i32(payload).context("invalid TCA_CAKE_STATS_BLUE_TIMER_US value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid cake stats nla")?),
+        })
+    }
+}
+
+/// Statistics of a CAKE tin. Tins are numbered from 1.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CakeTinStats {
+    pub index: u16,
+    pub nlas: Vec<CakeTinStatsNla>,
+}
+
+impl nlas::Nla for CakeTinStats {
+    fn value_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+
+    fn kind(&self) -> u16 {
+        self.index
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CakeTinStats {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid cake tin stats nla")?;
+            nlas.push(CakeTinStatsNla::parse(&nla)?);
+        }
+        Ok(Self {
+            index: buf.kind(),
+            nlas,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CakeTinStatsNla {
+    SentPackets(u32),
+    SentBytes64(u64),
+    DroppedPackets(u32),
+    DroppedBytes64(u64),
+    AcksDroppedPackets(u32),
+    AcksDroppedBytes64(u64),
+    EcnMarkedPackets(u32),
+    EcnMarkedBytes64(u64),
+    BacklogPackets(u32),
+    BacklogBytes(u32),
+    ThresholdRate64(u64),
+    TargetUs(u32),
+    IntervalUs(u32),
+    WayIndirectHits(u32),
+    WayMisses(u32),
+    WayCollisions(u32),
+    PeakDelayUs(u32),
+    AvgDelayUs(u32),
+    BaseDelayUs(u32),
+    SparseFlows(u32),
+    BulkFlows(u32),
+    UnresponsiveFlows(u32),
+    MaxSkblen(u32),
+    FlowQuantum(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for CakeTinStatsNla {
+    fn value_len(&self) -> usize {
+        use self::CakeTinStatsNla::*;
+        match self {
+            SentBytes64(_)
+            | DroppedBytes64(_)
+            | AcksDroppedBytes64(_)
+            | EcnMarkedBytes64(_)
+            | ThresholdRate64(_) => 8,
+            Other(ref nla) => nla.value_len(),
+            _ => 4,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::CakeTinStatsNla::*;
+        match self {
+            SentBytes64(ref value)
+            | DroppedBytes64(ref value)
+            | AcksDroppedBytes64(ref value)
+            | EcnMarkedBytes64(ref value)
+            | ThresholdRate64(ref value) => NativeEndian::write_u64(buffer, *value),
+            SentPackets(ref value)
+            | DroppedPackets(ref value)
+            | AcksDroppedPackets(ref value)
+            | EcnMarkedPackets(ref value)
+            | BacklogPackets(ref value)
+            | BacklogBytes(ref value)
+            | TargetUs(ref value)
+            | IntervalUs(ref value)
+            | WayIndirectHits(ref value)
+            | WayMisses(ref value)
+            | WayCollisions(ref value)
+            | PeakDelayUs(ref value)
+            | AvgDelayUs(ref value)
+            | BaseDelayUs(ref value)
+            | SparseFlows(ref value)
+            | BulkFlows(ref value)
+            | UnresponsiveFlows(ref value)
+            | MaxSkblen(ref value)
+            | FlowQuantum(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::CakeTinStatsNla::*;
+        match self {
+            SentPackets(_) => TCA_CAKE_TIN_STATS_SENT_PACKETS,
+            SentBytes64(_) => TCA_CAKE_TIN_STATS_SENT_BYTES64,
+            DroppedPackets(_) => TCA_CAKE_TIN_STATS_DROPPED_PACKETS,
+            DroppedBytes64(_) => TCA_CAKE_TIN_STATS_DROPPED_BYTES64,
+            AcksDroppedPackets(_) => TCA_CAKE_TIN_STATS_ACKS_DROPPED_PACKETS,
+            AcksDroppedBytes64(_) => TCA_CAKE_TIN_STATS_ACKS_DROPPED_BYTES64,
+            EcnMarkedPackets(_) => TCA_CAKE_TIN_STATS_ECN_MARKED_PACKETS,
+            EcnMarkedBytes64(_) => TCA_CAKE_TIN_STATS_ECN_MARKED_BYTES64,
+            BacklogPackets(_) => TCA_CAKE_TIN_STATS_BACKLOG_PACKETS,
+            BacklogBytes(_) => TCA_CAKE_TIN_STATS_BACKLOG_BYTES,
+            ThresholdRate64(_) => TCA_CAKE_TIN_STATS_THRESHOLD_RATE64,
+            TargetUs(_) => TCA_CAKE_TIN_STATS_TARGET_US,
+            IntervalUs(_) => TCA_CAKE_TIN_STATS_INTERVAL_US,
+            WayIndirectHits(_) => TCA_CAKE_TIN_STATS_WAY_INDIRECT_HITS,
+            WayMisses(_) => TCA_CAKE_TIN_STATS_WAY_MISSES,
+            WayCollisions(_) => TCA_CAKE_TIN_STATS_WAY_COLLISIONS,
+            PeakDelayUs(_) => TCA_CAKE_TIN_STATS_PEAK_DELAY_US,
+            AvgDelayUs(_) => TCA_CAKE_TIN_STATS_AVG_DELAY_US,
+            BaseDelayUs(_) => TCA_CAKE_TIN_STATS_BASE_DELAY_US,
+            SparseFlows(_) => TCA_CAKE_TIN_STATS_SPARSE_FLOWS,
+            BulkFlows(_) => TCA_CAKE_TIN_STATS_BULK_FLOWS,
+            UnresponsiveFlows(_) => TCA_CAKE_TIN_STATS_UNRESPONSIVE_FLOWS,
+            MaxSkblen(_) => TCA_CAKE_TIN_STATS_MAX_SKBLEN,
+            FlowQuantum(_) => TCA_CAKE_TIN_STATS_FLOW_QUANTUM,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CakeTinStatsNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::CakeTinStatsNla::*;
+        let payload = buf.value();
+        let err = "invalid cake tin stats nla";
+        Ok(match buf.kind() {
+            TCA_CAKE_TIN_STATS_SENT_PACKETS => SentPackets(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_SENT_BYTES64 => SentBytes64(parse_u64(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_DROPPED_PACKETS => DroppedPackets(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_DROPPED_BYTES64 => DroppedBytes64(parse_u64(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_ACKS_DROPPED_PACKETS => {
+                AcksDroppedPackets(parse_u32(payload).context(err)?)
+            }
+            TCA_CAKE_TIN_STATS_ACKS_DROPPED_BYTES64 => {
+                AcksDroppedBytes64(parse_u64(payload).context(err)?)
+            }
+            TCA_CAKE_TIN_STATS_ECN_MARKED_PACKETS => {
+                EcnMarkedPackets(parse_u32(payload).context(err)?)
+            }
+            TCA_CAKE_TIN_STATS_ECN_MARKED_BYTES64 => {
+                EcnMarkedBytes64(parse_u64(payload).context(err)?)
+            }
+            TCA_CAKE_TIN_STATS_BACKLOG_PACKETS => BacklogPackets(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_BACKLOG_BYTES => BacklogBytes(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_THRESHOLD_RATE64 => {
+                ThresholdRate64(parse_u64(payload).context(err)?)
+            }
+            TCA_CAKE_TIN_STATS_TARGET_US => TargetUs(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_INTERVAL_US => IntervalUs(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_WAY_INDIRECT_HITS => {
+                WayIndirectHits(parse_u32(payload).context(err)?)
+            }
+            TCA_CAKE_TIN_STATS_WAY_MISSES => WayMisses(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_WAY_COLLISIONS => WayCollisions(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_PEAK_DELAY_US => PeakDelayUs(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_AVG_DELAY_US => AvgDelayUs(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_BASE_DELAY_US => BaseDelayUs(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_SPARSE_FLOWS => SparseFlows(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_BULK_FLOWS => BulkFlows(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_UNRESPONSIVE_FLOWS => {
+                UnresponsiveFlows(parse_u32(payload).context(err)?)
+            }
+            TCA_CAKE_TIN_STATS_MAX_SKBLEN => MaxSkblen(parse_u32(payload).context(err)?),
+            TCA_CAKE_TIN_STATS_FLOW_QUANTUM => FlowQuantum(parse_u32(payload).context(err)?),
+            _ => Other(DefaultNla::parse(buf).context(err)?),
+        })
+    }
+}
@@ -0,0 +1,440 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::super::{emit_u32_list, parse_u32_list, TcRateSpec, TcRateSpecBuffer};
+
+pub const KIND: &str = "cbq";
+
+/// Options of the CBQ (Class Based Queueing) qdisc. They are the options of
+/// its root class, see [`CbqClass`](crate::nlas::tc::class::CbqClass).
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CbqQdisc {
+    pub nlas: Vec<CbqNla>,
+}
+
+impl Emitable for CbqQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CbqQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid cbq qdisc nla")?;
+            nlas.push(CbqNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+/// Attributes of the CBQ qdisc and classes
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CbqNla {
+    /// Link sharing parameters
+    LssOpt(TcCbqLss),
+    /// Weighted round robin parameters
+    WrrOpt(TcCbqWrr),
+    /// Filter options
+    Fopt(TcCbqFopt),
+    /// Overlimit strategy
+    OvlStrategy(TcCbqOvl),
+    /// Rate of the class
+    Rate(TcRateSpec),
+    /// Rate table of `rate`, see [`TcRateSpec::rate_table`]
+    Rtab(Vec<u32>),
+    /// Reshaping strategy
+    Police(TcCbqPolice),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for CbqNla {
+    fn value_len(&self) -> usize {
+        use self::CbqNla::*;
+        match self {
+            LssOpt(_) => TC_CBQ_LSS_LEN,
+            WrrOpt(_) => TC_CBQ_WRR_LEN,
+            Fopt(_) => TC_CBQ_FOPT_LEN,
+            OvlStrategy(_) => TC_CBQ_OVL_LEN,
+            Rate(ref rate) => rate.buffer_len(),
+            Rtab(ref table) => table.len() * 4,
+            Police(_) => TC_CBQ_POLICE_LEN,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::CbqNla::*;
+        match self {
+            LssOpt(ref lss) => lss.emit(buffer),
+            WrrOpt(ref wrr) => wrr.emit(buffer),
+            Fopt(ref fopt) => fopt.emit(buffer),
+            OvlStrategy(ref ovl) => ovl.emit(buffer),
+            Rate(ref rate) => rate.emit(buffer),
+            Rtab(ref table) => emit_u32_list(buffer, table),
+            Police(ref police) => police.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::CbqNla::*;
+        match self {
+            LssOpt(_) => TCA_CBQ_LSSOPT,
+            WrrOpt(_) => TCA_CBQ_WRROPT,
+            Fopt(_) => TCA_CBQ_FOPT,
+            OvlStrategy(_) => TCA_CBQ_OVL_STRATEGY,
+            Rate(_) => TCA_CBQ_RATE,
+            Rtab(_) => TCA_CBQ_RTAB,
+            Police(_) => TCA_CBQ_POLICE,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CbqNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::CbqNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CBQ_LSSOPT => LssOpt(
+                TcCbqLss::parse(&TcCbqLssBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CBQ_LSSOPT value")?,
+            ),
+            TCA_CBQ_WRROPT => WrrOpt(
+                TcCbqWrr::parse(&TcCbqWrrBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CBQ_WRROPT value")?,
+            ),
+            TCA_CBQ_FOPT => Fopt(
+                TcCbqFopt::parse(&TcCbqFoptBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CBQ_FOPT value")?,
+            ),
+            TCA_CBQ_OVL_STRATEGY => OvlStrategy(
+                TcCbqOvl::parse(&TcCbqOvlBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CBQ_OVL_STRATEGY value")?,
+            ),
+            TCA_CBQ_RATE => Rate(
+                TcRateSpec::parse(&TcRateSpecBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CBQ_RATE value")?,
+            ),
+            TCA_CBQ_RTAB => Rtab(parse_u32_list(payload).context("invalid TCA_CBQ_RTAB value")?),
+            TCA_CBQ_POLICE => Police(
+                TcCbqPolice::parse(&TcCbqPoliceBuffer::new_checked(payload)?)
+                    .context("invalid TCA_CBQ_POLICE value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid cbq nla")?),
+        })
+    }
+}
+
+/// Link sharing parameters (`struct tc_cbq_lssopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcCbqLss {
+    /// `TCF_CBQ_LSS_*` flags telling which of the fields below are set
+    pub change: u8,
+    /// `TCF_CBQ_LSS_BOUNDED` and `TCF_CBQ_LSS_ISOLATED` flags
+    pub flags: u8,
+    /// Log2 of the weight of the idle time in its moving average
+    pub ewma_log: u8,
+    /// Level of the class in the hierarchy, set by the kernel
+    pub level: u8,
+    /// Upper bound of the average idle time, in scheduler ticks
+    pub maxidle: u32,
+    /// Opposite of the lower bound of the average idle time, in scheduler
+    /// ticks
+    pub minidle: u32,
+    /// Time the class is throttled for when it is overlimit, in scheduler
+    /// ticks
+    pub offtime: u32,
+    /// Average packet size, in bytes
+    pub avpkt: u32,
+}
+
+pub const TC_CBQ_LSS_LEN: usize = 20;
+
+buffer!(TcCbqLssBuffer(TC_CBQ_LSS_LEN) {
+    change: (u8, 0),
+    flags: (u8, 1),
+    ewma_log: (u8, 2),
+    level: (u8, 3),
+    maxidle: (u32, 4..8),
+    minidle: (u32, 8..12),
+    offtime: (u32, 12..16),
+    avpkt: (u32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcCbqLssBuffer<T>> for TcCbqLss {
+    fn parse(buf: &TcCbqLssBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            change: buf.change(),
+            flags: buf.flags(),
+            ewma_log: buf.ewma_log(),
+            level: buf.level(),
+            maxidle: buf.maxidle(),
+            minidle: buf.minidle(),
+            offtime: buf.offtime(),
+            avpkt: buf.avpkt(),
+        })
+    }
+}
+
+impl Emitable for TcCbqLss {
+    fn buffer_len(&self) -> usize {
+        TC_CBQ_LSS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcCbqLssBuffer::new(buffer);
+        buffer.set_change(self.change);
+        buffer.set_flags(self.flags);
+        buffer.set_ewma_log(self.ewma_log);
+        buffer.set_level(self.level);
+        buffer.set_maxidle(self.maxidle);
+        buffer.set_minidle(self.minidle);
+        buffer.set_offtime(self.offtime);
+        buffer.set_avpkt(self.avpkt);
+    }
+}
+
+/// Weighted round robin parameters (`struct tc_cbq_wrropt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcCbqWrr {
+    pub flags: u8,
+    /// Priority of the class, from 1 to `TC_CBQ_MAXPRIO`
+    pub priority: u8,
+    /// Priority of the class when it borrows, set by the kernel
+    pub cpriority: u8,
+    /// Number of bytes the class can dequeue per round
+    pub allot: u32,
+    /// Weight of the class in its priority band
+    pub weight: u32,
+}
+
+pub const TC_CBQ_WRR_LEN: usize = 12;
+
+buffer!(TcCbqWrrBuffer(TC_CBQ_WRR_LEN) {
+    flags: (u8, 0),
+    priority: (u8, 1),
+    cpriority: (u8, 2),
+    reserved: (u8, 3),
+    allot: (u32, 4..8),
+    weight: (u32, 8..12),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcCbqWrrBuffer<T>> for TcCbqWrr {
+    fn parse(buf: &TcCbqWrrBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            flags: buf.flags(),
+            priority: buf.priority(),
+            cpriority: buf.cpriority(),
+            allot: buf.allot(),
+            weight: buf.weight(),
+        })
+    }
+}
+
+impl Emitable for TcCbqWrr {
+    fn buffer_len(&self) -> usize {
+        TC_CBQ_WRR_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcCbqWrrBuffer::new(buffer);
+        buffer.set_flags(self.flags);
+        buffer.set_priority(self.priority);
+        buffer.set_cpriority(self.cpriority);
+        buffer.set_reserved(0);
+        buffer.set_allot(self.allot);
+        buffer.set_weight(self.weight);
+    }
+}
+
+/// Overlimit strategy (`struct tc_cbq_ovl`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcCbqOvl {
+    /// One of the `TC_CBQ_OVL_*` strategies
+    pub strategy: u8,
+    /// Priority the packets are moved to with `TC_CBQ_OVL_LOWPRIO`
+    pub priority2: u8,
+    /// Penalty time of the class, in scheduler ticks
+    pub penalty: u32,
+}
+
+pub const TC_CBQ_OVL_LEN: usize = 8;
+
+buffer!(TcCbqOvlBuffer(TC_CBQ_OVL_LEN) {
+    strategy: (u8, 0),
+    priority2: (u8, 1),
+    pad: (u16, 2..4),
+    penalty: (u32, 4..8),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcCbqOvlBuffer<T>> for TcCbqOvl {
+    fn parse(buf: &TcCbqOvlBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            strategy: buf.strategy(),
+            priority2: buf.priority2(),
+            penalty: buf.penalty(),
+        })
+    }
+}
+
+impl Emitable for TcCbqOvl {
+    fn buffer_len(&self) -> usize {
+        TC_CBQ_OVL_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcCbqOvlBuffer::new(buffer);
+        buffer.set_strategy(self.strategy);
+        buffer.set_priority2(self.priority2);
+        buffer.set_pad(0);
+        buffer.set_penalty(self.penalty);
+    }
+}
+
+/// Filter options (`struct tc_cbq_fopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcCbqFopt {
+    /// Handle of the split node
+    pub split: u32,
+    /// Bitmap of the priorities the class is the default class of
+    pub defmap: u32,
+    /// Bitmap of the priorities of `defmap` to change
+    pub defchange: u32,
+}
+
+pub const TC_CBQ_FOPT_LEN: usize = 12;
+
+buffer!(TcCbqFoptBuffer(TC_CBQ_FOPT_LEN) {
+    split: (u32, 0..4),
+    defmap: (u32, 4..8),
+    defchange: (u32, 8..12),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcCbqFoptBuffer<T>> for TcCbqFopt {
+    fn parse(buf: &TcCbqFoptBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            split: buf.split(),
+            defmap: buf.defmap(),
+            defchange: buf.defchange(),
+        })
+    }
+}
+
+impl Emitable for TcCbqFopt {
+    fn buffer_len(&self) -> usize {
+        TC_CBQ_FOPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcCbqFoptBuffer::new(buffer);
+        buffer.set_split(self.split);
+        buffer.set_defmap(self.defmap);
+        buffer.set_defchange(self.defchange);
+    }
+}
+
+/// Reshaping strategy (`struct tc_cbq_police`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcCbqPolice {
+    /// `TC_POLICE_*` action
+    pub police: u8,
+}
+
+pub const TC_CBQ_POLICE_LEN: usize = 4;
+
+buffer!(TcCbqPoliceBuffer(TC_CBQ_POLICE_LEN) {
+    police: (u8, 0),
+    reserved1: (u8, 1),
+    reserved2: (u16, 2..4),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcCbqPoliceBuffer<T>> for TcCbqPolice {
+    fn parse(buf: &TcCbqPoliceBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            police: buf.police(),
+        })
+    }
+}
+
+impl Emitable for TcCbqPolice {
+    fn buffer_len(&self) -> usize {
+        TC_CBQ_POLICE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcCbqPoliceBuffer::new(buffer);
+        buffer.set_police(self.police);
+        buffer.set_reserved1(0);
+        buffer.set_reserved2(0);
+    }
+}
+
+/// CBQ qdisc and class statistics (`struct tc_cbq_xstats`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CbqXStats {
+    /// Number of times the class borrowed from its parents
+    pub borrows: u32,
+    /// Number of times the overlimit strategy was applied
+    pub overactions: u32,
+    /// Average idle time, in scheduler ticks
+    pub avgidle: i32,
+    /// Time until the class is no longer throttled, in scheduler ticks
+    pub undertime: i32,
+}
+
+pub const CBQ_XSTATS_LEN: usize = 16;
+
+buffer!(CbqXStatsBuffer(CBQ_XSTATS_LEN) {
+    borrows: (u32, 0..4),
+    overactions: (u32, 4..8),
+    avgidle: (i32, 8..12),
+    undertime: (i32, 12..16),
+});
+
+impl<T: AsRef<[u8]>> Parseable<CbqXStatsBuffer<T>> for CbqXStats {
+    fn parse(buf: &CbqXStatsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            borrows: buf.borrows(),
+            overactions: buf.overactions(),
+            avgidle: buf.avgidle(),
+            undertime: buf.undertime(),
+        })
+    }
+}
+
+impl Emitable for CbqXStats {
+    fn buffer_len(&self) -> usize {
+        CBQ_XSTATS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = CbqXStatsBuffer::new(buffer);
+        buffer.set_borrows(self.borrows);
+        buffer.set_overactions(self.overactions);
+        buffer.set_avgidle(self.avgidle);
+        buffer.set_undertime(self.undertime);
+    }
+}
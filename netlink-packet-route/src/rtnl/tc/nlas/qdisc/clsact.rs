@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT
+
+pub const KIND: &str = "clsact";
+
+/// The clsact qdisc does not take any option. Like the ingress qdisc, its
+/// parent must be [`TC_H_CLSACT`](crate::constants::TC_H_CLSACT) and its
+/// handle `ffff:0`. Filters are attached to its
+/// [`TC_H_MIN_INGRESS`](crate::constants::TC_H_MIN_INGRESS) and
+/// [`TC_H_MIN_EGRESS`](crate::constants::TC_H_MIN_EGRESS) minor handles.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clsact;
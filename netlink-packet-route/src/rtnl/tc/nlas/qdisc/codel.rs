@@ -0,0 +1,186 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "codel";
+
+/// Options of the CoDel (Controlled Delay) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodelQdisc {
+    pub nlas: Vec<CodelQdiscNla>,
+}
+
+impl Emitable for CodelQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CodelQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid codel qdisc nla")?;
+            nlas.push(CodelQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CodelQdiscNla {
+    /// Acceptable minimum standing queue delay, in microseconds
+    Target(u32),
+    /// Maximum number of packets that can be queued
+    Limit(u32),
+    /// Width of the moving time window, in microseconds
+    Interval(u32),
+    /// Whether packets are ECN marked instead of dropped
+    Ecn(u32),
+    /// Sojourn time above which packets are ECN marked, in microseconds
+    CeThreshold(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for CodelQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::CodelQdiscNla::*;
+        match self {
+            Target(_) | Limit(_) | Interval(_) | Ecn(_) | CeThreshold(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::CodelQdiscNla::*;
+        match self {
+            Target(ref value)
+            | Limit(ref value)
+            | Interval(ref value)
+            | Ecn(ref value)
+            | CeThreshold(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::CodelQdiscNla::*;
+        match self {
+            Target(_) => TCA_CODEL_TARGET,
+            Limit(_) => TCA_CODEL_LIMIT,
+            Interval(_) => TCA_CODEL_INTERVAL,
+            Ecn(_) => TCA_CODEL_ECN,
+            CeThreshold(_) => TCA_CODEL_CE_THRESHOLD,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for CodelQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::CodelQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_CODEL_TARGET => {
+                Target(parse_u32(payload).context("invalid TCA_CODEL_TARGET value")?)
+            }
+            TCA_CODEL_LIMIT => Limit(parse_u32(payload).context("invalid TCA_CODEL_LIMIT value")?),
+            TCA_CODEL_INTERVAL => {
+                Interval(parse_u32(payload).context("invalid TCA_CODEL_INTERVAL value")?)
+            }
+            TCA_CODEL_ECN => Ecn(parse_u32(payload).context("invalid TCA_CODEL_ECN value")?),
+            TCA_CODEL_CE_THRESHOLD => {
+                CeThreshold(parse_u32(payload).context("invalid TCA_CODEL_CE_THRESHOLD value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid codel qdisc nla")?),
+        })
+    }
+}
+
+/// CoDel statistics (`struct tc_codel_xstats`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodelXStats {
+    /// Largest packet seen so far
+    pub maxpacket: u32,
+    /// Number of packets dropped since entering the dropping state
+    pub count: u32,
+    /// Value of `count` when the dropping state was last left
+    pub lastcount: u32,
+    /// Sojourn time of the last dequeued packet, in microseconds
+    pub ldelay: u32,
+    /// Time until the next drop, in microseconds
+    pub drop_next: i32,
+    /// Number of times the packet limit was hit
+    pub drop_overlimit: u32,
+    /// Number of packets ECN marked instead of being dropped
+    pub ecn_mark: u32,
+    /// Whether the qdisc is in the dropping state
+    pub dropping: u32,
+    /// Number of packets above `ce_threshold`
+    pub ce_mark: u32,
+}
+
+pub const CODEL_XSTATS_LEN: usize = 36;
+
+buffer!(CodelXStatsBuffer(CODEL_XSTATS_LEN) {
+    maxpacket: (u32, 0..4),
+    count: (u32, 4..8),
+    lastcount: (u32, 8..12),
+    ldelay: (u32, 12..16),
+    drop_next: (i32, 16..20),
+    drop_overlimit: (u32, 20..24),
+    ecn_mark: (u32, 24..28),
+    dropping: (u32, 28..32),
+    ce_mark: (u32, 32..36),
+});
+
+impl<T: AsRef<[u8]>> Parseable<CodelXStatsBuffer<T>> for CodelXStats {
+    fn parse(buf: &CodelXStatsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            maxpacket: buf.maxpacket(),
+            count: buf.count(),
+            lastcount: buf.lastcount(),
+            ldelay: buf.ldelay(),
+            drop_next: buf.drop_next(),
+            drop_overlimit: buf.drop_overlimit(),
+            ecn_mark: buf.ecn_mark(),
+            dropping: buf.dropping(),
+            ce_mark: buf.ce_mark(),
+        })
+    }
+}
+
+impl Emitable for CodelXStats {
+    fn buffer_len(&self) -> usize {
+        CODEL_XSTATS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = CodelXStatsBuffer::new(buffer);
+        buffer.set_maxpacket(self.maxpacket);
+        buffer.set_count(self.count);
+        buffer.set_lastcount(self.lastcount);
+        buffer.set_ldelay(self.ldelay);
+        buffer.set_drop_next(self.drop_next);
+        buffer.set_drop_overlimit(self.drop_overlimit);
+        buffer.set_ecn_mark(self.ecn_mark);
+        buffer.set_dropping(self.dropping);
+        buffer.set_ce_mark(self.ce_mark);
+    }
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+pub const KIND: &str = "drr";
+
+/// The DRR (Deficit Round Robin) qdisc does not take any option, the
+/// quantum is set per class, see
+/// [`DrrClass`](crate::nlas::tc::class::DrrClass).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrrQdisc;
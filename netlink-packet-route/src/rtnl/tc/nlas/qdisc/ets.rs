@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_u32, parse_u8},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "ets";
+
+/// Options of the ETS (Enhanced Transmission Selection) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EtsQdisc {
+    pub nlas: Vec<EtsQdiscNla>,
+}
+
+impl Emitable for EtsQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for EtsQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid ets qdisc nla")?;
+            nlas.push(EtsQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EtsQdiscNla {
+    /// Number of bands, at most `TCQ_ETS_MAX_BANDS`
+    Nbands(u8),
+    /// Number of strict priority bands, which are the first bands
+    Nstrict(u8),
+    /// Quantum of each of the bandwidth sharing bands, in bytes
+    Quanta(Vec<u32>),
+    /// Band of each of the packet priorities, from 0 to `TC_PRIO_MAX`
+    Priomap(Vec<u8>),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for EtsQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::EtsQdiscNla::*;
+        match self {
+            Nbands(_) | Nstrict(_) => 1,
+            Quanta(ref quanta) => quanta.len() * EtsBandNla::Quantum(0).buffer_len(),
+            Priomap(ref priomap) => priomap.len() * EtsBandNla::Priomap(0).buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EtsQdiscNla::*;
+        match self {
+            Nbands(ref value) | Nstrict(ref value) => buffer[0] = *value,
+            Quanta(ref quanta) => quanta
+                .iter()
+                .map(|quantum| EtsBandNla::Quantum(*quantum))
+                .collect::<Vec<_>>()
+                .as_slice()
+                .emit(buffer),
+            Priomap(ref priomap) => priomap
+                .iter()
+                .map(|band| EtsBandNla::Priomap(*band))
+                .collect::<Vec<_>>()
+                .as_slice()
+                .emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EtsQdiscNla::*;
+        match self {
+            Nbands(_) => TCA_ETS_NBANDS,
+            Nstrict(_) => TCA_ETS_NSTRICT,
+            Quanta(_) => TCA_ETS_QUANTA,
+            Priomap(_) => TCA_ETS_PRIOMAP,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for EtsQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::EtsQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_ETS_NBANDS => Nbands(parse_u8(payload).context("invalid TCA_ETS_NBANDS value")?),
+            TCA_ETS_NSTRICT => Nstrict(parse_u8(payload).context("invalid TCA_ETS_NSTRICT value")?),
+            TCA_ETS_QUANTA => {
+                let mut quanta = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_ETS_QUANTA value")?;
+                    match EtsBandNla::parse(&nla).context("invalid TCA_ETS_QUANTA value")? {
+                        EtsBandNla::Quantum(quantum) => quanta.push(quantum),
+                        _ => {
+                            return Err(
+                                "invalid TCA_ETS_QUANTA value: expected TCA_ETS_QUANTA_BAND".into(),
+                            )
+                        }
+                    }
+                }
+                Quanta(quanta)
+            }
+            TCA_ETS_PRIOMAP => {
+                let mut priomap = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_ETS_PRIOMAP value")?;
+                    match EtsBandNla::parse(&nla).context("invalid TCA_ETS_PRIOMAP value")? {
+                        EtsBandNla::Priomap(band) => priomap.push(band),
+                        _ => {
+                            return Err(
+                                "invalid TCA_ETS_PRIOMAP value: expected TCA_ETS_PRIOMAP_BAND"
+                                    .into(),
+                            )
+                        }
+                    }
+                }
+                Priomap(priomap)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid ets qdisc nla")?),
+        })
+    }
+}
+
+/// An entry of the `TCA_ETS_QUANTA` and `TCA_ETS_PRIOMAP` lists
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum EtsBandNla {
+    Quantum(u32),
+    Priomap(u8),
+}
+
+impl nlas::Nla for EtsBandNla {
+    fn value_len(&self) -> usize {
+        match self {
+            EtsBandNla::Quantum(_) => 4,
+            EtsBandNla::Priomap(_) => 1,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            EtsBandNla::Quantum(ref value) => NativeEndian::write_u32(buffer, *value),
+            EtsBandNla::Priomap(ref value) => buffer[0] = *value,
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            EtsBandNla::Quantum(_) => TCA_ETS_QUANTA_BAND,
+            EtsBandNla::Priomap(_) => TCA_ETS_PRIOMAP_BAND,
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for EtsBandNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_ETS_QUANTA_BAND => EtsBandNla::Quantum(
+                parse_u32(payload).context("invalid TCA_ETS_QUANTA_BAND value")?,
+            ),
+            TCA_ETS_PRIOMAP_BAND => EtsBandNla::Priomap(
+                parse_u8(payload).context("invalid TCA_ETS_PRIOMAP_BAND value")?,
+            ),
+            kind => return Err(format!("unexpected ets band attribute {}", kind).into()),
+        })
+    }
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    nlas::NlaBuffer,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const PFIFO_KIND: &str = "pfifo";
+pub const BFIFO_KIND: &str = "bfifo";
+
+/// Options of the pfifo qdisc. The `TCA_OPTIONS` payload is a bare
+/// `struct tc_fifo_qopt`, whose limit is a number of packets.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PfifoQdisc {
+    pub qopt: FifoOpt,
+}
+
+/// Options of the bfifo qdisc. The `TCA_OPTIONS` payload is a bare
+/// `struct tc_fifo_qopt`, whose limit is a number of bytes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BfifoQdisc {
+    pub qopt: FifoOpt,
+}
+
+impl Emitable for PfifoQdisc {
+    fn buffer_len(&self) -> usize {
+        self.qopt.buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.qopt.emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for PfifoQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let qopt = FifoOpt::parse(&FifoOptBuffer::new_checked(buf.value())?)
+            .context("invalid pfifo qdisc parameters")?;
+        Ok(Self { qopt })
+    }
+}
+
+impl Emitable for BfifoQdisc {
+    fn buffer_len(&self) -> usize {
+        self.qopt.buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.qopt.emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for BfifoQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let qopt = FifoOpt::parse(&FifoOptBuffer::new_checked(buf.value())?)
+            .context("invalid bfifo qdisc parameters")?;
+        Ok(Self { qopt })
+    }
+}
+
+/// FIFO parameters (`struct tc_fifo_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FifoOpt {
+    /// Maximum length of the queue, in packets for pfifo and in bytes for
+    /// bfifo
+    pub limit: u32,
+}
+
+pub const FIFO_OPT_LEN: usize = 4;
+
+buffer!(FifoOptBuffer(FIFO_OPT_LEN) {
+    limit: (u32, 0..4),
+});
+
+impl<T: AsRef<[u8]>> Parseable<FifoOptBuffer<T>> for FifoOpt {
+    fn parse(buf: &FifoOptBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self { limit: buf.limit() })
+    }
+}
+
+impl Emitable for FifoOpt {
+    fn buffer_len(&self) -> usize {
+        FIFO_OPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = FifoOptBuffer::new(buffer);
+        buffer.set_limit(self.limit);
+    }
+}
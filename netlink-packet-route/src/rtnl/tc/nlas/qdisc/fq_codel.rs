@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "fq_codel";
+
+/// Options of the FQ_CoDel (Fair Queuing Controlled Delay) qdisc, see RFC 8290
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FqCodelQdisc {
+    pub nlas: Vec<FqCodelQdiscNla>,
+}
+
+impl Emitable for FqCodelQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FqCodelQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid fq_codel qdisc nla")?;
+            nlas.push(FqCodelQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FqCodelQdiscNla {
+    /// Acceptable minimum standing queue delay, in microseconds
+    Target(u32),
+    /// Maximum number of packets that can be queued
+    Limit(u32),
+    /// Width of the moving time window, in microseconds
+    Interval(u32),
+    /// Whether packets are ECN marked instead of dropped
+    Ecn(u32),
+    /// Number of flows packets are classified into
+    Flows(u32),
+    /// Number of bytes dequeued from a flow before moving to the next one
+    Quantum(u32),
+    /// Sojourn time above which packets are ECN marked, in microseconds
+    CeThreshold(u32),
+    /// Maximum number of packets dropped at once when the limit is reached
+    DropBatchSize(u32),
+    /// Maximum number of bytes that can be queued
+    MemoryLimit(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for FqCodelQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::FqCodelQdiscNla::*;
+        match self {
+            Target(_) | Limit(_) | Interval(_) | Ecn(_) | Flows(_) | Quantum(_)
+            | CeThreshold(_) | DropBatchSize(_) | MemoryLimit(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::FqCodelQdiscNla::*;
+        match self {
+            Target(ref value)
+            | Limit(ref value)
+            | Interval(ref value)
+            | Ecn(ref value)
+            | Flows(ref value)
+            | Quantum(ref value)
+            | CeThreshold(ref value)
+            | DropBatchSize(ref value)
+            | MemoryLimit(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::FqCodelQdiscNla::*;
+        match self {
+            Target(_) => TCA_FQ_CODEL_TARGET,
+            Limit(_) => TCA_FQ_CODEL_LIMIT,
+            Interval(_) => TCA_FQ_CODEL_INTERVAL,
+            Ecn(_) => TCA_FQ_CODEL_ECN,
+            Flows(_) => TCA_FQ_CODEL_FLOWS,
+            Quantum(_) => TCA_FQ_CODEL_QUANTUM,
+            CeThreshold(_) => TCA_FQ_CODEL_CE_THRESHOLD,
+            DropBatchSize(_) => TCA_FQ_CODEL_DROP_BATCH_SIZE,
+            MemoryLimit(_) => TCA_FQ_CODEL_MEMORY_LIMIT,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for FqCodelQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::FqCodelQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_FQ_CODEL_TARGET => {
+                Target(parse_u32(payload).context("invalid TCA_FQ_CODEL_TARGET value")?)
+            }
+            TCA_FQ_CODEL_LIMIT => {
+                Limit(parse_u32(payload).context("invalid TCA_FQ_CODEL_LIMIT value")?)
+            }
+            TCA_FQ_CODEL_INTERVAL => {
+                Interval(parse_u32(payload).context("invalid TCA_FQ_CODEL_INTERVAL value")?)
+            }
+            TCA_FQ_CODEL_ECN => Ecn(parse_u32(payload).context("invalid TCA_FQ_CODEL_ECN value")?),
+            TCA_FQ_CODEL_FLOWS => {
+                Flows(parse_u32(payload).context("invalid TCA_FQ_CODEL_FLOWS value")?)
+            }
+            TCA_FQ_CODEL_QUANTUM => {
+                Quantum(parse_u32(payload).context("invalid TCA_FQ_CODEL_QUANTUM value")?)
+            }
+            TCA_FQ_CODEL_CE_THRESHOLD => {
+                CeThreshold(parse_u32(payload).context("invalid TCA_FQ_CODEL_CE_THRESHOLD value")?)
+            }
+            TCA_FQ_CODEL_DROP_BATCH_SIZE => DropBatchSize(
+                parse_u32(payload).context("invalid TCA_FQ_CODEL_DROP_BATCH_SIZE value")?,
+            ),
+            TCA_FQ_CODEL_MEMORY_LIMIT => {
+                MemoryLimit(parse_u32(payload).context("invalid TCA_FQ_CODEL_MEMORY_LIMIT value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid fq_codel qdisc nla")?),
+        })
+    }
+}
+
+/// FQ_CoDel qdisc statistics (`struct tc_fq_codel_xstats` of type
+/// `TCA_FQ_CODEL_XSTATS_QDISC`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FqCodelXStats {
+    /// Largest packet seen so far
+    pub maxpacket: u32,
+    /// Number of times the packet limit was hit
+    pub drop_overlimit: u32,
+    /// Number of packets ECN marked instead of being dropped
+    pub ecn_mark: u32,
+    /// Number of times packets created a new flow
+    pub new_flow_count: u32,
+    /// Number of flows in the new list
+    pub new_flows_len: u32,
+    /// Number of flows in the old list
+    pub old_flows_len: u32,
+    /// Number of packets above `ce_threshold`
+    pub ce_mark: u32,
+    /// Memory usage, in bytes
+    pub memory_usage: u32,
+    /// Number of times the memory limit was hit
+    pub drop_overmemory: u32,
+}
+
+pub const FQ_CODEL_XSTATS_LEN: usize = 40;
+
+buffer!(FqCodelXStatsBuffer(FQ_CODEL_XSTATS_LEN) {
+    kind: (u32, 0..4),
+    maxpacket: (u32, 4..8),
+    drop_overlimit: (u32, 8..12),
+    ecn_mark: (u32, 12..16),
+    new_flow_count: (u32, 16..20),
+    new_flows_len: (u32, 20..24),
+    old_flows_len: (u32, 24..28),
+    ce_mark: (u32, 28..32),
+    memory_usage: (u32, 32..36),
+    drop_overmemory: (u32, 36..40),
+});
+
+impl FqCodelXStats {
+    /// The kernel uses the same attribute for qdisc and class statistics,
+    /// which are told apart by their first 32 bits word.
+    pub(crate) fn is_qdisc_xstats(payload: &[u8]) -> bool {
+        payload.len() >= 4 && NativeEndian::read_u32(&payload[..4]) == TCA_FQ_CODEL_XSTATS_QDISC
+    }
+}
+
+impl<T: AsRef<[u8]>> Parseable<FqCodelXStatsBuffer<T>> for FqCodelXStats {
+    fn parse(buf: &FqCodelXStatsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            maxpacket: buf.maxpacket(),
+            drop_overlimit: buf.drop_overlimit(),
+            ecn_mark: buf.ecn_mark(),
+            new_flow_count: buf.new_flow_count(),
+            new_flows_len: buf.new_flows_len(),
+            old_flows_len: buf.old_flows_len(),
+            ce_mark: buf.ce_mark(),
+            memory_usage: buf.memory_usage(),
+            drop_overmemory: buf.drop_overmemory(),
+        })
+    }
+}
+
+impl Emitable for FqCodelXStats {
+    fn buffer_len(&self) -> usize {
+        FQ_CODEL_XSTATS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = FqCodelXStatsBuffer::new(buffer);
+        buffer.set_kind(TCA_FQ_CODEL_XSTATS_QDISC);
+        buffer.set_maxpacket(self.maxpacket);
+        buffer.set_drop_overlimit(self.drop_overlimit);
+        buffer.set_ecn_mark(self.ecn_mark);
+        buffer.set_new_flow_count(self.new_flow_count);
+        buffer.set_new_flows_len(self.new_flows_len);
+        buffer.set_old_flows_len(self.old_flows_len);
+        buffer.set_ce_mark(self.ce_mark);
+        buffer.set_memory_usage(self.memory_usage);
+        buffer.set_drop_overmemory(self.drop_overmemory);
+    }
+}
@@ -0,0 +1,475 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_u32, parse_u64},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::{
+    super::{emit_u32_list, parse_u32_list},
+    red::{RedFlags, RED_STAB_SIZE},
+};
+
+pub const KIND: &str = "gred";
+
+/// Options of the GRED (Generic Random Early Detection) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GredQdisc {
+    pub nlas: Vec<GredQdiscNla>,
+}
+
+impl Emitable for GredQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for GredQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid gred qdisc nla")?;
+            nlas.push(GredQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GredQdiscNla {
+    /// RED parameters of the virtual queues. When the qdisc is dumped, there
+    /// is one entry per possible virtual queue (`MAX_DPS`), the unused ones
+    /// having their `dp` set to `MAX_DPS`. When a virtual queue is
+    /// configured, only its own parameters are given.
+    Parms(Vec<TcGredQopt>),
+    /// Table used to compute the average queue length after an idle period
+    Stab(Vec<u8>),
+    /// Number of virtual queues and default virtual queue
+    Dps(TcGredDps),
+    /// Maximum drop probability of each virtual queue, scaled so that
+    /// `u32::MAX` is 100%
+    MaxP(Vec<u32>),
+    /// Hard limit on the total queue length, in bytes
+    Limit(u32),
+    /// Flags and statistics of the virtual queues
+    VqList(Vec<TcGredVqEntry>),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for GredQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::GredQdiscNla::*;
+        match self {
+            Parms(ref qopts) => qopts.len() * TC_GRED_QOPT_LEN,
+            Stab(ref table) => table.len(),
+            Dps(_) => TC_GRED_DPS_LEN,
+            MaxP(ref values) => values.len() * 4,
+            Limit(_) => 4,
+            VqList(ref entries) => entries.as_slice().buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::GredQdiscNla::*;
+        match self {
+            Parms(ref qopts) => {
+                for (qopt, chunk) in qopts.iter().zip(buffer.chunks_mut(TC_GRED_QOPT_LEN)) {
+                    qopt.emit(chunk);
+                }
+            }
+            Stab(ref table) => buffer.copy_from_slice(table.as_slice()),
+            Dps(ref dps) => dps.emit(buffer),
+            MaxP(ref values) => emit_u32_list(buffer, values),
+            Limit(ref value) => NativeEndian::write_u32(buffer, *value),
+            VqList(ref entries) => entries.as_slice().emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::GredQdiscNla::*;
+        match self {
+            Parms(_) => TCA_GRED_PARMS,
+            Stab(_) => TCA_GRED_STAB,
+            Dps(_) => TCA_GRED_DPS,
+            MaxP(_) => TCA_GRED_MAX_P,
+            Limit(_) => TCA_GRED_LIMIT,
+            VqList(_) => TCA_GRED_VQ_LIST,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for GredQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::GredQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_GRED_PARMS => {
+                let chunks = payload.chunks_exact(TC_GRED_QOPT_LEN);
+                if !chunks.remainder().is_empty() {
+                    return Err(format!("invalid TCA_GRED_PARMS value: {:?}", payload).into());
+                }
+                let mut qopts = vec![];
+                for chunk in chunks {
+                    qopts.push(
+                        TcGredQopt::parse(&TcGredQoptBuffer::new(chunk))
+                            .context("invalid TCA_GRED_PARMS value")?,
+                    );
+                }
+                Parms(qopts)
+            }
+            TCA_GRED_STAB => {
+                if payload.len() != RED_STAB_SIZE {
+                    return Err(format!("invalid TCA_GRED_STAB value: {:?}", payload).into());
+                }
+                Stab(payload.to_vec())
+            }
+            TCA_GRED_DPS => Dps(TcGredDps::parse(&TcGredDpsBuffer::new_checked(payload)?)
+                .context("invalid TCA_GRED_DPS value")?),
+            TCA_GRED_MAX_P => {
+                MaxP(parse_u32_list(payload).context("invalid TCA_GRED_MAX_P value")?)
+            }
+            TCA_GRED_LIMIT => Limit(parse_u32(payload).context("invalid TCA_GRED_LIMIT value")?),
+            TCA_GRED_VQ_LIST => {
+                let mut entries = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_GRED_VQ_LIST value")?;
+                    entries.push(
+                        TcGredVqEntry::parse(&nla).context("invalid TCA_GRED_VQ_LIST value")?,
+                    );
+                }
+                VqList(entries)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid gred qdisc nla")?),
+        })
+    }
+}
+
+/// Number of virtual queues and default virtual queue (`struct
+/// tc_gred_sopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcGredDps {
+    /// Number of virtual queues, at most `MAX_DPS`
+    pub dps: u32,
+    /// Virtual queue of the packets whose `skb->tc_index` does not select one
+    pub def_dp: u32,
+    /// Whether the virtual queues share their buffer in priority order
+    /// (RIO mode)
+    pub grio: u8,
+    pub flags: RedFlags,
+}
+
+pub const TC_GRED_DPS_LEN: usize = 12;
+
+buffer!(TcGredDpsBuffer(TC_GRED_DPS_LEN) {
+    dps: (u32, 0..4),
+    def_dp: (u32, 4..8),
+    grio: (u8, 8),
+    flags: (u8, 9),
+    pad: (u16, 10..12),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcGredDpsBuffer<T>> for TcGredDps {
+    fn parse(buf: &TcGredDpsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            dps: buf.dps(),
+            def_dp: buf.def_dp(),
+            grio: buf.grio(),
+            flags: RedFlags::from_bits_truncate(buf.flags()),
+        })
+    }
+}
+
+impl Emitable for TcGredDps {
+    fn buffer_len(&self) -> usize {
+        TC_GRED_DPS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcGredDpsBuffer::new(buffer);
+        buffer.set_dps(self.dps);
+        buffer.set_def_dp(self.def_dp);
+        buffer.set_grio(self.grio);
+        buffer.set_flags(self.flags.bits());
+        buffer.set_pad(0);
+    }
+}
+
+/// RED parameters and statistics of a virtual queue (`struct
+/// tc_gred_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcGredQopt {
+    /// Hard limit on the queue length, in bytes
+    pub limit: u32,
+    /// Average queue length above which packets may be marked or dropped
+    pub qth_min: u32,
+    /// Average queue length above which all the packets are marked or
+    /// dropped
+    pub qth_max: u32,
+    /// Index of the virtual queue
+    pub dp: u32,
+    /// Current queue length, in bytes
+    pub backlog: u32,
+    /// Current average queue length
+    pub qave: u32,
+    /// Packets dropped or marked because the average queue length was above
+    /// `qth_max`
+    pub forced: u32,
+    /// Packets dropped or marked because the average queue length was
+    /// between `qth_min` and `qth_max`
+    pub early: u32,
+    /// Packets dropped for other reasons
+    pub other: u32,
+    /// Packets dropped because the hard limit was reached
+    pub pdrop: u32,
+    /// Log2 of the weight of the queue length in the moving average
+    pub wlog: u8,
+    /// Log2 of `qth_max - qth_min` divided by the maximum probability
+    pub plog: u8,
+    /// Log2 of the cell size of the idle damping table
+    pub scell_log: u8,
+    /// Priority of the virtual queue, in RIO mode
+    pub prio: u8,
+    /// Number of packets enqueued
+    pub packets: u32,
+    /// Number of bytes enqueued
+    pub bytesin: u32,
+}
+
+pub const TC_GRED_QOPT_LEN: usize = 52;
+
+buffer!(TcGredQoptBuffer(TC_GRED_QOPT_LEN) {
+    limit: (u32, 0..4),
+    qth_min: (u32, 4..8),
+    qth_max: (u32, 8..12),
+    dp: (u32, 12..16),
+    backlog: (u32, 16..20),
+    qave: (u32, 20..24),
+    forced: (u32, 24..28),
+    early: (u32, 28..32),
+    other: (u32, 32..36),
+    pdrop: (u32, 36..40),
+    wlog: (u8, 40),
+    plog: (u8, 41),
+    scell_log: (u8, 42),
+    prio: (u8, 43),
+    packets: (u32, 44..48),
+    bytesin: (u32, 48..52),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcGredQoptBuffer<T>> for TcGredQopt {
+    fn parse(buf: &TcGredQoptBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            limit: buf.limit(),
+            qth_min: buf.qth_min(),
+            qth_max: buf.qth_max(),
+            dp: buf.dp(),
+            backlog: buf.backlog(),
+            qave: buf.qave(),
+            forced: buf.forced(),
+            early: buf.early(),
+            other: buf.other(),
+            pdrop: buf.pdrop(),
+            wlog: buf.wlog(),
+            plog: buf.plog(),
+            scell_log: buf.scell_log(),
+            prio: buf.prio(),
+            packets: buf.packets(),
+            bytesin: buf.bytesin(),
+        })
+    }
+}
+
+impl Emitable for TcGredQopt {
+    fn buffer_len(&self) -> usize {
+        TC_GRED_QOPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcGredQoptBuffer::new(buffer);
+        buffer.set_limit(self.limit);
+        buffer.set_qth_min(self.qth_min);
+        buffer.set_qth_max(self.qth_max);
+        buffer.set_dp(self.dp);
+        buffer.set_backlog(self.backlog);
+        buffer.set_qave(self.qave);
+        buffer.set_forced(self.forced);
+        buffer.set_early(self.early);
+        buffer.set_other(self.other);
+        buffer.set_pdrop(self.pdrop);
+        buffer.set_wlog(self.wlog);
+        buffer.set_plog(self.plog);
+        buffer.set_scell_log(self.scell_log);
+        buffer.set_prio(self.prio);
+        buffer.set_packets(self.packets);
+        buffer.set_bytesin(self.bytesin);
+    }
+}
+
+/// A virtual queue of the `TCA_GRED_VQ_LIST` attribute
+/// (`TCA_GRED_VQ_ENTRY`)
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcGredVqEntry {
+    pub nlas: Vec<GredVqNla>,
+}
+
+impl nlas::Nla for TcGredVqEntry {
+    fn value_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+
+    fn kind(&self) -> u16 {
+        TCA_GRED_VQ_ENTRY
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TcGredVqEntry {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        if buf.kind() != TCA_GRED_VQ_ENTRY {
+            return Err(format!("unexpected gred virtual queue attribute {}", buf.kind()).into());
+        }
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid TCA_GRED_VQ_ENTRY value")?;
+            nlas.push(GredVqNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GredVqNla {
+    /// Index of the virtual queue
+    Dp(u32),
+    /// Number of bytes enqueued
+    StatBytes(u64),
+    /// Number of packets enqueued
+    StatPackets(u32),
+    /// Current queue length, in bytes
+    StatBacklog(u32),
+    /// Packets dropped because of the drop probability
+    StatProbDrop(u32),
+    /// Packets marked because of the drop probability
+    StatProbMark(u32),
+    /// Packets dropped because the average queue length was above `qth_max`
+    StatForcedDrop(u32),
+    /// Packets marked because the average queue length was above `qth_max`
+    StatForcedMark(u32),
+    /// Packets dropped because the hard limit was reached
+    StatPdrop(u32),
+    /// Packets dropped for other reasons
+    StatOther(u32),
+    /// `TC_RED_*` flags of the virtual queue
+    Flags(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for GredVqNla {
+    fn value_len(&self) -> usize {
+        use self::GredVqNla::*;
+        match self {
+            StatBytes(_) => 8,
+            Other(ref nla) => nla.value_len(),
+            _ => 4,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::GredVqNla::*;
+        match self {
+            StatBytes(ref value) => NativeEndian::write_u64(buffer, *value),
+            Dp(ref value)
+            | StatPackets(ref value)
+            | StatBacklog(ref value)
+            | StatProbDrop(ref value)
+            | StatProbMark(ref value)
+            | StatForcedDrop(ref value)
+            | StatForcedMark(ref value)
+            | StatPdrop(ref value)
+            | StatOther(ref value)
+            | Flags(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::GredVqNla::*;
+        match self {
+            Dp(_) => TCA_GRED_VQ_DP,
+            StatBytes(_) => TCA_GRED_VQ_STAT_BYTES,
+            StatPackets(_) => TCA_GRED_VQ_STAT_PACKETS,
+            StatBacklog(_) => TCA_GRED_VQ_STAT_BACKLOG,
+            StatProbDrop(_) => TCA_GRED_VQ_STAT_PROB_DROP,
+            StatProbMark(_) => TCA_GRED_VQ_STAT_PROB_MARK,
+            StatForcedDrop(_) => TCA_GRED_VQ_STAT_FORCED_DROP,
+            StatForcedMark(_) => TCA_GRED_VQ_STAT_FORCED_MARK,
+            StatPdrop(_) => TCA_GRED_VQ_STAT_PDROP,
+            StatOther(_) => TCA_GRED_VQ_STAT_OTHER,
+            Flags(_) => TCA_GRED_VQ_FLAGS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for GredVqNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::GredVqNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_GRED_VQ_DP => Dp(parse_u32(payload).context("invalid TCA_GRED_VQ_DP value")?),
+            TCA_GRED_VQ_STAT_BYTES => {
+                StatBytes(parse_u64(payload).context("invalid TCA_GRED_VQ_STAT_BYTES value")?)
+            }
+            TCA_GRED_VQ_STAT_PACKETS => {
+                StatPackets(parse_u32(payload).context("invalid TCA_GRED_VQ_STAT_PACKETS value")?)
+            }
+            TCA_GRED_VQ_STAT_BACKLOG => {
+                StatBacklog(parse_u32(payload).context("invalid TCA_GRED_VQ_STAT_BACKLOG value")?)
+            }
+            TCA_GRED_VQ_STAT_PROB_DROP => StatProbDrop(
+                parse_u32(payload).context("invalid TCA_GRED_VQ_STAT_PROB_DROP value")?,
+            ),
+            TCA_GRED_VQ_STAT_PROB_MARK => StatProbMark(
+                parse_u32(payload).context("invalid TCA_GRED_VQ_STAT_PROB_MARK value")?,
+            ),
+            TCA_GRED_VQ_STAT_FORCED_DROP => StatForcedDrop(
+                parse_u32(payload).context("invalid TCA_GRED_VQ_STAT_FORCED_DROP value")?,
+            ),
+            TCA_GRED_VQ_STAT_FORCED_MARK => StatForcedMark(
+                parse_u32(payload).context("invalid TCA_GRED_VQ_STAT_FORCED_MARK value")?,
+            ),
+            TCA_GRED_VQ_STAT_PDROP => {
+                StatPdrop(parse_u32(payload).context("invalid TCA_GRED_VQ_STAT_PDROP value")?)
+            }
+            TCA_GRED_VQ_STAT_OTHER => {
+                StatOther(parse_u32(payload).context("invalid TCA_GRED_VQ_STAT_OTHER value")?)
+            }
+            TCA_GRED_VQ_FLAGS => {
+                Flags(parse_u32(payload).context("invalid TCA_GRED_VQ_FLAGS value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid gred virtual queue nla")?),
+        })
+    }
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    nlas::NlaBuffer,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "hfsc";
+
+/// Options of the HFSC (Hierarchical Fair Service Curve) qdisc. The
+/// `TCA_OPTIONS` payload is a bare `struct tc_hfsc_qopt`; the service curves
+/// are set on the classes, see
+/// [`HfscClass`](crate::nlas::tc::class::HfscClass).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HfscQdisc {
+    pub qopt: HfscQopt,
+}
+
+impl Emitable for HfscQdisc {
+    fn buffer_len(&self) -> usize {
+        self.qopt.buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.qopt.emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for HfscQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let qopt = HfscQopt::parse(&HfscQoptBuffer::new_checked(buf.value())?)
+            .context("invalid hfsc qdisc parameters")?;
+        Ok(Self { qopt })
+    }
+}
+
+/// HFSC qdisc parameters (`struct tc_hfsc_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HfscQopt {
+    /// Minor number of the class unclassified traffic is sent to
+    pub defcls: u16,
+}
+
+pub const HFSC_QOPT_LEN: usize = 2;
+
+buffer!(HfscQoptBuffer(HFSC_QOPT_LEN) {
+    defcls: (u16, 0..2),
+});
+
+impl<T: AsRef<[u8]>> Parseable<HfscQoptBuffer<T>> for HfscQopt {
+    fn parse(buf: &HfscQoptBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            defcls: buf.defcls(),
+        })
+    }
+}
+
+impl Emitable for HfscQopt {
+    fn buffer_len(&self) -> usize {
+        HFSC_QOPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = HfscQoptBuffer::new(buffer);
+        buffer.set_defcls(self.defcls);
+    }
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::super::{emit_u32_list, parse_u32_list};
+
+pub const KIND: &str = "htb";
+
+/// Options of the HTB (Hierarchical Token Bucket) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HtbQdisc {
+    pub nlas: Vec<HtbQdiscNla>,
+}
+
+impl Emitable for HtbQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for HtbQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid htb qdisc nla")?;
+            nlas.push(HtbQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HtbQdiscNla {
+    /// Global parameters of the qdisc
+    Init(TcaHtbGlob),
+    Ctab(Vec<u32>),
+    Rtab(Vec<u32>),
+    /// Maximum length of the direct queue, in packets
+    DirectQlen(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for HtbQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::HtbQdiscNla::*;
+        match self {
+            Init(_) => TCA_HTB_GLOB_LEN,
+            Ctab(ref table) | Rtab(ref table) => table.len() * 4,
+            DirectQlen(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::HtbQdiscNla::*;
+        match self {
+            Init(ref glob) => glob.emit(buffer),
+            Ctab(ref table) | Rtab(ref table) => emit_u32_list(buffer, table),
+            DirectQlen(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::HtbQdiscNla::*;
+        match self {
+            Init(_) => TCA_HTB_INIT,
+            Ctab(_) => TCA_HTB_CTAB,
+            Rtab(_) => TCA_HTB_RTAB,
+            DirectQlen(_) => TCA_HTB_DIRECT_QLEN,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for HtbQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::HtbQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_HTB_INIT => Init(
+                TcaHtbGlob::parse(&TcaHtbGlobBuffer::new_checked(payload)?)
+                    .context("invalid TCA_HTB_INIT value")?,
+            ),
+            TCA_HTB_CTAB => Ctab(parse_u32_list(payload).context("invalid TCA_HTB_CTAB value")?),
+            TCA_HTB_RTAB => Rtab(parse_u32_list(payload).context("invalid TCA_HTB_RTAB value")?),
+            TCA_HTB_DIRECT_QLEN => {
+                DirectQlen(parse_u32(payload).context("invalid TCA_HTB_DIRECT_QLEN value")?)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid htb qdisc nla")?),
+        })
+    }
+}
+
+/// Global HTB parameters (`struct tc_htb_glob`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcaHtbGlob {
+    pub version: u32,
+    /// bps->quantum divisor
+    pub rate2quantum: u32,
+    /// Default class number
+    pub defcls: u32,
+    /// Debug flags
+    pub debug: u32,
+    /// Number of packets sent out of the direct queue
+    pub direct_pkts: u32,
+}
+
+pub const TCA_HTB_GLOB_LEN: usize = 20;
+
+buffer!(TcaHtbGlobBuffer(TCA_HTB_GLOB_LEN) {
+    version: (u32, 0..4),
+    rate2quantum: (u32, 4..8),
+    defcls: (u32, 8..12),
+    debug: (u32, 12..16),
+    direct_pkts: (u32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcaHtbGlobBuffer<T>> for TcaHtbGlob {
+    fn parse(buf: &TcaHtbGlobBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            version: buf.version(),
+            rate2quantum: buf.rate2quantum(),
+            defcls: buf.defcls(),
+            debug: buf.debug(),
+            direct_pkts: buf.direct_pkts(),
+        })
+    }
+}
+
+impl Emitable for TcaHtbGlob {
+    fn buffer_len(&self) -> usize {
+        TCA_HTB_GLOB_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcaHtbGlobBuffer::new(buffer);
+        buffer.set_version(self.version);
+        buffer.set_rate2quantum(self.rate2quantum);
+        buffer.set_defcls(self.defcls);
+        buffer.set_debug(self.debug);
+        buffer.set_direct_pkts(self.direct_pkts);
+    }
+}
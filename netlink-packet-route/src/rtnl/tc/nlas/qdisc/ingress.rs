@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: MIT
+
+pub const KIND: &str = "ingress";
+
+/// The ingress qdisc does not take any option.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ingress;
@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: MIT
+
+pub mod htb;
+pub use self::htb::HtbQdisc;
+
+pub mod ingress;
+pub use self::ingress::Ingress;
+
+pub mod tbf;
+pub use self::tbf::TbfQdisc;
+
+pub mod fq_codel;
+pub use self::fq_codel::FqCodelQdisc;
+
+pub mod netem;
+pub use self::netem::NetemQdisc;
+
+pub mod sfq;
+pub use self::sfq::SfqQdisc;
+
+pub mod prio;
+pub use self::prio::PrioQdisc;
+
+pub mod red;
+pub use self::red::RedQdisc;
+
+pub mod cake;
+pub use self::cake::CakeQdisc;
+
+pub mod codel;
+pub use self::codel::CodelQdisc;
+
+pub mod hfsc;
+pub use self::hfsc::HfscQdisc;
+
+pub mod drr;
+pub use self::drr::DrrQdisc;
+
+pub mod ets;
+pub use self::ets::EtsQdisc;
+
+pub mod taprio;
+pub use self::taprio::TaprioQdisc;
+
+pub mod pie;
+pub use self::pie::PieQdisc;
+
+pub mod gred;
+pub use self::gred::GredQdisc;
+
+pub mod qfq;
+pub use self::qfq::QfqQdisc;
+
+pub mod clsact;
+pub use self::clsact::Clsact;
+
+pub mod fifo;
+pub use self::fifo::{BfifoQdisc, PfifoQdisc};
+
+pub mod cbq;
+pub use self::cbq::CbqQdisc;
+
+use anyhow::Context;
+
+use crate::{
+    nlas::NlaBuffer,
+    traits::{Emitable, Parseable, ParseableParametrized},
+    DecodeError,
+};
+
+/// Qdisc specific options, carried by the `TCA_OPTIONS` attribute of
+/// `RTM_*QDISC` messages.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Qdisc {
+    Ingress(Ingress),
+    Htb(HtbQdisc),
+    Tbf(TbfQdisc),
+    FqCodel(FqCodelQdisc),
+    Netem(NetemQdisc),
+    Sfq(SfqQdisc),
+    Prio(PrioQdisc),
+    Red(RedQdisc),
+    Cake(CakeQdisc),
+    Codel(CodelQdisc),
+    Hfsc(HfscQdisc),
+    Drr(DrrQdisc),
+    Ets(EtsQdisc),
+    Taprio(TaprioQdisc),
+    Pie(PieQdisc),
+    Gred(GredQdisc),
+    Qfq(QfqQdisc),
+    Clsact(Clsact),
+    Pfifo(PfifoQdisc),
+    Bfifo(BfifoQdisc),
+    Cbq(CbqQdisc),
+    Other(#[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_base64"))] Vec<u8>),
+}
+
+impl Qdisc {
+    /// Create empty options for the given qdisc kind.
+    /// Unknown kinds give empty `Other` options rather than an error.
+    pub fn new(kind: &str) -> Self {
+        match kind {
+            ingress::KIND => Qdisc::Ingress(Ingress),
+            htb::KIND => Qdisc::Htb(HtbQdisc::default()),
+            tbf::KIND => Qdisc::Tbf(TbfQdisc::default()),
+            fq_codel::KIND => Qdisc::FqCodel(FqCodelQdisc::default()),
+            netem::KIND => Qdisc::Netem(NetemQdisc::default()),
+            sfq::KIND => Qdisc::Sfq(SfqQdisc::default()),
+            prio::KIND => Qdisc::Prio(PrioQdisc::default()),
+            red::KIND => Qdisc::Red(RedQdisc::default()),
+            cake::KIND => Qdisc::Cake(CakeQdisc::default()),
+            codel::KIND => Qdisc::Codel(CodelQdisc::default()),
+            hfsc::KIND => Qdisc::Hfsc(HfscQdisc::default()),
+            drr::KIND => Qdisc::Drr(DrrQdisc),
+            ets::KIND => Qdisc::Ets(EtsQdisc::default()),
+            taprio::KIND => Qdisc::Taprio(TaprioQdisc::default()),
+            pie::KIND => Qdisc::Pie(PieQdisc::default()),
+            gred::KIND => Qdisc::Gred(GredQdisc::default()),
+            qfq::KIND => Qdisc::Qfq(QfqQdisc),
+            clsact::KIND => Qdisc::Clsact(Clsact),
+            fifo::PFIFO_KIND => Qdisc::Pfifo(PfifoQdisc::default()),
+            fifo::BFIFO_KIND => Qdisc::Bfifo(BfifoQdisc::default()),
+            cbq::KIND => Qdisc::Cbq(CbqQdisc::default()),
+            _ => Qdisc::Other(Vec::new()),
+        }
+    }
+}
+
+impl Emitable for Qdisc {
+    fn buffer_len(&self) -> usize {
+        use self::Qdisc::*;
+        match self {
+            Ingress(_) => 0,
+            Htb(ref htb) => htb.buffer_len(),
+            Tbf(ref tbf) => tbf.buffer_len(),
+            FqCodel(ref fq_codel) => fq_codel.buffer_len(),
+            Netem(ref netem) => netem.buffer_len(),
+            Sfq(ref sfq) => sfq.buffer_len(),
+            Prio(ref prio) => prio.buffer_len(),
+            Red(ref red) => red.buffer_len(),
+            Cake(ref cake) => cake.buffer_len(),
+            Codel(ref codel) => codel.buffer_len(),
+            Hfsc(ref hfsc) => hfsc.buffer_len(),
+            Drr(_) => 0,
+            Ets(ref ets) => ets.buffer_len(),
+            Taprio(ref taprio) => taprio.buffer_len(),
+            Pie(ref pie) => pie.buffer_len(),
+            Gred(ref gred) => gred.buffer_len(),
+            Qfq(_) => 0,
+            Clsact(_) => 0,
+            Pfifo(ref pfifo) => pfifo.buffer_len(),
+            Bfifo(ref bfifo) => bfifo.buffer_len(),
+            Cbq(ref cbq) => cbq.buffer_len(),
+            Other(ref bytes) => bytes.len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        use self::Qdisc::*;
+        match self {
+            Ingress(_) => {}
+            Htb(ref htb) => htb.emit(buffer),
+            Tbf(ref tbf) => tbf.emit(buffer),
+            FqCodel(ref fq_codel) => fq_codel.emit(buffer),
+            Netem(ref netem) => netem.emit(buffer),
+            Sfq(ref sfq) => sfq.emit(buffer),
+            Prio(ref prio) => prio.emit(buffer),
+            Red(ref red) => red.emit(buffer),
+            Cake(ref cake) => cake.emit(buffer),
+            Codel(ref codel) => codel.emit(buffer),
+            Hfsc(ref hfsc) => hfsc.emit(buffer),
+            Drr(_) => {}
+            Ets(ref ets) => ets.emit(buffer),
+            Taprio(ref taprio) => taprio.emit(buffer),
+            Pie(ref pie) => pie.emit(buffer),
+            Gred(ref gred) => gred.emit(buffer),
+            Qfq(_) => {}
+            Clsact(_) => {}
+            Pfifo(ref pfifo) => pfifo.emit(buffer),
+            Bfifo(ref bfifo) => bfifo.emit(buffer),
+            Cbq(ref cbq) => cbq.emit(buffer),
+            Other(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<NlaBuffer<&'a T>, &str> for Qdisc {
+    fn parse_with_param(buf: &NlaBuffer<&'a T>, kind: &str) -> Result<Self, DecodeError> {
+        Ok(match kind {
+            ingress::KIND => Qdisc::Ingress(Ingress),
+            htb::KIND => {
+                Qdisc::Htb(HtbQdisc::parse(buf).context("failed to parse htb qdisc options")?)
+            }
+            tbf::KIND => {
+                Qdisc::Tbf(TbfQdisc::parse(buf).context("failed to parse tbf qdisc options")?)
+            }
+            fq_codel::KIND => Qdisc::FqCodel(
+                FqCodelQdisc::parse(buf).context("failed to parse fq_codel qdisc options")?,
+            ),
+            netem::KIND => {
+                Qdisc::Netem(NetemQdisc::parse(buf).context("failed to parse netem qdisc options")?)
+            }
+            sfq::KIND => {
+                Qdisc::Sfq(SfqQdisc::parse(buf).context("failed to parse sfq qdisc options")?)
+            }
+            prio::KIND => {
+                Qdisc::Prio(PrioQdisc::parse(buf).context("failed to parse prio qdisc options")?)
+            }
+            red::KIND => {
+                Qdisc::Red(RedQdisc::parse(buf).context("failed to parse red qdisc options")?)
+            }
+            cake::KIND => {
+                Qdisc::Cake(CakeQdisc::parse(buf).context("failed to parse cake qdisc options")?)
+            }
+            codel::KIND => {
+                Qdisc::Codel(CodelQdisc::parse(buf).context("failed to parse codel qdisc options")?)
+            }
+            hfsc::KIND => {
+                Qdisc::Hfsc(HfscQdisc::parse(buf).context("failed to parse hfsc qdisc options")?)
+            }
+            drr::KIND => Qdisc::Drr(DrrQdisc),
+            ets::KIND => {
+                Qdisc::Ets(EtsQdisc::parse(buf).context("failed to parse ets qdisc options")?)
+            }
+            taprio::KIND => Qdisc::Taprio(
+                TaprioQdisc::parse(buf).context("failed to parse taprio qdisc options")?,
+            ),
+            pie::KIND => {
+                Qdisc::Pie(PieQdisc::parse(buf).context("failed to parse pie qdisc options")?)
+            }
+            gred::KIND => {
+                Qdisc::Gred(GredQdisc::parse(buf).context("failed to parse gred qdisc options")?)
+            }
+            qfq::KIND => Qdisc::Qfq(QfqQdisc),
+            clsact::KIND => Qdisc::Clsact(Clsact),
+            fifo::PFIFO_KIND => {
+                Qdisc::Pfifo(PfifoQdisc::parse(buf).context("failed to parse pfifo qdisc options")?)
+            }
+            fifo::BFIFO_KIND => {
+                Qdisc::Bfifo(BfifoQdisc::parse(buf).context("failed to parse bfifo qdisc options")?)
+            }
+            cbq::KIND => {
+                Qdisc::Cbq(CbqQdisc::parse(buf).context("failed to parse cbq qdisc options")?)
+            }
+            _ => Qdisc::Other(buf.value().to_vec()),
+        })
+    }
+}
@@ -0,0 +1,554 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_u32, parse_u64},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "netem";
+
+/// Maximum number of entries of a delay distribution table
+pub const NETEM_DIST_MAX: usize = 16384;
+
+/// Options of the NETEM (Network Emulator) qdisc. Unlike most qdiscs, the
+/// `TCA_OPTIONS` payload starts with a fixed size structure, which is
+/// followed by the attributes.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetemQdisc {
+    pub qopt: TcNetemQopt,
+    pub nlas: Vec<NetemQdiscNla>,
+}
+
+impl Emitable for NetemQdisc {
+    fn buffer_len(&self) -> usize {
+        TC_NETEM_QOPT_LEN + self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.qopt.emit(buffer);
+        self.nlas.as_slice().emit(&mut buffer[TC_NETEM_QOPT_LEN..]);
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for NetemQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        let qopt = TcNetemQopt::parse(&TcNetemQoptBuffer::new_checked(payload)?)
+            .context("invalid netem qdisc parameters")?;
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(&payload[TC_NETEM_QOPT_LEN..]) {
+            let nla = nla.context("invalid netem qdisc nla")?;
+            nlas.push(NetemQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { qopt, nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetemQdiscNla {
+    /// Correlation of the delay, loss and duplication
+    Corr(TcNetemCorr),
+    /// Delay distribution table, at most [`NETEM_DIST_MAX`] entries
+    DelayDist(Vec<i16>),
+    Reorder(TcNetemReorder),
+    Corrupt(TcNetemCorrupt),
+    /// Loss model, used instead of the random loss of the parameters
+    Loss(Vec<NetemLossModel>),
+    /// Rate limiting
+    Rate(TcNetemRate),
+    /// Whether packets are ECN marked instead of dropped
+    Ecn(u32),
+    /// Rate in bytes per second, for rates that do not fit in 32 bits
+    Rate64(u64),
+    /// Latency, in nanoseconds
+    Latency64(i64),
+    /// Jitter, in nanoseconds
+    Jitter64(i64),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for NetemQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::NetemQdiscNla::*;
+        match self {
+            Corr(_) => TC_NETEM_CORR_LEN,
+            DelayDist(ref table) => table.len() * 2,
+            Reorder(_) => TC_NETEM_REORDER_LEN,
+            Corrupt(_) => TC_NETEM_CORRUPT_LEN,
+            Loss(ref models) => models.as_slice().buffer_len(),
+            Rate(_) => TC_NETEM_RATE_LEN,
+            Ecn(_) => 4,
+            Rate64(_) | Latency64(_) | Jitter64(_) => 8,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::NetemQdiscNla::*;
+        match self {
+            Corr(ref corr) => corr.emit(buffer),
+            DelayDist(ref table) => {
+                for (i, value) in table.iter().enumerate() {
+                    NativeEndian::write_i16(&mut buffer[i * 2..(i + 1) * 2], *value);
+                }
+            }
+            Reorder(ref reorder) => reorder.emit(buffer),
+            Corrupt(ref corrupt) => corrupt.emit(buffer),
+            Loss(ref models) => models.as_slice().emit(buffer),
+            Rate(ref rate) => rate.emit(buffer),
+            Ecn(ref value) => NativeEndian::write_u32(buffer, *value),
+            Rate64(ref value) => NativeEndian::write_u64(buffer, *value),
+            Latency64(ref value) | Jitter64(ref value) => NativeEndian::write_i64(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::NetemQdiscNla::*;
+        match self {
+            Corr(_) => TCA_NETEM_CORR,
+            DelayDist(_) => TCA_NETEM_DELAY_DIST,
+            Reorder(_) => TCA_NETEM_REORDER,
+            Corrupt(_) => TCA_NETEM_CORRUPT,
+            Loss(_) => TCA_NETEM_LOSS,
+            Rate(_) => TCA_NETEM_RATE,
+            Ecn(_) => TCA_NETEM_ECN,
+            Rate64(_) => TCA_NETEM_RATE64,
+            Latency64(_) => TCA_NETEM_LATENCY64,
+            Jitter64(_) => TCA_NETEM_JITTER64,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for NetemQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::NetemQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_NETEM_CORR => Corr(
+                TcNetemCorr::parse(&TcNetemCorrBuffer::new_checked(payload)?)
+                    .context("invalid TCA_NETEM_CORR value")?,
+            ),
+            TCA_NETEM_DELAY_DIST => {
+                DelayDist(parse_delay_dist(payload).context("invalid TCA_NETEM_DELAY_DIST value")?)
+            }
+            TCA_NETEM_REORDER => Reorder(
+                TcNetemReorder::parse(&TcNetemReorderBuffer::new_checked(payload)?)
+                    .context("invalid TCA_NETEM_REORDER value")?,
+            ),
+            TCA_NETEM_CORRUPT => Corrupt(
+                TcNetemCorrupt::parse(&TcNetemCorruptBuffer::new_checked(payload)?)
+                    .context("invalid TCA_NETEM_CORRUPT value")?,
+            ),
+            TCA_NETEM_LOSS => {
+                let mut models = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_NETEM_LOSS value")?;
+                    models.push(NetemLossModel::parse(&nla)?);
+                }
+                Loss(models)
+            }
+            TCA_NETEM_RATE => Rate(
+                TcNetemRate::parse(&TcNetemRateBuffer::new_checked(payload)?)
+                    .context("invalid TCA_NETEM_RATE value")?,
+            ),
+            TCA_NETEM_ECN => Ecn(parse_u32(payload).context("invalid TCA_NETEM_ECN value")?),
+            TCA_NETEM_RATE64 => {
+                Rate64(parse_u64(payload).context("invalid TCA_NETEM_RATE64 value")?)
+            }
+            TCA_NETEM_LATENCY64 => {
+                Latency64(parse_u64(payload).context("invalid TCA_NETEM_LATENCY64 value")? as i64)
+            }
+            TCA_NETEM_JITTER64 => {
+                Jitter64(parse_u64(payload).context("invalid TCA_NETEM_JITTER64 value")? as i64)
+            }
+            _ => Other(DefaultNla::parse(buf).context("invalid netem qdisc nla")?),
+        })
+    }
+}
+
+fn parse_delay_dist(payload: &[u8]) -> Result<Vec<i16>, DecodeError> {
+    let chunks = payload.chunks_exact(2);
+    if !chunks.remainder().is_empty() || chunks.len() > NETEM_DIST_MAX {
+        return Err(format!("invalid delay distribution table: {:?}", payload).into());
+    }
+    Ok(chunks.map(NativeEndian::read_i16).collect())
+}
+
+/// Loss models, carried by the `TCA_NETEM_LOSS` attribute
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetemLossModel {
+    /// 4-state Markov model
+    GilbertIntuitive(TcNetemGiModel),
+    /// Gilbert-Elliott model
+    GilbertElliott(TcNetemGeModel),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for NetemLossModel {
+    fn value_len(&self) -> usize {
+        use self::NetemLossModel::*;
+        match self {
+            GilbertIntuitive(_) => TC_NETEM_GI_MODEL_LEN,
+            GilbertElliott(_) => TC_NETEM_GE_MODEL_LEN,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::NetemLossModel::*;
+        match self {
+            GilbertIntuitive(ref model) => model.emit(buffer),
+            GilbertElliott(ref model) => model.emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::NetemLossModel::*;
+        match self {
+            GilbertIntuitive(_) => NETEM_LOSS_GI,
+            GilbertElliott(_) => NETEM_LOSS_GE,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for NetemLossModel {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::NetemLossModel::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            NETEM_LOSS_GI => GilbertIntuitive(
+                TcNetemGiModel::parse(&TcNetemGiModelBuffer::new_checked(payload)?)
+                    .context("invalid NETEM_LOSS_GI value")?,
+            ),
+            NETEM_LOSS_GE => GilbertElliott(
+                TcNetemGeModel::parse(&TcNetemGeModelBuffer::new_checked(payload)?)
+                    .context("invalid NETEM_LOSS_GE value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid netem loss model nla")?),
+        })
+    }
+}
+
+/// NETEM parameters (`struct tc_netem_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcNetemQopt {
+    /// Added delay, in scheduler ticks
+    pub latency: u32,
+    /// Maximum number of packets that can be queued
+    pub limit: u32,
+    /// Random packet loss, 0 is none and `u32::MAX` is 100%
+    pub loss: u32,
+    /// Re-ordering gap, 0 for none
+    pub gap: u32,
+    /// Random packet duplication, 0 is none and `u32::MAX` is 100%
+    pub duplicate: u32,
+    /// Random jitter, in scheduler ticks
+    pub jitter: u32,
+}
+
+pub const TC_NETEM_QOPT_LEN: usize = 24;
+
+buffer!(TcNetemQoptBuffer(TC_NETEM_QOPT_LEN) {
+    latency: (u32, 0..4),
+    limit: (u32, 4..8),
+    loss: (u32, 8..12),
+    gap: (u32, 12..16),
+    duplicate: (u32, 16..20),
+    jitter: (u32, 20..24),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcNetemQoptBuffer<T>> for TcNetemQopt {
+    fn parse(buf: &TcNetemQoptBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            latency: buf.latency(),
+            limit: buf.limit(),
+            loss: buf.loss(),
+            gap: buf.gap(),
+            duplicate: buf.duplicate(),
+            jitter: buf.jitter(),
+        })
+    }
+}
+
+impl Emitable for TcNetemQopt {
+    fn buffer_len(&self) -> usize {
+        TC_NETEM_QOPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcNetemQoptBuffer::new(buffer);
+        buffer.set_latency(self.latency);
+        buffer.set_limit(self.limit);
+        buffer.set_loss(self.loss);
+        buffer.set_gap(self.gap);
+        buffer.set_duplicate(self.duplicate);
+        buffer.set_jitter(self.jitter);
+    }
+}
+
+/// Correlations (`struct tc_netem_corr`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcNetemCorr {
+    pub delay_corr: u32,
+    pub loss_corr: u32,
+    pub dup_corr: u32,
+}
+
+pub const TC_NETEM_CORR_LEN: usize = 12;
+
+buffer!(TcNetemCorrBuffer(TC_NETEM_CORR_LEN) {
+    delay_corr: (u32, 0..4),
+    loss_corr: (u32, 4..8),
+    dup_corr: (u32, 8..12),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcNetemCorrBuffer<T>> for TcNetemCorr {
+    fn parse(buf: &TcNetemCorrBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            delay_corr: buf.delay_corr(),
+            loss_corr: buf.loss_corr(),
+            dup_corr: buf.dup_corr(),
+        })
+    }
+}
+
+impl Emitable for TcNetemCorr {
+    fn buffer_len(&self) -> usize {
+        TC_NETEM_CORR_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcNetemCorrBuffer::new(buffer);
+        buffer.set_delay_corr(self.delay_corr);
+        buffer.set_loss_corr(self.loss_corr);
+        buffer.set_dup_corr(self.dup_corr);
+    }
+}
+
+/// Re-ordering parameters (`struct tc_netem_reorder`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcNetemReorder {
+    pub probability: u32,
+    pub correlation: u32,
+}
+
+pub const TC_NETEM_REORDER_LEN: usize = 8;
+
+buffer!(TcNetemReorderBuffer(TC_NETEM_REORDER_LEN) {
+    probability: (u32, 0..4),
+    correlation: (u32, 4..8),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcNetemReorderBuffer<T>> for TcNetemReorder {
+    fn parse(buf: &TcNetemReorderBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            probability: buf.probability(),
+            correlation: buf.correlation(),
+        })
+    }
+}
+
+impl Emitable for TcNetemReorder {
+    fn buffer_len(&self) -> usize {
+        TC_NETEM_REORDER_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcNetemReorderBuffer::new(buffer);
+        buffer.set_probability(self.probability);
+        buffer.set_correlation(self.correlation);
+    }
+}
+
+/// Corruption parameters (`struct tc_netem_corrupt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcNetemCorrupt {
+    pub probability: u32,
+    pub correlation: u32,
+}
+
+pub const TC_NETEM_CORRUPT_LEN: usize = 8;
+
+buffer!(TcNetemCorruptBuffer(TC_NETEM_CORRUPT_LEN) {
+    probability: (u32, 0..4),
+    correlation: (u32, 4..8),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcNetemCorruptBuffer<T>> for TcNetemCorrupt {
+    fn parse(buf: &TcNetemCorruptBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            probability: buf.probability(),
+            correlation: buf.correlation(),
+        })
+    }
+}
+
+impl Emitable for TcNetemCorrupt {
+    fn buffer_len(&self) -> usize {
+        TC_NETEM_CORRUPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcNetemCorruptBuffer::new(buffer);
+        buffer.set_probability(self.probability);
+        buffer.set_correlation(self.correlation);
+    }
+}
+
+/// Rate limiting parameters (`struct tc_netem_rate`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcNetemRate {
+    /// Rate, in bytes per second
+    pub rate: u32,
+    pub packet_overhead: i32,
+    pub cell_size: u32,
+    pub cell_overhead: i32,
+}
+
+pub const TC_NETEM_RATE_LEN: usize = 16;
+
+buffer!(TcNetemRateBuffer(TC_NETEM_RATE_LEN) {
+    rate: (u32, 0..4),
+    packet_overhead: (i32, 4..8),
+    cell_size: (u32, 8..12),
+    cell_overhead: (i32, 12..16),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcNetemRateBuffer<T>> for TcNetemRate {
+    fn parse(buf: &TcNetemRateBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            rate: buf.rate(),
+            packet_overhead: buf.packet_overhead(),
+            cell_size: buf.cell_size(),
+            cell_overhead: buf.cell_overhead(),
+        })
+    }
+}
+
+impl Emitable for TcNetemRate {
+    fn buffer_len(&self) -> usize {
+        TC_NETEM_RATE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcNetemRateBuffer::new(buffer);
+        buffer.set_rate(self.rate);
+        buffer.set_packet_overhead(self.packet_overhead);
+        buffer.set_cell_size(self.cell_size);
+        buffer.set_cell_overhead(self.cell_overhead);
+    }
+}
+
+/// 4-state Markov loss model (`struct tc_netem_gimodel`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcNetemGiModel {
+    pub p13: u32,
+    pub p31: u32,
+    pub p32: u32,
+    pub p14: u32,
+    pub p23: u32,
+}
+
+pub const TC_NETEM_GI_MODEL_LEN: usize = 20;
+
+buffer!(TcNetemGiModelBuffer(TC_NETEM_GI_MODEL_LEN) {
+    p13: (u32, 0..4),
+    p31: (u32, 4..8),
+    p32: (u32, 8..12),
+    p14: (u32, 12..16),
+    p23: (u32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcNetemGiModelBuffer<T>> for TcNetemGiModel {
+    fn parse(buf: &TcNetemGiModelBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            p13: buf.p13(),
+            p31: buf.p31(),
+            p32: buf.p32(),
+            p14: buf.p14(),
+            p23: buf.p23(),
+        })
+    }
+}
+
+impl Emitable for TcNetemGiModel {
+    fn buffer_len(&self) -> usize {
+        TC_NETEM_GI_MODEL_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcNetemGiModelBuffer::new(buffer);
+        buffer.set_p13(self.p13);
+        buffer.set_p31(self.p31);
+        buffer.set_p32(self.p32);
+        buffer.set_p14(self.p14);
+        buffer.set_p23(self.p23);
+    }
+}
+
+/// Gilbert-Elliott loss model (`struct tc_netem_gemodel`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcNetemGeModel {
+    /// Probability of moving to the bad state
+    pub p: u32,
+    /// Probability of moving back to the good state
+    pub r: u32,
+    /// 1 minus the loss probability in the bad state
+    pub h: u32,
+    /// 1 minus the loss probability in the good state
+    pub k1: u32,
+}
+
+pub const TC_NETEM_GE_MODEL_LEN: usize = 16;
+
+buffer!(TcNetemGeModelBuffer(TC_NETEM_GE_MODEL_LEN) {
+    p: (u32, 0..4),
+    r: (u32, 4..8),
+    h: (u32, 8..12),
+    k1: (u32, 12..16),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcNetemGeModelBuffer<T>> for TcNetemGeModel {
+    fn parse(buf: &TcNetemGeModelBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            p: buf.p(),
+            r: buf.r(),
+            h: buf.h(),
+            k1: buf.k1(),
+        })
+    }
+}
+
+impl Emitable for TcNetemGeModel {
+    fn buffer_len(&self) -> usize {
+        TC_NETEM_GE_MODEL_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcNetemGeModelBuffer::new(buffer);
+        buffer.set_p(self.p);
+        buffer.set_r(self.r);
+        buffer.set_h(self.h);
+        buffer.set_k1(self.k1);
+    }
+}
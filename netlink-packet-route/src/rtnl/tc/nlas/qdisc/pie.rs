@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "pie";
+
+/// Options of the PIE (Proportional Integral controller Enhanced) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PieQdisc {
+    pub nlas: Vec<PieQdiscNla>,
+}
+
+impl Emitable for PieQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for PieQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid pie qdisc nla")?;
+            nlas.push(PieQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PieQdiscNla {
+    /// Target queue delay, in microseconds
+    Target(u32),
+    /// Maximum number of packets that can be queued
+    Limit(u32),
+    /// Interval between drop probability updates, in microseconds
+    Tupdate(u32),
+    /// Weight of the deviation from the target delay
+    Alpha(u32),
+    /// Weight of the variation of the queue delay
+    Beta(u32),
+    /// Whether packets are ECN marked instead of dropped
+    Ecn(u32),
+    /// Whether the drop probability is scaled by the packet size
+    Bytemode(u32),
+    /// Whether the queue delay is computed from the dequeue rate rather than
+    /// from packet timestamps
+    DqRateEstimator(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for PieQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::PieQdiscNla::*;
+        match self {
+            Other(ref nla) => nla.value_len(),
+            _ => 4,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::PieQdiscNla::*;
+        match self {
+            Target(ref value)
+            | Limit(ref value)
+            | Tupdate(ref value)
+            | Alpha(ref value)
+            | Beta(ref value)
+            | Ecn(ref value)
+            | Bytemode(ref value)
+            | DqRateEstimator(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::PieQdiscNla::*;
+        match self {
+            Target(_) => TCA_PIE_TARGET,
+            Limit(_) => TCA_PIE_LIMIT,
+            Tupdate(_) => TCA_PIE_TUPDATE,
+            Alpha(_) => TCA_PIE_ALPHA,
+            Beta(_) => TCA_PIE_BETA,
+            Ecn(_) => TCA_PIE_ECN,
+            Bytemode(_) => TCA_PIE_BYTEMODE,
+            DqRateEstimator(_) => TCA_PIE_DQ_RATE_ESTIMATOR,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for PieQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::PieQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_PIE_TARGET => Target(parse_u32(payload).context("invalid TCA_PIE_TARGET value")?),
+            TCA_PIE_LIMIT => Limit(parse_u32(payload).context("invalid TCA_PIE_LIMIT value")?),
+            TCA_PIE_TUPDATE => {
+                Tupdate(parse_u32(payload).context("invalid TCA_PIE_TUPDATE value")?)
+            }
+            TCA_PIE_ALPHA => Alpha(parse_u32(payload).context("invalid TCA_PIE_ALPHA value")?),
+            TCA_PIE_BETA => Beta(parse_u32(payload).context("invalid TCA_PIE_BETA value")?),
+            TCA_PIE_ECN => Ecn(parse_u32(payload).context("invalid TCA_PIE_ECN value")?),
+            TCA_PIE_BYTEMODE => {
+                Bytemode(parse_u32(payload).context("invalid TCA_PIE_BYTEMODE value")?)
+            }
+            TCA_PIE_DQ_RATE_ESTIMATOR => DqRateEstimator(
+                parse_u32(payload).context("invalid TCA_PIE_DQ_RATE_ESTIMATOR value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid pie qdisc nla")?),
+        })
+    }
+}
+
+/// PIE statistics (`struct tc_pie_xstats`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PieXStats {
+    /// Current drop probability, as a fixed point value
+    pub prob: u64,
+    /// Current queue delay, in microseconds
+    pub delay: u32,
+    /// Current average dequeue rate, in bytes per second
+    pub avg_dq_rate: u32,
+    /// Whether the queue delay is computed from the dequeue rate
+    pub dq_rate_estimating: u32,
+    /// Number of packets enqueued
+    pub packets_in: u32,
+    /// Number of packets dropped by the AQM
+    pub dropped: u32,
+    /// Number of times the packet limit was hit
+    pub overlimit: u32,
+    /// Largest queue length seen so far, in packets
+    pub maxq: u32,
+    /// Number of packets ECN marked instead of being dropped
+    pub ecn_mark: u32,
+}
+
+/// Size of `struct tc_pie_xstats`. Kernels older than 5.5, which do not
+/// report `dq_rate_estimating`, send statistics of the same size that are
+/// read with the fields after `avg_dq_rate` shifted by one.
+pub const PIE_XSTATS_LEN: usize = 40;
+
+buffer!(PieXStatsBuffer(PIE_XSTATS_LEN) {
+    prob: (u64, 0..8),
+    delay: (u32, 8..12),
+    avg_dq_rate: (u32, 12..16),
+    dq_rate_estimating: (u32, 16..20),
+    packets_in: (u32, 20..24),
+    dropped: (u32, 24..28),
+    overlimit: (u32, 28..32),
+    maxq: (u32, 32..36),
+    ecn_mark: (u32, 36..40),
+});
+
+impl<T: AsRef<[u8]>> Parseable<PieXStatsBuffer<T>> for PieXStats {
+    fn parse(buf: &PieXStatsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            prob: buf.prob(),
+            delay: buf.delay(),
+            avg_dq_rate: buf.avg_dq_rate(),
+            dq_rate_estimating: buf.dq_rate_estimating(),
+            packets_in: buf.packets_in(),
+            dropped: buf.dropped(),
+            overlimit: buf.overlimit(),
+            maxq: buf.maxq(),
+            ecn_mark: buf.ecn_mark(),
+        })
+    }
+}
+
+impl Emitable for PieXStats {
+    fn buffer_len(&self) -> usize {
+        PIE_XSTATS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = PieXStatsBuffer::new(buffer);
+        buffer.set_prob(self.prob);
+        buffer.set_delay(self.delay);
+        buffer.set_avg_dq_rate(self.avg_dq_rate);
+        buffer.set_dq_rate_estimating(self.dq_rate_estimating);
+        buffer.set_packets_in(self.packets_in);
+        buffer.set_dropped(self.dropped);
+        buffer.set_overlimit(self.overlimit);
+        buffer.set_maxq(self.maxq);
+        buffer.set_ecn_mark(self.ecn_mark);
+    }
+}
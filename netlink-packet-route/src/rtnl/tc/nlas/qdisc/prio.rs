@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    constants::*,
+    nlas::NlaBuffer,
+    traits::{Emitable, Parseable},
+    DecodeError,
+    EncodeError,
+};
+
+pub const KIND: &str = "prio";
+
+/// Options of the PRIO qdisc. The `TCA_OPTIONS` payload is a bare
+/// `struct tc_prio_qopt`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrioQdisc {
+    pub qopt: TcPrioMap,
+}
+
+impl Emitable for PrioQdisc {
+    fn buffer_len(&self) -> usize {
+        self.qopt.buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.qopt.emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for PrioQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let qopt = TcPrioMap::parse(&TcPrioMapBuffer::new_checked(buf.value())?)
+            .context("invalid prio qdisc parameters")?;
+        Ok(Self { qopt })
+    }
+}
+
+/// Bands and priority map of a PRIO qdisc (`struct tc_prio_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcPrioMap {
+    /// Number of bands
+    pub bands: u32,
+    /// Band of each of the packet priorities, from 0 to `TC_PRIO_MAX`
+    pub priomap: [u8; TC_PRIO_MAX as usize + 1],
+}
+
+impl Default for TcPrioMap {
+    /// The kernel default: 3 bands, with the priorities mapped to bands like
+    /// the IEEE 802.1p traffic classes are.
+    fn default() -> Self {
+        TcPrioMap {
+            bands: 3,
+            priomap: [1, 2, 2, 2, 1, 2, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1],
+        }
+    }
+}
+
+impl TcPrioMap {
+    /// Send the packets of priority `tos_class` to `band`.
+    pub fn set_priomap(&mut self, tos_class: u8, band: u8) -> Result<(), EncodeError> {
+        if tos_class > TC_PRIO_MAX {
+            return Err(format!(
+                "invalid priority {}, the maximum is {}",
+                tos_class, TC_PRIO_MAX
+            )
+            .into());
+        }
+        if u32::from(band) >= self.bands {
+            return Err(format!("invalid band {}, there are {} bands", band, self.bands).into());
+        }
+        self.priomap[tos_class as usize] = band;
+        Ok(())
+    }
+}
+
+pub const TC_PRIO_MAP_LEN: usize = 20;
+
+buffer!(TcPrioMapBuffer(TC_PRIO_MAP_LEN) {
+    bands: (u32, 0..4),
+    priomap: (slice, 4..20),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcPrioMapBuffer<&'a T>> for TcPrioMap {
+    fn parse(buf: &TcPrioMapBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let bands = buf.bands();
+        let mut priomap = [0; TC_PRIO_MAX as usize + 1];
+        priomap.copy_from_slice(buf.priomap());
+        if let Some(band) = priomap.iter().find(|band| u32::from(**band) >= bands) {
+            return Err(format!("invalid priomap: band {} out of {} bands", band, bands).into());
+        }
+        Ok(Self { bands, priomap })
+    }
+}
+
+impl Emitable for TcPrioMap {
+    fn buffer_len(&self) -> usize {
+        TC_PRIO_MAP_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcPrioMapBuffer::new(buffer);
+        buffer.set_bands(self.bands);
+        buffer.priomap_mut().copy_from_slice(&self.priomap);
+    }
+}
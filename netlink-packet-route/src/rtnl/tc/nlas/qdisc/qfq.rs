@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+
+pub const KIND: &str = "qfq";
+
+/// The QFQ (Quick Fair Queueing) qdisc does not take any option, the
+/// weights are set per class, see
+/// [`QfqClass`](crate::nlas::tc::class::QfqClass).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QfqQdisc;
@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::parse_u32,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "red";
+
+/// Size of the `TCA_RED_STAB` table
+pub const RED_STAB_SIZE: usize = 256;
+
+/// Options of the RED (Random Early Detection) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RedQdisc {
+    pub nlas: Vec<RedQdiscNla>,
+}
+
+impl Emitable for RedQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for RedQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid red qdisc nla")?;
+            nlas.push(RedQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RedQdiscNla {
+    Parms(TcRedQopt),
+    /// Table used to compute the average queue length after an idle period
+    Stab(Vec<u8>),
+    /// Maximum drop probability, scaled so that `u32::MAX` is 100%
+    MaxP(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for RedQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::RedQdiscNla::*;
+        match self {
+            Parms(_) => TC_RED_QOPT_LEN,
+            Stab(ref table) => table.len(),
+            MaxP(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::RedQdiscNla::*;
+        match self {
+            Parms(ref qopt) => qopt.emit(buffer),
+            Stab(ref table) => buffer.copy_from_slice(table.as_slice()),
+            MaxP(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::RedQdiscNla::*;
+        match self {
+            Parms(_) => TCA_RED_PARMS,
+            Stab(_) => TCA_RED_STAB,
+            MaxP(_) => TCA_RED_MAX_P,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for RedQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::RedQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_RED_PARMS => Parms(
+                TcRedQopt::parse(&TcRedQoptBuffer::new_checked(payload)?)
+                    .context("invalid TCA_RED_PARMS value")?,
+            ),
+            TCA_RED_STAB => {
+                if payload.len() != RED_STAB_SIZE {
+                    return Err(format!("invalid TCA_RED_STAB value: {:?}", payload).into());
+                }
+                Stab(payload.to_vec())
+            }
+            TCA_RED_MAX_P => MaxP(parse_u32(payload).context("invalid TCA_RED_MAX_P value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid red qdisc nla")?),
+        })
+    }
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct RedFlags: u8 {
+        /// Mark packets with ECN CE instead of dropping them, when possible
+        const TC_RED_ECN = TC_RED_ECN;
+        /// Drop packets above `qth_max` even if they could be ECN marked
+        const TC_RED_HARDDROP = TC_RED_HARDDROP;
+        /// Adapt `max_p` to the traffic
+        const TC_RED_ADAPTATIVE = TC_RED_ADAPTATIVE;
+        /// Never drop packets that are not ECN capable, enqueue them
+        const TC_RED_NODROP = TC_RED_NODROP;
+    }
+}
+
+impl Default for RedFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// RED parameters (`struct tc_red_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcRedQopt {
+    /// Hard limit on the queue length, in bytes
+    pub limit: u32,
+    /// Average queue length above which packets may be marked or dropped
+    pub qth_min: u32,
+    /// Average queue length above which all the packets are marked or
+    /// dropped
+    pub qth_max: u32,
+    /// Log2 of the weight of the queue length in the moving average
+    pub wlog: u8,
+    /// Log2 of `qth_max - qth_min` divided by the maximum probability
+    pub plog: u8,
+    /// Log2 of the cell size of the idle damping table
+    pub scell_log: u8,
+    pub flags: RedFlags,
+}
+
+pub const TC_RED_QOPT_LEN: usize = 16;
+
+buffer!(TcRedQoptBuffer(TC_RED_QOPT_LEN) {
+    limit: (u32, 0..4),
+    qth_min: (u32, 4..8),
+    qth_max: (u32, 8..12),
+    wlog: (u8, 12),
+    plog: (u8, 13),
+    scell_log: (u8, 14),
+    flags: (u8, 15),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcRedQoptBuffer<T>> for TcRedQopt {
+    fn parse(buf: &TcRedQoptBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            limit: buf.limit(),
+            qth_min: buf.qth_min(),
+            qth_max: buf.qth_max(),
+            wlog: buf.wlog(),
+            plog: buf.plog(),
+            scell_log: buf.scell_log(),
+            flags: RedFlags::from_bits_truncate(buf.flags()),
+        })
+    }
+}
+
+impl Emitable for TcRedQopt {
+    fn buffer_len(&self) -> usize {
+        TC_RED_QOPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcRedQoptBuffer::new(buffer);
+        buffer.set_limit(self.limit);
+        buffer.set_qth_min(self.qth_min);
+        buffer.set_qth_max(self.qth_max);
+        buffer.set_wlog(self.wlog);
+        buffer.set_plog(self.plog);
+        buffer.set_scell_log(self.scell_log);
+        buffer.set_flags(self.flags.bits());
+    }
+}
+
+/// RED statistics (`struct tc_red_xstats`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RedXStats {
+    /// Early drops
+    pub early: u32,
+    /// Drops because the hard limit was reached
+    pub pdrop: u32,
+    /// Drops for other reasons
+    pub other: u32,
+    /// Packets ECN marked instead of being dropped
+    pub marked: u32,
+}
+
+pub const RED_XSTATS_LEN: usize = 16;
+
+buffer!(RedXStatsBuffer(RED_XSTATS_LEN) {
+    early: (u32, 0..4),
+    pdrop: (u32, 4..8),
+    other: (u32, 8..12),
+    marked: (u32, 12..16),
+});
+
+impl<T: AsRef<[u8]>> Parseable<RedXStatsBuffer<T>> for RedXStats {
+    fn parse(buf: &RedXStatsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            early: buf.early(),
+            pdrop: buf.pdrop(),
+            other: buf.other(),
+            marked: buf.marked(),
+        })
+    }
+}
+
+impl Emitable for RedXStats {
+    fn buffer_len(&self) -> usize {
+        RED_XSTATS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = RedXStatsBuffer::new(buffer);
+        buffer.set_early(self.early);
+        buffer.set_pdrop(self.pdrop);
+        buffer.set_other(self.other);
+        buffer.set_marked(self.marked);
+    }
+}
@@ -0,0 +1,259 @@
+// SPDX-License-Identifier: MIT
+
+use crate::{
+    nlas::NlaBuffer,
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "sfq";
+
+/// Options of the SFQ (Stochastic Fairness Queueing) qdisc. The
+/// `TCA_OPTIONS` payload is a bare structure: the original `tc_sfq_qopt`,
+/// or its `tc_sfq_qopt_v1` extension, which the kernel uses in dumps.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SfqQdisc {
+    V0(TcSfqQopt),
+    V1(TcSfqQoptV1),
+}
+
+impl Default for SfqQdisc {
+    fn default() -> Self {
+        SfqQdisc::V0(TcSfqQopt::default())
+    }
+}
+
+impl Emitable for SfqQdisc {
+    fn buffer_len(&self) -> usize {
+        match self {
+            SfqQdisc::V0(ref qopt) => qopt.buffer_len(),
+            SfqQdisc::V1(ref qopt) => qopt.buffer_len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            SfqQdisc::V0(ref qopt) => qopt.emit(buffer),
+            SfqQdisc::V1(ref qopt) => qopt.emit(buffer),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for SfqQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        if payload.len() >= TC_SFQ_QOPT_V1_LEN {
+            Ok(SfqQdisc::V1(TcSfqQoptV1::parse(&TcSfqQoptV1Buffer::new(
+                payload,
+            ))?))
+        } else {
+            Ok(SfqQdisc::V0(TcSfqQopt::parse(
+                &TcSfqQoptBuffer::new_checked(payload)?,
+            )?))
+        }
+    }
+}
+
+/// SFQ parameters (`struct tc_sfq_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcSfqQopt {
+    /// Number of bytes a flow can dequeue during a round
+    pub quantum: u32,
+    /// Period, in seconds, after which the flow hash is randomised again.
+    /// This limits the time two flows that collide share a bucket. 0
+    /// disables perturbation, `tc` commonly uses 10 seconds.
+    pub perturb_period: i32,
+    /// Maximum number of packets that can be queued
+    pub limit: u32,
+    /// Number of hash buckets
+    pub divisor: u32,
+    /// Maximum number of flows
+    pub flows: u32,
+}
+
+pub const TC_SFQ_QOPT_LEN: usize = 20;
+
+buffer!(TcSfqQoptBuffer(TC_SFQ_QOPT_LEN) {
+    quantum: (u32, 0..4),
+    perturb_period: (i32, 4..8),
+    limit: (u32, 8..12),
+    divisor: (u32, 12..16),
+    flows: (u32, 16..20),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcSfqQoptBuffer<T>> for TcSfqQopt {
+    fn parse(buf: &TcSfqQoptBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            quantum: buf.quantum(),
+            perturb_period: buf.perturb_period(),
+            limit: buf.limit(),
+            divisor: buf.divisor(),
+            flows: buf.flows(),
+        })
+    }
+}
+
+impl Emitable for TcSfqQopt {
+    fn buffer_len(&self) -> usize {
+        TC_SFQ_QOPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcSfqQoptBuffer::new(buffer);
+        buffer.set_quantum(self.quantum);
+        buffer.set_perturb_period(self.perturb_period);
+        buffer.set_limit(self.limit);
+        buffer.set_divisor(self.divisor);
+        buffer.set_flows(self.flows);
+    }
+}
+
+/// Extended SFQ parameters (`struct tc_sfq_qopt_v1`), which add per-flow
+/// limits and RED to the original parameters.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcSfqQoptV1 {
+    pub v0: TcSfqQopt,
+    /// Maximum number of packets per flow
+    pub depth: u32,
+    /// Whether packets are dropped from the head rather than the tail
+    pub headdrop: u32,
+    /// Maximum number of bytes per flow
+    pub limit: u32,
+    /// RED minimum threshold, in bytes
+    pub qth_min: u32,
+    /// RED maximum threshold, in bytes
+    pub qth_max: u32,
+    pub wlog: u8,
+    pub plog: u8,
+    pub scell_log: u8,
+    pub flags: u8,
+    /// RED maximum drop probability
+    pub max_p: u32,
+    pub stats: TcSfqRedStats,
+}
+
+/// RED statistics of a SFQ qdisc (`struct tc_sfqred_stats`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcSfqRedStats {
+    /// Early drops
+    pub prob_drop: u32,
+    /// Drops because the average queue length exceeded `qth_max`
+    pub forced_drop: u32,
+    /// Early marks
+    pub prob_mark: u32,
+    /// Marks because the average queue length exceeded `qth_max`
+    pub forced_mark: u32,
+    /// Early marks in head drop mode
+    pub prob_mark_head: u32,
+    /// Forced marks in head drop mode
+    pub forced_mark_head: u32,
+}
+
+pub const TC_SFQ_QOPT_V1_LEN: usize = 72;
+
+buffer!(TcSfqQoptV1Buffer(TC_SFQ_QOPT_V1_LEN) {
+    v0: (slice, 0..20),
+    depth: (u32, 20..24),
+    headdrop: (u32, 24..28),
+    limit: (u32, 28..32),
+    qth_min: (u32, 32..36),
+    qth_max: (u32, 36..40),
+    wlog: (u8, 40),
+    plog: (u8, 41),
+    scell_log: (u8, 42),
+    flags: (u8, 43),
+    max_p: (u32, 44..48),
+    prob_drop: (u32, 48..52),
+    forced_drop: (u32, 52..56),
+    prob_mark: (u32, 56..60),
+    forced_mark: (u32, 60..64),
+    prob_mark_head: (u32, 64..68),
+    forced_mark_head: (u32, 68..72),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcSfqQoptV1Buffer<&'a T>> for TcSfqQoptV1 {
+    fn parse(buf: &TcSfqQoptV1Buffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            v0: TcSfqQopt::parse(&TcSfqQoptBuffer::new(buf.v0()))?,
+            depth: buf.depth(),
+            headdrop: buf.headdrop(),
+            limit: buf.limit(),
+            qth_min: buf.qth_min(),
+            qth_max: buf.qth_max(),
+            wlog: buf.wlog(),
+            plog: buf.plog(),
+            scell_log: buf.scell_log(),
+            flags: buf.flags(),
+            max_p: buf.max_p(),
+            stats: TcSfqRedStats {
+                prob_drop: buf.prob_drop(),
+                forced_drop: buf.forced_drop(),
+                prob_mark: buf.prob_mark(),
+                forced_mark: buf.forced_mark(),
+                prob_mark_head: buf.prob_mark_head(),
+                forced_mark_head: buf.forced_mark_head(),
+            },
+        })
+    }
+}
+
+impl Emitable for TcSfqQoptV1 {
+    fn buffer_len(&self) -> usize {
+        TC_SFQ_QOPT_V1_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcSfqQoptV1Buffer::new(buffer);
+        self.v0.emit(buffer.v0_mut());
+        buffer.set_depth(self.depth);
+        buffer.set_headdrop(self.headdrop);
+        buffer.set_limit(self.limit);
+        buffer.set_qth_min(self.qth_min);
+        buffer.set_qth_max(self.qth_max);
+        buffer.set_wlog(self.wlog);
+        buffer.set_plog(self.plog);
+        buffer.set_scell_log(self.scell_log);
+        buffer.set_flags(self.flags);
+        buffer.set_max_p(self.max_p);
+        buffer.set_prob_drop(self.stats.prob_drop);
+        buffer.set_forced_drop(self.stats.forced_drop);
+        buffer.set_prob_mark(self.stats.prob_mark);
+        buffer.set_forced_mark(self.stats.forced_mark);
+        buffer.set_prob_mark_head(self.stats.prob_mark_head);
+        buffer.set_forced_mark_head(self.stats.forced_mark_head);
+    }
+}
+
+/// Statistics of a SFQ class, that is a flow (`struct tc_sfq_xstats`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SfqXStats {
+    /// Number of bytes the flow can still dequeue during the current round
+    pub allot: i32,
+}
+
+pub const SFQ_XSTATS_LEN: usize = 4;
+
+buffer!(SfqXStatsBuffer(SFQ_XSTATS_LEN) {
+    allot: (i32, 0..4),
+});
+
+impl<T: AsRef<[u8]>> Parseable<SfqXStatsBuffer<T>> for SfqXStats {
+    fn parse(buf: &SfqXStatsBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self { allot: buf.allot() })
+    }
+}
+
+impl Emitable for SfqXStats {
+    fn buffer_len(&self) -> usize {
+        SFQ_XSTATS_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        SfqXStatsBuffer::new(buffer).set_allot(self.allot);
+    }
+}
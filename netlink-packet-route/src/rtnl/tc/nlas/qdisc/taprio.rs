@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_i32, parse_i64, parse_u32, parse_u8},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+pub const KIND: &str = "taprio";
+
+/// Options of the TAPRIO (Time Aware Priority Shaper) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaprioQdisc {
+    pub nlas: Vec<TaprioQdiscNla>,
+}
+
+impl Emitable for TaprioQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TaprioQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid taprio qdisc nla")?;
+            nlas.push(TaprioQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaprioQdiscNla {
+    /// Traffic classes and their mapping to priorities and queues
+    Priomap(TcMqprioQopt),
+    /// Gate control list
+    SchedEntryList(Vec<TaprioSchedEntry>),
+    /// Start of the schedule, in nanoseconds since the epoch of `clockid`
+    BaseTime(i64),
+    /// Clock the schedule is based on, for instance `CLOCK_TAI`
+    Clockid(i32),
+    /// Duration of the schedule, in nanoseconds
+    CycleTime(i64),
+    /// Duration the last cycle can be extended by, in nanoseconds
+    CycleTimeExtension(i64),
+    /// `TCA_TAPRIO_ATTR_FLAG_*` flags
+    Flags(u32),
+    /// Time the packets are sent before their transmission time when
+    /// txtime assist mode is used, in nanoseconds
+    TxtimeDelay(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for TaprioQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::TaprioQdiscNla::*;
+        match self {
+            Priomap(_) => TC_MQPRIO_QOPT_LEN,
+            SchedEntryList(ref entries) => entries.as_slice().buffer_len(),
+            BaseTime(_) | CycleTime(_) | CycleTimeExtension(_) => 8,
+            Clockid(_) | Flags(_) | TxtimeDelay(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::TaprioQdiscNla::*;
+        match self {
+            Priomap(ref qopt) => qopt.emit(buffer),
+            SchedEntryList(ref entries) => entries.as_slice().emit(buffer),
+            BaseTime(ref value) | CycleTime(ref value) | CycleTimeExtension(ref value) => {
+                NativeEndian::write_i64(buffer, *value)
+            }
+            Clockid(ref value) => NativeEndian::write_i32(buffer, *value),
+            Flags(ref value) | TxtimeDelay(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::TaprioQdiscNla::*;
+        match self {
+            Priomap(_) => TCA_TAPRIO_ATTR_PRIOMAP,
+            SchedEntryList(_) => TCA_TAPRIO_ATTR_SCHED_ENTRY_LIST,
+            BaseTime(_) => TCA_TAPRIO_ATTR_SCHED_BASE_TIME,
+            Clockid(_) => TCA_TAPRIO_ATTR_SCHED_CLOCKID,
+            CycleTime(_) => TCA_TAPRIO_ATTR_SCHED_CYCLE_TIME,
+            CycleTimeExtension(_) => TCA_TAPRIO_ATTR_SCHED_CYCLE_TIME_EXTENSION,
+            Flags(_) => TCA_TAPRIO_ATTR_FLAGS,
+            TxtimeDelay(_) => TCA_TAPRIO_ATTR_TXTIME_DELAY,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TaprioQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::TaprioQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_TAPRIO_ATTR_PRIOMAP => Priomap(
+                TcMqprioQopt::parse(&TcMqprioQoptBuffer::new_checked(payload)?)
+                    .context("invalid TCA_TAPRIO_ATTR_PRIOMAP value")?,
+            ),
+            TCA_TAPRIO_ATTR_SCHED_ENTRY_LIST => {
+                let mut entries = vec![];
+                for nla in NlasIterator::new(payload) {
+                    let nla = nla.context("invalid TCA_TAPRIO_ATTR_SCHED_ENTRY_LIST value")?;
+                    entries.push(
+                        TaprioSchedEntry::parse(&nla)
+                            .context("invalid TCA_TAPRIO_ATTR_SCHED_ENTRY_LIST value")?,
+                    );
+                }
+                SchedEntryList(entries)
+            }
+            TCA_TAPRIO_ATTR_SCHED_BASE_TIME => BaseTime(
+                parse_i64(payload).context("invalid TCA_TAPRIO_ATTR_SCHED_BASE_TIME value")?,
+            ),
+            TCA_TAPRIO_ATTR_SCHED_CLOCKID => {
+                Clockid(parse_i32(payload).context("invalid TCA_TAPRIO_ATTR_SCHED_CLOCKID value")?)
+            }
+            TCA_TAPRIO_ATTR_SCHED_CYCLE_TIME => CycleTime(
+                parse_i64(payload).context("invalid TCA_TAPRIO_ATTR_SCHED_CYCLE_TIME value")?,
+            ),
+            TCA_TAPRIO_ATTR_SCHED_CYCLE_TIME_EXTENSION => CycleTimeExtension(
+                parse_i64(payload)
+                    .context("invalid TCA_TAPRIO_ATTR_SCHED_CYCLE_TIME_EXTENSION value")?,
+            ),
+            TCA_TAPRIO_ATTR_FLAGS => {
+                Flags(parse_u32(payload).context("invalid TCA_TAPRIO_ATTR_FLAGS value")?)
+            }
+            TCA_TAPRIO_ATTR_TXTIME_DELAY => TxtimeDelay(
+                parse_u32(payload).context("invalid TCA_TAPRIO_ATTR_TXTIME_DELAY value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid taprio qdisc nla")?),
+        })
+    }
+}
+
+/// An entry of the gate control list (`TCA_TAPRIO_SCHED_ENTRY`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaprioSchedEntry {
+    /// Position of the entry in the list. It is set by the kernel and ignored
+    /// when the schedule is configured.
+    pub index: u32,
+    /// One of the `TC_TAPRIO_CMD_*` commands
+    pub command: u8,
+    /// Bitmap of the traffic classes whose gate is open
+    pub gate_mask: u32,
+    /// Duration of the entry, in nanoseconds
+    pub interval: u32,
+}
+
+impl TaprioSchedEntry {
+    fn nlas(&self) -> [TaprioSchedEntryNla; 4] {
+        [
+            TaprioSchedEntryNla::Index(self.index),
+            TaprioSchedEntryNla::Cmd(self.command),
+            TaprioSchedEntryNla::GateMask(self.gate_mask),
+            TaprioSchedEntryNla::Interval(self.interval),
+        ]
+    }
+}
+
+impl nlas::Nla for TaprioSchedEntry {
+    fn value_len(&self) -> usize {
+        self.nlas().as_ref().buffer_len()
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.nlas().as_ref().emit(buffer)
+    }
+
+    fn kind(&self) -> u16 {
+        TCA_TAPRIO_SCHED_ENTRY
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TaprioSchedEntry {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        if buf.kind() != TCA_TAPRIO_SCHED_ENTRY {
+            return Err(format!("unexpected taprio schedule attribute {}", buf.kind()).into());
+        }
+        let mut entry = TaprioSchedEntry::default();
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid TCA_TAPRIO_SCHED_ENTRY value")?;
+            match TaprioSchedEntryNla::parse(&nla)? {
+                TaprioSchedEntryNla::Index(index) => entry.index = index,
+                TaprioSchedEntryNla::Cmd(command) => entry.command = command,
+                TaprioSchedEntryNla::GateMask(gate_mask) => entry.gate_mask = gate_mask,
+                TaprioSchedEntryNla::Interval(interval) => entry.interval = interval,
+                TaprioSchedEntryNla::Other(_) => {}
+            }
+        }
+        Ok(entry)
+    }
+}
+
+/// Attributes of a `TCA_TAPRIO_SCHED_ENTRY`
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum TaprioSchedEntryNla {
+    Index(u32),
+    Cmd(u8),
+    GateMask(u32),
+    Interval(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for TaprioSchedEntryNla {
+    fn value_len(&self) -> usize {
+        use self::TaprioSchedEntryNla::*;
+        match self {
+            Cmd(_) => 1,
+            Index(_) | GateMask(_) | Interval(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::TaprioSchedEntryNla::*;
+        match self {
+            Cmd(ref value) => buffer[0] = *value,
+            Index(ref value) | GateMask(ref value) | Interval(ref value) => {
+                NativeEndian::write_u32(buffer, *value)
+            }
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::TaprioSchedEntryNla::*;
+        match self {
+            Index(_) => TCA_TAPRIO_SCHED_ENTRY_INDEX,
+            Cmd(_) => TCA_TAPRIO_SCHED_ENTRY_CMD,
+            GateMask(_) => TCA_TAPRIO_SCHED_ENTRY_GATE_MASK,
+            Interval(_) => TCA_TAPRIO_SCHED_ENTRY_INTERVAL,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TaprioSchedEntryNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::TaprioSchedEntryNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_TAPRIO_SCHED_ENTRY_INDEX => {
+                Index(parse_u32(payload).context("invalid TCA_TAPRIO_SCHED_ENTRY_INDEX value")?)
+            }
+            TCA_TAPRIO_SCHED_ENTRY_CMD => {
+                Cmd(parse_u8(payload).context("invalid TCA_TAPRIO_SCHED_ENTRY_CMD value")?)
+            }
+            TCA_TAPRIO_SCHED_ENTRY_GATE_MASK => GateMask(
+                parse_u32(payload).context("invalid TCA_TAPRIO_SCHED_ENTRY_GATE_MASK value")?,
+            ),
+            TCA_TAPRIO_SCHED_ENTRY_INTERVAL => Interval(
+                parse_u32(payload).context("invalid TCA_TAPRIO_SCHED_ENTRY_INTERVAL value")?,
+            ),
+            _ => Other(DefaultNla::parse(buf).context("invalid taprio schedule entry nla")?),
+        })
+    }
+}
+
+/// Mapping of the priorities to traffic classes, and of the traffic classes
+/// to transmit queues (`struct tc_mqprio_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcMqprioQopt {
+    /// Number of traffic classes
+    pub num_tc: u8,
+    /// Traffic class of each of the packet priorities
+    pub prio_tc_map: [u8; TC_QOPT_BITMASK as usize + 1],
+    /// Whether the configuration is offloaded to the hardware
+    pub hw: u8,
+    /// Number of queues of each traffic class
+    pub count: [u16; TC_QOPT_MAX_QUEUE],
+    /// First queue of each traffic class
+    pub offset: [u16; TC_QOPT_MAX_QUEUE],
+}
+
+pub const TC_MQPRIO_QOPT_LEN: usize = 82;
+
+buffer!(TcMqprioQoptBuffer(TC_MQPRIO_QOPT_LEN) {
+    num_tc: (u8, 0),
+    prio_tc_map: (slice, 1..17),
+    hw: (u8, 17),
+    count: (slice, 18..50),
+    offset: (slice, 50..82),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcMqprioQoptBuffer<&'a T>> for TcMqprioQopt {
+    fn parse(buf: &TcMqprioQoptBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut qopt = TcMqprioQopt {
+            num_tc: buf.num_tc(),
+            hw: buf.hw(),
+            ..Default::default()
+        };
+        qopt.prio_tc_map.copy_from_slice(buf.prio_tc_map());
+        NativeEndian::read_u16_into(buf.count(), &mut qopt.count);
+        NativeEndian::read_u16_into(buf.offset(), &mut qopt.offset);
+        Ok(qopt)
+    }
+}
+
+impl Emitable for TcMqprioQopt {
+    fn buffer_len(&self) -> usize {
+        TC_MQPRIO_QOPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcMqprioQoptBuffer::new(buffer);
+        buffer.set_num_tc(self.num_tc);
+        buffer.prio_tc_map_mut().copy_from_slice(&self.prio_tc_map);
+        buffer.set_hw(self.hw);
+        NativeEndian::write_u16_into(&self.count, buffer.count_mut());
+        NativeEndian::write_u16_into(&self.offset, buffer.offset_mut());
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{
+    constants::*,
+    nlas::{self, DefaultNla, NlaBuffer, NlasIterator},
+    parsers::{parse_u32, parse_u64},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use super::super::{emit_u32_list, parse_u32_list, TcRateSpec, TcRateSpecBuffer};
+
+pub const KIND: &str = "tbf";
+
+/// Options of the TBF (Token Bucket Filter) qdisc
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TbfQdisc {
+    pub nlas: Vec<TbfQdiscNla>,
+}
+
+impl Emitable for TbfQdisc {
+    fn buffer_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TbfQdisc {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(buf.value()) {
+            let nla = nla.context("invalid tbf qdisc nla")?;
+            nlas.push(TbfQdiscNla::parse(&nla)?);
+        }
+        Ok(Self { nlas })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TbfQdiscNla {
+    Parms(TcTbfQopt),
+    /// Rate table of `rate`
+    Rtab(Vec<u32>),
+    /// Rate table of `peakrate`
+    Ptab(Vec<u32>),
+    /// Rate in bytes per second, for rates that do not fit in 32 bits
+    Rate64(u64),
+    /// Peak rate in bytes per second, for rates that do not fit in 32 bits
+    Prate64(u64),
+    /// Size of the bucket, in bytes
+    Burst(u32),
+    /// Size of the peak rate bucket, in bytes
+    Pburst(u32),
+    Other(DefaultNla),
+}
+
+impl nlas::Nla for TbfQdiscNla {
+    fn value_len(&self) -> usize {
+        use self::TbfQdiscNla::*;
+        match self {
+            Parms(_) => TC_TBF_QOPT_LEN,
+            Rtab(ref table) | Ptab(ref table) => table.len() * 4,
+            Rate64(_) | Prate64(_) => 8,
+            Burst(_) | Pburst(_) => 4,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::TbfQdiscNla::*;
+        match self {
+            Parms(ref qopt) => qopt.emit(buffer),
+            Rtab(ref table) | Ptab(ref table) => emit_u32_list(buffer, table),
+            Rate64(ref value) | Prate64(ref value) => NativeEndian::write_u64(buffer, *value),
+            Burst(ref value) | Pburst(ref value) => NativeEndian::write_u32(buffer, *value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::TbfQdiscNla::*;
+        match self {
+            Parms(_) => TCA_TBF_PARMS,
+            Rtab(_) => TCA_TBF_RTAB,
+            Ptab(_) => TCA_TBF_PTAB,
+            Rate64(_) => TCA_TBF_RATE64,
+            Prate64(_) => TCA_TBF_PRATE64,
+            Burst(_) => TCA_TBF_BURST,
+            Pburst(_) => TCA_TBF_PBURST,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TbfQdiscNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        use self::TbfQdiscNla::*;
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            TCA_TBF_PARMS => Parms(
+                TcTbfQopt::parse(&TcTbfQoptBuffer::new_checked(payload)?)
+                    .context("invalid TCA_TBF_PARMS value")?,
+            ),
+            TCA_TBF_RTAB => Rtab(parse_u32_list(payload).context("invalid TCA_TBF_RTAB value")?),
+            TCA_TBF_PTAB => Ptab(parse_u32_list(payload).context("invalid TCA_TBF_PTAB value")?),
+            TCA_TBF_RATE64 => Rate64(parse_u64(payload).context("invalid TCA_TBF_RATE64 value")?),
+            TCA_TBF_PRATE64 => {
+                Prate64(parse_u64(payload).context("invalid TCA_TBF_PRATE64 value")?)
+            }
+            TCA_TBF_BURST => Burst(parse_u32(payload).context("invalid TCA_TBF_BURST value")?),
+            TCA_TBF_PBURST => Pburst(parse_u32(payload).context("invalid TCA_TBF_PBURST value")?),
+            _ => Other(DefaultNla::parse(buf).context("invalid tbf qdisc nla")?),
+        })
+    }
+}
+
+/// TBF parameters (`struct tc_tbf_qopt`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcTbfQopt {
+    pub rate: TcRateSpec,
+    pub peakrate: TcRateSpec,
+    /// Maximum number of bytes that can be queued
+    pub limit: u32,
+    /// Size of the bucket, in scheduler ticks
+    pub buffer: u32,
+    /// Size of the peak rate bucket, in scheduler ticks
+    pub mtu: u32,
+}
+
+pub const TC_TBF_QOPT_LEN: usize = 36;
+
+buffer!(TcTbfQoptBuffer(TC_TBF_QOPT_LEN) {
+    rate: (slice, 0..12),
+    peakrate: (slice, 12..24),
+    limit: (u32, 24..28),
+    buffer: (u32, 28..32),
+    mtu: (u32, 32..36),
+});
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<TcTbfQoptBuffer<&'a T>> for TcTbfQopt {
+    fn parse(buf: &TcTbfQoptBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            rate: TcRateSpec::parse(&TcRateSpecBuffer::new(buf.rate()))?,
+            peakrate: TcRateSpec::parse(&TcRateSpecBuffer::new(buf.peakrate()))?,
+            limit: buf.limit(),
+            buffer: buf.buffer(),
+            mtu: buf.mtu(),
+        })
+    }
+}
+
+impl Emitable for TcTbfQopt {
+    fn buffer_len(&self) -> usize {
+        TC_TBF_QOPT_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcTbfQoptBuffer::new(buffer);
+        self.rate.emit(buffer.rate_mut());
+        self.peakrate.emit(buffer.peakrate_mut());
+        buffer.set_limit(self.limit);
+        buffer.set_buffer(self.buffer);
+        buffer.set_mtu(self.mtu);
+    }
+}
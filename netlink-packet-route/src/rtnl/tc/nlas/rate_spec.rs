@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MIT
+
+use crate::{
+    tc::{compute_rate_table, rate::default_cell_log},
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+/// Rate specification (`struct tc_ratespec`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcRateSpec {
+    /// Log2 of the size of a cell of the rate table
+    pub cell_log: u8,
+    /// One of the `TC_LINKLAYER_*` constants, see [`Linklayer`](crate::tc::Linklayer)
+    pub linklayer: u8,
+    /// Per-packet overhead, in bytes
+    pub overhead: u16,
+    pub cell_align: i16,
+    /// Minimum packet unit, in bytes
+    pub mpu: u16,
+    /// Rate, in bytes per second
+    pub rate: u32,
+}
+
+pub const TC_RATE_SPEC_LEN: usize = 12;
+
+/// Number of entries of a rate table
+pub const TC_RTAB_SIZE: usize = 256;
+
+buffer!(TcRateSpecBuffer(TC_RATE_SPEC_LEN) {
+    cell_log: (u8, 0),
+    linklayer: (u8, 1),
+    overhead: (u16, 2..4),
+    cell_align: (i16, 4..6),
+    mpu: (u16, 6..8),
+    rate: (u32, 8..12),
+});
+
+impl TcRateSpec {
+    /// Compute the rate table the kernel expects alongside this rate
+    /// specification (`TCA_*_RTAB` attributes). Each of the 256 entries is
+    /// the time it takes to transmit a packet of the corresponding cell size
+    /// at this rate, in scheduler ticks. `cell_log` and `cell_align` are
+    /// updated to match the table. An `mtu` of 0 defaults to 2047 bytes.
+    pub fn rate_table(&mut self, mtu: u32) -> Vec<u32> {
+        self.cell_log = default_cell_log(mtu);
+        self.cell_align = -1;
+        compute_rate_table(
+            self.rate.into(),
+            mtu,
+            self.cell_log,
+            self.linklayer.into(),
+            self.mpu,
+        )
+        .to_vec()
+    }
+}
+
+impl<T: AsRef<[u8]>> Parseable<TcRateSpecBuffer<T>> for TcRateSpec {
+    fn parse(buf: &TcRateSpecBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            cell_log: buf.cell_log(),
+            linklayer: buf.linklayer(),
+            overhead: buf.overhead(),
+            cell_align: buf.cell_align(),
+            mpu: buf.mpu(),
+            rate: buf.rate(),
+        })
+    }
+}
+
+impl Emitable for TcRateSpec {
+    fn buffer_len(&self) -> usize {
+        TC_RATE_SPEC_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcRateSpecBuffer::new(buffer);
+        buffer.set_cell_log(self.cell_log);
+        buffer.set_linklayer(self.linklayer);
+        buffer.set_overhead(self.overhead);
+        buffer.set_cell_align(self.cell_align);
+        buffer.set_mpu(self.mpu);
+        buffer.set_rate(self.rate);
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
 
 /// Generic queue statistics
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stats {
     /// Number of enqueued bytes
     pub bytes: u64,
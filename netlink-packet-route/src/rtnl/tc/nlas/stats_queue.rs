@@ -7,6 +7,7 @@ use crate::{
 
 /// Queuing statistics
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatsQueue {
     /// queue length
     pub qlen: u32,
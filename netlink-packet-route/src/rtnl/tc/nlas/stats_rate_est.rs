@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT
+
+use crate::{
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+/// Rate estimator statistics (`struct gnet_stats_rate_est`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcRateEst {
+    /// Estimated byte rate, in bytes per second
+    pub bps: u32,
+    /// Estimated packet rate, in packets per second
+    pub pps: u32,
+}
+
+pub const TC_RATE_EST_LEN: usize = 8;
+
+buffer!(TcRateEstBuffer(TC_RATE_EST_LEN) {
+    bps: (u32, 0..4),
+    pps: (u32, 4..8),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcRateEstBuffer<T>> for TcRateEst {
+    fn parse(buf: &TcRateEstBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(TcRateEst {
+            bps: buf.bps(),
+            pps: buf.pps(),
+        })
+    }
+}
+
+impl Emitable for TcRateEst {
+    fn buffer_len(&self) -> usize {
+        TC_RATE_EST_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcRateEstBuffer::new(buffer);
+        buffer.set_bps(self.bps);
+        buffer.set_pps(self.pps);
+    }
+}
+
+/// 64 bits rate estimator statistics (`struct gnet_stats_rate_est64`), used
+/// when the rates do not fit in [`TcRateEst`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcRateEst64 {
+    /// Estimated byte rate, in bytes per second
+    pub bps: u64,
+    /// Estimated packet rate, in packets per second
+    pub pps: u64,
+}
+
+pub const TC_RATE_EST64_LEN: usize = 16;
+
+buffer!(TcRateEst64Buffer(TC_RATE_EST64_LEN) {
+    bps: (u64, 0..8),
+    pps: (u64, 8..16),
+});
+
+impl<T: AsRef<[u8]>> Parseable<TcRateEst64Buffer<T>> for TcRateEst64 {
+    fn parse(buf: &TcRateEst64Buffer<T>) -> Result<Self, DecodeError> {
+        Ok(TcRateEst64 {
+            bps: buf.bps(),
+            pps: buf.pps(),
+        })
+    }
+}
+
+impl Emitable for TcRateEst64 {
+    fn buffer_len(&self) -> usize {
+        TC_RATE_EST64_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TcRateEst64Buffer::new(buffer);
+        buffer.set_bps(self.bps);
+        buffer.set_pps(self.pps);
+    }
+}
@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+
+use crate::{
+    nlas::tc::{
+        class::{
+            drr::{self, DrrXStats, DrrXStatsBuffer},
+            htb::{self, HtbXStats, HtbXStatsBuffer},
+        },
+        qdisc::{
+            cake::{self, CakeXStats},
+            cbq::{self, CbqXStats, CbqXStatsBuffer},
+            codel::{self, CodelXStats, CodelXStatsBuffer},
+            fq_codel::{self, FqCodelXStats, FqCodelXStatsBuffer},
+            pie::{self, PieXStats, PieXStatsBuffer},
+            red::{self, RedXStats, RedXStatsBuffer},
+            sfq::{self, SfqXStats, SfqXStatsBuffer},
+        },
+    },
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+/// Qdisc or class specific statistics, carried by the `TCA_XSTATS` and
+/// `TCA_STATS_APP` attributes. Their format depends on the kind of qdisc.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum XStats {
+    Cake(CakeXStats),
+    FqCodel(FqCodelXStats),
+    Sfq(SfqXStats),
+    Red(RedXStats),
+    Codel(CodelXStats),
+    Drr(DrrXStats),
+    Htb(HtbXStats),
+    Pie(PieXStats),
+    Cbq(CbqXStats),
+    Other(#[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_base64"))] Vec<u8>),
+}
+
+impl XStats {
+    pub fn parse_with_kind(payload: &[u8], kind: &str) -> Result<Self, DecodeError> {
+        Ok(match kind {
+            cake::KIND => XStats::Cake(CakeXStats::parse(payload).context("invalid cake xstats")?),
+            fq_codel::KIND if FqCodelXStats::is_qdisc_xstats(payload) => XStats::FqCodel(
+                FqCodelXStats::parse(&FqCodelXStatsBuffer::new_checked(payload)?)
+                    .context("invalid fq_codel xstats")?,
+            ),
+            sfq::KIND if payload.len() == sfq::SFQ_XSTATS_LEN => XStats::Sfq(
+                SfqXStats::parse(&SfqXStatsBuffer::new(payload)).context("invalid sfq xstats")?,
+            ),
+            red::KIND => XStats::Red(
+                RedXStats::parse(&RedXStatsBuffer::new_checked(payload)?)
+                    .context("invalid red xstats")?,
+            ),
+            codel::KIND => XStats::Codel(
+                CodelXStats::parse(&CodelXStatsBuffer::new_checked(payload)?)
+                    .context("invalid codel xstats")?,
+            ),
+            drr::KIND => XStats::Drr(
+                DrrXStats::parse(&DrrXStatsBuffer::new_checked(payload)?)
+                    .context("invalid drr xstats")?,
+            ),
+            htb::KIND => XStats::Htb(
+                HtbXStats::parse(&HtbXStatsBuffer::new_checked(payload)?)
+                    .context("invalid htb xstats")?,
+            ),
+            pie::KIND if payload.len() == pie::PIE_XSTATS_LEN => XStats::Pie(
+                PieXStats::parse(&PieXStatsBuffer::new_checked(payload)?)
+                    .context("invalid pie xstats")?,
+            ),
+            cbq::KIND => XStats::Cbq(
+                CbqXStats::parse(&CbqXStatsBuffer::new_checked(payload)?)
+                    .context("invalid cbq xstats")?,
+            ),
+            _ => XStats::Other(payload.to_vec()),
+        })
+    }
+}
+
+impl Emitable for XStats {
+    fn buffer_len(&self) -> usize {
+        match self {
+            XStats::Cake(ref stats) => stats.buffer_len(),
+            XStats::FqCodel(ref stats) => stats.buffer_len(),
+            XStats::Sfq(ref stats) => stats.buffer_len(),
+            XStats::Red(ref stats) => stats.buffer_len(),
+            XStats::Codel(ref stats) => stats.buffer_len(),
+            XStats::Drr(ref stats) => stats.buffer_len(),
+            XStats::Htb(ref stats) => stats.buffer_len(),
+            XStats::Pie(ref stats) => stats.buffer_len(),
+            XStats::Cbq(ref stats) => stats.buffer_len(),
+            XStats::Other(ref bytes) => bytes.len(),
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        match self {
+            XStats::Cake(ref stats) => stats.emit(buffer),
+            XStats::FqCodel(ref stats) => stats.emit(buffer),
+            XStats::Sfq(ref stats) => stats.emit(buffer),
+            XStats::Red(ref stats) => stats.emit(buffer),
+            XStats::Codel(ref stats) => stats.emit(buffer),
+            XStats::Drr(ref stats) => stats.emit(buffer),
+            XStats::Htb(ref stats) => stats.emit(buffer),
+            XStats::Pie(ref stats) => stats.emit(buffer),
+            XStats::Cbq(ref stats) => stats.emit(buffer),
+            XStats::Other(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+        }
+    }
+}
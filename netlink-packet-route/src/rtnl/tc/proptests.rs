@@ -0,0 +1,677 @@
+// SPDX-License-Identifier: MIT
+
+//! Round-trip tests on generated messages: anything `emit()` produces must
+//! parse back to the same value. The options of every qdisc are generated
+//! by [`qdisc_options`], new qdiscs should be added there.
+
+use proptest::prelude::*;
+
+use crate::{
+    nlas::{
+        tc::{
+            qdisc::{
+                cake::{self, CakeQdiscNla},
+                cbq::{self, CbqNla, TcCbqFopt, TcCbqLss, TcCbqOvl, TcCbqPolice, TcCbqWrr},
+                clsact,
+                codel::{self, CodelQdiscNla},
+                drr,
+                ets::{self, EtsQdiscNla},
+                fifo::{self, FifoOpt},
+                fq_codel::{self, FqCodelQdiscNla},
+                gred::{self, GredQdiscNla, GredVqNla, TcGredDps, TcGredQopt, TcGredVqEntry},
+                hfsc::{self, HfscQopt},
+                htb::{self, HtbQdiscNla, TcaHtbGlob},
+                ingress,
+                netem::{
+                    self,
+                    NetemLossModel,
+                    NetemQdiscNla,
+                    TcNetemCorr,
+                    TcNetemCorrupt,
+                    TcNetemGeModel,
+                    TcNetemGiModel,
+                    TcNetemQopt,
+                    TcNetemRate,
+                    TcNetemReorder,
+                },
+                pie::{self, PieQdiscNla},
+                prio::{self, TcPrioMap},
+                qfq,
+                red::{self, RedFlags, RedQdiscNla, TcRedQopt, RED_STAB_SIZE},
+                sfq::{self, TcSfqQopt, TcSfqQoptV1, TcSfqRedStats},
+                taprio::{self, TaprioQdiscNla, TaprioSchedEntry, TcMqprioQopt},
+                tbf::{self, TbfQdiscNla, TcTbfQopt},
+                BfifoQdisc,
+                CakeQdisc,
+                CbqQdisc,
+                Clsact,
+                CodelQdisc,
+                DrrQdisc,
+                EtsQdisc,
+                FqCodelQdisc,
+                GredQdisc,
+                HfscQdisc,
+                HtbQdisc,
+                Ingress,
+                NetemQdisc,
+                PfifoQdisc,
+                PieQdisc,
+                PrioQdisc,
+                QfqQdisc,
+                RedQdisc,
+                SfqQdisc,
+                TaprioQdisc,
+                TbfQdisc,
+            },
+            Nla,
+            Qdisc,
+            TcRateSpec,
+        },
+        DefaultNla,
+        NlaBuffer,
+    },
+    traits::{Emitable, Parseable},
+    TcHandle,
+    TcHeader,
+    TcMessage,
+    TcMessageBuffer,
+};
+
+/// Any handle is valid, including `TC_H_ROOT` and `TC_H_INGRESS`
+fn handle() -> impl Strategy<Value = TcHandle> {
+    any::<u32>().prop_map(TcHandle)
+}
+
+fn header() -> impl Strategy<Value = TcHeader> {
+    (any::<u8>(), any::<i32>(), handle(), handle(), any::<u32>()).prop_map(
+        |(family, index, handle, parent, info)| TcHeader {
+            family,
+            index,
+            handle,
+            parent,
+            info,
+        },
+    )
+}
+
+fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..32)
+}
+
+/// Attributes with a type in `kinds`, which must not be known to the parser
+fn other_nla(kinds: std::ops::Range<u16>) -> impl Strategy<Value = DefaultNla> {
+    (kinds, bytes()).prop_map(|(kind, value)| {
+        let mut buffer = vec![0; 4 + value.len()];
+        let mut nla = NlaBuffer::new(&mut buffer[..]);
+        nla.set_kind(kind);
+        nla.set_length(4 + value.len() as u16);
+        buffer[4..].copy_from_slice(&value);
+        DefaultNla::parse(&NlaBuffer::new(&buffer[..])).unwrap()
+    })
+}
+
+fn u32_list() -> impl Strategy<Value = Vec<u32>> {
+    prop::collection::vec(any::<u32>(), 0..8)
+}
+
+/// Stability tables of RED and GRED, which must have exactly
+/// `RED_STAB_SIZE` entries
+fn red_stab() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), RED_STAB_SIZE..RED_STAB_SIZE + 1)
+}
+
+/// Unknown RED flags are dropped when parsing
+fn red_flags() -> impl Strategy<Value = RedFlags> {
+    any::<u8>().prop_map(RedFlags::from_bits_truncate)
+}
+
+fn rate_spec() -> impl Strategy<Value = TcRateSpec> {
+    (
+        any::<u8>(),
+        any::<u8>(),
+        any::<u16>(),
+        any::<i16>(),
+        any::<u16>(),
+        any::<u32>(),
+    )
+        .prop_map(
+            |(cell_log, linklayer, overhead, cell_align, mpu, rate)| TcRateSpec {
+                cell_log,
+                linklayer,
+                overhead,
+                cell_align,
+                mpu,
+                rate,
+            },
+        )
+}
+
+fn htb_nla() -> impl Strategy<Value = HtbQdiscNla> {
+    use HtbQdiscNla::*;
+    prop_oneof![
+        (
+            any::<u32>(),
+            any::<u32>(),
+            any::<u32>(),
+            any::<u32>(),
+            any::<u32>()
+        )
+            .prop_map(|(version, rate2quantum, defcls, debug, direct_pkts)| {
+                Init(TcaHtbGlob {
+                    version,
+                    rate2quantum,
+                    defcls,
+                    debug,
+                    direct_pkts,
+                })
+            }),
+        u32_list().prop_map(Ctab),
+        u32_list().prop_map(Rtab),
+        any::<u32>().prop_map(DirectQlen),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn tbf_nla() -> impl Strategy<Value = TbfQdiscNla> {
+    use TbfQdiscNla::*;
+    prop_oneof![
+        (
+            rate_spec(),
+            rate_spec(),
+            any::<u32>(),
+            any::<u32>(),
+            any::<u32>()
+        )
+            .prop_map(|(rate, peakrate, limit, buffer, mtu)| Parms(TcTbfQopt {
+                rate,
+                peakrate,
+                limit,
+                buffer,
+                mtu,
+            })),
+        u32_list().prop_map(Rtab),
+        u32_list().prop_map(Ptab),
+        any::<u64>().prop_map(Rate64),
+        any::<u64>().prop_map(Prate64),
+        any::<u32>().prop_map(Burst),
+        any::<u32>().prop_map(Pburst),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn netem_qopt() -> impl Strategy<Value = TcNetemQopt> {
+    any::<[u32; 6]>().prop_map(
+        |[latency, limit, loss, gap, duplicate, jitter]| TcNetemQopt {
+            latency,
+            limit,
+            loss,
+            gap,
+            duplicate,
+            jitter,
+        },
+    )
+}
+
+fn netem_loss_model() -> impl Strategy<Value = NetemLossModel> {
+    use NetemLossModel::*;
+    prop_oneof![
+        any::<[u32; 5]>().prop_map(|[p13, p31, p32, p14, p23]| {
+            GilbertIntuitive(TcNetemGiModel {
+                p13,
+                p31,
+                p32,
+                p14,
+                p23,
+            })
+        }),
+        any::<[u32; 4]>().prop_map(|[p, r, h, k1]| GilbertElliott(TcNetemGeModel { p, r, h, k1 })),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn netem_nla() -> impl Strategy<Value = NetemQdiscNla> {
+    use NetemQdiscNla::*;
+    prop_oneof![
+        any::<[u32; 3]>().prop_map(|[delay_corr, loss_corr, dup_corr]| Corr(TcNetemCorr {
+            delay_corr,
+            loss_corr,
+            dup_corr,
+        })),
+        prop::collection::vec(any::<i16>(), 0..16).prop_map(DelayDist),
+        any::<[u32; 2]>().prop_map(|[probability, correlation]| {
+            Reorder(TcNetemReorder {
+                probability,
+                correlation,
+            })
+        }),
+        any::<[u32; 2]>().prop_map(|[probability, correlation]| {
+            Corrupt(TcNetemCorrupt {
+                probability,
+                correlation,
+            })
+        }),
+        prop::collection::vec(netem_loss_model(), 0..4).prop_map(Loss),
+        (any::<u32>(), any::<i32>(), any::<u32>(), any::<i32>()).prop_map(
+            |(rate, packet_overhead, cell_size, cell_overhead)| Rate(TcNetemRate {
+                rate,
+                packet_overhead,
+                cell_size,
+                cell_overhead,
+            })
+        ),
+        any::<u32>().prop_map(Ecn),
+        any::<u64>().prop_map(Rate64),
+        any::<i64>().prop_map(Latency64),
+        any::<i64>().prop_map(Jitter64),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn sfq_qopt() -> impl Strategy<Value = TcSfqQopt> {
+    (
+        any::<u32>(),
+        any::<i32>(),
+        any::<u32>(),
+        any::<u32>(),
+        any::<u32>(),
+    )
+        .prop_map(
+            |(quantum, perturb_period, limit, divisor, flows)| TcSfqQopt {
+                quantum,
+                perturb_period,
+                limit,
+                divisor,
+                flows,
+            },
+        )
+}
+
+/// Both versions of the parameters, which are told apart by their length
+fn sfq_options() -> impl Strategy<Value = SfqQdisc> {
+    prop_oneof![
+        sfq_qopt().prop_map(SfqQdisc::V0),
+        (
+            sfq_qopt(),
+            any::<[u32; 5]>(),
+            any::<[u8; 4]>(),
+            any::<u32>(),
+            any::<[u32; 6]>(),
+        )
+            .prop_map(
+                |(
+                    v0,
+                    [depth, headdrop, limit, qth_min, qth_max],
+                    [wlog, plog, scell_log, flags],
+                    max_p,
+                    [prob_drop, forced_drop, prob_mark, forced_mark, prob_mark_head, forced_mark_head],
+                )| {
+                    SfqQdisc::V1(TcSfqQoptV1 {
+                        v0,
+                        depth,
+                        headdrop,
+                        limit,
+                        qth_min,
+                        qth_max,
+                        wlog,
+                        plog,
+                        scell_log,
+                        flags,
+                        max_p,
+                        stats: TcSfqRedStats {
+                            prob_drop,
+                            forced_drop,
+                            prob_mark,
+                            forced_mark,
+                            prob_mark_head,
+                            forced_mark_head,
+                        },
+                    })
+                }
+            ),
+    ]
+}
+
+/// The priority map can only refer to existing bands, of which there is at
+/// least one
+fn prio_map() -> impl Strategy<Value = TcPrioMap> {
+    (1u32..17, any::<[u8; 16]>()).prop_map(|(bands, mut priomap)| {
+        for band in priomap.iter_mut() {
+            *band %= bands as u8;
+        }
+        TcPrioMap { bands, priomap }
+    })
+}
+
+fn red_nla() -> impl Strategy<Value = RedQdiscNla> {
+    use RedQdiscNla::*;
+    prop_oneof![
+        (any::<[u32; 3]>(), any::<[u8; 3]>(), red_flags()).prop_map(
+            |([limit, qth_min, qth_max], [wlog, plog, scell_log], flags)| Parms(TcRedQopt {
+                limit,
+                qth_min,
+                qth_max,
+                wlog,
+                plog,
+                scell_log,
+                flags,
+            })
+        ),
+        red_stab().prop_map(Stab),
+        any::<u32>().prop_map(MaxP),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+/// `Raw` carries no value and the modes are generated from their numeric
+/// value, since an `Other` mode with a known value does not round-trip.
+fn cake_nla() -> impl Strategy<Value = CakeQdiscNla> {
+    use CakeQdiscNla::*;
+    prop_oneof![
+        any::<u64>().prop_map(BaseRate64),
+        any::<u32>().prop_map(|mode| DiffservMode(mode.into())),
+        any::<u32>().prop_map(Atm),
+        any::<u32>().prop_map(|mode| FlowMode(mode.into())),
+        any::<i32>().prop_map(Overhead),
+        any::<u32>().prop_map(Rtt),
+        any::<u32>().prop_map(Target),
+        any::<bool>().prop_map(AutorateIngress),
+        any::<u32>().prop_map(Memory),
+        any::<bool>().prop_map(Nat),
+        Just(Raw),
+        any::<bool>().prop_map(Wash),
+        any::<u32>().prop_map(Mpu),
+        any::<bool>().prop_map(Ingress),
+        any::<u32>().prop_map(AckFilter),
+        any::<bool>().prop_map(SplitGso),
+        any::<u32>().prop_map(Fwmark),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn ets_nla() -> impl Strategy<Value = EtsQdiscNla> {
+    use EtsQdiscNla::*;
+    prop_oneof![
+        any::<u8>().prop_map(Nbands),
+        any::<u8>().prop_map(Nstrict),
+        u32_list().prop_map(Quanta),
+        prop::collection::vec(any::<u8>(), 0..16).prop_map(Priomap),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn taprio_nla() -> impl Strategy<Value = TaprioQdiscNla> {
+    use TaprioQdiscNla::*;
+    let sched_entry = (any::<u32>(), any::<u8>(), any::<u32>(), any::<u32>()).prop_map(
+        |(index, command, gate_mask, interval)| TaprioSchedEntry {
+            index,
+            command,
+            gate_mask,
+            interval,
+        },
+    );
+    prop_oneof![
+        (
+            any::<u8>(),
+            any::<[u8; 16]>(),
+            any::<u8>(),
+            any::<[u16; 16]>(),
+            any::<[u16; 16]>(),
+        )
+            .prop_map(|(num_tc, prio_tc_map, hw, count, offset)| {
+                Priomap(TcMqprioQopt {
+                    num_tc,
+                    prio_tc_map,
+                    hw,
+                    count,
+                    offset,
+                })
+            }),
+        prop::collection::vec(sched_entry, 0..4).prop_map(SchedEntryList),
+        any::<i64>().prop_map(BaseTime),
+        any::<i32>().prop_map(Clockid),
+        any::<i64>().prop_map(CycleTime),
+        any::<i64>().prop_map(CycleTimeExtension),
+        any::<u32>().prop_map(Flags),
+        any::<u32>().prop_map(TxtimeDelay),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn gred_qopt() -> impl Strategy<Value = TcGredQopt> {
+    (any::<[u32; 10]>(), any::<[u8; 4]>(), any::<[u32; 2]>()).prop_map(
+        |(
+            [limit, qth_min, qth_max, dp, backlog, qave, forced, early, other, pdrop],
+            [wlog, plog, scell_log, prio],
+            [packets, bytesin],
+        )| TcGredQopt {
+            limit,
+            qth_min,
+            qth_max,
+            dp,
+            backlog,
+            qave,
+            forced,
+            early,
+            other,
+            pdrop,
+            wlog,
+            plog,
+            scell_log,
+            prio,
+            packets,
+            bytesin,
+        },
+    )
+}
+
+fn gred_vq_nla() -> impl Strategy<Value = GredVqNla> {
+    use GredVqNla::*;
+    prop_oneof![
+        any::<u32>().prop_map(Dp),
+        any::<u64>().prop_map(StatBytes),
+        any::<u32>().prop_map(StatPackets),
+        any::<u32>().prop_map(StatBacklog),
+        any::<u32>().prop_map(StatProbDrop),
+        any::<u32>().prop_map(StatProbMark),
+        any::<u32>().prop_map(StatForcedDrop),
+        any::<u32>().prop_map(StatForcedMark),
+        any::<u32>().prop_map(StatPdrop),
+        any::<u32>().prop_map(StatOther),
+        any::<u32>().prop_map(Flags),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn gred_nla() -> impl Strategy<Value = GredQdiscNla> {
+    use GredQdiscNla::*;
+    let vq = prop::collection::vec(gred_vq_nla(), 0..4).prop_map(|nlas| TcGredVqEntry { nlas });
+    prop_oneof![
+        prop::collection::vec(gred_qopt(), 0..4).prop_map(Parms),
+        red_stab().prop_map(Stab),
+        (any::<[u32; 2]>(), any::<u8>(), red_flags()).prop_map(|([dps, def_dp], grio, flags)| {
+            Dps(TcGredDps {
+                dps,
+                def_dp,
+                grio,
+                flags,
+            })
+        }),
+        u32_list().prop_map(MaxP),
+        any::<u32>().prop_map(Limit),
+        prop::collection::vec(vq, 0..4).prop_map(VqList),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn cbq_nla() -> impl Strategy<Value = CbqNla> {
+    use CbqNla::*;
+    prop_oneof![
+        (any::<[u8; 4]>(), any::<[u32; 4]>()).prop_map(
+            |([change, flags, ewma_log, level], [maxidle, minidle, offtime, avpkt])| {
+                LssOpt(TcCbqLss {
+                    change,
+                    flags,
+                    ewma_log,
+                    level,
+                    maxidle,
+                    minidle,
+                    offtime,
+                    avpkt,
+                })
+            }
+        ),
+        (any::<[u8; 3]>(), any::<[u32; 2]>()).prop_map(
+            |([flags, priority, cpriority], [allot, weight])| WrrOpt(TcCbqWrr {
+                flags,
+                priority,
+                cpriority,
+                allot,
+                weight,
+            })
+        ),
+        any::<[u32; 3]>().prop_map(|[split, defmap, defchange]| Fopt(TcCbqFopt {
+            split,
+            defmap,
+            defchange,
+        })),
+        (any::<[u8; 2]>(), any::<u32>()).prop_map(|([strategy, priority2], penalty)| {
+            OvlStrategy(TcCbqOvl {
+                strategy,
+                priority2,
+                penalty,
+            })
+        }),
+        rate_spec().prop_map(Rate),
+        u32_list().prop_map(Rtab),
+        any::<u8>().prop_map(|police| Police(TcCbqPolice { police })),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn fq_codel_nla() -> impl Strategy<Value = FqCodelQdiscNla> {
+    use FqCodelQdiscNla::*;
+    prop_oneof![
+        any::<u32>().prop_map(Target),
+        any::<u32>().prop_map(Limit),
+        any::<u32>().prop_map(Interval),
+        any::<u32>().prop_map(Ecn),
+        any::<u32>().prop_map(Flows),
+        any::<u32>().prop_map(Quantum),
+        any::<u32>().prop_map(CeThreshold),
+        any::<u32>().prop_map(DropBatchSize),
+        any::<u32>().prop_map(MemoryLimit),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn codel_nla() -> impl Strategy<Value = CodelQdiscNla> {
+    use CodelQdiscNla::*;
+    prop_oneof![
+        any::<u32>().prop_map(Target),
+        any::<u32>().prop_map(Limit),
+        any::<u32>().prop_map(Interval),
+        any::<u32>().prop_map(Ecn),
+        any::<u32>().prop_map(CeThreshold),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+fn pie_nla() -> impl Strategy<Value = PieQdiscNla> {
+    use PieQdiscNla::*;
+    prop_oneof![
+        any::<u32>().prop_map(Target),
+        any::<u32>().prop_map(Limit),
+        any::<u32>().prop_map(Tupdate),
+        any::<u32>().prop_map(Alpha),
+        any::<u32>().prop_map(Beta),
+        any::<u32>().prop_map(Ecn),
+        any::<u32>().prop_map(Bytemode),
+        any::<u32>().prop_map(DqRateEstimator),
+        other_nla(100..200).prop_map(Other),
+    ]
+}
+
+/// Qdisc options, with the kind they must be announced with
+fn qdisc_options() -> impl Strategy<Value = (&'static str, Qdisc)> {
+    prop_oneof![
+        Just((ingress::KIND, Qdisc::Ingress(Ingress))),
+        Just((clsact::KIND, Qdisc::Clsact(Clsact))),
+        prop::collection::vec(fq_codel_nla(), 0..8)
+            .prop_map(|nlas| (fq_codel::KIND, Qdisc::FqCodel(FqCodelQdisc { nlas }))),
+        prop::collection::vec(codel_nla(), 0..8)
+            .prop_map(|nlas| (codel::KIND, Qdisc::Codel(CodelQdisc { nlas }))),
+        prop::collection::vec(pie_nla(), 0..8)
+            .prop_map(|nlas| (pie::KIND, Qdisc::Pie(PieQdisc { nlas }))),
+        prop::collection::vec(htb_nla(), 0..8)
+            .prop_map(|nlas| (htb::KIND, Qdisc::Htb(HtbQdisc { nlas }))),
+        prop::collection::vec(tbf_nla(), 0..8)
+            .prop_map(|nlas| (tbf::KIND, Qdisc::Tbf(TbfQdisc { nlas }))),
+        (netem_qopt(), prop::collection::vec(netem_nla(), 0..8))
+            .prop_map(|(qopt, nlas)| (netem::KIND, Qdisc::Netem(NetemQdisc { qopt, nlas }))),
+        sfq_options().prop_map(|sfq| (sfq::KIND, Qdisc::Sfq(sfq))),
+        prio_map().prop_map(|qopt| (prio::KIND, Qdisc::Prio(PrioQdisc { qopt }))),
+        prop::collection::vec(red_nla(), 0..8)
+            .prop_map(|nlas| (red::KIND, Qdisc::Red(RedQdisc { nlas }))),
+        prop::collection::vec(cake_nla(), 0..8)
+            .prop_map(|nlas| (cake::KIND, Qdisc::Cake(CakeQdisc { nlas }))),
+        any::<u16>().prop_map(|defcls| {
+            let qopt = HfscQopt { defcls };
+            (hfsc::KIND, Qdisc::Hfsc(HfscQdisc { qopt }))
+        }),
+        Just((drr::KIND, Qdisc::Drr(DrrQdisc))),
+        prop::collection::vec(ets_nla(), 0..8)
+            .prop_map(|nlas| (ets::KIND, Qdisc::Ets(EtsQdisc { nlas }))),
+        prop::collection::vec(taprio_nla(), 0..8)
+            .prop_map(|nlas| (taprio::KIND, Qdisc::Taprio(TaprioQdisc { nlas }))),
+        prop::collection::vec(gred_nla(), 0..8)
+            .prop_map(|nlas| (gred::KIND, Qdisc::Gred(GredQdisc { nlas }))),
+        Just((qfq::KIND, Qdisc::Qfq(QfqQdisc))),
+        any::<u32>().prop_map(|limit| {
+            let qopt = FifoOpt { limit };
+            (fifo::PFIFO_KIND, Qdisc::Pfifo(PfifoQdisc { qopt }))
+        }),
+        any::<u32>().prop_map(|limit| {
+            let qopt = FifoOpt { limit };
+            (fifo::BFIFO_KIND, Qdisc::Bfifo(BfifoQdisc { qopt }))
+        }),
+        prop::collection::vec(cbq_nla(), 0..8)
+            .prop_map(|nlas| (cbq::KIND, Qdisc::Cbq(CbqQdisc { nlas }))),
+    ]
+}
+
+/// Attributes that do not depend on the qdisc kind. `TCA_PAD` is left out
+/// since it is skipped when parsing.
+fn generic_nla() -> impl Strategy<Value = Nla<Qdisc>> {
+    prop_oneof![
+        bytes().prop_map(Nla::Unspec),
+        bytes().prop_map(Nla::Rate),
+        bytes().prop_map(Nla::Fcnt),
+        bytes().prop_map(Nla::Stab),
+        any::<u32>().prop_map(Nla::Chain),
+        any::<u8>().prop_map(Nla::HwOffload),
+        other_nla(100..200).prop_map(Nla::Other),
+    ]
+}
+
+fn qdisc_message() -> impl Strategy<Value = TcMessage<Qdisc>> {
+    (
+        header(),
+        qdisc_options(),
+        prop::collection::vec(generic_nla(), 0..4),
+    )
+        .prop_map(|(header, (kind, options), others)| {
+            let mut nlas = vec![Nla::Kind(kind.to_string()), Nla::Options(options)];
+            nlas.extend(others);
+            TcMessage { header, nlas }
+        })
+}
+
+proptest! {
+    #[test]
+    fn qdisc_message_round_trip(msg in qdisc_message()) {
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf);
+        let parsed: TcMessage<Qdisc> = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+        prop_assert_eq!(parsed, msg);
+    }
+}
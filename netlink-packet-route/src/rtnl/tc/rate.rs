@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MIT
+
+use crate::{constants::*, nlas::tc::TC_RTAB_SIZE};
+
+/// Number of packet scheduler ticks per microsecond, as reported by
+/// `/proc/net/psched` on kernels using high resolution timers.
+const TICKS_PER_USEC: f64 = 15.625;
+
+/// Link layer of a rate specification, carried by its `linklayer` field. It
+/// determines how the size of a packet is adjusted in the rate table.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Linklayer {
+    /// Set by old versions of iproute2, treated like [`Linklayer::Ethernet`]
+    Unaware,
+    Ethernet,
+    /// Packets are split into 53 bytes ATM cells carrying 48 bytes each, as
+    /// on most ADSL lines
+    Atm,
+    Other(u8),
+}
+
+impl From<u8> for Linklayer {
+    fn from(value: u8) -> Self {
+        use self::Linklayer::*;
+        match value {
+            TC_LINKLAYER_UNAWARE => Unaware,
+            TC_LINKLAYER_ETHERNET => Ethernet,
+            TC_LINKLAYER_ATM => Atm,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<Linklayer> for u8 {
+    fn from(value: Linklayer) -> Self {
+        use self::Linklayer::*;
+        match value {
+            Unaware => TC_LINKLAYER_UNAWARE,
+            Ethernet => TC_LINKLAYER_ETHERNET,
+            Atm => TC_LINKLAYER_ATM,
+            Other(other) => other,
+        }
+    }
+}
+
+/// Compute a rate table (`TCA_*_RTAB` attributes) the same way as iproute2's
+/// `tc_calc_rtable()`. Entry `i` is the time it takes to transmit a packet of
+/// `(i + 1) << cell_log` bytes at `rate` bytes per second, in scheduler ticks.
+/// Packets smaller than `mpu` bytes are accounted as `mpu` bytes.
+///
+/// A `cell_log` of 0 is derived from `mtu` instead, so that the table covers
+/// packets of up to `mtu` bytes, and an `mtu` of 0 defaults to 2047 bytes.
+/// The per-packet overhead of the rate specification is not part of the
+/// table: the kernel adds it to the packet size before looking the table up.
+pub fn compute_rate_table(
+    rate: u64,
+    mtu: u32,
+    cell_log: u8,
+    linklayer: Linklayer,
+    mpu: u16,
+) -> [u32; TC_RTAB_SIZE] {
+    let cell_log = if cell_log == 0 {
+        default_cell_log(mtu)
+    } else {
+        cell_log
+    };
+    let mut table = [0; TC_RTAB_SIZE];
+    for (i, entry) in table.iter_mut().enumerate() {
+        let size = adjust_size(((i as u32) + 1) << cell_log, linklayer, mpu);
+        *entry = xmit_time(rate, size);
+    }
+    table
+}
+
+/// Smallest cell log for which a rate table covers packets of `mtu` bytes
+pub(crate) fn default_cell_log(mtu: u32) -> u8 {
+    let mtu = if mtu == 0 { 2047 } else { mtu };
+    let mut cell_log = 0;
+    while (mtu >> cell_log) > 255 {
+        cell_log += 1;
+    }
+    cell_log
+}
+
+fn adjust_size(size: u32, linklayer: Linklayer, mpu: u16) -> u32 {
+    let size = size.max(u32::from(mpu));
+    if linklayer == Linklayer::Atm {
+        // ATM cells carry 48 bytes of payload in 53 bytes
+        (f64::from(size) / 48.0).ceil() as u32 * 53
+    } else {
+        size
+    }
+}
+
+fn xmit_time(rate: u64, size: u32) -> u32 {
+    if rate == 0 {
+        return 0;
+    }
+    // iproute2 truncates the time to whole microseconds before converting it
+    // to ticks
+    let usecs = (1_000_000f64 * (f64::from(size) / rate as f64)) as u32;
+    (f64::from(usecs) * TICKS_PER_USEC) as u32
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: MIT
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    nlas::tc::{Nla, Stats},
+    TcMessage,
+};
+
+/// Rates computed from two [`Stats`] snapshots of the same qdisc or class
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct StatsRate {
+    pub bytes_per_sec: f64,
+    pub packets_per_sec: f64,
+    pub drops_per_sec: f64,
+    /// Backlog in bytes at the time of the last snapshot
+    pub backlog: u32,
+}
+
+/// Compute the rates between two snapshots taken `elapsed` apart. The 32 bits counters wrap
+/// around quickly on fast interfaces (in less than an hour at 1 Mpps), so a counter smaller than
+/// in the previous snapshot is assumed to have wrapped around once. If `elapsed` is zero, all the
+/// rates are zero.
+pub fn compute_rate(prev: &Stats, curr: &Stats, elapsed: Duration) -> StatsRate {
+    let secs = elapsed.as_secs_f64();
+    let rate = |delta: u64| {
+        if secs > 0.0 {
+            delta as f64 / secs
+        } else {
+            0.0
+        }
+    };
+    StatsRate {
+        bytes_per_sec: rate(curr.bytes.wrapping_sub(prev.bytes)),
+        packets_per_sec: rate(curr.packets.wrapping_sub(prev.packets).into()),
+        drops_per_sec: rate(curr.drops.wrapping_sub(prev.drops).into()),
+        backlog: curr.backlog,
+    }
+}
+
+/// Compute the rates of a qdisc or class from the messages returned by successive dumps. Only the
+/// `TCA_STATS` attribute is used: messages without it are ignored.
+///
+/// ```rust
+/// use std::time::{Duration, Instant};
+///
+/// use netlink_packet_route::{
+///     nlas::tc::{Nla, Qdisc, Stats},
+///     tc::TcStatsPoller,
+///     TcMessage,
+/// };
+///
+/// let snapshot = |bytes, packets| TcMessage::<Qdisc> {
+///     nlas: vec![Nla::Stats(Stats {
+///         bytes,
+///         packets,
+///         drops: 0,
+///         overlimits: 0,
+///         bps: 0,
+///         pps: 0,
+///         qlen: 0,
+///         backlog: 0,
+///     })],
+///     ..Default::default()
+/// };
+///
+/// let start = Instant::now();
+/// let mut poller = TcStatsPoller::new();
+/// assert_eq!(poller.poll(&snapshot(0, 0), start), None);
+/// let rate = poller
+///     .poll(&snapshot(3000, 2), start + Duration::from_secs(2))
+///     .unwrap();
+/// assert_eq!(rate.bytes_per_sec, 1500.0);
+/// assert_eq!(rate.packets_per_sec, 1.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TcStatsPoller {
+    prev: Option<(Stats, Instant)>,
+}
+
+impl TcStatsPoller {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the statistics of `msg`, received at `now`, and return the rates since the previous
+    /// snapshot if there is one
+    pub fn poll<A>(&mut self, msg: &TcMessage<A>, now: Instant) -> Option<StatsRate> {
+        let curr = msg.nlas.iter().find_map(|nla| match nla {
+            Nla::Stats(stats) => Some(*stats),
+            _ => None,
+        })?;
+        let rate = self
+            .prev
+            .map(|(prev, then)| compute_rate(&prev, &curr, now.saturating_duration_since(then)));
+        self.prev = Some((curr, now));
+        rate
+    }
+}
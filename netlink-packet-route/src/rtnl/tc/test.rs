@@ -0,0 +1,3967 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    net::Ipv4Addr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    constants::*,
+    nlas::tc::{
+        action::{
+            csum::{CsumActionNla, CsumUpdateFlags, TcCsum},
+            ct::{CtActionFlags, CtActionNla, TcCt},
+            gact::{GactActionNla, TcGact, TcGactP},
+            mirred::{MirredActionNla, TcMirred},
+            mpls::{MplsActionNla, TcMpls},
+            pedit::{PeditActionNla, TcPedit, TcPeditKey, TcPeditKeyEx},
+            police::{PoliceActionNla, TcPolice},
+            sample::{SampleActionNla, TcSample},
+            skbedit::{SkbeditActionNla, TcSkbedit},
+            tunnel_key::{TcTunnelKey, TunnelKeyActionNla},
+            vlan::{TcVlan, VlanActionNla},
+            CsumAction,
+            CtAction,
+            GactAction,
+            MirredAction,
+            MplsAction,
+            PeditAction,
+            PoliceAction,
+            SampleAction,
+            SkbeditAction,
+            TcActionNla,
+            TcActionOptions,
+            Tcf,
+            TunnelKeyAction,
+            VlanAction,
+        },
+        class::{
+            drr::{DrrClassNla, DrrXStats},
+            ets::EtsClassNla,
+            hfsc::{HfscClassNla, ServiceCurve},
+            htb::{HtbClassNla, HtbXStats, TcaHtbOpt},
+            qfq::QfqClassNla,
+            CbqClass,
+            DrrClass,
+            EtsClass,
+            HfscClass,
+            HtbClass,
+            QfqClass,
+        },
+        filter::{
+            basic::{BasicFilterNla, TcBasicPcnt},
+            bpf::{BpfFilterFlags, BpfFilterNla, BpfInsn},
+            flow::{FlowFilterNla, FlowKey, FlowMode},
+            flower::{
+                FlowerCtState,
+                FlowerFilterNla,
+                FlowerKeyFlags,
+                FlowerMplsLse,
+                FlowerMplsLseNla,
+            },
+            fw::FwFilterNla,
+            matchall::MatchallFilterNla,
+            u32::{TcU32Key, TcU32Mark, TcU32Pcnt, TcU32Sel, U32FilterNla},
+            BasicFilter,
+            BpfFilter,
+            FlowFilter,
+            FlowerFilter,
+            FwFilter,
+            MatchallFilter,
+            U32Filter,
+        },
+        qdisc::{
+            cake::{
+                CakeDiffservMode,
+                CakeFlowMode,
+                CakeQdiscNla,
+                CakeStatsNla,
+                CakeTinStats,
+                CakeTinStatsNla,
+                CakeXStats,
+            },
+            cbq::{CbqNla, CbqXStats, TcCbqFopt, TcCbqLss, TcCbqOvl, TcCbqPolice, TcCbqWrr},
+            codel::{CodelQdiscNla, CodelXStats},
+            ets::EtsQdiscNla,
+            fifo::FifoOpt,
+            fq_codel::{FqCodelQdiscNla, FqCodelXStats},
+            gred::{GredQdiscNla, GredVqNla, TcGredDps, TcGredQopt, TcGredVqEntry},
+            hfsc::HfscQopt,
+            htb::{HtbQdiscNla, TcaHtbGlob},
+            netem::{
+                NetemLossModel,
+                NetemQdiscNla,
+                TcNetemCorr,
+                TcNetemCorrupt,
+                TcNetemGeModel,
+                TcNetemQopt,
+                TcNetemRate,
+                TcNetemReorder,
+                NETEM_DIST_MAX,
+            },
+            pie::{PieQdiscNla, PieXStats},
+            prio::TcPrioMap,
+            red::{RedFlags, RedQdiscNla, RedXStats, TcRedQopt, RED_STAB_SIZE},
+            sfq::{SfqXStats, TcSfqQopt, TcSfqQoptV1},
+            taprio::{TaprioQdiscNla, TaprioSchedEntry, TcMqprioQopt},
+            tbf::{TbfQdiscNla, TcTbfQopt},
+            BfifoQdisc,
+            CakeQdisc,
+            CbqQdisc,
+            Clsact,
+            CodelQdisc,
+            DrrQdisc,
+            EtsQdisc,
+            FqCodelQdisc,
+            GredQdisc,
+            HfscQdisc,
+            HtbQdisc,
+            Ingress,
+            NetemQdisc,
+            PfifoQdisc,
+            PieQdisc,
+            PrioQdisc,
+            QfqQdisc,
+            RedQdisc,
+            SfqQdisc,
+            TaprioQdisc,
+            TbfQdisc,
+        },
+        Class,
+        Filter,
+        Nla,
+        Qdisc,
+        Stats,
+        Stats2,
+        TcAction,
+        TcRateEst,
+        TcRateEst64,
+        TcRateSpec,
+        XStats,
+    },
+    tc::{compute_rate, compute_rate_table, Linklayer, StatsRate, TcStatsPoller},
+    traits::{Emitable, Parseable, ParseableParametrized},
+    DecodeError,
+    NetlinkBuffer,
+    NetlinkMessage,
+    NetlinkPayload,
+    RtnlMessage,
+    RtnlMessageBuffer,
+    TcHandle,
+    TcHeader,
+    TcMessage,
+    TcMessageBuffer,
+    TcMessageBuilder,
+};
+
+// `tc qdisc add dev lo ingress`, as dumped by the kernel
+#[rustfmt::skip]
+static QDISC_INGRESS_PACKET: [u8; 92] = [
+    0x5c, 0x00, 0x00, 0x00, // length = 92
+    0x24, 0x00, // message type = 36 = RTM_NEWQDISC
+    0x02, 0x00, // flags = NLM_F_MULTI
+    0x01, 0x00, 0x00, 0x00, // seq number
+    0x00, 0x00, 0x00, 0x00, // pid
+
+    // tcmsg
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x01, 0x00, 0x00, 0x00, // interface index = 1
+    0x00, 0x00, 0xff, 0xff, // handle = ffff:0
+    0xf1, 0xff, 0xff, 0xff, // parent = TC_H_INGRESS
+    0x01, 0x00, 0x00, 0x00, // info (refcnt) = 1
+
+    // TCA_KIND
+    0x0c, 0x00, // length = 12
+    0x01, 0x00, // type = 1
+    0x69, 0x6e, 0x67, 0x72, 0x65, 0x73, 0x73, 0x00, // "ingress\0"
+
+    // TCA_HW_OFFLOAD
+    0x05, 0x00, // length = 5
+    0x0c, 0x00, // type = 12
+    0x00, // value = 0
+    0x00, 0x00, 0x00, // padding
+
+    // TCA_OPTIONS
+    0x04, 0x00, // length = 4
+    0x02, 0x00, // type = 2
+
+    // TCA_STATS2
+    0x20, 0x00, // length = 32
+    0x07, 0x00, // type = 7
+        // TCA_STATS_BASIC
+        0x10, 0x00, // length = 16
+        0x01, 0x00, // type = 1
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // bytes
+        0x00, 0x00, 0x00, 0x00, // packets
+        // TCA_STATS_QUEUE
+        0x0c, 0x00, // length = 12
+        0x03, 0x00, // type = 3
+        0x00, 0x00, 0x00, 0x00, // truncated queue stats
+        0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn tc_qdisc_ingress_parse() {
+    let nl_buffer = NetlinkBuffer::new(&QDISC_INGRESS_PACKET[..]).payload();
+    let actual =
+        RtnlMessage::parse_with_param(&RtnlMessageBuffer::new(&nl_buffer), RTM_NEWQDISC).unwrap();
+
+    let expected = RtnlMessage::NewQueueDiscipline(TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 1,
+            handle: TcHandle::from_parts(0xffff, 0),
+            parent: TcHandle::ingress(),
+            info: 1,
+        },
+        nlas: vec![
+            Nla::Kind("ingress".to_string()),
+            Nla::HwOffload(0),
+            Nla::Options(Qdisc::Ingress(Ingress)),
+            Nla::Stats2(vec![
+                Stats2::StatsBasic(vec![0; 12]),
+                Stats2::StatsQueue(vec![0; 8]),
+            ]),
+        ],
+    });
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn tc_qdisc_ingress_emit() {
+    let nl_buffer = NetlinkBuffer::new(&QDISC_INGRESS_PACKET[..]).payload();
+    let msg: TcMessage<Qdisc> = TcMessage::parse(&TcMessageBuffer::new(&nl_buffer)).unwrap();
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert_eq!(&buf[..], nl_buffer);
+}
+
+#[test]
+fn tc_message_skip_pad() {
+    #[rustfmt::skip]
+    let packet = [
+        // tcmsg
+        0x00, 0x00, 0x00, 0x00, // family and padding
+        0x01, 0x00, 0x00, 0x00, // interface index = 1
+        0x00, 0x00, 0xff, 0xff, // handle = ffff:0
+        0xf1, 0xff, 0xff, 0xff, // parent = TC_H_INGRESS
+        0x01, 0x00, 0x00, 0x00, // info (refcnt) = 1
+        // TCA_KIND
+        0x0c, 0x00, 0x01, 0x00,
+        0x69, 0x6e, 0x67, 0x72, 0x65, 0x73, 0x73, 0x00, // "ingress\0"
+        // TCA_PAD
+        0x04, 0x00, 0x09, 0x00,
+        // TCA_OPTIONS
+        0x04, 0x00, 0x02, 0x00,
+    ];
+    let msg: TcMessage<Qdisc> = TcMessage::parse(&TcMessageBuffer::new(&&packet[..])).unwrap();
+    assert_eq!(
+        msg.nlas,
+        vec![
+            Nla::Kind("ingress".to_string()),
+            Nla::Options(Qdisc::Ingress(Ingress)),
+        ]
+    );
+}
+
+#[test]
+fn tc_filter_chain_round_trip() {
+    // tc chain add dev lo ingress chain 42
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 1,
+            handle: TcHandle::default(),
+            parent: TcHandle::from_parts(0xffff, 0),
+            info: 0,
+        },
+        nlas: vec![Nla::Chain(42)],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert_eq!(
+        &buf[20..],
+        &[0x08, 0x00, 0x0b, 0x00, 0x2a, 0x00, 0x00, 0x00]
+    );
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+    let mut buf2 = vec![0; parsed.buffer_len()];
+    parsed.emit(&mut buf2);
+    assert_eq!(buf, buf2);
+}
+
+#[test]
+fn tc_unknown_kind() {
+    assert_eq!(Qdisc::new("someunknownqdisc"), Qdisc::Other(vec![]));
+    assert_eq!(Class::new("someunknownclass"), Class::Other(vec![]));
+    assert_eq!(Filter::new("someunknownfilter"), Filter::Other(vec![]));
+
+    let msg: TcMessage<Qdisc> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("someunknownqdisc".to_string()),
+            Nla::Options(Qdisc::Other(vec![0x08, 0x00, 0x01, 0x00, 1, 2, 3, 4])),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_message_builder() {
+    // `tc qdisc add dev lo ingress`
+    let msg = TcMessageBuilder::new()
+        .index(1)
+        .handle(TcHandle::from_parts(0xffff, 0))
+        .parent(TcHandle::ingress())
+        .hw_offload(0)
+        .options(Qdisc::Ingress(Ingress))
+        .kind("ingress")
+        .build()
+        .unwrap();
+    let expected = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 1,
+            handle: TcHandle::from_parts(0xffff, 0),
+            parent: TcHandle::ingress(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("ingress".to_string()),
+            Nla::Options(Qdisc::Ingress(Ingress)),
+            Nla::HwOffload(0),
+        ],
+    };
+    assert_eq!(msg, expected);
+
+    let msg: TcMessage<Qdisc> = TcMessageBuilder::new().index(1).build().unwrap();
+    assert!(msg.nlas.is_empty());
+
+    assert!(TcMessageBuilder::<Qdisc>::new()
+        .kind("ingress")
+        .build()
+        .is_err());
+    assert!(TcMessageBuilder::new()
+        .options(Qdisc::Ingress(Ingress))
+        .build()
+        .is_err());
+}
+
+// `tc qdisc add dev lo clsact`, as dumped by the kernel
+#[rustfmt::skip]
+static QDISC_CLSACT_PACKET: [u8; 92] = [
+    0x5c, 0x00, 0x00, 0x00, // length = 92
+    0x24, 0x00, // message type = 36 = RTM_NEWQDISC
+    0x02, 0x00, // flags = NLM_F_MULTI
+    0x01, 0x00, 0x00, 0x00, // seq number
+    0x00, 0x00, 0x00, 0x00, // pid
+
+    // tcmsg
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x01, 0x00, 0x00, 0x00, // interface index = 1
+    0x00, 0x00, 0xff, 0xff, // handle = ffff:0
+    0xf1, 0xff, 0xff, 0xff, // parent = TC_H_CLSACT
+    0x01, 0x00, 0x00, 0x00, // info (refcnt) = 1
+
+    // TCA_KIND
+    0x0b, 0x00, // length = 11
+    0x01, 0x00, // type = 1
+    0x63, 0x6c, 0x73, 0x61, 0x63, 0x74, 0x00, // "clsact\0"
+    0x00, // padding
+
+    // TCA_HW_OFFLOAD
+    0x05, 0x00, // length = 5
+    0x0c, 0x00, // type = 12
+    0x00, // value = 0
+    0x00, 0x00, 0x00, // padding
+
+    // TCA_OPTIONS
+    0x04, 0x00, // length = 4
+    0x02, 0x00, // type = 2
+
+    // TCA_STATS2
+    0x20, 0x00, // length = 32
+    0x07, 0x00, // type = 7
+        // TCA_STATS_BASIC
+        0x10, 0x00, // length = 16
+        0x01, 0x00, // type = 1
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // bytes
+        0x00, 0x00, 0x00, 0x00, // packets
+        // TCA_STATS_QUEUE
+        0x0c, 0x00, // length = 12
+        0x03, 0x00, // type = 3
+        0x00, 0x00, 0x00, 0x00, // truncated queue stats
+        0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn tc_qdisc_clsact_parse() {
+    let nl_buffer = NetlinkBuffer::new(&QDISC_CLSACT_PACKET[..]).payload();
+    let actual =
+        RtnlMessage::parse_with_param(&RtnlMessageBuffer::new(&nl_buffer), RTM_NEWQDISC).unwrap();
+
+    let expected = RtnlMessage::NewQueueDiscipline(TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 1,
+            handle: TcHandle::from_parts(0xffff, 0),
+            parent: TcHandle::ingress(),
+            info: 1,
+        },
+        nlas: vec![
+            Nla::Kind("clsact".to_string()),
+            Nla::HwOffload(0),
+            Nla::Options(Qdisc::Clsact(Clsact)),
+            Nla::Stats2(vec![
+                Stats2::StatsBasic(vec![0; 12]),
+                Stats2::StatsQueue(vec![0; 8]),
+            ]),
+        ],
+    });
+    assert_eq!(actual, expected);
+    assert_eq!(Qdisc::new("clsact"), Qdisc::Clsact(Clsact));
+}
+
+#[test]
+fn tc_qdisc_clsact_emit() {
+    let nl_buffer = NetlinkBuffer::new(&QDISC_CLSACT_PACKET[..]).payload();
+    let msg: TcMessage<Qdisc> = TcMessage::parse(&TcMessageBuffer::new(&nl_buffer)).unwrap();
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert_eq!(&buf[..], nl_buffer);
+}
+
+// `tc qdisc add dev lo root handle 1: htb default 10`, as dumped by the kernel
+#[rustfmt::skip]
+static QDISC_HTB_PACKET: [u8; 72] = [
+    // tcmsg
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x01, 0x00, 0x00, 0x00, // interface index = 1
+    0x00, 0x00, 0x01, 0x00, // handle = 1:0
+    0xff, 0xff, 0xff, 0xff, // parent = TC_H_ROOT
+    0x02, 0x00, 0x00, 0x00, // info (refcnt) = 2
+
+    // TCA_KIND
+    0x08, 0x00, // length = 8
+    0x01, 0x00, // type = 1
+    0x68, 0x74, 0x62, 0x00, // "htb\0"
+
+    // TCA_OPTIONS
+    0x24, 0x00, // length = 36
+    0x02, 0x00, // type = 2
+        // TCA_HTB_INIT
+        0x18, 0x00, // length = 24
+        0x02, 0x00, // type = 2
+        0x03, 0x00, 0x00, 0x00, // version = 3
+        0x0a, 0x00, 0x00, 0x00, // rate2quantum = 10
+        0x10, 0x00, 0x00, 0x00, // defcls = 0x10
+        0x00, 0x00, 0x00, 0x00, // debug = 0
+        0x00, 0x00, 0x00, 0x00, // direct_pkts = 0
+        // TCA_HTB_DIRECT_QLEN
+        0x08, 0x00, // length = 8
+        0x05, 0x00, // type = 5
+        0xe8, 0x03, 0x00, 0x00, // 1000
+
+    // TCA_HW_OFFLOAD
+    0x05, 0x00, // length = 5
+    0x0c, 0x00, // type = 12
+    0x00, // value = 0
+    0x00, 0x00, 0x00, // padding
+];
+
+fn qdisc_htb_message() -> TcMessage<Qdisc> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 1,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 2,
+        },
+        nlas: vec![
+            Nla::Kind("htb".to_string()),
+            Nla::Options(Qdisc::Htb(HtbQdisc {
+                nlas: vec![
+                    HtbQdiscNla::Init(TcaHtbGlob {
+                        version: 3,
+                        rate2quantum: 10,
+                        defcls: 0x10,
+                        debug: 0,
+                        direct_pkts: 0,
+                    }),
+                    HtbQdiscNla::DirectQlen(1000),
+                ],
+            })),
+            Nla::HwOffload(0),
+        ],
+    }
+}
+
+#[test]
+fn tc_qdisc_htb_parse() {
+    let actual = TcMessage::parse(&TcMessageBuffer::new(&&QDISC_HTB_PACKET[..])).unwrap();
+    assert_eq!(actual, qdisc_htb_message());
+}
+
+#[test]
+fn tc_qdisc_htb_emit() {
+    let msg = qdisc_htb_message();
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert_eq!(&buf[..], &QDISC_HTB_PACKET[..]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn tc_qdisc_htb_serde() {
+    let msg = qdisc_htb_message();
+    let json = serde_json::to_value(&msg).unwrap();
+    assert_eq!(json["header"]["handle"], "1:");
+    assert_eq!(json["header"]["parent"], "root");
+    let actual: TcMessage<Qdisc> = serde_json::from_value(json).unwrap();
+    assert_eq!(actual, msg);
+}
+
+#[test]
+fn tc_stats2_rate_est_round_trip() {
+    let mut msg = qdisc_htb_message();
+    msg.nlas.push(Nla::Stats2(vec![
+        Stats2::RateEst(TcRateEst {
+            bps: 125_000,
+            pps: 100,
+        }),
+        Stats2::RateEst64(TcRateEst64 {
+            bps: 1_250_000_000,
+            pps: 1_000_000,
+        }),
+    ]));
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    // TCA_STATS2 header, followed by the two nested rate estimator nlas
+    let stats = &buf[QDISC_HTB_PACKET.len()..];
+    assert_eq!(stats.len(), 4 + 4 + 8 + 4 + 16);
+    assert_eq!(&stats[4..8], &[12, 0, TCA_STATS_RATE_EST as u8, 0]);
+    assert_eq!(&stats[16..20], &[20, 0, TCA_STATS_RATE_EST64 as u8, 0]);
+
+    let actual: TcMessage<Qdisc> = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(actual, msg);
+}
+
+#[test]
+fn tc_qdisc_tbf_round_trip() {
+    // tc qdisc add dev eth0 root tbf rate 10mbit burst 10kb limit 100kb
+    let mut rate = TcRateSpec {
+        linklayer: TC_LINKLAYER_ETHERNET,
+        rate: 1_250_000,
+        ..Default::default()
+    };
+    let rtab = rate.rate_table(1514);
+    assert_eq!(rtab.len(), 256);
+    assert_eq!(rate.cell_log, 3);
+    assert_eq!(rate.cell_align, -1);
+    // 8 bytes at 10mbit/s take 6.4us, truncated to 6us like iproute2 does,
+    // that is 93 ticks
+    assert_eq!(rtab[0], 93);
+
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(0x8001, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("tbf".to_string()),
+            Nla::Options(Qdisc::Tbf(TbfQdisc {
+                nlas: vec![
+                    TbfQdiscNla::Parms(TcTbfQopt {
+                        rate,
+                        peakrate: TcRateSpec::default(),
+                        limit: 102_400,
+                        buffer: 128_000,
+                        mtu: 0,
+                    }),
+                    TbfQdiscNla::Burst(10_240),
+                    TbfQdiscNla::Rtab(rtab),
+                ],
+            })),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+// `tc qdisc add dev eth0 root fq_codel memory_limit 32Mb`, as dumped by the
+// kernel
+#[rustfmt::skip]
+static QDISC_FQ_CODEL_PACKET: [u8; 152] = [
+    // tcmsg
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x02, 0x00, 0x00, 0x00, // interface index = 2
+    0x00, 0x00, 0x00, 0x00, // handle = 0:0
+    0xff, 0xff, 0xff, 0xff, // parent = TC_H_ROOT
+    0x02, 0x00, 0x00, 0x00, // info (refcnt) = 2
+
+    // TCA_KIND
+    0x0d, 0x00, // length = 13
+    0x01, 0x00, // type = 1
+    0x66, 0x71, 0x5f, 0x63, 0x6f, 0x64, 0x65, 0x6c, 0x00, // "fq_codel\0"
+    0x00, 0x00, 0x00, // padding
+
+    // TCA_OPTIONS
+    0x44, 0x00, // length = 68
+    0x02, 0x00, // type = 2
+        // TCA_FQ_CODEL_TARGET
+        0x08, 0x00, 0x01, 0x00,
+        0x87, 0x13, 0x00, 0x00, // 4999
+        // TCA_FQ_CODEL_LIMIT
+        0x08, 0x00, 0x02, 0x00,
+        0x00, 0x28, 0x00, 0x00, // 10240
+        // TCA_FQ_CODEL_INTERVAL
+        0x08, 0x00, 0x03, 0x00,
+        0x9f, 0x86, 0x01, 0x00, // 99999
+        // TCA_FQ_CODEL_ECN
+        0x08, 0x00, 0x04, 0x00,
+        0x01, 0x00, 0x00, 0x00, // 1
+        // TCA_FQ_CODEL_FLOWS
+        0x08, 0x00, 0x05, 0x00,
+        0x00, 0x04, 0x00, 0x00, // 1024
+        // TCA_FQ_CODEL_QUANTUM
+        0x08, 0x00, 0x06, 0x00,
+        0xea, 0x05, 0x00, 0x00, // 1514
+        // TCA_FQ_CODEL_DROP_BATCH_SIZE
+        0x08, 0x00, 0x08, 0x00,
+        0x40, 0x00, 0x00, 0x00, // 64
+        // TCA_FQ_CODEL_MEMORY_LIMIT
+        0x08, 0x00, 0x09, 0x00,
+        0x00, 0x00, 0x00, 0x02, // 32Mb
+
+    // TCA_STATS2
+    0x30, 0x00, // length = 48
+    0x07, 0x00, // type = 7
+        // TCA_STATS_APP
+        0x2c, 0x00, // length = 44
+        0x04, 0x00, // type = 4
+        0x00, 0x00, 0x00, 0x00, // type = TCA_FQ_CODEL_XSTATS_QDISC
+        0xea, 0x05, 0x00, 0x00, // maxpacket = 1514
+        0x00, 0x00, 0x00, 0x00, // drop_overlimit
+        0x03, 0x00, 0x00, 0x00, // ecn_mark = 3
+        0x2a, 0x00, 0x00, 0x00, // new_flow_count = 42
+        0x00, 0x00, 0x00, 0x00, // new_flows_len
+        0x01, 0x00, 0x00, 0x00, // old_flows_len = 1
+        0x00, 0x00, 0x00, 0x00, // ce_mark
+        0x00, 0x06, 0x00, 0x00, // memory_usage = 1536
+        0x00, 0x00, 0x00, 0x00, // drop_overmemory
+];
+
+fn qdisc_fq_codel_message() -> TcMessage<Qdisc> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::default(),
+            parent: TcHandle::root(),
+            info: 2,
+        },
+        nlas: vec![
+            Nla::Kind("fq_codel".to_string()),
+            Nla::Options(Qdisc::FqCodel(FqCodelQdisc {
+                nlas: vec![
+                    FqCodelQdiscNla::Target(4999),
+                    FqCodelQdiscNla::Limit(10240),
+                    FqCodelQdiscNla::Interval(99999),
+                    FqCodelQdiscNla::Ecn(1),
+                    FqCodelQdiscNla::Flows(1024),
+                    FqCodelQdiscNla::Quantum(1514),
+                    FqCodelQdiscNla::DropBatchSize(64),
+                    FqCodelQdiscNla::MemoryLimit(32 * 1024 * 1024),
+                ],
+            })),
+            Nla::Stats2(vec![Stats2::StatsApp(XStats::FqCodel(FqCodelXStats {
+                maxpacket: 1514,
+                drop_overlimit: 0,
+                ecn_mark: 3,
+                new_flow_count: 42,
+                new_flows_len: 0,
+                old_flows_len: 1,
+                ce_mark: 0,
+                memory_usage: 1536,
+                drop_overmemory: 0,
+            }))]),
+        ],
+    }
+}
+
+#[test]
+fn tc_qdisc_fq_codel_parse() {
+    let actual = TcMessage::parse(&TcMessageBuffer::new(&&QDISC_FQ_CODEL_PACKET[..])).unwrap();
+    assert_eq!(actual, qdisc_fq_codel_message());
+}
+
+#[test]
+fn tc_qdisc_fq_codel_emit() {
+    let msg = qdisc_fq_codel_message();
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert_eq!(&buf[..], &QDISC_FQ_CODEL_PACKET[..]);
+}
+
+fn qdisc_netem_message(delay_dist: Vec<i16>) -> TcMessage<Qdisc> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(0x8002, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("netem".to_string()),
+            Nla::Options(Qdisc::Netem(NetemQdisc {
+                qopt: TcNetemQopt {
+                    latency: 1_562_500,
+                    limit: 1000,
+                    loss: 0x0147_ae14,
+                    gap: 5,
+                    duplicate: 0,
+                    jitter: 156_250,
+                },
+                nlas: vec![
+                    NetemQdiscNla::Corr(TcNetemCorr {
+                        delay_corr: 0x3fff_ffff,
+                        loss_corr: 0,
+                        dup_corr: 0,
+                    }),
+                    // an odd number of entries needs padding
+                    NetemQdiscNla::DelayDist(delay_dist),
+                    NetemQdiscNla::Reorder(TcNetemReorder {
+                        probability: 0x1999_9999,
+                        correlation: 0x7fff_ffff,
+                    }),
+                    NetemQdiscNla::Corrupt(TcNetemCorrupt {
+                        probability: 0x028f_5c28,
+                        correlation: 0,
+                    }),
+                    NetemQdiscNla::Loss(vec![NetemLossModel::GilbertElliott(TcNetemGeModel {
+                        p: 0x0147_ae14,
+                        r: 0xffff_ffff,
+                        h: 0,
+                        k1: 0,
+                    })]),
+                    NetemQdiscNla::Rate(TcNetemRate {
+                        rate: 125_000,
+                        packet_overhead: -4,
+                        cell_size: 0,
+                        cell_overhead: 0,
+                    }),
+                ],
+            })),
+        ],
+    }
+}
+
+#[test]
+fn tc_qdisc_netem_round_trip() {
+    let msg = qdisc_netem_message(vec![-32768, -1, 0, 1, 32767]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    // the options start with struct tc_netem_qopt, followed by the attributes
+    assert_eq!(&buf[32..36], &[0x80, 0x00, 0x02, 0x00]);
+    assert_eq!(&buf[36..40], &1_562_500u32.to_ne_bytes());
+
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_netem_delay_dist_too_large() {
+    let msg = qdisc_netem_message(vec![0; NETEM_DIST_MAX + 1]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert!(TcMessage::<Qdisc>::parse(&TcMessageBuffer::new(&buf)).is_err());
+}
+
+#[test]
+fn tc_qdisc_sfq_round_trip() {
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 2,
+        },
+        nlas: vec![
+            Nla::Kind("sfq".to_string()),
+            Nla::Options(Qdisc::Sfq(SfqQdisc::V1(TcSfqQoptV1 {
+                v0: TcSfqQopt {
+                    quantum: 1514,
+                    perturb_period: 10,
+                    limit: 127,
+                    divisor: 1024,
+                    flows: 128,
+                },
+                depth: 127,
+                ..Default::default()
+            }))),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // Older kernels and `tc` only know about the original structure
+    let mut msg = msg;
+    msg.nlas[1] = Nla::Options(Qdisc::Sfq(SfqQdisc::V0(TcSfqQopt {
+        quantum: 1514,
+        perturb_period: 0,
+        limit: 127,
+        divisor: 1024,
+        flows: 128,
+    })));
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_class_sfq_xstats_round_trip() {
+    let msg: TcMessage<Class> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 2),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("sfq".to_string()),
+            Nla::Stats2(vec![Stats2::StatsApp(XStats::Sfq(SfqXStats {
+                allot: -1486,
+            }))]),
+            Nla::XStats(XStats::Sfq(SfqXStats { allot: -1486 })),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_prio_round_trip() {
+    // tc qdisc add dev eth0 root handle 1: prio bands 3
+    let mut qopt = TcPrioMap::default();
+    assert_eq!(qopt.bands, 3);
+    qopt.set_priomap(TC_PRIO_CONTROL, 0).unwrap();
+    assert!(qopt.set_priomap(TC_PRIO_MAX + 1, 0).is_err());
+    assert!(qopt.set_priomap(TC_PRIO_BESTEFFORT, 3).is_err());
+
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("prio".to_string()),
+            Nla::Options(Qdisc::Prio(PrioQdisc { qopt })),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x18, 0x00, 0x02, 0x00, // TCA_OPTIONS, length = 24
+        0x03, 0x00, 0x00, 0x00, // bands = 3
+        0x01, 0x02, 0x02, 0x02, 0x01, 0x02, 0x00, 0x00, // priomap
+        0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+    ];
+    assert_eq!(&buf[32..], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // a band greater than the number of bands is rejected
+    buf[32 + 4] = 2;
+    assert!(TcMessage::<Qdisc>::parse(&TcMessageBuffer::new(&buf)).is_err());
+}
+
+#[test]
+fn tc_qdisc_red_round_trip() {
+    // tc qdisc add dev eth0 root red limit 400000 min 30000 max 90000 avpkt 1000 \
+    //     burst 55 ecn probability 0.02 bandwidth 10mbit
+    let qopt = TcRedQopt {
+        limit: 400_000,
+        qth_min: 30_000,
+        qth_max: 90_000,
+        wlog: 9,
+        plog: 21,
+        scell_log: 13,
+        flags: RedFlags::TC_RED_ECN,
+    };
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(0x8003, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("red".to_string()),
+            Nla::Options(Qdisc::Red(RedQdisc {
+                nlas: vec![
+                    RedQdiscNla::Parms(qopt),
+                    RedQdiscNla::Stab(vec![7; RED_STAB_SIZE]),
+                    RedQdiscNla::MaxP(0x051e_b851),
+                ],
+            })),
+            Nla::Stats2(vec![Stats2::StatsApp(XStats::Red(RedXStats {
+                early: 1,
+                pdrop: 2,
+                other: 3,
+                marked: 4,
+            }))]),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    // TCA_OPTIONS then TCA_RED_PARMS: the flags are the last byte of the
+    // parameters
+    assert_eq!(buf[28 + 4 + 4 + 15], TC_RED_ECN);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // without ECN, marked packets are dropped
+    let mut msg = msg;
+    msg.nlas[1] = Nla::Options(Qdisc::Red(RedQdisc {
+        nlas: vec![RedQdiscNla::Parms(TcRedQopt {
+            flags: RedFlags::TC_RED_HARDDROP,
+            ..qopt
+        })],
+    }));
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_cake_round_trip() {
+    // tc qdisc add dev eth0 root cake bandwidth 100mbit diffserv4 dual-dsthost nat \
+    //     overhead -4 mpu 64 rtt 50ms
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(0x8004, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("cake".to_string()),
+            Nla::Options(Qdisc::Cake(CakeQdisc {
+                nlas: vec![
+                    CakeQdiscNla::BaseRate64(12_500_000),
+                    CakeQdiscNla::DiffservMode(CakeDiffservMode::Diffserv4),
+                    CakeQdiscNla::FlowMode(CakeFlowMode::DualDstHost),
+                    CakeQdiscNla::Rtt(50_000),
+                    CakeQdiscNla::Target(2_500),
+                    CakeQdiscNla::AutorateIngress(false),
+                    CakeQdiscNla::Nat(true),
+                    CakeQdiscNla::Overhead(-4),
+                    CakeQdiscNla::Mpu(64),
+                    CakeQdiscNla::Raw,
+                ],
+            })),
+            Nla::Stats2(vec![Stats2::StatsApp(XStats::Cake(CakeXStats {
+                nlas: vec![
+                    CakeStatsNla::CapacityEstimate64(12_500_000),
+                    CakeStatsNla::MemoryLimit(4_194_304),
+                    CakeStatsNla::TinStats(vec![
+                        CakeTinStats {
+                            index: 1,
+                            nlas: vec![
+                                CakeTinStatsNla::SentPackets(10),
+                                CakeTinStatsNla::SentBytes64(15_140),
+                                CakeTinStatsNla::PeakDelayUs(12),
+                            ],
+                        },
+                        CakeTinStats {
+                            index: 2,
+                            nlas: vec![CakeTinStatsNla::SentPackets(0)],
+                        },
+                    ]),
+                ],
+            }))]),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+fn qdisc_codel_message(nlas: Vec<CodelQdiscNla>) -> TcMessage<Qdisc> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(0x8005, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("codel".to_string()),
+            Nla::Options(Qdisc::Codel(CodelQdisc { nlas })),
+            Nla::Stats2(vec![Stats2::StatsApp(XStats::Codel(CodelXStats {
+                maxpacket: 1514,
+                count: 1,
+                lastcount: 0,
+                ldelay: 7,
+                drop_next: -12,
+                drop_overlimit: 0,
+                ecn_mark: 0,
+                dropping: 0,
+                ce_mark: 0,
+            }))]),
+        ],
+    }
+}
+
+#[test]
+fn tc_qdisc_codel_round_trip() {
+    // tc qdisc add dev eth0 root codel target 5ms interval 100ms ecn
+    let msg = qdisc_codel_message(vec![
+        CodelQdiscNla::Target(5_000),
+        CodelQdiscNla::Limit(1000),
+        CodelQdiscNla::Interval(100_000),
+        CodelQdiscNla::Ecn(1),
+    ]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // extreme values
+    let msg = qdisc_codel_message(vec![
+        CodelQdiscNla::Target(0),
+        CodelQdiscNla::Limit(u32::MAX),
+        CodelQdiscNla::Interval(u32::MAX),
+        CodelQdiscNla::Ecn(0),
+        CodelQdiscNla::CeThreshold(u32::MAX),
+    ]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_hfsc_round_trip() {
+    // tc qdisc add dev eth0 root handle 1: hfsc default 10
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("hfsc".to_string()),
+            Nla::Options(Qdisc::Hfsc(HfscQdisc {
+                qopt: HfscQopt { defcls: 0x10 },
+            })),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x06, 0x00, 0x02, 0x00, // TCA_OPTIONS, length = 6
+        0x10, 0x00, // defcls = 0x10
+        0x00, 0x00, // padding
+    ];
+    assert_eq!(&buf[32..], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+fn class_hfsc_message(nlas: Vec<HfscClassNla>) -> TcMessage<Class> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0x10),
+            parent: TcHandle::from_parts(1, 1),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("hfsc".to_string()),
+            Nla::Options(Class::Hfsc(HfscClass { nlas })),
+        ],
+    }
+}
+
+#[test]
+fn tc_class_hfsc_round_trip() {
+    let rt = ServiceCurve {
+        m1: 1_250_000,
+        d: 10_000,
+        m2: 625_000,
+    };
+    let ls = ServiceCurve {
+        m1: 0,
+        d: 0,
+        m2: 625_000,
+    };
+    let ul = ServiceCurve {
+        m1: 0,
+        d: 0,
+        m2: u32::MAX,
+    };
+    assert_eq!(Class::new("hfsc"), Class::Hfsc(HfscClass::default()));
+
+    // tc class add dev eth0 parent 1:1 classid 1:10 hfsc
+    //     rt m1 10mbit d 10ms m2 5mbit
+    let msg = class_hfsc_message(vec![HfscClassNla::Rsc(rt)]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x14, 0x00, 0x02, 0x00, // TCA_OPTIONS, length = 20
+        0x10, 0x00, 0x01, 0x00, // TCA_HFSC_RSC, length = 16
+        0xd0, 0x12, 0x13, 0x00, // m1 = 1250000
+        0x10, 0x27, 0x00, 0x00, // d = 10000
+        0x68, 0x89, 0x09, 0x00, // m2 = 625000
+    ];
+    assert_eq!(&buf[32..], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // tc class add dev eth0 parent 1:1 classid 1:10 hfsc ls m2 5mbit
+    let msg = class_hfsc_message(vec![HfscClassNla::Fsc(ls)]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // all three curves, with the largest upper limit
+    let msg = class_hfsc_message(vec![
+        HfscClassNla::Rsc(rt),
+        HfscClassNla::Fsc(ls),
+        HfscClassNla::Usc(ul),
+    ]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_drr_round_trip() {
+    // tc qdisc add dev eth0 root handle 1: drr
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("drr".to_string()),
+            Nla::Options(Qdisc::Drr(DrrQdisc)),
+        ],
+    };
+    assert_eq!(Qdisc::new("drr"), Qdisc::Drr(DrrQdisc));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    // the TCA_OPTIONS attribute is empty
+    assert_eq!(&buf[28..], &[0x04, 0x00, 0x02, 0x00][..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_class_drr_round_trip() {
+    // tc class add dev eth0 parent 1: classid 1:1 drr quantum 1500
+    let msg: TcMessage<Class> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 1),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("drr".to_string()),
+            Nla::Options(Class::Drr(DrrClass {
+                nlas: vec![DrrClassNla::Quantum(1500)],
+            })),
+            Nla::XStats(XStats::Drr(DrrXStats { deficit: 1500 })),
+        ],
+    };
+    assert_eq!(Class::new("drr"), Class::Drr(DrrClass::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x0c, 0x00, 0x02, 0x00, // TCA_OPTIONS, length = 12
+        0x08, 0x00, 0x01, 0x00, // TCA_DRR_QUANTUM, length = 8
+        0xdc, 0x05, 0x00, 0x00, // 1500
+        0x08, 0x00, 0x04, 0x00, // TCA_XSTATS, length = 8
+        0xdc, 0x05, 0x00, 0x00, // deficit = 1500
+    ];
+    assert_eq!(&buf[28..], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_ets_round_trip() {
+    // tc qdisc add dev eth0 root handle 1: ets bands 8 strict 3 \
+    //     quanta 3000 2500 2000 1500 1000 priomap 7 6 5 4 3 2 1 0
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("ets".to_string()),
+            Nla::Options(Qdisc::Ets(EtsQdisc {
+                nlas: vec![
+                    EtsQdiscNla::Nbands(8),
+                    EtsQdiscNla::Nstrict(3),
+                    EtsQdiscNla::Quanta(vec![3000, 2500, 2000, 1500, 1000]),
+                    EtsQdiscNla::Priomap(vec![7, 6, 5, 4, 3, 2, 1, 0, 7, 7, 7, 7, 7, 7, 7, 7]),
+                ],
+            })),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x05, 0x00, 0x01, 0x00, // TCA_ETS_NBANDS, length = 5
+        0x08, 0x00, 0x00, 0x00, // 8 and padding
+        0x05, 0x00, 0x02, 0x00, // TCA_ETS_NSTRICT, length = 5
+        0x03, 0x00, 0x00, 0x00, // 3 and padding
+        0x2c, 0x00, 0x03, 0x00, // TCA_ETS_QUANTA, length = 44
+        0x08, 0x00, 0x04, 0x00, // TCA_ETS_QUANTA_BAND, length = 8
+        0xb8, 0x0b, 0x00, 0x00, // 3000
+    ];
+    // options start after the header (20 bytes), the kind (8 bytes) and the
+    // TCA_OPTIONS header (4 bytes)
+    assert_eq!(&buf[32..32 + expected_options.len()], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_class_ets_round_trip() {
+    // tc class change dev eth0 classid 1:4 ets quantum 1500
+    let msg: TcMessage<Class> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 4),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("ets".to_string()),
+            Nla::Options(Class::Ets(EtsClass {
+                nlas: vec![EtsClassNla::QuantaBand(1500)],
+            })),
+        ],
+    };
+    assert_eq!(Class::new("ets"), Class::Ets(EtsClass::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+fn qdisc_taprio_message(nlas: Vec<TaprioQdiscNla>) -> TcMessage<Qdisc> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(0x100, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("taprio".to_string()),
+            Nla::Options(Qdisc::Taprio(TaprioQdisc { nlas })),
+        ],
+    }
+}
+
+#[test]
+fn tc_qdisc_taprio_round_trip() {
+    // tc qdisc replace dev eth0 parent root handle 100 taprio num_tc 3 \
+    //     map 2 2 1 0 2 2 2 2 2 2 2 2 2 2 2 2 queues 1@0 1@1 2@2 \
+    //     base-time 1528743495910289987 \
+    //     sched-entry S 01 300000 sched-entry S 02 300000 \
+    //     sched-entry S 04 400000 clockid CLOCK_TAI
+    let mut qopt = TcMqprioQopt {
+        num_tc: 3,
+        prio_tc_map: [2, 2, 1, 0, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2],
+        ..Default::default()
+    };
+    qopt.count[..3].copy_from_slice(&[1, 1, 2]);
+    qopt.offset[..3].copy_from_slice(&[0, 1, 2]);
+    let entries = [(0x01, 300_000), (0x02, 300_000), (0x04, 400_000)]
+        .iter()
+        .enumerate()
+        .map(|(index, (gate_mask, interval))| TaprioSchedEntry {
+            index: index as u32,
+            command: TC_TAPRIO_CMD_SET_GATES,
+            gate_mask: *gate_mask,
+            interval: *interval,
+        })
+        .collect();
+    let msg = qdisc_taprio_message(vec![
+        TaprioQdiscNla::Priomap(qopt),
+        TaprioQdiscNla::SchedEntryList(entries),
+        TaprioQdiscNla::BaseTime(1_528_743_495_910_289_987),
+        TaprioQdiscNla::Clockid(11), // CLOCK_TAI
+        TaprioQdiscNla::CycleTime(1_000_000),
+        TaprioQdiscNla::CycleTimeExtension(0),
+        TaprioQdiscNla::Flags(0),
+    ]);
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_taprio_signed_base_time() {
+    let msg = qdisc_taprio_message(vec![TaprioQdiscNla::BaseTime(-1)]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x10, 0x00, 0x02, 0x00, // TCA_OPTIONS, length = 16
+        0x0c, 0x00, 0x03, 0x00, // TCA_TAPRIO_ATTR_SCHED_BASE_TIME, length = 12
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // -1
+    ];
+    assert_eq!(&buf[32..], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_pie_round_trip() {
+    // tc qdisc add dev eth0 root pie limit 1000 target 15ms tupdate 15ms \
+    //     alpha 2 beta 20 ecn dq_rate_estimator
+    let stats = PieXStats {
+        prob: 0x0000_0001_0000_0000,
+        delay: 1200,
+        avg_dq_rate: 125_000,
+        dq_rate_estimating: 1,
+        packets_in: 4242,
+        dropped: 12,
+        overlimit: 0,
+        maxq: 27,
+        ecn_mark: 3,
+    };
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(0x8001, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("pie".to_string()),
+            Nla::Options(Qdisc::Pie(PieQdisc {
+                nlas: vec![
+                    PieQdiscNla::Target(15_000),
+                    PieQdiscNla::Limit(1000),
+                    PieQdiscNla::Tupdate(15_000),
+                    PieQdiscNla::Alpha(2),
+                    PieQdiscNla::Beta(20),
+                    PieQdiscNla::Ecn(1),
+                    PieQdiscNla::Bytemode(0),
+                    PieQdiscNla::DqRateEstimator(1),
+                ],
+            })),
+            Nla::Stats2(vec![Stats2::StatsApp(XStats::Pie(stats))]),
+            Nla::XStats(XStats::Pie(stats)),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_pie_xstats_kernel_layout() {
+    // statistics of a pie qdisc, as dumped by the kernel
+    #[rustfmt::skip]
+    let payload = [
+        0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, // prob = 1 << 32
+        0xb0, 0x04, 0x00, 0x00, // delay = 1200
+        0x48, 0xe8, 0x01, 0x00, // avg_dq_rate = 125000
+        0x01, 0x00, 0x00, 0x00, // dq_rate_estimating = 1
+        0x92, 0x10, 0x00, 0x00, // packets_in = 4242
+        0x0c, 0x00, 0x00, 0x00, // dropped = 12
+        0x00, 0x00, 0x00, 0x00, // overlimit = 0
+        0x1b, 0x00, 0x00, 0x00, // maxq = 27
+        0x03, 0x00, 0x00, 0x00, // ecn_mark = 3
+    ];
+    let stats = XStats::parse_with_kind(&payload, "pie").unwrap();
+    assert_eq!(
+        stats,
+        XStats::Pie(PieXStats {
+            prob: 0x0000_0001_0000_0000,
+            delay: 1200,
+            avg_dq_rate: 125_000,
+            dq_rate_estimating: 1,
+            packets_in: 4242,
+            dropped: 12,
+            overlimit: 0,
+            maxq: 27,
+            ecn_mark: 3,
+        })
+    );
+    let mut buf = vec![0; stats.buffer_len()];
+    stats.emit(&mut buf);
+    assert_eq!(&buf[..], &payload[..]);
+
+    // statistics of an unknown size are kept as raw bytes
+    let payload = [0; 48];
+    assert_eq!(
+        XStats::parse_with_kind(&payload, "pie").unwrap(),
+        XStats::Other(payload.to_vec())
+    );
+}
+
+#[test]
+fn tc_qdisc_gred_round_trip() {
+    // tc qdisc add dev eth0 root handle 1: gred setup DPs 2 default 1
+    // tc qdisc change dev eth0 root gred limit 60KB min 15KB max 45KB \
+    //     avpkt 1000 burst 20 probability 0.02 DP 0
+    // tc qdisc change dev eth0 root gred limit 60KB min 10KB max 40KB \
+    //     avpkt 1000 burst 20 probability 0.05 DP 1 ecn
+    let qopt = |dp, qth_min, qth_max, plog| TcGredQopt {
+        limit: 61_440,
+        qth_min,
+        qth_max,
+        dp,
+        wlog: 10,
+        plog,
+        scell_log: 14,
+        prio: 8,
+        ..Default::default()
+    };
+    let vq = |dp, flags| TcGredVqEntry {
+        nlas: vec![
+            GredVqNla::Dp(dp),
+            GredVqNla::StatBytes(u64::from(u32::MAX) + 1),
+            GredVqNla::StatPackets(12),
+            GredVqNla::StatBacklog(0),
+            GredVqNla::StatProbDrop(0),
+            GredVqNla::StatProbMark(0),
+            GredVqNla::StatForcedDrop(0),
+            GredVqNla::StatForcedMark(0),
+            GredVqNla::StatPdrop(0),
+            GredVqNla::StatOther(0),
+            GredVqNla::Flags(flags),
+        ],
+    };
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("gred".to_string()),
+            Nla::Options(Qdisc::Gred(GredQdisc {
+                nlas: vec![
+                    GredQdiscNla::Dps(TcGredDps {
+                        dps: 2,
+                        def_dp: 1,
+                        ..Default::default()
+                    }),
+                    GredQdiscNla::MaxP(vec![85_899_345, 214_748_364]),
+                    GredQdiscNla::Limit(0),
+                    GredQdiscNla::Parms(vec![
+                        qopt(0, 15_360 << 10, 46_080 << 10, 21),
+                        qopt(1, 10_240 << 10, 40_960 << 10, 19),
+                    ]),
+                    GredQdiscNla::VqList(vec![vq(0, 0), vq(1, u32::from(TC_RED_ECN))]),
+                ],
+            })),
+        ],
+    };
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_dps = [
+        0x10, 0x00, 0x03, 0x00, // TCA_GRED_DPS, length = 16
+        0x02, 0x00, 0x00, 0x00, // DPs = 2
+        0x01, 0x00, 0x00, 0x00, // def_DP = 1
+        0x00, // grio
+        0x00, // flags
+        0x00, 0x00, // padding
+    ];
+    // options start after the header (20 bytes), the kind (12 bytes) and the
+    // TCA_OPTIONS header (4 bytes)
+    assert_eq!(&buf[36..52], &expected_dps[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_qfq_round_trip() {
+    // tc qdisc add dev eth0 root handle 1: qfq
+    let msg = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("qfq".to_string()),
+            Nla::Options(Qdisc::Qfq(QfqQdisc)),
+        ],
+    };
+    assert_eq!(Qdisc::new("qfq"), Qdisc::Qfq(QfqQdisc));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_class_qfq_round_trip() {
+    // tc class add dev eth0 parent 1: classid 1:1 qfq weight 10 maxpkt 9000
+    let msg: TcMessage<Class> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 1),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("qfq".to_string()),
+            Nla::Options(Class::Qfq(QfqClass {
+                nlas: vec![QfqClassNla::Weight(10), QfqClassNla::Lmax(9000)],
+            })),
+        ],
+    };
+    assert_eq!(Class::new("qfq"), Class::Qfq(QfqClass::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x14, 0x00, 0x02, 0x00, // TCA_OPTIONS, length = 20
+        0x08, 0x00, 0x01, 0x00, // TCA_QFQ_WEIGHT, length = 8
+        0x0a, 0x00, 0x00, 0x00, // 10
+        0x08, 0x00, 0x02, 0x00, // TCA_QFQ_LMAX, length = 8
+        0x28, 0x23, 0x00, 0x00, // 9000
+    ];
+    assert_eq!(&buf[28..], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_qdisc_fifo_round_trip() {
+    // tc qdisc add dev eth0 root pfifo limit 1000
+    // tc qdisc add dev eth0 root bfifo limit 1000
+    let qopt = FifoOpt { limit: 1000 };
+    for (kind, options) in [
+        ("pfifo", Qdisc::Pfifo(PfifoQdisc { qopt })),
+        ("bfifo", Qdisc::Bfifo(BfifoQdisc { qopt })),
+    ]
+    .iter()
+    .cloned()
+    {
+        let msg = TcMessage {
+            header: TcHeader {
+                family: 0,
+                index: 2,
+                handle: TcHandle::from_parts(0x8002, 0),
+                parent: TcHandle::root(),
+                info: 0,
+            },
+            nlas: vec![Nla::Kind(kind.to_string()), Nla::Options(options)],
+        };
+
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf);
+        #[rustfmt::skip]
+        let expected_options = [
+            0x08, 0x00, 0x02, 0x00, // TCA_OPTIONS, length = 8
+            0xe8, 0x03, 0x00, 0x00, // limit = 1000
+        ];
+        assert_eq!(&buf[32..], &expected_options[..]);
+        let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+        assert_eq!(msg, parsed);
+    }
+    assert_eq!(Qdisc::new("pfifo"), Qdisc::Pfifo(PfifoQdisc::default()));
+    assert_eq!(Qdisc::new("bfifo"), Qdisc::Bfifo(BfifoQdisc::default()));
+}
+
+#[test]
+fn tc_class_htb_round_trip() {
+    // tc class add dev eth0 parent 1: classid 1:10 htb rate 10mbit ceil 20mbit
+    let mut rate = TcRateSpec {
+        rate: 1_250_000,
+        ..Default::default()
+    };
+    let mut ceil = TcRateSpec {
+        rate: 2_500_000,
+        ..Default::default()
+    };
+    let rtab = rate.rate_table(0);
+    let ctab = ceil.rate_table(0);
+    let msg: TcMessage<Class> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0x10),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("htb".to_string()),
+            Nla::Options(Class::Htb(HtbClass {
+                nlas: vec![
+                    HtbClassNla::Parms(TcaHtbOpt {
+                        rate,
+                        ceil,
+                        buffer: 200_000,
+                        cbuffer: 100_000,
+                        quantum: 0,
+                        level: 0,
+                        prio: 0,
+                    }),
+                    HtbClassNla::Rtab(rtab),
+                    HtbClassNla::Ctab(ctab),
+                ],
+            })),
+        ],
+    };
+    assert_eq!(Class::new("htb"), Class::Htb(HtbClass::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_parms = [
+        0x30, 0x00, 0x01, 0x00, // TCA_HTB_PARMS, length = 48
+        // rate
+        0x03, 0x00, 0x00, 0x00, 0xff, 0xff, 0x00, 0x00, 0xd0, 0x12, 0x13, 0x00,
+        // ceil
+        0x03, 0x00, 0x00, 0x00, 0xff, 0xff, 0x00, 0x00, 0xa0, 0x25, 0x26, 0x00,
+    ];
+    // options start after the header (20 bytes), the kind (8 bytes) and the
+    // TCA_OPTIONS header (4 bytes)
+    assert_eq!(&buf[32..60], &expected_parms[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // rates above 32 bits are carried by TCA_HTB_RATE64 and TCA_HTB_CEIL64
+    let msg: TcMessage<Class> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("htb".to_string()),
+            Nla::Options(Class::Htb(HtbClass {
+                nlas: vec![
+                    HtbClassNla::Rate64(5_000_000_000),
+                    HtbClassNla::Ceil64(u64::MAX),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_class_htb_xstats() {
+    let msg: TcMessage<Class> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("htb".to_string()),
+            Nla::Stats2(vec![Stats2::StatsApp(XStats::Htb(HtbXStats {
+                lends: 10,
+                borrows: 2,
+                giants: 0,
+                tokens: 25_000,
+                ctokens: -1000,
+            }))]),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_stats = [
+        0x1c, 0x00, 0x07, 0x00, // TCA_STATS2, length = 28
+        0x18, 0x00, 0x04, 0x00, // TCA_STATS_APP, length = 24
+        0x0a, 0x00, 0x00, 0x00, // lends = 10
+        0x02, 0x00, 0x00, 0x00, // borrows = 2
+        0x00, 0x00, 0x00, 0x00, // giants = 0
+        0xa8, 0x61, 0x00, 0x00, // tokens = 25000
+        0x18, 0xfc, 0xff, 0xff, // ctokens = -1000
+    ];
+    assert_eq!(&buf[28..], &expected_stats[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+// `tc qdisc add dev eth0 root handle 1: cbq bandwidth 100Mbit avpkt 1000`, laid
+// out as dumped by the kernel (basic and queue statistics left out)
+#[rustfmt::skip]
+static QDISC_CBQ_PACKET: [u8; 124] = [
+    // tcmsg
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x02, 0x00, 0x00, 0x00, // interface index = 2
+    0x00, 0x00, 0x01, 0x00, // handle = 1:0
+    0xff, 0xff, 0xff, 0xff, // parent = TC_H_ROOT
+    0x01, 0x00, 0x00, 0x00, // info (refcnt) = 1
+
+    // TCA_KIND
+    0x08, 0x00, // length = 8
+    0x01, 0x00, // type = 1
+    0x63, 0x62, 0x71, 0x00, // "cbq\0"
+
+    // TCA_OPTIONS
+    0x48, 0x00, // length = 72
+    0x02, 0x00, // type = 2
+        // TCA_CBQ_RATE
+        0x10, 0x00, // length = 16
+        0x05, 0x00, // type = 5
+        0x03, // cell_log = 3
+        0x01, // linklayer = TC_LINKLAYER_ETHERNET
+        0x00, 0x00, // overhead = 0
+        0xff, 0xff, // cell_align = -1
+        0x00, 0x00, // mpu = 0
+        0x20, 0xbc, 0xbe, 0x00, // rate = 12500000
+        // TCA_CBQ_LSSOPT
+        0x18, 0x00, // length = 24
+        0x01, 0x00, // type = 1
+        0xff, // change
+        0x03, // flags = TCF_CBQ_LSS_BOUNDED | TCF_CBQ_LSS_ISOLATED
+        0x05, // ewma_log = 5
+        0x00, // level = 0
+        0x00, 0x00, 0x00, 0x00, // maxidle = 0
+        0xff, 0xff, 0xff, 0x7f, // minidle = 0x7fffffff
+        0x00, 0x00, 0x00, 0x00, // offtime = 0
+        0xe8, 0x03, 0x00, 0x00, // avpkt = 1000
+        // TCA_CBQ_WRROPT
+        0x10, 0x00, // length = 16
+        0x02, 0x00, // type = 2
+        0x00, // flags = 0
+        0x08, // priority = 8
+        0x08, // cpriority = 8
+        0x00, // reserved
+        0xea, 0x05, 0x00, 0x00, // allot = 1514
+        0x20, 0xbc, 0xbe, 0x00, // weight = 12500000
+        // TCA_CBQ_OVL_STRATEGY
+        0x0c, 0x00, // length = 12
+        0x04, 0x00, // type = 4
+        0x00, // strategy = TC_CBQ_OVL_CLASSIC
+        0x08, // priority2 = 8
+        0x00, 0x00, // pad
+        0x00, 0x00, 0x00, 0x00, // penalty = 0
+
+    // TCA_STATS2
+    0x18, 0x00, // length = 24
+    0x07, 0x00, // type = 7
+        // TCA_STATS_APP
+        0x14, 0x00, // length = 20
+        0x04, 0x00, // type = 4
+        0x00, 0x00, 0x00, 0x00, // borrows = 0
+        0x00, 0x00, 0x00, 0x00, // overactions = 0
+        0x1f, 0x00, 0x00, 0x00, // avgidle = 31
+        0x50, 0xfb, 0xff, 0xff, // undertime = -1200
+];
+
+fn qdisc_cbq_message() -> TcMessage<Qdisc> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 0),
+            parent: TcHandle::root(),
+            info: 1,
+        },
+        nlas: vec![
+            Nla::Kind("cbq".to_string()),
+            Nla::Options(Qdisc::Cbq(CbqQdisc {
+                nlas: vec![
+                    CbqNla::Rate(TcRateSpec {
+                        cell_log: 3,
+                        linklayer: TC_LINKLAYER_ETHERNET,
+                        overhead: 0,
+                        cell_align: -1,
+                        mpu: 0,
+                        rate: 12_500_000,
+                    }),
+                    CbqNla::LssOpt(TcCbqLss {
+                        change: 0xff,
+                        flags: TCF_CBQ_LSS_BOUNDED | TCF_CBQ_LSS_ISOLATED,
+                        ewma_log: TC_CBQ_DEF_EWMA,
+                        level: 0,
+                        maxidle: 0,
+                        minidle: 0x7fff_ffff,
+                        offtime: 0,
+                        avpkt: 1000,
+                    }),
+                    CbqNla::WrrOpt(TcCbqWrr {
+                        flags: 0,
+                        priority: TC_CBQ_MAXPRIO,
+                        cpriority: TC_CBQ_MAXPRIO,
+                        allot: 1514,
+                        weight: 12_500_000,
+                    }),
+                    CbqNla::OvlStrategy(TcCbqOvl {
+                        strategy: TC_CBQ_OVL_CLASSIC,
+                        priority2: TC_CBQ_MAXPRIO,
+                        penalty: 0,
+                    }),
+                ],
+            })),
+            Nla::Stats2(vec![Stats2::StatsApp(XStats::Cbq(CbqXStats {
+                borrows: 0,
+                overactions: 0,
+                avgidle: 31,
+                undertime: -1200,
+            }))]),
+        ],
+    }
+}
+
+#[test]
+fn tc_qdisc_cbq_parse() {
+    let actual = TcMessage::parse(&TcMessageBuffer::new(&&QDISC_CBQ_PACKET[..])).unwrap();
+    assert_eq!(actual, qdisc_cbq_message());
+    assert_eq!(Qdisc::new("cbq"), Qdisc::Cbq(CbqQdisc::default()));
+}
+
+#[test]
+fn tc_qdisc_cbq_emit() {
+    let msg = qdisc_cbq_message();
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert_eq!(&buf[..], &QDISC_CBQ_PACKET[..]);
+}
+
+#[test]
+fn tc_class_cbq_round_trip() {
+    let mut rate = TcRateSpec {
+        linklayer: TC_LINKLAYER_ETHERNET,
+        rate: 1_250_000,
+        ..Default::default()
+    };
+    let rtab = rate.rate_table(1514);
+    let msg: TcMessage<Class> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle::from_parts(1, 1),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0,
+        },
+        nlas: vec![
+            Nla::Kind("cbq".to_string()),
+            Nla::Options(Class::Cbq(CbqClass {
+                nlas: vec![
+                    CbqNla::Rate(rate),
+                    CbqNla::Rtab(rtab),
+                    CbqNla::Fopt(TcCbqFopt {
+                        split: 0x0001_0000,
+                        defmap: 0xffff,
+                        defchange: 0xffff,
+                    }),
+                    CbqNla::Police(TcCbqPolice { police: 0 }),
+                ],
+            })),
+            Nla::XStats(XStats::Cbq(CbqXStats::default())),
+        ],
+    };
+    assert_eq!(Class::new("cbq"), Class::Cbq(CbqClass::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_u32_round_trip() {
+    // `tc filter add dev eth0 parent 1: protocol ip prio 1 u32 \
+    //      match ip dst 10.0.0.1/32 flowid 1:10`
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle(0x8000_0800),
+            parent: TcHandle::from_parts(1, 0),
+            // priority 1, protocol ETH_P_IP in network byte order
+            info: 0x0001_0008,
+        },
+        nlas: vec![
+            Nla::Kind("u32".to_string()),
+            Nla::Options(Filter::U32(U32Filter {
+                nlas: vec![
+                    U32FilterNla::ClassId(0x0001_0010),
+                    U32FilterNla::Sel(TcU32Sel {
+                        flags: TC_U32_TERMINAL,
+                        keys: vec![TcU32Key {
+                            mask: 0xffff_ffff,
+                            val: 0x0a00_0001,
+                            off: 16,
+                            offmask: 0,
+                        }],
+                        ..Default::default()
+                    }),
+                ],
+            })),
+        ],
+    };
+    assert_eq!(Filter::new("u32"), Filter::U32(U32Filter::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_sel = [
+        0x24, 0x00, 0x05, 0x00, // TCA_U32_SEL, length = 36
+        0x01, // flags = TC_U32_TERMINAL
+        0x00, // offshift
+        0x01, // nkeys
+        0x00, // padding
+        0x00, 0x00, // offmask
+        0x00, 0x00, // off
+        0x00, 0x00, // offoff
+        0x00, 0x00, // hoff
+        0x00, 0x00, 0x00, 0x00, // hmask
+        // key
+        0xff, 0xff, 0xff, 0xff, // mask
+        0x0a, 0x00, 0x00, 0x01, // val = 10.0.0.1
+        0x10, 0x00, 0x00, 0x00, // off = 16, the IPv4 destination
+        0x00, 0x00, 0x00, 0x00, // offmask
+    ];
+    // the selector follows the header (20 bytes), the kind (8 bytes), the
+    // TCA_OPTIONS header (4 bytes) and TCA_U32_CLASSID (8 bytes)
+    assert_eq!(&buf[40..76], &expected_sel[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // hash table and the attributes only dumped by the kernel
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("u32".to_string()),
+            Nla::Options(Filter::U32(U32Filter {
+                nlas: vec![
+                    U32FilterNla::Divisor(256),
+                    U32FilterNla::Hash(0x8000_0000),
+                    U32FilterNla::Link(0x0001_0000),
+                    U32FilterNla::Indev("eth0".to_string()),
+                    U32FilterNla::Flags(TCA_CLS_FLAGS_SKIP_HW | TCA_CLS_FLAGS_NOT_IN_HW),
+                    U32FilterNla::Mark(TcU32Mark {
+                        val: 0x10,
+                        mask: 0xff,
+                        success: 3,
+                    }),
+                    U32FilterNla::Pcnt(TcU32Pcnt {
+                        rcnt: 10,
+                        rhit: 3,
+                        kcnts: vec![3, 3],
+                    }),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_u32_sel_too_many_keys() {
+    let mut sel = [0; 32];
+    TcU32Sel {
+        keys: vec![TcU32Key::default()],
+        ..Default::default()
+    }
+    .emit(&mut sel);
+    // nkeys = 2 with a single key
+    sel[2] = 2;
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("u32".to_string()),
+            Nla::Options(Filter::Other(
+                [&[0x24, 0x00, 0x05, 0x00][..], &sel[..]].concat(),
+            )),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert!(TcMessage::<Filter>::parse(&TcMessageBuffer::new(&buf)).is_err());
+}
+
+fn filter_bpf_message(nlas: Vec<BpfFilterNla>) -> TcMessage<Filter> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle(1),
+            parent: TcHandle::from_parts(0xffff, 0xfff2),
+            // priority 1, protocol ETH_P_ALL in network byte order
+            info: 0x0001_0300,
+        },
+        nlas: vec![
+            Nla::Kind("bpf".to_string()),
+            Nla::Options(Filter::Bpf(BpfFilter { nlas })),
+        ],
+    }
+}
+
+#[test]
+fn tc_filter_bpf_classic_round_trip() {
+    // `tc filter add dev eth0 ingress bpf bytecode '1,6 0 0 4294967295,' \
+    //      classid 1:10`
+    let msg = filter_bpf_message(vec![
+        BpfFilterNla::ClassId(0x0001_0010),
+        BpfFilterNla::OpsLen(1),
+        BpfFilterNla::Ops(vec![BpfInsn {
+            code: 0x06,
+            jt: 0,
+            jf: 0,
+            k: 0xffff_ffff,
+        }]),
+    ]);
+    assert_eq!(Filter::new("bpf"), Filter::Bpf(BpfFilter::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_ops = [
+        0x06, 0x00, 0x04, 0x00, // TCA_BPF_OPS_LEN, length = 6
+        0x01, 0x00, // 1 instruction
+        0x00, 0x00, // padding
+        0x0c, 0x00, 0x05, 0x00, // TCA_BPF_OPS, length = 12
+        0x06, 0x00, // code = BPF_RET | BPF_K
+        0x00, // jt
+        0x00, // jf
+        0xff, 0xff, 0xff, 0xff, // k
+    ];
+    // the program follows the header (20 bytes), the kind (8 bytes), the
+    // TCA_OPTIONS header (4 bytes) and TCA_BPF_CLASSID (8 bytes)
+    assert_eq!(&buf[40..60], &expected_ops[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_bpf_fd_round_trip() {
+    // `tc filter add dev eth0 ingress bpf da obj prog.o sec classifier`
+    let msg = filter_bpf_message(vec![
+        BpfFilterNla::Fd(5),
+        BpfFilterNla::Name("prog.o:[classifier]".to_string()),
+        BpfFilterNla::Flags(BpfFilterFlags::ACT_DIRECT),
+    ]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // the kernel dumps the id and tag of the program instead of the fd
+    let msg = filter_bpf_message(vec![
+        BpfFilterNla::Name("prog.o:[classifier]".to_string()),
+        BpfFilterNla::Flags(BpfFilterFlags::ACT_DIRECT),
+        BpfFilterNla::FlagsGen(TCA_CLS_FLAGS_NOT_IN_HW),
+        BpfFilterNla::Id(42),
+        BpfFilterNla::Tag(vec![0x1f, 0x2e, 0x3d, 0x4c, 0x5b, 0x6a, 0x79, 0x88]),
+    ]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_bpf_invalid_program() {
+    let insn = BpfInsn {
+        code: 0x06,
+        ..Default::default()
+    };
+    let invalid = [
+        // fd and bytecode
+        vec![
+            BpfFilterNla::Fd(5),
+            BpfFilterNla::OpsLen(1),
+            BpfFilterNla::Ops(vec![insn]),
+        ],
+        // bytecode length mismatch
+        vec![BpfFilterNla::OpsLen(2), BpfFilterNla::Ops(vec![insn])],
+        // bytecode without its length
+        vec![BpfFilterNla::Ops(vec![insn])],
+    ];
+    for nlas in invalid.iter().cloned() {
+        let filter = BpfFilter { nlas };
+        assert!(filter.validate().is_err());
+
+        let msg = filter_bpf_message(filter.nlas);
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf);
+        assert!(TcMessage::<Filter>::parse(&TcMessageBuffer::new(&buf)).is_err());
+    }
+}
+
+#[test]
+fn tc_filter_basic_round_trip() {
+    // `tc filter add dev eth0 parent 1: basic match 'cmp(u8 at 9 layer network eq 6)' \
+    //      classid 1:10`
+    #[rustfmt::skip]
+    let ematches = vec![
+        // TCA_EMATCH_TREE_HDR
+        0x08, 0x00, 0x01, 0x00,
+        0x01, 0x00, // nmatches = 1
+        0x00, 0x00, // progid
+        // TCA_EMATCH_TREE_LIST
+        0x1c, 0x00, 0x02, 0x00,
+            // match 1
+            0x18, 0x00, 0x01, 0x00,
+            0x00, 0x00, // matchid
+            0x01, 0x00, // kind = TCF_EM_CMP
+            0x00, 0x00, // flags
+            0x00, 0x00, // pad
+            // struct tcf_em_cmp
+            0x06, 0x00, 0x00, 0x00, // val = 6
+            0x00, 0x00, 0x00, 0x00, // mask
+            0x09, 0x00, // off = 9
+            0x01, // align = TCF_EM_ALIGN_U8, flags = 0
+            0x01, // layer = TCF_LAYER_NETWORK, opnd = TCF_EM_OPND_EQ
+    ];
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle(1),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0x0001_0008,
+        },
+        nlas: vec![
+            Nla::Kind("basic".to_string()),
+            Nla::Options(Filter::Basic(BasicFilter {
+                nlas: vec![
+                    BasicFilterNla::ClassId(0x0001_0010),
+                    BasicFilterNla::Ematches(ematches),
+                    BasicFilterNla::Pcnt(TcBasicPcnt { rcnt: 10, rhit: 4 }),
+                ],
+            })),
+        ],
+    };
+    assert_eq!(Filter::new("basic"), Filter::Basic(BasicFilter::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_fw_round_trip() {
+    // `tc filter add dev eth0 parent 1: protocol ip prio 1 handle 0x10/0xff fw \
+    //      classid 1:10`
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle(0x10),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0x0001_0008,
+        },
+        nlas: vec![
+            Nla::Kind("fw".to_string()),
+            Nla::Options(Filter::Fw(FwFilter {
+                nlas: vec![FwFilterNla::ClassId(0x0001_0010), FwFilterNla::Mask(0xff)],
+            })),
+        ],
+    };
+    assert_eq!(Filter::new("fw"), Filter::Fw(FwFilter::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x14, 0x00, 0x02, 0x00, // TCA_OPTIONS, length = 20
+        0x08, 0x00, 0x01, 0x00, // TCA_FW_CLASSID, length = 8
+        0x10, 0x00, 0x01, 0x00, // 1:10
+        0x08, 0x00, 0x05, 0x00, // TCA_FW_MASK, length = 8
+        0xff, 0x00, 0x00, 0x00, // 0xff
+    ];
+    // the options follow the header (20 bytes) and the kind (8 bytes)
+    assert_eq!(&buf[28..], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("fw".to_string()),
+            Nla::Options(Filter::Fw(FwFilter {
+                nlas: vec![FwFilterNla::Indev("eth1".to_string())],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_flow_round_trip() {
+    // `tc filter add dev eth0 parent 1: protocol ip prio 1 flow hash \
+    //      keys src,dst,proto-src,proto-dst divisor 1024 baseclass 1:1 perturb 10`
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle(1),
+            parent: TcHandle::from_parts(1, 0),
+            info: 0x0001_0008,
+        },
+        nlas: vec![
+            Nla::Kind("flow".to_string()),
+            Nla::Options(Filter::Flow(FlowFilter {
+                nlas: vec![
+                    FlowFilterNla::Keys(
+                        FlowKey::SRC | FlowKey::DST | FlowKey::PROTO_SRC | FlowKey::PROTO_DST,
+                    ),
+                    FlowFilterNla::Mode(FlowMode::Hash),
+                    FlowFilterNla::BaseClass(0x0001_0001),
+                    FlowFilterNla::Divisor(1024),
+                    FlowFilterNla::Perturb(10),
+                ],
+            })),
+        ],
+    };
+    assert_eq!(Filter::new("flow"), Filter::Flow(FlowFilter::default()));
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_keys = [
+        0x08, 0x00, 0x01, 0x00, // TCA_FLOW_KEYS, length = 8
+        0x1b, 0x00, 0x00, 0x00, // src, dst, proto-src and proto-dst
+        0x08, 0x00, 0x02, 0x00, // TCA_FLOW_MODE, length = 8
+        0x01, 0x00, 0x00, 0x00, // FLOW_MODE_HASH
+    ];
+    // the options follow the header (20 bytes), the kind (12 bytes) and the
+    // TCA_OPTIONS header (4 bytes)
+    assert_eq!(&buf[36..52], &expected_keys[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // map mode
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flow".to_string()),
+            Nla::Options(Filter::Flow(FlowFilter {
+                nlas: vec![
+                    FlowFilterNla::Keys(FlowKey::MARK),
+                    FlowFilterNla::Mode(FlowMode::Map),
+                    FlowFilterNla::Rshift(8),
+                    FlowFilterNla::Addend(1),
+                    FlowFilterNla::Mask(0xff),
+                    FlowFilterNla::Xor(0x0f),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_flower_round_trip() {
+    // `tc filter add dev eth0 ingress protocol ip flower skip_hw \
+    //      ip_proto tcp dst_ip 10.0.0.0/8 dst_port 80 classid 1:1`
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle(1),
+            parent: TcHandle::from_parts(0xffff, 0xfff2),
+            // priority 49152, protocol ETH_P_IP in network byte order
+            info: 0xc000_0008,
+        },
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![
+                    FlowerFilterNla::ClassId(0x0001_0001),
+                    FlowerFilterNla::KeyEthType(0x0800),
+                    FlowerFilterNla::KeyIpProto(6),
+                    FlowerFilterNla::KeyIpv4Dst(Ipv4Addr::new(10, 0, 0, 0)),
+                    FlowerFilterNla::KeyIpv4DstMask(Ipv4Addr::new(255, 0, 0, 0)),
+                    FlowerFilterNla::KeyTcpDst(80),
+                    FlowerFilterNla::Flags(TCA_CLS_FLAGS_SKIP_HW),
+                ],
+            })),
+        ],
+    };
+    assert_eq!(
+        Filter::new("flower"),
+        Filter::Flower(FlowerFilter::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_keys = [
+        0x06, 0x00, 0x08, 0x00, // TCA_FLOWER_KEY_ETH_TYPE, length = 6
+        0x08, 0x00, 0x00, 0x00, // ETH_P_IP, in network byte order
+        0x05, 0x00, 0x09, 0x00, // TCA_FLOWER_KEY_IP_PROTO, length = 5
+        0x06, 0x00, 0x00, 0x00, // IPPROTO_TCP
+        0x08, 0x00, 0x0c, 0x00, // TCA_FLOWER_KEY_IPV4_DST, length = 8
+        0x0a, 0x00, 0x00, 0x00, // 10.0.0.0
+        0x08, 0x00, 0x0d, 0x00, // TCA_FLOWER_KEY_IPV4_DST_MASK, length = 8
+        0xff, 0x00, 0x00, 0x00, // 255.0.0.0
+        0x06, 0x00, 0x13, 0x00, // TCA_FLOWER_KEY_TCP_DST, length = 6
+        0x00, 0x50, 0x00, 0x00, // 80, in network byte order
+    ];
+    // the keys follow the header (20 bytes), the kind (12 bytes), the
+    // TCA_OPTIONS header (4 bytes) and TCA_FLOWER_CLASSID (8 bytes)
+    assert_eq!(&buf[44..84], &expected_keys[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // ethernet and vlan keys
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![
+                    FlowerFilterNla::Indev("eth1".to_string()),
+                    FlowerFilterNla::KeyEthDst([0x52, 0x54, 0x00, 0x12, 0x34, 0x56]),
+                    FlowerFilterNla::KeyEthDstMask([0xff, 0xff, 0xff, 0x00, 0x00, 0x00]),
+                    FlowerFilterNla::KeyEthType(0x8100),
+                    FlowerFilterNla::KeyVlanId(100),
+                    FlowerFilterNla::KeyVlanPrio(3),
+                    FlowerFilterNla::KeyVlanEthType(0x86dd),
+                    FlowerFilterNla::KeyIpTtl(64),
+                    FlowerFilterNla::KeyIpTtlMask(0xff),
+                    FlowerFilterNla::InHwCount(0),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_flower_ct_round_trip() {
+    // `tc filter add dev eth0 ingress chain 1 protocol ip flower \
+    //      ct_state +trk+est+rpl ct_zone 2 ct_mark 0x10/0xff`, plus labels
+    let state = FlowerCtState::TRACKED | FlowerCtState::ESTABLISHED | FlowerCtState::REPLY;
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![
+                    FlowerFilterNla::KeyCtState(state),
+                    FlowerFilterNla::KeyCtStateMask(state),
+                    FlowerFilterNla::KeyCtZone(2),
+                    FlowerFilterNla::KeyCtZoneMask(0xffff),
+                    FlowerFilterNla::KeyCtMark(0x10),
+                    FlowerFilterNla::KeyCtMarkMask(0xff),
+                    FlowerFilterNla::KeyCtLabels([1; 16]),
+                    FlowerFilterNla::KeyCtLabelsMask([0xff; 16]),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_state = [
+        0x06, 0x00, 0x5b, 0x00, // TCA_FLOWER_KEY_CT_STATE, length = 6
+        0x2a, 0x00, 0x00, 0x00, // trk, est and rpl
+        0x06, 0x00, 0x5c, 0x00, // TCA_FLOWER_KEY_CT_STATE_MASK, length = 6
+        0x2a, 0x00, 0x00, 0x00,
+    ];
+    // the keys follow the header (20 bytes), the kind (12 bytes) and the
+    // TCA_OPTIONS header (4 bytes)
+    assert_eq!(&buf[36..52], &expected_state[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_flower_mpls_round_trip() {
+    // `tc filter add dev eth0 ingress protocol mpls_uc flower mpls_label 100 \
+    //      mpls_bos 1`
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![
+                    FlowerFilterNla::KeyEthType(0x8847),
+                    FlowerFilterNla::KeyMplsLabel(100),
+                    FlowerFilterNla::KeyMplsBos(1),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_mpls = [
+        0x08, 0x00, 0x46, 0x00, // TCA_FLOWER_KEY_MPLS_LABEL, length = 8
+        0x64, 0x00, 0x00, 0x00, // 100
+        0x05, 0x00, 0x44, 0x00, // TCA_FLOWER_KEY_MPLS_BOS, length = 5
+        0x01, 0x00, 0x00, 0x00, // bottom of stack
+    ];
+    // the keys follow the header (20 bytes), the kind (12 bytes), the
+    // TCA_OPTIONS header (4 bytes) and TCA_FLOWER_KEY_ETH_TYPE (8 bytes)
+    assert_eq!(&buf[44..60], &expected_mpls[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // labels are 20 bits long, the upper bits are dropped
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![FlowerFilterNla::KeyMplsLabel(0x0010_0064)],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    assert_eq!(&buf[40..44], &[0x64, 0x00, 0x00, 0x00]);
+
+    // `mpls lse depth 1 label 100 lse depth 2 label 200 bos 1`
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![FlowerFilterNla::KeyMplsOpts(vec![
+                    FlowerMplsLse {
+                        nlas: vec![FlowerMplsLseNla::Depth(1), FlowerMplsLseNla::Label(100)],
+                    },
+                    FlowerMplsLse {
+                        nlas: vec![
+                            FlowerMplsLseNla::Depth(2),
+                            FlowerMplsLseNla::Label(200),
+                            FlowerMplsLseNla::Bos(1),
+                        ],
+                    },
+                ])],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_flower_tunnel_round_trip() {
+    // `tc filter add dev vxlan0 ingress protocol ip flower enc_key_id 100 \
+    //      enc_dst_ip 10.0.0.1 enc_dst_port 4789`
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![
+                    FlowerFilterNla::KeyEncKeyId(100),
+                    FlowerFilterNla::KeyEncIpv4Dst(Ipv4Addr::new(10, 0, 0, 1)),
+                    FlowerFilterNla::KeyEncIpv4DstMask(Ipv4Addr::new(255, 255, 255, 255)),
+                    FlowerFilterNla::KeyEncUdpDstPort(4789),
+                    FlowerFilterNla::KeyEncUdpDstPortMask(0xffff),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_keys = [
+        0x08, 0x00, 0x1a, 0x00, // TCA_FLOWER_KEY_ENC_KEY_ID, length = 8
+        0x00, 0x00, 0x00, 0x64, // 100, in network byte order
+        0x08, 0x00, 0x1d, 0x00, // TCA_FLOWER_KEY_ENC_IPV4_DST, length = 8
+        0x0a, 0x00, 0x00, 0x01, // 10.0.0.1
+    ];
+    // the keys follow the header (20 bytes), the kind (12 bytes) and the
+    // TCA_OPTIONS header (4 bytes)
+    assert_eq!(&buf[36..52], &expected_keys[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![
+                    FlowerFilterNla::KeyEncIpv6Src([0xfe; 16]),
+                    FlowerFilterNla::KeyEncIpv6SrcMask([0xff; 16]),
+                    FlowerFilterNla::KeyEncUdpSrcPort(1024),
+                    FlowerFilterNla::KeyEncIpTos(0x10),
+                    FlowerFilterNla::KeyEncIpTtl(64),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_flower_ipv6_round_trip() {
+    // `tc filter add dev eth0 ingress protocol ipv6 flower \
+    //      dst_ip 2001:db8::/32 ip_proto ipv6-icmp type 128 code 0 ip_flags nofrag`
+    let mut dst = [0; 16];
+    dst[..4].copy_from_slice(&[0x20, 0x01, 0x0d, 0xb8]);
+    let mut dst_mask = [0; 16];
+    dst_mask[..4].copy_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![
+                    FlowerFilterNla::KeyEthType(0x86dd),
+                    FlowerFilterNla::KeyIpProto(58),
+                    FlowerFilterNla::KeyIpv6Dst(dst),
+                    FlowerFilterNla::KeyIpv6DstMask(dst_mask),
+                    FlowerFilterNla::KeyIcmpv6Type(128),
+                    FlowerFilterNla::KeyIcmpv6TypeMask(0xff),
+                    FlowerFilterNla::KeyIcmpv6Code(0),
+                    FlowerFilterNla::KeyIcmpv6CodeMask(0xff),
+                    FlowerFilterNla::KeyFlags(FlowerKeyFlags::empty()),
+                    FlowerFilterNla::KeyFlagsMask(FlowerKeyFlags::IS_FRAGMENT),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_dst = [
+        0x14, 0x00, 0x10, 0x00, // TCA_FLOWER_KEY_IPV6_DST, length = 20
+        0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    // the keys follow the header (20 bytes), the kind (12 bytes), the
+    // TCA_OPTIONS header (4 bytes), TCA_FLOWER_KEY_ETH_TYPE (8 bytes) and
+    // TCA_FLOWER_KEY_IP_PROTO (8 bytes)
+    assert_eq!(&buf[52..72], &expected_dst[..]);
+    // the flags are in network byte order
+    let flags_mask = buf.len() - 4;
+    assert_eq!(&buf[flags_mask..], &[0x00, 0x00, 0x00, 0x01]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("flower".to_string()),
+            Nla::Options(Filter::Flower(FlowerFilter {
+                nlas: vec![
+                    FlowerFilterNla::KeyIpv6Src([0xfe; 16]),
+                    FlowerFilterNla::KeyIpv6SrcMask([0xff; 16]),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_filter_matchall_round_trip() {
+    // `tc filter add dev eth0 parent ffff: matchall classid 1:1 action drop`
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 2,
+            handle: TcHandle(1),
+            parent: TcHandle::from_parts(0xffff, 0),
+            // priority 49152, protocol ETH_P_ALL in network byte order
+            info: 0xc000_0300,
+        },
+        nlas: vec![
+            Nla::Kind("matchall".to_string()),
+            Nla::Options(Filter::Matchall(MatchallFilter {
+                nlas: vec![
+                    MatchallFilterNla::ClassId(0x0001_0001),
+                    MatchallFilterNla::Flags(TCA_CLS_FLAGS_SKIP_HW),
+                    MatchallFilterNla::Act(vec![TcAction {
+                        tab: 1,
+                        nlas: vec![
+                            TcActionNla::Kind("gact".to_string()),
+                            TcActionNla::Options(TcActionOptions::Gact(GactAction {
+                                nlas: vec![GactActionNla::Parms(TcGact {
+                                    action: TC_ACT_SHOT,
+                                    ..Default::default()
+                                })],
+                            })),
+                        ],
+                    }]),
+                    MatchallFilterNla::Pcnt(42),
+                ],
+            })),
+        ],
+    };
+    assert_eq!(
+        Filter::new("matchall"),
+        Filter::Matchall(MatchallFilter::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_options = [
+        0x08, 0x00, 0x01, 0x00, // TCA_MATCHALL_CLASSID, length = 8
+        0x01, 0x00, 0x01, 0x00, // 1:1
+        0x08, 0x00, 0x03, 0x00, // TCA_MATCHALL_FLAGS, length = 8
+        0x01, 0x00, 0x00, 0x00, // TCA_CLS_FLAGS_SKIP_HW
+    ];
+    // the options follow the header (20 bytes), the kind (16 bytes) and the
+    // TCA_OPTIONS header (4 bytes)
+    assert_eq!(&buf[40..56], &expected_options[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+fn filter_u32_action_message(actions: Vec<TcAction>) -> TcMessage<Filter> {
+    TcMessage {
+        header: TcHeader {
+            family: 0,
+            index: 1,
+            handle: TcHandle(0x8000_0800),
+            parent: TcHandle::from_parts(0xffff, 0),
+            // priority 49152, protocol ETH_P_ALL in network byte order
+            info: 0xc000_0300,
+        },
+        nlas: vec![
+            Nla::Kind("u32".to_string()),
+            Nla::Options(Filter::U32(U32Filter {
+                nlas: vec![
+                    U32FilterNla::Sel(TcU32Sel {
+                        flags: TC_U32_TERMINAL,
+                        keys: vec![TcU32Key::default()],
+                        ..Default::default()
+                    }),
+                    U32FilterNla::Act(actions),
+                ],
+            })),
+        ],
+    }
+}
+
+#[test]
+fn tc_action_mirred_round_trip() {
+    // `tc filter add dev eth0 parent ffff: u32 match u32 0 0 \
+    //      action mirred egress redirect dev eth1`, eth1 being interface 2
+    let parms = TcMirred {
+        index: 1,
+        capab: 0,
+        action: TC_ACT_STOLEN,
+        refcnt: 1,
+        bindcnt: 1,
+        eaction: TCA_EGRESS_REDIR,
+        ifindex: 2,
+    };
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("mirred".to_string()),
+            TcActionNla::Options(TcActionOptions::Mirred(MirredAction {
+                nlas: vec![
+                    MirredActionNla::Parms(parms),
+                    MirredActionNla::Tm(Tcf {
+                        install: 4000,
+                        lastuse: 4000,
+                        expires: 0,
+                        firstuse: 0,
+                    }),
+                ],
+            })),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("mirred"),
+        TcActionOptions::Mirred(MirredAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_action = [
+        0x5c, 0x00, 0x07, 0x00, // TCA_U32_ACT, length = 92
+        0x58, 0x00, 0x01, 0x00, // first action, length = 88
+        0x0b, 0x00, 0x01, 0x00, // TCA_ACT_KIND, length = 11
+        0x6d, 0x69, 0x72, 0x72, 0x65, 0x64, 0x00, 0x00, // "mirred\0" and padding
+        0x48, 0x00, 0x02, 0x00, // TCA_ACT_OPTIONS, length = 72
+        0x20, 0x00, 0x02, 0x00, // TCA_MIRRED_PARMS, length = 32
+        0x01, 0x00, 0x00, 0x00, // index = 1
+        0x00, 0x00, 0x00, 0x00, // capab = 0
+        0x04, 0x00, 0x00, 0x00, // action = TC_ACT_STOLEN
+        0x01, 0x00, 0x00, 0x00, // refcnt = 1
+        0x01, 0x00, 0x00, 0x00, // bindcnt = 1
+        0x01, 0x00, 0x00, 0x00, // eaction = TCA_EGRESS_REDIR
+        0x02, 0x00, 0x00, 0x00, // ifindex = 2
+    ];
+    // the actions follow the header (20 bytes), the kind (8 bytes), the
+    // TCA_OPTIONS header (4 bytes) and TCA_U32_SEL (36 bytes)
+    assert_eq!(&buf[68..124], &expected_action[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // actions of unknown kinds keep their options as raw bytes
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("foo".to_string()),
+            TcActionNla::Options(TcActionOptions::Other(vec![0x01, 0x02, 0x03, 0x04])),
+            TcActionNla::Index(3),
+            TcActionNla::Cookie(vec![0xde, 0xad, 0xbe, 0xef]),
+            TcActionNla::Stats(vec![Stats2::StatsApp(XStats::Other(vec![0; 4]))]),
+        ],
+    }]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_gact_round_trip() {
+    // `tc filter add dev eth0 parent ffff: u32 match u32 0 0 action drop`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("gact".to_string()),
+            TcActionNla::Options(TcActionOptions::Gact(GactAction {
+                nlas: vec![GactActionNla::Parms(TcGact {
+                    index: 1,
+                    action: TC_ACT_SHOT,
+                    refcnt: 1,
+                    bindcnt: 1,
+                    ..Default::default()
+                })],
+            })),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("gact"),
+        TcActionOptions::Gact(GactAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_parms = [
+        0x18, 0x00, 0x02, 0x00, // TCA_GACT_PARMS, length = 24
+        0x01, 0x00, 0x00, 0x00, // index = 1
+        0x00, 0x00, 0x00, 0x00, // capab = 0
+        0x02, 0x00, 0x00, 0x00, // action = TC_ACT_SHOT
+        0x01, 0x00, 0x00, 0x00, // refcnt = 1
+        0x01, 0x00, 0x00, 0x00, // bindcnt = 1
+    ];
+    // the parameters follow the selector, which ends 68 bytes into the
+    // message, the action list header (4 bytes), the action header (4 bytes),
+    // TCA_ACT_KIND (12 bytes) and the TCA_ACT_OPTIONS header (4 bytes)
+    assert_eq!(&buf[92..116], &expected_parms[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // `action pass random netrand drop 10`: drop one packet out of 10 on
+    // average and let the others through
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("gact".to_string()),
+            TcActionNla::Options(TcActionOptions::Gact(GactAction {
+                nlas: vec![
+                    GactActionNla::Parms(TcGact {
+                        action: TC_ACT_OK,
+                        ..Default::default()
+                    }),
+                    GactActionNla::Prob(TcGactP {
+                        ptype: PGACT_NETRAND,
+                        pval: 10,
+                        paction: TC_ACT_SHOT,
+                    }),
+                ],
+            })),
+        ],
+    }]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_prob = [
+        0x0c, 0x00, 0x03, 0x00, // TCA_GACT_PROB, length = 12
+        0x01, 0x00,             // ptype = PGACT_NETRAND
+        0x0a, 0x00,             // pval = 10
+        0x02, 0x00, 0x00, 0x00, // paction = TC_ACT_SHOT
+    ];
+    // the probability follows the parameters
+    assert_eq!(&buf[116..128], &expected_prob[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_gact_prob_kernel_layout() {
+    // options of `action pass random determ drop 5`, as dumped by the kernel
+    #[rustfmt::skip]
+    let options = [
+        0x28, 0x00, 0x02, 0x80, // TCA_ACT_OPTIONS, nested, length = 40
+        0x18, 0x00, 0x02, 0x00, // TCA_GACT_PARMS, length = 24
+        0x01, 0x00, 0x00, 0x00, // index = 1
+        0x00, 0x00, 0x00, 0x00, // capab = 0
+        0x00, 0x00, 0x00, 0x00, // action = TC_ACT_OK
+        0x01, 0x00, 0x00, 0x00, // refcnt = 1
+        0x00, 0x00, 0x00, 0x00, // bindcnt = 0
+        0x0c, 0x00, 0x03, 0x00, // TCA_GACT_PROB, length = 12
+        0x02, 0x00,             // ptype = PGACT_DETERM
+        0x05, 0x00,             // pval = 5
+        0x02, 0x00, 0x00, 0x00, // paction = TC_ACT_SHOT
+    ];
+    let parsed =
+        GactAction::parse(&crate::nlas::NlaBuffer::new_checked(&options[..]).unwrap()).unwrap();
+    assert_eq!(
+        parsed,
+        GactAction {
+            nlas: vec![
+                GactActionNla::Parms(TcGact {
+                    index: 1,
+                    action: TC_ACT_OK,
+                    refcnt: 1,
+                    ..Default::default()
+                }),
+                GactActionNla::Prob(TcGactP {
+                    ptype: PGACT_DETERM,
+                    pval: 5,
+                    paction: TC_ACT_SHOT,
+                }),
+            ],
+        }
+    );
+}
+
+#[test]
+fn tc_action_vlan_round_trip() {
+    // `action vlan push id 10 priority 5`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("vlan".to_string()),
+            TcActionNla::Options(TcActionOptions::Vlan(VlanAction {
+                nlas: vec![
+                    VlanActionNla::Parms(TcVlan {
+                        action: TC_ACT_PIPE,
+                        v_action: TCA_VLAN_ACT_PUSH,
+                        ..Default::default()
+                    }),
+                    VlanActionNla::PushVlanId(10),
+                    VlanActionNla::PushVlanProtocol(0x8100),
+                    VlanActionNla::PushVlanPriority(5),
+                ],
+            })),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("vlan"),
+        TcActionOptions::Vlan(VlanAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_push = [
+        0x06, 0x00, 0x03, 0x00, // TCA_VLAN_PUSH_VLAN_ID, length = 6
+        0x0a, 0x00, // 10
+        0x00, 0x00, // padding
+        0x06, 0x00, 0x04, 0x00, // TCA_VLAN_PUSH_VLAN_PROTOCOL, length = 6
+        0x81, 0x00, // ETH_P_8021Q, in network byte order
+        0x00, 0x00, // padding
+        0x05, 0x00, 0x06, 0x00, // TCA_VLAN_PUSH_VLAN_PRIORITY, length = 5
+        0x05, // 5
+        0x00, 0x00, 0x00, // padding
+    ];
+    assert_eq!(&buf[buf.len() - 24..], &expected_push[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // `action vlan pop`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("vlan".to_string()),
+            TcActionNla::Options(TcActionOptions::Vlan(VlanAction {
+                nlas: vec![VlanActionNla::Parms(TcVlan {
+                    action: TC_ACT_PIPE,
+                    v_action: TCA_VLAN_ACT_POP,
+                    ..Default::default()
+                })],
+            })),
+        ],
+    }]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_police_round_trip() {
+    // `action police rate 1mbit burst 10k mtu 2kb drop`
+    let mut rate = TcRateSpec {
+        linklayer: TC_LINKLAYER_ETHERNET,
+        rate: 125_000,
+        ..Default::default()
+    };
+    let rtab = rate.rate_table(2048);
+    let police = TcPolice {
+        action: TC_POLICE_SHOT,
+        // 10kB take 81.92ms at 1Mbit/s, that is 1280000 ticks of 64ns
+        burst: 1_280_000,
+        mtu: 2048,
+        rate,
+        ..Default::default()
+    };
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("police".to_string()),
+            TcActionNla::Options(TcActionOptions::Police(PoliceAction {
+                nlas: vec![
+                    PoliceActionNla::Tbf(police),
+                    PoliceActionNla::Rate(rtab),
+                    PoliceActionNla::Result(TC_ACT_OK),
+                ],
+            })),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("police"),
+        TcActionOptions::Police(PoliceAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_tbf = [
+        0x3c, 0x00, 0x01, 0x00, // TCA_POLICE_TBF, length = 60
+        0x00, 0x00, 0x00, 0x00, // index = 0
+        0x02, 0x00, 0x00, 0x00, // action = TC_POLICE_SHOT
+        0x00, 0x00, 0x00, 0x00, // limit = 0
+        0x00, 0x88, 0x13, 0x00, // burst = 1280000
+        0x00, 0x08, 0x00, 0x00, // mtu = 2048
+        // rate, cell_log and cell_align are filled in by rate_table()
+        0x04, 0x01, 0x00, 0x00, 0xff, 0xff, 0x00, 0x00, 0x48, 0xe8, 0x01, 0x00,
+        // peakrate
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, // refcnt
+        0x00, 0x00, 0x00, 0x00, // bindcnt
+        0x00, 0x00, 0x00, 0x00, // capab
+    ];
+    // the parameters follow the selector, which ends 68 bytes into the
+    // message, the action list header (4 bytes), the action header (4 bytes),
+    // TCA_ACT_KIND (12 bytes) and the TCA_ACT_OPTIONS header (4 bytes)
+    assert_eq!(&buf[92..152], &expected_tbf[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // legacy policer attached directly to a filter
+    let msg: TcMessage<Filter> = TcMessage {
+        header: TcHeader::default(),
+        nlas: vec![
+            Nla::Kind("fw".to_string()),
+            Nla::Options(Filter::Fw(FwFilter {
+                nlas: vec![
+                    FwFilterNla::ClassId(0x0001_0010),
+                    FwFilterNla::Police(PoliceAction {
+                        nlas: vec![
+                            PoliceActionNla::Tbf(police),
+                            PoliceActionNla::Rate64(5_000_000_000),
+                        ],
+                    }),
+                ],
+            })),
+        ],
+    };
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_skbedit_round_trip() {
+    // `action skbedit mark 0x10/0xff`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("skbedit".to_string()),
+            TcActionNla::Options(TcActionOptions::Skbedit(SkbeditAction {
+                nlas: vec![
+                    SkbeditActionNla::Parms(TcSkbedit {
+                        action: TC_ACT_PIPE,
+                        ..Default::default()
+                    }),
+                    SkbeditActionNla::Mark(0x10),
+                    SkbeditActionNla::Mask(0xff),
+                ],
+            })),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("skbedit"),
+        TcActionOptions::Skbedit(SkbeditAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_mark = [
+        0x08, 0x00, 0x05, 0x00, // TCA_SKBEDIT_MARK, length = 8
+        0x10, 0x00, 0x00, 0x00, // 0x10
+        0x08, 0x00, 0x08, 0x00, // TCA_SKBEDIT_MASK, length = 8
+        0xff, 0x00, 0x00, 0x00, // 0xff
+    ];
+    assert_eq!(&buf[buf.len() - 16..], &expected_mark[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // `action skbedit queue_mapping 3 inheritdsfield`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("skbedit".to_string()),
+            TcActionNla::Options(TcActionOptions::Skbedit(SkbeditAction {
+                nlas: vec![
+                    SkbeditActionNla::Parms(TcSkbedit {
+                        action: TC_ACT_PIPE,
+                        ..Default::default()
+                    }),
+                    SkbeditActionNla::QueueMapping(3),
+                    SkbeditActionNla::Flags(SKBEDIT_F_INHERITDSFIELD),
+                ],
+            })),
+        ],
+    }]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_queue = [
+        0x06, 0x00, 0x04, 0x00, // TCA_SKBEDIT_QUEUE_MAPPING, length = 6
+        0x03, 0x00, // 3
+        0x00, 0x00, // padding
+        0x0c, 0x00, 0x09, 0x00, // TCA_SKBEDIT_FLAGS, length = 12
+        0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // SKBEDIT_F_INHERITDSFIELD
+    ];
+    assert_eq!(&buf[buf.len() - 20..], &expected_queue[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_csum_round_trip() {
+    // `action csum ip4h tcp udp`
+    let csum = CsumAction {
+        nlas: vec![CsumActionNla::Parms(TcCsum {
+            action: TC_ACT_OK,
+            update_flags: CsumUpdateFlags::IPV4HDR | CsumUpdateFlags::TCP | CsumUpdateFlags::UDP,
+            ..Default::default()
+        })],
+    };
+    assert!(csum.validate().is_ok());
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("csum".to_string()),
+            TcActionNla::Options(TcActionOptions::Csum(csum)),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("csum"),
+        TcActionOptions::Csum(CsumAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_parms = [
+        0x1c, 0x00, 0x01, 0x00, // TCA_CSUM_PARMS, length = 28
+        0x00, 0x00, 0x00, 0x00, // index = 0
+        0x00, 0x00, 0x00, 0x00, // capab = 0
+        0x00, 0x00, 0x00, 0x00, // action = TC_ACT_OK
+        0x00, 0x00, 0x00, 0x00, // refcnt = 0
+        0x00, 0x00, 0x00, 0x00, // bindcnt = 0
+        0x19, 0x00, 0x00, 0x00, // update_flags = IPV4HDR | TCP | UDP
+    ];
+    // the options follow the selector (68 bytes), the action list header,
+    // the action header, TCA_ACT_KIND (12 bytes) and the TCA_ACT_OPTIONS
+    // header
+    assert_eq!(&buf[92..120], &expected_parms[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    let noop = CsumAction {
+        nlas: vec![CsumActionNla::Parms(TcCsum::default())],
+    };
+    assert!(noop.validate().is_err());
+}
+
+#[test]
+fn tc_action_tunnel_key_round_trip() {
+    // `action tunnel_key set src_ip 10.0.0.1 dst_ip 10.0.0.2 id 42 dst_port 4789`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("tunnel_key".to_string()),
+            TcActionNla::Options(TcActionOptions::TunnelKey(TunnelKeyAction {
+                nlas: vec![
+                    TunnelKeyActionNla::Parms(TcTunnelKey {
+                        action: TC_ACT_PIPE,
+                        t_action: TCA_TUNNEL_KEY_ACT_SET,
+                        ..Default::default()
+                    }),
+                    TunnelKeyActionNla::EncIpv4Src(Ipv4Addr::new(10, 0, 0, 1)),
+                    TunnelKeyActionNla::EncIpv4Dst(Ipv4Addr::new(10, 0, 0, 2)),
+                    TunnelKeyActionNla::EncKeyId(42),
+                    TunnelKeyActionNla::EncDstPort(4789),
+                    TunnelKeyActionNla::NoCsum(true),
+                ],
+            })),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("tunnel_key"),
+        TcActionOptions::TunnelKey(TunnelKeyAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_key = [
+        0x08, 0x00, 0x03, 0x00, // TCA_TUNNEL_KEY_ENC_IPV4_SRC, length = 8
+        0x0a, 0x00, 0x00, 0x01, // 10.0.0.1
+        0x08, 0x00, 0x04, 0x00, // TCA_TUNNEL_KEY_ENC_IPV4_DST, length = 8
+        0x0a, 0x00, 0x00, 0x02, // 10.0.0.2
+        0x08, 0x00, 0x07, 0x00, // TCA_TUNNEL_KEY_ENC_KEY_ID, length = 8
+        0x00, 0x00, 0x00, 0x2a, // 42, in network byte order
+        0x06, 0x00, 0x09, 0x00, // TCA_TUNNEL_KEY_ENC_DST_PORT, length = 6
+        0x12, 0xb5, // 4789, in network byte order
+        0x00, 0x00, // padding
+        0x05, 0x00, 0x0a, 0x00, // TCA_TUNNEL_KEY_NO_CSUM, length = 5
+        0x01, // true
+        0x00, 0x00, 0x00, // padding
+    ];
+    assert_eq!(&buf[buf.len() - 40..], &expected_key[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // `action tunnel_key unset`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("tunnel_key".to_string()),
+            TcActionNla::Options(TcActionOptions::TunnelKey(TunnelKeyAction {
+                nlas: vec![TunnelKeyActionNla::Parms(TcTunnelKey {
+                    action: TC_ACT_PIPE,
+                    t_action: TCA_TUNNEL_KEY_ACT_RELEASE,
+                    ..Default::default()
+                })],
+            })),
+        ],
+    }]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_sample_round_trip() {
+    // `action sample rate 1000 group 5 trunc 128`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("sample".to_string()),
+            TcActionNla::Options(TcActionOptions::Sample(SampleAction {
+                nlas: vec![
+                    SampleActionNla::Parms(TcSample {
+                        action: TC_ACT_PIPE,
+                        ..Default::default()
+                    }),
+                    SampleActionNla::Rate(1000),
+                    SampleActionNla::TruncSize(128),
+                    SampleActionNla::PsampleGroup(5),
+                ],
+            })),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("sample"),
+        TcActionOptions::Sample(SampleAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_sample = [
+        0x08, 0x00, 0x03, 0x00, // TCA_SAMPLE_RATE, length = 8
+        0xe8, 0x03, 0x00, 0x00, // 1000
+        0x08, 0x00, 0x04, 0x00, // TCA_SAMPLE_TRUNC_SIZE, length = 8
+        0x80, 0x00, 0x00, 0x00, // 128
+        0x08, 0x00, 0x05, 0x00, // TCA_SAMPLE_PSAMPLE_GROUP, length = 8
+        0x05, 0x00, 0x00, 0x00, // 5
+    ];
+    assert_eq!(&buf[buf.len() - 24..], &expected_sample[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_ct_round_trip() {
+    // `action ct commit zone 5 nat src addr 192.168.0.1-192.168.0.10 port 1024-2048`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("ct".to_string()),
+            TcActionNla::Options(TcActionOptions::Ct(CtAction {
+                nlas: vec![
+                    CtActionNla::Parms(TcCt {
+                        action: TC_ACT_PIPE,
+                        ..Default::default()
+                    }),
+                    CtActionNla::Action(
+                        CtActionFlags::COMMIT | CtActionFlags::NAT | CtActionFlags::NAT_SRC,
+                    ),
+                    CtActionNla::Zone(5),
+                    CtActionNla::NatIpv4Min(Ipv4Addr::new(192, 168, 0, 1)),
+                    CtActionNla::NatIpv4Max(Ipv4Addr::new(192, 168, 0, 10)),
+                    CtActionNla::NatPortMin(1024),
+                    CtActionNla::NatPortMax(2048),
+                ],
+            })),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("ct"),
+        TcActionOptions::Ct(CtAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_ct = [
+        0x06, 0x00, 0x03, 0x00, // TCA_CT_ACTION, length = 6
+        0x19, 0x00, // COMMIT | NAT | NAT_SRC
+        0x00, 0x00, // padding
+        0x06, 0x00, 0x04, 0x00, // TCA_CT_ZONE, length = 6
+        0x05, 0x00, // 5
+        0x00, 0x00, // padding
+        0x08, 0x00, 0x09, 0x00, // TCA_CT_NAT_IPV4_MIN, length = 8
+        0xc0, 0xa8, 0x00, 0x01, // 192.168.0.1
+        0x08, 0x00, 0x0a, 0x00, // TCA_CT_NAT_IPV4_MAX, length = 8
+        0xc0, 0xa8, 0x00, 0x0a, // 192.168.0.10
+        0x06, 0x00, 0x0d, 0x00, // TCA_CT_NAT_PORT_MIN, length = 6
+        0x04, 0x00, // 1024, in network byte order
+        0x00, 0x00, // padding
+        0x06, 0x00, 0x0e, 0x00, // TCA_CT_NAT_PORT_MAX, length = 6
+        0x08, 0x00, // 2048, in network byte order
+        0x00, 0x00, // padding
+    ];
+    assert_eq!(&buf[buf.len() - 48..], &expected_ct[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // `action ct clear`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("ct".to_string()),
+            TcActionNla::Options(TcActionOptions::Ct(CtAction {
+                nlas: vec![
+                    CtActionNla::Parms(TcCt {
+                        action: TC_ACT_PIPE,
+                        ..Default::default()
+                    }),
+                    CtActionNla::Action(CtActionFlags::CLEAR),
+                ],
+            })),
+        ],
+    }]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_mpls_round_trip() {
+    // `action mpls push protocol mpls_uc label 1000 tc 3 ttl 64`
+    let push = |label| {
+        filter_u32_action_message(vec![TcAction {
+            tab: 1,
+            nlas: vec![
+                TcActionNla::Kind("mpls".to_string()),
+                TcActionNla::Options(TcActionOptions::Mpls(MplsAction {
+                    nlas: vec![
+                        MplsActionNla::Parms(TcMpls {
+                            action: TC_ACT_PIPE,
+                            m_action: TCA_MPLS_ACT_PUSH,
+                            ..Default::default()
+                        }),
+                        MplsActionNla::Proto(0x8847),
+                        MplsActionNla::Label(label),
+                        MplsActionNla::Tc(3),
+                        MplsActionNla::Ttl(64),
+                    ],
+                })),
+            ],
+        }])
+    };
+    let msg = push(1000);
+    assert_eq!(
+        TcActionOptions::new("mpls"),
+        TcActionOptions::Mpls(MplsAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_push = [
+        0x06, 0x00, 0x04, 0x00, // TCA_MPLS_PROTO, length = 6
+        0x88, 0x47, // ETH_P_MPLS_UC, in network byte order
+        0x00, 0x00, // padding
+        0x08, 0x00, 0x05, 0x00, // TCA_MPLS_LABEL, length = 8
+        0xe8, 0x03, 0x00, 0x00, // 1000
+        0x05, 0x00, 0x06, 0x00, // TCA_MPLS_TC, length = 5
+        0x03, // 3
+        0x00, 0x00, 0x00, // padding
+        0x05, 0x00, 0x07, 0x00, // TCA_MPLS_TTL, length = 5
+        0x40, // 64
+        0x00, 0x00, 0x00, // padding
+    ];
+    assert_eq!(&buf[buf.len() - 32..], &expected_push[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    // labels are 20 bits long, the upper bits are dropped
+    let mut masked = vec![0; push(0xfff0_0000 | 1000).buffer_len()];
+    push(0xfff0_0000 | 1000).emit(&mut masked);
+    assert_eq!(masked, buf);
+
+    // `action mpls pop protocol ipv4`
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("mpls".to_string()),
+            TcActionNla::Options(TcActionOptions::Mpls(MplsAction {
+                nlas: vec![
+                    MplsActionNla::Parms(TcMpls {
+                        action: TC_ACT_PIPE,
+                        m_action: TCA_MPLS_ACT_POP,
+                        ..Default::default()
+                    }),
+                    MplsActionNla::Proto(0x0800),
+                ],
+            })),
+        ],
+    }]);
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+}
+
+#[test]
+fn tc_action_pedit_round_trip() {
+    // `action pedit ex munge ip ttl set 63`
+    let pedit = PeditAction {
+        nlas: vec![
+            PeditActionNla::ParmsEx(TcPedit {
+                action: TC_ACT_PIPE,
+                keys: vec![TcPeditKey {
+                    mask: 0x00ff_ffff,
+                    val: 0x3f00_0000,
+                    off: 8,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            PeditActionNla::KeysEx(vec![TcPeditKeyEx {
+                htype: TCA_PEDIT_KEY_EX_HDR_TYPE_IP4,
+                cmd: TCA_PEDIT_KEY_EX_CMD_SET,
+            }]),
+        ],
+    };
+    assert!(pedit.validate().is_ok());
+    let msg = filter_u32_action_message(vec![TcAction {
+        tab: 1,
+        nlas: vec![
+            TcActionNla::Kind("pedit".to_string()),
+            TcActionNla::Options(TcActionOptions::Pedit(pedit)),
+        ],
+    }]);
+    assert_eq!(
+        TcActionOptions::new("pedit"),
+        TcActionOptions::Pedit(PeditAction::default())
+    );
+
+    let mut buf = vec![0; msg.buffer_len()];
+    msg.emit(&mut buf);
+    #[rustfmt::skip]
+    let expected_pedit = [
+        0x34, 0x00, 0x04, 0x00, // TCA_PEDIT_PARMS_EX, length = 52
+        0x00, 0x00, 0x00, 0x00, // index = 0
+        0x00, 0x00, 0x00, 0x00, // capab = 0
+        0x03, 0x00, 0x00, 0x00, // action = TC_ACT_PIPE
+        0x00, 0x00, 0x00, 0x00, // refcnt = 0
+        0x00, 0x00, 0x00, 0x00, // bindcnt = 0
+        0x01, // nkeys = 1
+        0x00, // flags = 0
+        0x00, 0x00, // padding
+        // key
+        0x00, 0xff, 0xff, 0xff, // mask, in network byte order
+        0x3f, 0x00, 0x00, 0x00, // val = 63, in network byte order
+        0x08, 0x00, 0x00, 0x00, // off = 8
+        0x00, 0x00, 0x00, 0x00, // at = 0
+        0x00, 0x00, 0x00, 0x00, // offmask = 0
+        0x00, 0x00, 0x00, 0x00, // shift = 0
+        0x18, 0x00, 0x05, 0x00, // TCA_PEDIT_KEYS_EX, length = 24
+        0x14, 0x00, 0x06, 0x00, // TCA_PEDIT_KEY_EX, length = 20
+        0x06, 0x00, 0x01, 0x00, // TCA_PEDIT_KEY_EX_HTYPE, length = 6
+        0x02, 0x00, // TCA_PEDIT_KEY_EX_HDR_TYPE_IP4
+        0x00, 0x00, // padding
+        0x06, 0x00, 0x02, 0x00, // TCA_PEDIT_KEY_EX_CMD, length = 6
+        0x00, 0x00, // TCA_PEDIT_KEY_EX_CMD_SET
+        0x00, 0x00, // padding
+    ];
+    // the options follow the selector (68 bytes), the action list header,
+    // the action header, TCA_ACT_KIND (12 bytes) and the TCA_ACT_OPTIONS
+    // header
+    assert_eq!(&buf[92..168], &expected_pedit[..]);
+    let parsed = TcMessage::parse(&TcMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(msg, parsed);
+
+    let mismatch = PeditAction {
+        nlas: vec![
+            PeditActionNla::ParmsEx(TcPedit::default()),
+            PeditActionNla::KeysEx(vec![TcPeditKeyEx::default()]),
+        ],
+    };
+    assert!(mismatch.validate().is_err());
+}
+
+#[test]
+fn tc_handle_display_from_str() {
+    let handle = TcHandle::from_parts(1, 0x10);
+    assert_eq!(handle, TcHandle(0x0001_0010));
+    assert_eq!(handle.major(), 1);
+    assert_eq!(handle.minor(), 0x10);
+    assert!(TcHandle::root().is_root());
+    assert!(TcHandle::ingress().is_ingress());
+    assert!(!handle.is_root());
+
+    for (s, handle) in [
+        ("1:10", TcHandle::from_parts(1, 0x10)),
+        ("1:", TcHandle::from_parts(1, 0)),
+        ("ffff:", TcHandle::from_parts(0xffff, 0)),
+        (":10", TcHandle::from_parts(0, 0x10)),
+        ("1:1", TcHandle(0x0001_0001)),
+        ("fffe:", TcHandle(0xfffe_0000)),
+        (":1", TcHandle(0x0000_0001)),
+        ("ffff:fff1", TcHandle::ingress()),
+        ("root", TcHandle::root()),
+        ("none", TcHandle::default()),
+    ]
+    .iter()
+    {
+        assert_eq!(s.parse::<TcHandle>().unwrap(), *handle);
+        assert_eq!(handle.to_string(), *s);
+    }
+    assert_eq!(
+        "1:0".parse::<TcHandle>().unwrap(),
+        TcHandle::from_parts(1, 0)
+    );
+    assert_eq!(
+        "ingress".parse::<TcHandle>().unwrap(),
+        TcHandle(TC_H_INGRESS)
+    );
+    assert_eq!(TcHandle::ingress().to_string(), "ffff:fff1");
+    // like iproute2, a bare number is the whole handle
+    for (s, handle) in [
+        ("10", TcHandle::from_parts(0, 0x10)),
+        ("ffff", TcHandle::from_parts(0, 0xffff)),
+        ("10000", TcHandle::from_parts(1, 0)),
+        ("ffff0000", TcHandle::from_parts(0xffff, 0)),
+    ]
+    .iter()
+    {
+        assert_eq!(s.parse::<TcHandle>().unwrap(), *handle);
+    }
+    assert!("100000000".parse::<TcHandle>().is_err());
+    assert!("x".parse::<TcHandle>().is_err());
+    assert!("10000:".parse::<TcHandle>().is_err());
+    assert!("1:x".parse::<TcHandle>().is_err());
+}
+
+#[rustfmt::skip]
+static QDISC_DEL_NOTIFICATION: [u8; 60] = [
+    0x3c, 0x00, 0x00, 0x00, // length = 60
+    0x25, 0x00, // message type = 37 = RTM_DELQDISC
+    0x00, 0x00, // flags
+    0x00, 0x00, 0x00, 0x00, // seq id = 0
+    0x00, 0x00, 0x00, 0x00, // pid = 0
+
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x03, 0x00, 0x00, 0x00, // interface index = 3
+    0x00, 0x00, 0xff, 0xff, // handle = ffff:
+    0xf1, 0xff, 0xff, 0xff, // parent = ffff:fff1
+    0x01, 0x00, 0x00, 0x00, // info = 1
+
+    // TCA_KIND
+    0x0c, 0x00, // length = 12
+    0x01, 0x00, // type = 1
+    0x69, 0x6e, 0x67, 0x72, 0x65, 0x73, 0x73, 0x00, // "ingress\0"
+
+    // TCA_HW_OFFLOAD
+    0x05, 0x00, // length = 5
+    0x0c, 0x00, // type = 12
+    0x00, // value = 0
+    0x00, 0x00, 0x00, // padding
+
+    // TCA_OPTIONS
+    0x04, 0x00, // length = 4
+    0x02, 0x00, // type = 2
+];
+
+#[rustfmt::skip]
+static FILTER_DEL_NOTIFICATION: [u8; 64] = [
+    0x40, 0x00, 0x00, 0x00, // length = 64
+    0x2d, 0x00, // message type = 45 = RTM_DELTFILTER
+    0x00, 0x00, // flags
+    0x00, 0x00, 0x00, 0x00, // seq id = 0
+    0x00, 0x00, 0x00, 0x00, // pid = 0
+
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x03, 0x00, 0x00, 0x00, // interface index = 3
+    0x10, 0x00, 0x00, 0x00, // handle = 0x10
+    0x00, 0x00, 0x01, 0x00, // parent = 1:
+    0x08, 0x00, 0x01, 0x00, // info = prio 1, protocol ETH_P_IP
+
+    // TCA_KIND
+    0x07, 0x00, // length = 7
+    0x01, 0x00, // type = 1
+    0x66, 0x77, 0x00, // "fw\0"
+    0x00, // padding
+
+    // TCA_CHAIN
+    0x08, 0x00, // length = 8
+    0x0b, 0x00, // type = 11
+    0x00, 0x00, 0x00, 0x00, // chain 0
+
+    // TCA_OPTIONS
+    0x0c, 0x00, // length = 12
+    0x02, 0x00, // type = 2
+        // TCA_FW_CLASSID
+        0x08, 0x00, // length = 8
+        0x01, 0x00, // type = 1
+        0x10, 0x00, 0x01, 0x00, // classid = 1:10
+];
+
+#[test]
+fn tc_notifications_parse() {
+    // Notifications sent to RTNLGRP_TC when a qdisc and a filter are deleted (`tc qdisc del dev
+    // eth0 ingress` and `tc filter del dev eth0 parent 1: protocol ip prio 1 handle 0x10 fw`),
+    // without the statistics. Unlike responses they have no sequence number or port id.
+    let qdisc = NetlinkMessage::<RtnlMessage>::deserialize(&QDISC_DEL_NOTIFICATION).unwrap();
+    assert_eq!(qdisc.header.sequence_number, 0);
+    assert_eq!(qdisc.header.port_number, 0);
+    assert_eq!(
+        qdisc.payload,
+        NetlinkPayload::InnerMessage(RtnlMessage::DelQueueDiscipline(TcMessage {
+            header: TcHeader {
+                family: 0,
+                index: 3,
+                handle: TcHandle::from_parts(0xffff, 0),
+                parent: TcHandle::ingress(),
+                info: 1,
+            },
+            nlas: vec![
+                Nla::Kind("ingress".to_string()),
+                Nla::HwOffload(0),
+                Nla::Options(Qdisc::Ingress(Ingress)),
+            ],
+        }))
+    );
+
+    let filter = NetlinkMessage::<RtnlMessage>::deserialize(&FILTER_DEL_NOTIFICATION).unwrap();
+    assert_eq!(
+        filter.payload,
+        NetlinkPayload::InnerMessage(RtnlMessage::DelTrafficFilter(TcMessage {
+            header: TcHeader {
+                family: 0,
+                index: 3,
+                handle: TcHandle(0x10),
+                parent: TcHandle::from_parts(1, 0),
+                info: 0x0001_0008,
+            },
+            nlas: vec![
+                Nla::Kind("fw".to_string()),
+                Nla::Chain(0),
+                Nla::Options(Filter::Fw(FwFilter {
+                    nlas: vec![FwFilterNla::ClassId(0x0001_0010)],
+                })),
+            ],
+        }))
+    );
+}
+
+fn stats(bytes: u64, packets: u32, drops: u32, backlog: u32) -> Stats {
+    Stats {
+        bytes,
+        packets,
+        drops,
+        overlimits: 0,
+        bps: 0,
+        pps: 0,
+        qlen: 0,
+        backlog,
+    }
+}
+
+#[test]
+fn tc_stats_rate() {
+    let prev = stats(1_000, 10, 1, 0);
+    let curr = stats(501_000, 1010, 5, 3000);
+    let rate = compute_rate(&prev, &curr, Duration::from_millis(500));
+    assert_eq!(
+        rate,
+        StatsRate {
+            bytes_per_sec: 1_000_000.0,
+            packets_per_sec: 2000.0,
+            drops_per_sec: 8.0,
+            backlog: 3000,
+        }
+    );
+
+    // no time elapsed
+    let rate = compute_rate(&prev, &curr, Duration::from_secs(0));
+    assert_eq!(rate.bytes_per_sec, 0.0);
+    assert_eq!(rate.backlog, 3000);
+}
+
+#[test]
+fn tc_stats_rate_wrap_around() {
+    let prev = stats(u64::MAX - 99, u32::MAX - 9, u32::MAX, 0);
+    let curr = stats(100, 10, 1, 0);
+    let rate = compute_rate(&prev, &curr, Duration::from_secs(1));
+    assert_eq!(rate.bytes_per_sec, 200.0);
+    assert_eq!(rate.packets_per_sec, 20.0);
+    assert_eq!(rate.drops_per_sec, 2.0);
+}
+
+#[test]
+fn tc_stats_poller() {
+    let msg = |stats| TcMessage::<Qdisc> {
+        header: TcHeader::default(),
+        nlas: vec![Nla::Kind("fq_codel".to_string()), Nla::Stats(stats)],
+    };
+    let start = Instant::now();
+    let mut poller = TcStatsPoller::new();
+    assert_eq!(poller.poll(&msg(stats(0, 0, 0, 0)), start), None);
+    // messages without TCA_STATS do not replace the previous snapshot
+    assert_eq!(poller.poll(&TcMessage::<Qdisc>::default(), start), None);
+
+    let rate = poller
+        .poll(
+            &msg(stats(10_000, 100, 0, 500)),
+            start + Duration::from_secs(10),
+        )
+        .unwrap();
+    assert_eq!(rate.bytes_per_sec, 1000.0);
+    assert_eq!(rate.packets_per_sec, 10.0);
+    assert_eq!(rate.backlog, 500);
+
+    let rate = poller
+        .poll(
+            &msg(stats(30_000, 300, 0, 0)),
+            start + Duration::from_secs(20),
+        )
+        .unwrap();
+    assert_eq!(rate.bytes_per_sec, 2000.0);
+    assert_eq!(rate.packets_per_sec, 20.0);
+}
+
+#[test]
+fn tc_header_display() {
+    let mut header = TcHeader {
+        family: 0,
+        index: 2,
+        handle: TcHandle::from_parts(1, 0),
+        parent: TcHandle::root(),
+        info: 0,
+    };
+    assert_eq!(header.to_string(), "1: dev 2 root");
+    header.handle = TcHandle::from_parts(0xffff, 0);
+    header.parent = TcHandle::ingress();
+    assert_eq!(header.to_string(), "ffff: dev 2 parent ffff:fff1");
+}
+
+// Rate table sent by `tc qdisc add dev lo root tbf rate 10mbit burst 10kb limit 100kb`
+// (iproute2 6.1)
+#[rustfmt::skip]
+static RTAB_10MBIT: [u32; 256] = [
+    93, 187, 296, 390, 500, 593, 687, 796,
+    890, 1000, 1093, 1187, 1296, 1390, 1500, 1593,
+    1687, 1796, 1890, 2000, 2093, 2187, 2296, 2390,
+    2500, 2593, 2687, 2796, 2890, 3000, 3093, 3187,
+    3296, 3390, 3500, 3593, 3687, 3796, 3890, 4000,
+    4093, 4187, 4296, 4390, 4500, 4593, 4687, 4796,
+    4890, 5000, 5093, 5187, 5296, 5390, 5500, 5593,
+    5687, 5796, 5890, 6000, 6093, 6187, 6296, 6390,
+    6500, 6593, 6687, 6796, 6890, 7000, 7093, 7187,
+    7296, 7390, 7500, 7593, 7687, 7796, 7890, 8000,
+    8093, 8187, 8296, 8390, 8500, 8593, 8687, 8796,
+    8890, 9000, 9093, 9187, 9296, 9390, 9500, 9593,
+    9687, 9796, 9890, 10000, 10093, 10187, 10296, 10390,
+    10500, 10593, 10687, 10796, 10890, 11000, 11093, 11187,
+    11296, 11390, 11500, 11593, 11687, 11796, 11890, 12000,
+    12093, 12187, 12296, 12390, 12500, 12593, 12687, 12796,
+    12890, 13000, 13093, 13187, 13296, 13390, 13500, 13593,
+    13687, 13796, 13890, 14000, 14093, 14187, 14296, 14390,
+    14500, 14593, 14687, 14796, 14890, 15000, 15093, 15187,
+    15296, 15390, 15500, 15593, 15687, 15796, 15890, 16000,
+    16093, 16187, 16296, 16390, 16500, 16593, 16687, 16796,
+    16890, 17000, 17093, 17187, 17296, 17390, 17500, 17593,
+    17687, 17796, 17890, 18000, 18093, 18187, 18296, 18390,
+    18500, 18593, 18687, 18796, 18890, 19000, 19093, 19187,
+    19296, 19390, 19500, 19593, 19687, 19796, 19890, 20000,
+    20093, 20187, 20296, 20390, 20500, 20593, 20687, 20796,
+    20890, 21000, 21093, 21187, 21296, 21390, 21500, 21593,
+    21687, 21796, 21890, 22000, 22093, 22187, 22296, 22390,
+    22500, 22593, 22687, 22796, 22890, 23000, 23093, 23187,
+    23296, 23390, 23500, 23593, 23687, 23796, 23890, 24000,
+    24093, 24187, 24296, 24390, 24500, 24593, 24687, 24796,
+    24890, 25000, 25093, 25187, 25296, 25390, 25500, 25593,
+];
+
+// Rate table sent by `tc qdisc add dev lo root tbf rate 1mbit burst 10kb limit 100kb mpu 64
+// linklayer atm` (iproute2 6.1)
+#[rustfmt::skip]
+static RTAB_1MBIT_ATM: [u32; 256] = [
+    13250, 13250, 13250, 13250, 13250, 13250, 13250, 13250,
+    13250, 13250, 13250, 13250, 19875, 19875, 19875, 19875,
+    19875, 19875, 26500, 26500, 26500, 26500, 26500, 26500,
+    33125, 33125, 33125, 33125, 33125, 33125, 39750, 39750,
+    39750, 39750, 39750, 39750, 46375, 46375, 46375, 46375,
+    46375, 46375, 53000, 53000, 53000, 53000, 53000, 53000,
+    59625, 59625, 59625, 59625, 59625, 59625, 66250, 66250,
+    66250, 66250, 66250, 66250, 72875, 72875, 72875, 72875,
+    72875, 72875, 79500, 79500, 79500, 79500, 79500, 79500,
+    86125, 86125, 86125, 86125, 86125, 86125, 92750, 92750,
+    92750, 92750, 92750, 92750, 99375, 99375, 99375, 99375,
+    99375, 99375, 106000, 106000, 106000, 106000, 106000, 106000,
+    112625, 112625, 112625, 112625, 112625, 112625, 119250, 119250,
+    119250, 119250, 119250, 119250, 125875, 125875, 125875, 125875,
+    125875, 125875, 132500, 132500, 132500, 132500, 132500, 132500,
+    139125, 139125, 139125, 139125, 139125, 139125, 145750, 145750,
+    145750, 145750, 145750, 145750, 152375, 152375, 152375, 152375,
+    152375, 152375, 159000, 159000, 159000, 159000, 159000, 159000,
+    165625, 165625, 165625, 165625, 165625, 165625, 172250, 172250,
+    172250, 172250, 172250, 172250, 178875, 178875, 178875, 178875,
+    178875, 178875, 185500, 185500, 185500, 185500, 185500, 185500,
+    192125, 192125, 192125, 192125, 192125, 192125, 198750, 198750,
+    198750, 198750, 198750, 198750, 205375, 205375, 205375, 205375,
+    205375, 205375, 212000, 212000, 212000, 212000, 212000, 212000,
+    218625, 218625, 218625, 218625, 218625, 218625, 225250, 225250,
+    225250, 225250, 225250, 225250, 231875, 231875, 231875, 231875,
+    231875, 231875, 238500, 238500, 238500, 238500, 238500, 238500,
+    245125, 245125, 245125, 245125, 245125, 245125, 251750, 251750,
+    251750, 251750, 251750, 251750, 258375, 258375, 258375, 258375,
+    258375, 258375, 265000, 265000, 265000, 265000, 265000, 265000,
+    271625, 271625, 271625, 271625, 271625, 271625, 278250, 278250,
+    278250, 278250, 278250, 278250, 284875, 284875, 284875, 284875,
+];
+
+#[test]
+fn tc_rate_table() {
+    let rtab = compute_rate_table(1_250_000, 0, 0, Linklayer::Ethernet, 0);
+    assert_eq!(rtab, RTAB_10MBIT);
+
+    let rtab = compute_rate_table(125_000, 0, 0, Linklayer::Atm, 64);
+    assert_eq!(rtab, RTAB_1MBIT_ATM);
+
+    // `tbf rate 100mbit burst 100kb limit 1mb mtu 9000` uses 64 bytes cells
+    let rtab = compute_rate_table(12_500_000, 9000, 0, Linklayer::Ethernet, 0);
+    assert_eq!(
+        rtab,
+        compute_rate_table(12_500_000, 0, 6, Linklayer::Ethernet, 0)
+    );
+    assert_eq!(&rtab[..3], &[78, 156, 234]);
+    assert_eq!(rtab[100], 8078);
+    assert_eq!(rtab[255], 20468);
+}
+
+#[test]
+fn tc_rate_spec_rate_table() {
+    let mut rate = TcRateSpec {
+        linklayer: TC_LINKLAYER_ATM,
+        mpu: 64,
+        rate: 125_000,
+        ..Default::default()
+    };
+    assert_eq!(rate.rate_table(0), RTAB_1MBIT_ATM.to_vec());
+    assert_eq!(rate.cell_log, 3);
+    assert_eq!(rate.cell_align, -1);
+
+    assert_eq!(Linklayer::from(rate.linklayer), Linklayer::Atm);
+    assert_eq!(u8::from(Linklayer::Ethernet), TC_LINKLAYER_ETHERNET);
+    assert_eq!(Linklayer::from(7), Linklayer::Other(7));
+}
+
+// `tc qdisc add dev eth0 root handle 1: prio bands 3`, with the options cut
+// after the number of bands
+#[rustfmt::skip]
+static QDISC_PRIO_TRUNCATED: [u8; 40] = [
+    0x00, // family
+    0x00, 0x00, 0x00, // padding
+    0x03, 0x00, 0x00, 0x00, // index = 3
+    0x00, 0x00, 0x01, 0x00, // handle = 1:0
+    0xff, 0xff, 0xff, 0xff, // parent = root
+    0x00, 0x00, 0x00, 0x00, // info
+    // TCA_KIND
+    0x09, 0x00, // length = 9
+    0x01, 0x00, // type = 1
+    0x70, 0x72, 0x69, 0x6f, 0x00, // "prio\0"
+    0x00, 0x00, 0x00, // padding
+    // TCA_OPTIONS
+    0x08, 0x00, // length = 8
+    0x02, 0x00, // type = 2
+    0x03, 0x00, 0x00, 0x00, // bands = 3, the priority map is missing
+];
+
+#[test]
+fn parse_truncated_qdisc_options() {
+    let err = TcMessage::<Qdisc>::parse(&TcMessageBuffer::new(&QDISC_PRIO_TRUNCATED)).unwrap_err();
+    match err {
+        DecodeError::Context { ref context, .. } => {
+            assert_eq!(context, "failed to parse tc message NLAs")
+        }
+        ref other => panic!("expected Context, got {:?}", other),
+    }
+    // struct tc_prio_qopt is 20 bytes long
+    assert!(matches!(
+        err.without_context(),
+        DecodeError::BufferTooShort {
+            expected: 20,
+            actual: 4,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn parse_invalid_nla_offset() {
+    // TCA_OPTIONS announces more bytes than the message holds
+    let mut data = QDISC_PRIO_TRUNCATED;
+    data[32] = 0x20;
+    let err = TcMessage::<Qdisc>::parse(&TcMessageBuffer::new(&data)).unwrap_err();
+    match err.without_context() {
+        DecodeError::InvalidNla { offset, error } => {
+            // the offset is relative to the first attribute, after the header
+            assert_eq!(*offset, 12);
+            assert!(matches!(
+                **error,
+                DecodeError::BufferTooShort {
+                    expected: 32,
+                    actual: 8,
+                    ..
+                }
+            ));
+        }
+        other => panic!("expected InvalidNla, got {:?}", other),
+    }
+}
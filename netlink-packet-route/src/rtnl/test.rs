@@ -98,3 +98,80 @@ fn test_attach_to_bridge() {
     });
     assert_eq!(expected, actual);
 }
+
+// Chains use the same tcmsg as filters, only the message type differs. This message was created
+// using `tc chain add dev eth0 parent ffff: chain 1`, with eth0 having index 3.
+#[rustfmt::skip]
+#[test]
+fn test_new_chain() {
+    use crate::{nlas::tc, *};
+    let data = vec![
+        0x2c, 0x00, 0x00, 0x00, // length = 44
+        0x64, 0x00, // message type = 100 = RTM_NEWCHAIN
+        0x05, 0x06, // flags = REQUEST | ACK | EXCL | CREATE
+        0x3d, 0x6e, 0x10, 0x65, // seq id
+        0x00, 0x00, 0x00, 0x00, // pid
+        0x00, // family
+        0x00, 0x00, 0x00, // padding
+        0x03, 0x00, 0x00, 0x00, // interface index = 3
+        0x00, 0x00, 0x00, 0x00, // handle
+        0x00, 0x00, 0xff, 0xff, // parent = ffff:
+        0x00, 0x00, 0x00, 0x00, // info
+        // NLA: chain
+        0x08, 0x00, // length = 8
+        0x0b, 0x00, // type = 11 = TCA_CHAIN
+        0x01, 0x00, 0x00, 0x00, // chain index = 1
+    ];
+    let mut expected = NetlinkMessage::from(RtnlMessage::NewTrafficChain(TcMessage {
+        header: TcHeader {
+            index: 3,
+            parent: TcHandle::from_parts(0xffff, 0),
+            ..Default::default()
+        },
+        nlas: vec![tc::Nla::Chain(1)],
+    }));
+    expected.header.flags = NetlinkFlags::new_create();
+    expected.header.sequence_number = 0x6510_6e3d;
+    expected.finalize();
+
+    let mut buf = vec![0; expected.buffer_len()];
+    expected.serialize(&mut buf);
+    assert_eq!(buf, data);
+
+    let parsed = NetlinkMessage::<RtnlMessage>::deserialize(&data).unwrap();
+    assert_eq!(parsed, expected);
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_deserialize_error() {
+    use crate::*;
+    let data = vec![
+        0x26, 0x00, 0x00, 0x00, // length = 38
+        0x10, 0x00, // message type = 16 = RTM_NEWLINK
+        0x00, 0x00, // flags
+        0x00, 0x00, 0x00, 0x00, // seq id
+        0x00, 0x00, 0x00, 0x00, // pid
+        0x00, // interface family
+        0x00, // padding
+        0x00, 0x00, // device type
+        0x06, 0x00, 0x00, 0x00, // interface index
+        0x00, 0x00, 0x00, 0x00, // device flags
+        0x00, 0x00, 0x00, 0x00, // device change flags
+        // NLA: MTU
+        0x06, 0x00, // length = 6
+        0x04, 0x00, // type = 4 = IFLA_MTU
+        0xdc, 0x05, // truncated value
+        ];
+    let err = NetlinkMessage::<RtnlMessage>::deserialize(&data).unwrap_err();
+    match err {
+        DecodeError::Context { ref context, .. } => {
+            assert_eq!(context, "Failed to parse message with type 16")
+        }
+        ref other => panic!("expected Context, got {:?}", other),
+    }
+    assert!(matches!(
+        err.without_context(),
+        DecodeError::InvalidLength { length: 2, .. }
+    ));
+}
@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: MIT
+
+//! Send requests on a mock socket, which replays responses recorded on a
+//! machine with only the loopback interface, or a veth pair for routes.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use netlink_packet_route::{
+    nlas::link::Nla,
+    LinkMessage,
+    NetlinkFlags,
+    NetlinkHeader,
+    NetlinkMessage,
+    NetlinkPayload,
+    RouteMessage,
+    RtnlMessage,
+};
+use netlink_sys::MockNetlinkSocket;
+
+#[rustfmt::skip]
+static NEW_LINK: [u8; 112] = [
+    0x70, 0x00, 0x00, 0x00, // length = 112
+    0x10, 0x00, // message type = 16 = RTM_NEWLINK
+    0x02, 0x00, // flags = NLM_F_MULTI
+    0x01, 0x00, 0x00, 0x00, // sequence number = 1
+    0x00, 0x00, 0x00, 0x00, // port number = 0
+
+    0x00, // interface family
+    0x00, // reserved
+    0x04, 0x03, // link layer type 772 = loopback
+    0x01, 0x00, 0x00, 0x00, // interface index = 1
+    0x49, 0x00, 0x00, 0x00, // device flags: UP, LOOPBACK, RUNNING, LOWERUP
+    0x00, 0x00, 0x00, 0x00, // reserved 2 (aka device change flag)
+
+    // nlas
+    0x07, 0x00, 0x03, 0x00, 0x6c, 0x6f, 0x00, // device name L=7,T=3,V=lo
+    0x00, // padding
+    0x08, 0x00, 0x0d, 0x00, 0xe8, 0x03, 0x00, 0x00, // TxQueue length L=8,T=13,V=1000
+    0x05, 0x00, 0x10, 0x00, 0x00, // OperState L=5,T=16,V=0 (unknown)
+    0x00, 0x00, 0x00, // padding
+    0x05, 0x00, 0x11, 0x00, 0x00, // Link mode L=5,T=17,V=0
+    0x00, 0x00, 0x00, // padding
+    0x08, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x00, // MTU L=8,T=4,V=65536
+    0x08, 0x00, 0x1b, 0x00, 0x00, 0x00, 0x00, 0x00, // Group L=8,T=27,V=9
+    0x08, 0x00, 0x1e, 0x00, 0x00, 0x00, 0x00, 0x00, // Promiscuity L=8,T=30,V=0
+    0x08, 0x00, 0x1f, 0x00, 0x01, 0x00, 0x00, 0x00, // Number of Tx Queues L=8,T=31,V=1
+    0x08, 0x00, 0x28, 0x00, 0xff, 0xff, 0x00, 0x00, // Maximum GSO segment count L=8,T=40,V=65536
+    0x08, 0x00, 0x29, 0x00, 0x00, 0x00, 0x01, 0x00, // Maximum GSO size L=8,T=41,V=65536
+];
+
+#[rustfmt::skip]
+static DONE: [u8; 20] = [
+    0x14, 0x00, 0x00, 0x00, // length = 20
+    0x03, 0x00, // message type = 3 = NLMSG_DONE
+    0x02, 0x00, // flags = NLM_F_MULTI
+    0x01, 0x00, 0x00, 0x00, // sequence number = 1
+    0x00, 0x00, 0x00, 0x00, // port number = 0
+    0x00, 0x00, 0x00, 0x00, // error code = 0
+];
+
+#[test]
+fn dump_links() {
+    let mut socket = MockNetlinkSocket::new(vec![[&NEW_LINK[..], &DONE[..]].concat()]);
+
+    let mut packet = NetlinkMessage {
+        header: NetlinkHeader::default(),
+        payload: NetlinkPayload::from(RtnlMessage::GetLink(LinkMessage::default())),
+    };
+    packet.header.flags = NetlinkFlags::new_dump();
+    packet.header.sequence_number = 1;
+    packet.finalize();
+    let mut buf = vec![0; packet.buffer_len()];
+    packet.serialize(&mut buf[..]);
+    assert_eq!(socket.send(&buf[..]).unwrap(), buf.len());
+
+    let mut receive_buffer = Vec::with_capacity(4096);
+    let size = socket.recv(&mut receive_buffer).unwrap();
+    assert_eq!(size, NEW_LINK.len() + DONE.len());
+
+    let mut messages = vec![];
+    let mut offset = 0;
+    while offset < size {
+        let message: NetlinkMessage<RtnlMessage> =
+            NetlinkMessage::deserialize(&receive_buffer[offset..]).unwrap();
+        offset += message.header.length as usize;
+        messages.push(message);
+    }
+    assert_eq!(messages.len(), 2);
+
+    match messages[0].payload {
+        NetlinkPayload::InnerMessage(RtnlMessage::NewLink(ref link)) => {
+            assert_eq!(link.header.index, 1);
+            assert!(link.nlas.contains(&Nla::IfName("lo".to_string())));
+        }
+        ref payload => panic!("expected a NewLink message, got {:?}", payload),
+    }
+    assert_eq!(messages[1].payload, NetlinkPayload::Done);
+    assert_eq!(socket.pending_responses(), 0);
+}
+
+// `ip route get 8.8.8.8`, without the RTM_F_LOOKUP_TABLE flag
+#[rustfmt::skip]
+static GET_ROUTE: [u8; 36] = [
+    0x24, 0x00, 0x00, 0x00, // length = 36
+    0x1a, 0x00, // message type = 26 = RTM_GETROUTE
+    0x01, 0x00, // flags = NLM_F_REQUEST
+    0x01, 0x00, 0x00, 0x00, // sequence number = 1
+    0x00, 0x00, 0x00, 0x00, // port number = 0
+
+    0x02, // address family = AF_INET
+    0x20, // destination prefix length = 32
+    0x00, // source prefix length
+    0x00, // tos
+    0x00, // table
+    0x00, // protocol
+    0x00, // scope
+    0x00, // type
+    0x00, 0x00, 0x00, 0x00, // flags
+
+    0x08, 0x00, 0x01, 0x00, 0x08, 0x08, 0x08, 0x08, // RTA_DST L=8,T=1,V=8.8.8.8
+];
+
+// Response to `ip route get 8.8.8.8` with a default route via 10.0.0.254
+#[rustfmt::skip]
+static NEW_ROUTE: [u8; 112] = [
+    0x70, 0x00, 0x00, 0x00, // length = 112
+    0x18, 0x00, // message type = 24 = RTM_NEWROUTE
+    0x00, 0x00, // flags
+    0x01, 0x00, 0x00, 0x00, // sequence number = 1
+    0x00, 0x00, 0x00, 0x00, // port number = 0
+
+    0x02, // address family = AF_INET
+    0x20, // destination prefix length = 32
+    0x00, // source prefix length
+    0x00, // tos
+    0xfe, // table = RT_TABLE_MAIN
+    0x00, // protocol
+    0x00, // scope = RT_SCOPE_UNIVERSE
+    0x01, // type = RTN_UNICAST
+    0x00, 0x02, 0x00, 0x00, // flags = RTM_F_CLONED
+
+    // nlas
+    0x08, 0x00, 0x0f, 0x00, 0xfe, 0x00, 0x00, 0x00, // RTA_TABLE L=8,T=15,V=254
+    0x08, 0x00, 0x01, 0x00, 0x08, 0x08, 0x08, 0x08, // RTA_DST L=8,T=1,V=8.8.8.8
+    0x08, 0x00, 0x04, 0x00, 0x03, 0x00, 0x00, 0x00, // RTA_OIF L=8,T=4,V=3
+    0x08, 0x00, 0x07, 0x00, 0x0a, 0x00, 0x00, 0x01, // RTA_PREFSRC L=8,T=7,V=10.0.0.1
+    0x08, 0x00, 0x05, 0x00, 0x0a, 0x00, 0x00, 0xfe, // RTA_GATEWAY L=8,T=5,V=10.0.0.254
+    0x08, 0x00, 0x19, 0x00, 0x00, 0x00, 0x00, 0x00, // RTA_UID L=8,T=25,V=0
+    0x24, 0x00, 0x0c, 0x00, // RTA_CACHEINFO L=36,T=12
+    0x02, 0x00, 0x00, 0x00, // clntref = 2
+    0x00, 0x00, 0x00, 0x00, // last use
+    0x00, 0x00, 0x00, 0x00, // expires
+    0x00, 0x00, 0x00, 0x00, // error
+    0x00, 0x00, 0x00, 0x00, // used
+    0x00, 0x00, 0x00, 0x00, // id
+    0x00, 0x00, 0x00, 0x00, // ts
+    0x00, 0x00, 0x00, 0x00, // ts age
+];
+
+#[test]
+fn lookup_route() {
+    let mut socket = MockNetlinkSocket::new(vec![NEW_ROUTE.to_vec()]);
+
+    let dst = IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8));
+    let mut packet = RouteMessage::lookup_request(dst, 32);
+    packet.header.sequence_number = 1;
+    let mut buf = vec![0; packet.buffer_len()];
+    packet.serialize(&mut buf[..]);
+    assert_eq!(buf, GET_ROUTE);
+    assert_eq!(socket.send(&buf[..]).unwrap(), buf.len());
+
+    let mut receive_buffer = Vec::with_capacity(4096);
+    let size = socket.recv(&mut receive_buffer).unwrap();
+    assert_eq!(size, NEW_ROUTE.len());
+
+    let message: NetlinkMessage<RtnlMessage> =
+        NetlinkMessage::deserialize(&receive_buffer[..size]).unwrap();
+    match message.payload {
+        NetlinkPayload::InnerMessage(RtnlMessage::NewRoute(ref route)) => {
+            assert_eq!(route.destination_prefix(), Some((dst, 32)));
+            assert_eq!(
+                route.gateway(),
+                Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 254)))
+            );
+            assert_eq!(route.output_interface(), Some(3));
+        }
+        ref payload => panic!("expected a NewRoute message, got {:?}", payload),
+    }
+}
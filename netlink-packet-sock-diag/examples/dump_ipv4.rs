@@ -3,6 +3,7 @@
 use netlink_packet_sock_diag::{
     constants::*,
     inet::{ExtensionFlags, InetRequest, SocketId, StateFlags},
+    NetlinkFlags,
     NetlinkHeader,
     NetlinkMessage,
     NetlinkPayload,
@@ -17,7 +18,7 @@ fn main() {
 
     let mut packet = NetlinkMessage {
         header: NetlinkHeader {
-            flags: NLM_F_REQUEST | NLM_F_DUMP,
+            flags: NetlinkFlags::new_dump(),
             ..Default::default()
         },
         payload: SockDiagMessage::InetRequest(InetRequest {
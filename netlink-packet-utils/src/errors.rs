@@ -31,30 +31,91 @@ impl From<anyhow::Error> for EncodeError {
     }
 }
 
+/// Error returned when parsing a netlink packet fails. The most common failures have their own
+/// variant so that they can be matched on, the others are described by a message.
 #[derive(Debug, Error)]
-#[error("Decode error occurred: {inner}")]
-pub struct DecodeError {
-    inner: anyhow::Error,
+pub enum DecodeError {
+    #[error("invalid {context}: buffer is {actual} bytes long instead of at least {expected}")]
+    BufferTooShort {
+        context: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("invalid {context}: length {length} is not valid")]
+    InvalidLength {
+        context: &'static str,
+        length: usize,
+    },
+
+    #[error("invalid {context} value")]
+    InvalidValue { context: &'static str },
+
+    #[error("invalid NLA at offset {offset}: {error}")]
+    InvalidNla {
+        offset: usize,
+        #[source]
+        error: Box<DecodeError>,
+    },
+
+    /// Error passed up through [`anyhow::Context`], with the message it was given
+    #[error("{context}: {source}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<DecodeError>,
+    },
+
+    #[error("invalid UTF-8 string: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+
+    #[error("Decode error occurred: {0}")]
+    Other(String),
+}
+
+impl From<std::string::FromUtf8Error> for DecodeError {
+    fn from(err: std::string::FromUtf8Error) -> Self {
+        DecodeError::Utf8(err.utf8_error())
+    }
 }
 
 impl From<&'static str> for DecodeError {
     fn from(msg: &'static str) -> Self {
-        DecodeError {
-            inner: anyhow!(msg),
-        }
+        DecodeError::Other(msg.to_string())
     }
 }
 
 impl From<String> for DecodeError {
     fn from(msg: String) -> Self {
-        DecodeError {
-            inner: anyhow!(msg),
-        }
+        DecodeError::Other(msg)
     }
 }
 
+/// A [`DecodeError`] that went through [`anyhow::Context`] becomes a [`DecodeError::Context`]
+/// wrapping it, so that the original variant is kept. Other errors become
+/// [`DecodeError::Other`], with the whole chain of contexts in the message.
 impl From<anyhow::Error> for DecodeError {
-    fn from(inner: anyhow::Error) -> DecodeError {
-        DecodeError { inner }
+    fn from(err: anyhow::Error) -> DecodeError {
+        // The first error of the chain is the context, unless there is none
+        let has_context = !matches!(err.chain().next(), Some(error) if error.is::<DecodeError>());
+        let context = err.to_string();
+        match err.downcast::<DecodeError>() {
+            Ok(source) if has_context => DecodeError::Context {
+                context,
+                source: Box::new(source),
+            },
+            Ok(err) => err,
+            Err(err) => DecodeError::Other(format!("{:#}", err)),
+        }
+    }
+}
+
+impl DecodeError {
+    /// Error that caused this one, without the [`DecodeError::Context`] it was wrapped in
+    pub fn without_context(&self) -> &DecodeError {
+        match self {
+            DecodeError::Context { source, .. } => source.without_context(),
+            _ => self,
+        }
     }
 }
@@ -15,3 +15,6 @@ pub mod traits;
 pub use self::traits::*;
 
 pub mod nla;
+
+#[cfg(feature = "serde")]
+pub mod serde_base64;
@@ -2,7 +2,6 @@
 
 use core::ops::Range;
 
-use anyhow::Context;
 use byteorder::{ByteOrder, NativeEndian};
 
 use crate::{
@@ -41,32 +40,29 @@ impl<T: AsRef<[u8]>> NlaBuffer<T> {
 
     pub fn new_checked(buffer: T) -> Result<NlaBuffer<T>, DecodeError> {
         let buffer = Self::new(buffer);
-        buffer.check_buffer_length().context("invalid NLA buffer")?;
+        buffer.check_buffer_length()?;
         Ok(buffer)
     }
 
     pub fn check_buffer_length(&self) -> Result<(), DecodeError> {
         let len = self.buffer.as_ref().len();
         if len < TYPE.end {
-            Err(format!(
-                "buffer has length {}, but an NLA header is {} bytes",
-                len, TYPE.end
-            )
-            .into())
+            Err(DecodeError::BufferTooShort {
+                context: "NLA header",
+                expected: TYPE.end,
+                actual: len,
+            })
         } else if len < self.length() as usize {
-            Err(format!(
-                "buffer has length: {}, but the NLA is {} bytes",
-                len,
-                self.length()
-            )
-            .into())
+            Err(DecodeError::BufferTooShort {
+                context: "NLA",
+                expected: self.length() as usize,
+                actual: len,
+            })
         } else if (self.length() as usize) < TYPE.end {
-            Err(format!(
-                "NLA has invalid length: {} (should be at least {} bytes",
-                self.length(),
-                TYPE.end,
-            )
-            .into())
+            Err(DecodeError::InvalidLength {
+                context: "NLA",
+                length: self.length() as usize,
+            })
         } else {
             Ok(())
         }
@@ -163,8 +159,10 @@ impl<'buffer, T: AsRef<[u8]> + AsMut<[u8]> + ?Sized> NlaBuffer<&'buffer mut T> {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DefaultNla {
     kind: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_base64"))]
     value: Vec<u8>,
 }
 
@@ -199,6 +197,55 @@ impl<'buffer, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'buffer T>> for Defa
     }
 }
 
+/// An NLA that borrows its value instead of owning it like [`DefaultNla`], for instance to copy an
+/// attribute from a received message into a new message without cloning its value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ZeroCopyNla<'a> {
+    kind: u16,
+    value: &'a [u8],
+}
+
+impl<'a> ZeroCopyNla<'a> {
+    /// `kind` can include the `NLA_F_NESTED` and `NLA_F_NET_BYTEORDER` flags
+    pub fn new(kind: u16, value: &'a [u8]) -> Self {
+        ZeroCopyNla { kind, value }
+    }
+
+    /// Borrow the value of the NLA in `buf`, keeping its flags
+    pub fn from_buffer<T: AsRef<[u8]> + ?Sized>(buf: &NlaBuffer<&'a T>) -> Self {
+        let mut kind = buf.kind();
+
+        if buf.network_byte_order_flag() {
+            kind |= NLA_F_NET_BYTEORDER;
+        }
+
+        if buf.nested_flag() {
+            kind |= NLA_F_NESTED;
+        }
+
+        ZeroCopyNla {
+            kind,
+            value: &buf.buffer.as_ref()[VALUE(buf.value_length())],
+        }
+    }
+
+    pub fn value(&self) -> &'a [u8] {
+        self.value
+    }
+}
+
+impl<'a> Nla for ZeroCopyNla<'a> {
+    fn value_len(&self) -> usize {
+        self.value.buffer_len()
+    }
+    fn kind(&self) -> u16 {
+        self.kind
+    }
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.value.emit(buffer);
+    }
+}
+
 pub trait Nla {
     fn value_len(&self) -> usize;
     fn kind(&self) -> u16;
@@ -287,6 +334,12 @@ impl<T> NlasIterator<T> {
             buffer,
         }
     }
+
+    /// Return the offset in the buffer of the next nla. It is the end of the buffer once an
+    /// invalid nla has been found.
+    pub fn offset(&self) -> usize {
+        self.position
+    }
 }
 
 impl<'buffer, T: AsRef<[u8]> + ?Sized + 'buffer> Iterator for NlasIterator<&'buffer T> {
@@ -306,14 +359,20 @@ impl<'buffer, T: AsRef<[u8]> + ?Sized + 'buffer> Iterator for NlasIterator<&'buf
 
         match NlaBuffer::new_checked(&self.buffer.as_ref()[self.position..]) {
             Ok(nla_buffer) => {
-                self.position += nla_buffer.length() as usize;
+                // skip the padding right away, so that `offset()` points to the next nla
+                let end = self.position + ((nla_buffer.length() as usize + 3) & !3);
+                self.position = std::cmp::min(end, self.buffer.as_ref().len());
                 Some(Ok(nla_buffer))
             }
             Err(e) => {
+                let offset = self.position;
                 // Make sure next time we call `next()`, we return None. We don't try to continue
                 // iterating after we failed to return a buffer.
                 self.position = self.buffer.as_ref().len();
-                Some(Err(e))
+                Some(Err(DecodeError::InvalidNla {
+                    offset,
+                    error: Box::new(e),
+                }))
             }
         }
     }
@@ -347,4 +406,82 @@ mod tests {
         assert_eq!([buffer_is_net, buffer_is_nest], [attr_is_net, attr_is_nest]);
         assert_eq!([attr_is_net, attr_is_nest], [emit_is_net, emit_is_nest]);
     }
+
+    #[test]
+    fn truncated_buffer() {
+        // the NLA header says 8 bytes but only 6 are available
+        static TRUNCATED: &[u8] = &[0x08, 0x00, 0x06, 0x00, 0x00, 0x00];
+        match NlaBuffer::new_checked(TRUNCATED) {
+            Err(DecodeError::BufferTooShort {
+                expected, actual, ..
+            }) => assert_eq!((expected, actual), (8, 6)),
+            other => panic!("expected BufferTooShort, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn iterator_error_offset() {
+        #[rustfmt::skip]
+        static NLAS: &[u8] = &[
+            0x05, 0x00, 0x01, 0x00, // length = 5, type = 1
+            0x2a, 0x00, 0x00, 0x00, // value = 42 + 3 bytes of padding
+            0x08, 0x00, 0x02, 0x00, // length = 8, type = 2
+            0x2a, 0x00, // truncated value
+        ];
+        let mut nlas = NlasIterator::new(NLAS);
+        assert_eq!(nlas.offset(), 0);
+        assert_eq!(nlas.next().unwrap().unwrap().kind(), 1);
+        assert_eq!(nlas.offset(), 8);
+
+        match nlas.next() {
+            Some(Err(DecodeError::InvalidNla { offset, error })) => {
+                assert_eq!(offset, 8);
+                assert!(matches!(
+                    *error,
+                    DecodeError::BufferTooShort {
+                        expected: 8,
+                        actual: 6,
+                        ..
+                    }
+                ));
+            }
+            other => panic!("expected InvalidNla, got {:?}", other),
+        }
+        assert_eq!(nlas.offset(), NLAS.len());
+        assert!(nlas.next().is_none());
+    }
+
+    #[test]
+    fn invalid_length() {
+        static TOO_SMALL: &[u8] = &[0x02, 0x00, 0x06, 0x00];
+        assert!(matches!(
+            NlaBuffer::new_checked(TOO_SMALL),
+            Err(DecodeError::InvalidLength { length: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn zero_copy() {
+        let mut attribute = vec![0; 4 + 4096];
+        {
+            let mut buffer = NlaBuffer::new(&mut attribute[..]);
+            buffer.set_kind(1);
+            buffer.set_nested_flag();
+            buffer.set_length(4 + 4096);
+        }
+        for (i, byte) in attribute[4..].iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        let buffer = NlaBuffer::new_checked(&attribute[..]).unwrap();
+        let nla = ZeroCopyNla::from_buffer(&buffer);
+        assert_eq!(nla.kind(), 1 | NLA_F_NESTED);
+        assert_eq!(nla.value().as_ptr(), attribute[4..].as_ptr());
+        assert_eq!(nla.value_len(), 4096);
+
+        let mut emitted = vec![0; nla.buffer_len()];
+        nla.emit(&mut emitted);
+        assert_eq!(emitted, attribute);
+        assert_eq!(nla, ZeroCopyNla::new(1 | NLA_F_NESTED, &attribute[4..]));
+    }
 }
@@ -5,14 +5,16 @@ use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr},
 };
 
-use anyhow::Context;
 use byteorder::{BigEndian, ByteOrder, NativeEndian};
 
 use crate::DecodeError;
 
 pub fn parse_mac(payload: &[u8]) -> Result<[u8; 6], DecodeError> {
     if payload.len() != 6 {
-        return Err(format!("invalid MAC address: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "MAC address",
+            length: payload.len(),
+        });
     }
     let mut address: [u8; 6] = [0; 6];
     for (i, byte) in payload.iter().enumerate() {
@@ -21,9 +23,24 @@ pub fn parse_mac(payload: &[u8]) -> Result<[u8; 6], DecodeError> {
     Ok(address)
 }
 
+pub fn parse_ipv4(payload: &[u8]) -> Result<Ipv4Addr, DecodeError> {
+    if payload.len() != 4 {
+        return Err(DecodeError::InvalidLength {
+            context: "IPv4 address",
+            length: payload.len(),
+        });
+    }
+    Ok(Ipv4Addr::new(
+        payload[0], payload[1], payload[2], payload[3],
+    ))
+}
+
 pub fn parse_ipv6(payload: &[u8]) -> Result<[u8; 16], DecodeError> {
     if payload.len() != 16 {
-        return Err(format!("invalid IPv6 address: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "IPv6 address",
+            length: payload.len(),
+        });
     }
     let mut address: [u8; 16] = [0; 16];
     for (i, byte) in payload.iter().enumerate() {
@@ -54,7 +71,10 @@ pub fn parse_ip(payload: &[u8]) -> Result<IpAddr, DecodeError> {
             payload[15],
         ])
         .into()),
-        _ => Err(format!("invalid IPv6 address: {:?}", payload).into()),
+        length => Err(DecodeError::InvalidLength {
+            context: "IP address",
+            length,
+        }),
     }
 }
 
@@ -68,55 +88,96 @@ pub fn parse_string(payload: &[u8]) -> Result<String, DecodeError> {
     } else {
         &payload[..payload.len()]
     };
-    let s = String::from_utf8(slice.to_vec()).context("invalid string")?;
+    let s = String::from_utf8(slice.to_vec())?;
     Ok(s)
 }
 
 pub fn parse_u8(payload: &[u8]) -> Result<u8, DecodeError> {
     if payload.len() != 1 {
-        return Err(format!("invalid u8: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "u8",
+            length: payload.len(),
+        });
     }
     Ok(payload[0])
 }
 
 pub fn parse_u32(payload: &[u8]) -> Result<u32, DecodeError> {
     if payload.len() != size_of::<u32>() {
-        return Err(format!("invalid u32: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "u32",
+            length: payload.len(),
+        });
     }
     Ok(NativeEndian::read_u32(payload))
 }
 
 pub fn parse_u64(payload: &[u8]) -> Result<u64, DecodeError> {
     if payload.len() != size_of::<u64>() {
-        return Err(format!("invalid u64: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "u64",
+            length: payload.len(),
+        });
     }
     Ok(NativeEndian::read_u64(payload))
 }
 
 pub fn parse_u128(payload: &[u8]) -> Result<u128, DecodeError> {
     if payload.len() != size_of::<u128>() {
-        return Err(format!("invalid u128: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "u128",
+            length: payload.len(),
+        });
     }
     Ok(NativeEndian::read_u128(payload))
 }
 
 pub fn parse_u16(payload: &[u8]) -> Result<u16, DecodeError> {
     if payload.len() != size_of::<u16>() {
-        return Err(format!("invalid u16: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "u16",
+            length: payload.len(),
+        });
     }
     Ok(NativeEndian::read_u16(payload))
 }
 
 pub fn parse_i32(payload: &[u8]) -> Result<i32, DecodeError> {
     if payload.len() != 4 {
-        return Err(format!("invalid u32: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "i32",
+            length: payload.len(),
+        });
     }
     Ok(NativeEndian::read_i32(payload))
 }
 
+pub fn parse_i64(payload: &[u8]) -> Result<i64, DecodeError> {
+    if payload.len() != size_of::<i64>() {
+        return Err(DecodeError::InvalidLength {
+            context: "i64",
+            length: payload.len(),
+        });
+    }
+    Ok(NativeEndian::read_i64(payload))
+}
+
 pub fn parse_u16_be(payload: &[u8]) -> Result<u16, DecodeError> {
     if payload.len() != size_of::<u16>() {
-        return Err(format!("invalid u16: {:?}", payload).into());
+        return Err(DecodeError::InvalidLength {
+            context: "u16",
+            length: payload.len(),
+        });
     }
     Ok(BigEndian::read_u16(payload))
 }
+
+pub fn parse_u32_be(payload: &[u8]) -> Result<u32, DecodeError> {
+    if payload.len() != size_of::<u32>() {
+        return Err(DecodeError::InvalidLength {
+            context: "u32",
+            length: payload.len(),
+        });
+    }
+    Ok(BigEndian::read_u32(payload))
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: MIT
+
+//! Serialize raw bytes as a base64 string, for use with
+//! `#[serde(with = "netlink_packet_utils::serde_base64")]`.
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&base64::encode(bytes))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    base64::decode(encoded).map_err(D::Error::custom)
+}
@@ -18,6 +18,18 @@ pub trait Emitable {
     fn emit(&self, buffer: &mut [u8]);
 }
 
+/// Raw bytes are emitted as they are, which allows emitting data borrowed from another buffer
+/// without copying it to an intermediate `Vec<u8>` first.
+impl Emitable for &[u8] {
+    fn buffer_len(&self) -> usize {
+        self.len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[..self.len()].copy_from_slice(self);
+    }
+}
+
 /// A `Parseable` type can be used to deserialize data from the type `T` for which it is
 /// implemented.
 pub trait Parseable<T>
@@ -1,6 +1,6 @@
 use futures::StreamExt;
 use genetlink::new_connection;
-use netlink_packet_core::{NetlinkMessage, NetlinkPayload, NLM_F_DUMP, NLM_F_REQUEST};
+use netlink_packet_core::{NetlinkFlags, NetlinkMessage, NetlinkPayload};
 use netlink_packet_generic::GenlMessage;
 use netlink_packet_wireguard::{
     nlas::{WgAllowedIpAttrs, WgDeviceAttrs, WgPeerAttrs},
@@ -27,7 +27,7 @@ async fn main() {
         nlas: vec![WgDeviceAttrs::IfName(argv[1].clone())],
     });
     let mut nlmsg = NetlinkMessage::from(genlmsg);
-    nlmsg.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+    nlmsg.header.flags = NetlinkFlags::new_dump();
 
     let mut res = handle.request(nlmsg).await.unwrap();
 
@@ -99,7 +99,7 @@ fn parse_nlas(buf: &[u8]) -> Result<Vec<WgDeviceAttrs>, DecodeError> {
 #[cfg(test)]
 
 mod test {
-    use netlink_packet_core::{NetlinkMessage, NLM_F_ACK, NLM_F_REQUEST};
+    use netlink_packet_core::{NetlinkFlags, NetlinkMessage};
     use netlink_packet_generic::GenlMessage;
 
     use crate::nlas::{WgAllowedIp, WgAllowedIpAttrs, WgPeer, WgPeerAttrs};
@@ -152,7 +152,7 @@ mod test {
             ],
         });
         let mut nlmsg = NetlinkMessage::from(genlmsg);
-        nlmsg.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+        nlmsg.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK;
 
         nlmsg.finalize();
         let mut buf = [0; 4096];
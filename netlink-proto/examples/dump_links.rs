@@ -1,14 +1,7 @@
 // SPDX-License-Identifier: MIT
 
 use futures::StreamExt;
-use netlink_packet_route::{
-    LinkMessage,
-    NetlinkHeader,
-    NetlinkMessage,
-    RtnlMessage,
-    NLM_F_DUMP,
-    NLM_F_REQUEST,
-};
+use netlink_packet_route::{LinkMessage, NetlinkFlags, NetlinkHeader, NetlinkMessage, RtnlMessage};
 use netlink_proto::{
     new_connection,
     sys::{protocols::NETLINK_ROUTE, SocketAddr},
@@ -27,7 +20,7 @@ async fn main() -> Result<(), String> {
     // Create the netlink message that requests the links to be dumped
     let request = NetlinkMessage {
         header: NetlinkHeader {
-            flags: NLM_F_DUMP | NLM_F_REQUEST,
+            flags: NetlinkFlags::new_dump(),
             ..Default::default()
         },
         payload: RtnlMessage::GetLink(LinkMessage::default()).into(),
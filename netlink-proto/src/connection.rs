@@ -69,13 +69,25 @@ where
         protocol: isize,
     ) -> io::Result<Self> {
         let socket = S::new(protocol)?;
-        Ok(Connection {
+        Ok(Connection::from_socket(
+            requests_rx,
+            unsolicited_messages_tx,
+            socket,
+        ))
+    }
+
+    pub(crate) fn from_socket(
+        requests_rx: UnboundedReceiver<Request<T>>,
+        unsolicited_messages_tx: UnboundedSender<(NetlinkMessage<T>, SocketAddr)>,
+        socket: S,
+    ) -> Self {
+        Connection {
             socket: NetlinkFramed::new(socket),
             protocol: Protocol::new(),
             requests_rx: Some(requests_rx),
             unsolicited_messages_tx: Some(unsolicited_messages_tx),
             socket_closed: false,
-        })
+        }
     }
 
     pub fn socket_mut(&mut self) -> &mut S {
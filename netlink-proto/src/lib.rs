@@ -17,11 +17,10 @@
 //! use futures::stream::StreamExt;
 //! use netlink_packet_audit::{
 //!     AuditMessage,
+//!     NetlinkFlags,
 //!     NetlinkMessage,
 //!     NetlinkPayload,
 //!     StatusMessage,
-//!     NLM_F_ACK,
-//!     NLM_F_REQUEST,
 //! };
 //! use std::process;
 //!
@@ -65,7 +64,7 @@
 //!         status.mask = AUDIT_STATUS_ENABLED | AUDIT_STATUS_PID;
 //!         let payload = AuditMessage::SetStatus(status);
 //!         let mut nl_msg = NetlinkMessage::from(payload);
-//!         nl_msg.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+//!         nl_msg.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK;
 //!
 //!         // We'll send unicast messages to the kernel.
 //!         let kernel_unicast: SocketAddr = SocketAddr::new(0, 0);
@@ -114,11 +113,10 @@
 //!
 //! use netlink_packet_route::{
 //!     LinkMessage,
+//!     NetlinkFlags,
 //!     NetlinkHeader,
 //!     NetlinkMessage,
 //!     RtnlMessage,
-//!     NLM_F_DUMP,
-//!     NLM_F_REQUEST,
 //! };
 //!
 //! use netlink_proto::{
@@ -140,7 +138,7 @@
 //!     let msg = NetlinkMessage {
 //!         header: NetlinkHeader {
 //!             sequence_number: 1,
-//!             flags: NLM_F_DUMP | NLM_F_REQUEST,
+//!             flags: NetlinkFlags::new_dump(),
 //!             ..Default::default()
 //!         },
 //!         payload: RtnlMessage::GetLink(LinkMessage::default()).into(),
@@ -194,7 +192,7 @@ use std::{fmt::Debug, io};
 pub use netlink_packet_core as packet;
 
 pub mod sys {
-    pub use netlink_sys::{protocols, AsyncSocket, AsyncSocketExt, SocketAddr};
+    pub use netlink_sys::{protocols, AsyncSocket, AsyncSocketExt, MockNetlinkSocket, SocketAddr};
 
     #[cfg(feature = "tokio_socket")]
     pub use netlink_sys::TokioSocket;
@@ -273,3 +271,27 @@ where
         messages_rx,
     ))
 }
+
+/// Variant of [`new_connection`] that uses an existing socket, for instance a
+/// [`sys::MockNetlinkSocket`] to test code that uses the connection without
+/// talking to the kernel.
+#[allow(clippy::type_complexity)]
+pub fn new_connection_from_socket<T, S>(
+    socket: S,
+) -> (
+    Connection<T, S>,
+    ConnectionHandle<T>,
+    UnboundedReceiver<(packet::NetlinkMessage<T>, sys::SocketAddr)>,
+)
+where
+    T: Debug + packet::NetlinkSerializable + packet::NetlinkDeserializable + Unpin,
+    S: sys::AsyncSocket,
+{
+    let (requests_tx, requests_rx) = unbounded::<Request<T>>();
+    let (messages_tx, messages_rx) = unbounded::<(packet::NetlinkMessage<T>, sys::SocketAddr)>();
+    (
+        Connection::from_socket(requests_rx, messages_tx, socket),
+        ConnectionHandle::new(requests_tx),
+        messages_rx,
+    )
+}
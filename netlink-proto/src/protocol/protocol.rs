@@ -6,8 +6,8 @@ use std::{
 };
 
 use netlink_packet_core::{
-    constants::*,
     NetlinkDeserializable,
+    NetlinkFlags,
     NetlinkMessage,
     NetlinkPayload,
     NetlinkSerializable,
@@ -102,7 +102,7 @@ where
         // multipart flag and we were not expecting an Ack
         let done = match message.payload {
             NetlinkPayload::InnerMessage(_)
-                if message.header.flags & NLM_F_MULTIPART == NLM_F_MULTIPART =>
+                if message.header.flags.contains(NetlinkFlags::MULTI) =>
             {
                 false
             }
@@ -149,11 +149,8 @@ where
         //  - when the request has the NLM_F_REQUEST flag
         //  - when the request has the NLM_F_ACK flag
         //  - when the request has the NLM_F_ECHO flag
-        let expecting_ack = flags & NLM_F_ACK == NLM_F_ACK;
-        if flags & NLM_F_REQUEST == NLM_F_REQUEST
-            || flags & NLM_F_ECHO == NLM_F_ECHO
-            || expecting_ack
-        {
+        let expecting_ack = flags.contains(NetlinkFlags::ACK);
+        if flags.intersects(NetlinkFlags::REQUEST | NetlinkFlags::ECHO) || expecting_ack {
             self.pending_requests.insert(
                 request_id,
                 PendingRequest {
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: MIT
+
+//! Run a connection on a mock socket, which replays a dump of the links
+//! recorded on a machine with only the loopback interface.
+
+use futures::{
+    executor::block_on,
+    future::{self, Either},
+    StreamExt,
+};
+use netlink_packet_route::{
+    nlas::link::Nla,
+    LinkMessage,
+    NetlinkFlags,
+    NetlinkHeader,
+    NetlinkMessage,
+    NetlinkPayload,
+    RtnlMessage,
+};
+use netlink_proto::{
+    new_connection_from_socket,
+    sys::{MockNetlinkSocket, SocketAddr},
+};
+
+#[rustfmt::skip]
+static NEW_LINK: [u8; 112] = [
+    0x70, 0x00, 0x00, 0x00, // length = 112
+    0x10, 0x00, // message type = 16 = RTM_NEWLINK
+    0x02, 0x00, // flags = NLM_F_MULTI
+    0x01, 0x00, 0x00, 0x00, // sequence number = 1
+    0x00, 0x00, 0x00, 0x00, // port number = 0
+
+    0x00, // interface family
+    0x00, // reserved
+    0x04, 0x03, // link layer type 772 = loopback
+    0x01, 0x00, 0x00, 0x00, // interface index = 1
+    0x49, 0x00, 0x00, 0x00, // device flags: UP, LOOPBACK, RUNNING, LOWERUP
+    0x00, 0x00, 0x00, 0x00, // reserved 2 (aka device change flag)
+
+    // nlas
+    0x07, 0x00, 0x03, 0x00, 0x6c, 0x6f, 0x00, // device name L=7,T=3,V=lo
+    0x00, // padding
+    0x08, 0x00, 0x0d, 0x00, 0xe8, 0x03, 0x00, 0x00, // TxQueue length L=8,T=13,V=1000
+    0x05, 0x00, 0x10, 0x00, 0x00, // OperState L=5,T=16,V=0 (unknown)
+    0x00, 0x00, 0x00, // padding
+    0x05, 0x00, 0x11, 0x00, 0x00, // Link mode L=5,T=17,V=0
+    0x00, 0x00, 0x00, // padding
+    0x08, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x00, // MTU L=8,T=4,V=65536
+    0x08, 0x00, 0x1b, 0x00, 0x00, 0x00, 0x00, 0x00, // Group L=8,T=27,V=9
+    0x08, 0x00, 0x1e, 0x00, 0x00, 0x00, 0x00, 0x00, // Promiscuity L=8,T=30,V=0
+    0x08, 0x00, 0x1f, 0x00, 0x01, 0x00, 0x00, 0x00, // Number of Tx Queues L=8,T=31,V=1
+    0x08, 0x00, 0x28, 0x00, 0xff, 0xff, 0x00, 0x00, // Maximum GSO segment count L=8,T=40,V=65536
+    0x08, 0x00, 0x29, 0x00, 0x00, 0x00, 0x01, 0x00, // Maximum GSO size L=8,T=41,V=65536
+];
+
+#[rustfmt::skip]
+static DONE: [u8; 20] = [
+    0x14, 0x00, 0x00, 0x00, // length = 20
+    0x03, 0x00, // message type = 3 = NLMSG_DONE
+    0x02, 0x00, // flags = NLM_F_MULTI
+    0x01, 0x00, 0x00, 0x00, // sequence number = 1
+    0x00, 0x00, 0x00, 0x00, // port number = 0
+    0x00, 0x00, 0x00, 0x00, // error code = 0
+];
+
+#[test]
+fn dump_links() {
+    // the connection uses 1 as the sequence number of its first request,
+    // like the recorded response
+    let socket = MockNetlinkSocket::new(vec![[&NEW_LINK[..], &DONE[..]].concat()]);
+    let (connection, mut handle, _) = new_connection_from_socket::<RtnlMessage, _>(socket);
+
+    let mut request = NetlinkMessage {
+        header: NetlinkHeader::default(),
+        payload: NetlinkPayload::from(RtnlMessage::GetLink(LinkMessage::default())),
+    };
+    request.header.flags = NetlinkFlags::new_dump();
+    let mut responses = handle.request(request, SocketAddr::new(0, 0)).unwrap();
+    let links = async {
+        let mut links = vec![];
+        while let Some(message) = responses.next().await {
+            if let NetlinkPayload::InnerMessage(RtnlMessage::NewLink(link)) = message.payload {
+                links.push(link);
+            }
+        }
+        links
+    };
+
+    // the connection runs until its handle is dropped, so it only completes
+    // if something went wrong
+    let links = match block_on(future::select(connection, Box::pin(links))) {
+        Either::Left(((), _)) => panic!("the connection shut down"),
+        Either::Right((links, _)) => links,
+    };
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].header.index, 1);
+    assert!(links[0].nlas.contains(&Nla::IfName("lo".to_string())));
+}
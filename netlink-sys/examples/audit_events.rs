@@ -18,10 +18,9 @@ use std::process;
 use netlink_packet_audit::{
     AuditMessage,
     NetlinkBuffer,
+    NetlinkFlags,
     NetlinkMessage,
     StatusMessage,
-    NLM_F_ACK,
-    NLM_F_REQUEST,
 };
 
 use netlink_sys::{protocols::NETLINK_AUDIT, Socket, SocketAddr};
@@ -39,7 +38,7 @@ fn main() {
     status.mask = AUDIT_STATUS_ENABLED | AUDIT_STATUS_PID;
     let payload = AuditMessage::SetStatus(status);
     let mut nl_msg = NetlinkMessage::from(payload);
-    nl_msg.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+    nl_msg.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK;
     nl_msg.finalize();
 
     let mut buf = vec![0; 1024 * 8];
@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::Result;
+
+use crate::{Socket, SocketAddr};
+
+/// Open a netlink socket and configure it before it is bound.
+///
+/// Some options only take effect if they are set early: on kernels older than 5.2,
+/// `NETLINK_EXT_ACK` must be enabled before the socket is bound, otherwise the error messages
+/// it receives do not carry extended acknowledgement attributes.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use netlink_sys::{protocols::NETLINK_ROUTE, NetlinkSocketBuilder};
+///
+/// let socket = NetlinkSocketBuilder::new(NETLINK_ROUTE)
+///     .ext_ack(true)
+///     .get_strict_chk(true)
+///     .recv_buffer_size(1024 * 1024)
+///     .pid_autoassign()
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct NetlinkSocketBuilder {
+    protocol: isize,
+    ext_ack: Option<bool>,
+    get_strict_chk: Option<bool>,
+    recv_buffer_size: Option<usize>,
+    send_buffer_size: Option<usize>,
+    address: Option<SocketAddr>,
+}
+
+impl NetlinkSocketBuilder {
+    /// Start building a socket for the given netlink subsystem. `protocol` must be one of the
+    /// [`netlink_sys::protocols`][protos] constants.
+    ///
+    /// [protos]: crate::protocols
+    pub fn new(protocol: isize) -> Self {
+        NetlinkSocketBuilder {
+            protocol,
+            ext_ack: None,
+            get_strict_chk: None,
+            recv_buffer_size: None,
+            send_buffer_size: None,
+            address: None,
+        }
+    }
+
+    /// Set `NETLINK_EXT_ACK` (see [`Socket::set_ext_ack`])
+    pub fn ext_ack(mut self, value: bool) -> Self {
+        self.ext_ack = Some(value);
+        self
+    }
+
+    /// Set `NETLINK_GET_STRICT_CHK` (see [`Socket::set_get_strict_chk`])
+    pub fn get_strict_chk(mut self, value: bool) -> Self {
+        self.get_strict_chk = Some(value);
+        self
+    }
+
+    /// Set the size of the receive buffer (see [`Socket::set_recv_buffer_size`])
+    pub fn recv_buffer_size(mut self, size: usize) -> Self {
+        self.recv_buffer_size = Some(size);
+        self
+    }
+
+    /// Set the size of the send buffer (see [`Socket::set_send_buffer_size`])
+    pub fn send_buffer_size(mut self, size: usize) -> Self {
+        self.send_buffer_size = Some(size);
+        self
+    }
+
+    /// Bind the socket to the given address once it is configured
+    pub fn bind(mut self, address: SocketAddr) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Bind the socket to a port number assigned by the kernel once it is configured. The
+    /// assigned address can then be retrieved with [`Socket::get_address`].
+    pub fn pid_autoassign(self) -> Self {
+        self.bind(SocketAddr::new(0, 0))
+    }
+
+    /// Open the socket, set the requested options and bind it if an address was given.
+    pub fn build(&self) -> Result<Socket> {
+        let mut socket = Socket::new(self.protocol)?;
+        if let Some(value) = self.ext_ack {
+            socket.set_ext_ack(value)?;
+        }
+        if let Some(value) = self.get_strict_chk {
+            socket.set_get_strict_chk(value)?;
+        }
+        if let Some(size) = self.recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+        if let Some(size) = self.send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+        if let Some(ref address) = self.address {
+            socket.bind(address)?;
+        }
+        Ok(socket)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::protocols::NETLINK_ROUTE;
+
+    #[test]
+    fn build_default() {
+        let socket = NetlinkSocketBuilder::new(NETLINK_ROUTE).build().unwrap();
+        assert!(!socket.get_ext_ack().unwrap());
+        assert!(!socket.get_get_strict_chk().unwrap());
+    }
+
+    #[test]
+    fn build_with_options() {
+        let socket = NetlinkSocketBuilder::new(NETLINK_ROUTE)
+            .ext_ack(true)
+            .get_strict_chk(true)
+            .recv_buffer_size(65536)
+            .send_buffer_size(65536)
+            .pid_autoassign()
+            .build()
+            .unwrap();
+
+        assert!(socket.get_ext_ack().unwrap());
+        assert!(socket.get_get_strict_chk().unwrap());
+        assert!(socket.get_recv_buffer_size().unwrap() >= 65536);
+        assert!(socket.get_send_buffer_size().unwrap() >= 65536);
+
+        let mut address = SocketAddr::new(0, 0);
+        socket.get_address(&mut address).unwrap();
+        assert!(address.port_number() != 0);
+    }
+}
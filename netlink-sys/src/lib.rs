@@ -33,12 +33,18 @@ pub use self::socket::Socket;
 mod addr;
 pub use self::addr::SocketAddr;
 
+mod builder;
+pub use self::builder::NetlinkSocketBuilder;
+
 mod async_socket;
 pub use self::async_socket::AsyncSocket;
 
 pub mod async_socket_ext;
 pub use self::async_socket_ext::AsyncSocketExt;
 
+mod mock;
+pub use self::mock::MockNetlinkSocket;
+
 #[cfg(feature = "tokio_socket")]
 mod tokio;
 #[cfg(feature = "tokio_socket")]
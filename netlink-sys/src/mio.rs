@@ -44,7 +44,7 @@ mod tests {
         // ```
         // let mut neighbour_dump_request = NetlinkMessage {
         //     header: NetlinkHeader {
-        //         flags: NLM_F_DUMP | NLM_F_REQUEST,
+        //         flags: NetlinkFlags::new_dump(),
         //         ..Default::default()
         //     },
         //     payload: NetlinkPayload::from(RtnlMessage::GetNeighbour(NeighbourMessage::default())),
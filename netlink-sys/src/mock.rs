@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::VecDeque,
+    io,
+    os::unix::io::FromRawFd,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{AsyncSocket, Socket, SocketAddr};
+
+/// A socket that is not connected to the kernel, for testing code that
+/// sends netlink requests without requiring privileges. The data sent on the
+/// socket is discarded, and each receive call returns the next of the
+/// responses it was created with, as if it came from the kernel.
+///
+/// Like the kernel, the socket only answers requests: each send makes the
+/// next response available, so the n-th response can be received once n
+/// messages have been sent. A response is a single datagram, which can hold
+/// several netlink messages, for instance a whole dump and its `NLMSG_DONE`.
+/// A task waiting to receive on the socket is woken up by the next send.
+///
+/// The [`Socket`] returned by [`AsyncSocket::socket_ref`] is a placeholder
+/// that is not backed by a file descriptor: calling its methods fails with
+/// `EBADF`.
+///
+/// ```rust
+/// use netlink_sys::MockNetlinkSocket;
+///
+/// let mut socket = MockNetlinkSocket::new(vec![vec![1, 2, 3, 4]]);
+/// socket.send(&[0; 16]).unwrap();
+///
+/// let mut buf = Vec::with_capacity(4096);
+/// assert_eq!(socket.recv(&mut buf).unwrap(), 4);
+/// assert_eq!(buf, vec![1, 2, 3, 4]);
+/// // there is nothing left to receive
+/// assert!(socket.recv(&mut buf).is_err());
+/// ```
+#[derive(Debug)]
+pub struct MockNetlinkSocket {
+    socket: Socket,
+    /// Responses to the messages that have not been sent yet
+    responses: VecDeque<Vec<u8>>,
+    /// Responses that can be received
+    received: VecDeque<Vec<u8>>,
+    /// Task waiting for a response
+    waker: Option<Waker>,
+}
+
+impl MockNetlinkSocket {
+    /// Create a socket that returns the given responses, in order
+    pub fn new(responses: Vec<Vec<u8>>) -> Self {
+        MockNetlinkSocket {
+            // -1 is never a valid file descriptor, so this socket does not
+            // own anything and closing it on drop is a no-op.
+            socket: unsafe { Socket::from_raw_fd(-1) },
+            responses: responses.into(),
+            received: VecDeque::new(),
+            waker: None,
+        }
+    }
+
+    /// Discard `buf`, and return its length as if it had been sent. The next
+    /// response, if any, can then be received.
+    pub fn send(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(response) = self.responses.pop_front() {
+            self.received.push_back(response);
+            if let Some(waker) = self.waker.take() {
+                waker.wake();
+            }
+        }
+        Ok(buf.len())
+    }
+
+    /// Write the next response into `buf`. Like [`Socket::recv`], the
+    /// response is truncated if `buf` is too small, and the length of the
+    /// whole response is returned. When no response can be received, because
+    /// they have all been received or the next one waits for a message to be
+    /// sent, this returns an error of kind [`io::ErrorKind::WouldBlock`], like
+    /// a non-blocking socket with no pending message.
+    pub fn recv<B>(&mut self, buf: &mut B) -> io::Result<usize>
+    where
+        B: bytes::BufMut,
+    {
+        let response = self
+            .received
+            .pop_front()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::WouldBlock))?;
+        let written = std::cmp::min(buf.remaining_mut(), response.len());
+        buf.put_slice(&response[..written]);
+        Ok(response.len())
+    }
+
+    /// Number of responses that have not been received yet
+    pub fn pending_responses(&self) -> usize {
+        self.responses.len() + self.received.len()
+    }
+
+    fn poll_recv_response<B>(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut B,
+    ) -> Poll<io::Result<usize>>
+    where
+        B: bytes::BufMut,
+    {
+        match self.recv(buf) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            res => Poll::Ready(res),
+        }
+    }
+}
+
+/// Responses appear to come from the kernel (port 0). Since a mock socket is
+/// not useful without responses, [`AsyncSocket::new`] creates a socket that
+/// never receives anything: use [`MockNetlinkSocket::new`] instead.
+impl AsyncSocket for MockNetlinkSocket {
+    fn socket_ref(&self) -> &Socket {
+        &self.socket
+    }
+
+    fn socket_mut(&mut self) -> &mut Socket {
+        &mut self.socket
+    }
+
+    fn new(_protocol: isize) -> io::Result<Self> {
+        Ok(MockNetlinkSocket::new(vec![]))
+    }
+
+    fn poll_send(&mut self, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.send(buf))
+    }
+
+    fn poll_send_to(
+        &mut self,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+        _addr: &SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.send(buf))
+    }
+
+    fn poll_recv<B>(&mut self, cx: &mut Context<'_>, buf: &mut B) -> Poll<io::Result<()>>
+    where
+        B: bytes::BufMut,
+    {
+        self.poll_recv_response(cx, buf).map_ok(|_| ())
+    }
+
+    fn poll_recv_from<B>(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut B,
+    ) -> Poll<io::Result<SocketAddr>>
+    where
+        B: bytes::BufMut,
+    {
+        self.poll_recv_response(cx, buf)
+            .map_ok(|_| SocketAddr::new(0, 0))
+    }
+
+    fn poll_recv_from_full(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<(Vec<u8>, SocketAddr)>> {
+        let mut buf = Vec::new();
+        self.poll_recv_response(cx, &mut buf)
+            .map_ok(|_| (buf, SocketAddr::new(0, 0)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn responses_in_order() {
+        let mut socket = MockNetlinkSocket::new(vec![vec![1; 8], vec![2; 4]]);
+        assert_eq!(socket.pending_responses(), 2);
+        socket.send(&[0; 16]).unwrap();
+        socket.send(&[0; 16]).unwrap();
+
+        let mut buf = vec![];
+        assert_eq!(socket.recv(&mut buf).unwrap(), 8);
+        assert_eq!(buf, vec![1; 8]);
+
+        buf.clear();
+        assert_eq!(socket.recv(&mut buf).unwrap(), 4);
+        assert_eq!(buf, vec![2; 4]);
+
+        assert_eq!(socket.pending_responses(), 0);
+        let err = socket.recv(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+    }
+
+    #[test]
+    fn response_after_send() {
+        let mut socket = MockNetlinkSocket::new(vec![vec![1; 8], vec![2; 4]]);
+        let mut buf = vec![];
+        let err = socket.recv(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+        socket.send(&[0; 16]).unwrap();
+        assert_eq!(socket.recv(&mut buf).unwrap(), 8);
+        // the second response waits for another message
+        let err = socket.recv(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(socket.pending_responses(), 1);
+    }
+
+    #[test]
+    fn truncated_response() {
+        let mut socket = MockNetlinkSocket::new(vec![vec![1; 8]]);
+        socket.send(&[0; 16]).unwrap();
+        let mut storage = [0; 4];
+        let mut buf = &mut storage[..];
+        assert_eq!(socket.recv(&mut buf).unwrap(), 8);
+        assert_eq!(storage, [1; 4]);
+    }
+
+    #[test]
+    fn placeholder_socket() {
+        let socket = MockNetlinkSocket::new(vec![]);
+        assert!(socket
+            .socket_ref()
+            .get_address(&mut SocketAddr::new(0, 0))
+            .is_err());
+    }
+}
@@ -4,9 +4,18 @@ use std::{
     io::{Error, Result},
     mem,
     os::unix::io::{AsRawFd, FromRawFd, RawFd},
+    sync::atomic::{AtomicU32, Ordering},
 };
 
-use crate::SocketAddr;
+use log::debug;
+
+use crate::{
+    constants::{NETLINK_EXT_ACK, NETLINK_GET_STRICT_CHK},
+    SocketAddr,
+};
+
+/// Length of a netlink message header, which starts every message
+const NETLINK_HEADER_LEN: usize = 16;
 
 /// A netlink socket.
 ///
@@ -51,24 +60,48 @@ use crate::SocketAddr;
 ///     }
 /// }
 /// ```
-#[derive(Clone, Debug)]
-pub struct Socket(RawFd);
+#[derive(Debug)]
+pub struct Socket {
+    fd: RawFd,
+    /// Port number the socket is bound to, or 0 if it is not known yet
+    pid: AtomicU32,
+    /// Last sequence number returned by [`Socket::next_seq`]
+    seq: AtomicU32,
+    /// Sequence number of the last message sent
+    last_sent_seq: AtomicU32,
+}
+
+impl Clone for Socket {
+    fn clone(&self) -> Self {
+        Socket {
+            fd: self.fd,
+            pid: AtomicU32::new(self.pid.load(Ordering::Relaxed)),
+            seq: AtomicU32::new(self.seq.load(Ordering::Relaxed)),
+            last_sent_seq: AtomicU32::new(self.last_sent_seq.load(Ordering::Relaxed)),
+        }
+    }
+}
 
 impl AsRawFd for Socket {
     fn as_raw_fd(&self) -> RawFd {
-        self.0
+        self.fd
     }
 }
 
 impl FromRawFd for Socket {
     unsafe fn from_raw_fd(fd: RawFd) -> Self {
-        Socket(fd)
+        Socket {
+            fd,
+            pid: AtomicU32::new(0),
+            seq: AtomicU32::new(0),
+            last_sent_seq: AtomicU32::new(0),
+        }
     }
 }
 
 impl Drop for Socket {
     fn drop(&mut self) {
-        unsafe { libc::close(self.0) };
+        unsafe { libc::close(self.fd) };
     }
 }
 
@@ -88,16 +121,19 @@ impl Socket {
         if res < 0 {
             return Err(Error::last_os_error());
         }
-        Ok(Socket(res))
+        Ok(unsafe { Socket::from_raw_fd(res) })
     }
 
     /// Bind the socket to the given address
     pub fn bind(&mut self, addr: &SocketAddr) -> Result<()> {
         let (addr_ptr, addr_len) = addr.as_raw();
-        let res = unsafe { libc::bind(self.0, addr_ptr, addr_len) };
+        let res = unsafe { libc::bind(self.fd, addr_ptr, addr_len) };
         if res < 0 {
             return Err(Error::last_os_error());
         }
+        let mut bound_addr = SocketAddr::new(0, 0);
+        self.get_address(&mut bound_addr)?;
+        self.pid.store(bound_addr.port_number(), Ordering::Relaxed);
         Ok(())
     }
 
@@ -109,12 +145,39 @@ impl Socket {
         Ok(addr)
     }
 
+    /// Port number of the socket. It is assigned by the kernel when binding to port 0, or when
+    /// an unbound socket sends its first message. Return 0 if the socket is not bound yet.
+    pub fn pid(&self) -> u32 {
+        let pid = self.pid.load(Ordering::Relaxed);
+        if pid != 0 {
+            return pid;
+        }
+        let mut addr = SocketAddr::new(0, 0);
+        if self.get_address(&mut addr).is_err() {
+            return 0;
+        }
+        self.pid.store(addr.port_number(), Ordering::Relaxed);
+        addr.port_number()
+    }
+
+    /// Return a new sequence number for a request sent on this socket. Sequence numbers start at
+    /// 1 and wrap around after `u32::MAX`, skipping 0 which is the sequence number of multicast
+    /// notifications.
+    pub fn next_seq(&self) -> u32 {
+        loop {
+            let seq = self.seq.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+            if seq != 0 {
+                return seq;
+            }
+        }
+    }
+
     /// Get the socket address
     pub fn get_address(&self, addr: &mut SocketAddr) -> Result<()> {
         let (addr_ptr, mut addr_len) = addr.as_raw_mut();
         let addr_len_copy = addr_len;
         let addr_len_ptr = &mut addr_len as *mut libc::socklen_t;
-        let res = unsafe { libc::getsockname(self.0, addr_ptr, addr_len_ptr) };
+        let res = unsafe { libc::getsockname(self.fd, addr_ptr, addr_len_ptr) };
         if res < 0 {
             return Err(Error::last_os_error());
         }
@@ -127,7 +190,7 @@ impl Socket {
     /// Make this socket non-blocking
     pub fn set_non_blocking(&self, non_blocking: bool) -> Result<()> {
         let mut non_blocking = non_blocking as libc::c_int;
-        let res = unsafe { libc::ioctl(self.0, libc::FIONBIO, &mut non_blocking) };
+        let res = unsafe { libc::ioctl(self.fd, libc::FIONBIO, &mut non_blocking) };
         if res < 0 {
             return Err(Error::last_os_error());
         }
@@ -207,7 +270,7 @@ impl Socket {
         // - https://stackoverflow.com/a/14046386/1836144
         // - https://lists.isc.org/pipermail/bind-users/2009-August/077527.html
         let (addr, addr_len) = remote_addr.as_raw();
-        let res = unsafe { libc::connect(self.0, addr, addr_len) };
+        let res = unsafe { libc::connect(self.fd, addr, addr_len) };
         if res < 0 {
             return Err(Error::last_os_error());
         }
@@ -270,12 +333,17 @@ impl Socket {
         let buf_ptr = chunk.as_mut_ptr() as *mut libc::c_void;
         let buf_len = chunk.len() as libc::size_t;
 
-        let res = unsafe { libc::recvfrom(self.0, buf_ptr, buf_len, flags, addr_ptr, addrlen_ptr) };
+        let res =
+            unsafe { libc::recvfrom(self.fd, buf_ptr, buf_len, flags, addr_ptr, addrlen_ptr) };
         if res < 0 {
             return Err(Error::last_os_error());
         } else {
             // with `MSG_TRUNC` `res` might exceed `buf_len`
             let written = std::cmp::min(buf_len, res as usize);
+            if flags & libc::MSG_PEEK == 0 {
+                let received = unsafe { std::slice::from_raw_parts(buf_ptr as *const u8, written) };
+                self.check_response(received);
+            }
             unsafe {
                 buf.advance_mut(written);
             }
@@ -293,12 +361,16 @@ impl Socket {
         let buf_ptr = chunk.as_mut_ptr() as *mut libc::c_void;
         let buf_len = chunk.len() as libc::size_t;
 
-        let res = unsafe { libc::recv(self.0, buf_ptr, buf_len, flags) };
+        let res = unsafe { libc::recv(self.fd, buf_ptr, buf_len, flags) };
         if res < 0 {
             return Err(Error::last_os_error());
         } else {
             // with `MSG_TRUNC` `res` might exceed `buf_len`
             let written = std::cmp::min(buf_len, res as usize);
+            if flags & libc::MSG_PEEK == 0 {
+                let received = unsafe { std::slice::from_raw_parts(buf_ptr as *const u8, written) };
+                self.check_response(received);
+            }
             unsafe {
                 buf.advance_mut(written);
             }
@@ -328,10 +400,11 @@ impl Socket {
         let buf_ptr = buf.as_ptr() as *const libc::c_void;
         let buf_len = buf.len() as libc::size_t;
 
-        let res = unsafe { libc::sendto(self.0, buf_ptr, buf_len, flags, addr_ptr, addr_len) };
+        let res = unsafe { libc::sendto(self.fd, buf_ptr, buf_len, flags, addr_ptr, addr_len) };
         if res < 0 {
             return Err(Error::last_os_error());
         }
+        self.record_request(buf);
         Ok(res as usize)
     }
 
@@ -341,35 +414,80 @@ impl Socket {
         let buf_ptr = buf.as_ptr() as *const libc::c_void;
         let buf_len = buf.len() as libc::size_t;
 
-        let res = unsafe { libc::send(self.0, buf_ptr, buf_len, flags) };
+        let res = unsafe { libc::send(self.fd, buf_ptr, buf_len, flags) };
         if res < 0 {
             return Err(Error::last_os_error());
         }
+        self.record_request(buf);
         Ok(res as usize)
     }
 
+    /// Remember the sequence number of an outgoing message, so that responses can be checked
+    /// against it.
+    fn record_request(&self, buf: &[u8]) {
+        if buf.len() >= NETLINK_HEADER_LEN {
+            let seq = u32::from_ne_bytes([buf[8], buf[9], buf[10], buf[11]]);
+            self.last_sent_seq.store(seq, Ordering::Relaxed);
+        }
+    }
+
+    /// Log a debug message if the first message of a received datagram does not answer the last
+    /// request sent on this socket. A mismatch is not necessarily an error: a connection may
+    /// have several requests in flight, and notifications triggered by other sockets carry their
+    /// sequence and port numbers. Multicast notifications, which have sequence number 0, and
+    /// messages received before any request was sent are not checked.
+    fn check_response(&self, buf: &[u8]) {
+        if buf.len() < NETLINK_HEADER_LEN {
+            return;
+        }
+        let seq = u32::from_ne_bytes([buf[8], buf[9], buf[10], buf[11]]);
+        let pid = u32::from_ne_bytes([buf[12], buf[13], buf[14], buf[15]]);
+        let last_sent_seq = self.last_sent_seq.load(Ordering::Relaxed);
+        if seq == 0 || last_sent_seq == 0 {
+            return;
+        }
+        if seq != last_sent_seq {
+            debug!(
+                "received a message with sequence number {} but the last request sent had \
+                 sequence number {}",
+                seq, last_sent_seq
+            );
+        }
+        let own_pid = self.pid.load(Ordering::Relaxed);
+        if own_pid != 0 && pid != own_pid {
+            debug!(
+                "received a message for port {} but the socket is bound to port {}",
+                pid, own_pid
+            );
+        }
+    }
+
     pub fn set_pktinfo(&mut self, value: bool) -> Result<()> {
         let value: libc::c_int = if value { 1 } else { 0 };
-        setsockopt(self.0, libc::SOL_NETLINK, libc::NETLINK_PKTINFO, value)
+        setsockopt(self.fd, libc::SOL_NETLINK, libc::NETLINK_PKTINFO, value)
     }
 
     pub fn get_pktinfo(&self) -> Result<bool> {
-        let res = getsockopt::<libc::c_int>(self.0, libc::SOL_NETLINK, libc::NETLINK_PKTINFO)?;
+        let res = getsockopt::<libc::c_int>(self.fd, libc::SOL_NETLINK, libc::NETLINK_PKTINFO)?;
         Ok(res == 1)
     }
 
+    /// Join the given multicast group. Contrary to the groups passed to [`SocketAddr::new`],
+    /// which is a bit mask limited to the first 32 groups, `group` is a group number such as
+    /// `RTNLGRP_LINK`.
     pub fn add_membership(&mut self, group: u32) -> Result<()> {
         setsockopt(
-            self.0,
+            self.fd,
             libc::SOL_NETLINK,
             libc::NETLINK_ADD_MEMBERSHIP,
             group,
         )
     }
 
+    /// Leave a multicast group joined with [`Socket::add_membership`]
     pub fn drop_membership(&mut self, group: u32) -> Result<()> {
         setsockopt(
-            self.0,
+            self.fd,
             libc::SOL_NETLINK,
             libc::NETLINK_DROP_MEMBERSHIP,
             group,
@@ -388,7 +506,7 @@ impl Socket {
     pub fn set_broadcast_error(&mut self, value: bool) -> Result<()> {
         let value: libc::c_int = if value { 1 } else { 0 };
         setsockopt(
-            self.0,
+            self.fd,
             libc::SOL_NETLINK,
             libc::NETLINK_BROADCAST_ERROR,
             value,
@@ -397,7 +515,7 @@ impl Socket {
 
     pub fn get_broadcast_error(&self) -> Result<bool> {
         let res =
-            getsockopt::<libc::c_int>(self.0, libc::SOL_NETLINK, libc::NETLINK_BROADCAST_ERROR)?;
+            getsockopt::<libc::c_int>(self.fd, libc::SOL_NETLINK, libc::NETLINK_BROADCAST_ERROR)?;
         Ok(res == 1)
     }
 
@@ -405,11 +523,11 @@ impl Socket {
     /// listeners to avoid receiving `ENOBUFS` errors.
     pub fn set_no_enobufs(&mut self, value: bool) -> Result<()> {
         let value: libc::c_int = if value { 1 } else { 0 };
-        setsockopt(self.0, libc::SOL_NETLINK, libc::NETLINK_NO_ENOBUFS, value)
+        setsockopt(self.fd, libc::SOL_NETLINK, libc::NETLINK_NO_ENOBUFS, value)
     }
 
     pub fn get_no_enobufs(&self) -> Result<bool> {
-        let res = getsockopt::<libc::c_int>(self.0, libc::SOL_NETLINK, libc::NETLINK_NO_ENOBUFS)?;
+        let res = getsockopt::<libc::c_int>(self.fd, libc::SOL_NETLINK, libc::NETLINK_NO_ENOBUFS)?;
         Ok(res == 1)
     }
 
@@ -420,7 +538,7 @@ impl Socket {
     pub fn set_listen_all_namespaces(&mut self, value: bool) -> Result<()> {
         let value: libc::c_int = if value { 1 } else { 0 };
         setsockopt(
-            self.0,
+            self.fd,
             libc::SOL_NETLINK,
             libc::NETLINK_LISTEN_ALL_NSID,
             value,
@@ -429,7 +547,7 @@ impl Socket {
 
     pub fn get_listen_all_namespaces(&self) -> Result<bool> {
         let res =
-            getsockopt::<libc::c_int>(self.0, libc::SOL_NETLINK, libc::NETLINK_LISTEN_ALL_NSID)?;
+            getsockopt::<libc::c_int>(self.fd, libc::SOL_NETLINK, libc::NETLINK_LISTEN_ALL_NSID)?;
         Ok(res == 1)
     }
 
@@ -439,13 +557,73 @@ impl Socket {
     /// guess from the sequence  number which message triggered the acknowledgment.
     pub fn set_cap_ack(&mut self, value: bool) -> Result<()> {
         let value: libc::c_int = if value { 1 } else { 0 };
-        setsockopt(self.0, libc::SOL_NETLINK, libc::NETLINK_CAP_ACK, value)
+        setsockopt(self.fd, libc::SOL_NETLINK, libc::NETLINK_CAP_ACK, value)
     }
 
     pub fn get_cap_ack(&self) -> Result<bool> {
-        let res = getsockopt::<libc::c_int>(self.0, libc::SOL_NETLINK, libc::NETLINK_CAP_ACK)?;
+        let res = getsockopt::<libc::c_int>(self.fd, libc::SOL_NETLINK, libc::NETLINK_CAP_ACK)?;
+        Ok(res == 1)
+    }
+
+    /// `NETLINK_EXT_ACK` (since Linux 4.12). Ask the kernel to add extended acknowledgement
+    /// attributes to the error messages, such as a human readable description of the error and
+    /// the offset of the attribute that caused it.
+    pub fn set_ext_ack(&mut self, value: bool) -> Result<()> {
+        let value: libc::c_int = if value { 1 } else { 0 };
+        setsockopt(self.fd, libc::SOL_NETLINK, NETLINK_EXT_ACK, value)
+    }
+
+    pub fn get_ext_ack(&self) -> Result<bool> {
+        let res = getsockopt::<libc::c_int>(self.fd, libc::SOL_NETLINK, NETLINK_EXT_ACK)?;
         Ok(res == 1)
     }
+
+    /// `NETLINK_GET_STRICT_CHK` (since Linux 4.20). Ask the kernel to strictly check the header
+    /// and attributes of the dump requests, and to apply the filters they contain, instead of
+    /// silently ignoring what it does not understand.
+    pub fn set_get_strict_chk(&mut self, value: bool) -> Result<()> {
+        let value: libc::c_int = if value { 1 } else { 0 };
+        setsockopt(self.fd, libc::SOL_NETLINK, NETLINK_GET_STRICT_CHK, value)
+    }
+
+    pub fn get_get_strict_chk(&self) -> Result<bool> {
+        let res = getsockopt::<libc::c_int>(self.fd, libc::SOL_NETLINK, NETLINK_GET_STRICT_CHK)?;
+        Ok(res == 1)
+    }
+
+    /// `SO_RCVBUF`. Set the size of the socket receive buffer. The kernel doubles this value to
+    /// make room for its bookkeeping, and [`Socket::get_recv_buffer_size`] returns the doubled
+    /// value. A large buffer reduces the risk of `ENOBUFS` errors when dumping large tables or
+    /// listening to busy multicast groups.
+    pub fn set_recv_buffer_size(&mut self, size: usize) -> Result<()> {
+        setsockopt(
+            self.fd,
+            libc::SOL_SOCKET,
+            libc::SO_RCVBUF,
+            size as libc::c_int,
+        )
+    }
+
+    pub fn get_recv_buffer_size(&self) -> Result<usize> {
+        let res = getsockopt::<libc::c_int>(self.fd, libc::SOL_SOCKET, libc::SO_RCVBUF)?;
+        Ok(res as usize)
+    }
+
+    /// `SO_SNDBUF`. Set the size of the socket send buffer. As with
+    /// [`Socket::set_recv_buffer_size`], the kernel doubles this value.
+    pub fn set_send_buffer_size(&mut self, size: usize) -> Result<()> {
+        setsockopt(
+            self.fd,
+            libc::SOL_SOCKET,
+            libc::SO_SNDBUF,
+            size as libc::c_int,
+        )
+    }
+
+    pub fn get_send_buffer_size(&self) -> Result<usize> {
+        let res = getsockopt::<libc::c_int>(self.fd, libc::SOL_SOCKET, libc::SO_SNDBUF)?;
+        Ok(res as usize)
+    }
 }
 
 /// Wrapper around `getsockopt`:
@@ -522,6 +700,26 @@ mod test {
         assert!(addr.port_number() != 0);
     }
 
+    #[test]
+    fn pid() {
+        let mut sock = Socket::new(NETLINK_ROUTE).unwrap();
+        assert_eq!(sock.pid(), 0);
+        let addr = sock.bind_auto().unwrap();
+        assert_eq!(sock.pid(), addr.port_number());
+    }
+
+    #[test]
+    fn next_seq() {
+        let sock = Socket::new(NETLINK_ROUTE).unwrap();
+        assert_eq!(sock.next_seq(), 1);
+        assert_eq!(sock.next_seq(), 2);
+
+        // 0 is skipped when the counter wraps around
+        sock.seq.store(u32::MAX - 1, Ordering::Relaxed);
+        assert_eq!(sock.next_seq(), u32::MAX);
+        assert_eq!(sock.next_seq(), 1);
+    }
+
     #[test]
     fn set_non_blocking() {
         let sock = Socket::new(NETLINK_ROUTE).unwrap();
@@ -548,6 +746,21 @@ mod test {
         sock.set_broadcast_error(false).unwrap();
         assert!(!sock.get_broadcast_error().unwrap());
 
+        sock.set_ext_ack(true).unwrap();
+        assert!(sock.get_ext_ack().unwrap());
+        sock.set_ext_ack(false).unwrap();
+        assert!(!sock.get_ext_ack().unwrap());
+
+        sock.set_get_strict_chk(true).unwrap();
+        assert!(sock.get_get_strict_chk().unwrap());
+        sock.set_get_strict_chk(false).unwrap();
+        assert!(!sock.get_get_strict_chk().unwrap());
+
+        sock.set_recv_buffer_size(65536).unwrap();
+        assert!(sock.get_recv_buffer_size().unwrap() >= 65536);
+        sock.set_send_buffer_size(65536).unwrap();
+        assert!(sock.get_send_buffer_size().unwrap() >= 65536);
+
         // FIXME: these require root permissions
         // sock.set_listen_all_namespaces(true).unwrap();
         // assert!(sock.get_listen_all_namespaces().unwrap());
@@ -143,3 +143,61 @@ impl AsyncSocket for TokioSocket {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        os::unix::{io::IntoRawFd, net::UnixDatagram},
+        thread,
+    };
+
+    use super::*;
+    use crate::AsyncSocketExt;
+
+    // RTM_GETLINK dump request, with sequence number 1
+    #[rustfmt::skip]
+    static GET_LINK_REQUEST: [u8; 32] = [
+        0x20, 0x00, 0x00, 0x00, // length = 32
+        0x12, 0x00, // type = RTM_GETLINK
+        0x01, 0x03, // flags = NLM_F_REQUEST | NLM_F_DUMP
+        0x01, 0x00, 0x00, 0x00, // sequence number = 1
+        0x00, 0x00, 0x00, 0x00, // port number
+        0x00, 0x00, 0x00, 0x00, // ifinfomsg
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+    ];
+
+    // End of the dump
+    #[rustfmt::skip]
+    static DONE: [u8; 20] = [
+        0x14, 0x00, 0x00, 0x00, // length = 20
+        0x03, 0x00, // type = NLMSG_DONE
+        0x02, 0x00, // flags = NLM_F_MULTI
+        0x01, 0x00, 0x00, 0x00, // sequence number = 1
+        0x00, 0x00, 0x00, 0x00, // port number
+        0x00, 0x00, 0x00, 0x00, // error code
+    ];
+
+    #[tokio::test]
+    async fn send_and_recv() {
+        // A datagram socket pair stands in for the kernel, which answers on the other end
+        let (local, kernel) = UnixDatagram::pair().unwrap();
+        let mut socket = unsafe { TokioSocket::from_raw_fd(local.into_raw_fd()) };
+        let kernel = thread::spawn(move || {
+            let mut buf = [0; 64];
+            let len = kernel.recv(&mut buf).unwrap();
+            assert_eq!(&buf[..len], &GET_LINK_REQUEST[..]);
+            kernel.send(&DONE).unwrap();
+        });
+
+        let sent = socket.send(&GET_LINK_REQUEST).await.unwrap();
+        assert_eq!(sent, GET_LINK_REQUEST.len());
+
+        let mut buf: Vec<u8> = Vec::with_capacity(64);
+        socket.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..], &DONE[..]);
+
+        kernel.join().unwrap();
+    }
+}
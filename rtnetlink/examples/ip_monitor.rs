@@ -18,28 +18,33 @@ async fn main() -> Result<(), String> {
     // messages - A channel receiver.
     let (mut conn, mut _handle, mut messages) = new_connection().map_err(|e| format!("{}", e))?;
 
-    // These flags specify what kinds of broadcast messages we want to listen for.
-    let groups = RTNLGRP_LINK
-        | RTNLGRP_IPV4_IFADDR
-        | RTNLGRP_IPV6_IFADDR
-        | RTNLGRP_IPV4_ROUTE
-        | RTNLGRP_IPV6_ROUTE
-        | RTNLGRP_MPLS_ROUTE
-        | RTNLGRP_IPV4_MROUTE
-        | RTNLGRP_IPV6_MROUTE
-        | RTNLGRP_NEIGH
-        | RTNLGRP_IPV4_NETCONF
-        | RTNLGRP_IPV6_NETCONF
-        | RTNLGRP_IPV4_RULE
-        | RTNLGRP_IPV6_RULE
-        | RTNLGRP_NSID
-        | RTNLGRP_MPLS_NETCONF;
-
-    let addr = SocketAddr::new(0, groups);
-    conn.socket_mut()
-        .socket_mut()
-        .bind(&addr)
-        .expect("Failed to bind");
+    // These are the kinds of broadcast messages we want to listen for. Unlike the legacy
+    // `RTMGRP_*` masks, `RTNLGRP_*` are group numbers, so each group must be joined separately.
+    let groups = [
+        RTNLGRP_LINK,
+        RTNLGRP_IPV4_IFADDR,
+        RTNLGRP_IPV6_IFADDR,
+        RTNLGRP_IPV4_ROUTE,
+        RTNLGRP_IPV6_ROUTE,
+        RTNLGRP_MPLS_ROUTE,
+        RTNLGRP_IPV4_MROUTE,
+        RTNLGRP_IPV6_MROUTE,
+        RTNLGRP_NEIGH,
+        RTNLGRP_IPV4_NETCONF,
+        RTNLGRP_IPV6_NETCONF,
+        RTNLGRP_IPV4_RULE,
+        RTNLGRP_IPV6_RULE,
+        RTNLGRP_NSID,
+        RTNLGRP_MPLS_NETCONF,
+    ];
+
+    let socket = conn.socket_mut().socket_mut();
+    socket.bind(&SocketAddr::new(0, 0)).expect("Failed to bind");
+    for group in groups {
+        socket
+            .add_membership(group)
+            .expect("Failed to join multicast group");
+    }
 
     // Spawn `Connection` to start polling netlink socket.
     tokio::spawn(conn);
@@ -6,15 +6,11 @@ use std::net::{IpAddr, Ipv4Addr};
 use netlink_packet_route::{
     nlas::address::Nla,
     AddressMessage,
+    NetlinkFlags,
     NetlinkMessage,
     RtnlMessage,
     AF_INET,
     AF_INET6,
-    NLM_F_ACK,
-    NLM_F_CREATE,
-    NLM_F_EXCL,
-    NLM_F_REPLACE,
-    NLM_F_REQUEST,
 };
 
 use crate::{try_nl, Error, Handle};
@@ -93,8 +89,9 @@ impl AddressAddRequest {
             replace,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::NewAddress(message));
-        let replace = if replace { NLM_F_REPLACE } else { NLM_F_EXCL };
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | replace | NLM_F_CREATE;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK | NetlinkFlags::CREATE;
+        req.header.flags.set(NetlinkFlags::REPLACE, replace);
+        req.header.flags.set(NetlinkFlags::EXCL, !replace);
 
         let mut response = handle.request(req)?;
         while let Some(message) = response.next().await {
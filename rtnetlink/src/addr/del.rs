@@ -3,7 +3,7 @@
 use futures::stream::StreamExt;
 
 use crate::{
-    packet::{AddressMessage, NetlinkMessage, RtnlMessage, NLM_F_ACK, NLM_F_REQUEST},
+    packet::{AddressMessage, NetlinkFlags, NetlinkMessage, RtnlMessage},
     try_nl,
     Error,
     Handle,
@@ -27,7 +27,7 @@ impl AddressDelRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::DelAddress(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK;
         let mut response = handle.request(req)?;
         while let Some(msg) = response.next().await {
             try_nl!(msg);
@@ -10,10 +10,9 @@ use std::net::IpAddr;
 use netlink_packet_route::{
     nlas::address::Nla,
     AddressMessage,
+    NetlinkFlags,
     NetlinkMessage,
     RtnlMessage,
-    NLM_F_DUMP,
-    NLM_F_REQUEST,
 };
 
 use crate::{try_rtnl, Error, Handle};
@@ -45,7 +44,7 @@ impl AddressGetRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::GetAddress(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         let filter = filter_builder.build();
         match handle.request(req) {
@@ -4,16 +4,22 @@ use futures::stream::StreamExt;
 
 use crate::{
     packet::{
-        nlas::link::{Info, InfoData, InfoKind, InfoMacVlan, InfoVlan, InfoVxlan, Nla, VethInfo},
+        nlas::link::{
+            Info,
+            InfoData,
+            InfoKind,
+            InfoMacVlan,
+            InfoVlan,
+            InfoVrf,
+            InfoVxlan,
+            Nla,
+            VethInfo,
+        },
         LinkMessage,
+        NetlinkFlags,
         NetlinkMessage,
         RtnlMessage,
         IFF_UP,
-        NLM_F_ACK,
-        NLM_F_CREATE,
-        NLM_F_EXCL,
-        NLM_F_REPLACE,
-        NLM_F_REQUEST,
     },
     try_nl,
     Error,
@@ -267,8 +273,9 @@ impl LinkAddRequest {
             replace,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::NewLink(message));
-        let replace = if replace { NLM_F_REPLACE } else { NLM_F_EXCL };
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | replace | NLM_F_CREATE;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK | NetlinkFlags::CREATE;
+        req.header.flags.set(NetlinkFlags::REPLACE, replace);
+        req.header.flags.set(NetlinkFlags::EXCL, !replace);
 
         let mut response = handle.request(req)?;
         while let Some(message) = response.next().await {
@@ -376,6 +383,18 @@ impl LinkAddRequest {
             .append_nla(Nla::IfName(name))
     }
 
+    /// Create a new VRF device bound to the given routing table, which
+    /// must not be 0.
+    /// This is equivalent to `ip link add NAME type vrf table TABLE_ID`.
+    pub fn vrf(self, name: String, table_id: u32) -> Self {
+        self.name(name)
+            .link_info(
+                InfoKind::Vrf,
+                Some(InfoData::Vrf(vec![InfoVrf::TableId(table_id)])),
+            )
+            .up()
+    }
+
     /// Replace existing matching link.
     pub fn replace(self) -> Self {
         Self {
@@ -384,6 +403,13 @@ impl LinkAddRequest {
         }
     }
 
+    /// Set the number of transmit and receive queues of the link.
+    /// This is equivalent to `ip link add NAME numtxqueues TX numrxqueues RX ...`.
+    pub fn queues(self, tx: u32, rx: u32) -> Self {
+        self.append_nla(Nla::NumTxQueues(tx))
+            .append_nla(Nla::NumRxQueues(rx))
+    }
+
     fn up(mut self) -> Self {
         self.message.header.flags = IFF_UP;
         self.message.header.change_mask = IFF_UP;
@@ -7,7 +7,7 @@ use futures::{
 };
 
 use crate::{
-    packet::{constants::*, nlas::link::Nla, LinkMessage, NetlinkMessage, RtnlMessage},
+    packet::{nlas::link::Nla, LinkMessage, NetlinkFlags, NetlinkMessage, RtnlMessage},
     try_rtnl,
     Error,
     Handle,
@@ -52,9 +52,9 @@ impl LinkGetRequest {
         let mut req = NetlinkMessage::from(RtnlMessage::GetLink(message));
 
         if dump {
-            req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+            req.header.flags = NetlinkFlags::new_dump();
         } else {
-            req.header.flags = NLM_F_REQUEST;
+            req.header.flags = NetlinkFlags::new_get();
         }
 
         match handle.request(req) {
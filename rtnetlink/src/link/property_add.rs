@@ -4,14 +4,10 @@ use crate::{
     packet::{
         nlas::link::{Nla, Prop},
         LinkMessage,
+        NetlinkFlags,
         NetlinkMessage,
         NetlinkPayload,
         RtnlMessage,
-        NLM_F_ACK,
-        NLM_F_APPEND,
-        NLM_F_CREATE,
-        NLM_F_EXCL,
-        NLM_F_REQUEST,
     },
     Error,
     Handle,
@@ -37,7 +33,7 @@ impl LinkNewPropRequest {
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::NewLinkProp(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_EXCL | NLM_F_CREATE | NLM_F_APPEND;
+        req.header.flags = NetlinkFlags::new_create() | NetlinkFlags::APPEND;
 
         let mut response = handle.request(req)?;
         while let Some(message) = response.next().await {
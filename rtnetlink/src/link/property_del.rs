@@ -4,12 +4,10 @@ use crate::{
     packet::{
         nlas::link::{Nla, Prop},
         LinkMessage,
+        NetlinkFlags,
         NetlinkMessage,
         NetlinkPayload,
         RtnlMessage,
-        NLM_F_ACK,
-        NLM_F_EXCL,
-        NLM_F_REQUEST,
     },
     Error,
     Handle,
@@ -35,7 +33,7 @@ impl LinkDelPropRequest {
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::DelLinkProp(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_EXCL;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK | NetlinkFlags::EXCL;
 
         let mut response = handle.request(req)?;
         while let Some(message) = response.next().await {
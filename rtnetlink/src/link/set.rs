@@ -4,15 +4,12 @@ use crate::{
     packet::{
         nlas::link::Nla,
         LinkMessage,
+        NetlinkFlags,
         NetlinkMessage,
         RtnlMessage,
         IFF_NOARP,
         IFF_PROMISC,
         IFF_UP,
-        NLM_F_ACK,
-        NLM_F_CREATE,
-        NLM_F_EXCL,
-        NLM_F_REQUEST,
     },
     try_nl,
     Error,
@@ -40,7 +37,7 @@ impl LinkSetRequest {
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::SetLink(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | NLM_F_EXCL | NLM_F_CREATE;
+        req.header.flags = NetlinkFlags::new_create();
 
         let mut response = handle.request(req)?;
         while let Some(message) = response.next().await {
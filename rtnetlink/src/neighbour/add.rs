@@ -5,6 +5,7 @@ use futures::stream::StreamExt;
 use netlink_packet_route::{
     constants::*,
     neighbour::{NeighbourMessage, Nla},
+    NetlinkFlags,
     NetlinkPayload,
     RtnlMessage,
 };
@@ -137,8 +138,9 @@ impl NeighbourAddRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::NewNeighbour(message));
-        let replace = if replace { NLM_F_REPLACE } else { NLM_F_EXCL };
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | replace | NLM_F_CREATE;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK | NetlinkFlags::CREATE;
+        req.header.flags.set(NetlinkFlags::REPLACE, replace);
+        req.header.flags.set(NetlinkFlags::EXCL, !replace);
 
         let mut response = handle.request(req)?;
         while let Some(message) = response.next().await {
@@ -3,8 +3,8 @@
 use futures::stream::StreamExt;
 
 use netlink_packet_route::{
-    constants::*,
     neighbour::NeighbourMessage,
+    NetlinkFlags,
     NetlinkPayload,
     RtnlMessage,
 };
@@ -31,7 +31,7 @@ impl NeighbourDelRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::DelNeighbour(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK;
         let mut response = handle.request(req)?;
         while let Some(msg) = response.next().await {
             if let NetlinkPayload::Error(e) = msg.payload {
@@ -9,6 +9,7 @@ use futures::{
 use netlink_packet_route::{
     constants::*,
     neighbour::NeighbourMessage,
+    NetlinkFlags,
     NetlinkPayload,
     RtnlMessage,
 };
@@ -47,7 +48,7 @@ impl NeighbourGetRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::GetNeighbour(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         match handle.request(req) {
             Ok(response) => Either::Left(response.map(move |msg| {
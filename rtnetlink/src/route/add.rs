@@ -9,8 +9,12 @@ use std::{
 use netlink_packet_route::{
     constants::*,
     nlas::route::Nla,
+    NetlinkFlags,
     NetlinkMessage,
     RouteMessage,
+    RouteProtocol,
+    RouteScope,
+    RouteType,
     RtnlMessage,
 };
 
@@ -29,9 +33,9 @@ impl<T> RouteAddRequest<T> {
         let mut message = RouteMessage::default();
 
         message.header.table = RT_TABLE_MAIN;
-        message.header.protocol = RTPROT_STATIC;
-        message.header.scope = RT_SCOPE_UNIVERSE;
-        message.header.kind = RTN_UNICAST;
+        message.header.protocol = RouteProtocol::Static;
+        message.header.scope = RouteScope::Universe;
+        message.header.kind = RouteType::Unicast;
 
         RouteAddRequest {
             handle,
@@ -64,7 +68,7 @@ impl<T> RouteAddRequest<T> {
     /// Sets the route protocol.
     ///
     /// Default is static route protocol.
-    pub fn protocol(mut self, protocol: u8) -> Self {
+    pub fn protocol(mut self, protocol: RouteProtocol) -> Self {
         self.message.header.protocol = protocol;
         self
     }
@@ -72,7 +76,7 @@ impl<T> RouteAddRequest<T> {
     /// Sets the route scope.
     ///
     /// Default is universe route scope.
-    pub fn scope(mut self, scope: u8) -> Self {
+    pub fn scope(mut self, scope: RouteScope) -> Self {
         self.message.header.scope = scope;
         self
     }
@@ -80,7 +84,7 @@ impl<T> RouteAddRequest<T> {
     /// Sets the route kind.
     ///
     /// Default is unicast route kind.
-    pub fn kind(mut self, kind: u8) -> Self {
+    pub fn kind(mut self, kind: RouteType) -> Self {
         self.message.header.kind = kind;
         self
     }
@@ -124,8 +128,9 @@ impl<T> RouteAddRequest<T> {
             ..
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::NewRoute(message));
-        let replace = if replace { NLM_F_REPLACE } else { NLM_F_EXCL };
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | replace | NLM_F_CREATE;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK | NetlinkFlags::CREATE;
+        req.header.flags.set(NetlinkFlags::REPLACE, replace);
+        req.header.flags.set(NetlinkFlags::EXCL, !replace);
 
         let mut response = handle.request(req)?;
         while let Some(message) = response.next().await {
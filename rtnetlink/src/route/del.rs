@@ -3,7 +3,7 @@
 use futures::stream::StreamExt;
 
 use crate::{
-    packet::{NetlinkMessage, NetlinkPayload, RouteMessage, RtnlMessage, NLM_F_ACK, NLM_F_REQUEST},
+    packet::{NetlinkFlags, NetlinkMessage, NetlinkPayload, RouteMessage, RtnlMessage},
     Error,
     Handle,
 };
@@ -26,7 +26,7 @@ impl RouteDelRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::DelRoute(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK;
         let mut response = handle.request(req)?;
         while let Some(msg) = response.next().await {
             if let NetlinkPayload::Error(e) = msg.payload {
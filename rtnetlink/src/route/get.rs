@@ -6,7 +6,16 @@ use futures::{
     FutureExt,
 };
 
-use netlink_packet_route::{constants::*, NetlinkMessage, RouteMessage, RtnlMessage};
+use netlink_packet_route::{
+    constants::*,
+    NetlinkFlags,
+    NetlinkMessage,
+    RouteMessage,
+    RouteProtocol,
+    RouteScope,
+    RouteType,
+    RtnlMessage,
+};
 
 use crate::{try_rtnl, Error, Handle};
 
@@ -47,12 +56,12 @@ impl RouteGetRequest {
         // > is the wildcard.
         message.header.destination_prefix_length = 0;
         message.header.source_prefix_length = 0;
-        message.header.scope = RT_SCOPE_UNIVERSE;
-        message.header.kind = RTN_UNSPEC;
+        message.header.scope = RouteScope::Universe;
+        message.header.kind = RouteType::Unspec;
 
         // I don't know if these two fields matter
         message.header.table = RT_TABLE_UNSPEC;
-        message.header.protocol = RTPROT_UNSPEC;
+        message.header.protocol = RouteProtocol::Unspec;
 
         RouteGetRequest { handle, message }
     }
@@ -68,7 +77,7 @@ impl RouteGetRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::GetRoute(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         match handle.request(req) {
             Ok(response) => {
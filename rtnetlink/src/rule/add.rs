@@ -9,8 +9,10 @@ use std::{
 use netlink_packet_route::{
     constants::*,
     nlas::rule::Nla,
+    NetlinkFlags,
     NetlinkMessage,
     RtnlMessage,
+    RuleAction,
     RuleMessage,
 };
 
@@ -29,7 +31,7 @@ impl<T> RuleAddRequest<T> {
         let mut message = RuleMessage::default();
 
         message.header.table = RT_TABLE_MAIN;
-        message.header.action = FR_ACT_UNSPEC;
+        message.header.action = RuleAction::Unspec;
 
         RuleAddRequest {
             handle,
@@ -66,7 +68,7 @@ impl<T> RuleAddRequest<T> {
     }
 
     /// Set action.
-    pub fn action(mut self, action: u8) -> Self {
+    pub fn action(mut self, action: RuleAction) -> Self {
         self.message.header.action = action;
         self
     }
@@ -110,8 +112,9 @@ impl<T> RuleAddRequest<T> {
             ..
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::NewRule(message));
-        let replace = if replace { NLM_F_REPLACE } else { NLM_F_EXCL };
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK | replace | NLM_F_CREATE;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK | NetlinkFlags::CREATE;
+        req.header.flags.set(NetlinkFlags::REPLACE, replace);
+        req.header.flags.set(NetlinkFlags::EXCL, !replace);
 
         let mut response = handle.request(req)?;
         while let Some(message) = response.next().await {
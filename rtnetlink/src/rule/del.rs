@@ -3,7 +3,7 @@
 use futures::stream::StreamExt;
 
 use crate::{
-    packet::{NetlinkMessage, RtnlMessage, RuleMessage, NLM_F_ACK, NLM_F_REQUEST},
+    packet::{NetlinkFlags, NetlinkMessage, RtnlMessage, RuleMessage},
     try_nl,
     Error,
     Handle,
@@ -27,7 +27,7 @@ impl RuleDelRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::DelRule(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+        req.header.flags = NetlinkFlags::REQUEST | NetlinkFlags::ACK;
         let mut response = handle.request(req)?;
         while let Some(msg) = response.next().await {
             try_nl!(msg);
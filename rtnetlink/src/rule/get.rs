@@ -7,7 +7,14 @@ use futures::{
     FutureExt,
 };
 
-use netlink_packet_route::{constants::*, NetlinkMessage, RtnlMessage, RuleMessage};
+use netlink_packet_route::{
+    constants::*,
+    NetlinkFlags,
+    NetlinkMessage,
+    RtnlMessage,
+    RuleAction,
+    RuleMessage,
+};
 
 use crate::{try_rtnl, Error, Handle};
 
@@ -24,7 +31,7 @@ impl RuleGetRequest {
         message.header.dst_len = 0;
         message.header.src_len = 0;
         message.header.tos = 0;
-        message.header.action = FR_ACT_UNSPEC;
+        message.header.action = RuleAction::Unspec;
         message.header.table = RT_TABLE_UNSPEC;
 
         RuleGetRequest { handle, message }
@@ -41,7 +48,7 @@ impl RuleGetRequest {
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::GetRule(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         match handle.request(req) {
             Ok(response) => {
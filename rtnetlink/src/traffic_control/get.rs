@@ -7,7 +7,13 @@ use futures::{
 };
 
 use crate::{
-    packet::{NetlinkMessage, RtnlMessage, TcMessage, NLM_F_DUMP, NLM_F_REQUEST},
+    packet::{
+        tc::{Class, Filter, Qdisc},
+        NetlinkFlags,
+        NetlinkMessage,
+        RtnlMessage,
+        TcMessage,
+    },
     try_rtnl,
     Error,
     Handle,
@@ -15,7 +21,7 @@ use crate::{
 
 pub struct QDiscGetRequest {
     handle: Handle,
-    message: TcMessage,
+    message: TcMessage<Qdisc>,
 }
 
 impl QDiscGetRequest {
@@ -27,27 +33,27 @@ impl QDiscGetRequest {
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl TryStream<Ok = TcMessage, Error = Error> {
+    pub fn execute(self) -> impl TryStream<Ok = TcMessage<Qdisc>, Error = Error> {
         let QDiscGetRequest {
             mut handle,
             message,
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::GetQueueDiscipline(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         match handle.request(req) {
             Ok(response) => Either::Left(
                 response.map(move |msg| Ok(try_rtnl!(msg, RtnlMessage::NewQueueDiscipline))),
             ),
-            Err(e) => Either::Right(future::err::<TcMessage, Error>(e).into_stream()),
+            Err(e) => Either::Right(future::err::<TcMessage<Qdisc>, Error>(e).into_stream()),
         }
     }
 }
 
 pub struct TrafficClassGetRequest {
     handle: Handle,
-    message: TcMessage,
+    message: TcMessage<Class>,
 }
 
 impl TrafficClassGetRequest {
@@ -58,27 +64,27 @@ impl TrafficClassGetRequest {
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl TryStream<Ok = TcMessage, Error = Error> {
+    pub fn execute(self) -> impl TryStream<Ok = TcMessage<Class>, Error = Error> {
         let TrafficClassGetRequest {
             mut handle,
             message,
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::GetTrafficClass(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         match handle.request(req) {
             Ok(response) => Either::Left(
                 response.map(move |msg| Ok(try_rtnl!(msg, RtnlMessage::NewTrafficClass))),
             ),
-            Err(e) => Either::Right(future::err::<TcMessage, Error>(e).into_stream()),
+            Err(e) => Either::Right(future::err::<TcMessage<Class>, Error>(e).into_stream()),
         }
     }
 }
 
 pub struct TrafficFilterGetRequest {
     handle: Handle,
-    message: TcMessage,
+    message: TcMessage<Filter>,
 }
 
 impl TrafficFilterGetRequest {
@@ -89,27 +95,27 @@ impl TrafficFilterGetRequest {
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl TryStream<Ok = TcMessage, Error = Error> {
+    pub fn execute(self) -> impl TryStream<Ok = TcMessage<Filter>, Error = Error> {
         let TrafficFilterGetRequest {
             mut handle,
             message,
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::GetTrafficFilter(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         match handle.request(req) {
             Ok(response) => Either::Left(
                 response.map(move |msg| Ok(try_rtnl!(msg, RtnlMessage::NewTrafficFilter))),
             ),
-            Err(e) => Either::Right(future::err::<TcMessage, Error>(e).into_stream()),
+            Err(e) => Either::Right(future::err::<TcMessage<Filter>, Error>(e).into_stream()),
         }
     }
 }
 
 pub struct TrafficChainGetRequest {
     handle: Handle,
-    message: TcMessage,
+    message: TcMessage<Filter>,
 }
 
 impl TrafficChainGetRequest {
@@ -120,20 +126,20 @@ impl TrafficChainGetRequest {
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl TryStream<Ok = TcMessage, Error = Error> {
+    pub fn execute(self) -> impl TryStream<Ok = TcMessage<Filter>, Error = Error> {
         let TrafficChainGetRequest {
             mut handle,
             message,
         } = self;
 
         let mut req = NetlinkMessage::from(RtnlMessage::GetTrafficChain(message));
-        req.header.flags = NLM_F_REQUEST | NLM_F_DUMP;
+        req.header.flags = NetlinkFlags::new_dump();
 
         match handle.request(req) {
             Ok(response) => Either::Left(
                 response.map(move |msg| Ok(try_rtnl!(msg, RtnlMessage::NewTrafficChain))),
             ),
-            Err(e) => Either::Right(future::err::<TcMessage, Error>(e).into_stream()),
+            Err(e) => Either::Right(future::err::<TcMessage<Filter>, Error>(e).into_stream()),
         }
     }
 }
@@ -8,8 +8,14 @@ use tokio::runtime::Runtime;
 use crate::{
     new_connection,
     packet::{
-        rtnl::tc::nlas::Nla::{Chain, HwOffload, Kind},
+        rtnl::tc::nlas::{
+            Class,
+            Filter,
+            Nla::{Chain, HwOffload, Kind},
+            Qdisc,
+        },
         ErrorMessage,
+        TcHandle,
         TcMessage,
         AF_UNSPEC,
     },
@@ -18,7 +24,7 @@ use crate::{
 
 static TEST_DUMMY_NIC: &str = "netlink-test";
 
-async fn _get_qdiscs() -> Vec<TcMessage> {
+async fn _get_qdiscs() -> Vec<TcMessage<Qdisc>> {
     let (connection, handle, _) = new_connection().unwrap();
     tokio::spawn(connection);
     let mut qdiscs_iter = handle.qdisc().get().execute();
@@ -35,14 +41,14 @@ fn test_get_qdiscs() {
     let qdisc_of_loopback_nic = &qdiscs[0];
     assert_eq!(qdisc_of_loopback_nic.header.family, AF_UNSPEC as u8);
     assert_eq!(qdisc_of_loopback_nic.header.index, 1);
-    assert_eq!(qdisc_of_loopback_nic.header.handle, 0);
-    assert_eq!(qdisc_of_loopback_nic.header.parent, u32::MAX);
+    assert_eq!(qdisc_of_loopback_nic.header.handle, TcHandle::default());
+    assert_eq!(qdisc_of_loopback_nic.header.parent, TcHandle::root());
     assert_eq!(qdisc_of_loopback_nic.header.info, 2); // refcount
     assert_eq!(qdisc_of_loopback_nic.nlas[0], Kind("noqueue".to_string()));
     assert_eq!(qdisc_of_loopback_nic.nlas[1], HwOffload(0));
 }
 
-async fn _get_tclasses(ifindex: i32) -> Vec<TcMessage> {
+async fn _get_tclasses(ifindex: i32) -> Vec<TcMessage<Class>> {
     let (connection, handle, _) = new_connection().unwrap();
     tokio::spawn(connection);
     let mut tclasses_iter = handle.traffic_class(ifindex).get().execute();
@@ -208,7 +214,7 @@ fn _remove_test_filter_from_dummy() {
         ));
 }
 
-async fn _get_filters(ifindex: i32) -> Vec<TcMessage> {
+async fn _get_filters(ifindex: i32) -> Vec<TcMessage<Filter>> {
     let (connection, handle, _) = new_connection().unwrap();
     tokio::spawn(connection);
     let mut filters_iter = handle.traffic_filter(ifindex).get().execute();
@@ -219,7 +225,7 @@ async fn _get_filters(ifindex: i32) -> Vec<TcMessage> {
     filters
 }
 
-async fn _get_chains(ifindex: i32) -> Vec<TcMessage> {
+async fn _get_chains(ifindex: i32) -> Vec<TcMessage<Filter>> {
     let (connection, handle, _) = new_connection().unwrap();
     tokio::spawn(connection);
     let mut chains_iter = handle.traffic_chain(ifindex).get().execute();
@@ -234,7 +240,7 @@ async fn _get_chains(ifindex: i32) -> Vec<TcMessage> {
             Ok(None) => {
                 break;
             }
-            Err(NetlinkError(ErrorMessage { code, header: _ })) => {
+            Err(NetlinkError(ErrorMessage { code, .. })) => {
                 assert_eq!(code, -95);
                 eprintln!(
                     "The chain in traffic control is not supported, \
@@ -266,22 +272,22 @@ fn test_get_traffic_classes_filters_and_chains() {
     let tclass = &tclasses[0];
     assert_eq!(tclass.header.family, AF_UNSPEC as u8);
     assert_eq!(tclass.header.index, ifindex);
-    assert_eq!(tclass.header.parent, u32::MAX);
+    assert_eq!(tclass.header.parent, TcHandle::root());
     assert_eq!(tclass.nlas[0], Kind("htb".to_string()));
     assert_eq!(filters.len(), 2);
     assert_eq!(filters[0].header.family, AF_UNSPEC as u8);
     assert_eq!(filters[0].header.index, ifindex);
-    assert_eq!(filters[0].header.parent, u16::MAX as u32 + 1);
+    assert_eq!(filters[0].header.parent, TcHandle::from_parts(1, 0));
     assert_eq!(filters[0].nlas[0], Kind("basic".to_string()));
     assert_eq!(filters[1].header.family, AF_UNSPEC as u8);
     assert_eq!(filters[1].header.index, ifindex);
-    assert_eq!(filters[1].header.parent, u16::MAX as u32 + 1);
+    assert_eq!(filters[1].header.parent, TcHandle::from_parts(1, 0));
     assert_eq!(filters[1].nlas[0], Kind("basic".to_string()));
     assert!(chains.len() <= 1);
     if chains.len() == 1 {
         assert_eq!(chains[0].header.family, AF_UNSPEC as u8);
         assert_eq!(chains[0].header.index, ifindex);
-        assert_eq!(chains[0].header.parent, u16::MAX as u32 + 1);
-        assert_eq!(chains[0].nlas[0], Chain([0u8, 0, 0, 0].to_vec()));
+        assert_eq!(chains[0].header.parent, TcHandle::from_parts(1, 0));
+        assert_eq!(chains[0].nlas[0], Chain(0));
     }
 }